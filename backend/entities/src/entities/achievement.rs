@@ -14,6 +14,7 @@ pub struct Model {
     pub icon: Option<String>,
     pub points: i32,
     pub category: Option<String>,
+    pub criteria: Option<Json>,
     pub is_actived: bool,
     pub created_on: DateTimeWithTimeZone,
     pub updated_on: DateTimeWithTimeZone,
@@ -39,10 +40,18 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     User1,
+    #[sea_orm(has_many = "super::achievement_backfill::Entity")]
+    AchievementBackfill,
     #[sea_orm(has_many = "super::user_achievement::Entity")]
     UserAchievement,
 }
 
+impl Related<super::achievement_backfill::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AchievementBackfill.def()
+    }
+}
+
 impl Related<super::user_achievement::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::UserAchievement.def()
@@ -30,8 +30,12 @@ pub enum Relation {
     FlashcardFile,
     #[sea_orm(has_many = "super::flashcard_type_relation::Entity")]
     FlashcardTypeRelation,
+    #[sea_orm(has_many = "super::game_session_answer::Entity")]
+    GameSessionAnswer,
     #[sea_orm(has_many = "super::game_type_flashcard::Entity")]
     GameTypeFlashcard,
+    #[sea_orm(has_many = "super::user_flashcard_review::Entity")]
+    UserFlashcardReview,
     #[sea_orm(
         belongs_to = "super::user::Entity",
         from = "Column::CreatedById",
@@ -62,10 +66,22 @@ impl Related<super::flashcard_type_relation::Entity> for Entity {
     }
 }
 
+impl Related<super::game_session_answer::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GameSessionAnswer.def()
+    }
+}
+
 impl Related<super::game_type_flashcard::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::GameTypeFlashcard.def()
     }
 }
 
+impl Related<super::user_flashcard_review::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UserFlashcardReview.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -10,19 +10,23 @@ pub struct Model {
     pub name: Option<String>,
     pub file_name: String,
     pub content_type: String,
-    #[sea_orm(column_type = "VarBinary(StringLen::None)")]
-    pub data: Vec<u8>,
+    #[sea_orm(column_type = "VarBinary(StringLen::None)", nullable)]
+    pub data: Option<Vec<u8>>,
     pub created_on: DateTimeWithTimeZone,
     pub updated_on: DateTimeWithTimeZone,
     pub is_actived: bool,
     pub created_by_id: i32,
     pub updated_by_id: i32,
+    pub storage_key: Option<String>,
+    pub checksum: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::flashcard::Entity")]
     Flashcard,
+    #[sea_orm(has_many = "super::flashcard_file_variant::Entity")]
+    FlashcardFileVariant,
     #[sea_orm(
         belongs_to = "super::user::Entity",
         from = "Column::CreatedById",
@@ -47,4 +51,10 @@ impl Related<super::flashcard::Entity> for Entity {
     }
 }
 
+impl Related<super::flashcard_file_variant::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::FlashcardFileVariant.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -25,6 +25,7 @@ pub struct Model {
     pub updated_on: Option<DateTimeWithTimeZone>,
     pub created_by_id: Option<i32>,
     pub updated_by_id: Option<i32>,
+    pub status_id: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -37,6 +38,8 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     FlashcardType,
+    #[sea_orm(has_many = "super::game_session_answer::Entity")]
+    GameSessionAnswer,
     #[sea_orm(
         belongs_to = "super::game_type::Entity",
         from = "Column::GameTypeId",
@@ -77,6 +80,12 @@ impl Related<super::flashcard_type::Entity> for Entity {
     }
 }
 
+impl Related<super::game_session_answer::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GameSessionAnswer.def()
+    }
+}
+
 impl Related<super::game_type::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::GameType.def()
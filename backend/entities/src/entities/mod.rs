@@ -3,22 +3,34 @@
 pub mod prelude;
 
 pub mod achievement;
+pub mod achievement_backfill;
+pub mod email_outbox;
 pub mod flashcard;
 pub mod flashcard_file;
+pub mod flashcard_file_variant;
 pub mod flashcard_type;
 pub mod flashcard_type_relation;
 pub mod game_session;
+pub mod game_session_answer;
 pub mod game_type;
 pub mod game_type_flashcard;
+pub mod leaderboard_score;
 pub mod mail_template;
 pub mod permission;
 pub mod role;
 pub mod role_permission;
+pub mod signing_key;
 pub mod system_settings;
 pub mod user;
 pub mod user_achievement;
+pub mod user_api_key;
+pub mod user_external_login;
+pub mod user_flashcard_review;
 pub mod user_game_progress;
 pub mod user_permission;
+pub mod user_recovery_code;
 pub mod user_role;
 pub mod user_stats;
+pub mod user_streak_day;
 pub mod user_token;
+pub mod user_xp_event;
@@ -14,12 +14,22 @@ pub struct Model {
     pub updated_on: DateTimeWithTimeZone,
     pub updated_by_id: Option<i32>,
     pub is_actived: bool,
+    pub requires_mfa: bool,
+    pub parent_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::role_permission::Entity")]
     RolePermission,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    SelfRef,
     #[sea_orm(
         belongs_to = "super::user::Entity",
         from = "Column::CreatedById",
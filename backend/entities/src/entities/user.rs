@@ -17,10 +17,30 @@ pub struct Model {
     pub updated_on: DateTimeWithTimeZone,
     pub updated_by_id: Option<i32>,
     pub status_id: i32,
+    pub mfa_secret: Option<String>,
+    pub mfa_enabled_on: Option<DateTimeWithTimeZone>,
+    pub mfa_last_used_step: Option<i64>,
+    pub failed_login_count: i32,
+    pub last_failed_login_on: Option<DateTimeWithTimeZone>,
+    pub locked_until: Option<DateTimeWithTimeZone>,
+    pub status_reason: Option<String>,
+    pub status_expires_on: Option<DateTimeWithTimeZone>,
+    pub status_changed_by_id: Option<i32>,
+    pub status_changed_on: Option<DateTimeWithTimeZone>,
+    pub kind_id: i32,
+    pub authorization_version: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::StatusChangedById",
+        to = "Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    SelfRef3,
     #[sea_orm(
         belongs_to = "Entity",
         from = "Column::CreatedById",
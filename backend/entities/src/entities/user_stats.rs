@@ -19,6 +19,13 @@ pub struct Model {
     pub current_streak: i32,
     pub best_streak: i32,
     pub last_played_at: Option<DateTimeWithTimeZone>,
+    pub utc_offset_minutes: i32,
+    pub streak_freezes: i32,
+    pub points_spent: i64,
+    pub last_streak_day: Option<Date>,
+    pub streak_reminders_enabled: bool,
+    pub streak_reminded_on: Option<Date>,
+    pub total_xp: i64,
     pub created_on: DateTimeWithTimeZone,
     pub updated_on: DateTimeWithTimeZone,
     pub created_by_id: Option<i32>,
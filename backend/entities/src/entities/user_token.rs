@@ -16,6 +16,7 @@ pub struct Model {
     pub updated_by_id: i32,
     pub is_actived: bool,
     pub purpose: i32,
+    pub family_id: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
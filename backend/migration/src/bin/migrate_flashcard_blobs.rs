@@ -0,0 +1,75 @@
+//! Moves flashcard images still held in `flashcard_file.data` into the configured blob
+//! store, then clears the column. Rows that already have a storage key are skipped, so
+//! the command can be re-run after a failure.
+//!
+//! `APP_ENV=prod cargo run -p rex-game-migration --bin migrate_flashcard_blobs`
+
+use rex_game_entities::entities::flashcard_file::{self, Entity as FlashcardFile};
+use rex_game_shared::infrastructure::database::SeaOrmConnection;
+use rex_game_shared::infrastructure::helpers::{
+    blob_store_provider::BlobStoreFactory, configuration_helper::ConfigurationHelper,
+};
+use sea_orm_migration::sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+
+const BATCH_SIZE: u64 = 50;
+const BLOB_KEY_PREFIX: &str = "flashcards";
+
+#[tokio::main]
+async fn main() {
+    ConfigurationHelper::init();
+    let config = ConfigurationHelper::new();
+    let db_connection = SeaOrmConnection::new(&config.get("DATABASE_URL"))
+        .await
+        .expect("Failed to connect to database");
+    let db = db_connection.pool.as_ref();
+    let blob_store = BlobStoreFactory::create(&config);
+
+    let mut last_id = 0;
+    let mut moved = 0;
+    let mut failed = 0;
+    loop {
+        let files = FlashcardFile::find()
+            .filter(flashcard_file::Column::Id.gt(last_id))
+            .filter(flashcard_file::Column::StorageKey.is_null())
+            .filter(flashcard_file::Column::Data.is_not_null())
+            .order_by_asc(flashcard_file::Column::Id)
+            .limit(BATCH_SIZE)
+            .all(db)
+            .await
+            .expect("Failed to load flashcard files");
+        if files.is_empty() {
+            break;
+        }
+
+        for file in files {
+            last_id = file.id;
+            let data = file.data.clone().unwrap_or_default();
+            let key = BlobStoreFactory::new_key(BLOB_KEY_PREFIX);
+            if let Err(err) = blob_store.put(&key, &data, &file.content_type).await {
+                eprintln!("❌ flashcard_file {}: {}", file.id, err);
+                failed += 1;
+                continue;
+            }
+
+            let mut updating: flashcard_file::ActiveModel = file.into();
+            updating.storage_key = Set(Some(key.clone()));
+            updating.checksum = Set(Some(BlobStoreFactory::checksum(&data)));
+            updating.data = Set(None);
+            match updating.update(db).await {
+                Ok(_) => moved += 1,
+                Err(err) => {
+                    eprintln!("❌ flashcard_file {}: {}", last_id, err);
+                    let _ = blob_store.delete(&key).await;
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    eprintln!("📦 Moved {} flashcard images, {} failed", moved, failed);
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveIden)]
+pub enum AchievementBackfill {
+    Table,
+    #[sea_orm(iden = "id")]
+    Id,
+    #[sea_orm(iden = "achievement_id")]
+    AchievementId,
+    #[sea_orm(iden = "is_dry_run")]
+    IsDryRun,
+    #[sea_orm(iden = "status")]
+    Status,
+    #[sea_orm(iden = "last_user_id")]
+    LastUserId,
+    #[sea_orm(iden = "total_users")]
+    TotalUsers,
+    #[sea_orm(iden = "processed_users")]
+    ProcessedUsers,
+    #[sea_orm(iden = "affected_users")]
+    AffectedUsers,
+    #[sea_orm(iden = "unlocked_count")]
+    UnlockedCount,
+    #[sea_orm(iden = "last_error")]
+    LastError,
+    #[sea_orm(iden = "created_by_id")]
+    CreatedById,
+    #[sea_orm(iden = "started_on")]
+    StartedOn,
+    #[sea_orm(iden = "completed_on")]
+    CompletedOn,
+    #[sea_orm(iden = "created_on")]
+    CreatedOn,
+    #[sea_orm(iden = "updated_on")]
+    UpdatedOn,
+}
@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveIden)]
+pub enum EmailOutbox {
+    Table,
+    #[sea_orm(iden = "id")]
+    Id,
+    #[sea_orm(iden = "to_email")]
+    ToEmail,
+    #[sea_orm(iden = "to_name")]
+    ToName,
+    #[sea_orm(iden = "from_email")]
+    FromEmail,
+    #[sea_orm(iden = "from_name")]
+    FromName,
+    #[sea_orm(iden = "subject")]
+    Subject,
+    #[sea_orm(iden = "html_body")]
+    HtmlBody,
+    #[sea_orm(iden = "text_body")]
+    TextBody,
+    #[sea_orm(iden = "status")]
+    Status,
+    #[sea_orm(iden = "attempts")]
+    Attempts,
+    #[sea_orm(iden = "last_error")]
+    LastError,
+    #[sea_orm(iden = "next_attempt_at")]
+    NextAttemptAt,
+    #[sea_orm(iden = "sent_on")]
+    SentOn,
+    #[sea_orm(iden = "created_on")]
+    CreatedOn,
+    #[sea_orm(iden = "updated_on")]
+    UpdatedOn,
+}
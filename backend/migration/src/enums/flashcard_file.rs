@@ -24,4 +24,8 @@ pub enum FlashcardFile {
     Data,
     #[sea_orm(iden = "is_actived")]
     IsActived,
+    #[sea_orm(iden = "storage_key")]
+    StorageKey,
+    #[sea_orm(iden = "checksum")]
+    Checksum,
 }
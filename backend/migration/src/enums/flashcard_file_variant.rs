@@ -0,0 +1,26 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveIden)]
+pub enum FlashcardFileVariant {
+    Table,
+    #[sea_orm(iden = "id")]
+    Id,
+    #[sea_orm(iden = "flashcard_file_id")]
+    FlashcardFileId,
+    #[sea_orm(iden = "size")]
+    Size,
+    #[sea_orm(iden = "format")]
+    Format,
+    #[sea_orm(iden = "content_type")]
+    ContentType,
+    #[sea_orm(iden = "storage_key")]
+    StorageKey,
+    #[sea_orm(iden = "checksum")]
+    Checksum,
+    #[sea_orm(iden = "width")]
+    Width,
+    #[sea_orm(iden = "height")]
+    Height,
+    #[sea_orm(iden = "created_on")]
+    CreatedOn,
+}
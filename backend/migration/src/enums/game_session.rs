@@ -31,6 +31,8 @@ pub enum GameSession {
     StartedAt,
     #[sea_orm(iden = "completed_at")]
     CompletedAt,
+    #[sea_orm(iden = "status_id")]
+    StatusId,
     #[sea_orm(iden = "created_on")]
     CreatedOn,
     #[sea_orm(iden = "updated_on")]
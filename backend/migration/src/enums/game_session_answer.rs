@@ -0,0 +1,28 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveIden)]
+pub enum GameSessionAnswer {
+    Table,
+    #[sea_orm(iden = "id")]
+    Id,
+    #[sea_orm(iden = "game_session_id")]
+    GameSessionId,
+    #[sea_orm(iden = "flashcard_id")]
+    FlashcardId,
+    #[sea_orm(iden = "position")]
+    Position,
+    #[sea_orm(iden = "answer")]
+    Answer,
+    #[sea_orm(iden = "is_correct")]
+    IsCorrect,
+    #[sea_orm(iden = "points")]
+    Points,
+    #[sea_orm(iden = "combo")]
+    Combo,
+    #[sea_orm(iden = "response_ms")]
+    ResponseMs,
+    #[sea_orm(iden = "answered_at")]
+    AnsweredAt,
+    #[sea_orm(iden = "created_on")]
+    CreatedOn,
+}
@@ -0,0 +1,28 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveIden)]
+pub enum LeaderboardScore {
+    Table,
+    #[sea_orm(iden = "id")]
+    Id,
+    #[sea_orm(iden = "period")]
+    Period,
+    #[sea_orm(iden = "period_start")]
+    PeriodStart,
+    #[sea_orm(iden = "game_type_id")]
+    GameTypeId,
+    #[sea_orm(iden = "flashcard_type_id")]
+    FlashcardTypeId,
+    #[sea_orm(iden = "user_id")]
+    UserId,
+    #[sea_orm(iden = "total_score")]
+    TotalScore,
+    #[sea_orm(iden = "games_played")]
+    GamesPlayed,
+    #[sea_orm(iden = "best_score")]
+    BestScore,
+    #[sea_orm(iden = "accuracy_total")]
+    AccuracyTotal,
+    #[sea_orm(iden = "updated_on")]
+    UpdatedOn,
+}
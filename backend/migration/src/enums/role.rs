@@ -19,4 +19,8 @@ pub enum Role {
     UpdatedOn,
     #[sea_orm(iden = "is_actived")]
     IsActived,
+    #[sea_orm(iden = "requires_mfa")]
+    RequiresMfa,
+    #[sea_orm(iden = "parent_id")]
+    ParentId,
 }
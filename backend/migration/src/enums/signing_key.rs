@@ -0,0 +1,22 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveIden)]
+pub enum SigningKey {
+    Table,
+    #[sea_orm(iden = "id")]
+    Id,
+    #[sea_orm(iden = "kid")]
+    Kid,
+    #[sea_orm(iden = "algorithm")]
+    Algorithm,
+    #[sea_orm(iden = "private_key")]
+    PrivateKey,
+    #[sea_orm(iden = "public_key")]
+    PublicKey,
+    #[sea_orm(iden = "created_on")]
+    CreatedOn,
+    #[sea_orm(iden = "activates_on")]
+    ActivatesOn,
+    #[sea_orm(iden = "expires_on")]
+    ExpiresOn,
+}
@@ -24,4 +24,28 @@ pub enum User {
     UpdatedById,
     #[sea_orm(iden = "status_id")]
     StatusId,
+    #[sea_orm(iden = "mfa_secret")]
+    MfaSecret,
+    #[sea_orm(iden = "mfa_enabled_on")]
+    MfaEnabledOn,
+    #[sea_orm(iden = "mfa_last_used_step")]
+    MfaLastUsedStep,
+    #[sea_orm(iden = "failed_login_count")]
+    FailedLoginCount,
+    #[sea_orm(iden = "last_failed_login_on")]
+    LastFailedLoginOn,
+    #[sea_orm(iden = "locked_until")]
+    LockedUntil,
+    #[sea_orm(iden = "status_reason")]
+    StatusReason,
+    #[sea_orm(iden = "status_expires_on")]
+    StatusExpiresOn,
+    #[sea_orm(iden = "status_changed_by_id")]
+    StatusChangedById,
+    #[sea_orm(iden = "status_changed_on")]
+    StatusChangedOn,
+    #[sea_orm(iden = "kind_id")]
+    KindId,
+    #[sea_orm(iden = "authorization_version")]
+    AuthorizationVersion,
 }
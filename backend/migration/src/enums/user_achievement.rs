@@ -38,6 +38,8 @@ pub enum Achievement {
     Points,
     #[sea_orm(iden = "category")]
     Category,
+    #[sea_orm(iden = "criteria")]
+    Criteria,
     #[sea_orm(iden = "is_actived")]
     IsActived,
     #[sea_orm(iden = "created_on")]
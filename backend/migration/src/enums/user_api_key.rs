@@ -0,0 +1,28 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveIden)]
+pub enum UserApiKey {
+    Table,
+    #[sea_orm(iden = "id")]
+    Id,
+    #[sea_orm(iden = "user_id")]
+    UserId,
+    #[sea_orm(iden = "name")]
+    Name,
+    #[sea_orm(iden = "key_prefix")]
+    KeyPrefix,
+    #[sea_orm(iden = "key_hash")]
+    KeyHash,
+    #[sea_orm(iden = "scopes")]
+    Scopes,
+    #[sea_orm(iden = "created_by_id")]
+    CreatedById,
+    #[sea_orm(iden = "created_on")]
+    CreatedOn,
+    #[sea_orm(iden = "expires_on")]
+    ExpiresOn,
+    #[sea_orm(iden = "last_used_on")]
+    LastUsedOn,
+    #[sea_orm(iden = "revoked_on")]
+    RevokedOn,
+}
@@ -0,0 +1,20 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveIden)]
+pub enum UserExternalLogin {
+    Table,
+    #[sea_orm(iden = "id")]
+    Id,
+    #[sea_orm(iden = "user_id")]
+    UserId,
+    #[sea_orm(iden = "provider")]
+    Provider,
+    #[sea_orm(iden = "subject")]
+    Subject,
+    #[sea_orm(iden = "email")]
+    Email,
+    #[sea_orm(iden = "created_on")]
+    CreatedOn,
+    #[sea_orm(iden = "last_used_on")]
+    LastUsedOn,
+}
@@ -0,0 +1,28 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveIden)]
+pub enum UserFlashcardReview {
+    Table,
+    #[sea_orm(iden = "id")]
+    Id,
+    #[sea_orm(iden = "user_id")]
+    UserId,
+    #[sea_orm(iden = "flashcard_id")]
+    FlashcardId,
+    #[sea_orm(iden = "ease_factor")]
+    EaseFactor,
+    #[sea_orm(iden = "interval_days")]
+    IntervalDays,
+    #[sea_orm(iden = "repetitions")]
+    Repetitions,
+    #[sea_orm(iden = "lapses")]
+    Lapses,
+    #[sea_orm(iden = "due_at")]
+    DueAt,
+    #[sea_orm(iden = "last_reviewed_at")]
+    LastReviewedAt,
+    #[sea_orm(iden = "created_on")]
+    CreatedOn,
+    #[sea_orm(iden = "updated_on")]
+    UpdatedOn,
+}
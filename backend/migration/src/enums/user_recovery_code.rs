@@ -0,0 +1,16 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveIden)]
+pub enum UserRecoveryCode {
+    Table,
+    #[sea_orm(iden = "id")]
+    Id,
+    #[sea_orm(iden = "user_id")]
+    UserId,
+    #[sea_orm(iden = "code_hash")]
+    CodeHash,
+    #[sea_orm(iden = "used_on")]
+    UsedOn,
+    #[sea_orm(iden = "created_on")]
+    CreatedOn,
+}
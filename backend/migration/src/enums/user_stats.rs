@@ -25,6 +25,20 @@ pub enum UserStats {
     BestStreak,
     #[sea_orm(iden = "last_played_at")]
     LastPlayedAt,
+    #[sea_orm(iden = "utc_offset_minutes")]
+    UtcOffsetMinutes,
+    #[sea_orm(iden = "streak_freezes")]
+    StreakFreezes,
+    #[sea_orm(iden = "points_spent")]
+    PointsSpent,
+    #[sea_orm(iden = "last_streak_day")]
+    LastStreakDay,
+    #[sea_orm(iden = "streak_reminders_enabled")]
+    StreakRemindersEnabled,
+    #[sea_orm(iden = "streak_reminded_on")]
+    StreakRemindedOn,
+    #[sea_orm(iden = "total_xp")]
+    TotalXp,
     #[sea_orm(iden = "created_on")]
     CreatedOn,
     #[sea_orm(iden = "created_by_id")]
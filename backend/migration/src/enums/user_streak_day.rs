@@ -0,0 +1,16 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveIden)]
+pub enum UserStreakDay {
+    Table,
+    #[sea_orm(iden = "id")]
+    Id,
+    #[sea_orm(iden = "user_id")]
+    UserId,
+    #[sea_orm(iden = "day")]
+    Day,
+    #[sea_orm(iden = "kind")]
+    Kind,
+    #[sea_orm(iden = "created_on")]
+    CreatedOn,
+}
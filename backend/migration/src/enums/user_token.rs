@@ -36,4 +36,6 @@ pub enum UserToken {
     IsActived,
     #[sea_orm(iden = "purpose")]
     Purpose,
+    #[sea_orm(iden = "family_id")]
+    FamilyId,
 }
@@ -0,0 +1,18 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveIden)]
+pub enum UserXpEvent {
+    Table,
+    #[sea_orm(iden = "id")]
+    Id,
+    #[sea_orm(iden = "user_id")]
+    UserId,
+    #[sea_orm(iden = "game_session_id")]
+    GameSessionId,
+    #[sea_orm(iden = "source")]
+    Source,
+    #[sea_orm(iden = "amount")]
+    Amount,
+    #[sea_orm(iden = "created_on")]
+    CreatedOn,
+}
@@ -15,6 +15,28 @@ mod m20260118_100002_seed_achievements;
 mod m20260123_100000_add_user_game_progress;
 mod m20260211_100000_add_game_type_flashcard;
 mod m20260211_100001_seed_flashcard_types;
+mod m20261017_100000_add_game_session_answer;
+mod m20261017_100001_add_game_session_status;
+mod m20261017_100002_add_user_token_family;
+mod m20261017_100003_add_user_flashcard_review;
+mod m20261017_100004_add_flashcard_file_storage_key;
+mod m20261017_100005_add_flashcard_file_variant;
+mod m20261017_100006_add_email_outbox;
+mod m20261017_100007_add_user_mfa;
+mod m20261017_100008_add_user_login_lockout;
+mod m20261017_100009_add_user_status_details;
+mod m20261017_100010_add_user_external_login;
+mod m20261017_100011_add_signing_key;
+mod m20261017_100012_seed_magic_link_template;
+mod m20261017_100013_add_user_api_key;
+mod m20261017_100014_add_user_authorization_version;
+mod m20261017_100015_add_role_parent;
+mod m20261017_100016_seed_own_content_permissions;
+mod m20261017_100017_add_achievement_criteria;
+mod m20261017_100018_add_achievement_backfill;
+mod m20261017_100019_add_leaderboard_score;
+mod m20261017_100020_add_user_streak_day;
+mod m20261017_100021_add_user_xp_event;
 
 pub struct Migrator;
 
@@ -36,6 +58,28 @@ impl MigratorTrait for Migrator {
             Box::new(m20260123_100000_add_user_game_progress::Migration),
             Box::new(m20260211_100000_add_game_type_flashcard::Migration),
             Box::new(m20260211_100001_seed_flashcard_types::Migration),
+            Box::new(m20261017_100000_add_game_session_answer::Migration),
+            Box::new(m20261017_100001_add_game_session_status::Migration),
+            Box::new(m20261017_100002_add_user_token_family::Migration),
+            Box::new(m20261017_100003_add_user_flashcard_review::Migration),
+            Box::new(m20261017_100004_add_flashcard_file_storage_key::Migration),
+            Box::new(m20261017_100005_add_flashcard_file_variant::Migration),
+            Box::new(m20261017_100006_add_email_outbox::Migration),
+            Box::new(m20261017_100007_add_user_mfa::Migration),
+            Box::new(m20261017_100008_add_user_login_lockout::Migration),
+            Box::new(m20261017_100009_add_user_status_details::Migration),
+            Box::new(m20261017_100010_add_user_external_login::Migration),
+            Box::new(m20261017_100011_add_signing_key::Migration),
+            Box::new(m20261017_100012_seed_magic_link_template::Migration),
+            Box::new(m20261017_100013_add_user_api_key::Migration),
+            Box::new(m20261017_100014_add_user_authorization_version::Migration),
+            Box::new(m20261017_100015_add_role_parent::Migration),
+            Box::new(m20261017_100016_seed_own_content_permissions::Migration),
+            Box::new(m20261017_100017_add_achievement_criteria::Migration),
+            Box::new(m20261017_100018_add_achievement_backfill::Migration),
+            Box::new(m20261017_100019_add_leaderboard_score::Migration),
+            Box::new(m20261017_100020_add_user_streak_day::Migration),
+            Box::new(m20261017_100021_add_user_xp_event::Migration),
         ]
     }
 }
@@ -0,0 +1,109 @@
+use sea_orm_migration::prelude::*;
+
+use crate::enums::{
+    flashcard::Flashcard, game_session::GameSession, game_session_answer::GameSessionAnswer,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create game_session_answer table. Rows are dealt when the session starts
+        // (answered_at is null) and filled in as the player answers.
+        manager
+            .create_table(
+                Table::create()
+                    .table(GameSessionAnswer::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GameSessionAnswer::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(GameSessionAnswer::GameSessionId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GameSessionAnswer::FlashcardId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GameSessionAnswer::Position)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GameSessionAnswer::Answer)
+                            .string_len(255)
+                            .null(),
+                    )
+                    .col(ColumnDef::new(GameSessionAnswer::IsCorrect).boolean().null())
+                    .col(
+                        ColumnDef::new(GameSessionAnswer::Points)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(GameSessionAnswer::Combo)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(GameSessionAnswer::ResponseMs).integer().null())
+                    .col(
+                        ColumnDef::new(GameSessionAnswer::AnsweredAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(GameSessionAnswer::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_game_session_answer_session")
+                            .from(GameSessionAnswer::Table, GameSessionAnswer::GameSessionId)
+                            .to(GameSession::Table, GameSession::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_game_session_answer_flashcard")
+                            .from(GameSessionAnswer::Table, GameSessionAnswer::FlashcardId)
+                            .to(Flashcard::Table, Flashcard::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_game_session_answer_session")
+                    .table(GameSessionAnswer::Table)
+                    .col(GameSessionAnswer::GameSessionId)
+                    .col(GameSessionAnswer::Position)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GameSessionAnswer::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,69 @@
+use sea_orm_migration::prelude::*;
+
+use crate::enums::game_session::GameSession;
+
+/// 1 = started, 2 = completed, 3 = abandoned, 4 = expired
+const STATUS_COMPLETED: i32 = 2;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameSession::Table)
+                    .add_column(
+                        ColumnDef::new(GameSession::StatusId)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Sessions completed before statuses existed
+        let completed_update = Query::update()
+            .table(GameSession::Table)
+            .value(GameSession::StatusId, STATUS_COMPLETED)
+            .and_where(Expr::col(GameSession::CompletedAt).is_not_null())
+            .to_owned();
+        manager.exec_stmt(completed_update).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-game_session-user_id-status_id")
+                    .table(GameSession::Table)
+                    .col(GameSession::UserId)
+                    .col(GameSession::StatusId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-game_session-user_id-status_id")
+                    .table(GameSession::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GameSession::Table)
+                    .drop_column(GameSession::StatusId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,72 @@
+use sea_orm_migration::prelude::*;
+
+use crate::enums::user_token::UserToken;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Refresh tokens issued by one login share a family; other purposes leave it empty
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserToken::Table)
+                    .add_column(ColumnDef::new(UserToken::FamilyId).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-user_token-family_id")
+                    .table(UserToken::Table)
+                    .col(UserToken::FamilyId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-user_token-token")
+                    .table(UserToken::Table)
+                    .col(UserToken::Token)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-user_token-token")
+                    .table(UserToken::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-user_token-family_id")
+                    .table(UserToken::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserToken::Table)
+                    .drop_column(UserToken::FamilyId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
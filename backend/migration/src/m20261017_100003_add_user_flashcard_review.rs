@@ -0,0 +1,131 @@
+use sea_orm_migration::prelude::*;
+
+use crate::enums::{flashcard::Flashcard, user::User, user_flashcard_review::UserFlashcardReview};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create user_flashcard_review table. One row per card a user has seen in a game,
+        // holding the spaced-repetition state. Ease is stored in permille (2500 = 2.5).
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserFlashcardReview::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserFlashcardReview::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(UserFlashcardReview::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserFlashcardReview::FlashcardId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserFlashcardReview::EaseFactor)
+                            .integer()
+                            .not_null()
+                            .default(2500),
+                    )
+                    .col(
+                        ColumnDef::new(UserFlashcardReview::IntervalDays)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(UserFlashcardReview::Repetitions)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(UserFlashcardReview::Lapses)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(UserFlashcardReview::DueAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserFlashcardReview::LastReviewedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserFlashcardReview::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(UserFlashcardReview::UpdatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_flashcard_review_user")
+                            .from(UserFlashcardReview::Table, UserFlashcardReview::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_flashcard_review_flashcard")
+                            .from(UserFlashcardReview::Table, UserFlashcardReview::FlashcardId)
+                            .to(Flashcard::Table, Flashcard::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_flashcard_review_unique")
+                    .table(UserFlashcardReview::Table)
+                    .col(UserFlashcardReview::UserId)
+                    .col(UserFlashcardReview::FlashcardId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Due-card lookups scan a user's rows by due date
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_flashcard_review_due")
+                    .table(UserFlashcardReview::Table)
+                    .col(UserFlashcardReview::UserId)
+                    .col(UserFlashcardReview::DueAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserFlashcardReview::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+use crate::enums::flashcard_file::FlashcardFile;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Image bytes move to the blob store; rows keep the key and a SHA-256 of the bytes.
+        // `data` stays nullable until the `migrate_flashcard_blobs` command has emptied it.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(FlashcardFile::Table)
+                    .add_column(
+                        ColumnDef::new(FlashcardFile::StorageKey)
+                            .string_len(255)
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(FlashcardFile::Checksum)
+                            .string_len(64)
+                            .null(),
+                    )
+                    .modify_column(ColumnDef::new(FlashcardFile::Data).binary().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Only reversible while every row still holds its bytes in `data`
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(FlashcardFile::Table)
+                    .drop_column(FlashcardFile::StorageKey)
+                    .drop_column(FlashcardFile::Checksum)
+                    .modify_column(ColumnDef::new(FlashcardFile::Data).binary().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+}
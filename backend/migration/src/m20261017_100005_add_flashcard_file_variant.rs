@@ -0,0 +1,106 @@
+use sea_orm_migration::prelude::*;
+
+use crate::enums::{flashcard_file::FlashcardFile, flashcard_file_variant::FlashcardFileVariant};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create flashcard_file_variant table. Resized, re-encoded copies of a flashcard
+        // image, generated on first request and stored in the blob store like the original.
+        manager
+            .create_table(
+                Table::create()
+                    .table(FlashcardFileVariant::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(FlashcardFileVariant::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(FlashcardFileVariant::FlashcardFileId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(FlashcardFileVariant::Size)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(FlashcardFileVariant::Format)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(FlashcardFileVariant::ContentType)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(FlashcardFileVariant::StorageKey)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(FlashcardFileVariant::Checksum)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(FlashcardFileVariant::Width)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(FlashcardFileVariant::Height)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(FlashcardFileVariant::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_flashcard_file_variant_flashcard_file")
+                            .from(
+                                FlashcardFileVariant::Table,
+                                FlashcardFileVariant::FlashcardFileId,
+                            )
+                            .to(FlashcardFile::Table, FlashcardFile::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_flashcard_file_variant_unique")
+                    .table(FlashcardFileVariant::Table)
+                    .col(FlashcardFileVariant::FlashcardFileId)
+                    .col(FlashcardFileVariant::Size)
+                    .col(FlashcardFileVariant::Format)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(FlashcardFileVariant::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,101 @@
+use sea_orm_migration::prelude::*;
+
+use crate::enums::email_outbox::EmailOutbox;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create email_outbox table. Emails are written here in the same transaction as the
+        // data they belong to and sent by a background worker. `status` is `pending`,
+        // `sent` or `dead`; a pending row is picked up once `next_attempt_at` has passed.
+        manager
+            .create_table(
+                Table::create()
+                    .table(EmailOutbox::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EmailOutbox::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(EmailOutbox::ToEmail)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(EmailOutbox::ToName).string_len(255).null())
+                    .col(
+                        ColumnDef::new(EmailOutbox::FromEmail)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(EmailOutbox::FromName).string_len(255).null())
+                    .col(ColumnDef::new(EmailOutbox::Subject).text().not_null())
+                    .col(ColumnDef::new(EmailOutbox::HtmlBody).text().null())
+                    .col(ColumnDef::new(EmailOutbox::TextBody).text().null())
+                    .col(
+                        ColumnDef::new(EmailOutbox::Status)
+                            .string_len(16)
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(
+                        ColumnDef::new(EmailOutbox::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(EmailOutbox::LastError).text().null())
+                    .col(
+                        ColumnDef::new(EmailOutbox::NextAttemptAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(EmailOutbox::SentOn)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(EmailOutbox::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(EmailOutbox::UpdatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The worker polls pending rows by due time
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_email_outbox_status_next_attempt")
+                    .table(EmailOutbox::Table)
+                    .col(EmailOutbox::Status)
+                    .col(EmailOutbox::NextAttemptAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(EmailOutbox::Table).to_owned())
+            .await
+    }
+}
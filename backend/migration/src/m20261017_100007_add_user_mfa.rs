@@ -0,0 +1,134 @@
+use sea_orm_migration::prelude::*;
+
+use crate::enums::{role::Role, user::User, user_recovery_code::UserRecoveryCode};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // TOTP secret of the user. It is stored before the first code is confirmed, so
+        // two-factor authentication is only on once mfa_enabled_on is set. The last used
+        // time step stops a code from being replayed within its window.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(ColumnDef::new(User::MfaSecret).string_len(64).null())
+                    .add_column(
+                        ColumnDef::new(User::MfaEnabledOn)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(ColumnDef::new(User::MfaLastUsedStep).big_integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Members of a role that requires it must enrol before they can sign in
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Role::Table)
+                    .add_column(
+                        ColumnDef::new(Role::RequiresMfa)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let update = Query::update()
+            .table(Role::Table)
+            .value(Role::RequiresMfa, true)
+            .and_where(Expr::col(Role::Name).is_in(["RootAdmin", "Admin"]))
+            .to_owned();
+        manager.exec_stmt(update).await?;
+
+        // One-time codes that stand in for a TOTP code when the authenticator is lost
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserRecoveryCode::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserRecoveryCode::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(UserRecoveryCode::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserRecoveryCode::CodeHash)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserRecoveryCode::UsedOn)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserRecoveryCode::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_recovery_code_user")
+                            .from(UserRecoveryCode::Table, UserRecoveryCode::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_recovery_code_user_id")
+                    .table(UserRecoveryCode::Table)
+                    .col(UserRecoveryCode::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserRecoveryCode::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Role::Table)
+                    .drop_column(Role::RequiresMfa)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(User::MfaSecret)
+                    .drop_column(User::MfaEnabledOn)
+                    .drop_column(User::MfaLastUsedStep)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,126 @@
+use crate::enums::{mail_template::MailTemplate, user::User};
+use rex_game_shared::domain::enums::mail_template_names::MailTemplateNames;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Failed password logins of the account, whatever address they come from. They
+        // slow down further attempts and lock the account once there are too many.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(
+                        ColumnDef::new(User::FailedLoginCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_column(
+                        ColumnDef::new(User::LastFailedLoginOn)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(User::LockedUntil)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let insert = Query::insert()
+            .into_table(MailTemplate::Table)
+            .columns([
+                MailTemplate::Name,
+                MailTemplate::Subject,
+                MailTemplate::Body,
+                MailTemplate::IsActived,
+                MailTemplate::IsEnabled,
+                MailTemplate::CreatedOn,
+                MailTemplate::UpdatedOn,
+            ])
+            .values_panic([
+                MailTemplateNames::ACCOUNT_LOCKED.into(),
+                "Your [platform_name] Account Has Been Locked".into(),
+                "<div class='container'>
+
+        <div class='content'>
+            <h2>Your Account Has Been Locked</h2>
+            <p>Dear [user_name],</p>
+            <p>We have locked your [platform_name] account after too many failed login attempts.</p>
+            <p>You can log in again after [locked_until], or ask an administrator to unlock your account sooner.</p>
+            <p>If these attempts were not made by you, we recommend resetting your password once you are back in.</p>
+            <p>Best regards,<br>[platform_name] Team</p>
+        </div>
+        <!-- Footer -->
+        <div class='footer'>
+            <p><a href='[platform_url]'>[platform_name]</a></p>
+        </div>
+    </div>"
+                    .into(),
+                true.into(),
+                true.into(),
+                chrono::Utc::now().into(),
+                chrono::Utc::now().into(),
+            ])
+            .values_panic([
+                MailTemplateNames::ACCOUNT_UNLOCKED.into(),
+                "Your [platform_name] Account Has Been Unlocked".into(),
+                "<div class='container'>
+
+        <div class='content'>
+            <h2>Your Account Has Been Unlocked</h2>
+            <p>Dear [user_name],</p>
+            <p>An administrator has unlocked your [platform_name] account. You can log in again.</p>
+            <p style='text-align: center;'>
+                <a href='[platform_url]' class='button'>Log In to Your Account</a>
+            </p>
+            <p>If the button doesn't work, copy and paste this link into your browser: <br>
+            <a href='[platform_url]'>[platform_url]</a></p>
+            <p>Best regards,<br>[platform_name] Team</p>
+        </div>
+        <!-- Footer -->
+        <div class='footer'>
+            <p><a href='[platform_url]'>[platform_name]</a></p>
+        </div>
+    </div>"
+                    .into(),
+                true.into(),
+                true.into(),
+                chrono::Utc::now().into(),
+                chrono::Utc::now().into(),
+            ])
+            .to_owned();
+        manager.exec_stmt(insert).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let deleted = Query::delete()
+            .from_table(MailTemplate::Table)
+            .and_where(Expr::col(MailTemplate::Name).is_in([
+                MailTemplateNames::ACCOUNT_LOCKED,
+                MailTemplateNames::ACCOUNT_UNLOCKED,
+            ]))
+            .to_owned();
+        manager.exec_stmt(deleted).await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(User::FailedLoginCount)
+                    .drop_column(User::LastFailedLoginOn)
+                    .drop_column(User::LockedUntil)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,66 @@
+use crate::enums::user::User;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Why the account is in its current status, until when, and who put it there.
+        // Mostly used by suspensions and bans.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(ColumnDef::new(User::StatusReason).string_len(500).null())
+                    .add_column(
+                        ColumnDef::new(User::StatusExpiresOn)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .add_column(ColumnDef::new(User::StatusChangedById).integer().null())
+                    .add_column(
+                        ColumnDef::new(User::StatusChangedOn)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_user_status_changed_by")
+                    .from(User::Table, User::StatusChangedById)
+                    .to(User::Table, User::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk_user_status_changed_by")
+                    .table(User::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(User::StatusReason)
+                    .drop_column(User::StatusExpiresOn)
+                    .drop_column(User::StatusChangedById)
+                    .drop_column(User::StatusChangedOn)
+                    .to_owned(),
+            )
+            .await
+    }
+}
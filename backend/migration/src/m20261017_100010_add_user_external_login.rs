@@ -0,0 +1,100 @@
+use sea_orm_migration::prelude::*;
+
+use crate::enums::{user::User, user_external_login::UserExternalLogin};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Accounts at OpenID Connect providers that sign the user in. The subject is the
+        // provider's stable id for the account; the email is only what it said last time.
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserExternalLogin::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserExternalLogin::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(UserExternalLogin::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserExternalLogin::Provider)
+                            .string_len(50)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserExternalLogin::Subject)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserExternalLogin::Email)
+                            .string_len(255)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserExternalLogin::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(UserExternalLogin::LastUsedOn)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_external_login_user")
+                            .from(UserExternalLogin::Table, UserExternalLogin::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // A provider account signs in one user, and a user links a provider once
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_external_login_provider_subject")
+                    .table(UserExternalLogin::Table)
+                    .col(UserExternalLogin::Provider)
+                    .col(UserExternalLogin::Subject)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_external_login_user_provider")
+                    .table(UserExternalLogin::Table)
+                    .col(UserExternalLogin::UserId)
+                    .col(UserExternalLogin::Provider)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserExternalLogin::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+
+use crate::enums::signing_key::SigningKey;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Keys that sign access and refresh tokens. A new key is published before it signs
+        // anything, and a replaced key keeps verifying until the tokens it signed expire.
+        manager
+            .create_table(
+                Table::create()
+                    .table(SigningKey::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SigningKey::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SigningKey::Kid)
+                            .string_len(64)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SigningKey::Algorithm)
+                            .string_len(10)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SigningKey::PrivateKey).text().not_null())
+                    .col(ColumnDef::new(SigningKey::PublicKey).text().not_null())
+                    .col(
+                        ColumnDef::new(SigningKey::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(SigningKey::ActivatesOn)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SigningKey::ExpiresOn)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SigningKey::Table).to_owned())
+            .await
+    }
+}
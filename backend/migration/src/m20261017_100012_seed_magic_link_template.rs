@@ -0,0 +1,68 @@
+use crate::enums::mail_template::MailTemplate;
+use rex_game_shared::domain::enums::mail_template_names::MailTemplateNames;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Magic links are stored in user_token like other emailed tokens; only their
+        // email is new
+        let insert = Query::insert()
+            .into_table(MailTemplate::Table)
+            .columns([
+                MailTemplate::Name,
+                MailTemplate::Subject,
+                MailTemplate::Body,
+                MailTemplate::IsActived,
+                MailTemplate::IsEnabled,
+                MailTemplate::CreatedOn,
+                MailTemplate::UpdatedOn,
+            ])
+            .values_panic([
+                MailTemplateNames::MAGIC_LINK_LOGIN.into(),
+                "Your [platform_name] Sign-in Link".into(),
+                "<div class='container'>
+
+        <div class='content'>
+            <h2>Sign In to [platform_name]</h2>
+            <p>Dear [user_name],</p>
+            <p>We received a request to sign in to your [platform_name] account without a password.</p>
+            <p>Click the button below on the same device and browser you asked for the link from:</p>
+            <p style='text-align: center;'>
+                <a href='[login_url]' class='button'>Sign In</a>
+            </p>
+            <p>If the button doesn't work, copy and paste this link into your browser: <br>
+            <a href='[login_url]'>[login_url]</a></p>
+            <p>This link works once and expires at [expiration_date]. If you did not ask to sign in, you can ignore this email.</p>
+            <p>Best regards,<br>[platform_name] Team</p>
+        </div>
+        <!-- Footer -->
+        <div class='footer'>
+            <p><a href='[platform_url]'>[platform_name]</a></p>
+        </div>
+    </div>"
+                    .into(),
+                true.into(),
+                true.into(),
+                chrono::Utc::now().into(),
+                chrono::Utc::now().into(),
+            ])
+            .to_owned();
+        manager.exec_stmt(insert).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let deleted = Query::delete()
+            .from_table(MailTemplate::Table)
+            .and_where(Expr::col(MailTemplate::Name).eq(MailTemplateNames::MAGIC_LINK_LOGIN))
+            .to_owned();
+        manager.exec_stmt(deleted).await?;
+
+        Ok(())
+    }
+}
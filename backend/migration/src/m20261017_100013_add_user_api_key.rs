@@ -0,0 +1,116 @@
+use sea_orm_migration::prelude::*;
+
+use crate::enums::{user::User, user_api_key::UserApiKey};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Every existing user is a person; service accounts only use API keys
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .add_column(ColumnDef::new(User::KindId).integer().not_null().default(1))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Named keys that act for their user, limited to some of the user's permissions.
+        // Only a hash of the key is kept; the prefix lets the owner tell keys apart.
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserApiKey::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserApiKey::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserApiKey::UserId).integer().not_null())
+                    .col(ColumnDef::new(UserApiKey::Name).string_len(100).not_null())
+                    .col(
+                        ColumnDef::new(UserApiKey::KeyPrefix)
+                            .string_len(20)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserApiKey::KeyHash)
+                            .string_len(64)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(UserApiKey::Scopes).text().not_null())
+                    .col(ColumnDef::new(UserApiKey::CreatedById).integer().null())
+                    .col(
+                        ColumnDef::new(UserApiKey::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(UserApiKey::ExpiresOn)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserApiKey::LastUsedOn)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserApiKey::RevokedOn)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_api_key_user")
+                            .from(UserApiKey::Table, UserApiKey::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_api_key_created_by")
+                            .from(UserApiKey::Table, UserApiKey::CreatedById)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_api_key_user_id")
+                    .table(UserApiKey::Table)
+                    .col(UserApiKey::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserApiKey::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(User::Table)
+                    .drop_column(User::KindId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,86 @@
+use crate::enums::permission::Permission;
+use chrono::Utc;
+use rex_game_shared::domain::enums::permission_codes::PermissionCodes;
+use sea_orm_migration::prelude::*;
+
+const OWN_CONTENT_PERMISSIONS: [(&str, &str, &str, PermissionCodes); 4] = [
+    (
+        "Update own flashcard",
+        "Can update the flashcards they created",
+        "flashcard",
+        PermissionCodes::FlashcardUpdateOwn,
+    ),
+    (
+        "Delete own flashcard",
+        "Can delete the flashcards they created",
+        "flashcard",
+        PermissionCodes::FlashcardDeleteOwn,
+    ),
+    (
+        "Update own flashcard type",
+        "Can update the flashcard types they created",
+        "flashcard_type",
+        PermissionCodes::FlashcardTypeUpdateOwn,
+    ),
+    (
+        "Delete own flashcard type",
+        "Can delete the flashcard types they created",
+        "flashcard_type",
+        PermissionCodes::FlashcardTypeDeleteOwn,
+    ),
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let now_utc = Utc::now().fixed_offset();
+
+        // Granted to roles instead of the matching "any" permissions, so contributors can
+        // author their own content without touching the rest of the catalogue
+        let mut permission_insert = Query::insert()
+            .into_table(Permission::Table)
+            .columns([
+                Permission::Name,
+                Permission::Description,
+                Permission::Module,
+                Permission::Code,
+                Permission::CreatedOn,
+                Permission::UpdatedOn,
+                Permission::IsActived,
+            ])
+            .to_owned();
+        for (name, description, module, code) in OWN_CONTENT_PERMISSIONS {
+            permission_insert.values_panic([
+                name.into(),
+                description.into(),
+                module.into(),
+                code.as_str().into(),
+                now_utc.into(),
+                now_utc.into(),
+                true.into(),
+            ]);
+        }
+        manager.exec_stmt(permission_insert).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let deleted = Query::delete()
+            .from_table(Permission::Table)
+            .and_where(
+                Expr::col(Permission::Code).is_in(
+                    OWN_CONTENT_PERMISSIONS
+                        .iter()
+                        .map(|(_, _, _, code)| code.as_str()),
+                ),
+            )
+            .to_owned();
+        manager.exec_stmt(deleted).await?;
+
+        Ok(())
+    }
+}
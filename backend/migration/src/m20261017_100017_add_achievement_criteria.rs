@@ -0,0 +1,89 @@
+use crate::enums::user_achievement::Achievement;
+use sea_orm_migration::prelude::*;
+
+/// Rules equivalent to the ones the seeded achievements used to be checked with
+const SEEDED_CRITERIA: [(&str, &str); 10] = [
+    (
+        "first_game",
+        r#"{"all":[{"metric":"games_played","comparator":"gte","threshold":1,"scope":"lifetime"}]}"#,
+    ),
+    (
+        "perfect_score",
+        r#"{"all":[{"metric":"accuracy","comparator":"gte","threshold":100,"scope":"session"}]}"#,
+    ),
+    (
+        "speed_demon",
+        r#"{"all":[{"metric":"time_spent_seconds","comparator":"lte","threshold":30,"scope":"session"},{"metric":"score","comparator":"gt","threshold":0,"scope":"session"}]}"#,
+    ),
+    (
+        "combo_master",
+        r#"{"all":[{"metric":"combo_max","comparator":"gte","threshold":10,"scope":"session"}]}"#,
+    ),
+    (
+        "streak_7",
+        r#"{"all":[{"metric":"streak_days","comparator":"gte","threshold":7,"scope":"lifetime"}]}"#,
+    ),
+    (
+        "games_10",
+        r#"{"all":[{"metric":"games_played","comparator":"gte","threshold":10,"scope":"lifetime"}]}"#,
+    ),
+    (
+        "games_50",
+        r#"{"all":[{"metric":"games_played","comparator":"gte","threshold":50,"scope":"lifetime"}]}"#,
+    ),
+    (
+        "games_100",
+        r#"{"all":[{"metric":"games_played","comparator":"gte","threshold":100,"scope":"lifetime"}]}"#,
+    ),
+    (
+        "score_1000",
+        r#"{"all":[{"metric":"score","comparator":"gte","threshold":1000,"scope":"lifetime"}]}"#,
+    ),
+    (
+        "score_10000",
+        r#"{"all":[{"metric":"score","comparator":"gte","threshold":10000,"scope":"lifetime"}]}"#,
+    ),
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Achievements without criteria never unlock
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Achievement::Table)
+                    .add_column(ColumnDef::new(Achievement::Criteria).json_binary().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        for (code, criteria) in SEEDED_CRITERIA {
+            let update = Query::update()
+                .table(Achievement::Table)
+                .value(
+                    Achievement::Criteria,
+                    Expr::val(criteria).cast_as(Alias::new("jsonb")),
+                )
+                .and_where(Expr::col(Achievement::Code).eq(code))
+                .to_owned();
+            manager.exec_stmt(update).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Achievement::Table)
+                    .drop_column(Achievement::Criteria)
+                    .to_owned(),
+            )
+            .await
+    }
+}
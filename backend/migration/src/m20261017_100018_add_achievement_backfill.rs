@@ -0,0 +1,145 @@
+use sea_orm_migration::prelude::*;
+
+use crate::enums::{
+    achievement_backfill::AchievementBackfill, user::User, user_achievement::Achievement,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Admin-requested replays of game history against achievement criteria. A background
+        // worker walks users in id order from `last_user_id`, one batch per transaction.
+        // `status` is `queued`, `running`, `completed` or `failed`; a null `achievement_id`
+        // replays every active achievement. Dry runs only count, they never unlock.
+        manager
+            .create_table(
+                Table::create()
+                    .table(AchievementBackfill::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AchievementBackfill::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AchievementBackfill::AchievementId)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(AchievementBackfill::IsDryRun)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(AchievementBackfill::Status)
+                            .string_len(16)
+                            .not_null()
+                            .default("queued"),
+                    )
+                    .col(
+                        ColumnDef::new(AchievementBackfill::LastUserId)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(AchievementBackfill::TotalUsers)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(AchievementBackfill::ProcessedUsers)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(AchievementBackfill::AffectedUsers)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(AchievementBackfill::UnlockedCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(AchievementBackfill::LastError).text().null())
+                    .col(
+                        ColumnDef::new(AchievementBackfill::CreatedById)
+                            .integer()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(AchievementBackfill::StartedOn)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(AchievementBackfill::CompletedOn)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(AchievementBackfill::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(AchievementBackfill::UpdatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_achievement_backfill_achievement")
+                            .from(
+                                AchievementBackfill::Table,
+                                AchievementBackfill::AchievementId,
+                            )
+                            .to(Achievement::Table, Achievement::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_achievement_backfill_created_by")
+                            .from(AchievementBackfill::Table, AchievementBackfill::CreatedById)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The worker picks the oldest unfinished backfill
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_achievement_backfill_status")
+                    .table(AchievementBackfill::Table)
+                    .col(AchievementBackfill::Status)
+                    .col(AchievementBackfill::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AchievementBackfill::Table).to_owned())
+            .await
+    }
+}
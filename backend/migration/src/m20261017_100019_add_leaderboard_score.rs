@@ -0,0 +1,178 @@
+use sea_orm_migration::prelude::*;
+
+use crate::enums::{leaderboard_score::LeaderboardScore, user::User};
+
+// Rebuilds the rollups from completed sessions. History is bucketed in UTC; sessions
+// completed after the migration are bucketed with the configured leaderboard offset.
+const SEED_LEADERBOARD_SCORE_SQL: &str = r#"
+INSERT INTO leaderboard_score (
+    period, period_start, game_type_id, flashcard_type_id, user_id,
+    total_score, games_played, best_score, accuracy_total, updated_on
+)
+SELECT
+    p.period,
+    CASE p.period
+        WHEN 'all_time' THEN DATE '1970-01-01'
+        ELSE date_trunc(p.unit, gs.completed_at AT TIME ZONE 'UTC')::date
+    END,
+    s.game_type_id,
+    s.flashcard_type_id,
+    gs.user_id,
+    SUM(gs.score),
+    COUNT(*),
+    MAX(gs.score),
+    COALESCE(SUM(gs.accuracy), 0),
+    CURRENT_TIMESTAMP
+FROM game_session gs
+CROSS JOIN (
+    VALUES ('daily', 'day'), ('weekly', 'week'), ('monthly', 'month'), ('all_time', 'day')
+) AS p (period, unit)
+CROSS JOIN LATERAL (
+    SELECT DISTINCT v.game_type_id, v.flashcard_type_id
+    FROM (
+        VALUES
+            (0, 0),
+            (gs.game_type_id, 0),
+            (0, COALESCE(gs.flashcard_type_id, 0)),
+            (gs.game_type_id, COALESCE(gs.flashcard_type_id, 0))
+    ) AS v (game_type_id, flashcard_type_id)
+) AS s
+WHERE gs.status_id = 2 AND gs.completed_at IS NOT NULL
+GROUP BY 1, 2, 3, 4, 5
+"#;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Per-user totals for each leaderboard bucket, kept up to date when a session
+        // completes. `period` is `daily`, `weekly`, `monthly` or `all_time` and
+        // `period_start` is the first day of the bucket (1970-01-01 for all time).
+        // A `game_type_id` or `flashcard_type_id` of 0 means the row covers all of them.
+        manager
+            .create_table(
+                Table::create()
+                    .table(LeaderboardScore::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LeaderboardScore::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(LeaderboardScore::Period)
+                            .string_len(8)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LeaderboardScore::PeriodStart)
+                            .date()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LeaderboardScore::GameTypeId)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(LeaderboardScore::FlashcardTypeId)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(LeaderboardScore::UserId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LeaderboardScore::TotalScore)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(LeaderboardScore::GamesPlayed)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(LeaderboardScore::BestScore)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(LeaderboardScore::AccuracyTotal)
+                            .decimal_len(12, 2)
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(LeaderboardScore::UpdatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_leaderboard_score_user")
+                            .from(LeaderboardScore::Table, LeaderboardScore::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One row per user and bucket, the target of the completion upsert
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_leaderboard_score_bucket_user")
+                    .table(LeaderboardScore::Table)
+                    .col(LeaderboardScore::Period)
+                    .col(LeaderboardScore::PeriodStart)
+                    .col(LeaderboardScore::GameTypeId)
+                    .col(LeaderboardScore::FlashcardTypeId)
+                    .col(LeaderboardScore::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Board pages and rank counts walk a bucket by score
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_leaderboard_score_bucket_score")
+                    .table(LeaderboardScore::Table)
+                    .col(LeaderboardScore::Period)
+                    .col(LeaderboardScore::PeriodStart)
+                    .col(LeaderboardScore::GameTypeId)
+                    .col(LeaderboardScore::FlashcardTypeId)
+                    .col(LeaderboardScore::TotalScore)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(SEED_LEADERBOARD_SCORE_SQL)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LeaderboardScore::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,189 @@
+use crate::enums::{
+    mail_template::MailTemplate, user::User, user_stats::UserStats, user_streak_day::UserStreakDay,
+};
+use rex_game_shared::domain::enums::mail_template_names::MailTemplateNames;
+use sea_orm_migration::prelude::*;
+
+// Days with a completed session become the start of the ledger. Players have no offset
+// yet, so history is bucketed in UTC like the old counter; `current_streak` is kept.
+const SEED_USER_STREAK_DAY_SQL: &str = r#"
+INSERT INTO user_streak_day (user_id, day, kind, created_on)
+SELECT DISTINCT gs.user_id, (gs.completed_at AT TIME ZONE 'UTC')::date, 'played', CURRENT_TIMESTAMP
+FROM game_session gs
+WHERE gs.status_id = 2 AND gs.completed_at IS NOT NULL;
+
+UPDATE user_stats
+SET last_streak_day = d.last_day
+FROM (SELECT user_id, MAX(day) AS last_day FROM user_streak_day GROUP BY user_id) AS d
+WHERE user_stats.user_id = d.user_id;
+"#;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Streak settings and state per player. Days start at midnight in the player's
+        // `utc_offset_minutes`. `points_spent` is score spent on freezes, so the score
+        // itself and the leaderboards are untouched. `streak_reminded_on` is the local
+        // day the last at-risk reminder was sent for.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserStats::Table)
+                    .add_column(
+                        ColumnDef::new(UserStats::UtcOffsetMinutes)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_column(
+                        ColumnDef::new(UserStats::StreakFreezes)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_column(
+                        ColumnDef::new(UserStats::PointsSpent)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_column(ColumnDef::new(UserStats::LastStreakDay).date().null())
+                    .add_column(
+                        ColumnDef::new(UserStats::StreakRemindersEnabled)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .add_column(ColumnDef::new(UserStats::StreakRemindedOn).date().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // One row per local day that kept a player's streak going: `played` when they
+        // practised, `frozen` when a streak freeze covered the day
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserStreakDay::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserStreakDay::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserStreakDay::UserId).integer().not_null())
+                    .col(ColumnDef::new(UserStreakDay::Day).date().not_null())
+                    .col(
+                        ColumnDef::new(UserStreakDay::Kind)
+                            .string_len(8)
+                            .not_null()
+                            .default("played"),
+                    )
+                    .col(
+                        ColumnDef::new(UserStreakDay::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_streak_day_user")
+                            .from(UserStreakDay::Table, UserStreakDay::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_streak_day_user_day")
+                    .table(UserStreakDay::Table)
+                    .col(UserStreakDay::UserId)
+                    .col(UserStreakDay::Day)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(SEED_USER_STREAK_DAY_SQL)
+            .await?;
+
+        let insert = Query::insert()
+            .into_table(MailTemplate::Table)
+            .columns([
+                MailTemplate::Name,
+                MailTemplate::Subject,
+                MailTemplate::Body,
+                MailTemplate::IsActived,
+                MailTemplate::IsEnabled,
+                MailTemplate::CreatedOn,
+                MailTemplate::UpdatedOn,
+            ])
+            .values_panic([
+                MailTemplateNames::STREAK_REMINDER.into(),
+                "Keep your [platform_name] streak going".into(),
+                "<div class='container'>
+
+        <div class='content'>
+            <h2>Your Streak Is at Risk</h2>
+            <p>Dear [user_name],</p>
+            <p>You have practised [streak_days] days in a row on [platform_name]. Play a round before the end of today to keep your streak.</p>
+            <p style='text-align: center;'>
+                <a href='[platform_url]' class='button'>Play Now</a>
+            </p>
+            <p>You can turn these reminders off in your streak settings.</p>
+            <p>Best regards,<br>[platform_name] Team</p>
+        </div>
+        <!-- Footer -->
+        <div class='footer'>
+            <p><a href='[platform_url]'>[platform_name]</a></p>
+        </div>
+    </div>"
+                    .into(),
+                true.into(),
+                true.into(),
+                chrono::Utc::now().into(),
+                chrono::Utc::now().into(),
+            ])
+            .to_owned();
+        manager.exec_stmt(insert).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let deleted = Query::delete()
+            .from_table(MailTemplate::Table)
+            .and_where(Expr::col(MailTemplate::Name).eq(MailTemplateNames::STREAK_REMINDER))
+            .to_owned();
+        manager.exec_stmt(deleted).await?;
+
+        manager
+            .drop_table(Table::drop().table(UserStreakDay::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserStats::Table)
+                    .drop_column(UserStats::UtcOffsetMinutes)
+                    .drop_column(UserStats::StreakFreezes)
+                    .drop_column(UserStats::PointsSpent)
+                    .drop_column(UserStats::LastStreakDay)
+                    .drop_column(UserStats::StreakRemindersEnabled)
+                    .drop_column(UserStats::StreakRemindedOn)
+                    .to_owned(),
+            )
+            .await
+    }
+}
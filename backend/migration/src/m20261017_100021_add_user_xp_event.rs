@@ -0,0 +1,162 @@
+use crate::enums::{
+    game_session::GameSession, system_settings::SystemSettings, user::User, user_stats::UserStats,
+    user_xp_event::UserXpEvent,
+};
+use sea_orm_migration::prelude::*;
+
+const PROGRESSION_SETTINGS_KEY: &str = "progression";
+const DEFAULT_PROGRESSION_SETTINGS: &str = r#"{"curve":{"base_xp":100,"exponent":1.5,"max_level":100},"session_xp":50,"game_type_xp":{},"accuracy_bonus_xp":50}"#;
+
+// Awards XP for history with the default settings: 50 per completed session with an
+// answered card, up to 50 more for its accuracy, and the points of each unlocked achievement.
+const SEED_USER_XP_EVENT_SQL: &str = r#"
+INSERT INTO user_xp_event (user_id, game_session_id, source, amount, created_on)
+SELECT gs.user_id, gs.id, 'session', 50, gs.completed_at
+FROM game_session gs
+WHERE gs.status_id = 2 AND gs.completed_at IS NOT NULL AND gs.cards_played > 0;
+
+INSERT INTO user_xp_event (user_id, game_session_id, source, amount, created_on)
+SELECT gs.user_id, gs.id, 'accuracy', ROUND(50 * LEAST(GREATEST(gs.accuracy, 0), 100) / 100), gs.completed_at
+FROM game_session gs
+WHERE gs.status_id = 2 AND gs.completed_at IS NOT NULL AND gs.cards_played > 0
+    AND ROUND(50 * LEAST(GREATEST(gs.accuracy, 0), 100) / 100) > 0;
+
+INSERT INTO user_xp_event (user_id, game_session_id, source, amount, created_on)
+SELECT ua.user_id, NULL, 'achievement', a.points, ua.unlocked_at
+FROM user_achievement ua
+JOIN achievement a ON a.id = ua.achievement_id
+WHERE a.points > 0;
+
+UPDATE user_stats
+SET total_xp = x.total_xp
+FROM (SELECT user_id, SUM(amount) AS total_xp FROM user_xp_event GROUP BY user_id) AS x
+WHERE user_stats.user_id = x.user_id;
+"#;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Sum of the player's XP ledger; the level is derived from it with the curve
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserStats::Table)
+                    .add_column(
+                        ColumnDef::new(UserStats::TotalXp)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One row per XP grant. `source` is `session`, `accuracy` or `achievement`
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserXpEvent::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserXpEvent::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserXpEvent::UserId).integer().not_null())
+                    .col(ColumnDef::new(UserXpEvent::GameSessionId).integer().null())
+                    .col(
+                        ColumnDef::new(UserXpEvent::Source)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(UserXpEvent::Amount).integer().not_null())
+                    .col(
+                        ColumnDef::new(UserXpEvent::CreatedOn)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_xp_event_user")
+                            .from(UserXpEvent::Table, UserXpEvent::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_xp_event_game_session")
+                            .from(UserXpEvent::Table, UserXpEvent::GameSessionId)
+                            .to(GameSession::Table, GameSession::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_xp_event_user")
+                    .table(UserXpEvent::Table)
+                    .col(UserXpEvent::UserId)
+                    .col(UserXpEvent::CreatedOn)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(SEED_USER_XP_EVENT_SQL)
+            .await?;
+
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(SystemSettings::Table)
+                    .columns([
+                        SystemSettings::Key,
+                        SystemSettings::Value,
+                        SystemSettings::Description,
+                    ])
+                    .values_panic([
+                        PROGRESSION_SETTINGS_KEY.into(),
+                        DEFAULT_PROGRESSION_SETTINGS.into(),
+                        "XP awarded per session and the curve that turns XP into levels".into(),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(SystemSettings::Table)
+                    .and_where(Expr::col(SystemSettings::Key).eq(PROGRESSION_SETTINGS_KEY))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(UserXpEvent::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserStats::Table)
+                    .drop_column(UserStats::TotalXp)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -11,7 +11,11 @@ pub struct FlashcardDetailDto {
     pub sub_description: Option<String>,
     pub created_on: DateTime<Utc>,
     pub updated_on: DateTime<Utc>,
+    pub created_by_id: i32,
     pub image_id: i32,
+    /// Appended as `?v=` to the image URL; absent for images without a checksum.
+    #[serde(default)]
+    pub image_version: Option<String>,
     pub flashcard_types: Vec<FlashcardTypeDto>,
     pub game_types: Vec<FlashcardGameTypeInfo>,
 }
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct FlashcardExportDto {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub sub_description: Option<String>,
+    pub file_name: String,
+    pub content_type: String,
+    pub image_data: Vec<u8>,
+}
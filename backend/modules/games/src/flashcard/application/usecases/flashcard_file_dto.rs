@@ -1,7 +1,22 @@
+use chrono::{DateTime, Utc};
+
 pub struct FlashcardFileDto {
     pub id: i32,
     pub name: Option<String>,
     pub file_name: String,
     pub content_type: String,
     pub data: Vec<u8>,
+    pub checksum: Option<String>,
+    pub updated_on: DateTime<Utc>,
+}
+
+impl FlashcardFileDto {
+    /// Short content hash used to build cache-busting image URLs.
+    pub fn version(&self) -> Option<String> {
+        self.checksum.as_deref().map(image_version)
+    }
+}
+
+pub fn image_version(checksum: &str) -> String {
+    checksum.chars().take(16).collect()
 }
@@ -8,5 +8,7 @@ pub struct FlashcardTypeDto {
     pub description: Option<String>,
     pub created_on: DateTime<Utc>,
     pub updated_on: DateTime<Utc>,
+    /// Absent for the types seeded with the catalogue
+    pub created_by_id: Option<i32>,
     pub is_actived: bool,
 }
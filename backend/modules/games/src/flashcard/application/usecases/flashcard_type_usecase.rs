@@ -50,6 +50,7 @@ impl<TFT: FlashcardTypeRepositoryTrait> FlashcardTypeUseCaseTrait for FlashcardT
                         description: f.description,
                         created_on: f.created_on.with_timezone(&Utc),
                         updated_on: f.updated_on.with_timezone(&Utc),
+                        created_by_id: f.created_by_id,
                         is_actived: f.is_actived,
                     })
                     .collect();
@@ -71,6 +72,7 @@ impl<TFT: FlashcardTypeRepositoryTrait> FlashcardTypeUseCaseTrait for FlashcardT
                 description: f.description,
                 created_on: f.created_on.with_timezone(&Utc),
                 updated_on: f.updated_on.with_timezone(&Utc),
+                created_by_id: f.created_by_id,
                 is_actived: f.is_actived,
             }),
             Err(_) => None,
@@ -95,6 +97,7 @@ impl<TFT: FlashcardTypeRepositoryTrait> FlashcardTypeUseCaseTrait for FlashcardT
                         description: f.description,
                         created_on: f.created_on.with_timezone(&Utc),
                         updated_on: f.updated_on.with_timezone(&Utc),
+                        created_by_id: f.created_by_id,
                         is_actived: f.is_actived,
                     })
                     .collect();
@@ -1,6 +1,7 @@
 use crate::flashcard::domain::{
     models::{
-        flashcard_file_model::FlashcardFileModel, flashcard_model::FlashcardModel,
+        flashcard_file_model::FlashcardFileModel,
+        flashcard_file_variant_model::FlashcardFileVariantModel, flashcard_model::FlashcardModel,
         flashcard_type_relation_model::FlashcardTypeRelationModel,
     },
     repositories::{
@@ -10,14 +11,22 @@ use crate::flashcard::domain::{
     },
 };
 use chrono::Utc;
+use rex_game_shared::domain::transaction_manager_trait::{
+    TransactionManagerTrait, TransactionWrapperTrait,
+};
+use rex_game_shared::infrastructure::database::TransactionManager;
+use rex_game_shared::infrastructure::helpers::image_helper::{
+    ImageHelper, ImageVariantFormat, ImageVariantSize,
+};
 use rex_game_shared::ApplicationError;
 
 use rex_game_shared::domain::models::page_list_model::PageListModel;
 
 use super::{
     flashcard_creation_dto::FlashcardCreationDto, flashcard_dto::FlashcardDto,
-    flashcard_file_dto::FlashcardFileDto, flashcard_updation_dto::FlashcardUpdationDto,
-    flashcard_usecase_trait::FlashcardUseCaseTrait,
+    flashcard_export_dto::FlashcardExportDto,
+    flashcard_file_dto::{image_version, FlashcardFileDto},
+    flashcard_updation_dto::FlashcardUpdationDto, flashcard_usecase_trait::FlashcardUseCaseTrait,
 };
 
 #[derive(Clone)]
@@ -30,6 +39,7 @@ where
     _flashcard_repository: TF,
     _flashcard_file_repository: TFF,
     _flashcard_type_relation_repository: TFTR,
+    _transaction_manager: TransactionManager,
 }
 
 impl<
@@ -42,13 +52,84 @@ impl<
         flashcard_repository: TF,
         flashcard_file_repository: TFF,
         flashcard_type_relation_repository: TFTR,
+        transaction_manager: TransactionManager,
     ) -> Self {
         Self {
             _flashcard_repository: flashcard_repository,
             _flashcard_file_repository: flashcard_file_repository,
             _flashcard_type_relation_repository: flashcard_type_relation_repository,
+            _transaction_manager: transaction_manager,
         }
     }
+
+    /// Re-encodes an uploaded image so EXIF data never reaches the store.
+    async fn sanitize_image(
+        data: Vec<u8>,
+        content_type: &str,
+    ) -> Result<Vec<u8>, ApplicationError> {
+        ImageHelper::sanitize_blocking(data, content_type.to_string())
+            .await
+            .map_err(|_| ApplicationError::invalid_input("Flashcard image could not be decoded"))
+    }
+
+    /// Stored blob keys are added to `stored_blobs`, so the caller can delete them if the
+    /// transaction does not commit.
+    async fn create_flashcard_without_commit(
+        &self,
+        flashcard_req: FlashcardCreationDto,
+        transaction: &dyn TransactionWrapperTrait,
+        stored_blobs: &mut Vec<String>,
+    ) -> Result<i32, ApplicationError> {
+        let image_data = flashcard_req
+            .image_data
+            .ok_or(ApplicationError::invalid_input("Flashcard image is required"))?;
+        let image_data = Self::sanitize_image(image_data, &flashcard_req.content_type).await?;
+        let active_flashcard_file = FlashcardFileModel {
+            name: Some(flashcard_req.name.clone()),
+            file_name: flashcard_req.file_name,
+            content_type: flashcard_req.content_type,
+            data: image_data,
+            created_by_id: flashcard_req.created_by_id,
+            updated_by_id: flashcard_req.updated_by_id,
+            ..Default::default()
+        };
+        let (new_file_id, storage_key) = self
+            ._flashcard_file_repository
+            .create_without_commit(active_flashcard_file, Box::new(transaction))
+            .await?;
+        stored_blobs.push(storage_key);
+
+        let active_flashcard = FlashcardModel {
+            name: flashcard_req.name,
+            description: flashcard_req.description,
+            sub_description: flashcard_req.sub_description,
+            file_id: new_file_id,
+            created_by_id: flashcard_req.created_by_id,
+            updated_by_id: flashcard_req.updated_by_id,
+            ..Default::default()
+        };
+        let created_id = self
+            ._flashcard_repository
+            .create_without_commit(active_flashcard, Box::new(transaction))
+            .await?;
+
+        let active_type_relations = flashcard_req
+            .type_ids
+            .iter()
+            .map(|type_id| FlashcardTypeRelationModel {
+                flashcard_id: created_id,
+                flashcard_type_id: *type_id,
+                created_by_id: flashcard_req.created_by_id,
+                updated_by_id: flashcard_req.updated_by_id,
+                ..Default::default()
+            })
+            .collect();
+        self._flashcard_type_relation_repository
+            .create_without_commit(active_type_relations, Box::new(transaction))
+            .await?;
+
+        Ok(created_id)
+    }
 }
 
 impl<
@@ -69,6 +150,10 @@ impl<
             .await
         {
             Ok(page_list) => {
+                let checksums = self
+                    ._flashcard_file_repository
+                    .get_checksums(page_list.items.iter().map(|f| f.file_id).collect())
+                    .await?;
                 let items = page_list
                     .items
                     .into_iter()
@@ -79,7 +164,9 @@ impl<
                         sub_description: f.sub_description,
                         created_on: f.created_on.with_timezone(&Utc),
                         updated_on: f.updated_on.with_timezone(&Utc),
+                        created_by_id: f.created_by_id,
                         image_id: f.file_id,
+                        image_version: checksums.get(&f.file_id).map(|c| image_version(c)),
                         is_actived: f.is_actived,
                         flashcard_type_names: vec![],
                     })
@@ -98,12 +185,19 @@ impl<
         let existing = self._flashcard_repository.get_by_id(id).await;
         match existing {
             Some(f) => Some(FlashcardDto {
+                image_version: self
+                    ._flashcard_file_repository
+                    .get_checksums(vec![f.file_id])
+                    .await
+                    .ok()
+                    .and_then(|checksums| checksums.get(&f.file_id).map(|c| image_version(c))),
                 id: f.id,
                 name: f.name,
                 description: f.description,
                 sub_description: f.sub_description,
                 created_on: f.created_on.with_timezone(&Utc),
                 updated_on: f.updated_on.with_timezone(&Utc),
+                created_by_id: f.created_by_id,
                 image_id: f.file_id,
                 is_actived: f.is_actived,
                 flashcard_type_names: vec![],
@@ -116,11 +210,16 @@ impl<
         &'a self,
         flashcard_req: FlashcardCreationDto,
     ) -> Result<i32, ApplicationError> {
+        let image_data = Self::sanitize_image(
+            flashcard_req.image_data.unwrap_or_default(),
+            &flashcard_req.content_type,
+        )
+        .await?;
         let active_flashcard_file = FlashcardFileModel {
             name: Some(flashcard_req.name.clone()),
             file_name: flashcard_req.file_name,
             content_type: flashcard_req.content_type,
-            data: flashcard_req.image_data.unwrap(),
+            data: image_data,
             created_by_id: flashcard_req.created_by_id,
             updated_by_id: flashcard_req.updated_by_id,
             ..Default::default()
@@ -196,6 +295,7 @@ impl<
                     id: existing_flashcard.file_id.to_string(),
                 })?;
 
+            let req_file = Self::sanitize_image(req_file, &existing_file.content_type).await?;
             let updating_file = FlashcardFileModel {
                 data: req_file,
                 id: existing_file.id,
@@ -280,6 +380,69 @@ impl<
         }
     }
 
+    async fn import_flashcards(
+        &self,
+        flashcards: Vec<FlashcardCreationDto>,
+    ) -> Result<Vec<i32>, ApplicationError> {
+        let transaction = self._transaction_manager.begin().await?;
+        let mut created_ids = Vec::with_capacity(flashcards.len());
+        let mut stored_blobs = Vec::with_capacity(flashcards.len());
+        for flashcard_req in flashcards {
+            match self
+                .create_flashcard_without_commit(flashcard_req, &transaction, &mut stored_blobs)
+                .await
+            {
+                Ok(created_id) => created_ids.push(created_id),
+                Err(err) => {
+                    let rollback = self._transaction_manager.rollback(transaction).await;
+                    self._flashcard_file_repository
+                        .delete_blobs(&stored_blobs)
+                        .await;
+                    rollback?;
+                    return Err(err);
+                }
+            }
+        }
+
+        // Nothing references the blobs unless the rows are committed
+        if let Err(err) = self._transaction_manager.commit(transaction).await {
+            self._flashcard_file_repository
+                .delete_blobs(&stored_blobs)
+                .await;
+            return Err(err.into());
+        }
+        Ok(created_ids)
+    }
+
+    async fn export_flashcards(
+        &self,
+        flashcard_type_id: i32,
+    ) -> Result<Vec<FlashcardExportDto>, ApplicationError> {
+        let flashcards = self
+            ._flashcard_repository
+            .get_by_flashcard_type_id(flashcard_type_id)
+            .await?;
+
+        let mut exported = Vec::with_capacity(flashcards.len());
+        for flashcard in flashcards {
+            let file = self
+                ._flashcard_file_repository
+                .get_by_id(flashcard.file_id)
+                .await?;
+            exported.push(FlashcardExportDto {
+                id: flashcard.id,
+                name: flashcard.name,
+                description: flashcard.description,
+                sub_description: flashcard.sub_description,
+                file_name: file.file_name,
+                content_type: file.content_type,
+                image_data: file.data,
+            });
+        }
+
+        Ok(exported)
+    }
+
     async fn get_image_by_file_id<'a>(
         &'a self,
         file_id: i32,
@@ -299,6 +462,94 @@ impl<
             file_name: existing.file_name,
             content_type: existing.content_type,
             data: existing.data,
+            checksum: existing.checksum,
+            updated_on: existing.updated_on,
+        })
+    }
+
+    async fn get_image_info_by_file_id(
+        &self,
+        file_id: i32,
+    ) -> Result<FlashcardFileDto, ApplicationError> {
+        let existing = self
+            ._flashcard_file_repository
+            .get_info_by_id(file_id)
+            .await
+            .map_err(|_| ApplicationError::EntityNotFound {
+                entity: "Flashcard_file".to_string(),
+                id: file_id.to_string(),
+            })?;
+
+        Ok(FlashcardFileDto {
+            id: existing.id,
+            name: existing.name,
+            file_name: existing.file_name,
+            content_type: existing.content_type,
+            data: existing.data,
+            checksum: existing.checksum,
+            updated_on: existing.updated_on,
+        })
+    }
+
+    async fn get_image_variant(
+        &self,
+        file_id: i32,
+        size: ImageVariantSize,
+        format: ImageVariantFormat,
+    ) -> Result<FlashcardFileDto, ApplicationError> {
+        let not_found = |_| ApplicationError::EntityNotFound {
+            entity: "Flashcard_file".to_string(),
+            id: file_id.to_string(),
+        };
+        let stored = self
+            ._flashcard_file_repository
+            .get_variant(file_id, size.as_str(), format.as_str())
+            .await?;
+        let (source, variant) = match stored {
+            Some(variant) => (
+                self._flashcard_file_repository
+                    .get_info_by_id(file_id)
+                    .await
+                    .map_err(not_found)?,
+                variant,
+            ),
+            None => {
+                let mut source = self
+                    ._flashcard_file_repository
+                    .get_by_id(file_id)
+                    .await
+                    .map_err(not_found)?;
+                let rendered = ImageHelper::render_variant_blocking(
+                    std::mem::take(&mut source.data),
+                    size,
+                    format,
+                )
+                .await?;
+                let variant = FlashcardFileVariantModel {
+                    flashcard_file_id: file_id,
+                    size: size.as_str().to_string(),
+                    format: format.as_str().to_string(),
+                    content_type: rendered.content_type,
+                    data: rendered.data,
+                    width: rendered.width as i32,
+                    height: rendered.height as i32,
+                    ..Default::default()
+                };
+                self._flashcard_file_repository
+                    .create_variant(variant.clone())
+                    .await?;
+                (source, variant)
+            }
+        };
+
+        Ok(FlashcardFileDto {
+            id: source.id,
+            name: source.name,
+            file_name: source.file_name,
+            content_type: variant.content_type,
+            data: variant.data,
+            checksum: source.checksum,
+            updated_on: source.updated_on,
         })
     }
 
@@ -1,8 +1,13 @@
 use super::{
     flashcard_creation_dto::FlashcardCreationDto, flashcard_dto::FlashcardDto,
-    flashcard_file_dto::FlashcardFileDto, flashcard_updation_dto::FlashcardUpdationDto,
+    flashcard_export_dto::FlashcardExportDto, flashcard_file_dto::FlashcardFileDto,
+    flashcard_updation_dto::FlashcardUpdationDto,
+};
+use rex_game_shared::{
+    domain::models::page_list_model::PageListModel,
+    infrastructure::helpers::image_helper::{ImageVariantFormat, ImageVariantSize},
+    ApplicationError,
 };
-use rex_game_shared::{domain::models::page_list_model::PageListModel, ApplicationError};
 use std::future::Future;
 
 pub trait FlashcardUseCaseTrait {
@@ -17,10 +22,33 @@ pub trait FlashcardUseCaseTrait {
         &'a self,
         flashcard: FlashcardCreationDto,
     ) -> impl Future<Output = Result<i32, ApplicationError>>;
+    /// Creates every flashcard, its image and type relations in a single transaction.
+    /// Returns the new ids in the same order as `flashcards`.
+    fn import_flashcards(
+        &self,
+        flashcards: Vec<FlashcardCreationDto>,
+    ) -> impl Future<Output = Result<Vec<i32>, ApplicationError>>;
+    fn export_flashcards(
+        &self,
+        flashcard_type_id: i32,
+    ) -> impl Future<Output = Result<Vec<FlashcardExportDto>, ApplicationError>>;
     fn get_image_by_file_id<'a>(
         &'a self,
         file_id: i32,
     ) -> impl Future<Output = Result<FlashcardFileDto, ApplicationError>>;
+    /// Image metadata with empty `data`, for answering conditional requests.
+    fn get_image_info_by_file_id(
+        &self,
+        file_id: i32,
+    ) -> impl Future<Output = Result<FlashcardFileDto, ApplicationError>>;
+    /// The image resized and re-encoded, generated on first request. `checksum` stays the
+    /// one of the source image.
+    fn get_image_variant(
+        &self,
+        file_id: i32,
+        size: ImageVariantSize,
+        format: ImageVariantFormat,
+    ) -> impl Future<Output = Result<FlashcardFileDto, ApplicationError>>;
 
     fn update_flashcard<'a>(
         &'a self,
@@ -1,6 +1,7 @@
 pub mod flashcard_creation_dto;
 pub mod flashcard_detail_dto;
 pub mod flashcard_dto;
+pub mod flashcard_export_dto;
 pub mod flashcard_file_dto;
 pub mod flashcard_type_creation_dto;
 pub mod flashcard_type_dto;
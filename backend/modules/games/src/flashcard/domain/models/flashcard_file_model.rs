@@ -7,6 +7,7 @@ pub struct FlashcardFileModel {
     pub file_name: String,
     pub content_type: String,
     pub data: Vec<u8>,
+    pub checksum: Option<String>,
     pub created_on: DateTime<Utc>,
     pub updated_on: DateTime<Utc>,
     pub created_by_id: i32,
@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+
+/// A resized, re-encoded copy of a flashcard image.
+#[derive(Clone, Default)]
+pub struct FlashcardFileVariantModel {
+    pub id: i32,
+    pub flashcard_file_id: i32,
+    pub size: String,
+    pub format: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+    pub width: i32,
+    pub height: i32,
+    pub created_on: DateTime<Utc>,
+}
@@ -1,9 +1,11 @@
 pub mod flashcard_file_model;
+pub mod flashcard_file_variant_model;
 pub mod flashcard_model;
 pub mod flashcard_type_model;
 pub mod flashcard_type_relation_model;
 
 pub use flashcard_file_model::FlashcardFileModel;
+pub use flashcard_file_variant_model::FlashcardFileVariantModel;
 pub use flashcard_model::FlashcardModel;
 pub use flashcard_type_model::FlashcardTypeModel;
 pub use flashcard_type_relation_model::FlashcardTypeRelationModel;
@@ -1,6 +1,11 @@
+use std::collections::HashMap;
 use std::future::Future;
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
 use rex_game_shared::InfraError;
-use crate::flashcard::domain::models::flashcard_file_model::FlashcardFileModel;
+use crate::flashcard::domain::models::{
+    flashcard_file_model::FlashcardFileModel,
+    flashcard_file_variant_model::FlashcardFileVariantModel,
+};
 
 pub trait FlashcardFileRepositoryTrait {
     fn create(
@@ -8,6 +13,18 @@ pub trait FlashcardFileRepositoryTrait {
         flashcard_file_req: FlashcardFileModel,
     ) -> impl Future<Output = Result<i32, InfraError>>;
 
+    /// Stores the blob right away and inserts the row on `transaction`. Returns the new id
+    /// and the blob key, which has to be passed to `delete_blobs` if the transaction is
+    /// rolled back.
+    fn create_without_commit(
+        &self,
+        flashcard_file_req: FlashcardFileModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> impl Future<Output = Result<(i32, String), InfraError>>;
+
+    /// Removes blobs stored for rows that were never committed. Failures are ignored.
+    fn delete_blobs(&self, storage_keys: &[String]) -> impl Future<Output = ()>;
+
     fn update(
         &self,
         flashcard_file_req: FlashcardFileModel,
@@ -15,5 +32,32 @@ pub trait FlashcardFileRepositoryTrait {
 
     fn get_by_id(&self, id: i32) -> impl Future<Output = Result<FlashcardFileModel, InfraError>>;
 
+    /// Like `get_by_id` but leaves `data` empty, so the blob is not fetched.
+    fn get_info_by_id(
+        &self,
+        id: i32,
+    ) -> impl Future<Output = Result<FlashcardFileModel, InfraError>>;
+
+    /// Stored checksums by file id. Files without one are left out.
+    fn get_checksums(
+        &self,
+        ids: Vec<i32>,
+    ) -> impl Future<Output = Result<HashMap<i32, String>, InfraError>>;
+
+    /// A stored variant with its bytes, or `None` when it has not been generated yet.
+    fn get_variant(
+        &self,
+        file_id: i32,
+        size: &str,
+        format: &str,
+    ) -> impl Future<Output = Result<Option<FlashcardFileVariantModel>, InfraError>>;
+
+    /// Stores a generated variant. If another request stored the same one first, that
+    /// one is kept.
+    fn create_variant(
+        &self,
+        variant: FlashcardFileVariantModel,
+    ) -> impl Future<Output = Result<(), InfraError>>;
+
     fn delete_by_id(&self, id: i32) -> impl Future<Output = Result<u64, InfraError>>;
 }
@@ -1,5 +1,6 @@
 use crate::flashcard::domain::models::flashcard_model::FlashcardModel;
 use rex_game_shared::domain::models::page_list_model::PageListModel;
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
 use rex_game_shared::InfraError;
 use std::future::Future;
 
@@ -13,9 +14,20 @@ pub trait FlashcardRepositoryTrait {
 
     fn create(&self, flashcard: FlashcardModel) -> impl Future<Output = Result<i32, InfraError>>;
 
+    fn create_without_commit(
+        &self,
+        flashcard: FlashcardModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> impl Future<Output = Result<i32, InfraError>>;
+
     fn update(&self, flashcard: FlashcardModel) -> impl Future<Output = Result<bool, InfraError>>;
 
     fn get_by_id(&self, id: i32) -> impl Future<Output = Option<FlashcardModel>>;
 
+    fn get_by_flashcard_type_id(
+        &self,
+        flashcard_type_id: i32,
+    ) -> impl Future<Output = Result<Vec<FlashcardModel>, InfraError>>;
+
     fn delete_by_id(&self, id: i32) -> impl Future<Output = Result<u64, InfraError>>;
 }
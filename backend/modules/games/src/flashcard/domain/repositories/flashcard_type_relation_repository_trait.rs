@@ -1,4 +1,5 @@
 use std::future::Future;
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
 use rex_game_shared::InfraError;
 use crate::flashcard::domain::models::flashcard_type_relation_model::FlashcardTypeRelationModel;
 
@@ -8,6 +9,12 @@ pub trait FlashcardTypeRelationRepositoryTrait {
         flashcard_type_relations_req: Vec<FlashcardTypeRelationModel>,
     ) -> impl Future<Output = Result<i32, InfraError>>;
 
+    fn create_without_commit(
+        &self,
+        flashcard_type_relations_req: Vec<FlashcardTypeRelationModel>,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> impl Future<Output = Result<u64, InfraError>>;
+
     fn get_by_flashcard_id(
         &self,
         flashcard_id: i32,
@@ -1,62 +1,263 @@
 use crate::flashcard::domain::{
-    models::flashcard_file_model::FlashcardFileModel,
+    models::{
+        flashcard_file_model::FlashcardFileModel,
+        flashcard_file_variant_model::FlashcardFileVariantModel,
+    },
     repositories::flashcard_file_repository_trait::FlashcardFileRepositoryTrait,
 };
 use chrono::Utc;
+use rex_game_shared::domain::helpers::blob_store_trait::BlobStoreTrait;
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
+use rex_game_shared::infrastructure::database::SeaOrmTransactionWrapper;
+use rex_game_shared::infrastructure::helpers::blob_store_provider::BlobStoreFactory;
 use rex_game_shared::InfraError;
-use sea_orm::{DatabaseConnection, EntityTrait, Set};
-use std::sync::Arc;
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
+    QuerySelect, Set,
+};
+use std::{collections::HashMap, sync::Arc};
 
 use rex_game_entities::entities::flashcard_file::{
     self, Entity as FlashcardFile,
 };
+use rex_game_entities::entities::flashcard_file_variant::{
+    self, Entity as FlashcardFileVariant,
+};
+
+const BLOB_KEY_PREFIX: &str = "flashcards";
+const VARIANT_BLOB_KEY_PREFIX: &str = "flashcards/variants";
 
 #[derive(Clone)]
 pub struct FlashcardFileRepository {
     _db_connection: Arc<DatabaseConnection>,
+    _blob_store: Arc<dyn BlobStoreTrait>,
 }
 
 impl FlashcardFileRepository {
-    pub fn new(db_connection: Arc<DatabaseConnection>) -> Self {
+    pub fn new(db_connection: Arc<DatabaseConnection>, blob_store: Arc<dyn BlobStoreTrait>) -> Self {
         Self {
             _db_connection: db_connection,
+            _blob_store: blob_store,
+        }
+    }
+
+    /// Uploads the bytes under a fresh key and returns the key with its checksum.
+    async fn store_data(
+        &self,
+        data: &[u8],
+        content_type: &str,
+    ) -> Result<(String, String), InfraError> {
+        let key = BlobStoreFactory::new_key(BLOB_KEY_PREFIX);
+        self._blob_store.put(&key, data, content_type).await?;
+        Ok((key, BlobStoreFactory::checksum(data)))
+    }
+
+    /// Reads the bytes from the blob store, or from `data` for rows not migrated yet.
+    async fn load_data(&self, file: &flashcard_file::Model) -> Result<Vec<u8>, InfraError> {
+        let key = match &file.storage_key {
+            Some(key) => key,
+            None => return Ok(file.data.clone().unwrap_or_default()),
+        };
+
+        let data = self._blob_store.get(key).await?;
+        if let Some(checksum) = &file.checksum {
+            if &BlobStoreFactory::checksum(&data) != checksum {
+                return Err(InfraError::file_storage(format!(
+                    "Checksum mismatch for blob {}",
+                    key
+                )));
+            }
+        }
+        Ok(data)
+    }
+
+    fn to_model(file: flashcard_file::Model, data: Vec<u8>) -> FlashcardFileModel {
+        // Rows not moved to the blob store yet have no stored checksum
+        let checksum = file
+            .checksum
+            .or_else(|| file.data.as_deref().map(BlobStoreFactory::checksum));
+        FlashcardFileModel {
+            data,
+            checksum,
+            file_name: file.file_name,
+            content_type: file.content_type,
+            created_by_id: file.created_by_id,
+            updated_by_id: file.updated_by_id,
+            created_on: file.created_on.with_timezone(&Utc),
+            updated_on: file.updated_on.with_timezone(&Utc),
+            id: file.id,
+            name: file.name,
+            is_actived: file.is_actived,
+        }
+    }
+
+    /// Drops the generated variants of a file, e.g. because its image was replaced.
+    async fn delete_variants(&self, file_id: i32) -> Result<(), InfraError> {
+        let db = self._db_connection.as_ref();
+        let variants = FlashcardFileVariant::find()
+            .filter(flashcard_file_variant::Column::FlashcardFileId.eq(file_id))
+            .all(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string()))?;
+        if variants.is_empty() {
+            return Ok(());
+        }
+
+        FlashcardFileVariant::delete_many()
+            .filter(flashcard_file_variant::Column::FlashcardFileId.eq(file_id))
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string()))?;
+        for variant in variants {
+            let _ = self._blob_store.delete(&variant.storage_key).await;
         }
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<flashcard_file::Model, InfraError> {
+        let db = self._db_connection.as_ref();
+        FlashcardFile::find_by_id(id)
+            .one(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?
+            .ok_or(InfraError::not_found("FlashcardFile", id.to_string()))
     }
 }
 
 impl FlashcardFileRepositoryTrait for FlashcardFileRepository {
     async fn get_by_id(&self, id: i32) -> Result<FlashcardFileModel, InfraError> {
+        let file = self.find_by_id(id).await?;
+        let data = self.load_data(&file).await?;
+        Ok(Self::to_model(file, data))
+    }
+
+    async fn get_info_by_id(&self, id: i32) -> Result<FlashcardFileModel, InfraError> {
+        let file = self.find_by_id(id).await?;
+        Ok(Self::to_model(file, Vec::new()))
+    }
+
+    async fn get_checksums(&self, ids: Vec<i32>) -> Result<HashMap<i32, String>, InfraError> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let db = self._db_connection.as_ref();
+        let rows: Vec<(i32, Option<String>)> = FlashcardFile::find()
+            .select_only()
+            .column(flashcard_file::Column::Id)
+            .column(flashcard_file::Column::Checksum)
+            .filter(flashcard_file::Column::Id.is_in(ids))
+            .into_tuple()
+            .all(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(id, checksum)| checksum.map(|c| (id, c)))
+            .collect())
+    }
+
+    async fn get_variant(
+        &self,
+        file_id: i32,
+        size: &str,
+        format: &str,
+    ) -> Result<Option<FlashcardFileVariantModel>, InfraError> {
+        let db = self._db_connection.as_ref();
+        let variant = FlashcardFileVariant::find()
+            .filter(flashcard_file_variant::Column::FlashcardFileId.eq(file_id))
+            .filter(flashcard_file_variant::Column::Size.eq(size))
+            .filter(flashcard_file_variant::Column::Format.eq(format))
+            .one(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string()))?;
+        let variant = match variant {
+            Some(variant) => variant,
+            None => return Ok(None),
+        };
+
+        // A missing or corrupt blob is treated as not generated, so it gets rebuilt
+        let data = match self._blob_store.get(&variant.storage_key).await {
+            Ok(data) if BlobStoreFactory::checksum(&data) == variant.checksum => data,
+            _ => {
+                FlashcardFileVariant::delete_by_id(variant.id)
+                    .exec(db)
+                    .await
+                    .map_err(|err| InfraError::database(err.to_string()))?;
+                return Ok(None);
+            }
+        };
+
+        Ok(Some(FlashcardFileVariantModel {
+            id: variant.id,
+            flashcard_file_id: variant.flashcard_file_id,
+            size: variant.size,
+            format: variant.format,
+            content_type: variant.content_type,
+            data,
+            width: variant.width,
+            height: variant.height,
+            created_on: variant.created_on.with_timezone(&Utc),
+        }))
+    }
+
+    async fn create_variant(&self, variant: FlashcardFileVariantModel) -> Result<(), InfraError> {
         let db = self._db_connection.as_ref();
+        let storage_key = BlobStoreFactory::new_key(VARIANT_BLOB_KEY_PREFIX);
+        self._blob_store
+            .put(&storage_key, &variant.data, &variant.content_type)
+            .await?;
 
-        let flashcard = FlashcardFile::find_by_id(id).one(db).await.map_err(|err| {
-            InfraError::database(err.to_string().as_str())
-        })?;
-
-        match flashcard {
-            Some(f) => Ok(FlashcardFileModel {
-                file_name: f.file_name,
-                content_type: f.content_type,
-                data: f.data,
-                created_by_id: f.created_by_id,
-                updated_by_id: f.updated_by_id,
-                created_on: f.created_on.with_timezone(&Utc),
-                updated_on: f.updated_on.with_timezone(&Utc),
-                id: f.id,
-                name: f.name,
-                is_actived: f.is_actived,
-            }),
-            None => Err(InfraError::not_found("FlashcardFile", id.to_string())),
+        let new_variant = flashcard_file_variant::ActiveModel {
+            flashcard_file_id: Set(variant.flashcard_file_id),
+            size: Set(variant.size),
+            format: Set(variant.format),
+            content_type: Set(variant.content_type),
+            storage_key: Set(storage_key.clone()),
+            checksum: Set(BlobStoreFactory::checksum(&variant.data)),
+            width: Set(variant.width),
+            height: Set(variant.height),
+            created_on: Set(Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+        let inserted = FlashcardFileVariant::insert(new_variant)
+            .on_conflict(
+                OnConflict::columns([
+                    flashcard_file_variant::Column::FlashcardFileId,
+                    flashcard_file_variant::Column::Size,
+                    flashcard_file_variant::Column::Format,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec(db)
+            .await;
+        match inserted {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                let _ = self._blob_store.delete(&storage_key).await;
+                match err {
+                    DbErr::RecordNotInserted => Ok(()),
+                    err => Err(InfraError::database(err.to_string())),
+                }
+            }
         }
     }
 
     async fn create(&self, flashcard_file_req: FlashcardFileModel) -> Result<i32, InfraError> {
         let db = self._db_connection.as_ref();
+        let (storage_key, checksum) = self
+            .store_data(&flashcard_file_req.data, &flashcard_file_req.content_type)
+            .await?;
 
         let new_flashcard_file = flashcard_file::ActiveModel {
             name: Set(flashcard_file_req.name),
             file_name: Set(flashcard_file_req.file_name),
             content_type: Set(flashcard_file_req.content_type),
-            data: Set(flashcard_file_req.data),
+            data: Set(None),
+            storage_key: Set(Some(storage_key.clone())),
+            checksum: Set(Some(checksum)),
             created_by_id: Set(flashcard_file_req.created_by_id),
             updated_by_id: Set(flashcard_file_req.updated_by_id),
             created_on: Set(Utc::now().fixed_offset()),
@@ -64,11 +265,63 @@ impl FlashcardFileRepositoryTrait for FlashcardFileRepository {
             is_actived: Set(true),
             ..Default::default()
         };
-        FlashcardFile::insert(new_flashcard_file)
-            .exec(db)
+        match FlashcardFile::insert(new_flashcard_file).exec(db).await {
+            Ok(result) => Ok(result.last_insert_id),
+            Err(err) => {
+                let _ = self._blob_store.delete(&storage_key).await;
+                Err(InfraError::database(err.to_string()))
+            }
+        }
+    }
+
+    async fn create_without_commit(
+        &self,
+        flashcard_file_req: FlashcardFileModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<(i32, String), InfraError> {
+        let transact = match transaction
+            .as_ref()
+            .as_any()
+            .downcast_ref::<SeaOrmTransactionWrapper>()
+        {
+            Some(i) => i,
+            None => return Err(InfraError::database("Unable to cast the transaction")),
+        };
+
+        // The blob is written outside the transaction, so the caller deletes it on rollback
+        let (storage_key, checksum) = self
+            .store_data(&flashcard_file_req.data, &flashcard_file_req.content_type)
+            .await?;
+        let new_flashcard_file = flashcard_file::ActiveModel {
+            name: Set(flashcard_file_req.name),
+            file_name: Set(flashcard_file_req.file_name),
+            content_type: Set(flashcard_file_req.content_type),
+            data: Set(None),
+            storage_key: Set(Some(storage_key.clone())),
+            checksum: Set(Some(checksum)),
+            created_by_id: Set(flashcard_file_req.created_by_id),
+            updated_by_id: Set(flashcard_file_req.updated_by_id),
+            created_on: Set(Utc::now().fixed_offset()),
+            updated_on: Set(Utc::now().fixed_offset()),
+            is_actived: Set(true),
+            ..Default::default()
+        };
+        match FlashcardFile::insert(new_flashcard_file)
+            .exec(transact.txn.as_ref().unwrap())
             .await
-            .map(|result| result.last_insert_id)
-            .map_err(|err| InfraError::database(err.to_string()))
+        {
+            Ok(result) => Ok((result.last_insert_id, storage_key)),
+            Err(err) => {
+                let _ = self._blob_store.delete(&storage_key).await;
+                Err(InfraError::database(err.to_string()))
+            }
+        }
+    }
+
+    async fn delete_blobs(&self, storage_keys: &[String]) {
+        for storage_key in storage_keys {
+            let _ = self._blob_store.delete(storage_key).await;
+        }
     }
 
     async fn update(&self, flashcard_file_req: FlashcardFileModel) -> Result<bool, InfraError> {
@@ -82,38 +335,60 @@ impl FlashcardFileRepositoryTrait for FlashcardFileRepository {
                 InfraError::database(err.to_string().as_str())
             })?;
 
-        let mut flashcard_file: flashcard_file::ActiveModel = match flashcard_file_option {
-            Some(f) => f.into(),
+        let existing_file = match flashcard_file_option {
+            Some(f) => f,
             None => {
                 return Err(InfraError::not_found("FlashcardFile", flashcard_file_req.id.to_string()))
             }
         };
+        let previous_key = existing_file.storage_key.clone();
+        let mut flashcard_file: flashcard_file::ActiveModel = existing_file.into();
+        let (storage_key, checksum) = self
+            .store_data(&flashcard_file_req.data, &flashcard_file_req.content_type)
+            .await?;
 
         flashcard_file.updated_by_id = Set(flashcard_file_req.updated_by_id);
         flashcard_file.updated_on = Set(Utc::now().fixed_offset());
         flashcard_file.file_name = Set(flashcard_file_req.file_name);
         flashcard_file.content_type = Set(flashcard_file_req.content_type);
-        flashcard_file.data = Set(flashcard_file_req.data);
+        flashcard_file.data = Set(None);
+        flashcard_file.storage_key = Set(Some(storage_key.clone()));
+        flashcard_file.checksum = Set(Some(checksum));
         flashcard_file.name = Set(flashcard_file_req.name);
 
-        FlashcardFile::update(flashcard_file)
-            .exec(db)
-            .await
-            .map(|_| true)
-            .map_err(|err| {
-                eprintln!("Database Error Details:");
-                eprintln!("  Error: {:?}", err);
-                eprintln!("  Error String: {}", err.to_string());
-                InfraError::database(err.to_string())
-            })
+        if let Err(err) = FlashcardFile::update(flashcard_file).exec(db).await {
+            eprintln!("Database Error Details:");
+            eprintln!("  Error: {:?}", err);
+            eprintln!("  Error String: {}", err);
+            let _ = self._blob_store.delete(&storage_key).await;
+            return Err(InfraError::database(err.to_string()));
+        }
+
+        // The row no longer points at the old blob, so losing it here only leaks storage
+        if let Some(previous_key) = previous_key {
+            let _ = self._blob_store.delete(&previous_key).await;
+        }
+        self.delete_variants(flashcard_file_req.id).await?;
+        Ok(true)
     }
 
     async fn delete_by_id(&self, id: i32) -> Result<u64, InfraError> {
         let db = self._db_connection.as_ref();
-        FlashcardFile::delete_by_id(id)
+        let existing_file = FlashcardFile::find_by_id(id)
+            .one(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string()))?;
+        self.delete_variants(id).await?;
+
+        let deleted = FlashcardFile::delete_by_id(id)
             .exec(db)
             .await
             .map(|result| result.rows_affected)
-            .map_err(|err| InfraError::database(err.to_string()))
+            .map_err(|err| InfraError::database(err.to_string()))?;
+
+        if let Some(storage_key) = existing_file.and_then(|f| f.storage_key) {
+            let _ = self._blob_store.delete(&storage_key).await;
+        }
+        Ok(deleted)
     }
 }
@@ -10,6 +10,8 @@ use rex_game_entities::entities::{
 };
 use chrono::Utc;
 use rex_game_shared::domain::models::page_list_model::PageListModel;
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
+use rex_game_shared::infrastructure::database::SeaOrmTransactionWrapper;
 use rex_game_shared::InfraError;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, JoinType,
@@ -111,6 +113,39 @@ impl FlashcardRepositoryTrait for FlashcardRepository {
         }
     }
 
+    async fn get_by_flashcard_type_id(
+        &self,
+        flashcard_type_id: i32,
+    ) -> Result<Vec<FlashcardModel>, InfraError> {
+        let db = self._db_connection.as_ref();
+        let flashcards = Flashcard::find()
+            .join(
+                JoinType::InnerJoin,
+                FlashcardRelation::FlashcardTypeRelation.def(),
+            )
+            .filter(flashcard_type_relation::Column::FlashcardTypeId.eq(flashcard_type_id))
+            .order_by(flashcard::Column::Id, sea_orm::Order::Asc)
+            .all(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(flashcards
+            .into_iter()
+            .map(|f| FlashcardModel {
+                id: f.id,
+                name: f.name,
+                description: f.description,
+                sub_description: f.sub_description,
+                created_on: f.created_on.with_timezone(&Utc),
+                updated_on: f.updated_on.with_timezone(&Utc),
+                created_by_id: f.created_by_id,
+                updated_by_id: f.updated_by_id,
+                file_id: f.file_id,
+                is_actived: f.is_actived,
+            })
+            .collect())
+    }
+
     async fn create(&self, flashcard: FlashcardModel) -> Result<i32, InfraError> {
         let db = self._db_connection.as_ref();
 
@@ -133,6 +168,42 @@ impl FlashcardRepositoryTrait for FlashcardRepository {
         }
     }
 
+    async fn create_without_commit(
+        &self,
+        flashcard: FlashcardModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<i32, InfraError> {
+        let transact = match transaction
+            .as_ref()
+            .as_any()
+            .downcast_ref::<SeaOrmTransactionWrapper>()
+        {
+            Some(i) => i,
+            None => return Err(InfraError::database("Unable to cast the transaction")),
+        };
+
+        let new_flashcard = flashcard::ActiveModel {
+            name: Set(flashcard.name),
+            description: Set(flashcard.description),
+            sub_description: Set(flashcard.sub_description),
+            file_id: Set(flashcard.file_id),
+            created_by_id: Set(flashcard.created_by_id),
+            updated_by_id: Set(flashcard.updated_by_id),
+            created_on: Set(Utc::now().fixed_offset()),
+            updated_on: Set(Utc::now().fixed_offset()),
+            is_actived: Set(true),
+            ..Default::default()
+        };
+
+        match Flashcard::insert(new_flashcard)
+            .exec(transact.txn.as_ref().unwrap())
+            .await
+        {
+            Ok(result) => Ok(result.last_insert_id),
+            Err(err) => Err(InfraError::database(err.to_string().as_str())),
+        }
+    }
+
     async fn update(&self, flashcard_req: FlashcardModel) -> Result<bool, InfraError> {
         let db = self._db_connection.as_ref();
 
@@ -6,6 +6,8 @@ use rex_game_entities::entities::flashcard_type_relation::{
     self, Entity as FlashcardTypeRelation,
 };
 use chrono::Utc;
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
+use rex_game_shared::infrastructure::database::SeaOrmTransactionWrapper;
 use rex_game_shared::InfraError;
 use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
 use std::sync::Arc;
@@ -59,6 +61,45 @@ impl FlashcardTypeRelationRepositoryTrait for FlashcardTypeRelationRepository {
         }
     }
 
+    async fn create_without_commit(
+        &self,
+        flashcard_type_relations_req: Vec<FlashcardTypeRelationModel>,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<u64, InfraError> {
+        if flashcard_type_relations_req.is_empty() {
+            return Ok(0);
+        }
+
+        let transact = match transaction
+            .as_ref()
+            .as_any()
+            .downcast_ref::<SeaOrmTransactionWrapper>()
+        {
+            Some(i) => i,
+            None => return Err(InfraError::database("Unable to cast the transaction")),
+        };
+
+        let count = flashcard_type_relations_req.len() as u64;
+        let flashcard_type_relations =
+            flashcard_type_relations_req
+                .into_iter()
+                .map(|f| flashcard_type_relation::ActiveModel {
+                    flashcard_id: Set(f.flashcard_id),
+                    flashcard_type_id: Set(f.flashcard_type_id),
+                    created_by_id: Set(f.created_by_id),
+                    updated_by_id: Set(f.updated_by_id),
+                    created_on: Set(Utc::now().fixed_offset()),
+                    updated_on: Set(Utc::now().fixed_offset()),
+                    ..Default::default()
+                });
+
+        FlashcardTypeRelation::insert_many(flashcard_type_relations)
+            .exec(transact.txn.as_ref().unwrap())
+            .await
+            .map(|_| count)
+            .map_err(|err| InfraError::database(err.to_string().as_str()))
+    }
+
     async fn get_by_flashcard_id(
         &self,
         flashcard_id: i32,
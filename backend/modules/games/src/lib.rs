@@ -5,7 +5,8 @@ pub mod scoring;
 pub use flashcard::application::usecases::{
     flashcard_creation_dto::FlashcardCreationDto,
     flashcard_detail_dto::{FlashcardDetailDto, FlashcardGameTypeInfo},
-    flashcard_dto::FlashcardDto, flashcard_file_dto::FlashcardFileDto,
+    flashcard_dto::FlashcardDto, flashcard_export_dto::FlashcardExportDto,
+    flashcard_file_dto::FlashcardFileDto,
     flashcard_type_creation_dto::FlashcardTypeCreationDto, flashcard_type_dto::FlashcardTypeDto,
     flashcard_type_updation_dto::FlashcardTypeUpdationDto, flashcard_updation_dto::FlashcardUpdationDto,
     FlashcardTypeUseCase, FlashcardTypeUseCaseTrait, FlashcardUseCase, FlashcardUseCaseTrait,
@@ -18,9 +19,12 @@ pub use flashcard::infrastructure::repositories::{
 
 // Re-export scoring module items
 pub use scoring::{
-    AchievementCreationDto, AchievementDto, AchievementUpdationDto, AdminAchievementDto,
-    AdminGameSessionDto, AdminUserStatsDto, CompleteGameSessionDto, GameCompleteResponseDto,
-    GameProgressDto, GameSessionDto, GameTypeCreationDto, GameTypeDto, GameTypeUpdationDto,
-    LeaderboardEntryDto, SaveGameProgressDto, ScoringRepository, ScoringRepositoryTrait,
-    ScoringUseCase, ScoringUseCaseTrait, StartGameSessionDto, UserStatsDto,
+    AchievementBackfillCreationDto, AchievementBackfillDto, AchievementCreationDto, AchievementDto,
+    AchievementUpdationDto, AdminAchievementDto, AdminGameSessionDto, AdminUserStatsDto,
+    CompleteGameSessionDto, DealtCardDto, DueCardDto, GameAnswerResultDto, GameCompleteResponseDto,
+    GameProgressDto, GameSessionDto, GameSessionStartedDto, GameTypeCreationDto, GameTypeDto,
+    GameTypeUpdationDto, LeaderboardEntryDto, LeaderboardFilterDto, LeaderboardPositionDto,
+    LevelUpDto, SaveGameProgressDto, ScoringRepository, ScoringRepositoryTrait, ScoringUseCase,
+    ScoringUseCaseTrait, StartGameSessionDto, StreakDayDto, StreakDto, StreakReminderBatchDto,
+    StreakReminderDto, StreakSettingsDto, SubmitGameAnswerDto, UserStatsDto, XpAwardDto,
 };
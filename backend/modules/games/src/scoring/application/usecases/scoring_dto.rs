@@ -1,8 +1,10 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use crate::scoring::domain::rules::AchievementCriteria;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GameTypeDto {
     pub id: i32,
@@ -22,20 +24,41 @@ pub struct StartGameSessionDto {
     pub flashcard_type_id: Option<i32>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DealtCardDto {
+    pub position: i32,
+    pub flashcard_id: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameSessionStartedDto {
+    pub session_id: i32,
+    pub started_at: String,
+    pub cards: Vec<DealtCardDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct SubmitGameAnswerDto {
+    pub flashcard_id: i32,
+    #[validate(length(max = 255))]
+    pub answer: String,
+    /// Speed match only: whether the player judged `answer` to match the card.
+    pub is_match: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameAnswerResultDto {
+    pub flashcard_id: i32,
+    pub is_correct: bool,
+    pub points: i32,
+    pub combo: i32,
+    pub score: i32,
+    pub remaining_cards: i32,
+}
+
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct CompleteGameSessionDto {
     pub session_id: i32,
-    #[validate(range(min = 0))]
-    pub score: i32,
-    pub max_score: Option<i32>,
-    #[validate(range(min = 0))]
-    pub correct_answers: i32,
-    #[validate(range(min = 0))]
-    pub wrong_answers: i32,
-    #[validate(range(min = 0))]
-    pub combo_max: i32,
-    #[validate(range(min = 0))]
-    pub time_spent_seconds: i32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -67,6 +90,12 @@ pub struct UserStatsDto {
     pub average_accuracy: Decimal,
     pub current_streak: i32,
     pub best_streak: i32,
+    pub total_xp: i64,
+    pub level: i32,
+    /// Total XP at which the current level was reached
+    pub level_xp: i64,
+    /// Total XP needed for the next level, `None` at the top level
+    pub next_level_xp: Option<i64>,
     pub rank: Option<i32>,
 }
 
@@ -82,6 +111,75 @@ pub struct LeaderboardEntryDto {
     pub average_accuracy: Decimal,
 }
 
+/// Which leaderboard to read; everything left out means the all-time board over all games.
+#[derive(Debug, Default, Deserialize)]
+pub struct LeaderboardFilterDto {
+    /// `daily`, `weekly`, `monthly` or `all_time`
+    pub period: Option<String>,
+    pub game_type_code: Option<String>,
+    pub flashcard_type_id: Option<i32>,
+}
+
+/// A player's place on one leaderboard with the players ranked around them.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LeaderboardPositionDto {
+    pub period: String,
+    pub period_start: NaiveDate,
+    /// `None` until the player completes a game in this window
+    pub rank: Option<i32>,
+    pub entries: Vec<LeaderboardEntryDto>,
+}
+
+/// A player's daily streak, as of their local `today`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StreakDto {
+    pub current_streak: i32,
+    pub best_streak: i32,
+    pub freezes: i32,
+    pub max_freezes: i32,
+    pub freeze_price: i64,
+    /// Score still available to spend on freezes
+    pub available_points: i64,
+    pub today: NaiveDate,
+    /// Whether `today` has already been played
+    pub played_today: bool,
+    /// Whether missing `today` would end the streak
+    pub at_risk: bool,
+    pub utc_offset_minutes: i32,
+    pub reminders_enabled: bool,
+    pub recent_days: Vec<StreakDayDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StreakDayDto {
+    pub day: NaiveDate,
+    /// `played` or `frozen`
+    pub kind: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StreakSettingsDto {
+    /// Where the player's days start, in minutes east of UTC
+    pub utc_offset_minutes: i32,
+    pub reminders_enabled: bool,
+}
+
+/// A reminder claimed for a player whose streak is at risk.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StreakReminderDto {
+    pub user_id: i32,
+    pub user_name: String,
+    pub email: String,
+    pub current_streak: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StreakReminderBatchDto {
+    pub reminders: Vec<StreakReminderDto>,
+    /// Where the next batch starts, `None` once every player was checked
+    pub next_user_id: Option<i32>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AchievementDto {
     pub id: i32,
@@ -95,10 +193,30 @@ pub struct AchievementDto {
     pub unlocked_at: Option<String>,
 }
 
+/// XP earned by a completed session, per source.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct XpAwardDto {
+    pub session_xp: i32,
+    pub accuracy_xp: i32,
+    pub achievement_xp: i32,
+    pub total: i32,
+}
+
+/// A level reached by a completed session.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LevelUpDto {
+    pub level: i32,
+    /// Total XP at which the level is reached
+    pub level_xp: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GameCompleteResponseDto {
     pub session: GameSessionDto,
     pub new_achievements: Vec<AchievementDto>,
+    pub xp_earned: XpAwardDto,
+    /// Levels reached by this session, lowest first
+    pub level_ups: Vec<LevelUpDto>,
     pub updated_stats: UserStatsDto,
 }
 
@@ -120,8 +238,6 @@ pub struct GameProgressDto {
 pub struct SaveGameProgressDto {
     #[validate(length(min = 1, max = 50))]
     pub game_type_code: String,
-    #[validate(range(min = 1))]
-    pub current_level: i32,
     #[validate(range(min = 0))]
     pub total_score: i64,
 }
@@ -131,6 +247,18 @@ pub struct ResetGameProgressDto {
     pub game_type_code: String,
 }
 
+// Review DTOs
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DueCardDto {
+    pub flashcard_id: i32,
+    pub flashcard_name: Option<String>,
+    pub is_new: bool,
+    pub ease_factor: i32,
+    pub interval_days: i32,
+    pub lapses: i32,
+    pub due_at: String,
+}
+
 // ---- Admin DTOs ----
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
@@ -160,6 +288,7 @@ pub struct AdminAchievementDto {
     pub icon: Option<String>,
     pub points: i32,
     pub category: Option<String>,
+    pub criteria: Option<AchievementCriteria>,
     pub is_actived: bool,
     pub created_on: DateTime<Utc>,
     pub updated_on: DateTime<Utc>,
@@ -176,6 +305,9 @@ pub struct AchievementCreationDto {
     #[validate(range(min = 0))]
     pub points: i32,
     pub category: Option<String>,
+    /// Without criteria the achievement never unlocks
+    #[serde(default)]
+    pub criteria: Option<AchievementCriteria>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -186,6 +318,34 @@ pub struct AchievementUpdationDto {
     pub icon: Option<String>,
     pub points: Option<i32>,
     pub category: Option<String>,
+    pub criteria: Option<AchievementCriteria>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct AchievementBackfillCreationDto {
+    /// Replays every active achievement when missing
+    pub achievement_id: Option<i32>,
+    /// Only counts what would be unlocked
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AchievementBackfillDto {
+    pub id: i32,
+    pub achievement_id: Option<i32>,
+    pub is_dry_run: bool,
+    pub status: String,
+    pub total_users: i32,
+    pub processed_users: i32,
+    pub affected_users: i32,
+    pub unlocked_count: i32,
+    pub last_error: Option<String>,
+    pub created_by_id: Option<i32>,
+    pub started_on: Option<DateTime<Utc>>,
+    pub completed_on: Option<DateTime<Utc>>,
+    pub created_on: DateTime<Utc>,
+    pub updated_on: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -223,5 +383,7 @@ pub struct AdminUserStatsDto {
     pub average_accuracy: Decimal,
     pub current_streak: i32,
     pub best_streak: i32,
+    pub total_xp: i64,
+    pub level: i32,
     pub last_played_at: Option<String>,
 }
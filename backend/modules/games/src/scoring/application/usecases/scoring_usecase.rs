@@ -1,4 +1,11 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, Utc};
+use rex_game_shared::domain::transaction_manager_trait::{
+    TransactionManagerTrait, TransactionWrapperTrait,
+};
+use rex_game_shared::infrastructure::database::TransactionManager;
+use rex_game_shared::ApplicationError;
+use rust_decimal::prelude::ToPrimitive;
 use sea_orm::DbErr;
 use std::sync::Arc;
 
@@ -6,24 +13,60 @@ use crate::scoring::domain::repositories::ScoringRepositoryTrait;
 
 use rex_game_shared::domain::models::page_list_model::PageListModel;
 
-use crate::scoring::domain::models::GameTypeModel;
+use crate::scoring::domain::models::{
+    AchievementBackfillModel, AchievementBackfillStatuses, FlashcardReviewModel,
+    GameSessionAnswerModel, GameSessionCompletion, GameSessionModel, GameSessionStatuses,
+    GameTypeModel, LeaderboardEntry, UserStatsModel,
+};
+use crate::scoring::domain::rules::{
+    backfill_unlocks, check_open_session, leaderboard_window_offset, scoring_rule_for,
+    AchievementCalendar, AchievementCriteria, AnswerTiming, GameScoringRule, LeaderboardBucket,
+    LeaderboardPeriod, LeaderboardScope, LevelCurve, ProgressionSettings, ReviewGrade, ReviewState,
+    SessionClosed, StreakDayKinds, StreakPolicy, XpAward, MAX_UTC_OFFSET_MINUTES,
+    MIN_UTC_OFFSET_MINUTES,
+};
 
 use super::scoring_dto::{
-    AchievementCreationDto, AchievementDto, AchievementUpdationDto, AdminAchievementDto,
-    AdminGameSessionDto, AdminUserStatsDto, CompleteGameSessionDto, GameCompleteResponseDto,
-    GameProgressDto, GameSessionDto, GameTypeCreationDto, GameTypeDto, GameTypeUpdationDto,
-    LeaderboardEntryDto, SaveGameProgressDto, StartGameSessionDto, UserStatsDto,
+    AchievementBackfillCreationDto, AchievementBackfillDto, AchievementCreationDto, AchievementDto,
+    AchievementUpdationDto, AdminAchievementDto, AdminGameSessionDto, AdminUserStatsDto,
+    CompleteGameSessionDto, DealtCardDto, DueCardDto, GameAnswerResultDto, GameCompleteResponseDto,
+    GameProgressDto, GameSessionDto, GameSessionStartedDto, GameTypeCreationDto, GameTypeDto,
+    GameTypeUpdationDto, LeaderboardEntryDto, LeaderboardFilterDto, LeaderboardPositionDto,
+    LevelUpDto, SaveGameProgressDto, StartGameSessionDto, StreakDayDto, StreakDto,
+    StreakReminderBatchDto, StreakReminderDto, StreakSettingsDto, SubmitGameAnswerDto,
+    UserStatsDto, XpAwardDto,
 };
 use super::scoring_usecase_trait::ScoringUseCaseTrait;
 
+/// Upper bound on the number of cards in one review deck.
+const MAX_REVIEW_DECK_SIZE: u64 = 100;
+/// Upper bound on the players shown on each side in the "around me" view.
+const MAX_LEADERBOARD_RADIUS: u64 = 25;
+/// Days of the streak ledger shown with a player's streak.
+const STREAK_RECENT_DAYS: i64 = 28;
+
 #[derive(Clone)]
 pub struct ScoringUseCase {
     repository: Arc<dyn ScoringRepositoryTrait>,
+    transaction_manager: TransactionManager,
+    /// Where days, weeks and months of the leaderboards begin
+    leaderboard_offset: FixedOffset,
+    streak_policy: StreakPolicy,
 }
 
 impl ScoringUseCase {
-    pub fn new(repository: Arc<dyn ScoringRepositoryTrait>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<dyn ScoringRepositoryTrait>,
+        transaction_manager: TransactionManager,
+        leaderboard_offset: FixedOffset,
+        streak_policy: StreakPolicy,
+    ) -> Self {
+        Self {
+            repository,
+            transaction_manager,
+            leaderboard_offset,
+            streak_policy,
+        }
     }
 
     fn map_game_type_dto(gt: GameTypeModel) -> GameTypeDto {
@@ -39,47 +82,553 @@ impl ScoringUseCase {
         }
     }
 
+    fn get_scoring_rule(
+        session: &GameSessionModel,
+    ) -> Result<Box<dyn GameScoringRule>, ApplicationError> {
+        let code = session.game_type_code.as_deref().unwrap_or_default();
+        scoring_rule_for(code).ok_or(ApplicationError::business_rule(format!(
+            "No scoring rule for game type: {}",
+            code
+        )))
+    }
+
+    fn map_leaderboard_entry_dto(entry: LeaderboardEntry) -> LeaderboardEntryDto {
+        LeaderboardEntryDto {
+            rank: entry.rank,
+            user_id: entry.user_id,
+            user_name: entry.user_name,
+            user_display_name: entry.user_display_name,
+            total_score: entry.total_score,
+            total_games_played: entry.total_games_played,
+            best_score: entry.best_score,
+            average_accuracy: entry.average_accuracy,
+        }
+    }
+
+    /// The current window of the leaderboard a filter asks for.
+    async fn get_leaderboard_bucket(
+        &self,
+        filter: &LeaderboardFilterDto,
+    ) -> Result<LeaderboardBucket, ApplicationError> {
+        let period = match filter.period.as_deref() {
+            None => LeaderboardPeriod::AllTime,
+            Some(value) => LeaderboardPeriod::parse(value).ok_or(
+                ApplicationError::invalid_input(format!("Invalid leaderboard period: {}", value)),
+            )?,
+        };
+
+        let game_type_id = match filter.game_type_code.as_deref() {
+            None => None,
+            Some(code) => Some(
+                self.repository
+                    .get_game_type_by_code(code)
+                    .await?
+                    .ok_or(ApplicationError::not_found("GameType", code))?
+                    .id,
+            ),
+        };
+
+        let scope = LeaderboardScope::new(game_type_id, filter.flashcard_type_id);
+        Ok(LeaderboardBucket::containing(
+            period,
+            scope,
+            Utc::now(),
+            self.leaderboard_offset,
+        ))
+    }
+
+    /// Counts activity at `at` towards the player's streak, on the caller's transaction.
+    async fn record_streak_activity_with_transaction(
+        &self,
+        user_id: i32,
+        at: DateTime<Utc>,
+        transaction: &dyn TransactionWrapperTrait,
+    ) -> Result<(), DbErr> {
+        let Some(stats) = self
+            .repository
+            .lock_user_stats_without_commit(user_id, Box::new(transaction))
+            .await?
+        else {
+            return Ok(());
+        };
+
+        let day = stats.local_day(at);
+        if let Some(progress) = stats
+            .streak_state()
+            .record_activity(day, &self.streak_policy)
+        {
+            self.repository
+                .save_streak_progress_without_commit(user_id, &progress, Box::new(transaction))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Counts activity at `at` towards the player's streak on its own transaction. Days
+    /// that are already counted return before taking the lock.
+    async fn record_streak_activity(
+        &self,
+        user_id: i32,
+        at: DateTime<Utc>,
+    ) -> Result<(), ApplicationError> {
+        let stats = self.repository.get_user_stats(user_id).await?;
+        if stats.is_some_and(|s| s.last_streak_day >= Some(s.local_day(at))) {
+            return Ok(());
+        }
+
+        let transaction = self.transaction_manager.begin().await?;
+        if let Err(err) = self
+            .record_streak_activity_with_transaction(user_id, at, &transaction)
+            .await
+        {
+            self.transaction_manager.rollback(transaction).await?;
+            return Err(err.into());
+        }
+        self.transaction_manager.commit(transaction).await?;
+        Ok(())
+    }
+
+    fn map_streak_dto(&self, stats: UserStatsModel, recent_days: Vec<StreakDayDto>) -> StreakDto {
+        let today = stats.local_day(Utc::now());
+        let state = stats.streak_state();
+        StreakDto {
+            current_streak: state.current_on(today),
+            best_streak: state.best_streak,
+            freezes: state.freezes,
+            max_freezes: self.streak_policy.max_freezes,
+            freeze_price: self.streak_policy.freeze_price,
+            available_points: stats.available_points(),
+            today,
+            played_today: state.last_day == Some(today),
+            at_risk: state.is_at_risk(today),
+            utc_offset_minutes: stats.utc_offset_minutes,
+            reminders_enabled: stats.streak_reminders_enabled,
+            recent_days,
+        }
+    }
+
+    async fn get_streak_stats(&self, user_id: i32) -> Result<UserStatsModel, ApplicationError> {
+        self.repository.ensure_user_stats_exists(user_id).await?;
+        self.repository
+            .get_user_stats(user_id)
+            .await?
+            .ok_or(ApplicationError::not_found("UserStats", user_id.to_string()))
+    }
+
+    /// The stored progression settings, or the defaults when there are none. Settings
+    /// are validated on save, so ones that no longer parse also fall back to the defaults.
+    async fn get_progression_settings(&self) -> Result<ProgressionSettings, DbErr> {
+        let value = self.repository.get_progression_settings().await?;
+        Ok(value
+            .and_then(|value| serde_json::from_str(&value).ok())
+            .unwrap_or_default())
+    }
+
+    fn map_user_stats_dto(
+        stats: UserStatsModel,
+        curve: &LevelCurve,
+        rank: Option<i32>,
+    ) -> UserStatsDto {
+        let progress = curve.progress(stats.total_xp);
+        UserStatsDto {
+            current_streak: stats.current_streak_at(Utc::now()),
+            user_id: stats.user_id,
+            user_name: stats.user_name,
+            user_display_name: stats.user_display_name,
+            total_score: stats.total_score,
+            total_games_played: stats.total_games_played,
+            total_time_played_seconds: stats.total_time_played_seconds,
+            best_score: stats.best_score,
+            best_combo: stats.best_combo,
+            average_accuracy: stats.average_accuracy,
+            best_streak: stats.best_streak,
+            total_xp: progress.total_xp,
+            level: progress.level,
+            level_xp: progress.level_xp,
+            next_level_xp: progress.next_level_xp,
+            rank,
+        }
+    }
+
+    fn session_closed_error(session_id: i32, closed: SessionClosed) -> ApplicationError {
+        match closed {
+            SessionClosed::NotOwned => {
+                ApplicationError::not_found("Game session", session_id.to_string())
+            }
+            SessionClosed::Finished(status) => ApplicationError::conflict(format!(
+                "Game session {} is already {}",
+                session_id,
+                status.as_str()
+            )),
+            SessionClosed::UnknownStatus => ApplicationError::invalid_state(format!(
+                "Game session {} has an unknown status",
+                session_id
+            )),
+            SessionClosed::Expired => {
+                ApplicationError::invalid_state(format!("Game session {} has expired", session_id))
+            }
+        }
+    }
+
+    /// Loads a session that belongs to `user_id` and is still accepting answers.
+    /// Sessions past `MAX_SESSION_DURATION_MINUTES` are moved to expired.
+    async fn get_open_game_session(
+        &self,
+        user_id: i32,
+        session_id: i32,
+    ) -> Result<GameSessionModel, ApplicationError> {
+        let session = self
+            .repository
+            .get_game_session(session_id)
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Game session", session_id.to_string()))?;
+
+        if let Err(closed) = check_open_session(&session, user_id, Utc::now()) {
+            if closed == SessionClosed::Expired {
+                self.repository
+                    .update_game_session_status(
+                        session_id,
+                        user_id,
+                        GameSessionStatuses::Started as i32,
+                        GameSessionStatuses::Expired as i32,
+                    )
+                    .await?;
+            }
+            return Err(Self::session_closed_error(session_id, closed));
+        }
+
+        Ok(session)
+    }
+
+    /// Same as `get_open_game_session`, with the session row locked on `transaction`
+    /// so that an answer cannot land while the session is being completed.
+    async fn lock_open_game_session(
+        &self,
+        user_id: i32,
+        session_id: i32,
+        transaction: &dyn TransactionWrapperTrait,
+    ) -> Result<GameSessionModel, ApplicationError> {
+        let session = self
+            .repository
+            .lock_game_session_without_commit(session_id, Box::new(transaction))
+            .await?
+            .ok_or_else(|| ApplicationError::not_found("Game session", session_id.to_string()))?;
+
+        check_open_session(&session, user_id, Utc::now())
+            .map_err(|closed| Self::session_closed_error(session_id, closed))?;
+        Ok(session)
+    }
+
+    /// Session update, stats, leaderboards, achievements and XP for a completion, all on
+    /// one transaction. Also returns the levels the session reached.
+    async fn complete_game_session_with_transaction(
+        &self,
+        user_id: i32,
+        session_id: i32,
+        settings: &ProgressionSettings,
+        transaction: &dyn TransactionWrapperTrait,
+    ) -> Result<(GameSessionModel, Vec<AchievementDto>, XpAward, Vec<i32>), ApplicationError> {
+        let session = self
+            .lock_open_game_session(user_id, session_id, transaction)
+            .await?;
+        let rule = Self::get_scoring_rule(&session)?;
+        let answers = self
+            .repository
+            .get_game_session_answers_with_transaction(session.id, Box::new(transaction))
+            .await?;
+
+        // Everything is derived from the answer log, nothing from the client
+        let answered: Vec<&GameSessionAnswerModel> =
+            answers.iter().filter(|a| a.answered_at.is_some()).collect();
+        let score: i32 = answered.iter().map(|a| a.points).sum();
+        let correct_answers = answered
+            .iter()
+            .filter(|a| a.is_correct == Some(true))
+            .count() as i32;
+        let wrong_answers = answered.len() as i32 - correct_answers;
+        let combo_max = answered.iter().map(|a| a.combo).max().unwrap_or(0);
+        let mut positions: Vec<i32> = answers.iter().map(|a| a.position).collect();
+        positions.dedup();
+        let max_score = rule.max_score(positions.len() as i32);
+        let time_spent_seconds = (Utc::now() - session.started_at).num_seconds() as i32;
+
+        let completion = GameSessionCompletion {
+            session_id: session.id,
+            user_id,
+            score,
+            max_score: Some(max_score),
+            correct_answers,
+            wrong_answers,
+            combo_max,
+            time_spent_seconds,
+        };
+        let completed = self
+            .repository
+            .complete_game_session_without_commit(&completion, Box::new(transaction))
+            .await?
+            .ok_or(ApplicationError::conflict(format!(
+                "Game session {} is already completed",
+                session.id
+            )))?;
+
+        self.repository
+            .update_user_stats_without_commit(user_id, &completed, Box::new(transaction))
+            .await?;
+        self.record_streak_activity_with_transaction(
+            user_id,
+            completed.completed_at.unwrap_or_else(Utc::now),
+            transaction,
+        )
+        .await?;
+
+        let buckets = LeaderboardBucket::for_session(
+            completed.game_type_id,
+            completed.flashcard_type_id,
+            completed.completed_at.unwrap_or_else(Utc::now),
+            self.leaderboard_offset,
+        );
+        self.repository
+            .add_leaderboard_score_without_commit(
+                user_id,
+                &buckets,
+                &completed,
+                Box::new(transaction),
+            )
+            .await?;
+
+        let new_achievements = self
+            .check_and_unlock_achievements(user_id, &completed, transaction)
+            .await?;
+
+        let award = settings.session_award(
+            completed.game_type_code.as_deref().unwrap_or_default(),
+            completed.cards_played,
+            completed.accuracy.and_then(|a| a.to_f64()).unwrap_or(0.0),
+            new_achievements.iter().map(|a| a.points).sum(),
+        );
+        let total_xp = self
+            .repository
+            .add_xp_without_commit(user_id, Some(completed.id), &award, Box::new(transaction))
+            .await?;
+        let level_ups = settings
+            .curve
+            .levels_gained(total_xp - award.total() as i64, total_xp);
+
+        Ok((completed, new_achievements, award, level_ups))
+    }
+
+    /// Scores and records one answer with the session locked on `transaction`. Also
+    /// returns the review grade when this is the first attempt at the card.
+    async fn record_game_answer_with_transaction(
+        &self,
+        user_id: i32,
+        session_id: i32,
+        dto: SubmitGameAnswerDto,
+        now: DateTime<Utc>,
+        transaction: &dyn TransactionWrapperTrait,
+    ) -> Result<(GameAnswerResultDto, Option<ReviewGrade>), ApplicationError> {
+        let session = self
+            .lock_open_game_session(user_id, session_id, transaction)
+            .await?;
+        let rule = Self::get_scoring_rule(&session)?;
+        let answers = self
+            .repository
+            .get_game_session_answers_with_transaction(session_id, Box::new(transaction))
+            .await?;
+
+        let dealt_card = answers
+            .iter()
+            .find(|a| a.flashcard_id == dto.flashcard_id && a.answered_at.is_none())
+            .cloned()
+            .ok_or(ApplicationError::invalid_input(format!(
+                "Flashcard {} is not an open card in this round",
+                dto.flashcard_id
+            )))?;
+
+        let last_answer = answers
+            .iter()
+            .filter(|a| a.answered_at.is_some())
+            .max_by_key(|a| a.answered_at);
+
+        let previous_at = last_answer
+            .and_then(|a| a.answered_at)
+            .unwrap_or(session.started_at);
+        let timing = AnswerTiming {
+            response_ms: (now - previous_at).num_milliseconds(),
+            elapsed_ms: (now - session.started_at).num_milliseconds(),
+        };
+
+        let combo_before = last_answer.map(|a| a.combo).unwrap_or(0);
+        let expected = dealt_card.flashcard_name.clone().unwrap_or_default();
+        let is_correct = rule.is_correct(&expected, &dto.answer, dto.is_match);
+        let points = rule.points(is_correct, combo_before, timing);
+        let combo = if is_correct { combo_before + 1 } else { 0 };
+
+        let answered = GameSessionAnswerModel {
+            answer: Some(dto.answer),
+            is_correct: Some(is_correct),
+            points,
+            combo,
+            response_ms: Some(timing.response_ms.clamp(0, i32::MAX as i64) as i32),
+            answered_at: Some(now),
+            ..dealt_card
+        };
+
+        // Games that allow retries log the miss separately and keep the card open
+        let card_closed = is_correct || !rule.allows_retry();
+        if card_closed {
+            let recorded = self
+                .repository
+                .update_game_session_answer_without_commit(&answered, Box::new(transaction))
+                .await?;
+            if !recorded {
+                return Err(ApplicationError::conflict(format!(
+                    "Flashcard {} was already answered in this round",
+                    dto.flashcard_id
+                )));
+            }
+        } else {
+            self.repository
+                .create_game_session_answer_without_commit(&answered, Box::new(transaction))
+                .await?;
+        }
+
+        // Only the first attempt at a card in a round counts as a review
+        let first_attempt = !answers
+            .iter()
+            .any(|a| a.flashcard_id == dto.flashcard_id && a.answered_at.is_some());
+        let review_grade =
+            first_attempt.then(|| ReviewGrade::from_answer(is_correct, timing.response_ms));
+
+        let score = answers.iter().map(|a| a.points).sum::<i32>() + points;
+        let remaining_cards = answers.iter().filter(|a| a.answered_at.is_none()).count() as i32
+            - if card_closed { 1 } else { 0 };
+
+        let result = GameAnswerResultDto {
+            flashcard_id: dto.flashcard_id,
+            is_correct,
+            points,
+            combo,
+            score,
+            remaining_cards,
+        };
+        Ok((result, review_grade))
+    }
+
+    /// Moves the user's review schedule for a card forward by one graded answer.
+    async fn record_flashcard_review(
+        &self,
+        user_id: i32,
+        flashcard_id: i32,
+        grade: ReviewGrade,
+        reviewed_at: chrono::DateTime<Utc>,
+    ) -> Result<(), DbErr> {
+        let current = self
+            .repository
+            .get_flashcard_review(user_id, flashcard_id)
+            .await?;
+        let state = current
+            .as_ref()
+            .map(|r| ReviewState {
+                ease_factor: r.ease_factor,
+                interval_days: r.interval_days,
+                repetitions: r.repetitions,
+                lapses: r.lapses,
+            })
+            .unwrap_or_default()
+            .review(grade);
+
+        self.repository
+            .save_flashcard_review(&FlashcardReviewModel {
+                id: current.map(|r| r.id).unwrap_or_default(),
+                user_id,
+                flashcard_id,
+                flashcard_name: None,
+                ease_factor: state.ease_factor,
+                interval_days: state.interval_days,
+                repetitions: state.repetitions,
+                lapses: state.lapses,
+                due_at: state.due_at(reviewed_at),
+                last_reviewed_at: Some(reviewed_at),
+            })
+            .await
+    }
+
+    /// The player's UTC offset and the days their streak freezes covered
+    async fn get_achievement_calendar_with_transaction(
+        &self,
+        user_id: i32,
+        transaction: &dyn TransactionWrapperTrait,
+    ) -> Result<AchievementCalendar, DbErr> {
+        let stats = self
+            .repository
+            .get_user_stats_with_transaction(user_id, Box::new(transaction))
+            .await?;
+        let streak_days = self
+            .repository
+            .get_user_streak_days_with_transaction(user_id, NaiveDate::MIN, Box::new(transaction))
+            .await?;
+        Ok(AchievementCalendar {
+            utc_offset_minutes: stats.map(|s| s.utc_offset_minutes).unwrap_or_default(),
+            frozen_days: streak_days
+                .into_iter()
+                .filter(|d| StreakDayKinds::parse(&d.kind) == Some(StreakDayKinds::Frozen))
+                .map(|d| d.day)
+                .collect(),
+        })
+    }
+
+    /// Unlocks the achievements whose criteria hold now that `session` is completed.
     async fn check_and_unlock_achievements(
         &self,
         user_id: i32,
-        session_score: i32,
-        session_accuracy: Option<rust_decimal::Decimal>,
-        session_time: i32,
-        combo_max: i32,
+        session: &GameSessionModel,
+        transaction: &dyn TransactionWrapperTrait,
     ) -> Result<Vec<AchievementDto>, DbErr> {
         let mut new_achievements = Vec::new();
-        let stats = self.repository.get_user_stats(user_id).await?;
         let achievements = self.repository.get_achievements().await?;
+        let mut history: Option<(Vec<GameSessionModel>, AchievementCalendar)> = None;
+        let now = Utc::now();
 
         for achievement in achievements {
+            let Some(criteria) = achievement.criteria.as_ref() else {
+                continue;
+            };
+
             let already_unlocked = self
                 .repository
-                .check_achievement_unlocked(user_id, &achievement.code)
+                .check_achievement_unlocked(user_id, &achievement.code, Box::new(transaction))
                 .await?;
 
             if already_unlocked {
                 continue;
             }
 
-            let should_unlock = match achievement.code.as_str() {
-                "first_game" => stats.as_ref().map(|s| s.total_games_played >= 1).unwrap_or(false),
-                "perfect_score" => session_accuracy
-                    .map(|acc| acc >= rust_decimal::Decimal::from(100))
-                    .unwrap_or(false),
-                "speed_demon" => session_time <= 30 && session_score > 0,
-                "combo_master" => combo_max >= 10,
-                "streak_7" => stats.as_ref().map(|s| s.current_streak >= 7).unwrap_or(false),
-                "games_10" => stats.as_ref().map(|s| s.total_games_played >= 10).unwrap_or(false),
-                "games_50" => stats.as_ref().map(|s| s.total_games_played >= 50).unwrap_or(false),
-                "games_100" => stats.as_ref().map(|s| s.total_games_played >= 100).unwrap_or(false),
-                "score_1000" => stats.as_ref().map(|s| s.total_score >= 1000).unwrap_or(false),
-                "score_10000" => stats.as_ref().map(|s| s.total_score >= 10000).unwrap_or(false),
-                _ => false,
+            // Past sessions are only loaded once an achievement needs them
+            if history.is_none() && criteria.needs_history() {
+                let sessions = self
+                    .repository
+                    .get_completed_game_sessions_with_transaction(user_id, Box::new(transaction))
+                    .await?;
+                let calendar = self
+                    .get_achievement_calendar_with_transaction(user_id, transaction)
+                    .await?;
+                history = Some((sessions, calendar));
+            }
+
+            let should_unlock = match &history {
+                Some((sessions, calendar)) => criteria.is_met(session, sessions, calendar, now),
+                None => criteria.is_met(session, &[], &AchievementCalendar::default(), now),
             };
 
             if should_unlock {
                 self.repository
-                    .unlock_achievement(user_id, achievement.id)
+                    .unlock_achievement_without_commit(
+                        user_id,
+                        achievement.id,
+                        now,
+                        Box::new(transaction),
+                    )
                     .await?;
 
                 new_achievements.push(AchievementDto {
@@ -91,13 +640,126 @@ impl ScoringUseCase {
                     points: achievement.points,
                     category: achievement.category,
                     unlocked: true,
-                    unlocked_at: Some(chrono::Utc::now().to_rfc3339()),
+                    unlocked_at: Some(now.to_rfc3339()),
                 });
             }
         }
 
         Ok(new_achievements)
     }
+
+    fn map_achievement_backfill_dto(backfill: AchievementBackfillModel) -> AchievementBackfillDto {
+        AchievementBackfillDto {
+            id: backfill.id,
+            achievement_id: backfill.achievement_id,
+            is_dry_run: backfill.is_dry_run,
+            status: backfill.status.as_str().to_string(),
+            total_users: backfill.total_users,
+            processed_users: backfill.processed_users,
+            affected_users: backfill.affected_users,
+            unlocked_count: backfill.unlocked_count,
+            last_error: backfill.last_error,
+            created_by_id: backfill.created_by_id,
+            started_on: backfill.started_on,
+            completed_on: backfill.completed_on,
+            created_on: backfill.created_on,
+            updated_on: backfill.updated_on,
+        }
+    }
+
+    /// Replays the history of the next batch of users and advances the backfill's cursor.
+    /// Each achievement unlocks at the completion that first met its criteria.
+    async fn run_achievement_backfill_batch(
+        &self,
+        mut backfill: AchievementBackfillModel,
+        batch_size: u64,
+        transaction: &dyn TransactionWrapperTrait,
+    ) -> Result<AchievementBackfillModel, ApplicationError> {
+        backfill.start(Utc::now());
+
+        let achievements = match backfill.achievement_id {
+            Some(id) => self
+                .repository
+                .get_achievement_by_id(id)
+                .await?
+                .into_iter()
+                .collect(),
+            None => self.repository.get_achievements().await?,
+        };
+        let achievements: Vec<(i32, i32, AchievementCriteria)> = achievements
+            .into_iter()
+            .filter(|a| a.is_actived)
+            .filter_map(|a| a.criteria.map(|criteria| (a.id, a.points, criteria)))
+            .collect();
+
+        let user_ids = if achievements.is_empty() {
+            Vec::new()
+        } else {
+            self.repository
+                .get_user_ids_with_completed_sessions_with_transaction(
+                    backfill.last_user_id,
+                    batch_size,
+                    Box::new(transaction),
+                )
+                .await?
+        };
+        let is_last_batch = (user_ids.len() as u64) < batch_size;
+
+        for user_id in user_ids {
+            let unlocked = self
+                .repository
+                .get_unlocked_achievement_ids_with_transaction(user_id, Box::new(transaction))
+                .await?;
+            let pending: Vec<&(i32, i32, AchievementCriteria)> = achievements
+                .iter()
+                .filter(|(id, _, _)| !unlocked.contains(id))
+                .collect();
+
+            let unlocks = if pending.is_empty() {
+                Vec::new()
+            } else {
+                let history = self
+                    .repository
+                    .get_completed_game_sessions_with_transaction(user_id, Box::new(transaction))
+                    .await?;
+                let calendar = self
+                    .get_achievement_calendar_with_transaction(user_id, transaction)
+                    .await?;
+                backfill_unlocks(&pending, &history, &calendar)
+            };
+
+            let writes = backfill.record_user(user_id, &unlocks);
+            for unlock in writes {
+                self.repository
+                    .unlock_achievement_without_commit(
+                        user_id,
+                        unlock.achievement_id,
+                        unlock.unlocked_at,
+                        Box::new(transaction),
+                    )
+                    .await?;
+            }
+
+            // Late unlocks earn their points as XP like any other unlock
+            let achievement_xp: i32 = writes.iter().map(|unlock| unlock.points).sum();
+            if achievement_xp > 0 {
+                let award = XpAward {
+                    achievement_xp,
+                    ..Default::default()
+                };
+                self.repository
+                    .add_xp_without_commit(user_id, None, &award, Box::new(transaction))
+                    .await?;
+            }
+        }
+
+        backfill.finish_batch(is_last_batch, Utc::now());
+
+        self.repository
+            .update_achievement_backfill_without_commit(&backfill, Box::new(transaction))
+            .await?;
+        Ok(backfill)
+    }
 }
 
 #[async_trait]
@@ -115,7 +777,7 @@ impl ScoringUseCaseTrait for ScoringUseCase {
         &self,
         user_id: i32,
         dto: StartGameSessionDto,
-    ) -> Result<i32, DbErr> {
+    ) -> Result<GameSessionStartedDto, DbErr> {
         let game_type = self
             .repository
             .get_game_type_by_code(&dto.game_type_code)
@@ -125,6 +787,11 @@ impl ScoringUseCaseTrait for ScoringUseCase {
                 dto.game_type_code
             )))?;
 
+        let rule = scoring_rule_for(&game_type.code).ok_or(DbErr::Custom(format!(
+            "No scoring rule for game type: {}",
+            game_type.code
+        )))?;
+
         self.repository.ensure_user_stats_exists(user_id).await?;
 
         let session_id = self
@@ -132,68 +799,107 @@ impl ScoringUseCaseTrait for ScoringUseCase {
             .create_game_session(user_id, game_type.id, dto.flashcard_type_id)
             .await?;
 
-        Ok(session_id)
+        // Deal the round server-side so answers can only be given for these cards
+        let dealt = self
+            .repository
+            .deal_game_session_cards(
+                session_id,
+                game_type.id,
+                dto.flashcard_type_id,
+                rule.round_size(),
+            )
+            .await?;
+
+        if dealt.is_empty() {
+            self.repository.delete_game_session(session_id).await?;
+            return Err(DbErr::Custom(format!(
+                "No flashcards available for game type: {}",
+                game_type.code
+            )));
+        }
+
+        let session = self
+            .repository
+            .get_game_session(session_id)
+            .await?
+            .ok_or(DbErr::Custom("Session not found".to_string()))?;
+
+        Ok(GameSessionStartedDto {
+            session_id,
+            started_at: session.started_at.to_rfc3339(),
+            cards: dealt
+                .into_iter()
+                .map(|card| DealtCardDto {
+                    position: card.position,
+                    flashcard_id: card.flashcard_id,
+                })
+                .collect(),
+        })
     }
 
-    async fn complete_game_session(
+    async fn submit_game_answer(
         &self,
         user_id: i32,
-        dto: CompleteGameSessionDto,
-    ) -> Result<GameCompleteResponseDto, DbErr> {
-        // Complete the session
-        self.repository
-            .complete_game_session(
-                dto.session_id,
-                dto.score,
-                dto.max_score,
-                dto.correct_answers,
-                dto.wrong_answers,
-                dto.combo_max,
-                dto.time_spent_seconds,
-            )
-            .await?;
+        session_id: i32,
+        dto: SubmitGameAnswerDto,
+    ) -> Result<GameAnswerResultDto, ApplicationError> {
+        // Moves a session that ran out of time to expired
+        self.get_open_game_session(user_id, session_id).await?;
+
+        let now = Utc::now();
+        let flashcard_id = dto.flashcard_id;
+        let transaction = self.transaction_manager.begin().await?;
+        let (result, review_grade) = match self
+            .record_game_answer_with_transaction(user_id, session_id, dto, now, &transaction)
+            .await
+        {
+            Ok(recorded) => recorded,
+            Err(err) => {
+                self.transaction_manager.rollback(transaction).await?;
+                return Err(err);
+            }
+        };
+        self.transaction_manager.commit(transaction).await?;
 
-        // Get completed session
-        let sessions = self.repository.get_user_game_sessions(user_id, 1, 1).await?;
-        let session = sessions.into_iter().find(|s| s.id == dto.session_id);
+        if let Some(grade) = review_grade {
+            self.record_flashcard_review(user_id, flashcard_id, grade, now)
+                .await?;
+        }
 
-        let session_model = session.ok_or(DbErr::Custom("Session not found".to_string()))?;
+        // Practising counts towards the streak whether or not the round is finished
+        self.record_streak_activity(user_id, now).await?;
 
-        // Update user stats
-        self.repository
-            .update_user_stats(user_id, &session_model)
-            .await?;
+        Ok(result)
+    }
 
-        // Check achievements
-        let new_achievements = self
-            .check_and_unlock_achievements(
-                user_id,
-                dto.score,
-                session_model.accuracy,
-                dto.time_spent_seconds,
-                dto.combo_max,
-            )
-            .await?;
+    async fn complete_game_session(
+        &self,
+        user_id: i32,
+        dto: CompleteGameSessionDto,
+    ) -> Result<GameCompleteResponseDto, ApplicationError> {
+        let session = self.get_open_game_session(user_id, dto.session_id).await?;
+        let settings = self.get_progression_settings().await?;
+
+        let transaction = self.transaction_manager.begin().await?;
+        let (session_model, new_achievements, award, level_ups) = match self
+            .complete_game_session_with_transaction(user_id, session.id, &settings, &transaction)
+            .await
+        {
+            Ok(result) => result,
+            Err(err) => {
+                self.transaction_manager.rollback(transaction).await?;
+                return Err(err);
+            }
+        };
+        self.transaction_manager.commit(transaction).await?;
 
         // Get updated stats
         let stats = self.repository.get_user_stats(user_id).await?;
         let rank = self.repository.get_user_rank(user_id).await?;
 
+        let start = settings.curve.progress(0);
         let stats_dto = stats
-            .map(|s| UserStatsDto {
-                user_id: s.user_id,
-                user_name: s.user_name,
-                user_display_name: s.user_display_name,
-                total_score: s.total_score,
-                total_games_played: s.total_games_played,
-                total_time_played_seconds: s.total_time_played_seconds,
-                best_score: s.best_score,
-                best_combo: s.best_combo,
-                average_accuracy: s.average_accuracy,
-                current_streak: s.current_streak,
-                best_streak: s.best_streak,
-                rank,
-            })
+            .map(|s| Self::map_user_stats_dto(s, &settings.curve, rank))
             .unwrap_or(UserStatsDto {
                 user_id,
                 user_name: None,
@@ -206,6 +912,10 @@ impl ScoringUseCaseTrait for ScoringUseCase {
                 average_accuracy: rust_decimal::Decimal::ZERO,
                 current_streak: 0,
                 best_streak: 0,
+                total_xp: start.total_xp,
+                level: start.level,
+                level_xp: start.level_xp,
+                next_level_xp: start.next_level_xp,
                 rank: None,
             });
 
@@ -225,10 +935,50 @@ impl ScoringUseCaseTrait for ScoringUseCase {
                 completed_at: session_model.completed_at.map(|dt| dt.to_rfc3339()),
             },
             new_achievements,
+            xp_earned: XpAwardDto {
+                session_xp: award.session_xp,
+                accuracy_xp: award.accuracy_xp,
+                achievement_xp: award.achievement_xp,
+                total: award.total(),
+            },
+            level_ups: level_ups
+                .into_iter()
+                .map(|level| LevelUpDto {
+                    level,
+                    level_xp: settings.curve.xp_for_level(level),
+                })
+                .collect(),
             updated_stats: stats_dto,
         })
     }
 
+    async fn abandon_game_session(
+        &self,
+        user_id: i32,
+        session_id: i32,
+    ) -> Result<bool, ApplicationError> {
+        let session = self.get_open_game_session(user_id, session_id).await?;
+
+        let abandoned = self
+            .repository
+            .update_game_session_status(
+                session.id,
+                user_id,
+                GameSessionStatuses::Started as i32,
+                GameSessionStatuses::Abandoned as i32,
+            )
+            .await?;
+
+        if !abandoned {
+            return Err(ApplicationError::conflict(format!(
+                "Game session {} is no longer in progress",
+                session_id
+            )));
+        }
+
+        Ok(true)
+    }
+
     async fn get_user_game_history(
         &self,
         user_id: i32,
@@ -301,45 +1051,182 @@ impl ScoringUseCaseTrait for ScoringUseCase {
     async fn get_user_stats(&self, user_id: i32) -> Result<Option<UserStatsDto>, DbErr> {
         let stats = self.repository.get_user_stats(user_id).await?;
         let rank = self.repository.get_user_rank(user_id).await?;
+        let settings = self.get_progression_settings().await?;
 
-        Ok(stats.map(|s| UserStatsDto {
-            user_id: s.user_id,
-            user_name: s.user_name,
-            user_display_name: s.user_display_name,
-            total_score: s.total_score,
-            total_games_played: s.total_games_played,
-            total_time_played_seconds: s.total_time_played_seconds,
-            best_score: s.best_score,
-            best_combo: s.best_combo,
-            average_accuracy: s.average_accuracy,
-            current_streak: s.current_streak,
-            best_streak: s.best_streak,
-            rank,
-        }))
+        Ok(stats.map(|s| Self::map_user_stats_dto(s, &settings.curve, rank)))
     }
 
     async fn get_leaderboard(
         &self,
+        filter: LeaderboardFilterDto,
         page: u64,
         page_size: u64,
-    ) -> Result<Vec<LeaderboardEntryDto>, DbErr> {
-        let entries = self.repository.get_leaderboard(page, page_size).await?;
+    ) -> Result<Vec<LeaderboardEntryDto>, ApplicationError> {
+        let bucket = self.get_leaderboard_bucket(&filter).await?;
+        let offset = (page.max(1) - 1) * page_size;
+        let entries = self
+            .repository
+            .get_leaderboard(&bucket, offset, page_size)
+            .await?;
 
         Ok(entries
             .into_iter()
-            .map(|e| LeaderboardEntryDto {
-                rank: e.rank,
-                user_id: e.user_id,
-                user_name: e.user_name,
-                user_display_name: e.user_display_name,
-                total_score: e.total_score,
-                total_games_played: e.total_games_played,
-                best_score: e.best_score,
-                average_accuracy: e.average_accuracy,
-            })
+            .map(Self::map_leaderboard_entry_dto)
             .collect())
     }
 
+    async fn get_leaderboard_position(
+        &self,
+        user_id: i32,
+        filter: LeaderboardFilterDto,
+        radius: u64,
+    ) -> Result<LeaderboardPositionDto, ApplicationError> {
+        let bucket = self.get_leaderboard_bucket(&filter).await?;
+        let rank = self
+            .repository
+            .get_leaderboard_rank(&bucket, user_id)
+            .await?;
+
+        let entries = match rank {
+            Some(rank) => {
+                let radius = radius.min(MAX_LEADERBOARD_RADIUS);
+                let offset = leaderboard_window_offset(rank as u64, radius);
+                self.repository
+                    .get_leaderboard(&bucket, offset, radius * 2 + 1)
+                    .await?
+            }
+            None => Vec::new(),
+        };
+
+        Ok(LeaderboardPositionDto {
+            period: bucket.period.as_str().to_string(),
+            period_start: bucket.period_start,
+            rank,
+            entries: entries
+                .into_iter()
+                .map(Self::map_leaderboard_entry_dto)
+                .collect(),
+        })
+    }
+
+    async fn get_user_streak(&self, user_id: i32) -> Result<StreakDto, ApplicationError> {
+        let stats = self.get_streak_stats(user_id).await?;
+        let from = stats.local_day(Utc::now()) - Duration::days(STREAK_RECENT_DAYS - 1);
+        let recent_days = self
+            .repository
+            .get_user_streak_days(user_id, from)
+            .await?
+            .into_iter()
+            .map(|d| StreakDayDto {
+                day: d.day,
+                kind: d.kind,
+            })
+            .collect();
+
+        Ok(self.map_streak_dto(stats, recent_days))
+    }
+
+    async fn update_streak_settings(
+        &self,
+        user_id: i32,
+        dto: StreakSettingsDto,
+    ) -> Result<StreakDto, ApplicationError> {
+        if !(MIN_UTC_OFFSET_MINUTES..=MAX_UTC_OFFSET_MINUTES).contains(&dto.utc_offset_minutes) {
+            return Err(ApplicationError::invalid_input(format!(
+                "UTC offset must be between {} and {} minutes",
+                MIN_UTC_OFFSET_MINUTES, MAX_UTC_OFFSET_MINUTES
+            )));
+        }
+
+        self.repository
+            .update_streak_settings(user_id, dto.utc_offset_minutes, dto.reminders_enabled)
+            .await?;
+        self.get_user_streak(user_id).await
+    }
+
+    async fn purchase_streak_freeze(&self, user_id: i32) -> Result<StreakDto, ApplicationError> {
+        let stats = self.get_streak_stats(user_id).await?;
+        let policy = self.streak_policy;
+        if stats.streak_freezes >= policy.max_freezes {
+            return Err(ApplicationError::invalid_state(format!(
+                "Already holding the maximum of {} streak freezes",
+                policy.max_freezes
+            )));
+        }
+        if stats.available_points() < policy.freeze_price {
+            return Err(ApplicationError::business_rule(format!(
+                "A streak freeze costs {} points, {} available",
+                policy.freeze_price,
+                stats.available_points()
+            )));
+        }
+
+        let purchased = self
+            .repository
+            .purchase_streak_freeze(user_id, policy.freeze_price, policy.max_freezes)
+            .await?;
+        if !purchased {
+            return Err(ApplicationError::conflict(
+                "Streak freeze could not be purchased, please try again",
+            ));
+        }
+        self.get_user_streak(user_id).await
+    }
+
+    async fn claim_streak_reminders(
+        &self,
+        after_user_id: i32,
+        batch_size: u64,
+    ) -> Result<StreakReminderBatchDto, ApplicationError> {
+        let now = Utc::now();
+        // A streak cannot survive more missed days than a player holds freezes, and local
+        // days are at most a day away from UTC
+        let active_since =
+            now.date_naive() - Duration::days(self.streak_policy.max_freezes as i64 + 2);
+        let candidates = self
+            .repository
+            .get_streak_reminder_candidates(after_user_id, active_since, batch_size)
+            .await?;
+        let next_user_id = match candidates.last() {
+            Some(last) if candidates.len() as u64 >= batch_size => Some(last.stats.user_id),
+            _ => None,
+        };
+
+        let mut reminders = Vec::new();
+        for candidate in candidates {
+            let stats = candidate.stats;
+            let due = self.streak_policy.is_reminder_due(
+                &stats.streak_state(),
+                now,
+                stats.utc_offset_minutes,
+                stats.streak_reminded_on,
+            );
+            if !due
+                || !self
+                    .repository
+                    .mark_streak_reminded(stats.user_id, stats.local_day(now))
+                    .await?
+            {
+                continue;
+            }
+
+            reminders.push(StreakReminderDto {
+                user_id: stats.user_id,
+                user_name: stats
+                    .user_display_name
+                    .or(stats.user_name)
+                    .unwrap_or_default(),
+                email: candidate.email,
+                current_streak: stats.current_streak,
+            });
+        }
+
+        Ok(StreakReminderBatchDto {
+            reminders,
+            next_user_id,
+        })
+    }
+
     async fn get_all_achievements(&self, user_id: Option<i32>) -> Result<Vec<AchievementDto>, DbErr> {
         let achievements = self.repository.get_achievements().await?;
 
@@ -441,9 +1328,16 @@ impl ScoringUseCaseTrait for ScoringUseCase {
             }
         };
 
+        // The level is the one the XP earned in this game reaches, not a number from the client
+        let settings = self.get_progression_settings().await?;
+        let game_type_xp = self
+            .repository
+            .get_game_type_xp(user_id, game_type.id)
+            .await?;
+        let current_level = settings.curve.level_for_xp(game_type_xp);
         let progress = self
             .repository
-            .save_game_progress(user_id, game_type.id, dto.current_level, dto.total_score)
+            .save_game_progress(user_id, game_type.id, current_level, dto.total_score)
             .await?;
 
         Ok(GameProgressDto {
@@ -480,6 +1374,42 @@ impl ScoringUseCaseTrait for ScoringUseCase {
             .await
     }
 
+    // Reviews
+    async fn get_due_cards(
+        &self,
+        user_id: i32,
+        flashcard_type_id: i32,
+        limit: u64,
+    ) -> Result<Vec<DueCardDto>, ApplicationError> {
+        let limit = limit.clamp(1, MAX_REVIEW_DECK_SIZE);
+        let mut deck = self
+            .repository
+            .get_due_flashcard_reviews(user_id, flashcard_type_id, Utc::now(), limit)
+            .await?;
+
+        let remaining = limit - deck.len() as u64;
+        if remaining > 0 {
+            let new_cards = self
+                .repository
+                .get_unreviewed_flashcards(user_id, flashcard_type_id, remaining)
+                .await?;
+            deck.extend(new_cards);
+        }
+
+        Ok(deck
+            .into_iter()
+            .map(|r| DueCardDto {
+                flashcard_id: r.flashcard_id,
+                flashcard_name: r.flashcard_name,
+                is_new: r.last_reviewed_at.is_none(),
+                ease_factor: r.ease_factor,
+                interval_days: r.interval_days,
+                lapses: r.lapses,
+                due_at: r.due_at.to_rfc3339(),
+            })
+            .collect())
+    }
+
     // ---- Admin: Game Types ----
 
     async fn admin_get_game_types(
@@ -553,6 +1483,7 @@ impl ScoringUseCaseTrait for ScoringUseCase {
                 icon: a.icon,
                 points: a.points,
                 category: a.category,
+                criteria: a.criteria,
                 is_actived: a.is_actived,
                 created_on: a.created_on,
                 updated_on: a.updated_on,
@@ -571,6 +1502,7 @@ impl ScoringUseCaseTrait for ScoringUseCase {
             icon: a.icon,
             points: a.points,
             category: a.category,
+            criteria: a.criteria,
             is_actived: a.is_actived,
             created_on: a.created_on,
             updated_on: a.updated_on,
@@ -587,6 +1519,7 @@ impl ScoringUseCaseTrait for ScoringUseCase {
             icon: dto.icon,
             points: dto.points,
             category: dto.category,
+            criteria: dto.criteria,
             is_actived: true,
             created_on: chrono::Utc::now(),
             updated_on: chrono::Utc::now(),
@@ -604,6 +1537,7 @@ impl ScoringUseCaseTrait for ScoringUseCase {
                 if dto.icon.is_some() { model.icon = dto.icon; }
                 if let Some(points) = dto.points { model.points = points; }
                 if dto.category.is_some() { model.category = dto.category; }
+                if dto.criteria.is_some() { model.criteria = dto.criteria; }
                 self.repository.update_achievement(model).await
             }
             None => Ok(false),
@@ -618,6 +1552,118 @@ impl ScoringUseCaseTrait for ScoringUseCase {
         self.repository.toggle_achievement_active(id).await
     }
 
+    // ---- Admin: Achievement Backfills ----
+
+    async fn admin_get_achievement_backfills(
+        &self,
+        page: u64,
+        page_size: u64,
+    ) -> Result<PageListModel<AchievementBackfillDto>, DbErr> {
+        let (items, total_count) = self.repository.get_achievement_backfills_paged(page, page_size).await?;
+        let list = items
+            .into_iter()
+            .map(Self::map_achievement_backfill_dto)
+            .collect();
+        Ok(PageListModel { items: list, total_count })
+    }
+
+    async fn admin_get_achievement_backfill_by_id(&self, id: i32) -> Result<Option<AchievementBackfillDto>, DbErr> {
+        let item = self.repository.get_achievement_backfill_by_id(id).await?;
+        Ok(item.map(Self::map_achievement_backfill_dto))
+    }
+
+    async fn admin_create_achievement_backfill(
+        &self,
+        user_id: i32,
+        dto: AchievementBackfillCreationDto,
+    ) -> Result<i32, ApplicationError> {
+        if let Some(achievement_id) = dto.achievement_id {
+            let achievement = self
+                .repository
+                .get_achievement_by_id(achievement_id)
+                .await?
+                .ok_or_else(|| {
+                    ApplicationError::not_found("Achievement", achievement_id.to_string())
+                })?;
+            if !achievement.is_actived {
+                return Err(ApplicationError::invalid_input("Achievement is not active"));
+            }
+            if achievement.criteria.is_none() {
+                return Err(ApplicationError::invalid_input(
+                    "Achievement has no criteria to replay",
+                ));
+            }
+        }
+
+        let total_users = self
+            .repository
+            .count_users_with_completed_sessions()
+            .await?;
+        let now = Utc::now();
+        let backfill = AchievementBackfillModel {
+            id: 0,
+            achievement_id: dto.achievement_id,
+            is_dry_run: dto.dry_run,
+            status: AchievementBackfillStatuses::Queued,
+            last_user_id: 0,
+            total_users: i32::try_from(total_users).unwrap_or(i32::MAX),
+            processed_users: 0,
+            affected_users: 0,
+            unlocked_count: 0,
+            last_error: None,
+            created_by_id: Some(user_id),
+            started_on: None,
+            completed_on: None,
+            created_on: now,
+            updated_on: now,
+        };
+        Ok(self
+            .repository
+            .create_achievement_backfill(backfill)
+            .await?)
+    }
+
+    async fn process_achievement_backfill_batch(
+        &self,
+        batch_size: u64,
+    ) -> Result<Option<AchievementBackfillDto>, ApplicationError> {
+        let transaction = self.transaction_manager.begin().await?;
+        let backfill = match self
+            .repository
+            .get_next_achievement_backfill_with_transaction(Box::new(&transaction))
+            .await
+        {
+            Ok(Some(backfill)) => backfill,
+            Ok(None) => {
+                self.transaction_manager.commit(transaction).await?;
+                return Ok(None);
+            }
+            Err(err) => {
+                self.transaction_manager.rollback(transaction).await?;
+                return Err(err.into());
+            }
+        };
+
+        let backfill_id = backfill.id;
+        match self
+            .run_achievement_backfill_batch(backfill, batch_size, &transaction)
+            .await
+        {
+            Ok(backfill) => {
+                self.transaction_manager.commit(transaction).await?;
+                Ok(Some(Self::map_achievement_backfill_dto(backfill)))
+            }
+            Err(err) => {
+                // Batches already committed stay; a new backfill skips what they unlocked
+                self.transaction_manager.rollback(transaction).await?;
+                self.repository
+                    .fail_achievement_backfill(backfill_id, &err.to_string())
+                    .await?;
+                Err(err)
+            }
+        }
+    }
+
     // ---- Admin: Game Sessions ----
 
     async fn admin_get_game_sessions(
@@ -663,9 +1709,11 @@ impl ScoringUseCaseTrait for ScoringUseCase {
         page_size: u64,
     ) -> Result<PageListModel<AdminUserStatsDto>, DbErr> {
         let (items, total_count) = self.repository.get_all_user_stats_paged(page, page_size).await?;
+        let settings = self.get_progression_settings().await?;
         let list = items
             .into_iter()
             .map(|s| AdminUserStatsDto {
+                current_streak: s.current_streak_at(Utc::now()),
                 id: s.id,
                 user_id: s.user_id,
                 user_name: s.user_name,
@@ -676,15 +1724,54 @@ impl ScoringUseCaseTrait for ScoringUseCase {
                 best_score: s.best_score,
                 best_combo: s.best_combo,
                 average_accuracy: s.average_accuracy,
-                current_streak: s.current_streak,
                 best_streak: s.best_streak,
+                total_xp: s.total_xp,
+                level: settings.curve.level_for_xp(s.total_xp),
                 last_played_at: s.last_played_at.map(|dt| dt.to_rfc3339()),
             })
             .collect();
         Ok(PageListModel { items: list, total_count })
     }
 
-    async fn admin_reset_user_stats(&self, user_id: i32) -> Result<bool, DbErr> {
-        self.repository.reset_user_stats(user_id).await
+    async fn admin_reset_user_stats(&self, user_id: i32) -> Result<bool, ApplicationError> {
+        // The stats, leaderboard, streak days and XP ledger are cleared together or not at all
+        let transaction = self.transaction_manager.begin().await?;
+        match self
+            .repository
+            .reset_user_stats_without_commit(user_id, Box::new(&transaction))
+            .await
+        {
+            Ok(reset) => {
+                self.transaction_manager.commit(transaction).await?;
+                Ok(reset)
+            }
+            Err(err) => {
+                self.transaction_manager.rollback(transaction).await?;
+                Err(err.into())
+            }
+        }
+    }
+
+    // ---- Admin: Progression ----
+
+    async fn admin_get_progression_settings(&self) -> Result<ProgressionSettings, DbErr> {
+        self.get_progression_settings().await
+    }
+
+    async fn admin_update_progression_settings(
+        &self,
+        user_id: i32,
+        settings: ProgressionSettings,
+    ) -> Result<ProgressionSettings, ApplicationError> {
+        settings
+            .validate()
+            .map_err(ApplicationError::invalid_input)?;
+
+        let value = serde_json::to_string(&settings)
+            .map_err(|err| ApplicationError::invalid_input(err.to_string()))?;
+        self.repository
+            .save_progression_settings(value, user_id)
+            .await?;
+        Ok(settings)
     }
 }
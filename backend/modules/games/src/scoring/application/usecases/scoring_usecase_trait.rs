@@ -1,14 +1,18 @@
 use async_trait::async_trait;
+use rex_game_shared::ApplicationError;
 use sea_orm::DbErr;
 
 use rex_game_shared::domain::models::page_list_model::PageListModel;
 
 use super::scoring_dto::{
-    AchievementCreationDto, AchievementDto, AchievementUpdationDto, AdminAchievementDto,
-    AdminGameSessionDto, AdminUserStatsDto, CompleteGameSessionDto, GameCompleteResponseDto,
-    GameProgressDto, GameSessionDto, GameTypeCreationDto, GameTypeDto, GameTypeUpdationDto,
-    LeaderboardEntryDto, SaveGameProgressDto, StartGameSessionDto, UserStatsDto,
+    AchievementBackfillCreationDto, AchievementBackfillDto, AchievementCreationDto, AchievementDto,
+    AchievementUpdationDto, AdminAchievementDto, AdminGameSessionDto, AdminUserStatsDto, CompleteGameSessionDto, DueCardDto,
+    GameAnswerResultDto, GameCompleteResponseDto, GameProgressDto, GameSessionDto, GameSessionStartedDto,
+    GameTypeCreationDto, GameTypeDto, GameTypeUpdationDto, LeaderboardEntryDto,
+    LeaderboardFilterDto, LeaderboardPositionDto, SaveGameProgressDto, StartGameSessionDto,
+    StreakDto, StreakReminderBatchDto, StreakSettingsDto, SubmitGameAnswerDto, UserStatsDto,
 };
+use crate::scoring::domain::rules::ProgressionSettings;
 
 #[async_trait]
 pub trait ScoringUseCaseTrait: Send + Sync {
@@ -20,13 +24,26 @@ pub trait ScoringUseCaseTrait: Send + Sync {
         &self,
         user_id: i32,
         dto: StartGameSessionDto,
-    ) -> Result<i32, DbErr>;
+    ) -> Result<GameSessionStartedDto, DbErr>;
+
+    async fn submit_game_answer(
+        &self,
+        user_id: i32,
+        session_id: i32,
+        dto: SubmitGameAnswerDto,
+    ) -> Result<GameAnswerResultDto, ApplicationError>;
 
     async fn complete_game_session(
         &self,
         user_id: i32,
         dto: CompleteGameSessionDto,
-    ) -> Result<GameCompleteResponseDto, DbErr>;
+    ) -> Result<GameCompleteResponseDto, ApplicationError>;
+
+    async fn abandon_game_session(
+        &self,
+        user_id: i32,
+        session_id: i32,
+    ) -> Result<bool, ApplicationError>;
 
     async fn get_user_game_history(
         &self,
@@ -48,9 +65,33 @@ pub trait ScoringUseCaseTrait: Send + Sync {
     // Leaderboard
     async fn get_leaderboard(
         &self,
+        filter: LeaderboardFilterDto,
         page: u64,
         page_size: u64,
-    ) -> Result<Vec<LeaderboardEntryDto>, DbErr>;
+    ) -> Result<Vec<LeaderboardEntryDto>, ApplicationError>;
+    async fn get_leaderboard_position(
+        &self,
+        user_id: i32,
+        filter: LeaderboardFilterDto,
+        radius: u64,
+    ) -> Result<LeaderboardPositionDto, ApplicationError>;
+
+    // Streaks
+    async fn get_user_streak(&self, user_id: i32) -> Result<StreakDto, ApplicationError>;
+    async fn update_streak_settings(
+        &self,
+        user_id: i32,
+        dto: StreakSettingsDto,
+    ) -> Result<StreakDto, ApplicationError>;
+    /// Spends score on one streak freeze.
+    async fn purchase_streak_freeze(&self, user_id: i32) -> Result<StreakDto, ApplicationError>;
+    /// Claims the reminders due now for players after `after_user_id`, up to `batch_size`
+    /// checked. A claimed reminder is not returned again for the same local day.
+    async fn claim_streak_reminders(
+        &self,
+        after_user_id: i32,
+        batch_size: u64,
+    ) -> Result<StreakReminderBatchDto, ApplicationError>;
 
     // Achievements
     async fn get_all_achievements(&self, user_id: Option<i32>) -> Result<Vec<AchievementDto>, DbErr>;
@@ -71,6 +112,15 @@ pub trait ScoringUseCaseTrait: Send + Sync {
 
     async fn reset_game_progress(&self, user_id: i32, game_type_code: &str) -> Result<(), DbErr>;
 
+    // Reviews
+    /// Builds a review deck for the flashcard type: due cards first, topped up with new ones.
+    async fn get_due_cards(
+        &self,
+        user_id: i32,
+        flashcard_type_id: i32,
+        limit: u64,
+    ) -> Result<Vec<DueCardDto>, ApplicationError>;
+
     // Admin: Game Types
     async fn admin_get_game_types(&self, name: Option<String>, page: u64, page_size: u64) -> Result<PageListModel<GameTypeDto>, DbErr>;
     async fn admin_get_game_type_by_id(&self, id: i32) -> Result<Option<GameTypeDto>, DbErr>;
@@ -87,11 +137,38 @@ pub trait ScoringUseCaseTrait: Send + Sync {
     async fn admin_delete_achievement(&self, id: i32) -> Result<u64, DbErr>;
     async fn admin_toggle_achievement_active(&self, id: i32) -> Result<bool, DbErr>;
 
+    // Admin: Achievement Backfills
+    async fn admin_get_achievement_backfills(&self, page: u64, page_size: u64) -> Result<PageListModel<AchievementBackfillDto>, DbErr>;
+    async fn admin_get_achievement_backfill_by_id(&self, id: i32) -> Result<Option<AchievementBackfillDto>, DbErr>;
+    /// Queues a replay of completed sessions against achievement criteria. The backfill worker
+    /// runs it in batches of users.
+    async fn admin_create_achievement_backfill(
+        &self,
+        user_id: i32,
+        dto: AchievementBackfillCreationDto,
+    ) -> Result<i32, ApplicationError>;
+    /// Runs one batch of users for the oldest unfinished backfill, on one transaction.
+    /// Returns `None` when no backfill is waiting.
+    async fn process_achievement_backfill_batch(
+        &self,
+        batch_size: u64,
+    ) -> Result<Option<AchievementBackfillDto>, ApplicationError>;
+
     // Admin: Game Sessions
     async fn admin_get_game_sessions(&self, page: u64, page_size: u64) -> Result<PageListModel<AdminGameSessionDto>, DbErr>;
     async fn admin_delete_game_session(&self, id: i32) -> Result<u64, DbErr>;
 
     // Admin: User Stats
     async fn admin_get_user_stats(&self, page: u64, page_size: u64) -> Result<PageListModel<AdminUserStatsDto>, DbErr>;
-    async fn admin_reset_user_stats(&self, user_id: i32) -> Result<bool, DbErr>;
+    async fn admin_reset_user_stats(&self, user_id: i32) -> Result<bool, ApplicationError>;
+
+    // Admin: Progression
+    async fn admin_get_progression_settings(&self) -> Result<ProgressionSettings, DbErr>;
+    /// Validates and stores the settings. Totals already earned are kept; levels follow
+    /// the new curve from the next read.
+    async fn admin_update_progression_settings(
+        &self,
+        user_id: i32,
+        settings: ProgressionSettings,
+    ) -> Result<ProgressionSettings, ApplicationError>;
 }
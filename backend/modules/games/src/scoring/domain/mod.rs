@@ -1,5 +1,7 @@
 pub mod models;
 pub mod repositories;
+pub mod rules;
 
 pub use models::*;
 pub use repositories::*;
+pub use rules::*;
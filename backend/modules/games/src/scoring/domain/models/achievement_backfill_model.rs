@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AchievementBackfillStatuses {
+    Queued,
+    Running,
+    Completed,
+    /// Stopped on an error; the cursor keeps the last committed batch
+    Failed,
+}
+
+impl AchievementBackfillStatuses {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AchievementBackfillStatuses::Queued => "queued",
+            AchievementBackfillStatuses::Running => "running",
+            AchievementBackfillStatuses::Completed => "completed",
+            AchievementBackfillStatuses::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "queued" => Some(AchievementBackfillStatuses::Queued),
+            "running" => Some(AchievementBackfillStatuses::Running),
+            "completed" => Some(AchievementBackfillStatuses::Completed),
+            "failed" => Some(AchievementBackfillStatuses::Failed),
+            _ => None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            AchievementBackfillStatuses::Completed | AchievementBackfillStatuses::Failed
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AchievementBackfillModel {
+    pub id: i32,
+    /// `None` replays every active achievement
+    pub achievement_id: Option<i32>,
+    pub is_dry_run: bool,
+    pub status: AchievementBackfillStatuses,
+    /// Users are walked in id order; everything up to this id has been processed
+    pub last_user_id: i32,
+    pub total_users: i32,
+    pub processed_users: i32,
+    pub affected_users: i32,
+    pub unlocked_count: i32,
+    pub last_error: Option<String>,
+    pub created_by_id: Option<i32>,
+    pub started_on: Option<DateTime<Utc>>,
+    pub completed_on: Option<DateTime<Utc>>,
+    pub created_on: DateTime<Utc>,
+    pub updated_on: DateTime<Utc>,
+}
+
+/// An achievement a replayed user would have unlocked, dated by the session that first met it
+#[derive(Debug, Clone, PartialEq)]
+pub struct AchievementBackfillUnlock {
+    pub achievement_id: i32,
+    pub points: i32,
+    pub unlocked_at: DateTime<Utc>,
+}
+
+impl AchievementBackfillModel {
+    pub fn start(&mut self, now: DateTime<Utc>) {
+        if self.status == AchievementBackfillStatuses::Queued {
+            self.status = AchievementBackfillStatuses::Running;
+            self.started_on = Some(now);
+        }
+    }
+
+    /// Counts a replayed user and moves the cursor past them. Returns the unlocks to
+    /// write, which a dry run leaves empty.
+    pub fn record_user<'a>(
+        &mut self,
+        user_id: i32,
+        unlocks: &'a [AchievementBackfillUnlock],
+    ) -> &'a [AchievementBackfillUnlock] {
+        if !unlocks.is_empty() {
+            self.affected_users += 1;
+            self.unlocked_count += unlocks.len() as i32;
+        }
+        self.processed_users += 1;
+        self.last_user_id = user_id;
+
+        if self.is_dry_run {
+            &[]
+        } else {
+            unlocks
+        }
+    }
+
+    /// A batch shorter than asked for has reached the last user
+    pub fn finish_batch(&mut self, is_last_batch: bool, now: DateTime<Utc>) {
+        if is_last_batch {
+            self.status = AchievementBackfillStatuses::Completed;
+            self.completed_on = Some(now);
+        }
+    }
+}
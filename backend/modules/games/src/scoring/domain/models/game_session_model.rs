@@ -1,7 +1,9 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use crate::scoring::domain::rules::{streak_local_day, AchievementCriteria, StreakState};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameSessionModel {
     pub id: i32,
@@ -22,6 +24,7 @@ pub struct GameSessionModel {
     pub combo_max: i32,
     pub started_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    pub status_id: i32,
     pub created_on: DateTime<Utc>,
 }
 
@@ -52,6 +55,56 @@ pub struct UserStatsModel {
     pub current_streak: i32,
     pub best_streak: i32,
     pub last_played_at: Option<DateTime<Utc>>,
+    /// Where the player's days start, in minutes east of UTC
+    pub utc_offset_minutes: i32,
+    pub streak_freezes: i32,
+    /// Score spent on streak freezes
+    pub points_spent: i64,
+    pub last_streak_day: Option<NaiveDate>,
+    pub streak_reminders_enabled: bool,
+    pub streak_reminded_on: Option<NaiveDate>,
+    /// Sum of the player's XP ledger
+    pub total_xp: i64,
+}
+
+impl UserStatsModel {
+    pub fn streak_state(&self) -> StreakState {
+        StreakState {
+            current_streak: self.current_streak,
+            best_streak: self.best_streak,
+            freezes: self.streak_freezes,
+            last_day: self.last_streak_day,
+        }
+    }
+
+    /// The player's calendar day at `at`.
+    pub fn local_day(&self, at: DateTime<Utc>) -> NaiveDate {
+        streak_local_day(at, self.utc_offset_minutes)
+    }
+
+    /// The streak as it stands at `at`; 0 once it is broken, even before the next activity.
+    pub fn current_streak_at(&self, at: DateTime<Utc>) -> i32 {
+        self.streak_state().current_on(self.local_day(at))
+    }
+
+    /// Score the player can still spend on streak freezes.
+    pub fn available_points(&self) -> i64 {
+        (self.total_score - self.points_spent).max(0)
+    }
+}
+
+/// A player whose streak may need a reminder, with where to send it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreakReminderCandidate {
+    pub email: String,
+    pub stats: UserStatsModel,
+}
+
+/// One day in a player's streak ledger.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreakDayModel {
+    pub day: NaiveDate,
+    pub kind: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +116,8 @@ pub struct AchievementModel {
     pub icon: Option<String>,
     pub points: i32,
     pub category: Option<String>,
+    /// `None` for achievements that cannot unlock
+    pub criteria: Option<AchievementCriteria>,
     pub is_actived: bool,
     pub created_on: DateTime<Utc>,
     pub updated_on: DateTime<Utc>,
@@ -100,3 +155,47 @@ pub struct GameProgressModel {
     pub total_score: i64,
     pub last_played_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSessionAnswerModel {
+    pub id: i32,
+    pub game_session_id: i32,
+    pub flashcard_id: i32,
+    pub flashcard_name: Option<String>,
+    pub position: i32,
+    pub answer: Option<String>,
+    pub is_correct: Option<bool>,
+    pub points: i32,
+    pub combo: i32,
+    pub response_ms: Option<i32>,
+    pub answered_at: Option<DateTime<Utc>>,
+}
+
+/// Totals of a finished round, worked out from its answer log.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GameSessionCompletion {
+    pub session_id: i32,
+    pub user_id: i32,
+    pub score: i32,
+    pub max_score: Option<i32>,
+    pub correct_answers: i32,
+    pub wrong_answers: i32,
+    pub combo_max: i32,
+    pub time_spent_seconds: i32,
+}
+
+/// Spaced-repetition state of one flashcard for one user. Cards that were never
+/// reviewed have no stored row and come back with `last_reviewed_at` unset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlashcardReviewModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub flashcard_id: i32,
+    pub flashcard_name: Option<String>,
+    pub ease_factor: i32,
+    pub interval_days: i32,
+    pub repetitions: i32,
+    pub lapses: i32,
+    pub due_at: DateTime<Utc>,
+    pub last_reviewed_at: Option<DateTime<Utc>>,
+}
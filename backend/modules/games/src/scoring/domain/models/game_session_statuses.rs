@@ -0,0 +1,32 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameSessionStatuses {
+    Started = 1,
+    Completed = 2,
+    Abandoned = 3,
+    Expired = 4,
+}
+
+impl GameSessionStatuses {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameSessionStatuses::Started => "started",
+            GameSessionStatuses::Completed => "completed",
+            GameSessionStatuses::Abandoned => "abandoned",
+            GameSessionStatuses::Expired => "expired",
+        }
+    }
+}
+
+impl TryFrom<i32> for GameSessionStatuses {
+    type Error = ();
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(GameSessionStatuses::Started),
+            2 => Ok(GameSessionStatuses::Completed),
+            3 => Ok(GameSessionStatuses::Abandoned),
+            4 => Ok(GameSessionStatuses::Expired),
+            _ => Err(()),
+        }
+    }
+}
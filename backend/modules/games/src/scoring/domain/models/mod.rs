@@ -1,3 +1,9 @@
+pub mod achievement_backfill_model;
 pub mod game_session_model;
+pub mod game_session_statuses;
 
+pub use achievement_backfill_model::{
+    AchievementBackfillModel, AchievementBackfillStatuses, AchievementBackfillUnlock,
+};
 pub use game_session_model::*;
+pub use game_session_statuses::GameSessionStatuses;
@@ -1,10 +1,16 @@
 use async_trait::async_trait;
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
 use sea_orm::DbErr;
 
+use chrono::{DateTime, NaiveDate, Utc};
+
 use crate::scoring::domain::models::{
-    AchievementModel, GameProgressModel, GameSessionModel, GameTypeModel, LeaderboardEntry,
-    UserAchievementModel, UserStatsModel,
+    AchievementBackfillModel, AchievementModel, FlashcardReviewModel, GameProgressModel,
+    GameSessionAnswerModel, GameSessionCompletion, GameSessionModel, GameTypeModel,
+    LeaderboardEntry, StreakDayModel, StreakReminderCandidate, UserAchievementModel,
+    UserStatsModel,
 };
+use crate::scoring::domain::rules::{LeaderboardBucket, StreakProgress, XpAward};
 
 #[async_trait]
 pub trait ScoringRepositoryTrait: Send + Sync {
@@ -20,16 +26,23 @@ pub trait ScoringRepositoryTrait: Send + Sync {
         flashcard_type_id: Option<i32>,
     ) -> Result<i32, DbErr>;
 
-    async fn complete_game_session(
+    /// Moves a started session owned by `user_id` to completed. Returns `None` when
+    /// the session does not exist, belongs to someone else or is no longer started.
+    async fn complete_game_session_without_commit(
+        &self,
+        completion: &GameSessionCompletion,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Option<GameSessionModel>, DbErr>;
+
+    /// Moves a session owned by `user_id` from `from_status_id` to `to_status_id`.
+    /// Returns `false` when no row matched.
+    async fn update_game_session_status(
         &self,
         session_id: i32,
-        score: i32,
-        max_score: Option<i32>,
-        correct_answers: i32,
-        wrong_answers: i32,
-        combo_max: i32,
-        time_spent_seconds: i32,
-    ) -> Result<(), DbErr>;
+        user_id: i32,
+        from_status_id: i32,
+        to_status_id: i32,
+    ) -> Result<bool, DbErr>;
 
     async fn get_user_game_sessions(
         &self,
@@ -45,31 +58,179 @@ pub trait ScoringRepositoryTrait: Send + Sync {
         limit: u64,
     ) -> Result<Vec<GameSessionModel>, DbErr>;
 
+    async fn get_game_session(&self, session_id: i32) -> Result<Option<GameSessionModel>, DbErr>;
+
+    /// Loads the session with its row locked until the transaction ends, so answers and
+    /// the completion of the session are applied one at a time.
+    async fn lock_game_session_without_commit(
+        &self,
+        session_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Option<GameSessionModel>, DbErr>;
+
+    /// Every completed session of the user, read on the transaction so that one completed
+    /// in it is included.
+    async fn get_completed_game_sessions_with_transaction(
+        &self,
+        user_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Vec<GameSessionModel>, DbErr>;
+
+    // Game Session Answers
+    async fn deal_game_session_cards(
+        &self,
+        session_id: i32,
+        game_type_id: i32,
+        flashcard_type_id: Option<i32>,
+        card_count: u64,
+    ) -> Result<Vec<GameSessionAnswerModel>, DbErr>;
+    async fn get_game_session_answers_with_transaction(
+        &self,
+        session_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Vec<GameSessionAnswerModel>, DbErr>;
+    async fn create_game_session_answer_without_commit(
+        &self,
+        model: &GameSessionAnswerModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<i32, DbErr>;
+    /// Records the answer on a dealt card that is still open. Returns `false` when the
+    /// card was answered in the meantime.
+    async fn update_game_session_answer_without_commit(
+        &self,
+        model: &GameSessionAnswerModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<bool, DbErr>;
+
     // User Stats
     async fn get_user_stats(&self, user_id: i32) -> Result<Option<UserStatsModel>, DbErr>;
-    async fn update_user_stats(&self, user_id: i32, session: &GameSessionModel)
-        -> Result<(), DbErr>;
+    async fn get_user_stats_with_transaction(
+        &self,
+        user_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Option<UserStatsModel>, DbErr>;
+    async fn update_user_stats_without_commit(
+        &self,
+        user_id: i32,
+        session: &GameSessionModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<(), DbErr>;
     async fn ensure_user_stats_exists(&self, user_id: i32) -> Result<(), DbErr>;
 
     // Leaderboard
     async fn get_leaderboard(
         &self,
-        page: u64,
-        page_size: u64,
+        bucket: &LeaderboardBucket,
+        offset: u64,
+        limit: u64,
     ) -> Result<Vec<LeaderboardEntry>, DbErr>;
+    async fn get_leaderboard_rank(
+        &self,
+        bucket: &LeaderboardBucket,
+        user_id: i32,
+    ) -> Result<Option<i32>, DbErr>;
+    async fn add_leaderboard_score_without_commit(
+        &self,
+        user_id: i32,
+        buckets: &[LeaderboardBucket],
+        session: &GameSessionModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<(), DbErr>;
 
     async fn get_user_rank(&self, user_id: i32) -> Result<Option<i32>, DbErr>;
 
+    // Streaks
+    /// Loads the user's stats and locks them until `transaction` ends, creating them if missing.
+    async fn lock_user_stats_without_commit(
+        &self,
+        user_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Option<UserStatsModel>, DbErr>;
+    /// Adds the progress' days to the ledger and stores its streak state.
+    async fn save_streak_progress_without_commit(
+        &self,
+        user_id: i32,
+        progress: &StreakProgress,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<(), DbErr>;
+    async fn get_user_streak_days(
+        &self,
+        user_id: i32,
+        from: NaiveDate,
+    ) -> Result<Vec<StreakDayModel>, DbErr>;
+    async fn get_user_streak_days_with_transaction(
+        &self,
+        user_id: i32,
+        from: NaiveDate,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Vec<StreakDayModel>, DbErr>;
+    async fn update_streak_settings(
+        &self,
+        user_id: i32,
+        utc_offset_minutes: i32,
+        reminders_enabled: bool,
+    ) -> Result<(), DbErr>;
+    /// Spends `price` points on a freeze. `false` when the user cannot afford it or
+    /// already holds `max_freezes`.
+    async fn purchase_streak_freeze(
+        &self,
+        user_id: i32,
+        price: i64,
+        max_freezes: i32,
+    ) -> Result<bool, DbErr>;
+    /// Active users after `after_user_id` with reminders on and a streak last extended
+    /// on or after `active_since`, by user id.
+    async fn get_streak_reminder_candidates(
+        &self,
+        after_user_id: i32,
+        active_since: NaiveDate,
+        limit: u64,
+    ) -> Result<Vec<StreakReminderCandidate>, DbErr>;
+    /// Records the local day a reminder is sent for. `false` when it was already sent.
+    async fn mark_streak_reminded(&self, user_id: i32, day: NaiveDate) -> Result<bool, DbErr>;
+
+    // Progression
+    /// Adds the award's non-zero amounts to the XP ledger and returns the user's new total.
+    async fn add_xp_without_commit(
+        &self,
+        user_id: i32,
+        game_session_id: Option<i32>,
+        award: &XpAward,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<i64, DbErr>;
+    /// XP the user earned in sessions of the game type, by the ledger.
+    async fn get_game_type_xp(&self, user_id: i32, game_type_id: i32) -> Result<i64, DbErr>;
+    /// The progression settings JSON from the system settings, if there is one.
+    async fn get_progression_settings(&self) -> Result<Option<String>, DbErr>;
+    async fn save_progression_settings(
+        &self,
+        value: String,
+        updated_by_id: i32,
+    ) -> Result<(), DbErr>;
+
     // Achievements
     async fn get_achievements(&self) -> Result<Vec<AchievementModel>, DbErr>;
     async fn get_user_achievements(&self, user_id: i32)
         -> Result<Vec<UserAchievementModel>, DbErr>;
-    async fn unlock_achievement(&self, user_id: i32, achievement_id: i32) -> Result<(), DbErr>;
+    /// Unlocks the achievement at `unlocked_at` unless the user already has it.
+    async fn unlock_achievement_without_commit(
+        &self,
+        user_id: i32,
+        achievement_id: i32,
+        unlocked_at: DateTime<Utc>,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<(), DbErr>;
     async fn check_achievement_unlocked(
         &self,
         user_id: i32,
         achievement_code: &str,
+        transaction: Box<&dyn TransactionWrapperTrait>,
     ) -> Result<bool, DbErr>;
+    async fn get_unlocked_achievement_ids_with_transaction(
+        &self,
+        user_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Vec<i32>, DbErr>;
 
     // Game Progress
     async fn get_game_progress(
@@ -88,6 +249,33 @@ pub trait ScoringRepositoryTrait: Send + Sync {
 
     async fn reset_game_progress(&self, user_id: i32, game_type_id: i32) -> Result<(), DbErr>;
 
+    // Flashcard Reviews
+    async fn get_flashcard_review(
+        &self,
+        user_id: i32,
+        flashcard_id: i32,
+    ) -> Result<Option<FlashcardReviewModel>, DbErr>;
+
+    /// Inserts or updates the review row for `(user_id, flashcard_id)`.
+    async fn save_flashcard_review(&self, model: &FlashcardReviewModel) -> Result<(), DbErr>;
+
+    /// Reviewed cards of the flashcard type due at or before `due_before`, most overdue first.
+    async fn get_due_flashcard_reviews(
+        &self,
+        user_id: i32,
+        flashcard_type_id: i32,
+        due_before: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<FlashcardReviewModel>, DbErr>;
+
+    /// Active cards of the flashcard type the user has never reviewed.
+    async fn get_unreviewed_flashcards(
+        &self,
+        user_id: i32,
+        flashcard_type_id: i32,
+        limit: u64,
+    ) -> Result<Vec<FlashcardReviewModel>, DbErr>;
+
     // Admin: Game Types
     async fn get_game_types_paged(
         &self,
@@ -114,6 +302,45 @@ pub trait ScoringRepositoryTrait: Send + Sync {
     async fn delete_achievement(&self, id: i32) -> Result<u64, DbErr>;
     async fn toggle_achievement_active(&self, id: i32) -> Result<bool, DbErr>;
 
+    // Admin: Achievement Backfills
+    async fn get_achievement_backfills_paged(
+        &self,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(Vec<AchievementBackfillModel>, u64), DbErr>;
+    async fn get_achievement_backfill_by_id(
+        &self,
+        id: i32,
+    ) -> Result<Option<AchievementBackfillModel>, DbErr>;
+    async fn create_achievement_backfill(
+        &self,
+        model: AchievementBackfillModel,
+    ) -> Result<i32, DbErr>;
+
+    /// The oldest queued or running backfill, locked until the transaction ends so that
+    /// only one worker advances it at a time.
+    async fn get_next_achievement_backfill_with_transaction(
+        &self,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Option<AchievementBackfillModel>, DbErr>;
+    async fn update_achievement_backfill_without_commit(
+        &self,
+        model: &AchievementBackfillModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<(), DbErr>;
+    async fn fail_achievement_backfill(&self, id: i32, error: &str) -> Result<(), DbErr>;
+
+    /// Number of users with at least one completed session
+    async fn count_users_with_completed_sessions(&self) -> Result<u64, DbErr>;
+
+    /// Ids of users with a completed session, above `after_user_id` and in ascending order.
+    async fn get_user_ids_with_completed_sessions_with_transaction(
+        &self,
+        after_user_id: i32,
+        limit: u64,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Vec<i32>, DbErr>;
+
     // Admin: Game Sessions
     async fn get_all_game_sessions_paged(
         &self,
@@ -128,5 +355,9 @@ pub trait ScoringRepositoryTrait: Send + Sync {
         page: u64,
         page_size: u64,
     ) -> Result<(Vec<UserStatsModel>, u64), DbErr>;
-    async fn reset_user_stats(&self, user_id: i32) -> Result<bool, DbErr>;
+    async fn reset_user_stats_without_commit(
+        &self,
+        user_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<bool, DbErr>;
 }
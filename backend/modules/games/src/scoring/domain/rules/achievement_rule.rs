@@ -0,0 +1,608 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use crate::scoring::domain::models::{AchievementBackfillUnlock, GameSessionModel};
+use crate::scoring::domain::rules::streak_local_day;
+
+/// What an achievement requires, stored as JSON on the achievement. It unlocks once every
+/// condition holds after a completed session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AchievementCriteria {
+    pub all: Vec<AchievementCondition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AchievementCondition {
+    pub metric: AchievementMetric,
+    pub comparator: AchievementComparator,
+    pub threshold: f64,
+    pub scope: AchievementScope,
+    /// Only sessions of this game type count, and only completing one can unlock
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_type_code: Option<String>,
+    /// Only sessions of this flashcard type count, and only completing one can unlock
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flashcard_type_id: Option<i32>,
+    /// Only sessions completed in the last so many days count
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_days: Option<i64>,
+}
+
+/// A figure computed over the sessions in scope
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AchievementMetric {
+    /// Number of sessions
+    GamesPlayed,
+    /// Sum of the scores
+    Score,
+    /// Highest score of one session
+    BestScore,
+    /// Average accuracy, in percent
+    Accuracy,
+    /// Sum of the time spent, in seconds
+    TimeSpentSeconds,
+    /// Longest combo of one session
+    ComboMax,
+    CorrectAnswers,
+    WrongAnswers,
+    /// Consecutive days with a session, up to the day of the latest one. Days are the
+    /// player's own and days a streak freeze covered bridge the run without adding to it.
+    StreakDays,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AchievementComparator {
+    Gte,
+    Gt,
+    Lte,
+    Lt,
+    Eq,
+}
+
+/// Which of the user's completed sessions a metric is computed over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AchievementScope {
+    /// The session just completed
+    Session,
+    /// Every session of the user
+    Lifetime,
+    /// Sessions of the same game type as the one just completed
+    GameType,
+    /// Sessions of the same flashcard type as the one just completed
+    FlashcardType,
+}
+
+/// The player's days, for metrics that count them
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AchievementCalendar {
+    pub utc_offset_minutes: i32,
+    /// Days a streak freeze covered
+    pub frozen_days: Vec<NaiveDate>,
+}
+
+impl AchievementCriteria {
+    /// Whether any condition needs more than the session just completed
+    pub fn needs_history(&self) -> bool {
+        self.all
+            .iter()
+            .any(|condition| condition.scope != AchievementScope::Session)
+    }
+
+    /// Checks the criteria can be evaluated; the message tells an admin what to fix
+    pub fn validate(&self) -> Result<(), String> {
+        if self.all.is_empty() {
+            return Err("Criteria need at least one condition".to_string());
+        }
+        for condition in &self.all {
+            if !condition.threshold.is_finite() {
+                return Err("Threshold must be a number".to_string());
+            }
+            if condition.window_days.is_some_and(|days| days < 1) {
+                return Err("Window must be at least one day".to_string());
+            }
+            if condition.scope == AchievementScope::Session && condition.window_days.is_some() {
+                return Err("A window only applies to sessions beyond the current one".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the criteria hold once `session` is completed. `history` is every completed
+    /// session of the user, including `session`.
+    pub fn is_met(
+        &self,
+        session: &GameSessionModel,
+        history: &[GameSessionModel],
+        calendar: &AchievementCalendar,
+        now: DateTime<Utc>,
+    ) -> bool {
+        !self.all.is_empty()
+            && self
+                .all
+                .iter()
+                .all(|condition| condition.is_met(session, history, calendar, now))
+    }
+
+    /// Replays `history` in completion order and returns when the criteria first held, as
+    /// if each session had just been completed at that time. `history` is every completed
+    /// session of the user, in any order.
+    pub fn first_met_at(
+        &self,
+        history: &[GameSessionModel],
+        calendar: &AchievementCalendar,
+    ) -> Option<DateTime<Utc>> {
+        let mut completed: Vec<GameSessionModel> = history
+            .iter()
+            .filter(|s| s.completed_at.is_some())
+            .cloned()
+            .collect();
+        completed.sort_by_key(|s| (s.completed_at, s.id));
+
+        (0..completed.len()).find_map(|index| {
+            let session = &completed[index];
+            let at = session.completed_at?;
+            self.is_met(session, &completed[..=index], calendar, at)
+                .then_some(at)
+        })
+    }
+}
+
+/// What replaying `history` would have unlocked out of `pending` achievements, given as
+/// `(id, points, criteria)`, each dated when its criteria first held.
+pub fn backfill_unlocks(
+    pending: &[&(i32, i32, AchievementCriteria)],
+    history: &[GameSessionModel],
+    calendar: &AchievementCalendar,
+) -> Vec<AchievementBackfillUnlock> {
+    pending
+        .iter()
+        .filter_map(|(achievement_id, points, criteria)| {
+            criteria
+                .first_met_at(history, calendar)
+                .map(|unlocked_at| AchievementBackfillUnlock {
+                    achievement_id: *achievement_id,
+                    points: *points,
+                    unlocked_at,
+                })
+        })
+        .collect()
+}
+
+impl AchievementCondition {
+    pub fn is_met(
+        &self,
+        session: &GameSessionModel,
+        history: &[GameSessionModel],
+        calendar: &AchievementCalendar,
+        now: DateTime<Utc>,
+    ) -> bool {
+        if !self.matches_filters(session) {
+            return false;
+        }
+        if self.scope == AchievementScope::FlashcardType && session.flashcard_type_id.is_none() {
+            return false;
+        }
+
+        let window_start = self.window_days.map(|days| now - Duration::days(days));
+        let sessions: Vec<&GameSessionModel> = match self.scope {
+            AchievementScope::Session => vec![session],
+            _ => history
+                .iter()
+                .filter(|s| self.matches_filters(s))
+                .filter(|s| match self.scope {
+                    AchievementScope::GameType => s.game_type_id == session.game_type_id,
+                    AchievementScope::FlashcardType => {
+                        s.flashcard_type_id == session.flashcard_type_id
+                    }
+                    _ => true,
+                })
+                .filter(|s| {
+                    window_start.is_none_or(|start| s.completed_at.is_some_and(|at| at >= start))
+                })
+                .collect(),
+        };
+
+        self.comparator
+            .compare(self.metric.value(&sessions, calendar), self.threshold)
+    }
+
+    fn matches_filters(&self, session: &GameSessionModel) -> bool {
+        self.game_type_code
+            .as_ref()
+            .is_none_or(|code| session.game_type_code.as_ref() == Some(code))
+            && self
+                .flashcard_type_id
+                .is_none_or(|id| session.flashcard_type_id == Some(id))
+    }
+}
+
+impl AchievementMetric {
+    /// The metric over `sessions`; zero when there are none
+    pub fn value(&self, sessions: &[&GameSessionModel], calendar: &AchievementCalendar) -> f64 {
+        let sum =
+            |f: fn(&GameSessionModel) -> i32| -> f64 { sessions.iter().map(|s| f(s) as f64).sum() };
+        let max = |f: fn(&GameSessionModel) -> i32| -> f64 {
+            sessions.iter().map(|s| f(s)).max().unwrap_or(0) as f64
+        };
+
+        match self {
+            AchievementMetric::GamesPlayed => sessions.len() as f64,
+            AchievementMetric::Score => sum(|s| s.score),
+            AchievementMetric::BestScore => max(|s| s.score),
+            AchievementMetric::Accuracy => {
+                if sessions.is_empty() {
+                    return 0.0;
+                }
+                // Sessions without an accuracy count as zero, as in the user's stats
+                let total: f64 = sessions
+                    .iter()
+                    .map(|s| s.accuracy.and_then(|a| a.to_f64()).unwrap_or(0.0))
+                    .sum();
+                total / sessions.len() as f64
+            }
+            AchievementMetric::TimeSpentSeconds => sum(|s| s.time_spent_seconds),
+            AchievementMetric::ComboMax => max(|s| s.combo_max),
+            AchievementMetric::CorrectAnswers => sum(|s| s.correct_answers),
+            AchievementMetric::WrongAnswers => sum(|s| s.wrong_answers),
+            AchievementMetric::StreakDays => streak_days(sessions, calendar) as f64,
+        }
+    }
+}
+
+impl AchievementComparator {
+    pub fn compare(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            AchievementComparator::Gte => value >= threshold,
+            AchievementComparator::Gt => value > threshold,
+            AchievementComparator::Lte => value <= threshold,
+            AchievementComparator::Lt => value < threshold,
+            AchievementComparator::Eq => value == threshold,
+        }
+    }
+}
+
+/// Days in a row with a completed session, counting back from the latest one
+fn streak_days(sessions: &[&GameSessionModel], calendar: &AchievementCalendar) -> i64 {
+    let mut days: Vec<NaiveDate> = sessions
+        .iter()
+        .filter_map(|s| s.completed_at)
+        .map(|at| streak_local_day(at, calendar.utc_offset_minutes))
+        .collect();
+    days.sort();
+    days.dedup();
+
+    let mut streak = 0;
+    let mut expected = days.last().copied();
+    while let Some(day) = expected {
+        if days.binary_search(&day).is_ok() {
+            streak += 1;
+        } else if !calendar.frozen_days.contains(&day) {
+            break;
+        }
+        expected = day.pred_opt();
+    }
+    streak
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::domain::models::{AchievementBackfillModel, AchievementBackfillStatuses};
+    use rex_game_shared::test_helpers::now;
+    use rust_decimal::Decimal;
+
+    fn session(id: i32, game_type_code: &str, score: i32, days_ago: i64) -> GameSessionModel {
+        GameSessionModel {
+            id,
+            user_id: 1,
+            user_name: None,
+            user_display_name: None,
+            game_type_id: if game_type_code == "quiz" { 1 } else { 2 },
+            game_type_code: Some(game_type_code.to_string()),
+            game_type_name: None,
+            flashcard_type_id: Some(7),
+            score,
+            max_score: None,
+            accuracy: Some(Decimal::from(100)),
+            time_spent_seconds: 20,
+            cards_played: 5,
+            correct_answers: 5,
+            wrong_answers: 0,
+            combo_max: 5,
+            started_at: now() - Duration::days(days_ago),
+            completed_at: Some(now() - Duration::days(days_ago)),
+            status_id: 2,
+            created_on: now() - Duration::days(days_ago),
+        }
+    }
+
+    fn condition(
+        metric: AchievementMetric,
+        threshold: f64,
+        scope: AchievementScope,
+    ) -> AchievementCondition {
+        AchievementCondition {
+            metric,
+            comparator: AchievementComparator::Gte,
+            threshold,
+            scope,
+            game_type_code: None,
+            flashcard_type_id: None,
+            window_days: None,
+        }
+    }
+
+    fn criteria(all: Vec<AchievementCondition>) -> AchievementCriteria {
+        AchievementCriteria { all }
+    }
+
+    fn calendar() -> AchievementCalendar {
+        AchievementCalendar::default()
+    }
+
+    fn backfill(is_dry_run: bool) -> AchievementBackfillModel {
+        AchievementBackfillModel {
+            id: 1,
+            achievement_id: None,
+            is_dry_run,
+            status: AchievementBackfillStatuses::Queued,
+            last_user_id: 0,
+            total_users: 3,
+            processed_users: 0,
+            affected_users: 0,
+            unlocked_count: 0,
+            last_error: None,
+            created_by_id: None,
+            started_on: None,
+            completed_on: None,
+            created_on: now(),
+            updated_on: now(),
+        }
+    }
+
+    #[test]
+    fn test_session_scope_ignores_history() {
+        let current = session(3, "quiz", 50, 0);
+        let history = vec![session(1, "quiz", 500, 2), session(3, "quiz", 50, 0)];
+        let criteria = criteria(vec![condition(
+            AchievementMetric::Score,
+            100.0,
+            AchievementScope::Session,
+        )]);
+        assert!(!criteria.is_met(&current, &history, &calendar(), now()));
+    }
+
+    #[test]
+    fn test_lifetime_scope_sums_every_session() {
+        let current = session(3, "quiz", 50, 0);
+        let history = vec![
+            session(1, "quiz", 500, 2),
+            session(2, "spelling", 500, 1),
+            current.clone(),
+        ];
+        let criteria = criteria(vec![condition(
+            AchievementMetric::Score,
+            1000.0,
+            AchievementScope::Lifetime,
+        )]);
+        assert!(criteria.is_met(&current, &history, &calendar(), now()));
+    }
+
+    #[test]
+    fn test_game_type_scope_counts_the_same_game_only() {
+        let current = session(3, "quiz", 50, 0);
+        let history = vec![
+            session(1, "quiz", 500, 2),
+            session(2, "spelling", 500, 1),
+            current.clone(),
+        ];
+        let criteria = criteria(vec![condition(
+            AchievementMetric::GamesPlayed,
+            3.0,
+            AchievementScope::GameType,
+        )]);
+        assert!(!criteria.is_met(&current, &history, &calendar(), now()));
+
+        let mut spelling_games = condition(
+            AchievementMetric::GamesPlayed,
+            1.0,
+            AchievementScope::Lifetime,
+        );
+        spelling_games.game_type_code = Some("spelling".to_string());
+        assert!(!self::criteria(vec![spelling_games]).is_met(
+            &current,
+            &history,
+            &calendar(),
+            now()
+        ));
+    }
+
+    #[test]
+    fn test_window_drops_older_sessions() {
+        let current = session(3, "quiz", 50, 0);
+        let history = vec![session(1, "quiz", 500, 10), current.clone()];
+        let mut recent_games = condition(
+            AchievementMetric::GamesPlayed,
+            2.0,
+            AchievementScope::Lifetime,
+        );
+        recent_games.window_days = Some(7);
+        assert!(!criteria(vec![recent_games]).is_met(&current, &history, &calendar(), now()));
+    }
+
+    #[test]
+    fn test_streak_counts_consecutive_days_back_from_the_latest() {
+        let sessions = [
+            session(1, "quiz", 10, 5),
+            session(2, "quiz", 10, 2),
+            session(3, "quiz", 10, 1),
+            session(4, "quiz", 10, 1),
+            session(5, "quiz", 10, 0),
+        ];
+        let refs: Vec<&GameSessionModel> = sessions.iter().collect();
+        assert_eq!(AchievementMetric::StreakDays.value(&refs, &calendar()), 3.0);
+    }
+
+    #[test]
+    fn test_streak_follows_the_player_days_and_freezes() {
+        // Late in the evening UTC is already the next day at UTC+2
+        let mut late = session(1, "quiz", 10, 3);
+        late.completed_at = late.completed_at.map(|at| at + Duration::hours(14));
+        let sessions = [late, session(2, "quiz", 10, 1)];
+        let refs: Vec<&GameSessionModel> = sessions.iter().collect();
+        let mut calendar = AchievementCalendar {
+            utc_offset_minutes: 120,
+            ..Default::default()
+        };
+        assert_eq!(AchievementMetric::StreakDays.value(&refs, &calendar), 2.0);
+        calendar.utc_offset_minutes = 0;
+        assert_eq!(AchievementMetric::StreakDays.value(&refs, &calendar), 1.0);
+
+        let frozen_day = (now() - Duration::days(2)).date_naive();
+        calendar.frozen_days = vec![frozen_day];
+        let sessions = [session(1, "quiz", 10, 3), session(2, "quiz", 10, 1)];
+        let refs: Vec<&GameSessionModel> = sessions.iter().collect();
+        assert_eq!(AchievementMetric::StreakDays.value(&refs, &calendar), 2.0);
+    }
+
+    #[test]
+    fn test_first_met_at_uses_the_window_at_that_time() {
+        let mut windowed = condition(
+            AchievementMetric::GamesPlayed,
+            2.0,
+            AchievementScope::Lifetime,
+        );
+        windowed.window_days = Some(2);
+        let criteria = criteria(vec![windowed]);
+
+        let spread = vec![
+            session(1, "quiz", 10, 9),
+            session(2, "quiz", 10, 6),
+            session(3, "quiz", 10, 3),
+        ];
+        assert_eq!(criteria.first_met_at(&spread, &calendar()), None);
+
+        let close = vec![session(1, "quiz", 10, 9), session(2, "quiz", 10, 8)];
+        assert_eq!(
+            criteria.first_met_at(&close, &calendar()),
+            Some(now() - Duration::days(8))
+        );
+    }
+
+    #[test]
+    fn test_backfill_unlocks_are_dated_by_the_session_that_met_them() {
+        let history = vec![session(1, "quiz", 600, 4), session(2, "quiz", 600, 2)];
+        let first_game = (
+            1,
+            10,
+            criteria(vec![condition(
+                AchievementMetric::GamesPlayed,
+                1.0,
+                AchievementScope::Lifetime,
+            )]),
+        );
+        let high_score = (
+            2,
+            50,
+            criteria(vec![condition(
+                AchievementMetric::Score,
+                1000.0,
+                AchievementScope::Lifetime,
+            )]),
+        );
+        let out_of_reach = (
+            3,
+            90,
+            criteria(vec![condition(
+                AchievementMetric::GamesPlayed,
+                5.0,
+                AchievementScope::Lifetime,
+            )]),
+        );
+
+        let unlocks = backfill_unlocks(
+            &[&first_game, &high_score, &out_of_reach],
+            &history,
+            &calendar(),
+        );
+        assert_eq!(
+            unlocks,
+            vec![
+                AchievementBackfillUnlock {
+                    achievement_id: 1,
+                    points: 10,
+                    unlocked_at: now() - Duration::days(4),
+                },
+                AchievementBackfillUnlock {
+                    achievement_id: 2,
+                    points: 50,
+                    unlocked_at: now() - Duration::days(2),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dry_run_backfill_counts_unlocks_without_writing_them() {
+        let unlocks = vec![AchievementBackfillUnlock {
+            achievement_id: 1,
+            points: 10,
+            unlocked_at: now(),
+        }];
+
+        let mut dry_run = backfill(true);
+        assert!(dry_run.record_user(4, &unlocks).is_empty());
+        assert_eq!(dry_run.affected_users, 1);
+        assert_eq!(dry_run.unlocked_count, 1);
+
+        let mut real = backfill(false);
+        assert_eq!(real.record_user(4, &unlocks), unlocks.as_slice());
+    }
+
+    #[test]
+    fn test_backfill_progress_moves_the_cursor_and_completes_on_a_short_batch() {
+        let unlocks = vec![
+            AchievementBackfillUnlock {
+                achievement_id: 1,
+                points: 10,
+                unlocked_at: now(),
+            },
+            AchievementBackfillUnlock {
+                achievement_id: 2,
+                points: 50,
+                unlocked_at: now(),
+            },
+        ];
+        let mut backfill = backfill(false);
+        backfill.start(now());
+        assert_eq!(backfill.status, AchievementBackfillStatuses::Running);
+        assert_eq!(backfill.started_on, Some(now()));
+
+        backfill.record_user(4, &unlocks);
+        backfill.record_user(7, &[]);
+        backfill.finish_batch(false, now());
+        assert_eq!(backfill.status, AchievementBackfillStatuses::Running);
+        assert_eq!(
+            (
+                backfill.processed_users,
+                backfill.affected_users,
+                backfill.unlocked_count,
+                backfill.last_user_id
+            ),
+            (2, 1, 2, 7)
+        );
+
+        let later = now() + Duration::minutes(1);
+        backfill.start(later);
+        backfill.record_user(9, &[]);
+        backfill.finish_batch(true, later);
+        assert_eq!(backfill.started_on, Some(now()));
+        assert_eq!(backfill.status, AchievementBackfillStatuses::Completed);
+        assert_eq!(backfill.completed_on, Some(later));
+        assert_eq!(backfill.last_user_id, 9);
+    }
+}
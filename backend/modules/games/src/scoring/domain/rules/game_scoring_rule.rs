@@ -0,0 +1,178 @@
+/// Timing information the server measured for a single answer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnswerTiming {
+    /// Milliseconds since the previous answer (or since the session started).
+    pub response_ms: i64,
+    /// Milliseconds since the session started.
+    pub elapsed_ms: i64,
+}
+
+/// Per-game-type scoring rules. Mirrors the formulas the client games use so
+/// the score shown in-game matches the one stored on the session.
+pub trait GameScoringRule: Send + Sync {
+    /// Number of cards dealt for one round.
+    fn round_size(&self) -> u64;
+
+    /// Whether a wrong answer leaves the dealt card open for another attempt.
+    fn allows_retry(&self) -> bool {
+        false
+    }
+
+    fn is_correct(&self, expected: &str, answer: &str, is_match: Option<bool>) -> bool {
+        let _ = is_match;
+        normalize_answer(expected) == normalize_answer(answer)
+    }
+
+    /// Points for one answer. `combo` is the streak of correct answers before this one.
+    fn points(&self, is_correct: bool, combo: i32, timing: AnswerTiming) -> i32;
+
+    /// Best possible score for a round of `card_count` cards.
+    fn max_score(&self, card_count: i32) -> i32;
+}
+
+pub struct MemoryMatchScoringRule;
+pub struct QuizScoringRule;
+pub struct SpeedMatchScoringRule;
+pub struct SpellingScoringRule;
+
+impl GameScoringRule for MemoryMatchScoringRule {
+    fn round_size(&self) -> u64 {
+        6
+    }
+
+    fn allows_retry(&self) -> bool {
+        true
+    }
+
+    fn points(&self, is_correct: bool, _combo: i32, timing: AnswerTiming) -> i32 {
+        if !is_correct {
+            return 0;
+        }
+        100 + time_bonus(30, timing.response_ms, 10)
+    }
+
+    fn max_score(&self, card_count: i32) -> i32 {
+        card_count * (100 + 30 * 10)
+    }
+}
+
+impl GameScoringRule for QuizScoringRule {
+    fn round_size(&self) -> u64 {
+        10
+    }
+
+    fn points(&self, is_correct: bool, _combo: i32, timing: AnswerTiming) -> i32 {
+        if !is_correct {
+            return 0;
+        }
+        100 + time_bonus(10, timing.response_ms, 10)
+    }
+
+    fn max_score(&self, card_count: i32) -> i32 {
+        card_count * (100 + 10 * 10)
+    }
+}
+
+impl GameScoringRule for SpeedMatchScoringRule {
+    fn round_size(&self) -> u64 {
+        15
+    }
+
+    /// The player judges whether the name shown matches the card, so the answer
+    /// is the name that was shown plus the player's match / no-match call.
+    fn is_correct(&self, expected: &str, answer: &str, is_match: Option<bool>) -> bool {
+        let shown_matches = normalize_answer(expected) == normalize_answer(answer);
+        shown_matches == is_match.unwrap_or(true)
+    }
+
+    fn points(&self, is_correct: bool, combo: i32, timing: AnswerTiming) -> i32 {
+        if !is_correct {
+            return 0;
+        }
+        // Time bonus is based on what is left of the 60 second round.
+        100 + time_bonus(60, timing.elapsed_ms, 2) + combo * 10
+    }
+
+    fn max_score(&self, card_count: i32) -> i32 {
+        (0..card_count).map(|combo| 100 + 60 * 2 + combo * 10).sum()
+    }
+}
+
+impl GameScoringRule for SpellingScoringRule {
+    fn round_size(&self) -> u64 {
+        5
+    }
+
+    fn points(&self, is_correct: bool, _combo: i32, timing: AnswerTiming) -> i32 {
+        if !is_correct {
+            return 0;
+        }
+        150 + time_bonus(30, timing.response_ms, 2)
+    }
+
+    fn max_score(&self, card_count: i32) -> i32 {
+        card_count * (150 + 30 * 2)
+    }
+}
+
+/// Returns the scoring rule for a game type code, or `None` for unknown games.
+pub fn scoring_rule_for(game_type_code: &str) -> Option<Box<dyn GameScoringRule>> {
+    match game_type_code {
+        "memory_match" => Some(Box::new(MemoryMatchScoringRule)),
+        "quiz" => Some(Box::new(QuizScoringRule)),
+        "speed_match" => Some(Box::new(SpeedMatchScoringRule)),
+        "spelling" => Some(Box::new(SpellingScoringRule)),
+        _ => None,
+    }
+}
+
+pub fn normalize_answer(answer: &str) -> String {
+    answer.trim().to_lowercase()
+}
+
+fn time_bonus(limit_secs: i64, taken_ms: i64, multiplier: i64) -> i32 {
+    let taken_secs = taken_ms.max(0) / 1000;
+    ((limit_secs - taken_secs).max(0) * multiplier) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(response_ms: i64, elapsed_ms: i64) -> AnswerTiming {
+        AnswerTiming {
+            response_ms,
+            elapsed_ms,
+        }
+    }
+
+    #[test]
+    fn test_answers_are_compared_case_insensitively() {
+        let rule = QuizScoringRule;
+        assert!(rule.is_correct("Apple", "  apple ", None));
+        assert!(!rule.is_correct("Apple", "apples", None));
+    }
+
+    #[test]
+    fn test_quiz_time_bonus_expires_after_ten_seconds() {
+        assert_eq!(QuizScoringRule.points(true, 0, timing(2_500, 0)), 180);
+        assert_eq!(QuizScoringRule.points(true, 0, timing(15_000, 0)), 100);
+        assert_eq!(QuizScoringRule.points(false, 0, timing(2_500, 0)), 0);
+    }
+
+    #[test]
+    fn test_speed_match_uses_match_call() {
+        let rule = SpeedMatchScoringRule;
+        assert!(rule.is_correct("Cat", "Cat", Some(true)));
+        assert!(rule.is_correct("Cat", "Dog", Some(false)));
+        assert!(!rule.is_correct("Cat", "Dog", Some(true)));
+    }
+
+    #[test]
+    fn test_speed_match_rewards_combo_and_remaining_time() {
+        assert_eq!(
+            SpeedMatchScoringRule.points(true, 3, timing(500, 50_000)),
+            150
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::scoring::domain::models::{GameSessionModel, GameSessionStatuses};
+
+/// Sessions left open longer than this can no longer be answered or completed.
+pub const MAX_SESSION_DURATION_MINUTES: i64 = 120;
+
+/// Why a session can no longer be answered, completed or abandoned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionClosed {
+    /// The session belongs to another player
+    NotOwned,
+    /// The session already moved to a final status
+    Finished(GameSessionStatuses),
+    UnknownStatus,
+    /// The session is still started but ran past `MAX_SESSION_DURATION_MINUTES`
+    Expired,
+}
+
+/// Checks that `session` belongs to `user_id` and still accepts answers at `now`.
+pub fn check_open_session(
+    session: &GameSessionModel,
+    user_id: i32,
+    now: DateTime<Utc>,
+) -> Result<(), SessionClosed> {
+    if session.user_id != user_id {
+        return Err(SessionClosed::NotOwned);
+    }
+
+    match GameSessionStatuses::try_from(session.status_id) {
+        Ok(GameSessionStatuses::Started) => {}
+        Ok(status) => return Err(SessionClosed::Finished(status)),
+        Err(_) => return Err(SessionClosed::UnknownStatus),
+    }
+
+    if session.started_at + Duration::minutes(MAX_SESSION_DURATION_MINUTES) < now {
+        return Err(SessionClosed::Expired);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rex_game_shared::test_helpers::now;
+
+    fn session(user_id: i32, status: GameSessionStatuses, minutes_ago: i64) -> GameSessionModel {
+        GameSessionModel {
+            id: 1,
+            user_id,
+            user_name: None,
+            user_display_name: None,
+            game_type_id: 1,
+            game_type_code: Some("quiz".to_string()),
+            game_type_name: None,
+            flashcard_type_id: None,
+            score: 0,
+            max_score: None,
+            accuracy: None,
+            time_spent_seconds: 0,
+            cards_played: 0,
+            correct_answers: 0,
+            wrong_answers: 0,
+            combo_max: 0,
+            started_at: now() - Duration::minutes(minutes_ago),
+            completed_at: None,
+            status_id: status as i32,
+            created_on: now() - Duration::minutes(minutes_ago),
+        }
+    }
+
+    #[test]
+    fn test_only_a_started_session_of_the_player_is_open() {
+        let started = session(1, GameSessionStatuses::Started, 5);
+        assert_eq!(check_open_session(&started, 1, now()), Ok(()));
+        assert_eq!(
+            check_open_session(&started, 2, now()),
+            Err(SessionClosed::NotOwned)
+        );
+
+        let completed = session(1, GameSessionStatuses::Completed, 5);
+        assert_eq!(
+            check_open_session(&completed, 1, now()),
+            Err(SessionClosed::Finished(GameSessionStatuses::Completed))
+        );
+    }
+
+    #[test]
+    fn test_session_past_max_duration_is_expired() {
+        let stale = session(
+            1,
+            GameSessionStatuses::Started,
+            MAX_SESSION_DURATION_MINUTES + 1,
+        );
+        assert_eq!(
+            check_open_session(&stale, 1, now()),
+            Err(SessionClosed::Expired)
+        );
+
+        let last_minute = session(
+            1,
+            GameSessionStatuses::Started,
+            MAX_SESSION_DURATION_MINUTES,
+        );
+        assert_eq!(check_open_session(&last_minute, 1, now()), Ok(()));
+    }
+}
@@ -0,0 +1,191 @@
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, Utc};
+
+/// Key stored in a leaderboard rollup when the row covers every game or flashcard type.
+pub const LEADERBOARD_ANY: i32 = 0;
+
+/// Time window a leaderboard ranks over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderboardPeriod {
+    Daily,
+    /// Weeks start on Monday
+    Weekly,
+    Monthly,
+    AllTime,
+}
+
+impl LeaderboardPeriod {
+    pub const ALL: [LeaderboardPeriod; 4] = [
+        LeaderboardPeriod::Daily,
+        LeaderboardPeriod::Weekly,
+        LeaderboardPeriod::Monthly,
+        LeaderboardPeriod::AllTime,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LeaderboardPeriod::Daily => "daily",
+            LeaderboardPeriod::Weekly => "weekly",
+            LeaderboardPeriod::Monthly => "monthly",
+            LeaderboardPeriod::AllTime => "all_time",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "daily" => Some(LeaderboardPeriod::Daily),
+            "weekly" => Some(LeaderboardPeriod::Weekly),
+            "monthly" => Some(LeaderboardPeriod::Monthly),
+            "all_time" => Some(LeaderboardPeriod::AllTime),
+            _ => None,
+        }
+    }
+
+    /// First day of the window containing `at`, as seen from the leaderboard's offset.
+    pub fn start_of(&self, at: DateTime<Utc>, offset: FixedOffset) -> NaiveDate {
+        let day = at.with_timezone(&offset).date_naive();
+        match self {
+            LeaderboardPeriod::Daily => day,
+            LeaderboardPeriod::Weekly => {
+                day - Duration::days(day.weekday().num_days_from_monday() as i64)
+            }
+            LeaderboardPeriod::Monthly => day.with_day(1).unwrap_or(day),
+            LeaderboardPeriod::AllTime => NaiveDate::from_ymd_opt(1970, 1, 1).unwrap_or(day),
+        }
+    }
+}
+
+/// Game and flashcard type keys of one leaderboard, `LEADERBOARD_ANY` when not filtered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeaderboardScope {
+    pub game_type_id: i32,
+    pub flashcard_type_id: i32,
+}
+
+impl LeaderboardScope {
+    pub fn new(game_type_id: Option<i32>, flashcard_type_id: Option<i32>) -> Self {
+        Self {
+            game_type_id: game_type_id.unwrap_or(LEADERBOARD_ANY),
+            flashcard_type_id: flashcard_type_id.unwrap_or(LEADERBOARD_ANY),
+        }
+    }
+
+    /// Every leaderboard a completed session counts towards.
+    pub fn for_session(game_type_id: i32, flashcard_type_id: Option<i32>) -> Vec<Self> {
+        let mut scopes = vec![Self::new(None, None), Self::new(Some(game_type_id), None)];
+        if flashcard_type_id.is_some() {
+            scopes.push(Self::new(None, flashcard_type_id));
+            scopes.push(Self::new(Some(game_type_id), flashcard_type_id));
+        }
+        scopes
+    }
+}
+
+/// One leaderboard: a period window narrowed to a game and flashcard type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeaderboardBucket {
+    pub period: LeaderboardPeriod,
+    pub period_start: NaiveDate,
+    pub scope: LeaderboardScope,
+}
+
+impl LeaderboardBucket {
+    pub fn containing(
+        period: LeaderboardPeriod,
+        scope: LeaderboardScope,
+        at: DateTime<Utc>,
+        offset: FixedOffset,
+    ) -> Self {
+        Self {
+            period,
+            period_start: period.start_of(at, offset),
+            scope,
+        }
+    }
+
+    /// Every bucket a session completed at `at` adds its score to.
+    pub fn for_session(
+        game_type_id: i32,
+        flashcard_type_id: Option<i32>,
+        at: DateTime<Utc>,
+        offset: FixedOffset,
+    ) -> Vec<Self> {
+        let scopes = LeaderboardScope::for_session(game_type_id, flashcard_type_id);
+        LeaderboardPeriod::ALL
+            .into_iter()
+            .flat_map(|period| {
+                scopes
+                    .iter()
+                    .map(move |scope| Self::containing(period, *scope, at, offset))
+            })
+            .collect()
+    }
+}
+
+/// Offset of the first row shown around a player, keeping them centred where possible.
+pub fn leaderboard_window_offset(rank: u64, radius: u64) -> u64 {
+    rank.saturating_sub(radius + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_periods_start_in_the_leaderboard_offset() {
+        // Sunday 2026-10-18 20:30 UTC is already Monday morning at +07:00
+        let at = Utc.with_ymd_and_hms(2026, 10, 18, 20, 30, 0).unwrap();
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let bangkok = FixedOffset::east_opt(7 * 3600).unwrap();
+
+        let start = |period: LeaderboardPeriod, offset| period.start_of(at, offset);
+
+        assert_eq!(start(LeaderboardPeriod::Daily, utc), date(2026, 10, 18));
+        assert_eq!(start(LeaderboardPeriod::Weekly, utc), date(2026, 10, 12));
+        assert_eq!(start(LeaderboardPeriod::Daily, bangkok), date(2026, 10, 19));
+        assert_eq!(
+            start(LeaderboardPeriod::Weekly, bangkok),
+            date(2026, 10, 19)
+        );
+        assert_eq!(
+            start(LeaderboardPeriod::Monthly, bangkok),
+            date(2026, 10, 1)
+        );
+        assert_eq!(start(LeaderboardPeriod::AllTime, bangkok), date(1970, 1, 1));
+    }
+
+    #[test]
+    fn test_session_scopes_skip_missing_flashcard_type() {
+        assert_eq!(LeaderboardScope::for_session(3, None).len(), 2);
+
+        let scopes = LeaderboardScope::for_session(3, Some(5));
+        assert_eq!(scopes.len(), 4);
+        assert!(scopes.contains(&LeaderboardScope::new(None, Some(5))));
+        assert!(scopes.contains(&LeaderboardScope::new(Some(3), Some(5))));
+    }
+
+    #[test]
+    fn test_session_counts_towards_every_period_and_scope() {
+        let at = Utc.with_ymd_and_hms(2026, 10, 17, 9, 0, 0).unwrap();
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let buckets = LeaderboardBucket::for_session(3, Some(5), at, utc);
+
+        assert_eq!(buckets.len(), 16);
+        assert!(buckets.contains(&LeaderboardBucket {
+            period: LeaderboardPeriod::Monthly,
+            period_start: date(2026, 10, 1),
+            scope: LeaderboardScope::new(Some(3), None),
+        }));
+    }
+
+    #[test]
+    fn test_window_offset_clamps_at_the_top() {
+        assert_eq!(leaderboard_window_offset(1, 5), 0);
+        assert_eq!(leaderboard_window_offset(6, 5), 0);
+        assert_eq!(leaderboard_window_offset(20, 5), 14);
+    }
+}
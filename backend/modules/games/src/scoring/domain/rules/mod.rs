@@ -0,0 +1,15 @@
+pub mod achievement_rule;
+pub mod game_scoring_rule;
+pub mod game_session_rule;
+pub mod leaderboard_period;
+pub mod progression_rule;
+pub mod review_schedule;
+pub mod streak_rule;
+
+pub use achievement_rule::*;
+pub use game_scoring_rule::*;
+pub use game_session_rule::*;
+pub use leaderboard_period::*;
+pub use progression_rule::*;
+pub use review_schedule::*;
+pub use streak_rule::*;
@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// XP needed for each level: reaching level `n` takes `base_xp * (n - 1) ^ exponent` XP
+/// in total, so every level costs a little more than the one before.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LevelCurve {
+    pub base_xp: i64,
+    pub exponent: f64,
+    pub max_level: i32,
+}
+
+impl Default for LevelCurve {
+    fn default() -> Self {
+        Self {
+            base_xp: 100,
+            exponent: 1.5,
+            max_level: 100,
+        }
+    }
+}
+
+/// Where a total amount of XP puts a player on the curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelProgress {
+    pub level: i32,
+    pub total_xp: i64,
+    /// Total XP at which the current level was reached
+    pub level_xp: i64,
+    /// Total XP needed for the next level, `None` at the top level
+    pub next_level_xp: Option<i64>,
+}
+
+impl LevelCurve {
+    /// Total XP needed to reach `level`; level 1 needs none.
+    pub fn xp_for_level(&self, level: i32) -> i64 {
+        let steps = (level.clamp(1, self.max_level) - 1) as f64;
+        (self.base_xp as f64 * steps.powf(self.exponent)).round() as i64
+    }
+
+    pub fn level_for_xp(&self, total_xp: i64) -> i32 {
+        (2..=self.max_level)
+            .take_while(|level| self.xp_for_level(*level) <= total_xp)
+            .last()
+            .unwrap_or(1)
+    }
+
+    pub fn progress(&self, total_xp: i64) -> LevelProgress {
+        let level = self.level_for_xp(total_xp);
+        LevelProgress {
+            level,
+            total_xp,
+            level_xp: self.xp_for_level(level),
+            next_level_xp: (level < self.max_level).then(|| self.xp_for_level(level + 1)),
+        }
+    }
+
+    /// Every level reached by going from `before_xp` to `after_xp`, lowest first.
+    pub fn levels_gained(&self, before_xp: i64, after_xp: i64) -> Vec<i32> {
+        (self.level_for_xp(before_xp) + 1..=self.level_for_xp(after_xp)).collect()
+    }
+}
+
+/// Where XP in the ledger came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XpSources {
+    /// Completing a session, by game type
+    Session,
+    /// Bonus for the accuracy of a completed session
+    Accuracy,
+    /// Points of the achievements a session unlocked
+    Achievement,
+}
+
+impl XpSources {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            XpSources::Session => "session",
+            XpSources::Accuracy => "accuracy",
+            XpSources::Achievement => "achievement",
+        }
+    }
+}
+
+/// XP earned by one completed session, per source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct XpAward {
+    pub session_xp: i32,
+    pub accuracy_xp: i32,
+    pub achievement_xp: i32,
+}
+
+impl XpAward {
+    pub fn total(&self) -> i32 {
+        self.session_xp + self.accuracy_xp + self.achievement_xp
+    }
+
+    /// The non-zero amounts, as ledger entries.
+    pub fn entries(&self) -> Vec<(XpSources, i32)> {
+        [
+            (XpSources::Session, self.session_xp),
+            (XpSources::Accuracy, self.accuracy_xp),
+            (XpSources::Achievement, self.achievement_xp),
+        ]
+        .into_iter()
+        .filter(|(_, amount)| *amount > 0)
+        .collect()
+    }
+}
+
+/// How XP is earned and turned into levels. Admins edit it as JSON in the system settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProgressionSettings {
+    pub curve: LevelCurve,
+    /// XP for completing a session of a game type not listed in `game_type_xp`
+    pub session_xp: i32,
+    /// XP for completing a session, by game type code
+    #[serde(default)]
+    pub game_type_xp: BTreeMap<String, i32>,
+    /// XP added for a session with 100% accuracy, scaled down with the accuracy
+    pub accuracy_bonus_xp: i32,
+    /// Cards a session needs for its full session and accuracy XP; shorter ones earn a share
+    #[serde(default = "default_full_session_cards")]
+    pub full_session_cards: i32,
+}
+
+fn default_full_session_cards() -> i32 {
+    10
+}
+
+impl Default for ProgressionSettings {
+    fn default() -> Self {
+        Self {
+            curve: LevelCurve::default(),
+            session_xp: 50,
+            game_type_xp: BTreeMap::new(),
+            accuracy_bonus_xp: 50,
+            full_session_cards: default_full_session_cards(),
+        }
+    }
+}
+
+impl ProgressionSettings {
+    /// Checks the settings can be used; the message tells an admin what to fix
+    pub fn validate(&self) -> Result<(), String> {
+        if self.curve.base_xp < 1 {
+            return Err("Base XP must be at least 1".to_string());
+        }
+        if !(1.0..=3.0).contains(&self.curve.exponent) {
+            return Err("Exponent must be between 1 and 3".to_string());
+        }
+        if !(1..=1000).contains(&self.curve.max_level) {
+            return Err("Max level must be between 1 and 1000".to_string());
+        }
+        if self.session_xp < 0
+            || self.accuracy_bonus_xp < 0
+            || self.game_type_xp.values().any(|xp| *xp < 0)
+        {
+            return Err("XP amounts cannot be negative".to_string());
+        }
+        if !(1..=1000).contains(&self.full_session_cards) {
+            return Err("Full session cards must be between 1 and 1000".to_string());
+        }
+        Ok(())
+    }
+
+    /// XP for a completed session. Sessions without an answered card earn nothing for
+    /// playing, shorter ones than `full_session_cards` a share of it; `achievement_points`
+    /// are those of the achievements the session unlocked.
+    pub fn session_award(
+        &self,
+        game_type_code: &str,
+        cards_played: i32,
+        accuracy: f64,
+        achievement_points: i32,
+    ) -> XpAward {
+        if cards_played < 1 {
+            return XpAward {
+                achievement_xp: achievement_points.max(0),
+                ..Default::default()
+            };
+        }
+
+        let share =
+            cards_played.min(self.full_session_cards) as f64 / self.full_session_cards as f64;
+        let session_xp = self
+            .game_type_xp
+            .get(game_type_code)
+            .copied()
+            .unwrap_or(self.session_xp);
+        let session_xp = (session_xp as f64 * share).round() as i32;
+        let accuracy_xp = (self.accuracy_bonus_xp as f64 * share * accuracy.clamp(0.0, 100.0)
+            / 100.0)
+            .round() as i32;
+        XpAward {
+            session_xp,
+            accuracy_xp,
+            achievement_xp: achievement_points.max(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curve_levels_follow_the_thresholds_up_to_the_max_level() {
+        let curve = LevelCurve::default();
+        assert_eq!(curve.xp_for_level(1), 0);
+        assert_eq!(curve.xp_for_level(2), 100);
+        assert_eq!(curve.xp_for_level(5), 800);
+        assert_eq!(curve.level_for_xp(0), 1);
+        assert_eq!(curve.level_for_xp(99), 1);
+        assert_eq!(curve.level_for_xp(100), 2);
+        assert_eq!(curve.level_for_xp(799), 4);
+        assert_eq!(curve.level_for_xp(800), 5);
+
+        let curve = LevelCurve {
+            max_level: 3,
+            ..Default::default()
+        };
+        let progress = curve.progress(1_000_000);
+        assert_eq!(progress.level, 3);
+        assert_eq!(progress.next_level_xp, None);
+        assert_eq!(curve.progress(150).next_level_xp, Some(283));
+    }
+
+    #[test]
+    fn test_levels_gained_lists_every_level_crossed() {
+        let curve = LevelCurve::default();
+        assert_eq!(curve.levels_gained(90, 820), vec![2, 3, 4, 5]);
+        assert!(curve.levels_gained(100, 150).is_empty());
+    }
+
+    #[test]
+    fn test_session_award_uses_game_type_accuracy_and_achievements() {
+        let mut settings = ProgressionSettings::default();
+        settings.game_type_xp.insert("quiz".to_string(), 80);
+
+        let award = settings.session_award("quiz", 10, 75.0, 20);
+        assert_eq!(award.session_xp, 80);
+        assert_eq!(award.accuracy_xp, 38);
+        assert_eq!(award.achievement_xp, 20);
+        assert_eq!(award.total(), 138);
+        assert_eq!(settings.session_award("spelling", 10, 0.0, 0).total(), 50);
+    }
+
+    #[test]
+    fn test_short_session_earns_a_share_of_the_session_xp() {
+        let settings = ProgressionSettings::default();
+        let award = settings.session_award("quiz", 1, 100.0, 0);
+        assert_eq!(award.session_xp, 5);
+        assert_eq!(award.accuracy_xp, 5);
+        assert_eq!(settings.session_award("quiz", 30, 100.0, 0).total(), 100);
+
+        let empty = settings.session_award("quiz", 0, 100.0, 10);
+        assert_eq!(empty.entries(), vec![(XpSources::Achievement, 10)]);
+    }
+
+    #[test]
+    fn test_settings_reject_unusable_curves() {
+        assert!(ProgressionSettings::default().validate().is_ok());
+        let mut settings = ProgressionSettings::default();
+        settings.curve.exponent = 0.5;
+        assert!(settings.validate().is_err());
+        settings.curve.exponent = 1.5;
+        settings.game_type_xp.insert("quiz".to_string(), -1);
+        assert!(settings.validate().is_err());
+    }
+}
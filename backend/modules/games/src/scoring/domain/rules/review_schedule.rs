@@ -0,0 +1,147 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// Ease every card starts with, in permille (2500 = intervals grow 2.5x per review).
+pub const DEFAULT_EASE_FACTOR: i32 = 2500;
+const MIN_EASE_FACTOR: i32 = 1300;
+const MAX_INTERVAL_DAYS: i32 = 365;
+
+/// How well a card was recalled, derived from an in-game answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewGrade {
+    Again,
+    Hard,
+    Good,
+    Easy,
+}
+
+impl ReviewGrade {
+    /// Wrong answers are a lapse; correct ones are graded by how quickly they came.
+    pub fn from_answer(is_correct: bool, response_ms: i64) -> Self {
+        match (is_correct, response_ms) {
+            (false, _) => ReviewGrade::Again,
+            (true, ms) if ms <= 3_000 => ReviewGrade::Easy,
+            (true, ms) if ms <= 10_000 => ReviewGrade::Good,
+            _ => ReviewGrade::Hard,
+        }
+    }
+}
+
+/// Spaced-repetition state of one card for one user, scheduled with SM-2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReviewState {
+    pub ease_factor: i32,
+    pub interval_days: i32,
+    pub repetitions: i32,
+    pub lapses: i32,
+}
+
+impl Default for ReviewState {
+    fn default() -> Self {
+        Self {
+            ease_factor: DEFAULT_EASE_FACTOR,
+            interval_days: 0,
+            repetitions: 0,
+            lapses: 0,
+        }
+    }
+}
+
+impl ReviewState {
+    /// Applies one review and returns the state to store.
+    pub fn review(self, grade: ReviewGrade) -> Self {
+        if grade == ReviewGrade::Again {
+            return Self {
+                ease_factor: (self.ease_factor - 200).max(MIN_EASE_FACTOR),
+                interval_days: 1,
+                repetitions: 0,
+                lapses: self.lapses + 1,
+            };
+        }
+
+        let ease_factor = match grade {
+            ReviewGrade::Hard => self.ease_factor - 150,
+            ReviewGrade::Easy => self.ease_factor + 150,
+            _ => self.ease_factor,
+        }
+        .max(MIN_EASE_FACTOR);
+
+        let next_interval = match self.repetitions {
+            0 => 1,
+            1 => 6,
+            _ => scale(self.interval_days, ease_factor),
+        };
+        let interval_days = match grade {
+            ReviewGrade::Hard => scale(self.interval_days.max(1), 1200),
+            ReviewGrade::Easy => scale(next_interval, 1300),
+            _ => next_interval,
+        };
+
+        Self {
+            ease_factor,
+            interval_days: interval_days.clamp(1, MAX_INTERVAL_DAYS),
+            repetitions: self.repetitions + 1,
+            lapses: self.lapses,
+        }
+    }
+
+    pub fn due_at(&self, reviewed_at: DateTime<Utc>) -> DateTime<Utc> {
+        reviewed_at + Duration::days(self.interval_days as i64)
+    }
+}
+
+fn scale(days: i32, permille: i32) -> i32 {
+    ((days as i64 * permille as i64 + 500) / 1000) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_good_reviews_follow_sm2_intervals() {
+        let first = ReviewState::default().review(ReviewGrade::Good);
+        let second = first.review(ReviewGrade::Good);
+        let third = second.review(ReviewGrade::Good);
+        assert_eq!(first.interval_days, 1);
+        assert_eq!(second.interval_days, 6);
+        assert_eq!(third.interval_days, 15);
+        assert_eq!(third.ease_factor, DEFAULT_EASE_FACTOR);
+    }
+
+    #[test]
+    fn test_lapse_resets_interval_and_lowers_ease() {
+        let state = ReviewState {
+            ease_factor: 1400,
+            interval_days: 40,
+            repetitions: 5,
+            lapses: 1,
+        };
+        let lapsed = state.review(ReviewGrade::Again);
+        assert_eq!(lapsed.interval_days, 1);
+        assert_eq!(lapsed.repetitions, 0);
+        assert_eq!(lapsed.lapses, 2);
+        assert_eq!(lapsed.ease_factor, MIN_EASE_FACTOR);
+    }
+
+    #[test]
+    fn test_interval_is_capped() {
+        let state = ReviewState {
+            ease_factor: 3000,
+            interval_days: 300,
+            repetitions: 8,
+            lapses: 0,
+        };
+        assert_eq!(
+            state.review(ReviewGrade::Easy).interval_days,
+            MAX_INTERVAL_DAYS
+        );
+    }
+
+    #[test]
+    fn test_answers_are_graded_by_speed() {
+        assert_eq!(ReviewGrade::from_answer(false, 500), ReviewGrade::Again);
+        assert_eq!(ReviewGrade::from_answer(true, 2_000), ReviewGrade::Easy);
+        assert_eq!(ReviewGrade::from_answer(true, 8_000), ReviewGrade::Good);
+        assert_eq!(ReviewGrade::from_answer(true, 20_000), ReviewGrade::Hard);
+    }
+}
@@ -0,0 +1,252 @@
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, Timelike, Utc};
+
+/// Bounds of a player's UTC offset, from UTC-12:00 to UTC+14:00.
+pub const MIN_UTC_OFFSET_MINUTES: i32 = -12 * 60;
+pub const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+
+/// How streaks survive missed days and when players are reminded to keep them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreakPolicy {
+    /// Freezes a player can hold at once
+    pub max_freezes: i32,
+    /// A freeze is earned each time the streak reaches a multiple of this many days
+    pub freeze_earned_every_days: i32,
+    /// Score points one freeze costs; spending them does not lower the player's score
+    pub freeze_price: i64,
+    /// Local hour from which a player whose streak is at risk gets a reminder
+    pub reminder_hour: u32,
+}
+
+impl Default for StreakPolicy {
+    fn default() -> Self {
+        Self {
+            max_freezes: 2,
+            freeze_earned_every_days: 7,
+            freeze_price: 1000,
+            reminder_hour: 19,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreakDayKinds {
+    /// The player practised that day
+    Played,
+    /// A freeze covered a missed day
+    Frozen,
+}
+
+impl StreakDayKinds {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StreakDayKinds::Played => "played",
+            StreakDayKinds::Frozen => "frozen",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "played" => Some(StreakDayKinds::Played),
+            "frozen" => Some(StreakDayKinds::Frozen),
+            _ => None,
+        }
+    }
+}
+
+/// A player's streak as of the last day in their ledger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StreakState {
+    /// Days played in the run ending on `last_day`; frozen days bridge it without adding
+    pub current_streak: i32,
+    pub best_streak: i32,
+    pub freezes: i32,
+    pub last_day: Option<NaiveDate>,
+}
+
+/// What one day of activity changes: the new state and the ledger days to add.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreakProgress {
+    pub state: StreakState,
+    pub frozen_days: Vec<NaiveDate>,
+    pub played_day: NaiveDate,
+}
+
+/// Offset of a player's day boundaries; out of range values fall back to UTC.
+pub fn streak_offset(utc_offset_minutes: i32) -> FixedOffset {
+    FixedOffset::east_opt(utc_offset_minutes * 60).unwrap_or(FixedOffset::east_opt(0).unwrap())
+}
+
+/// The player's calendar day at `at`.
+pub fn streak_local_day(at: DateTime<Utc>, utc_offset_minutes: i32) -> NaiveDate {
+    at.with_timezone(&streak_offset(utc_offset_minutes))
+        .date_naive()
+}
+
+impl StreakState {
+    /// Applies activity on `day`. Missed days since the last one are covered by freezes
+    /// when there are enough, otherwise the streak starts over and the freezes are kept.
+    /// `None` when the day is already counted.
+    pub fn record_activity(self, day: NaiveDate, policy: &StreakPolicy) -> Option<StreakProgress> {
+        if self.last_day.is_some_and(|last| last >= day) {
+            return None;
+        }
+
+        let mut state = self;
+        let mut frozen_days = Vec::new();
+        match self.last_day {
+            Some(last) if self.current_streak > 0 => {
+                let missed = (day - last).num_days() - 1;
+                if missed == 0 {
+                    state.current_streak += 1;
+                } else if missed <= self.freezes as i64 {
+                    frozen_days = (1..=missed)
+                        .map(|offset| last + Duration::days(offset))
+                        .collect();
+                    state.freezes -= missed as i32;
+                    state.current_streak += 1;
+                } else {
+                    state.current_streak = 1;
+                }
+            }
+            _ => state.current_streak = 1,
+        }
+
+        if policy.freeze_earned_every_days > 0
+            && state.current_streak % policy.freeze_earned_every_days == 0
+        {
+            state.freezes = (state.freezes + 1).min(policy.max_freezes);
+        }
+        state.best_streak = state.best_streak.max(state.current_streak);
+        state.last_day = Some(day);
+
+        Some(StreakProgress {
+            state,
+            frozen_days,
+            played_day: day,
+        })
+    }
+
+    /// The streak as it stands on `today`, counting freezes that would cover missed days.
+    pub fn current_on(&self, today: NaiveDate) -> i32 {
+        let Some(last) = self.last_day else {
+            return 0;
+        };
+        let missed = (today - last).num_days() - 1;
+        if missed <= self.freezes as i64 {
+            self.current_streak
+        } else {
+            0
+        }
+    }
+
+    /// Whether skipping `today` would end the streak.
+    pub fn is_at_risk(&self, today: NaiveDate) -> bool {
+        let Some(last) = self.last_day else {
+            return false;
+        };
+        self.current_on(today) > 0
+            && last < today
+            && (today - last).num_days() > self.freezes as i64
+    }
+}
+
+impl StreakPolicy {
+    /// Whether a player should be reminded at `now`, given the day they were last reminded for.
+    pub fn is_reminder_due(
+        &self,
+        state: &StreakState,
+        now: DateTime<Utc>,
+        utc_offset_minutes: i32,
+        reminded_on: Option<NaiveDate>,
+    ) -> bool {
+        let local = now.with_timezone(&streak_offset(utc_offset_minutes));
+        let today = local.date_naive();
+        local.hour() >= self.reminder_hour
+            && state.is_at_risk(today)
+            && reminded_on.is_none_or(|day| day < today)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn state(current_streak: i32, freezes: i32, last_day: u32) -> StreakState {
+        StreakState {
+            current_streak,
+            best_streak: current_streak,
+            freezes,
+            last_day: Some(date(last_day)),
+        }
+    }
+
+    #[test]
+    fn test_local_day_follows_the_player_offset() {
+        // 06:00 and 23:00 at UTC+7 are the same local day but different UTC days
+        let morning = Utc.with_ymd_and_hms(2026, 10, 16, 23, 0, 0).unwrap();
+        let night = Utc.with_ymd_and_hms(2026, 10, 17, 16, 0, 0).unwrap();
+        assert_eq!(streak_local_day(morning, 7 * 60), date(17));
+        assert_eq!(streak_local_day(night, 7 * 60), date(17));
+        assert_eq!(streak_local_day(morning, 0), date(16));
+    }
+
+    #[test]
+    fn test_activity_extends_the_streak_once_per_day() {
+        let policy = StreakPolicy::default();
+        let progress = state(3, 0, 16).record_activity(date(17), &policy).unwrap();
+        assert_eq!(progress.state.current_streak, 4);
+        assert_eq!(progress.state.best_streak, 4);
+        assert!(progress.frozen_days.is_empty());
+        assert_eq!(progress.state.record_activity(date(17), &policy), None);
+    }
+
+    #[test]
+    fn test_freezes_cover_missed_days_only_when_there_are_enough() {
+        let policy = StreakPolicy::default();
+        let progress = state(3, 2, 14).record_activity(date(17), &policy).unwrap();
+        assert_eq!(progress.frozen_days, vec![date(15), date(16)]);
+        assert_eq!(progress.state.freezes, 0);
+        assert_eq!(progress.state.current_streak, 4);
+
+        let progress = state(5, 1, 14).record_activity(date(17), &policy).unwrap();
+        assert!(progress.frozen_days.is_empty());
+        assert_eq!(progress.state.freezes, 1);
+        assert_eq!(progress.state.current_streak, 1);
+        assert_eq!(progress.state.best_streak, 5);
+    }
+
+    #[test]
+    fn test_freeze_is_earned_on_milestones_up_to_the_cap() {
+        let policy = StreakPolicy::default();
+        let earned = state(6, 0, 16).record_activity(date(17), &policy).unwrap();
+        assert_eq!(earned.state.freezes, 1);
+        let capped = state(13, 2, 16).record_activity(date(17), &policy).unwrap();
+        assert_eq!(capped.state.freezes, 2);
+    }
+
+    #[test]
+    fn test_streak_is_at_risk_only_when_a_miss_would_end_it() {
+        assert!(state(4, 0, 16).is_at_risk(date(17)));
+        assert!(!state(4, 1, 16).is_at_risk(date(17)));
+        assert!(!state(4, 0, 17).is_at_risk(date(17)));
+        assert!(!state(4, 0, 14).is_at_risk(date(17)));
+        assert_eq!(state(4, 0, 14).current_on(date(17)), 0);
+        assert_eq!(state(4, 2, 14).current_on(date(17)), 4);
+    }
+
+    #[test]
+    fn test_reminder_waits_for_the_local_evening_and_is_sent_once() {
+        let policy = StreakPolicy::default();
+        let at_risk = state(4, 0, 16);
+        // 12:30 UTC is 19:30 at UTC+7
+        let evening = Utc.with_ymd_and_hms(2026, 10, 17, 12, 30, 0).unwrap();
+        assert!(policy.is_reminder_due(&at_risk, evening, 7 * 60, None));
+        assert!(!policy.is_reminder_due(&at_risk, evening, 0, None));
+        assert!(!policy.is_reminder_due(&at_risk, evening, 7 * 60, Some(date(17))));
+    }
+}
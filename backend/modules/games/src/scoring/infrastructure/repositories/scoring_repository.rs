@@ -1,24 +1,39 @@
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
+use rex_game_shared::domain::enums::user_statuses::UserStatuses;
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
+use rex_game_shared::infrastructure::database::SeaOrmTransactionWrapper;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait,
-    QueryFilter, QueryOrder, QuerySelect, Set,
+    sea_query::{Expr, OnConflict, Query}, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait,
+    DatabaseConnection, DatabaseTransaction, EntityTrait, ExprTrait, JoinType, Order,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set,
 };
 use std::sync::Arc;
 
 use rex_game_entities::entities::{
-    achievement, game_session, game_type, user, user_achievement, user_game_progress, user_stats,
+    achievement, achievement_backfill, flashcard, flashcard_type_relation, game_session,
+    game_session_answer, game_type, game_type_flashcard, leaderboard_score, system_settings, user,
+    user_achievement, user_flashcard_review, user_game_progress, user_stats, user_streak_day,
+    user_xp_event,
 };
 
 use crate::scoring::domain::{
     models::{
-        AchievementModel, GameProgressModel, GameSessionModel, GameTypeModel, LeaderboardEntry,
-        UserAchievementModel, UserStatsModel,
+        AchievementBackfillModel, AchievementBackfillStatuses, AchievementModel,
+        FlashcardReviewModel, GameProgressModel, GameSessionAnswerModel, GameSessionCompletion,
+        GameSessionModel, GameSessionStatuses, GameTypeModel, LeaderboardEntry, StreakDayModel,
+        StreakReminderCandidate, UserAchievementModel, UserStatsModel,
+    },
+    rules::{
+        AchievementCriteria, LeaderboardBucket, StreakDayKinds, StreakProgress, XpAward,
+        DEFAULT_EASE_FACTOR,
     },
     repositories::ScoringRepositoryTrait,
 };
 
+const PROGRESSION_SETTINGS_KEY: &str = "progression";
+
 pub struct ScoringRepository {
     db: Arc<DatabaseConnection>,
 }
@@ -27,6 +42,238 @@ impl ScoringRepository {
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
         Self { db }
     }
+
+    fn get_transaction(
+        transaction: &dyn TransactionWrapperTrait,
+    ) -> Result<&DatabaseTransaction, sea_orm::DbErr> {
+        transaction
+            .as_any()
+            .downcast_ref::<SeaOrmTransactionWrapper>()
+            .and_then(|wrapper| wrapper.txn.as_ref())
+            .ok_or(sea_orm::DbErr::Custom(
+                "Unable to cast the transaction".to_string(),
+            ))
+    }
+
+    fn map_session_model(
+        session: game_session::Model,
+        game_type: Option<game_type::Model>,
+    ) -> GameSessionModel {
+        GameSessionModel {
+            id: session.id,
+            user_id: session.user_id,
+            user_name: None,
+            user_display_name: None,
+            game_type_id: session.game_type_id,
+            game_type_code: game_type.as_ref().map(|gt| gt.code.clone()),
+            game_type_name: game_type.map(|gt| gt.name),
+            flashcard_type_id: session.flashcard_type_id,
+            score: session.score,
+            max_score: session.max_score,
+            accuracy: session.accuracy,
+            time_spent_seconds: session.time_spent_seconds,
+            cards_played: session.cards_played,
+            correct_answers: session.correct_answers,
+            wrong_answers: session.wrong_answers,
+            combo_max: session.combo_max,
+            started_at: session.started_at.with_timezone(&Utc),
+            completed_at: session.completed_at.map(|dt| dt.with_timezone(&Utc)),
+            status_id: session.status_id,
+            created_on: session.created_on.with_timezone(&Utc),
+        }
+    }
+
+    /// Criteria that no longer parse are treated as missing, so the achievement cannot unlock
+    fn parse_achievement_criteria(
+        criteria: Option<serde_json::Value>,
+    ) -> Option<AchievementCriteria> {
+        criteria.and_then(|c| serde_json::from_value(c).ok())
+    }
+
+    fn to_achievement_criteria_json(
+        criteria: Option<AchievementCriteria>,
+    ) -> Option<serde_json::Value> {
+        criteria.and_then(|c| serde_json::to_value(c).ok())
+    }
+
+    fn map_achievement_backfill_model(
+        backfill: achievement_backfill::Model,
+    ) -> AchievementBackfillModel {
+        AchievementBackfillModel {
+            id: backfill.id,
+            achievement_id: backfill.achievement_id,
+            is_dry_run: backfill.is_dry_run,
+            status: AchievementBackfillStatuses::parse(&backfill.status)
+                .unwrap_or(AchievementBackfillStatuses::Failed),
+            last_user_id: backfill.last_user_id,
+            total_users: backfill.total_users,
+            processed_users: backfill.processed_users,
+            affected_users: backfill.affected_users,
+            unlocked_count: backfill.unlocked_count,
+            last_error: backfill.last_error,
+            created_by_id: backfill.created_by_id,
+            started_on: backfill.started_on.map(|dt| dt.with_timezone(&Utc)),
+            completed_on: backfill.completed_on.map(|dt| dt.with_timezone(&Utc)),
+            created_on: backfill.created_on.with_timezone(&Utc),
+            updated_on: backfill.updated_on.with_timezone(&Utc),
+        }
+    }
+
+    fn leaderboard_bucket_condition(bucket: &LeaderboardBucket) -> Condition {
+        Condition::all()
+            .add(leaderboard_score::Column::Period.eq(bucket.period.as_str()))
+            .add(leaderboard_score::Column::PeriodStart.eq(bucket.period_start))
+            .add(leaderboard_score::Column::GameTypeId.eq(bucket.scope.game_type_id))
+            .add(leaderboard_score::Column::FlashcardTypeId.eq(bucket.scope.flashcard_type_id))
+    }
+
+    async fn find_user_stats<C: ConnectionTrait>(
+        db: &C,
+        user_id: i32,
+    ) -> Result<Option<UserStatsModel>, sea_orm::DbErr> {
+        let stats = user_stats::Entity::find()
+            .filter(user_stats::Column::UserId.eq(user_id))
+            .find_also_related(user::Entity)
+            .one(db)
+            .await?;
+
+        Ok(stats.map(|(stats, user)| Self::map_user_stats_model(stats, user.as_ref())))
+    }
+
+    fn map_user_stats_model(
+        stats: user_stats::Model,
+        user: Option<&user::Model>,
+    ) -> UserStatsModel {
+        UserStatsModel {
+            id: stats.id,
+            user_id: stats.user_id,
+            user_name: user.map(|u| u.name.clone()),
+            user_display_name: user.and_then(|u| u.display_name.clone()),
+            total_score: stats.total_score,
+            total_games_played: stats.total_games_played,
+            total_time_played_seconds: stats.total_time_played_seconds,
+            best_score: stats.best_score,
+            best_combo: stats.best_combo,
+            average_accuracy: stats.average_accuracy,
+            current_streak: stats.current_streak,
+            best_streak: stats.best_streak,
+            last_played_at: stats.last_played_at.map(|dt| dt.with_timezone(&Utc)),
+            utc_offset_minutes: stats.utc_offset_minutes,
+            streak_freezes: stats.streak_freezes,
+            points_spent: stats.points_spent,
+            last_streak_day: stats.last_streak_day,
+            streak_reminders_enabled: stats.streak_reminders_enabled,
+            streak_reminded_on: stats.streak_reminded_on,
+            total_xp: stats.total_xp,
+        }
+    }
+
+    async fn ensure_user_stats<C: ConnectionTrait>(
+        db: &C,
+        user_id: i32,
+    ) -> Result<(), sea_orm::DbErr> {
+        let existing = user_stats::Entity::find()
+            .filter(user_stats::Column::UserId.eq(user_id))
+            .one(db)
+            .await?;
+
+        if existing.is_none() {
+            let now = Utc::now().fixed_offset();
+            let stats = user_stats::ActiveModel {
+                user_id: Set(user_id),
+                total_score: Set(0),
+                total_games_played: Set(0),
+                total_time_played_seconds: Set(0),
+                best_score: Set(0),
+                best_combo: Set(0),
+                average_accuracy: Set(Decimal::ZERO),
+                current_streak: Set(0),
+                best_streak: Set(0),
+                last_played_at: Set(None),
+                updated_on: Set(now),
+                ..Default::default()
+            };
+            stats.insert(db).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn find_game_session_answers<C: ConnectionTrait>(
+        db: &C,
+        session_id: i32,
+    ) -> Result<Vec<GameSessionAnswerModel>, sea_orm::DbErr> {
+        let answers = game_session_answer::Entity::find()
+            .filter(game_session_answer::Column::GameSessionId.eq(session_id))
+            .order_by_asc(game_session_answer::Column::Position)
+            .order_by_asc(game_session_answer::Column::Id)
+            .find_also_related(flashcard::Entity)
+            .all(db)
+            .await?;
+
+        Ok(answers
+            .into_iter()
+            .map(|(answer, flashcard)| Self::map_answer_model(answer, flashcard))
+            .collect())
+    }
+
+    async fn find_user_streak_days<C: ConnectionTrait>(
+        db: &C,
+        user_id: i32,
+        from: NaiveDate,
+    ) -> Result<Vec<StreakDayModel>, sea_orm::DbErr> {
+        let days = user_streak_day::Entity::find()
+            .filter(user_streak_day::Column::UserId.eq(user_id))
+            .filter(user_streak_day::Column::Day.gte(from))
+            .order_by_asc(user_streak_day::Column::Day)
+            .all(db)
+            .await?;
+
+        Ok(days
+            .into_iter()
+            .map(|d| StreakDayModel {
+                day: d.day,
+                kind: d.kind,
+            })
+            .collect())
+    }
+
+    fn map_answer_model(
+        answer: game_session_answer::Model,
+        flashcard: Option<flashcard::Model>,
+    ) -> GameSessionAnswerModel {
+        GameSessionAnswerModel {
+            id: answer.id,
+            game_session_id: answer.game_session_id,
+            flashcard_id: answer.flashcard_id,
+            flashcard_name: flashcard.map(|f| f.name),
+            position: answer.position,
+            answer: answer.answer,
+            is_correct: answer.is_correct,
+            points: answer.points,
+            combo: answer.combo,
+            response_ms: answer.response_ms,
+            answered_at: answer.answered_at.map(|dt| dt.with_timezone(&Utc)),
+        }
+    }
+
+    fn map_review_model(
+        review: user_flashcard_review::Model,
+        flashcard: Option<flashcard::Model>,
+    ) -> FlashcardReviewModel {
+        FlashcardReviewModel {
+            id: review.id,
+            user_id: review.user_id,
+            flashcard_id: review.flashcard_id,
+            flashcard_name: flashcard.map(|f| f.name),
+            ease_factor: review.ease_factor,
+            interval_days: review.interval_days,
+            repetitions: review.repetitions,
+            lapses: review.lapses,
+            due_at: review.due_at.with_timezone(&Utc),
+            last_reviewed_at: review.last_reviewed_at.map(|dt| dt.with_timezone(&Utc)),
+        }
+    }
 }
 
 #[async_trait]
@@ -95,6 +342,7 @@ impl ScoringRepositoryTrait for ScoringRepository {
             combo_max: Set(0),
             started_at: Set(now),
             completed_at: Set(None),
+            status_id: Set(GameSessionStatuses::Started as i32),
             created_on: Set(now),
             ..Default::default()
         };
@@ -103,17 +351,23 @@ impl ScoringRepositoryTrait for ScoringRepository {
         Ok(result.id)
     }
 
-    async fn complete_game_session(
+    async fn complete_game_session_without_commit(
         &self,
-        session_id: i32,
-        score: i32,
-        max_score: Option<i32>,
-        correct_answers: i32,
-        wrong_answers: i32,
-        combo_max: i32,
-        time_spent_seconds: i32,
-    ) -> Result<(), sea_orm::DbErr> {
+        completion: &GameSessionCompletion,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Option<GameSessionModel>, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
         let now = Utc::now().fixed_offset();
+        let GameSessionCompletion {
+            session_id,
+            user_id,
+            score,
+            max_score,
+            correct_answers,
+            wrong_answers,
+            combo_max,
+            time_spent_seconds,
+        } = *completion;
         let total_answers = correct_answers + wrong_answers;
         let accuracy = if total_answers > 0 {
             Some(Decimal::from(correct_answers * 100) / Decimal::from(total_answers))
@@ -121,25 +375,66 @@ impl ScoringRepositoryTrait for ScoringRepository {
             None
         };
 
-        let session = game_session::Entity::find_by_id(session_id)
-            .one(self.db.as_ref())
+        // Conditional update so concurrent or replayed completions cannot both win
+        let result = game_session::Entity::update_many()
+            .col_expr(game_session::Column::Score, Expr::value(score))
+            .col_expr(game_session::Column::MaxScore, Expr::value(max_score))
+            .col_expr(game_session::Column::Accuracy, Expr::value(accuracy))
+            .col_expr(
+                game_session::Column::CorrectAnswers,
+                Expr::value(correct_answers),
+            )
+            .col_expr(game_session::Column::WrongAnswers, Expr::value(wrong_answers))
+            .col_expr(game_session::Column::CardsPlayed, Expr::value(total_answers))
+            .col_expr(game_session::Column::ComboMax, Expr::value(combo_max))
+            .col_expr(
+                game_session::Column::TimeSpentSeconds,
+                Expr::value(time_spent_seconds),
+            )
+            .col_expr(game_session::Column::CompletedAt, Expr::value(Some(now)))
+            .col_expr(
+                game_session::Column::StatusId,
+                Expr::value(GameSessionStatuses::Completed as i32),
+            )
+            .col_expr(game_session::Column::UpdatedOn, Expr::value(Some(now)))
+            .filter(game_session::Column::Id.eq(session_id))
+            .filter(game_session::Column::UserId.eq(user_id))
+            .filter(game_session::Column::StatusId.eq(GameSessionStatuses::Started as i32))
+            .exec(txn)
             .await?;
 
-        if let Some(session) = session {
-            let mut active: game_session::ActiveModel = session.into();
-            active.score = Set(score);
-            active.max_score = Set(max_score);
-            active.accuracy = Set(accuracy);
-            active.correct_answers = Set(correct_answers);
-            active.wrong_answers = Set(wrong_answers);
-            active.cards_played = Set(correct_answers + wrong_answers);
-            active.combo_max = Set(combo_max);
-            active.time_spent_seconds = Set(time_spent_seconds);
-            active.completed_at = Set(Some(now));
-            active.update(self.db.as_ref()).await?;
+        if result.rows_affected == 0 {
+            return Ok(None);
         }
 
-        Ok(())
+        let session = game_session::Entity::find_by_id(session_id)
+            .find_also_related(game_type::Entity)
+            .one(txn)
+            .await?;
+
+        Ok(session.map(|(session, game_type)| Self::map_session_model(session, game_type)))
+    }
+
+    async fn update_game_session_status(
+        &self,
+        session_id: i32,
+        user_id: i32,
+        from_status_id: i32,
+        to_status_id: i32,
+    ) -> Result<bool, sea_orm::DbErr> {
+        let result = game_session::Entity::update_many()
+            .col_expr(game_session::Column::StatusId, Expr::value(to_status_id))
+            .col_expr(
+                game_session::Column::UpdatedOn,
+                Expr::value(Some(Utc::now().fixed_offset())),
+            )
+            .filter(game_session::Column::Id.eq(session_id))
+            .filter(game_session::Column::UserId.eq(user_id))
+            .filter(game_session::Column::StatusId.eq(from_status_id))
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(result.rows_affected > 0)
     }
 
     async fn get_user_game_sessions(
@@ -181,6 +476,7 @@ impl ScoringRepositoryTrait for ScoringRepository {
                 combo_max: session.combo_max,
                 started_at: session.started_at.with_timezone(&Utc),
                 completed_at: session.completed_at.map(|dt| dt.with_timezone(&Utc)),
+                status_id: session.status_id,
                 created_on: session.created_on.with_timezone(&Utc),
             })
             .collect())
@@ -228,75 +524,219 @@ impl ScoringRepositoryTrait for ScoringRepository {
                 combo_max: session.combo_max,
                 started_at: session.started_at.with_timezone(&Utc),
                 completed_at: session.completed_at.map(|dt| dt.with_timezone(&Utc)),
+                status_id: session.status_id,
                 created_on: session.created_on.with_timezone(&Utc),
             })
             .collect())
     }
 
-    async fn get_user_stats(&self, user_id: i32) -> Result<Option<UserStatsModel>, sea_orm::DbErr> {
-        let stats = user_stats::Entity::find()
-            .filter(user_stats::Column::UserId.eq(user_id))
-            .find_also_related(user::Entity)
+    async fn get_game_session(
+        &self,
+        session_id: i32,
+    ) -> Result<Option<GameSessionModel>, sea_orm::DbErr> {
+        let session = game_session::Entity::find_by_id(session_id)
+            .find_also_related(game_type::Entity)
             .one(self.db.as_ref())
             .await?;
 
-        Ok(stats.map(|(stats, user)| UserStatsModel {
-            id: stats.id,
-            user_id: stats.user_id,
-            user_name: user.as_ref().map(|u| u.name.clone()),
-            user_display_name: user.and_then(|u| u.display_name),
-            total_score: stats.total_score,
-            total_games_played: stats.total_games_played,
-            total_time_played_seconds: stats.total_time_played_seconds,
-            best_score: stats.best_score,
-            best_combo: stats.best_combo,
-            average_accuracy: stats.average_accuracy,
-            current_streak: stats.current_streak,
-            best_streak: stats.best_streak,
-            last_played_at: stats.last_played_at.map(|dt| dt.with_timezone(&Utc)),
-        }))
+        Ok(session.map(|(session, game_type)| Self::map_session_model(session, game_type)))
     }
 
-    async fn ensure_user_stats_exists(&self, user_id: i32) -> Result<(), sea_orm::DbErr> {
-        let existing = user_stats::Entity::find()
-            .filter(user_stats::Column::UserId.eq(user_id))
-            .one(self.db.as_ref())
+    async fn lock_game_session_without_commit(
+        &self,
+        session_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Option<GameSessionModel>, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
+        // Locked on its own, as the game type is on the nullable side of the join
+        let locked = game_session::Entity::find_by_id(session_id)
+            .lock_exclusive()
+            .one(txn)
             .await?;
+        if locked.is_none() {
+            return Ok(None);
+        }
 
-        if existing.is_none() {
-            let now = Utc::now().fixed_offset();
-            let stats = user_stats::ActiveModel {
-                user_id: Set(user_id),
-                total_score: Set(0),
-                total_games_played: Set(0),
-                total_time_played_seconds: Set(0),
-                best_score: Set(0),
-                best_combo: Set(0),
-                average_accuracy: Set(Decimal::ZERO),
-                current_streak: Set(0),
-                best_streak: Set(0),
-                last_played_at: Set(None),
-                updated_on: Set(now),
-                ..Default::default()
-            };
-            stats.insert(self.db.as_ref()).await?;
+        let session = game_session::Entity::find_by_id(session_id)
+            .find_also_related(game_type::Entity)
+            .one(txn)
+            .await?;
+
+        Ok(session.map(|(session, game_type)| Self::map_session_model(session, game_type)))
+    }
+
+    async fn get_completed_game_sessions_with_transaction(
+        &self,
+        user_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Vec<GameSessionModel>, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
+        let sessions = game_session::Entity::find()
+            .filter(game_session::Column::UserId.eq(user_id))
+            .filter(game_session::Column::StatusId.eq(GameSessionStatuses::Completed as i32))
+            .find_also_related(game_type::Entity)
+            .all(txn)
+            .await?;
+
+        Ok(sessions
+            .into_iter()
+            .map(|(session, game_type)| Self::map_session_model(session, game_type))
+            .collect())
+    }
+
+    async fn deal_game_session_cards(
+        &self,
+        session_id: i32,
+        game_type_id: i32,
+        flashcard_type_id: Option<i32>,
+        card_count: u64,
+    ) -> Result<Vec<GameSessionAnswerModel>, sea_orm::DbErr> {
+        let mut query = flashcard::Entity::find().filter(flashcard::Column::IsActived.eq(true));
+
+        query = match flashcard_type_id {
+            Some(ft_id) => query
+                .join(
+                    JoinType::InnerJoin,
+                    flashcard::Relation::FlashcardTypeRelation.def(),
+                )
+                .filter(flashcard_type_relation::Column::FlashcardTypeId.eq(ft_id)),
+            None => query
+                .join(
+                    JoinType::InnerJoin,
+                    flashcard::Relation::GameTypeFlashcard.def(),
+                )
+                .filter(game_type_flashcard::Column::GameTypeId.eq(game_type_id)),
+        };
+
+        let flashcards = query
+            .order_by(Expr::cust("RANDOM()"), Order::Asc)
+            .limit(card_count)
+            .all(self.db.as_ref())
+            .await?;
+
+        if flashcards.is_empty() {
+            return Ok(Vec::new());
         }
 
-        Ok(())
+        let now = Utc::now().fixed_offset();
+        let dealt = flashcards
+            .iter()
+            .enumerate()
+            .map(|(position, f)| game_session_answer::ActiveModel {
+                game_session_id: Set(session_id),
+                flashcard_id: Set(f.id),
+                position: Set(position as i32),
+                points: Set(0),
+                combo: Set(0),
+                created_on: Set(now),
+                ..Default::default()
+            });
+
+        game_session_answer::Entity::insert_many(dealt)
+            .exec(self.db.as_ref())
+            .await?;
+
+        Self::find_game_session_answers(self.db.as_ref(), session_id).await
     }
 
-    async fn update_user_stats(
+    async fn get_game_session_answers_with_transaction(
+        &self,
+        session_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Vec<GameSessionAnswerModel>, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
+        Self::find_game_session_answers(txn, session_id).await
+    }
+
+    async fn create_game_session_answer_without_commit(
+        &self,
+        model: &GameSessionAnswerModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<i32, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
+        let answer = game_session_answer::ActiveModel {
+            game_session_id: Set(model.game_session_id),
+            flashcard_id: Set(model.flashcard_id),
+            position: Set(model.position),
+            answer: Set(model.answer.clone()),
+            is_correct: Set(model.is_correct),
+            points: Set(model.points),
+            combo: Set(model.combo),
+            response_ms: Set(model.response_ms),
+            answered_at: Set(model.answered_at.map(|dt| dt.fixed_offset())),
+            created_on: Set(Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+
+        let result = answer.insert(txn).await?;
+        Ok(result.id)
+    }
+
+    async fn update_game_session_answer_without_commit(
+        &self,
+        model: &GameSessionAnswerModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<bool, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
+        // Conditional update so two answers for the same card cannot both be recorded
+        let result = game_session_answer::Entity::update_many()
+            .col_expr(
+                game_session_answer::Column::Answer,
+                Expr::value(model.answer.clone()),
+            )
+            .col_expr(
+                game_session_answer::Column::IsCorrect,
+                Expr::value(model.is_correct),
+            )
+            .col_expr(game_session_answer::Column::Points, Expr::value(model.points))
+            .col_expr(game_session_answer::Column::Combo, Expr::value(model.combo))
+            .col_expr(
+                game_session_answer::Column::ResponseMs,
+                Expr::value(model.response_ms),
+            )
+            .col_expr(
+                game_session_answer::Column::AnsweredAt,
+                Expr::value(model.answered_at.map(|dt| dt.fixed_offset())),
+            )
+            .filter(game_session_answer::Column::Id.eq(model.id))
+            .filter(game_session_answer::Column::AnsweredAt.is_null())
+            .exec(txn)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn get_user_stats(&self, user_id: i32) -> Result<Option<UserStatsModel>, sea_orm::DbErr> {
+        Self::find_user_stats(self.db.as_ref(), user_id).await
+    }
+
+    async fn get_user_stats_with_transaction(
+        &self,
+        user_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Option<UserStatsModel>, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
+        Self::find_user_stats(txn, user_id).await
+    }
+
+    async fn ensure_user_stats_exists(&self, user_id: i32) -> Result<(), sea_orm::DbErr> {
+        Self::ensure_user_stats(self.db.as_ref(), user_id).await
+    }
+
+    async fn update_user_stats_without_commit(
         &self,
         user_id: i32,
         session: &GameSessionModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
     ) -> Result<(), sea_orm::DbErr> {
-        self.ensure_user_stats_exists(user_id).await?;
+        let txn = Self::get_transaction(*transaction)?;
+        Self::ensure_user_stats(txn, user_id).await?;
 
         let stats = user_stats::Entity::find()
             .filter(user_stats::Column::UserId.eq(user_id))
-            .one(self.db.as_ref())
+            .lock_exclusive()
+            .one(txn)
             .await?;
-
         if let Some(stats) = stats {
             let now = Utc::now().fixed_offset();
             let new_total_games = stats.total_games_played + 1;
@@ -312,30 +752,6 @@ impl ScoringRepositoryTrait for ScoringRepository {
                     / Decimal::from(new_total_games)
             };
 
-            // Check streak
-            let (new_current_streak, new_best_streak) = {
-                let today = Utc::now().date_naive();
-                let last_played = stats
-                    .last_played_at
-                    .map(|dt| dt.with_timezone(&Utc).date_naive());
-
-                let streak = if let Some(last) = last_played {
-                    let days_diff = (today - last).num_days();
-                    if days_diff == 1 {
-                        stats.current_streak + 1
-                    } else if days_diff == 0 {
-                        stats.current_streak
-                    } else {
-                        1
-                    }
-                } else {
-                    1
-                };
-
-                let best = std::cmp::max(streak, stats.best_streak);
-                (streak, best)
-            };
-
             let mut active: user_stats::ActiveModel = stats.into();
             active.total_score = Set(new_total_score);
             active.total_games_played = Set(new_total_games);
@@ -344,11 +760,9 @@ impl ScoringRepositoryTrait for ScoringRepository {
             active.best_score = Set(std::cmp::max(active.best_score.unwrap(), session.score));
             active.best_combo = Set(std::cmp::max(active.best_combo.unwrap(), session.combo_max));
             active.average_accuracy = Set(new_avg_accuracy);
-            active.current_streak = Set(new_current_streak);
-            active.best_streak = Set(new_best_streak);
             active.last_played_at = Set(Some(now));
             active.updated_on = Set(now);
-            active.update(self.db.as_ref()).await?;
+            active.update(txn).await?;
         }
 
         Ok(())
@@ -356,36 +770,454 @@ impl ScoringRepositoryTrait for ScoringRepository {
 
     async fn get_leaderboard(
         &self,
-        page: u64,
-        page_size: u64,
+        bucket: &LeaderboardBucket,
+        offset: u64,
+        limit: u64,
     ) -> Result<Vec<LeaderboardEntry>, sea_orm::DbErr> {
-        let offset = (page - 1) * page_size;
-
-        let stats = user_stats::Entity::find()
-            .filter(user_stats::Column::TotalGamesPlayed.gt(0))
-            .order_by_desc(user_stats::Column::TotalScore)
+        // Suspended, banned and deleted players drop off the board. Ties keep a stable
+        // order so pages and ranks agree
+        let scores = leaderboard_score::Entity::find()
+            .filter(Self::leaderboard_bucket_condition(bucket))
+            .filter(user::Column::StatusId.eq(UserStatuses::Actived as i32))
+            .order_by_desc(leaderboard_score::Column::TotalScore)
+            .order_by_asc(leaderboard_score::Column::UserId)
             .offset(offset)
-            .limit(page_size)
+            .limit(limit)
             .find_also_related(user::Entity)
             .all(self.db.as_ref())
             .await?;
 
-        Ok(stats
+        Ok(scores
             .into_iter()
             .enumerate()
-            .map(|(idx, (stats, user))| LeaderboardEntry {
+            .map(|(idx, (score, user))| LeaderboardEntry {
                 rank: (offset + idx as u64 + 1) as i32,
-                user_id: stats.user_id,
+                user_id: score.user_id,
                 user_name: user.as_ref().map(|u| u.name.clone()).unwrap_or_default(),
                 user_display_name: user.and_then(|u| u.display_name),
-                total_score: stats.total_score,
-                total_games_played: stats.total_games_played,
-                best_score: stats.best_score,
-                average_accuracy: stats.average_accuracy,
+                total_score: score.total_score,
+                total_games_played: score.games_played,
+                best_score: score.best_score,
+                average_accuracy: if score.games_played > 0 {
+                    (score.accuracy_total / Decimal::from(score.games_played)).round_dp(2)
+                } else {
+                    Decimal::ZERO
+                },
             })
             .collect())
     }
 
+    async fn get_leaderboard_rank(
+        &self,
+        bucket: &LeaderboardBucket,
+        user_id: i32,
+    ) -> Result<Option<i32>, sea_orm::DbErr> {
+        let score = leaderboard_score::Entity::find()
+            .filter(Self::leaderboard_bucket_condition(bucket))
+            .filter(leaderboard_score::Column::UserId.eq(user_id))
+            .one(self.db.as_ref())
+            .await?;
+
+        if let Some(score) = score {
+            let ahead = leaderboard_score::Entity::find()
+                .join(JoinType::InnerJoin, leaderboard_score::Relation::User.def())
+                .filter(Self::leaderboard_bucket_condition(bucket))
+                .filter(user::Column::StatusId.eq(UserStatuses::Actived as i32))
+                .filter(
+                    Condition::any()
+                        .add(leaderboard_score::Column::TotalScore.gt(score.total_score))
+                        .add(
+                            Condition::all()
+                                .add(leaderboard_score::Column::TotalScore.eq(score.total_score))
+                                .add(leaderboard_score::Column::UserId.lt(user_id)),
+                        ),
+                )
+                .count(self.db.as_ref())
+                .await?;
+
+            Ok(Some(ahead as i32 + 1))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn add_leaderboard_score_without_commit(
+        &self,
+        user_id: i32,
+        buckets: &[LeaderboardBucket],
+        session: &GameSessionModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<(), sea_orm::DbErr> {
+        if buckets.is_empty() {
+            return Ok(());
+        }
+
+        let txn = Self::get_transaction(*transaction)?;
+        let now = Utc::now().fixed_offset();
+        let accuracy = session.accuracy.unwrap_or(Decimal::ZERO);
+        let rows = buckets.iter().map(|bucket| leaderboard_score::ActiveModel {
+            period: Set(bucket.period.as_str().to_string()),
+            period_start: Set(bucket.period_start),
+            game_type_id: Set(bucket.scope.game_type_id),
+            flashcard_type_id: Set(bucket.scope.flashcard_type_id),
+            user_id: Set(user_id),
+            total_score: Set(session.score as i64),
+            games_played: Set(1),
+            best_score: Set(session.score),
+            accuracy_total: Set(accuracy),
+            updated_on: Set(now),
+            ..Default::default()
+        });
+
+        // Adds onto the existing row in the database, so concurrent completions never
+        // overwrite each other
+        leaderboard_score::Entity::insert_many(rows)
+            .on_conflict(
+                OnConflict::columns([
+                    leaderboard_score::Column::Period,
+                    leaderboard_score::Column::PeriodStart,
+                    leaderboard_score::Column::GameTypeId,
+                    leaderboard_score::Column::FlashcardTypeId,
+                    leaderboard_score::Column::UserId,
+                ])
+                .values([
+                    (
+                        leaderboard_score::Column::TotalScore,
+                        Expr::cust("leaderboard_score.total_score + excluded.total_score"),
+                    ),
+                    (
+                        leaderboard_score::Column::GamesPlayed,
+                        Expr::cust("leaderboard_score.games_played + excluded.games_played"),
+                    ),
+                    (
+                        leaderboard_score::Column::BestScore,
+                        Expr::cust("GREATEST(leaderboard_score.best_score, excluded.best_score)"),
+                    ),
+                    (
+                        leaderboard_score::Column::AccuracyTotal,
+                        Expr::cust("leaderboard_score.accuracy_total + excluded.accuracy_total"),
+                    ),
+                    (
+                        leaderboard_score::Column::UpdatedOn,
+                        Expr::cust("excluded.updated_on"),
+                    ),
+                ])
+                .to_owned(),
+            )
+            .exec(txn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn lock_user_stats_without_commit(
+        &self,
+        user_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Option<UserStatsModel>, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
+        Self::ensure_user_stats(txn, user_id).await?;
+
+        let stats = user_stats::Entity::find()
+            .filter(user_stats::Column::UserId.eq(user_id))
+            .lock_exclusive()
+            .one(txn)
+            .await?;
+        Ok(stats.map(|stats| Self::map_user_stats_model(stats, None)))
+    }
+
+    async fn save_streak_progress_without_commit(
+        &self,
+        user_id: i32,
+        progress: &StreakProgress,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<(), sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
+        let now = Utc::now().fixed_offset();
+        let days = progress
+            .frozen_days
+            .iter()
+            .map(|day| (*day, StreakDayKinds::Frozen))
+            .chain(std::iter::once((
+                progress.played_day,
+                StreakDayKinds::Played,
+            )));
+        let rows = days.map(|(day, kind)| user_streak_day::ActiveModel {
+            user_id: Set(user_id),
+            day: Set(day),
+            kind: Set(kind.as_str().to_string()),
+            created_on: Set(now),
+            ..Default::default()
+        });
+        user_streak_day::Entity::insert_many(rows)
+            .on_conflict_do_nothing_on([
+                user_streak_day::Column::UserId,
+                user_streak_day::Column::Day,
+            ])
+            .exec(txn)
+            .await?;
+
+        let state = progress.state;
+        user_stats::Entity::update_many()
+            .col_expr(
+                user_stats::Column::CurrentStreak,
+                Expr::value(state.current_streak),
+            )
+            .col_expr(
+                user_stats::Column::BestStreak,
+                Expr::value(state.best_streak),
+            )
+            .col_expr(
+                user_stats::Column::StreakFreezes,
+                Expr::value(state.freezes),
+            )
+            .col_expr(
+                user_stats::Column::LastStreakDay,
+                Expr::value(state.last_day),
+            )
+            .col_expr(user_stats::Column::UpdatedOn, Expr::value(now))
+            .filter(user_stats::Column::UserId.eq(user_id))
+            .exec(txn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_user_streak_days(
+        &self,
+        user_id: i32,
+        from: NaiveDate,
+    ) -> Result<Vec<StreakDayModel>, sea_orm::DbErr> {
+        Self::find_user_streak_days(self.db.as_ref(), user_id, from).await
+    }
+
+    async fn get_user_streak_days_with_transaction(
+        &self,
+        user_id: i32,
+        from: NaiveDate,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Vec<StreakDayModel>, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
+        Self::find_user_streak_days(txn, user_id, from).await
+    }
+
+    async fn update_streak_settings(
+        &self,
+        user_id: i32,
+        utc_offset_minutes: i32,
+        reminders_enabled: bool,
+    ) -> Result<(), sea_orm::DbErr> {
+        Self::ensure_user_stats(self.db.as_ref(), user_id).await?;
+
+        user_stats::Entity::update_many()
+            .col_expr(
+                user_stats::Column::UtcOffsetMinutes,
+                Expr::value(utc_offset_minutes),
+            )
+            .col_expr(
+                user_stats::Column::StreakRemindersEnabled,
+                Expr::value(reminders_enabled),
+            )
+            .col_expr(
+                user_stats::Column::UpdatedOn,
+                Expr::value(Utc::now().fixed_offset()),
+            )
+            .filter(user_stats::Column::UserId.eq(user_id))
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn purchase_streak_freeze(
+        &self,
+        user_id: i32,
+        price: i64,
+        max_freezes: i32,
+    ) -> Result<bool, sea_orm::DbErr> {
+        // Checked and spent in one statement so concurrent purchases cannot overspend
+        let result = user_stats::Entity::update_many()
+            .col_expr(
+                user_stats::Column::StreakFreezes,
+                Expr::col(user_stats::Column::StreakFreezes).add(1),
+            )
+            .col_expr(
+                user_stats::Column::PointsSpent,
+                Expr::col(user_stats::Column::PointsSpent).add(price),
+            )
+            .col_expr(
+                user_stats::Column::UpdatedOn,
+                Expr::value(Utc::now().fixed_offset()),
+            )
+            .filter(user_stats::Column::UserId.eq(user_id))
+            .filter(user_stats::Column::StreakFreezes.lt(max_freezes))
+            .filter(
+                Expr::col(user_stats::Column::TotalScore)
+                    .sub(Expr::col(user_stats::Column::PointsSpent))
+                    .gte(price),
+            )
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn get_streak_reminder_candidates(
+        &self,
+        after_user_id: i32,
+        active_since: NaiveDate,
+        limit: u64,
+    ) -> Result<Vec<StreakReminderCandidate>, sea_orm::DbErr> {
+        let stats = user_stats::Entity::find()
+            .filter(user_stats::Column::UserId.gt(after_user_id))
+            .filter(user_stats::Column::StreakRemindersEnabled.eq(true))
+            .filter(user_stats::Column::CurrentStreak.gt(0))
+            .filter(user_stats::Column::LastStreakDay.gte(active_since))
+            .filter(user::Column::StatusId.eq(UserStatuses::Actived as i32))
+            .order_by_asc(user_stats::Column::UserId)
+            .limit(limit)
+            .find_also_related(user::Entity)
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(stats
+            .into_iter()
+            .filter_map(|(stats, user)| {
+                let user = user?;
+                Some(StreakReminderCandidate {
+                    email: user.email.clone(),
+                    stats: Self::map_user_stats_model(stats, Some(&user)),
+                })
+            })
+            .collect())
+    }
+
+    async fn mark_streak_reminded(
+        &self,
+        user_id: i32,
+        day: NaiveDate,
+    ) -> Result<bool, sea_orm::DbErr> {
+        // Only one worker gets to claim a player's reminder for the day
+        let result = user_stats::Entity::update_many()
+            .col_expr(user_stats::Column::StreakRemindedOn, Expr::value(Some(day)))
+            .filter(user_stats::Column::UserId.eq(user_id))
+            .filter(
+                Condition::any()
+                    .add(user_stats::Column::StreakRemindedOn.is_null())
+                    .add(user_stats::Column::StreakRemindedOn.lt(day)),
+            )
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn add_xp_without_commit(
+        &self,
+        user_id: i32,
+        game_session_id: Option<i32>,
+        award: &XpAward,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<i64, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
+        Self::ensure_user_stats(txn, user_id).await?;
+
+        let entries = award.entries();
+        if !entries.is_empty() {
+            let now = Utc::now().fixed_offset();
+            let rows = entries
+                .into_iter()
+                .map(|(source, amount)| user_xp_event::ActiveModel {
+                    user_id: Set(user_id),
+                    game_session_id: Set(game_session_id),
+                    source: Set(source.as_str().to_string()),
+                    amount: Set(amount),
+                    created_on: Set(now),
+                    ..Default::default()
+                });
+            user_xp_event::Entity::insert_many(rows).exec(txn).await?;
+
+            user_stats::Entity::update_many()
+                .col_expr(
+                    user_stats::Column::TotalXp,
+                    Expr::col(user_stats::Column::TotalXp).add(award.total() as i64),
+                )
+                .col_expr(user_stats::Column::UpdatedOn, Expr::value(now))
+                .filter(user_stats::Column::UserId.eq(user_id))
+                .exec(txn)
+                .await?;
+        }
+
+        let total_xp = user_stats::Entity::find()
+            .filter(user_stats::Column::UserId.eq(user_id))
+            .one(txn)
+            .await?
+            .map(|stats| stats.total_xp)
+            .unwrap_or_default();
+        Ok(total_xp)
+    }
+
+    async fn get_game_type_xp(
+        &self,
+        user_id: i32,
+        game_type_id: i32,
+    ) -> Result<i64, sea_orm::DbErr> {
+        let xp = user_xp_event::Entity::find()
+            .select_only()
+            .column_as(
+                Expr::col((user_xp_event::Entity, user_xp_event::Column::Amount)).sum(),
+                "xp",
+            )
+            .join(JoinType::InnerJoin, user_xp_event::Relation::GameSession.def())
+            .filter(user_xp_event::Column::UserId.eq(user_id))
+            .filter(game_session::Column::GameTypeId.eq(game_type_id))
+            .into_tuple::<Option<i64>>()
+            .one(self.db.as_ref())
+            .await?;
+        Ok(xp.flatten().unwrap_or_default())
+    }
+
+    async fn get_progression_settings(&self) -> Result<Option<String>, sea_orm::DbErr> {
+        let setting = system_settings::Entity::find()
+            .filter(system_settings::Column::Key.eq(PROGRESSION_SETTINGS_KEY))
+            .filter(system_settings::Column::IsActived.eq(true))
+            .one(self.db.as_ref())
+            .await?;
+        Ok(setting.map(|s| s.value))
+    }
+
+    async fn save_progression_settings(
+        &self,
+        value: String,
+        updated_by_id: i32,
+    ) -> Result<(), sea_orm::DbErr> {
+        let now = Utc::now().fixed_offset();
+        let setting = system_settings::ActiveModel {
+            key: Set(PROGRESSION_SETTINGS_KEY.to_string()),
+            value: Set(value),
+            created_by_id: Set(Some(updated_by_id)),
+            created_on: Set(now),
+            updated_on: Set(now),
+            updated_by_id: Set(Some(updated_by_id)),
+            is_actived: Set(true),
+            ..Default::default()
+        };
+        system_settings::Entity::insert(setting)
+            .on_conflict(
+                OnConflict::column(system_settings::Column::Key)
+                    .update_columns([
+                        system_settings::Column::Value,
+                        system_settings::Column::UpdatedOn,
+                        system_settings::Column::UpdatedById,
+                        system_settings::Column::IsActived,
+                    ])
+                    .to_owned(),
+            )
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
     async fn get_user_rank(&self, user_id: i32) -> Result<Option<i32>, sea_orm::DbErr> {
         let user_stats = user_stats::Entity::find()
             .filter(user_stats::Column::UserId.eq(user_id))
@@ -394,6 +1226,8 @@ impl ScoringRepositoryTrait for ScoringRepository {
 
         if let Some(stats) = user_stats {
             let rank = user_stats::Entity::find()
+                .join(JoinType::InnerJoin, user_stats::Relation::User1.def())
+                .filter(user::Column::StatusId.eq(UserStatuses::Actived as i32))
                 .filter(user_stats::Column::TotalScore.gt(stats.total_score))
                 .count(self.db.as_ref())
                 .await?;
@@ -422,6 +1256,7 @@ impl ScoringRepositoryTrait for ScoringRepository {
                 icon: a.icon,
                 points: a.points,
                 category: a.category,
+                criteria: Self::parse_achievement_criteria(a.criteria),
                 is_actived: a.is_actived,
                 created_on: a.created_on.with_timezone(&Utc),
                 updated_on: a.updated_on.with_timezone(&Utc),
@@ -453,6 +1288,7 @@ impl ScoringRepositoryTrait for ScoringRepository {
                         icon: a.icon,
                         points: a.points,
                         category: a.category,
+                        criteria: Self::parse_achievement_criteria(a.criteria),
                         is_actived: a.is_actived,
                         created_on: a.created_on.with_timezone(&Utc),
                         updated_on: a.updated_on.with_timezone(&Utc),
@@ -463,27 +1299,29 @@ impl ScoringRepositoryTrait for ScoringRepository {
             .collect())
     }
 
-    async fn unlock_achievement(
+    async fn unlock_achievement_without_commit(
         &self,
         user_id: i32,
         achievement_id: i32,
+        unlocked_at: DateTime<Utc>,
+        transaction: Box<&dyn TransactionWrapperTrait>,
     ) -> Result<(), sea_orm::DbErr> {
-        let now = Utc::now().fixed_offset();
+        let txn = Self::get_transaction(*transaction)?;
 
         let existing = user_achievement::Entity::find()
             .filter(user_achievement::Column::UserId.eq(user_id))
             .filter(user_achievement::Column::AchievementId.eq(achievement_id))
-            .one(self.db.as_ref())
+            .one(txn)
             .await?;
 
         if existing.is_none() {
             let ua = user_achievement::ActiveModel {
                 user_id: Set(user_id),
                 achievement_id: Set(achievement_id),
-                unlocked_at: Set(now),
+                unlocked_at: Set(unlocked_at.fixed_offset()),
                 ..Default::default()
             };
-            ua.insert(self.db.as_ref()).await?;
+            ua.insert(txn).await?;
         }
 
         Ok(())
@@ -493,17 +1331,19 @@ impl ScoringRepositoryTrait for ScoringRepository {
         &self,
         user_id: i32,
         achievement_code: &str,
+        transaction: Box<&dyn TransactionWrapperTrait>,
     ) -> Result<bool, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
         let achievement = achievement::Entity::find()
             .filter(achievement::Column::Code.eq(achievement_code))
-            .one(self.db.as_ref())
+            .one(txn)
             .await?;
 
         if let Some(achievement) = achievement {
             let existing = user_achievement::Entity::find()
                 .filter(user_achievement::Column::UserId.eq(user_id))
                 .filter(user_achievement::Column::AchievementId.eq(achievement.id))
-                .one(self.db.as_ref())
+                .one(txn)
                 .await?;
 
             Ok(existing.is_some())
@@ -512,6 +1352,21 @@ impl ScoringRepositoryTrait for ScoringRepository {
         }
     }
 
+    async fn get_unlocked_achievement_ids_with_transaction(
+        &self,
+        user_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Vec<i32>, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
+        user_achievement::Entity::find()
+            .select_only()
+            .column(user_achievement::Column::AchievementId)
+            .filter(user_achievement::Column::UserId.eq(user_id))
+            .into_tuple::<i32>()
+            .all(txn)
+            .await
+    }
+
     // Game Progress
     async fn get_game_progress(
         &self,
@@ -624,9 +1479,9 @@ impl ScoringRepositoryTrait for ScoringRepository {
             .one(self.db.as_ref())
             .await?;
 
+        // The level is earned XP, so starting over only clears the score
         if let Some(p) = existing {
             let mut active: user_game_progress::ActiveModel = p.into();
-            active.current_level = Set(1);
             active.total_score = Set(0);
             active.last_played_at = Set(now);
             active.updated_on = Set(now);
@@ -636,6 +1491,138 @@ impl ScoringRepositoryTrait for ScoringRepository {
         Ok(())
     }
 
+    // ---- Flashcard Reviews ----
+
+    async fn get_flashcard_review(
+        &self,
+        user_id: i32,
+        flashcard_id: i32,
+    ) -> Result<Option<FlashcardReviewModel>, sea_orm::DbErr> {
+        let review = user_flashcard_review::Entity::find()
+            .filter(user_flashcard_review::Column::UserId.eq(user_id))
+            .filter(user_flashcard_review::Column::FlashcardId.eq(flashcard_id))
+            .find_also_related(flashcard::Entity)
+            .one(self.db.as_ref())
+            .await?;
+
+        Ok(review.map(|(review, flashcard)| Self::map_review_model(review, flashcard)))
+    }
+
+    async fn save_flashcard_review(
+        &self,
+        model: &FlashcardReviewModel,
+    ) -> Result<(), sea_orm::DbErr> {
+        let now = Utc::now().fixed_offset();
+
+        let existing = user_flashcard_review::Entity::find()
+            .filter(user_flashcard_review::Column::UserId.eq(model.user_id))
+            .filter(user_flashcard_review::Column::FlashcardId.eq(model.flashcard_id))
+            .one(self.db.as_ref())
+            .await?;
+
+        if let Some(review) = existing {
+            let mut active: user_flashcard_review::ActiveModel = review.into();
+            active.ease_factor = Set(model.ease_factor);
+            active.interval_days = Set(model.interval_days);
+            active.repetitions = Set(model.repetitions);
+            active.lapses = Set(model.lapses);
+            active.due_at = Set(model.due_at.fixed_offset());
+            active.last_reviewed_at = Set(model.last_reviewed_at.map(|dt| dt.fixed_offset()));
+            active.updated_on = Set(now);
+            active.update(self.db.as_ref()).await?;
+        } else {
+            let review = user_flashcard_review::ActiveModel {
+                user_id: Set(model.user_id),
+                flashcard_id: Set(model.flashcard_id),
+                ease_factor: Set(model.ease_factor),
+                interval_days: Set(model.interval_days),
+                repetitions: Set(model.repetitions),
+                lapses: Set(model.lapses),
+                due_at: Set(model.due_at.fixed_offset()),
+                last_reviewed_at: Set(model.last_reviewed_at.map(|dt| dt.fixed_offset())),
+                created_on: Set(now),
+                updated_on: Set(now),
+                ..Default::default()
+            };
+            review.insert(self.db.as_ref()).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn get_due_flashcard_reviews(
+        &self,
+        user_id: i32,
+        flashcard_type_id: i32,
+        due_before: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<FlashcardReviewModel>, sea_orm::DbErr> {
+        let type_flashcards = Query::select()
+            .column(flashcard_type_relation::Column::FlashcardId)
+            .from(flashcard_type_relation::Entity)
+            .and_where(flashcard_type_relation::Column::FlashcardTypeId.eq(flashcard_type_id))
+            .to_owned();
+
+        let reviews = user_flashcard_review::Entity::find()
+            .filter(user_flashcard_review::Column::UserId.eq(user_id))
+            .filter(user_flashcard_review::Column::DueAt.lte(due_before.fixed_offset()))
+            .filter(user_flashcard_review::Column::FlashcardId.in_subquery(type_flashcards))
+            .find_also_related(flashcard::Entity)
+            .filter(flashcard::Column::IsActived.eq(true))
+            .order_by_asc(user_flashcard_review::Column::DueAt)
+            .limit(limit)
+            .all(self.db.as_ref())
+            .await?;
+
+        Ok(reviews
+            .into_iter()
+            .map(|(review, flashcard)| Self::map_review_model(review, flashcard))
+            .collect())
+    }
+
+    async fn get_unreviewed_flashcards(
+        &self,
+        user_id: i32,
+        flashcard_type_id: i32,
+        limit: u64,
+    ) -> Result<Vec<FlashcardReviewModel>, sea_orm::DbErr> {
+        let reviewed_flashcards = Query::select()
+            .column(user_flashcard_review::Column::FlashcardId)
+            .from(user_flashcard_review::Entity)
+            .and_where(user_flashcard_review::Column::UserId.eq(user_id))
+            .to_owned();
+
+        let flashcards = flashcard::Entity::find()
+            .filter(flashcard::Column::IsActived.eq(true))
+            .join(
+                JoinType::InnerJoin,
+                flashcard::Relation::FlashcardTypeRelation.def(),
+            )
+            .filter(flashcard_type_relation::Column::FlashcardTypeId.eq(flashcard_type_id))
+            .filter(flashcard::Column::Id.not_in_subquery(reviewed_flashcards))
+            .order_by_asc(flashcard::Column::Id)
+            .limit(limit)
+            .all(self.db.as_ref())
+            .await?;
+
+        let now = Utc::now();
+        Ok(flashcards
+            .into_iter()
+            .map(|f| FlashcardReviewModel {
+                id: 0,
+                user_id,
+                flashcard_id: f.id,
+                flashcard_name: Some(f.name),
+                ease_factor: DEFAULT_EASE_FACTOR,
+                interval_days: 0,
+                repetitions: 0,
+                lapses: 0,
+                due_at: now,
+                last_reviewed_at: None,
+            })
+            .collect())
+    }
+
     // ---- Admin: Game Types ----
 
     async fn get_game_types_paged(
@@ -791,6 +1778,7 @@ impl ScoringRepositoryTrait for ScoringRepository {
                 icon: a.icon,
                 points: a.points,
                 category: a.category,
+                criteria: Self::parse_achievement_criteria(a.criteria),
                 is_actived: a.is_actived,
                 created_on: a.created_on.with_timezone(&Utc),
                 updated_on: a.updated_on.with_timezone(&Utc),
@@ -816,6 +1804,7 @@ impl ScoringRepositoryTrait for ScoringRepository {
             icon: a.icon,
             points: a.points,
             category: a.category,
+            criteria: Self::parse_achievement_criteria(a.criteria),
             is_actived: a.is_actived,
             created_on: a.created_on.with_timezone(&Utc),
             updated_on: a.updated_on.with_timezone(&Utc),
@@ -831,6 +1820,7 @@ impl ScoringRepositoryTrait for ScoringRepository {
             icon: Set(model.icon),
             points: Set(model.points),
             category: Set(model.category),
+            criteria: Set(Self::to_achievement_criteria_json(model.criteria)),
             is_actived: Set(true),
             created_on: Set(now),
             updated_on: Set(now),
@@ -854,6 +1844,7 @@ impl ScoringRepositoryTrait for ScoringRepository {
             active.icon = Set(model.icon);
             active.points = Set(model.points);
             active.category = Set(model.category);
+            active.criteria = Set(Self::to_achievement_criteria_json(model.criteria));
             active.updated_on = Set(now);
             active.update(self.db.as_ref()).await?;
             Ok(true)
@@ -889,6 +1880,151 @@ impl ScoringRepositoryTrait for ScoringRepository {
         }
     }
 
+    // ---- Admin: Achievement Backfills ----
+
+    async fn get_achievement_backfills_paged(
+        &self,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(Vec<AchievementBackfillModel>, u64), sea_orm::DbErr> {
+        let query = achievement_backfill::Entity::find();
+        let total_count = query.clone().count(self.db.as_ref()).await?;
+
+        let items = query
+            .order_by_desc(achievement_backfill::Column::Id)
+            .paginate(self.db.as_ref(), page_size)
+            .fetch_page(page - 1)
+            .await?;
+
+        let list = items
+            .into_iter()
+            .map(Self::map_achievement_backfill_model)
+            .collect();
+
+        Ok((list, total_count))
+    }
+
+    async fn get_achievement_backfill_by_id(
+        &self,
+        id: i32,
+    ) -> Result<Option<AchievementBackfillModel>, sea_orm::DbErr> {
+        let item = achievement_backfill::Entity::find_by_id(id)
+            .one(self.db.as_ref())
+            .await?;
+
+        Ok(item.map(Self::map_achievement_backfill_model))
+    }
+
+    async fn create_achievement_backfill(
+        &self,
+        model: AchievementBackfillModel,
+    ) -> Result<i32, sea_orm::DbErr> {
+        let now = Utc::now().fixed_offset();
+        let active = achievement_backfill::ActiveModel {
+            achievement_id: Set(model.achievement_id),
+            is_dry_run: Set(model.is_dry_run),
+            status: Set(AchievementBackfillStatuses::Queued.as_str().to_string()),
+            last_user_id: Set(0),
+            total_users: Set(model.total_users),
+            processed_users: Set(0),
+            affected_users: Set(0),
+            unlocked_count: Set(0),
+            created_by_id: Set(model.created_by_id),
+            created_on: Set(now),
+            updated_on: Set(now),
+            ..Default::default()
+        };
+        let result = active.insert(self.db.as_ref()).await?;
+        Ok(result.id)
+    }
+
+    async fn get_next_achievement_backfill_with_transaction(
+        &self,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Option<AchievementBackfillModel>, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
+        let item = achievement_backfill::Entity::find()
+            .filter(achievement_backfill::Column::Status.is_in([
+                AchievementBackfillStatuses::Queued.as_str(),
+                AchievementBackfillStatuses::Running.as_str(),
+            ]))
+            .order_by_asc(achievement_backfill::Column::Id)
+            .lock_exclusive()
+            .one(txn)
+            .await?;
+
+        Ok(item.map(Self::map_achievement_backfill_model))
+    }
+
+    async fn update_achievement_backfill_without_commit(
+        &self,
+        model: &AchievementBackfillModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<(), sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
+        let active = achievement_backfill::ActiveModel {
+            id: Set(model.id),
+            status: Set(model.status.as_str().to_string()),
+            last_user_id: Set(model.last_user_id),
+            processed_users: Set(model.processed_users),
+            affected_users: Set(model.affected_users),
+            unlocked_count: Set(model.unlocked_count),
+            last_error: Set(model.last_error.clone()),
+            started_on: Set(model.started_on.map(|dt| dt.fixed_offset())),
+            completed_on: Set(model.completed_on.map(|dt| dt.fixed_offset())),
+            updated_on: Set(Utc::now().fixed_offset()),
+            ..Default::default()
+        };
+        active.update(txn).await?;
+        Ok(())
+    }
+
+    async fn fail_achievement_backfill(&self, id: i32, error: &str) -> Result<(), sea_orm::DbErr> {
+        let now = Utc::now().fixed_offset();
+        achievement_backfill::Entity::update_many()
+            .col_expr(
+                achievement_backfill::Column::Status,
+                Expr::value(AchievementBackfillStatuses::Failed.as_str()),
+            )
+            .col_expr(achievement_backfill::Column::LastError, Expr::value(error))
+            .col_expr(achievement_backfill::Column::CompletedOn, Expr::value(now))
+            .col_expr(achievement_backfill::Column::UpdatedOn, Expr::value(now))
+            .filter(achievement_backfill::Column::Id.eq(id))
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn count_users_with_completed_sessions(&self) -> Result<u64, sea_orm::DbErr> {
+        game_session::Entity::find()
+            .select_only()
+            .column(game_session::Column::UserId)
+            .distinct()
+            .filter(game_session::Column::StatusId.eq(GameSessionStatuses::Completed as i32))
+            .count(self.db.as_ref())
+            .await
+    }
+
+    async fn get_user_ids_with_completed_sessions_with_transaction(
+        &self,
+        after_user_id: i32,
+        limit: u64,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<Vec<i32>, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
+        game_session::Entity::find()
+            .select_only()
+            .column(game_session::Column::UserId)
+            .distinct()
+            .filter(game_session::Column::StatusId.eq(GameSessionStatuses::Completed as i32))
+            .filter(game_session::Column::UserId.gt(after_user_id))
+            .order_by_asc(game_session::Column::UserId)
+            .limit(limit)
+            .into_tuple::<i32>()
+            .all(txn)
+            .await
+    }
+
     // ---- Admin: Game Sessions ----
 
     async fn get_all_game_sessions_paged(
@@ -939,6 +2075,7 @@ impl ScoringRepositoryTrait for ScoringRepository {
                     combo_max: session.combo_max,
                     started_at: session.started_at.with_timezone(&Utc),
                     completed_at: session.completed_at.map(|dt| dt.with_timezone(&Utc)),
+                    status_id: session.status_id,
                     created_on: session.created_on.with_timezone(&Utc),
                 }
             })
@@ -982,31 +2119,23 @@ impl ScoringRepositoryTrait for ScoringRepository {
             .into_iter()
             .map(|stats| {
                 let u = user_map.get(&stats.user_id);
-                UserStatsModel {
-                    id: stats.id,
-                    user_id: stats.user_id,
-                    user_name: u.map(|u| u.name.clone()),
-                    user_display_name: u.and_then(|u| u.display_name.clone()),
-                    total_score: stats.total_score,
-                    total_games_played: stats.total_games_played,
-                    total_time_played_seconds: stats.total_time_played_seconds,
-                    best_score: stats.best_score,
-                    best_combo: stats.best_combo,
-                    average_accuracy: stats.average_accuracy,
-                    current_streak: stats.current_streak,
-                    best_streak: stats.best_streak,
-                    last_played_at: stats.last_played_at.map(|dt| dt.with_timezone(&Utc)),
-                }
+                Self::map_user_stats_model(stats, u)
             })
             .collect();
 
         Ok((list, total_count))
     }
 
-    async fn reset_user_stats(&self, user_id: i32) -> Result<bool, sea_orm::DbErr> {
+    async fn reset_user_stats_without_commit(
+        &self,
+        user_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<bool, sea_orm::DbErr> {
+        let txn = Self::get_transaction(*transaction)?;
         let existing = user_stats::Entity::find()
             .filter(user_stats::Column::UserId.eq(user_id))
-            .one(self.db.as_ref())
+            .lock_exclusive()
+            .one(txn)
             .await?;
 
         if let Some(stats) = existing {
@@ -1021,8 +2150,26 @@ impl ScoringRepositoryTrait for ScoringRepository {
             active.current_streak = Set(0);
             active.best_streak = Set(0);
             active.last_played_at = Set(None);
+            active.streak_freezes = Set(0);
+            active.points_spent = Set(0);
+            active.last_streak_day = Set(None);
+            active.streak_reminded_on = Set(None);
+            active.total_xp = Set(0);
             active.updated_on = Set(now);
-            active.update(self.db.as_ref()).await?;
+            active.update(txn).await?;
+
+            leaderboard_score::Entity::delete_many()
+                .filter(leaderboard_score::Column::UserId.eq(user_id))
+                .exec(txn)
+                .await?;
+            user_streak_day::Entity::delete_many()
+                .filter(user_streak_day::Column::UserId.eq(user_id))
+                .exec(txn)
+                .await?;
+            user_xp_event::Entity::delete_many()
+                .filter(user_xp_event::Column::UserId.eq(user_id))
+                .exec(txn)
+                .await?;
             Ok(true)
         } else {
             Ok(false)
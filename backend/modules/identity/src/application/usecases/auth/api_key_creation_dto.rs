@@ -0,0 +1,9 @@
+use chrono::{DateTime, Utc};
+
+pub struct ApiKeyCreationDto {
+    pub name: String,
+    /// Permission codes the key may use; the owner must hold every one of them
+    pub scopes: Vec<String>,
+    pub expires_on: Option<DateTime<Utc>>,
+    pub created_by_id: i32,
+}
@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct ApiKeyDto {
+    pub id: i32,
+    pub name: String,
+    /// Start of the key, to tell keys apart; the rest is never shown again
+    pub key_prefix: String,
+    pub scopes: Vec<String>,
+    pub created_on: DateTime<Utc>,
+    pub expires_on: Option<DateTime<Utc>>,
+    pub last_used_on: Option<DateTime<Utc>>,
+}
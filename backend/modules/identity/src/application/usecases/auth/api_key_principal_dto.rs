@@ -0,0 +1,12 @@
+/// The user an API key acts for, with what the key is allowed to do
+#[derive(Default)]
+pub struct ApiKeyPrincipalDto {
+    pub api_key_id: i32,
+    pub user_id: i32,
+    pub email: String,
+    pub name: String,
+    pub display_name: Option<String>,
+    pub roles: Vec<String>,
+    /// The key's scopes that the user still holds
+    pub permissions: Vec<String>,
+}
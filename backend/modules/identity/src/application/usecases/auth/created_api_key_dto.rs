@@ -0,0 +1,10 @@
+use super::api_key_dto::ApiKeyDto;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct CreatedApiKeyDto {
+    #[serde(flatten)]
+    pub api_key: ApiKeyDto,
+    /// The whole key. Only its hash is stored, so it is shown this once.
+    pub key: String,
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct ExternalAuthorizationDto {
+    pub authorization_url: String,
+    /// Has to be kept by the client and sent back with the callback
+    pub flow_token: String,
+}
@@ -0,0 +1,10 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct ExternalLoginDto {
+    pub provider: String,
+    pub email: Option<String>,
+    pub created_on: DateTime<Utc>,
+    pub last_used_on: Option<DateTime<Utc>>,
+}
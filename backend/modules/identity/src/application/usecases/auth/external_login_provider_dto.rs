@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct ExternalLoginProviderDto {
+    pub name: String,
+    pub display_name: String,
+}
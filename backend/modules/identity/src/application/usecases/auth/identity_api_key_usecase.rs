@@ -0,0 +1,313 @@
+use super::{
+    api_key_creation_dto::ApiKeyCreationDto, api_key_dto::ApiKeyDto,
+    api_key_principal_dto::ApiKeyPrincipalDto, created_api_key_dto::CreatedApiKeyDto,
+    identity_api_key_usecase_trait::IdentityApiKeyUseCaseTrait,
+    identity_user_usecase_trait::IdentityUserUseCaseTrait,
+    service_account_creation_dto::ServiceAccountCreationDto,
+};
+use crate::application::usecases::{
+    loggedin_user_dto::LoggedInUserDto, roles::ROLE_ROOT_ADMIN, user_creation_dto::UserCreationDto,
+    user_usecase_trait::UserUseCaseTrait,
+};
+use crate::domain::{
+    models::user_api_key_model::UserApiKeyModel,
+    repositories::{
+        permission_repository_trait::PermissionRepositoryTrait,
+        user_api_key_repository_trait::UserApiKeyRepositoryTrait,
+    },
+    rules::api_key_scope_rule::ApiKeyScopeRule,
+    services::{password_hasher_trait::PasswordHasherTrait, token_helper_trait::TokenHelperTrait},
+};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use chrono::{Duration, Utc};
+use data_encoding::BASE64URL_NOPAD;
+use rex_game_shared::domain::enums::{user_kinds::UserKinds, user_statuses::UserStatuses};
+use rex_game_shared::{ApplicationError, InfraError};
+use std::collections::HashSet;
+
+/// Marks the keys this service issues, which helps secret scanners find leaked ones
+const API_KEY_PREFIX: &str = "rgk_";
+const API_KEY_BYTES: usize = 32;
+/// Characters of the key kept in clear, including `API_KEY_PREFIX`
+const API_KEY_VISIBLE_LENGTH: usize = 12;
+/// How stale `last_used_on` may get before a request updates it
+const LAST_USED_PRECISION_SECS: i64 = 60;
+const SERVICE_ACCOUNT_EMAIL_DOMAIN: &str = "service-accounts.invalid";
+
+#[derive(Clone)]
+pub struct IdentityApiKeyUseCase<AKR, IU, US, PR, PH, TH>
+where
+    AKR: UserApiKeyRepositoryTrait,
+    IU: IdentityUserUseCaseTrait,
+    US: UserUseCaseTrait,
+    PR: PermissionRepositoryTrait,
+    PH: PasswordHasherTrait,
+    TH: TokenHelperTrait,
+{
+    _user_api_key_repository: AKR,
+    _identity_user_usecase: IU,
+    _user_usecase: US,
+    _permission_repository: PR,
+    _password_hasher: PH,
+    _token_helper: TH,
+}
+
+impl<AKR, IU, US, PR, PH, TH> IdentityApiKeyUseCase<AKR, IU, US, PR, PH, TH>
+where
+    AKR: UserApiKeyRepositoryTrait,
+    IU: IdentityUserUseCaseTrait,
+    US: UserUseCaseTrait,
+    PR: PermissionRepositoryTrait,
+    PH: PasswordHasherTrait,
+    TH: TokenHelperTrait,
+{
+    pub fn new(
+        user_api_key_repository: AKR,
+        identity_user_usecase: IU,
+        user_usecase: US,
+        permission_repository: PR,
+        password_hasher: PH,
+        token_helper: TH,
+    ) -> Self {
+        Self {
+            _user_api_key_repository: user_api_key_repository,
+            _identity_user_usecase: identity_user_usecase,
+            _user_usecase: user_usecase,
+            _permission_repository: permission_repository,
+            _password_hasher: password_hasher,
+            _token_helper: token_helper,
+        }
+    }
+
+    fn to_dto(api_key: UserApiKeyModel) -> ApiKeyDto {
+        ApiKeyDto {
+            id: api_key.id,
+            name: api_key.name,
+            key_prefix: api_key.key_prefix,
+            scopes: api_key.scopes,
+            created_on: api_key.created_on,
+            expires_on: api_key.expires_on,
+            last_used_on: api_key.last_used_on,
+        }
+    }
+
+    fn is_root_admin(user: &LoggedInUserDto) -> bool {
+        user.roles
+            .iter()
+            .any(|role| role.role_name == ROLE_ROOT_ADMIN)
+    }
+
+    fn granted_permissions(user: &LoggedInUserDto) -> HashSet<String> {
+        user.permissions
+            .iter()
+            .map(|permission| permission.permisson_code.to_owned())
+            .collect()
+    }
+}
+
+impl<AKR, IU, US, PR, PH, TH> IdentityApiKeyUseCaseTrait
+    for IdentityApiKeyUseCase<AKR, IU, US, PR, PH, TH>
+where
+    AKR: UserApiKeyRepositoryTrait,
+    IU: IdentityUserUseCaseTrait,
+    US: UserUseCaseTrait,
+    PR: PermissionRepositoryTrait,
+    PH: PasswordHasherTrait,
+    TH: TokenHelperTrait,
+{
+    async fn create_service_account(
+        &self,
+        service_account: ServiceAccountCreationDto,
+    ) -> Result<i32, ApplicationError> {
+        let email = format!(
+            "{}@{}",
+            service_account.name.to_lowercase(),
+            SERVICE_ACCOUNT_EMAIL_DOMAIN
+        );
+        if self
+            ._user_usecase
+            .get_user_by_name(&service_account.name)
+            .await
+            .is_ok()
+            || self._user_usecase.get_user_by_email(&email).await.is_ok()
+        {
+            return Err(ApplicationError::conflict(
+                "A user with this name already exists",
+            ));
+        }
+
+        // No password, so it has no way to sign in
+        self._user_usecase
+            .create_user(UserCreationDto {
+                email,
+                name: service_account.name,
+                display_name: service_account.display_name,
+                password: String::new(),
+                security_stamp: self._password_hasher.generate_salt(),
+                status_id: UserStatuses::Actived as i32,
+                kind_id: UserKinds::ServiceAccount as i32,
+            })
+            .await
+    }
+
+    async fn ensure_service_account(&self, user_id: i32) -> Result<(), ApplicationError> {
+        let user = self._user_usecase.get_user_by_id(user_id).await?;
+        if user.kind_id != UserKinds::ServiceAccount as i32 {
+            return Err(ApplicationError::not_found(
+                "Service account",
+                user_id.to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn create_api_key(
+        &self,
+        user_id: i32,
+        api_key: ApiKeyCreationDto,
+    ) -> Result<CreatedApiKeyDto, ApplicationError> {
+        let mut scopes: Vec<String> = Vec::new();
+        for scope in api_key.scopes {
+            if !scopes.contains(&scope) {
+                scopes.push(scope);
+            }
+        }
+        if scopes.is_empty() {
+            return Err(ApplicationError::invalid_input(
+                "An API key needs at least one permission",
+            ));
+        }
+        if api_key
+            .expires_on
+            .is_some_and(|expires_on| expires_on <= Utc::now())
+        {
+            return Err(ApplicationError::invalid_input(
+                "The expiry date must be in the future",
+            ));
+        }
+
+        let owner = self
+            ._identity_user_usecase
+            .get_logged_in_user_by_id(user_id)
+            .await?;
+        // Root admins pass every permission check, so they may hand out any permission
+        let granted = if Self::is_root_admin(&owner) {
+            self._permission_repository
+                .get_by_codes(scopes.to_owned())
+                .await?
+                .into_iter()
+                .map(|permission| permission.code)
+                .collect()
+        } else {
+            Self::granted_permissions(&owner)
+        };
+        let missing_scopes = ApiKeyScopeRule::missing_scopes(&scopes, &granted);
+        if !missing_scopes.is_empty() {
+            return Err(ApplicationError::forbidden(format!(
+                "The key cannot have permissions its user does not have: {}",
+                missing_scopes.join(", ")
+            )));
+        }
+
+        let mut key_bytes = [0u8; API_KEY_BYTES];
+        OsRng.fill_bytes(&mut key_bytes);
+        let key = format!("{}{}", API_KEY_PREFIX, BASE64URL_NOPAD.encode(&key_bytes));
+        let mut created = UserApiKeyModel {
+            user_id,
+            name: api_key.name,
+            key_prefix: key[..API_KEY_VISIBLE_LENGTH].to_string(),
+            key_hash: self._token_helper.hash_token(&key),
+            scopes,
+            created_by_id: Some(api_key.created_by_id),
+            created_on: Utc::now(),
+            expires_on: api_key.expires_on,
+            ..Default::default()
+        };
+        created.id = self
+            ._user_api_key_repository
+            .create(created.clone())
+            .await?;
+
+        Ok(CreatedApiKeyDto {
+            api_key: Self::to_dto(created),
+            key,
+        })
+    }
+
+    async fn get_api_keys(&self, user_id: i32) -> Result<Vec<ApiKeyDto>, ApplicationError> {
+        let api_keys = self
+            ._user_api_key_repository
+            .get_by_user_id(user_id)
+            .await?;
+        Ok(api_keys.into_iter().map(Self::to_dto).collect())
+    }
+
+    async fn revoke_api_key(
+        &self,
+        user_id: i32,
+        api_key_id: i32,
+    ) -> Result<bool, ApplicationError> {
+        let revoked = self
+            ._user_api_key_repository
+            .revoke(user_id, api_key_id, Utc::now())
+            .await?;
+        if !revoked {
+            return Err(ApplicationError::not_found(
+                "API key",
+                api_key_id.to_string(),
+            ));
+        }
+        Ok(revoked)
+    }
+
+    async fn authenticate(&self, key: &str) -> Result<ApiKeyPrincipalDto, ApplicationError> {
+        let invalid_key =
+            || ApplicationError::unauthorized("The API key is invalid or has expired");
+        if !key.starts_with(API_KEY_PREFIX) {
+            return Err(invalid_key());
+        }
+
+        let now = Utc::now();
+        let api_key = self
+            ._user_api_key_repository
+            .get_by_hash(&self._token_helper.hash_token(key))
+            .await?
+            .filter(|api_key| ApiKeyScopeRule::is_usable(api_key, now))
+            .ok_or_else(invalid_key)?;
+
+        let owner = match self
+            ._identity_user_usecase
+            .get_logged_in_user_by_id(api_key.user_id)
+            .await
+        {
+            Ok(owner) => owner,
+            Err(ApplicationError::Infrastructure(InfraError::NotFound { .. })) => {
+                return Err(invalid_key())
+            }
+            Err(err) => return Err(err),
+        };
+        let permissions = ApiKeyScopeRule::effective_permissions(
+            &api_key.scopes,
+            &Self::granted_permissions(&owner),
+            Self::is_root_admin(&owner),
+        );
+
+        self._user_api_key_repository
+            .mark_used(
+                api_key.id,
+                now,
+                now - Duration::seconds(LAST_USED_PRECISION_SECS),
+            )
+            .await?;
+
+        Ok(ApiKeyPrincipalDto {
+            api_key_id: api_key.id,
+            user_id: owner.id,
+            email: owner.email,
+            name: owner.name,
+            display_name: owner.display_name,
+            roles: owner.roles.into_iter().map(|role| role.role_name).collect(),
+            permissions,
+        })
+    }
+}
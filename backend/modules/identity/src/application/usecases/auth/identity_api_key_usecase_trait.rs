@@ -0,0 +1,41 @@
+use super::{
+    api_key_creation_dto::ApiKeyCreationDto, api_key_dto::ApiKeyDto,
+    api_key_principal_dto::ApiKeyPrincipalDto, created_api_key_dto::CreatedApiKeyDto,
+    service_account_creation_dto::ServiceAccountCreationDto,
+};
+use rex_game_shared::ApplicationError;
+use std::future::Future;
+
+pub trait IdentityApiKeyUseCaseTrait {
+    /// Creates an active user that cannot sign in and acts only through its API keys
+    fn create_service_account(
+        &self,
+        service_account: ServiceAccountCreationDto,
+    ) -> impl Future<Output = Result<i32, ApplicationError>>;
+    /// Fails with not found unless the user is a service account
+    fn ensure_service_account(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<(), ApplicationError>>;
+    fn create_api_key(
+        &self,
+        user_id: i32,
+        api_key: ApiKeyCreationDto,
+    ) -> impl Future<Output = Result<CreatedApiKeyDto, ApplicationError>>;
+    fn get_api_keys(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<Vec<ApiKeyDto>, ApplicationError>>;
+    /// Fails with not found when the user has no such key
+    fn revoke_api_key(
+        &self,
+        user_id: i32,
+        api_key_id: i32,
+    ) -> impl Future<Output = Result<bool, ApplicationError>>;
+    /// Checks the key and finds what it may do. The owner's status is left to the caller,
+    /// as it is for access tokens.
+    fn authenticate(
+        &self,
+        key: &str,
+    ) -> impl Future<Output = Result<ApiKeyPrincipalDto, ApplicationError>>;
+}
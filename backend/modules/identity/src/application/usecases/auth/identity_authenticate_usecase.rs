@@ -2,124 +2,276 @@ use std::sync::Arc;
 
 use super::{
     identity_authenticate_usecase_trait::IdentityAuthenticateUseCaseTrait,
+    identity_login_throttle_usecase_trait::IdentityLoginThrottleUseCaseTrait,
+    identity_mfa_usecase_trait::IdentityMfaUseCaseTrait,
+    identity_user_status_usecase_trait::IdentityUserStatusUseCaseTrait,
+    identity_user_usecase_trait::IdentityUserUseCaseTrait,
     login_claims::LoginClaims,
+    magic_link_dto::MagicLinkDto,
+    mfa_enrolment_dto::MfaEnrolmentDto,
+    mfa_login_result::MfaLoginResult,
+    password_login_result::{AccountLockout, MfaChallenge, PasswordLoginResult},
+    user_session_dto::UserSessionDto,
 };
 use crate::application::usecases::user_usecase_trait::UserUseCaseTrait;
-use crate::domain::services::{
-    password_hasher_trait::PasswordHasherTrait,
-    token_helper_trait::TokenHelperTrait,
-    token_types::{TokenGenerationOptions, TokenValidationResult},
+use crate::domain::{
+    models::user_token_model::UserTokenModel,
+    repositories::user_token_repository_trait::UserTokenRepositoryTrait,
+    rules::{
+        AccessTokenRule, InvalidMagicLink, MagicLinkRule, RefreshTokenCheck,
+        RefreshTokenRevocation, RefreshTokenRule, RefusedAccessToken, TokenStampRule,
+    },
+    services::{
+        password_hasher_trait::PasswordHasherTrait,
+        token_helper_trait::TokenHelperTrait,
+        token_types::{TokenGenerationOptions, TokenGenerationResult, TokenValidationResult},
+    },
 };
-use chrono::{Duration, Utc};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use chrono::{DateTime, Duration, Utc};
+use data_encoding::BASE64URL_NOPAD;
 use rex_game_shared::domain::configuration_helper_trait::ConfigurationHelperTrait;
 use rex_game_shared::domain::enums::user_token_porposes::UserTokenPurposes;
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
 use rex_game_shared::ApplicationError;
+use uuid::Uuid;
+
+/// How long the password step of a two-factor login stays valid
+const MFA_TOKEN_EXPIRATION_SECS: i64 = 5 * 60;
+/// How long an emailed sign-in link stays valid
+const MAGIC_LINK_EXPIRATION_SECS: i64 = 15 * 60;
+/// Random bytes behind a sign-in link
+const MAGIC_LINK_TOKEN_BYTES: usize = 32;
+
+/// What the sign-in flows are built from, passed to `IdentityAuthenticateUseCase::new`
+pub struct IdentityAuthenticateDependencies<PH, US, TH, UTR, MF, LT, UST, IU> {
+    pub password_hasher: PH,
+    pub user_usecase: US,
+    pub token_helper: TH,
+    pub user_token_repository: UTR,
+    pub mfa_usecase: MF,
+    pub login_throttle_usecase: LT,
+    pub user_status_usecase: UST,
+    pub identity_user_usecase: IU,
+}
 
 #[derive(Clone)]
-pub struct IdentityAuthenticateUseCase<CF, PH, US, TH>
+pub struct IdentityAuthenticateUseCase<CF, PH, US, TH, UTR, MF, LT, UST, IU>
 where
     CF: ConfigurationHelperTrait,
     PH: PasswordHasherTrait,
     US: UserUseCaseTrait,
     TH: TokenHelperTrait,
+    UTR: UserTokenRepositoryTrait,
+    MF: IdentityMfaUseCaseTrait,
+    LT: IdentityLoginThrottleUseCaseTrait,
+    UST: IdentityUserStatusUseCaseTrait,
+    IU: IdentityUserUseCaseTrait,
 {
     _password_hasher: PH,
     _user_usecase: US,
     _token_helper: TH,
+    _user_token_repository: UTR,
+    _mfa_usecase: MF,
+    _login_throttle_usecase: LT,
+    _user_status_usecase: UST,
+    _identity_user_usecase: IU,
     _configuration_helper: Arc<CF>,
 }
 
-impl<CF, PH, US, TH> IdentityAuthenticateUseCase<CF, PH, US, TH>
+impl<CF, PH, US, TH, UTR, MF, LT, UST, IU>
+    IdentityAuthenticateUseCase<CF, PH, US, TH, UTR, MF, LT, UST, IU>
 where
     CF: ConfigurationHelperTrait,
     PH: PasswordHasherTrait,
     US: UserUseCaseTrait,
     TH: TokenHelperTrait,
+    UTR: UserTokenRepositoryTrait,
+    MF: IdentityMfaUseCaseTrait,
+    LT: IdentityLoginThrottleUseCaseTrait,
+    UST: IdentityUserStatusUseCaseTrait,
+    IU: IdentityUserUseCaseTrait,
 {
     pub fn new(
         configuration_helper: Arc<CF>,
-        password_hasher: PH,
-        user_usecase: US,
-        token_helper: TH,
+        dependencies: IdentityAuthenticateDependencies<PH, US, TH, UTR, MF, LT, UST, IU>,
     ) -> Self {
         Self {
             _configuration_helper: configuration_helper,
-            _password_hasher: password_hasher,
-            _user_usecase: user_usecase,
-            _token_helper: token_helper,
+            _password_hasher: dependencies.password_hasher,
+            _user_usecase: dependencies.user_usecase,
+            _token_helper: dependencies.token_helper,
+            _user_token_repository: dependencies.user_token_repository,
+            _mfa_usecase: dependencies.mfa_usecase,
+            _login_throttle_usecase: dependencies.login_throttle_usecase,
+            _user_status_usecase: dependencies.user_status_usecase,
+            _identity_user_usecase: dependencies.identity_user_usecase,
         }
     }
-}
 
-impl<CF, PH, US, TH> IdentityAuthenticateUseCaseTrait
-    for IdentityAuthenticateUseCase<CF, PH, US, TH>
-where
-    CF: ConfigurationHelperTrait,
-    PH: PasswordHasherTrait,
-    US: UserUseCaseTrait,
-    TH: TokenHelperTrait,
-{
-    async fn password_login(
+    /// Issues an access token carrying the user's current roles and permissions, so requests
+    /// can be authorized without looking them up
+    async fn issue_access_token(
         &self,
+        user_id: i32,
         email: &str,
-        password: &str,
-    ) -> Result<LoginClaims, ApplicationError> {
-        let existing_user = match self._user_usecase.get_user_by_email(email).await {
-            Ok(existing_user) => existing_user,
-            Err(err) => return Err(err),
-        };
-
-        match self
-            ._password_hasher
-            .verify_password(password, &existing_user.password_hash)
-        {
-            Ok(_) => true,
-            Err(err) => return Err(ApplicationError::invalid_input(err.to_string())),
-        };
-
-        // Note: Permissions and roles will be empty on initial login
-        // They will be populated by the authorization middleware on subsequent requests
-        // This is a simplified implementation - for full optimization,
-        // create a dedicated repository method that fetches user with roles and permissions in one query
-        let permissions: Vec<String> = vec![];
-        let roles: Vec<String> = vec![];
+        security_stamp: &str,
+    ) -> Result<TokenGenerationResult, ApplicationError> {
+        // Read before the roles, so a change in between leaves the token with the old version
+        let authorization_version = self
+            ._user_usecase
+            .get_authorization_version(user_id)
+            .await?;
+        let user = self
+            ._identity_user_usecase
+            .get_logged_in_user_by_id(user_id)
+            .await?;
+        let roles: Vec<String> = user.roles.into_iter().map(|role| role.role_name).collect();
+        // A permission granted both directly and through a role is listed once
+        let mut permissions: Vec<String> = user
+            .permissions
+            .into_iter()
+            .map(|permission| permission.permisson_code)
+            .collect();
+        permissions.sort();
+        permissions.dedup();
 
         let expiration = self
             ._configuration_helper
             .get_value::<i64>("JWT_EXPIRATION");
         let generated_access_token_options = TokenGenerationOptions {
             email: Some(email.to_string()),
-            user_id: existing_user.id,
+            user_id,
             exp_secs: Duration::milliseconds(expiration).num_seconds(),
             purpose: UserTokenPurposes::Login.to_string(),
             iat: Some(Utc::now().timestamp()),
             permissions,
             roles,
+            security_stamp: Some(security_stamp.to_owned()),
+            authorization_version: Some(authorization_version),
         };
 
-        let generated_access_token = match self
+        match self
             ._token_helper
             .generate_token(generated_access_token_options)
         {
-            Some(claims) => claims,
-            None => {
-                return Err(ApplicationError::invalid_input(String::from(
-                    "Failed to generate refresh token",
-                )))
-            }
-        };
+            Some(claims) => Ok(claims),
+            None => Err(ApplicationError::invalid_input(String::from(
+                "Failed to generate access token",
+            ))),
+        }
+    }
+
+    /// Issues the access token and starts a new refresh token family
+    async fn issue_login_claims(
+        &self,
+        user_id: i32,
+        email: &str,
+        security_stamp: String,
+    ) -> Result<LoginClaims, ApplicationError> {
+        let generated_access_token = self
+            .issue_access_token(user_id, email, &security_stamp)
+            .await?;
+
+        // Each login starts a new token family, which is what a session is
+        let generated_refresh_token = self
+            .issue_refresh_token(user_id, None, security_stamp, Uuid::new_v4().to_string())
+            .await?;
 
+        Ok(LoginClaims {
+            access_token: generated_access_token.token,
+            refresh_token: generated_refresh_token.token,
+            refresh_token_expiration: generated_refresh_token.exp,
+            email: email.to_string(),
+            sub: generated_access_token.sub,
+            expiration: generated_access_token.exp,
+        })
+    }
+
+    /// Finishes a login whose first factor has been verified: checks the account status,
+    /// then either issues tokens or asks for the second factor
+    async fn complete_login(
+        &self,
+        user_id: i32,
+        email: &str,
+        security_stamp: String,
+    ) -> Result<PasswordLoginResult, ApplicationError> {
+        self._user_status_usecase.ensure_can_access(user_id).await?;
+
+        // Users with two-factor authentication, or whose role demands it, only get a
+        // token for the second step here
+        let mfa_status = self._mfa_usecase.get_status(user_id).await?;
+        if mfa_status.enabled || mfa_status.required {
+            let generated_mfa_token_options = TokenGenerationOptions {
+                email: Some(email.to_string()),
+                user_id,
+                exp_secs: MFA_TOKEN_EXPIRATION_SECS,
+                purpose: UserTokenPurposes::MfaPending.to_string(),
+                iat: Some(Utc::now().timestamp()),
+                permissions: vec![],
+                roles: vec![],
+                security_stamp: Some(security_stamp),
+                authorization_version: None,
+            };
+            let generated_mfa_token = self
+                ._token_helper
+                .generate_token(generated_mfa_token_options)
+                .ok_or_else(|| {
+                    ApplicationError::invalid_input("Failed to generate two-factor token")
+                })?;
+
+            return Ok(PasswordLoginResult::MfaRequired(MfaChallenge {
+                mfa_token: generated_mfa_token.token,
+                expiration: generated_mfa_token.exp,
+                enrolment_required: !mfa_status.enabled,
+            }));
+        }
+
+        let login_claims = self
+            .issue_login_claims(user_id, email, security_stamp)
+            .await?;
+        Ok(PasswordLoginResult::Authenticated(login_claims))
+    }
+
+    /// Checks a token issued by `password_login` for a user who still owes a second factor
+    async fn validate_mfa_token(
+        &self,
+        mfa_token: &str,
+    ) -> Result<TokenValidationResult, ApplicationError> {
+        let claims = self._token_helper.validate_token(mfa_token)?;
+        if claims.token_type != UserTokenPurposes::MfaPending.as_str() {
+            return Err(ApplicationError::invalid_token("Invalid two-factor token"));
+        }
+        self.validate_security_stamp(claims.sub, claims.security_stamp.as_deref())
+            .await?;
+        self._user_status_usecase
+            .ensure_can_access(claims.sub)
+            .await?;
+
+        Ok(claims)
+    }
+
+    /// Generates a refresh token and stores its hash as the newest member of the family
+    async fn issue_refresh_token(
+        &self,
+        user_id: i32,
+        email: Option<String>,
+        security_stamp: String,
+        family_id: String,
+    ) -> Result<TokenGenerationResult, ApplicationError> {
         let refresh_expiration = self
             ._configuration_helper
             .get_value::<i64>("JWT_REFRESH_EXPIRATION");
         let generated_refresh_token_options = TokenGenerationOptions {
-            email: None,
-            user_id: existing_user.id,
+            email,
+            user_id,
             exp_secs: Duration::milliseconds(refresh_expiration).num_seconds(),
             purpose: UserTokenPurposes::RefreshToken.to_string(),
             iat: Some(Utc::now().timestamp()),
             permissions: vec![], // Refresh tokens don't need permissions
             roles: vec![],
+            security_stamp: Some(security_stamp),
+            authorization_version: None,
         };
         let generated_refresh_token = match self
             ._token_helper
@@ -133,13 +285,219 @@ where
             }
         };
 
-        Ok(LoginClaims {
-            access_token: generated_access_token.token,
-            refresh_token: generated_refresh_token.token,
-            refresh_token_expiration: generated_refresh_token.exp,
-            email: email.to_string(),
-            sub: generated_access_token.sub,
-            expiration: generated_access_token.exp,
+        let user_token = UserTokenModel {
+            user_id,
+            token: self
+                ._token_helper
+                .hash_token(&generated_refresh_token.token),
+            expiration: generated_refresh_token.exp as i32,
+            created_by_id: user_id,
+            updated_by_id: user_id,
+            is_actived: true,
+            purpose: UserTokenPurposes::RefreshToken as i32,
+            family_id: Some(family_id),
+            ..Default::default()
+        };
+        self._user_token_repository.create(user_token).await?;
+
+        Ok(generated_refresh_token)
+    }
+
+    /// Stored in place of a sign-in link, so the link is only found together with the nonce
+    /// of the device that asked for it
+    fn hash_magic_link(&self, token: &str, nonce: &str) -> Result<String, InvalidMagicLink> {
+        MagicLinkRule::bound_value(token, nonce)
+            .map(|bound_value| self._token_helper.hash_token(&bound_value))
+    }
+
+    /// Looks up the stored refresh token owned by the user, if any
+    async fn find_refresh_token(
+        &self,
+        user_id: i32,
+        refresh_token: &str,
+    ) -> Result<Option<UserTokenModel>, ApplicationError> {
+        let token_hash = self._token_helper.hash_token(refresh_token);
+        let user_token = match self._user_token_repository.get_by_token(&token_hash).await {
+            Ok(user_token) => user_token,
+            Err(rex_game_shared::InfraError::NotFound { .. }) => return Ok(None),
+            Err(err) => return Err(ApplicationError::Infrastructure(err)),
+        };
+
+        if !RefreshTokenRule::belongs_to(&user_token, user_id) {
+            return Ok(None);
+        }
+
+        Ok(Some(user_token))
+    }
+}
+
+impl<CF, PH, US, TH, UTR, MF, LT, UST, IU> IdentityAuthenticateUseCaseTrait
+    for IdentityAuthenticateUseCase<CF, PH, US, TH, UTR, MF, LT, UST, IU>
+where
+    CF: ConfigurationHelperTrait,
+    PH: PasswordHasherTrait,
+    US: UserUseCaseTrait,
+    TH: TokenHelperTrait,
+    UTR: UserTokenRepositoryTrait,
+    MF: IdentityMfaUseCaseTrait,
+    LT: IdentityLoginThrottleUseCaseTrait,
+    UST: IdentityUserStatusUseCaseTrait,
+    IU: IdentityUserUseCaseTrait,
+{
+    async fn password_login(
+        &self,
+        email: &str,
+        password: &str,
+    ) -> Result<PasswordLoginResult, ApplicationError> {
+        let existing_user = match self._user_usecase.get_user_by_email(email).await {
+            Ok(existing_user) => existing_user,
+            Err(err) => return Err(err),
+        };
+
+        // Checked before the password hash, so a locked account costs no hashing
+        self._login_throttle_usecase
+            .ensure_can_attempt(existing_user.id)
+            .await?;
+
+        if let Err(err) = self
+            ._password_hasher
+            .verify_password(password, &existing_user.password_hash)
+        {
+            let locked_until = self
+                ._login_throttle_usecase
+                .record_failed_attempt(existing_user.id)
+                .await?;
+            return match locked_until {
+                Some(locked_until) => Ok(PasswordLoginResult::Locked(AccountLockout {
+                    user_id: existing_user.id,
+                    email: existing_user.email,
+                    name: existing_user.name,
+                    locked_until,
+                })),
+                None => Err(ApplicationError::invalid_input(err.to_string())),
+            };
+        }
+        self._login_throttle_usecase
+            .record_successful_attempt(existing_user.id)
+            .await?;
+
+        // Only checked once the password is right, so the status is not given away
+        self.complete_login(existing_user.id, email, existing_user.security_stamp)
+            .await
+    }
+
+    async fn external_login(&self, user_id: i32) -> Result<PasswordLoginResult, ApplicationError> {
+        let user = self._user_usecase.get_user_by_id(user_id).await?;
+        let security_stamp = self._user_usecase.get_security_stamp(user_id).await?;
+        self.complete_login(user.id, &user.email, security_stamp)
+            .await
+    }
+
+    async fn create_magic_link(
+        &self,
+        user_id: i32,
+        nonce: &str,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<MagicLinkDto, ApplicationError> {
+        let mut token_bytes = [0u8; MAGIC_LINK_TOKEN_BYTES];
+        OsRng.fill_bytes(&mut token_bytes);
+        let token = BASE64URL_NOPAD.encode(&token_bytes);
+        let expiration = Utc::now() + Duration::seconds(MAGIC_LINK_EXPIRATION_SECS);
+
+        let user_token = UserTokenModel {
+            user_id,
+            token: self
+                .hash_magic_link(&token, nonce)
+                .map_err(|_| ApplicationError::invalid_input("A device nonce is required"))?,
+            expiration: expiration.timestamp() as i32,
+            created_by_id: user_id,
+            updated_by_id: user_id,
+            is_actived: true,
+            purpose: UserTokenPurposes::MagicLink as i32,
+            ..Default::default()
+        };
+        self._user_token_repository
+            .create_without_commit(user_token, transaction)
+            .await?;
+
+        Ok(MagicLinkDto { token, expiration })
+    }
+
+    async fn magic_link_login(
+        &self,
+        token: &str,
+        nonce: &str,
+    ) -> Result<PasswordLoginResult, ApplicationError> {
+        let invalid_link =
+            || ApplicationError::invalid_token("The sign-in link is invalid or has expired");
+        let hashed_link = self
+            .hash_magic_link(token, nonce)
+            .map_err(|_| invalid_link())?;
+
+        // A wrong nonce finds nothing, the same as a wrong token
+        let user_token = match self._user_token_repository.get_by_token(&hashed_link).await {
+            Ok(user_token) => user_token,
+            Err(rex_game_shared::InfraError::NotFound { .. }) => return Err(invalid_link()),
+            Err(err) => return Err(ApplicationError::Infrastructure(err)),
+        };
+        MagicLinkRule::check(&user_token, Utc::now()).map_err(|_| invalid_link())?;
+
+        // Deactivating is the claim on the link, so two clicks cannot both sign in
+        let claimed = self
+            ._user_token_repository
+            .deactivate_if_active(user_token.id, user_token.user_id)
+            .await?;
+        if !claimed {
+            return Err(invalid_link());
+        }
+
+        let user = self
+            ._user_usecase
+            .get_user_by_id(user_token.user_id)
+            .await?;
+        let security_stamp = self._user_usecase.get_security_stamp(user.id).await?;
+        self.complete_login(user.id, &user.email, security_stamp)
+            .await
+    }
+
+    async fn begin_mfa_enrolment(
+        &self,
+        mfa_token: &str,
+    ) -> Result<MfaEnrolmentDto, ApplicationError> {
+        let claims = self.validate_mfa_token(mfa_token).await?;
+        let email = claims.email.unwrap_or_default();
+        self._mfa_usecase.begin_enrolment(claims.sub, &email).await
+    }
+
+    async fn complete_mfa_login(
+        &self,
+        mfa_token: &str,
+        code: &str,
+    ) -> Result<MfaLoginResult, ApplicationError> {
+        let claims = self.validate_mfa_token(mfa_token).await?;
+        let user_id = claims.sub;
+        let email = claims
+            .email
+            .ok_or_else(|| ApplicationError::invalid_token("Invalid two-factor token"))?;
+        let security_stamp = claims
+            .security_stamp
+            .ok_or_else(|| ApplicationError::invalid_token("Invalid two-factor token"))?;
+
+        // A user who had to enrol proves the new authenticator works with the same code
+        let mfa_status = self._mfa_usecase.get_status(user_id).await?;
+        let recovery_codes = if mfa_status.enabled {
+            self._mfa_usecase.verify_code(user_id, code).await?;
+            None
+        } else {
+            Some(self._mfa_usecase.confirm_enrolment(user_id, code).await?)
+        };
+
+        let login_claims = self
+            .issue_login_claims(user_id, &email, security_stamp)
+            .await?;
+        Ok(MfaLoginResult {
+            claims: login_claims,
+            recovery_codes,
         })
     }
 
@@ -148,14 +506,10 @@ where
         access_token: &str,
         refresh_token: &str,
     ) -> Result<LoginClaims, ApplicationError> {
-        let refresh_expiration = self
-            ._configuration_helper
-            .get_value::<i64>("JWT_REFRESH_EXPIRATION");
-        let access_token_claims = match self._token_helper.refresh_access_token(
-            access_token,
-            refresh_token,
-            refresh_expiration,
-        ) {
+        let access_token_claims = match self
+            ._token_helper
+            .validate_refresh_token(access_token, refresh_token)
+        {
             Some(claims) => claims,
             None => {
                 return Err(ApplicationError::invalid_input(String::from(
@@ -168,46 +522,193 @@ where
             .email
             .ok_or_else(|| ApplicationError::not_found("No email found", "".to_string()))?;
 
-        let refresh_expiration = self
-            ._configuration_helper
-            .get_value::<i64>("JWT_REFRESH_EXPIRATION");
-        let generated_token_options = TokenGenerationOptions {
-            email: Some(email.to_owned()),
-            user_id: access_token_claims.sub,
-            exp_secs: Duration::milliseconds(refresh_expiration).num_seconds(),
-            purpose: UserTokenPurposes::RefreshToken.to_string(),
-            iat: Some(Utc::now().timestamp()),
-            permissions: vec![],
-            roles: vec![],
-        };
-        let generated_token = match self._token_helper.generate_token(generated_token_options) {
-            Some(refresh_token) => refresh_token,
-            None => {
-                return Err(ApplicationError::invalid_input(String::from(
-                    "Failed to generate refresh token",
-                )))
+        let user_id = access_token_claims.sub;
+        let user_token = self
+            .find_refresh_token(user_id, refresh_token)
+            .await?
+            .ok_or_else(|| ApplicationError::invalid_token("Refresh token is not recognized"))?;
+
+        // Tokens minted before the stamp rotated (password reset, forced logout) are dead, and
+        // a rotated token coming back means it was copied; either way the family goes
+        let security_stamp = self._user_usecase.get_security_stamp(user_id).await?;
+        let refresh_token_claims = self._token_helper.validate_token(refresh_token)?;
+        let family_id = match RefreshTokenRule::check(
+            &user_token,
+            refresh_token_claims.security_stamp.as_deref(),
+            &security_stamp,
+        ) {
+            RefreshTokenCheck::Rotate { family_id } => family_id,
+            RefreshTokenCheck::Unrecognized => {
+                return Err(ApplicationError::invalid_token(
+                    "Refresh token is not recognized",
+                ))
+            }
+            RefreshTokenCheck::RevokeFamily { family_id, reason } => {
+                self._user_token_repository
+                    .deactivate_family(user_id, &family_id)
+                    .await?;
+                return Err(ApplicationError::invalid_token(match reason {
+                    RefreshTokenRevocation::StampRotated => "Refresh token has been revoked",
+                    RefreshTokenRevocation::Replayed => "Refresh token has already been used",
+                }));
             }
         };
 
+        // Suspended, banned or deleted users lose the session too
+        if let Err(err) = self._user_status_usecase.ensure_can_access(user_id).await {
+            self._user_token_repository
+                .deactivate_family(user_id, &family_id)
+                .await?;
+            return Err(err);
+        }
+
+        // Two requests racing with the same token: only the first one rotates it
+        let rotated = self
+            ._user_token_repository
+            .deactivate_if_active(user_token.id, user_id)
+            .await?;
+        if !rotated {
+            self._user_token_repository
+                .deactivate_family(user_id, &family_id)
+                .await?;
+            return Err(ApplicationError::invalid_token(
+                "Refresh token has already been used",
+            ));
+        }
+
+        // The new access token picks up any change to the user's roles and permissions
+        let generated_access_token = self
+            .issue_access_token(user_id, &email, &security_stamp)
+            .await?;
+        let generated_token = self
+            .issue_refresh_token(user_id, Some(email.to_owned()), security_stamp, family_id)
+            .await?;
+
         Ok(LoginClaims {
-            access_token: access_token_claims.token,
+            access_token: generated_access_token.token,
             refresh_token: generated_token.token,
             refresh_token_expiration: generated_token.exp,
             email: email,
-            sub: access_token_claims.sub,
-            expiration: access_token_claims.exp,
+            sub: generated_access_token.sub,
+            expiration: generated_access_token.exp,
         })
     }
 
+    async fn logout(&self, user_id: i32, refresh_token: &str) -> Result<bool, ApplicationError> {
+        let user_token = match self.find_refresh_token(user_id, refresh_token).await? {
+            Some(user_token) => user_token,
+            None => return Ok(false),
+        };
+
+        let revoked = match user_token.family_id {
+            Some(family_id) => {
+                self._user_token_repository
+                    .deactivate_family(user_id, &family_id)
+                    .await?
+                    > 0
+            }
+            None => {
+                self._user_token_repository
+                    .deactivate_if_active(user_token.id, user_id)
+                    .await?
+            }
+        };
+        Ok(revoked)
+    }
+
+    async fn get_sessions(&self, user_id: i32) -> Result<Vec<UserSessionDto>, ApplicationError> {
+        let user_tokens = self
+            ._user_token_repository
+            .get_active_by_user(user_id, UserTokenPurposes::RefreshToken as i32)
+            .await?;
+
+        // Only the newest token of a family is active, so each row is one session
+        let sessions = user_tokens
+            .into_iter()
+            .filter_map(|f| {
+                Some(UserSessionDto {
+                    id: f.family_id?,
+                    last_refreshed_on: f.created_on,
+                    expiration: DateTime::from_timestamp(f.expiration as i64, 0)?,
+                })
+            })
+            .collect();
+        Ok(sessions)
+    }
+
+    async fn revoke_session(
+        &self,
+        user_id: i32,
+        session_id: &str,
+    ) -> Result<bool, ApplicationError> {
+        let user_tokens = self
+            ._user_token_repository
+            .get_active_by_user(user_id, UserTokenPurposes::RefreshToken as i32)
+            .await?;
+        if RefreshTokenRule::find_session(&user_tokens, user_id, session_id).is_none() {
+            return Err(ApplicationError::not_found("Session", session_id));
+        }
+
+        let revoked = self
+            ._user_token_repository
+            .deactivate_family(user_id, session_id)
+            .await?;
+        if revoked == 0 {
+            return Err(ApplicationError::not_found("Session", session_id));
+        }
+
+        Ok(true)
+    }
+
+    async fn sign_out_everywhere(&self, user_id: i32) -> Result<bool, ApplicationError> {
+        self._user_usecase.rotate_security_stamp(user_id).await?;
+        self._user_token_repository
+            .deactivate_by_user(user_id, UserTokenPurposes::RefreshToken as i32)
+            .await?;
+        Ok(true)
+    }
+
+    async fn validate_security_stamp(
+        &self,
+        user_id: i32,
+        security_stamp: Option<&str>,
+    ) -> Result<(), ApplicationError> {
+        let current_security_stamp = self._user_usecase.get_security_stamp(user_id).await?;
+        TokenStampRule::check_security_stamp(security_stamp, &current_security_stamp)
+            .map_err(|_| ApplicationError::invalid_token("Token has been revoked"))
+    }
+
+    async fn validate_authorization_version(
+        &self,
+        user_id: i32,
+        authorization_version: Option<i32>,
+    ) -> Result<(), ApplicationError> {
+        let current_authorization_version = self
+            ._user_usecase
+            .get_authorization_version(user_id)
+            .await?;
+        TokenStampRule::check_authorization_version(
+            authorization_version,
+            current_authorization_version,
+        )
+        .map_err(|_| ApplicationError::invalid_token("Token roles and permissions are out of date"))
+    }
+
     fn validate_token(
         &self,
         access_token: &str,
     ) -> Result<TokenValidationResult, ApplicationError> {
         let verify_result = self._token_helper.validate_token(access_token);
 
-        match verify_result {
-            Ok(claims) => Ok(claims),
-            Err(_) => Err(ApplicationError::invalid_token("Invalid token")),
+        let claims = verify_result.map_err(|_| ApplicationError::invalid_token("Invalid token"))?;
+        match AccessTokenRule::check_purpose(&claims.token_type) {
+            Ok(()) => Ok(claims),
+            Err(RefusedAccessToken::MfaPending) => Err(ApplicationError::invalid_token(
+                "Two-factor authentication is not complete",
+            )),
+            Err(RefusedAccessToken::WrongPurpose) => {
+                Err(ApplicationError::invalid_token("Invalid token"))
+            }
         }
     }
 }
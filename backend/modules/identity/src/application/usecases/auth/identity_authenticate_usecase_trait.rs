@@ -1,22 +1,93 @@
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
 use rex_game_shared::ApplicationError;
 
 use crate::domain::services::TokenValidationResult;
 
-use super::login_claims::LoginClaims;
+use super::{
+    login_claims::LoginClaims, magic_link_dto::MagicLinkDto, mfa_enrolment_dto::MfaEnrolmentDto,
+    mfa_login_result::MfaLoginResult, password_login_result::PasswordLoginResult,
+    user_session_dto::UserSessionDto,
+};
 use std::future::Future;
 
 pub trait IdentityAuthenticateUseCaseTrait {
+    /// Checks the password. Users who need a second factor get a challenge instead of tokens.
+    /// Repeated failures slow the account down and lock it; see `LoginThrottlePolicy`.
     fn password_login(
         &self,
         email: &str,
         password: &str,
-    ) -> impl Future<Output = Result<LoginClaims, ApplicationError>>;
+    ) -> impl Future<Output = Result<PasswordLoginResult, ApplicationError>>;
+    /// Signs in a user whose identity an external provider has vouched for. The password
+    /// throttle does not apply, but the account status and second factor still do.
+    fn external_login(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<PasswordLoginResult, ApplicationError>>;
+    /// Creates a single-use sign-in link for the user, bound to the nonce kept by the device
+    /// that asked for it. The link is stored in the transaction, so it commits with its email.
+    fn create_magic_link(
+        &self,
+        user_id: i32,
+        nonce: &str,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> impl Future<Output = Result<MagicLinkDto, ApplicationError>>;
+    /// Signs in with a link from `create_magic_link`, on the device holding its nonce. The
+    /// account status and second factor still apply.
+    fn magic_link_login(
+        &self,
+        token: &str,
+        nonce: &str,
+    ) -> impl Future<Output = Result<PasswordLoginResult, ApplicationError>>;
+    /// Starts a mandatory enrolment for the user behind a two-factor token
+    fn begin_mfa_enrolment(
+        &self,
+        mfa_token: &str,
+    ) -> impl Future<Output = Result<MfaEnrolmentDto, ApplicationError>>;
+    /// Finishes a login with a TOTP or recovery code, completing the enrolment if one is due
+    fn complete_mfa_login(
+        &self,
+        mfa_token: &str,
+        code: &str,
+    ) -> impl Future<Output = Result<MfaLoginResult, ApplicationError>>;
     fn refresh_access_token(
         &self,
         access_token: &str,
         refresh_token: &str,
     ) -> impl Future<Output = Result<LoginClaims, ApplicationError>>;
 
+    fn logout(
+        &self,
+        user_id: i32,
+        refresh_token: &str,
+    ) -> impl Future<Output = Result<bool, ApplicationError>>;
+    fn get_sessions(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<Vec<UserSessionDto>, ApplicationError>>;
+    fn revoke_session(
+        &self,
+        user_id: i32,
+        session_id: &str,
+    ) -> impl Future<Output = Result<bool, ApplicationError>>;
+    /// Rotates the user's security stamp and revokes all of their refresh tokens
+    fn sign_out_everywhere(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<bool, ApplicationError>>;
+    fn validate_security_stamp(
+        &self,
+        user_id: i32,
+        security_stamp: Option<&str>,
+    ) -> impl Future<Output = Result<(), ApplicationError>>;
+    /// Fails when the user's roles or permissions changed after the access token was issued,
+    /// so the roles and permissions it carries can no longer be trusted
+    fn validate_authorization_version(
+        &self,
+        user_id: i32,
+        authorization_version: Option<i32>,
+    ) -> impl Future<Output = Result<(), ApplicationError>>;
+
     fn validate_token(&self, access_token: &str)
         -> Result<TokenValidationResult, ApplicationError>;
 }
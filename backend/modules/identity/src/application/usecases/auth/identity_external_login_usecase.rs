@@ -0,0 +1,320 @@
+use super::{
+    external_authorization_dto::ExternalAuthorizationDto, external_login_dto::ExternalLoginDto,
+    external_login_provider_dto::ExternalLoginProviderDto,
+    identity_external_login_usecase_trait::IdentityExternalLoginUseCaseTrait,
+};
+use crate::application::usecases::{
+    user_creation_dto::UserCreationDto, user_usecase_trait::UserUseCaseTrait,
+};
+use crate::domain::{
+    models::user_external_login_model::UserExternalLoginModel,
+    repositories::user_external_login_repository_trait::UserExternalLoginRepositoryTrait,
+    services::{
+        oidc_client_trait::OidcClientTrait, oidc_types::OidcIdentity,
+        password_hasher_trait::PasswordHasherTrait,
+    },
+};
+use chrono::Utc;
+use rex_game_shared::domain::enums::{user_kinds::UserKinds, user_statuses::UserStatuses};
+use rex_game_shared::domain::transaction_manager_trait::{
+    TransactionManagerTrait, TransactionWrapperTrait,
+};
+use rex_game_shared::infrastructure::database::TransactionManager;
+use rex_game_shared::{ApplicationError, InfraError};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct IdentityExternalLoginUseCase<OC, ELR, US, PH>
+where
+    OC: OidcClientTrait,
+    ELR: UserExternalLoginRepositoryTrait,
+    US: UserUseCaseTrait,
+    PH: PasswordHasherTrait,
+{
+    _oidc_client: OC,
+    _user_external_login_repository: ELR,
+    _user_usecase: US,
+    _password_hasher: PH,
+    _transaction_manager: TransactionManager,
+}
+
+impl<OC, ELR, US, PH> IdentityExternalLoginUseCase<OC, ELR, US, PH>
+where
+    OC: OidcClientTrait,
+    ELR: UserExternalLoginRepositoryTrait,
+    US: UserUseCaseTrait,
+    PH: PasswordHasherTrait,
+{
+    pub fn new(
+        oidc_client: OC,
+        user_external_login_repository: ELR,
+        user_usecase: US,
+        password_hasher: PH,
+        transaction_manager: TransactionManager,
+    ) -> Self {
+        Self {
+            _oidc_client: oidc_client,
+            _user_external_login_repository: user_external_login_repository,
+            _user_usecase: user_usecase,
+            _password_hasher: password_hasher,
+            _transaction_manager: transaction_manager,
+        }
+    }
+
+    fn to_dto(external_login: UserExternalLoginModel) -> ExternalLoginDto {
+        ExternalLoginDto {
+            provider: external_login.provider,
+            email: external_login.email,
+            created_on: external_login.created_on,
+            last_used_on: external_login.last_used_on,
+        }
+    }
+
+    /// Works out the active user to create for a provider account seen for the first time. It
+    /// gets no password, so signing in with the provider is its only way in until one is set.
+    async fn new_user(&self, identity: &OidcIdentity) -> Result<UserCreationDto, ApplicationError> {
+        let email = match (&identity.email, identity.email_verified) {
+            (Some(email), true) => email.to_owned(),
+            _ => {
+                return Err(ApplicationError::forbidden(
+                    "The provider did not share a verified email address",
+                ))
+            }
+        };
+
+        // Taking over an existing account needs its password first, see `complete_link`
+        match self._user_usecase.get_user_by_email(&email).await {
+            Ok(_) => {
+                return Err(ApplicationError::conflict(
+                    "An account with this email already exists. Sign in with your password and link the provider from your account settings",
+                ))
+            }
+            Err(ApplicationError::Infrastructure(InfraError::NotFound { .. })) => {}
+            Err(err) => return Err(err),
+        }
+
+        let local_part = email.split('@').next().unwrap_or_default();
+        let mut name: String = local_part
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+            .collect();
+        if name.is_empty() {
+            name = String::from("user");
+        }
+        if self._user_usecase.get_user_by_name(&name).await.is_ok() {
+            name = format!("{}-{}", name, &Uuid::new_v4().simple().to_string()[..6]);
+        }
+
+        Ok(UserCreationDto {
+            email,
+            name,
+            display_name: identity.name.to_owned(),
+            password: String::new(),
+            security_stamp: self._password_hasher.generate_salt(),
+            status_id: UserStatuses::Actived as i32,
+            kind_id: UserKinds::Person as i32,
+        })
+    }
+
+    /// Creates the user together with its link, so a failed link leaves no user behind
+    async fn provision_user_with_transaction(
+        &self,
+        user: UserCreationDto,
+        identity: OidcIdentity,
+        transaction: &dyn TransactionWrapperTrait,
+    ) -> Result<i32, ApplicationError> {
+        let user_id = self
+            ._user_usecase
+            .create_user_with_transaction(user, Box::new(transaction))
+            .await?;
+        let created = self
+            ._user_external_login_repository
+            .create_without_commit(
+                UserExternalLoginModel {
+                    user_id,
+                    provider: identity.provider,
+                    subject: identity.subject,
+                    email: identity.email,
+                    last_used_on: Some(Utc::now()),
+                    ..Default::default()
+                },
+                Box::new(transaction),
+            )
+            .await?;
+        if !created {
+            return Err(ApplicationError::conflict(
+                "The account is being signed in elsewhere, please try again",
+            ));
+        }
+
+        Ok(user_id)
+    }
+}
+
+impl<OC, ELR, US, PH> IdentityExternalLoginUseCaseTrait
+    for IdentityExternalLoginUseCase<OC, ELR, US, PH>
+where
+    OC: OidcClientTrait,
+    ELR: UserExternalLoginRepositoryTrait,
+    US: UserUseCaseTrait,
+    PH: PasswordHasherTrait,
+{
+    fn get_providers(&self) -> Vec<ExternalLoginProviderDto> {
+        self._oidc_client
+            .get_providers()
+            .into_iter()
+            .map(|f| ExternalLoginProviderDto {
+                name: f.name,
+                display_name: f.display_name,
+            })
+            .collect()
+    }
+
+    async fn begin_sign_in(
+        &self,
+        provider: &str,
+    ) -> Result<ExternalAuthorizationDto, ApplicationError> {
+        let authorization = self
+            ._oidc_client
+            .begin_authorization(provider, None)
+            .await?;
+        Ok(ExternalAuthorizationDto {
+            authorization_url: authorization.authorization_url,
+            flow_token: authorization.flow_token,
+        })
+    }
+
+    async fn complete_sign_in(
+        &self,
+        provider: &str,
+        code: &str,
+        state: &str,
+        flow_token: &str,
+    ) -> Result<i32, ApplicationError> {
+        let identity = self
+            ._oidc_client
+            .complete_authorization(provider, code, state, flow_token)
+            .await?;
+        if identity.link_user_id.is_some() {
+            return Err(ApplicationError::invalid_token(
+                "The sign-in was started to link an account",
+            ));
+        }
+
+        let existing = self
+            ._user_external_login_repository
+            .get_by_subject(&identity.provider, &identity.subject)
+            .await?;
+        if let Some(external_login) = existing {
+            self._user_external_login_repository
+                .mark_used(external_login.id, identity.email, Utc::now())
+                .await?;
+            return Ok(external_login.user_id);
+        }
+
+        let user = self.new_user(&identity).await?;
+        let transaction = self._transaction_manager.begin().await?;
+        let provisioned = self
+            .provision_user_with_transaction(user, identity, &transaction)
+            .await;
+        match provisioned {
+            Ok(user_id) => {
+                self._transaction_manager.commit(transaction).await?;
+                Ok(user_id)
+            }
+            Err(err) => {
+                self._transaction_manager.rollback(transaction).await?;
+                Err(err)
+            }
+        }
+    }
+
+    async fn begin_link(
+        &self,
+        provider: &str,
+        user_id: i32,
+    ) -> Result<ExternalAuthorizationDto, ApplicationError> {
+        let authorization = self
+            ._oidc_client
+            .begin_authorization(provider, Some(user_id))
+            .await?;
+        Ok(ExternalAuthorizationDto {
+            authorization_url: authorization.authorization_url,
+            flow_token: authorization.flow_token,
+        })
+    }
+
+    async fn complete_link(
+        &self,
+        provider: &str,
+        user_id: i32,
+        code: &str,
+        state: &str,
+        flow_token: &str,
+    ) -> Result<ExternalLoginDto, ApplicationError> {
+        let identity = self
+            ._oidc_client
+            .complete_authorization(provider, code, state, flow_token)
+            .await?;
+        // A flow started by someone else must not attach their provider account to this user
+        if identity.link_user_id != Some(user_id) {
+            return Err(ApplicationError::forbidden(
+                "The link was started by another account",
+            ));
+        }
+
+        let external_login = UserExternalLoginModel {
+            user_id,
+            provider: identity.provider,
+            subject: identity.subject,
+            email: identity.email,
+            created_on: Utc::now(),
+            ..Default::default()
+        };
+        let created = self
+            ._user_external_login_repository
+            .create(external_login.clone())
+            .await?;
+        if !created {
+            return Err(ApplicationError::conflict(
+                "The provider account is already linked to a user, or you already linked an account at this provider",
+            ));
+        }
+
+        Ok(Self::to_dto(external_login))
+    }
+
+    async fn get_links(&self, user_id: i32) -> Result<Vec<ExternalLoginDto>, ApplicationError> {
+        let external_logins = self
+            ._user_external_login_repository
+            .get_by_user_id(user_id)
+            .await?;
+        Ok(external_logins.into_iter().map(Self::to_dto).collect())
+    }
+
+    async fn unlink(&self, user_id: i32, provider: &str) -> Result<bool, ApplicationError> {
+        let external_logins = self
+            ._user_external_login_repository
+            .get_by_user_id(user_id)
+            .await?;
+        if !external_logins.iter().any(|f| f.provider == provider) {
+            return Ok(false);
+        }
+
+        if external_logins.len() == 1 {
+            let user = self._user_usecase.get_user_by_id(user_id).await?;
+            let user_details = self._user_usecase.get_user_by_email(&user.email).await?;
+            if user_details.password_hash.is_empty() {
+                return Err(ApplicationError::invalid_state(
+                    "Set a password before removing the only way to sign in",
+                ));
+            }
+        }
+
+        let deleted = self
+            ._user_external_login_repository
+            .delete(user_id, provider)
+            .await?;
+        Ok(deleted)
+    }
+}
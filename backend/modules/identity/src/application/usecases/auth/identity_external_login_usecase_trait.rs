@@ -0,0 +1,46 @@
+use super::{
+    external_authorization_dto::ExternalAuthorizationDto, external_login_dto::ExternalLoginDto,
+    external_login_provider_dto::ExternalLoginProviderDto,
+};
+use rex_game_shared::ApplicationError;
+use std::future::Future;
+
+pub trait IdentityExternalLoginUseCaseTrait {
+    fn get_providers(&self) -> Vec<ExternalLoginProviderDto>;
+    fn begin_sign_in(
+        &self,
+        provider: &str,
+    ) -> impl Future<Output = Result<ExternalAuthorizationDto, ApplicationError>>;
+    /// Returns the user the provider account belongs to. The first sign-in creates an
+    /// active user, unless the email is already taken by an account that has to link it.
+    fn complete_sign_in(
+        &self,
+        provider: &str,
+        code: &str,
+        state: &str,
+        flow_token: &str,
+    ) -> impl Future<Output = Result<i32, ApplicationError>>;
+    fn begin_link(
+        &self,
+        provider: &str,
+        user_id: i32,
+    ) -> impl Future<Output = Result<ExternalAuthorizationDto, ApplicationError>>;
+    fn complete_link(
+        &self,
+        provider: &str,
+        user_id: i32,
+        code: &str,
+        state: &str,
+        flow_token: &str,
+    ) -> impl Future<Output = Result<ExternalLoginDto, ApplicationError>>;
+    fn get_links(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<Vec<ExternalLoginDto>, ApplicationError>>;
+    /// Refuses to remove the last way into an account that has no password
+    fn unlink(
+        &self,
+        user_id: i32,
+        provider: &str,
+    ) -> impl Future<Output = Result<bool, ApplicationError>>;
+}
@@ -0,0 +1,79 @@
+use super::identity_login_throttle_usecase_trait::IdentityLoginThrottleUseCaseTrait;
+use crate::domain::{
+    repositories::user_login_throttle_repository_trait::UserLoginThrottleRepositoryTrait,
+    rules::login_throttle_policy::LoginThrottlePolicy,
+};
+use chrono::{DateTime, Utc};
+use rex_game_shared::ApplicationError;
+
+#[derive(Clone)]
+pub struct IdentityLoginThrottleUseCase<ULR>
+where
+    ULR: UserLoginThrottleRepositoryTrait,
+{
+    _user_login_throttle_repository: ULR,
+    _policy: LoginThrottlePolicy,
+}
+
+impl<ULR> IdentityLoginThrottleUseCase<ULR>
+where
+    ULR: UserLoginThrottleRepositoryTrait,
+{
+    pub fn new(user_login_throttle_repository: ULR, policy: LoginThrottlePolicy) -> Self {
+        Self {
+            _user_login_throttle_repository: user_login_throttle_repository,
+            _policy: policy,
+        }
+    }
+}
+
+impl<ULR> IdentityLoginThrottleUseCaseTrait for IdentityLoginThrottleUseCase<ULR>
+where
+    ULR: UserLoginThrottleRepositoryTrait,
+{
+    async fn ensure_can_attempt(&self, user_id: i32) -> Result<(), ApplicationError> {
+        let state = self
+            ._user_login_throttle_repository
+            .get_by_user_id(user_id)
+            .await?;
+
+        match self._policy.retry_after(&state, Utc::now()) {
+            Some(retry_after_secs) => Err(ApplicationError::too_many_attempts(retry_after_secs)),
+            None => Ok(()),
+        }
+    }
+
+    async fn record_failed_attempt(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<DateTime<Utc>>, ApplicationError> {
+        let now = Utc::now();
+        let failed_login_count = self
+            ._user_login_throttle_repository
+            .record_failure(user_id, now)
+            .await?;
+        if !self._policy.should_lock(failed_login_count) {
+            return Ok(None);
+        }
+
+        let locked_until = self._policy.locked_until(now);
+        let locked = self
+            ._user_login_throttle_repository
+            .lock(user_id, locked_until, now)
+            .await?;
+        Ok(locked.then_some(locked_until))
+    }
+
+    async fn record_successful_attempt(&self, user_id: i32) -> Result<(), ApplicationError> {
+        self._user_login_throttle_repository.reset(user_id).await?;
+        Ok(())
+    }
+
+    async fn unlock(&self, user_id: i32) -> Result<bool, ApplicationError> {
+        let unlocked = self
+            ._user_login_throttle_repository
+            .unlock(user_id, Utc::now())
+            .await?;
+        Ok(unlocked)
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use rex_game_shared::ApplicationError;
+use std::future::Future;
+
+pub trait IdentityLoginThrottleUseCaseTrait {
+    /// Fails with `TooManyAttempts` while the account is locked or waiting out a delay
+    fn ensure_can_attempt(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<(), ApplicationError>>;
+    /// Counts a wrong password. Returns the end of the lock when this failure locked the
+    /// account.
+    fn record_failed_attempt(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<Option<DateTime<Utc>>, ApplicationError>>;
+    fn record_successful_attempt(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<(), ApplicationError>>;
+    /// Lifts a lock before it runs out. Returns false when the account was not locked.
+    fn unlock(&self, user_id: i32) -> impl Future<Output = Result<bool, ApplicationError>>;
+}
@@ -0,0 +1,194 @@
+use super::{
+    identity_mfa_usecase_trait::IdentityMfaUseCaseTrait, mfa_enrolment_dto::MfaEnrolmentDto,
+    mfa_status_dto::MfaStatusDto,
+};
+use crate::domain::{
+    repositories::user_mfa_repository_trait::UserMfaRepositoryTrait,
+    services::{token_helper_trait::TokenHelperTrait, totp_helper_trait::TotpHelperTrait},
+};
+use chrono::Utc;
+use rex_game_shared::ApplicationError;
+
+#[derive(Clone)]
+pub struct IdentityMfaUseCase<UMR, TP, TH>
+where
+    UMR: UserMfaRepositoryTrait,
+    TP: TotpHelperTrait,
+    TH: TokenHelperTrait,
+{
+    _user_mfa_repository: UMR,
+    _totp_helper: TP,
+    _token_helper: TH,
+}
+
+impl<UMR, TP, TH> IdentityMfaUseCase<UMR, TP, TH>
+where
+    UMR: UserMfaRepositoryTrait,
+    TP: TotpHelperTrait,
+    TH: TokenHelperTrait,
+{
+    pub fn new(user_mfa_repository: UMR, totp_helper: TP, token_helper: TH) -> Self {
+        Self {
+            _user_mfa_repository: user_mfa_repository,
+            _totp_helper: totp_helper,
+            _token_helper: token_helper,
+        }
+    }
+
+    /// Generates recovery codes and their hashes, which are all that is stored
+    fn generate_recovery_codes(&self) -> (Vec<String>, Vec<String>) {
+        let recovery_codes = self._totp_helper.generate_recovery_codes();
+        let recovery_code_hashes = recovery_codes
+            .iter()
+            .map(|code| {
+                self._token_helper
+                    .hash_token(&self._totp_helper.normalize_recovery_code(code))
+            })
+            .collect();
+        (recovery_codes, recovery_code_hashes)
+    }
+}
+
+impl<UMR, TP, TH> IdentityMfaUseCaseTrait for IdentityMfaUseCase<UMR, TP, TH>
+where
+    UMR: UserMfaRepositoryTrait,
+    TP: TotpHelperTrait,
+    TH: TokenHelperTrait,
+{
+    async fn get_status(&self, user_id: i32) -> Result<MfaStatusDto, ApplicationError> {
+        let user_mfa = self._user_mfa_repository.get_by_user_id(user_id).await?;
+        let required = self._user_mfa_repository.is_required(user_id).await?;
+        let recovery_codes_remaining = match user_mfa.enabled_on {
+            Some(_) => {
+                self._user_mfa_repository
+                    .count_unused_recovery_codes(user_id)
+                    .await?
+            }
+            None => 0,
+        };
+
+        Ok(MfaStatusDto {
+            enabled: user_mfa.enabled_on.is_some(),
+            required,
+            recovery_codes_remaining,
+        })
+    }
+
+    async fn begin_enrolment(
+        &self,
+        user_id: i32,
+        account_name: &str,
+    ) -> Result<MfaEnrolmentDto, ApplicationError> {
+        let user_mfa = self._user_mfa_repository.get_by_user_id(user_id).await?;
+        if user_mfa.enabled_on.is_some() {
+            return Err(ApplicationError::conflict(
+                "Two-factor authentication is already enabled",
+            ));
+        }
+
+        let secret = self._totp_helper.generate_secret();
+        self._user_mfa_repository
+            .set_pending_secret(user_id, &secret)
+            .await?;
+
+        Ok(MfaEnrolmentDto {
+            otpauth_uri: self._totp_helper.provisioning_uri(&secret, account_name),
+            secret,
+        })
+    }
+
+    async fn confirm_enrolment(
+        &self,
+        user_id: i32,
+        code: &str,
+    ) -> Result<Vec<String>, ApplicationError> {
+        let user_mfa = self._user_mfa_repository.get_by_user_id(user_id).await?;
+        if user_mfa.enabled_on.is_some() {
+            return Err(ApplicationError::conflict(
+                "Two-factor authentication is already enabled",
+            ));
+        }
+        let secret = user_mfa.secret.ok_or_else(|| {
+            ApplicationError::invalid_state("Two-factor enrolment has not been started")
+        })?;
+
+        let step = self
+            ._totp_helper
+            .verify_code(&secret, code, Utc::now().timestamp())
+            .ok_or_else(|| ApplicationError::invalid_input("Invalid verification code"))?;
+
+        let (recovery_codes, recovery_code_hashes) = self.generate_recovery_codes();
+        self._user_mfa_repository
+            .enable(user_id, step, recovery_code_hashes)
+            .await?;
+
+        Ok(recovery_codes)
+    }
+
+    async fn verify_code(&self, user_id: i32, code: &str) -> Result<(), ApplicationError> {
+        let user_mfa = self._user_mfa_repository.get_by_user_id(user_id).await?;
+        let secret = match (user_mfa.secret, user_mfa.enabled_on) {
+            (Some(secret), Some(_)) => secret,
+            _ => {
+                return Err(ApplicationError::invalid_state(
+                    "Two-factor authentication is not enabled",
+                ))
+            }
+        };
+
+        if let Some(step) = self
+            ._totp_helper
+            .verify_code(&secret, code, Utc::now().timestamp())
+        {
+            // A code seen once is dead, even if it is still inside its time window
+            if self
+                ._user_mfa_repository
+                .use_time_step(user_id, step)
+                .await?
+            {
+                return Ok(());
+            }
+            return Err(ApplicationError::InvalidCredentials);
+        }
+
+        let code_hash = self
+            ._token_helper
+            .hash_token(&self._totp_helper.normalize_recovery_code(code));
+        if self
+            ._user_mfa_repository
+            .use_recovery_code(user_id, &code_hash)
+            .await?
+        {
+            return Ok(());
+        }
+
+        Err(ApplicationError::InvalidCredentials)
+    }
+
+    async fn regenerate_recovery_codes(
+        &self,
+        user_id: i32,
+        code: &str,
+    ) -> Result<Vec<String>, ApplicationError> {
+        self.verify_code(user_id, code).await?;
+
+        let (recovery_codes, recovery_code_hashes) = self.generate_recovery_codes();
+        self._user_mfa_repository
+            .replace_recovery_codes(user_id, recovery_code_hashes)
+            .await?;
+
+        Ok(recovery_codes)
+    }
+
+    async fn disable(&self, user_id: i32, code: &str) -> Result<bool, ApplicationError> {
+        if self._user_mfa_repository.is_required(user_id).await? {
+            return Err(ApplicationError::forbidden(
+                "Two-factor authentication is required for your role",
+            ));
+        }
+        self.verify_code(user_id, code).await?;
+        self._user_mfa_repository.disable(user_id).await?;
+
+        Ok(true)
+    }
+}
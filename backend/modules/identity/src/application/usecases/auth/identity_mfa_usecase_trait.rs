@@ -0,0 +1,39 @@
+use rex_game_shared::ApplicationError;
+use std::future::Future;
+
+use super::{mfa_enrolment_dto::MfaEnrolmentDto, mfa_status_dto::MfaStatusDto};
+
+pub trait IdentityMfaUseCaseTrait {
+    fn get_status(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<MfaStatusDto, ApplicationError>>;
+    /// Generates a new secret. Two-factor authentication stays off until a code is confirmed.
+    fn begin_enrolment(
+        &self,
+        user_id: i32,
+        account_name: &str,
+    ) -> impl Future<Output = Result<MfaEnrolmentDto, ApplicationError>>;
+    /// Turns two-factor authentication on and returns the recovery codes
+    fn confirm_enrolment(
+        &self,
+        user_id: i32,
+        code: &str,
+    ) -> impl Future<Output = Result<Vec<String>, ApplicationError>>;
+    /// Accepts a TOTP code or an unused recovery code. Each is only accepted once.
+    fn verify_code(
+        &self,
+        user_id: i32,
+        code: &str,
+    ) -> impl Future<Output = Result<(), ApplicationError>>;
+    fn regenerate_recovery_codes(
+        &self,
+        user_id: i32,
+        code: &str,
+    ) -> impl Future<Output = Result<Vec<String>, ApplicationError>>;
+    fn disable(
+        &self,
+        user_id: i32,
+        code: &str,
+    ) -> impl Future<Output = Result<bool, ApplicationError>>;
+}
@@ -0,0 +1,72 @@
+use super::identity_signing_key_usecase_trait::IdentitySigningKeyUseCaseTrait;
+use crate::domain::{
+    repositories::signing_key_repository_trait::SigningKeyRepositoryTrait,
+    rules::signing_key_rotation_policy::SigningKeyRotationPolicy,
+    services::{json_web_key::JsonWebKey, signing_keyring_trait::SigningKeyringTrait},
+};
+use chrono::Utc;
+use rex_game_shared::ApplicationError;
+
+#[derive(Clone)]
+pub struct IdentitySigningKeyUseCase<SKR, KR>
+where
+    SKR: SigningKeyRepositoryTrait,
+    KR: SigningKeyringTrait,
+{
+    _signing_key_repository: SKR,
+    _keyring: KR,
+    _policy: SigningKeyRotationPolicy,
+    _algorithm: String,
+}
+
+impl<SKR, KR> IdentitySigningKeyUseCase<SKR, KR>
+where
+    SKR: SigningKeyRepositoryTrait,
+    KR: SigningKeyringTrait,
+{
+    pub fn new(
+        signing_key_repository: SKR,
+        keyring: KR,
+        policy: SigningKeyRotationPolicy,
+        algorithm: &str,
+    ) -> Self {
+        Self {
+            _signing_key_repository: signing_key_repository,
+            _keyring: keyring,
+            _policy: policy,
+            _algorithm: algorithm.to_string(),
+        }
+    }
+}
+
+impl<SKR, KR> IdentitySigningKeyUseCaseTrait for IdentitySigningKeyUseCase<SKR, KR>
+where
+    SKR: SigningKeyRepositoryTrait,
+    KR: SigningKeyringTrait,
+{
+    async fn refresh_keys(&self) -> Result<bool, ApplicationError> {
+        let now = Utc::now();
+        let mut signing_keys = self._signing_key_repository.get_valid(now).await?;
+        let rotated = self._policy.is_rotation_due(&signing_keys, now);
+        if rotated {
+            let mut signing_key = self._keyring.generate_key(&self._algorithm)?;
+            signing_key.activates_on = self._policy.activates_on(&signing_keys, now);
+            let activates_on = signing_key.activates_on;
+            self._signing_key_repository.create(signing_key).await?;
+            self._signing_key_repository
+                .expire_replaced(
+                    activates_on,
+                    self._policy.replaced_key_expires_on(activates_on),
+                )
+                .await?;
+            signing_keys = self._signing_key_repository.get_valid(now).await?;
+        }
+
+        self._keyring.load(signing_keys)?;
+        Ok(rotated)
+    }
+
+    fn get_public_keys(&self) -> Vec<JsonWebKey> {
+        self._keyring.public_keys()
+    }
+}
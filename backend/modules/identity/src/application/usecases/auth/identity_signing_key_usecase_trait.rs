@@ -0,0 +1,11 @@
+use crate::domain::services::json_web_key::JsonWebKey;
+use rex_game_shared::ApplicationError;
+use std::future::Future;
+
+pub trait IdentitySigningKeyUseCaseTrait {
+    /// Rotates the signing key when it is due and loads the valid keys into the keyring.
+    /// Returns whether a new key was created.
+    fn refresh_keys(&self) -> impl Future<Output = Result<bool, ApplicationError>>;
+    /// Public keys that verify tokens, including ones published ahead of signing
+    fn get_public_keys(&self) -> Vec<JsonWebKey>;
+}
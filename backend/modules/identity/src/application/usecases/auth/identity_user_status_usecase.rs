@@ -0,0 +1,178 @@
+use super::{
+    identity_user_status_usecase_trait::IdentityUserStatusUseCaseTrait,
+    user_status_dto::UserStatusDto, user_suspension_dto::UserSuspensionDto,
+};
+use crate::application::usecases::user_stamps_cache::UserStampsCache;
+use crate::domain::{
+    models::user_status_model::UserStatusModel,
+    repositories::user_status_repository_trait::UserStatusRepositoryTrait,
+    rules::user_access_rule::{UserAccess, UserAccessRule},
+};
+use chrono::Utc;
+use rex_game_shared::domain::enums::user_statuses::UserStatuses;
+use rex_game_shared::ApplicationError;
+
+#[derive(Clone)]
+pub struct IdentityUserStatusUseCase<USR>
+where
+    USR: UserStatusRepositoryTrait,
+{
+    _user_status_repository: USR,
+    _user_stamps_cache: UserStampsCache,
+}
+
+impl<USR> IdentityUserStatusUseCase<USR>
+where
+    USR: UserStatusRepositoryTrait,
+{
+    pub fn new(user_status_repository: USR, user_stamps_cache: UserStampsCache) -> Self {
+        Self {
+            _user_status_repository: user_status_repository,
+            _user_stamps_cache: user_stamps_cache,
+        }
+    }
+
+    fn to_dto(status: UserStatusModel) -> UserStatusDto {
+        UserStatusDto {
+            user_id: status.user_id,
+            status_id: status.status_id,
+            status_reason: status.status_reason,
+            status_expires_on: status.status_expires_on,
+            status_changed_by_id: status.status_changed_by_id,
+            status_changed_on: status.status_changed_on,
+        }
+    }
+}
+
+impl<USR> IdentityUserStatusUseCaseTrait for IdentityUserStatusUseCase<USR>
+where
+    USR: UserStatusRepositoryTrait,
+{
+    async fn ensure_can_access(&self, user_id: i32) -> Result<(), ApplicationError> {
+        let now = Utc::now();
+        // Checked on every request, so the status is cached with the user's stamps
+        let status = match self._user_stamps_cache.get_status(user_id) {
+            Some(status) => status,
+            None => {
+                let status = self._user_status_repository.get_by_user_id(user_id).await?;
+                self._user_stamps_cache.set_status(user_id, &status);
+                status
+            }
+        };
+
+        match UserAccessRule::check(&status, now) {
+            UserAccess::Allowed => Ok(()),
+            UserAccess::SuspensionExpired => {
+                // Losing the race to another request lifting it is fine too
+                self._user_status_repository
+                    .lift_expired_suspension(user_id, now)
+                    .await?;
+                self._user_stamps_cache.remove(user_id);
+                Ok(())
+            }
+            UserAccess::NotConfirmed => Err(ApplicationError::forbidden(
+                "The account has not been confirmed yet",
+            )),
+            // Deleted accounts look like wrong credentials, as if they did not exist
+            UserAccess::Removed => Err(ApplicationError::InvalidCredentials),
+            UserAccess::Suspended { reason, until } => {
+                let mut message = match until {
+                    Some(until) => format!(
+                        "The account is suspended until {}",
+                        until.format("%d/%m/%Y %H:%M")
+                    ),
+                    None => "The account is suspended".to_string(),
+                };
+                if let Some(reason) = reason {
+                    message = format!("{}: {}", message, reason);
+                }
+                Err(ApplicationError::forbidden(message))
+            }
+            UserAccess::Banned { reason } => Err(ApplicationError::forbidden(match reason {
+                Some(reason) => format!("The account is banned: {}", reason),
+                None => "The account is banned".to_string(),
+            })),
+        }
+    }
+
+    async fn get_status(&self, user_id: i32) -> Result<UserStatusDto, ApplicationError> {
+        let status = self._user_status_repository.get_by_user_id(user_id).await?;
+        Ok(Self::to_dto(status))
+    }
+
+    async fn suspend(
+        &self,
+        user_id: i32,
+        suspension: UserSuspensionDto,
+    ) -> Result<UserStatusDto, ApplicationError> {
+        if user_id == suspension.changed_by_id {
+            return Err(ApplicationError::invalid_input(
+                "You cannot suspend your own account",
+            ));
+        }
+
+        let now = Utc::now();
+        let status_id = if suspension.banned {
+            if suspension.expires_on.is_some() {
+                return Err(ApplicationError::invalid_input("A ban cannot expire"));
+            }
+            UserStatuses::Banned
+        } else {
+            UserStatuses::Suspended
+        };
+        if suspension
+            .expires_on
+            .is_some_and(|expires_on| expires_on <= now)
+        {
+            return Err(ApplicationError::invalid_input(
+                "The suspension must end in the future",
+            ));
+        }
+
+        let status = UserStatusModel {
+            user_id,
+            status_id: status_id as i32,
+            status_reason: Some(suspension.reason),
+            status_expires_on: suspension.expires_on,
+            status_changed_by_id: Some(suspension.changed_by_id),
+            status_changed_on: Some(now),
+        };
+        // A suspension can be changed or turned into a ban; deleted users stay deleted
+        let changed = self
+            ._user_status_repository
+            .change_status(
+                status.clone(),
+                vec![
+                    UserStatuses::Pending as i32,
+                    UserStatuses::Actived as i32,
+                    UserStatuses::Suspended as i32,
+                    UserStatuses::Banned as i32,
+                ],
+            )
+            .await?;
+        self._user_stamps_cache.remove(user_id);
+        if !changed {
+            return Err(ApplicationError::not_found("User", user_id.to_string()));
+        }
+
+        Ok(Self::to_dto(status))
+    }
+
+    async fn reinstate(&self, user_id: i32, changed_by_id: i32) -> Result<bool, ApplicationError> {
+        let reinstated = self
+            ._user_status_repository
+            .change_status(
+                UserStatusModel {
+                    user_id,
+                    status_id: UserStatuses::Actived as i32,
+                    status_changed_by_id: Some(changed_by_id),
+                    status_changed_on: Some(Utc::now()),
+                    ..Default::default()
+                },
+                vec![UserStatuses::Suspended as i32, UserStatuses::Banned as i32],
+            )
+            .await?;
+        self._user_stamps_cache.remove(user_id);
+        Ok(reinstated)
+    }
+}
@@ -0,0 +1,26 @@
+use super::{user_status_dto::UserStatusDto, user_suspension_dto::UserSuspensionDto};
+use rex_game_shared::ApplicationError;
+use std::future::Future;
+
+pub trait IdentityUserStatusUseCaseTrait {
+    /// Fails unless the user's status lets them sign in and use the API. A suspension that
+    /// has run out is lifted on the way.
+    fn ensure_can_access(&self, user_id: i32)
+        -> impl Future<Output = Result<(), ApplicationError>>;
+    fn get_status(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<UserStatusDto, ApplicationError>>;
+    /// Suspends or bans the user. The caller is expected to sign them out everywhere.
+    fn suspend(
+        &self,
+        user_id: i32,
+        suspension: UserSuspensionDto,
+    ) -> impl Future<Output = Result<UserStatusDto, ApplicationError>>;
+    /// Lifts a suspension or ban. Returns false when the user had neither.
+    fn reinstate(
+        &self,
+        user_id: i32,
+        changed_by_id: i32,
+    ) -> impl Future<Output = Result<bool, ApplicationError>>;
+}
@@ -1,3 +1,4 @@
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
 use rex_game_shared::ApplicationError;
 
 use super::{
@@ -54,6 +55,27 @@ where
         }
     }
 
+    async fn create_user_token_with_transaction(
+        &self,
+        user_token_req: UserTokenCreationDto,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<i32, ApplicationError> {
+        let user_token_model = UserTokenModel {
+            token: user_token_req.token,
+            user_id: user_token_req.user_id,
+            created_by_id: user_token_req.created_by_id,
+            updated_by_id: user_token_req.updated_by_id,
+            expiration: user_token_req.expiration,
+            purpose: user_token_req.purpose,
+            is_actived: true,
+            ..Default::default()
+        };
+        self._user_token_repository
+            .create_without_commit(user_token_model, transaction)
+            .await
+            .map_err(ApplicationError::Infrastructure)
+    }
+
     async fn get_user_token_by_token(&self, token: &str) -> Result<UserTokenDto, ApplicationError> {
         let existing = self._user_token_repository.get_by_token(token).await;
         match existing {
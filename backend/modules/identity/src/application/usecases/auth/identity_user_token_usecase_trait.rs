@@ -1,3 +1,4 @@
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
 use rex_game_shared::ApplicationError;
 
 use super::{
@@ -11,6 +12,11 @@ pub trait IdentityUserTokenUseCaseTrait {
         &self,
         user_token_req: UserTokenCreationDto,
     ) -> impl Future<Output = Result<i32, ApplicationError>>;
+    fn create_user_token_with_transaction(
+        &self,
+        user_token_req: UserTokenCreationDto,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> impl Future<Output = Result<i32, ApplicationError>>;
     fn get_user_token_by_token(
         &self,
         token: &str,
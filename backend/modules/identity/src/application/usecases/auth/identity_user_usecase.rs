@@ -1,16 +1,22 @@
 use super::{
-    identity_user_trait::IdentityUserTrait, identity_user_usecase_trait::IdentityUserUseCaseTrait,
+    identity_user_trait::IdentityUserTrait,
+    identity_user_usecase_trait::IdentityUserUseCaseTrait,
+    permission_explanation_dto::{PermissionExplanationDto, PermissionSourceDto},
 };
 use crate::application::usecases::loggedin_user_dto::{
     LoggedInUserDto, LoggedInUserPermissonDto, LoggedInUserRoleDto,
 };
 use crate::application::usecases::role_usecase_trait::RoleUseCaseTrait;
+use crate::application::usecases::roles::ROLE_ROOT_ADMIN;
 use crate::application::usecases::user_creation_dto::UserCreationDto;
 use crate::application::usecases::user_usecase_trait::UserUseCaseTrait;
+use crate::domain::rules::role_hierarchy_rule::RoleHierarchyRule;
 use crate::domain::services::password_hasher_trait::PasswordHasherTrait;
 use crate::domain::services::token_helper_trait::TokenHelperTrait;
+use rex_game_shared::domain::enums::user_kinds::UserKinds;
 use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
 use rex_game_shared::ApplicationError;
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::pin::Pin;
 
@@ -43,6 +49,69 @@ where
             _token_helper: token_helper,
         }
     }
+
+    /// Roles and permissions of the user, including the roles inherited from assigned ones
+    /// and the permissions granted through any of them
+    async fn load_logged_in_user(
+        user_usecase: &US,
+        role_usecase: &RS,
+        id: i32,
+        email: String,
+        name: String,
+        display_name: Option<String>,
+    ) -> Result<LoggedInUserDto, ApplicationError> {
+        let assigned_role_ids = user_usecase
+            .get_user_roles_by_user_id(id)
+            .await
+            .map_err(|_| ApplicationError::invalid_input("Failed to get the assigned roles"))?
+            .into_iter()
+            .map(|r| r.role_id)
+            .collect::<Vec<_>>();
+        let roles = role_usecase
+            .get_inherited_roles(assigned_role_ids)
+            .await
+            .map_err(|_| ApplicationError::invalid_input("Failed to get the inherited roles"))?
+            .into_iter()
+            .map(|r| LoggedInUserRoleDto {
+                role_name: r.name,
+                role_id: r.id,
+            })
+            .collect::<Vec<_>>();
+
+        let mut permissions = user_usecase
+            .get_user_permissions_by_user_id(id)
+            .await
+            .map_err(|_| ApplicationError::invalid_input("Failed to get the assigned permissions"))?
+            .into_iter()
+            .map(|p| LoggedInUserPermissonDto {
+                permisson_code: p.permission_code,
+                permisson_id: p.permission_id,
+                permisson_name: p.permission_name,
+            })
+            .collect::<Vec<_>>();
+
+        if !roles.is_empty() {
+            if let Ok(role_perms) = role_usecase
+                .get_roles_permissions_by_role_ids(roles.iter().map(|r| r.role_id).collect())
+                .await
+            {
+                permissions.extend(role_perms.into_iter().map(|p| LoggedInUserPermissonDto {
+                    permisson_code: p.permission_code,
+                    permisson_id: p.permission_id,
+                    permisson_name: p.permission_name,
+                }));
+            }
+        }
+
+        Ok(LoggedInUserDto {
+            email,
+            name,
+            display_name,
+            id,
+            roles,
+            permissions,
+        })
+    }
 }
 
 impl<PH, US, RS, TH> IdentityUserUseCaseTrait for IdentityUserUseCase<PH, US, RS, TH>
@@ -73,6 +142,7 @@ where
                     password: String::from(user.password_hash()),
                     security_stamp: String::from(user.security_stamp()),
                     status_id: user.status_id(),
+                    kind_id: UserKinds::Person as i32,
                 },
                 transaction,
             )
@@ -105,6 +175,7 @@ where
                 password: String::from(user.password_hash()),
                 security_stamp: String::from(user.security_stamp()),
                 status_id: user.status_id(),
+                kind_id: UserKinds::Person as i32,
             })
             .await
             .map_err(|err| err)?;
@@ -138,53 +209,94 @@ where
                 Ok(user) => user,
             };
 
-            let roles = user_usecase
-                .get_user_roles_by_user_id(user.id)
-                .await
-                .map_err(|_| ApplicationError::invalid_input("Failed to get the assigned roles"))?
-                .into_iter()
-                .map(|r| LoggedInUserRoleDto {
-                    role_name: r.role_name,
-                    role_id: r.role_id,
-                })
-                .collect::<Vec<_>>();
-
-            let mut permissions = user_usecase
-                .get_user_permissions_by_user_id(user.id)
-                .await
-                .map_err(|_| {
-                    ApplicationError::invalid_input("Failed to get the assigned permissions")
-                })?
-                .into_iter()
-                .map(|p| LoggedInUserPermissonDto {
-                    permisson_code: p.permission_code,
-                    permisson_id: p.permission_id,
-                    permisson_name: p.permission_name,
-                })
-                .collect::<Vec<_>>();
-
-            if !roles.is_empty() {
-                if let Ok(role_perms) = role_usecase
-                    .get_roles_permissions_by_role_ids(roles.iter().map(|r| r.role_id).collect())
-                    .await
-                {
-                    permissions.extend(role_perms.into_iter().map(|p| LoggedInUserPermissonDto {
-                        permisson_code: p.permission_code,
-                        permisson_id: p.permission_id,
-                        permisson_name: p.permission_name,
-                    }));
-                }
+            Self::load_logged_in_user(
+                &user_usecase,
+                &role_usecase,
+                user.id,
+                user.email,
+                user.name,
+                user.display_name,
+            )
+            .await
+        })
+    }
+
+    async fn get_logged_in_user_by_id(
+        &self,
+        user_id: i32,
+    ) -> Result<LoggedInUserDto, ApplicationError> {
+        let user = self._user_usecase.get_user_by_id(user_id).await?;
+
+        Self::load_logged_in_user(
+            &self._user_usecase,
+            &self._role_usecase,
+            user.id,
+            user.email,
+            user.name,
+            user.display_name,
+        )
+        .await
+    }
+
+    async fn explain_permission(
+        &self,
+        user_id: i32,
+        permission_code: &str,
+    ) -> Result<PermissionExplanationDto, ApplicationError> {
+        self._user_usecase.get_user_by_id(user_id).await?;
+
+        let mut sources = Vec::new();
+        let user_permissions = self
+            ._user_usecase
+            .get_user_permissions_by_user_id(user_id)
+            .await?;
+        if user_permissions
+            .iter()
+            .any(|permission| permission.permission_code == permission_code)
+        {
+            sources.push(PermissionSourceDto::default());
+        }
+
+        let assigned_roles = self
+            ._user_usecase
+            .get_user_roles_by_user_id(user_id)
+            .await?;
+        let parents = self._role_usecase.get_role_parents().await?;
+        let assigned_role_ids: Vec<i32> = assigned_roles.iter().map(|r| r.role_id).collect();
+        let roles = self
+            ._role_usecase
+            .get_inherited_roles(assigned_role_ids.clone())
+            .await?;
+        let role_names: HashMap<i32, String> = roles.into_iter().map(|r| (r.id, r.name)).collect();
+
+        let granting_role_ids: HashSet<i32> = self
+            ._role_usecase
+            .get_roles_permissions_by_role_ids(role_names.keys().copied().collect())
+            .await?
+            .into_iter()
+            .filter(|permission| permission.permission_code == permission_code)
+            .map(|permission| permission.role_id)
+            .collect();
+        for role_id in assigned_role_ids {
+            for path in RoleHierarchyRule::paths_to(role_id, &granting_role_ids, &parents) {
+                sources.push(PermissionSourceDto {
+                    role_path: path
+                        .iter()
+                        .map(|id| role_names.get(id).cloned().unwrap_or_default())
+                        .collect(),
+                });
             }
+        }
 
-            Ok(LoggedInUserDto {
-                email: user.email,
-                name: user.name,
-                display_name: user.display_name,
-                id: user.id,
-                roles,
-                permissions,
-                ..Default::default()
-            })
+        let is_root_admin = assigned_roles
+            .iter()
+            .any(|role| role.role_name == ROLE_ROOT_ADMIN);
+        Ok(PermissionExplanationDto {
+            user_id,
+            permission_code: permission_code.to_string(),
+            granted: is_root_admin || !sources.is_empty(),
+            is_root_admin,
+            sources,
         })
     }
 }
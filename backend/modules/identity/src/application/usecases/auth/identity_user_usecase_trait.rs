@@ -4,6 +4,7 @@ use std::future::Future;
 use std::pin::Pin;
 
 use super::identity_user_trait::IdentityUserTrait;
+use super::permission_explanation_dto::PermissionExplanationDto;
 use crate::application::usecases::loggedin_user_dto::LoggedInUserDto;
 
 pub trait IdentityUserUseCaseTrait {
@@ -28,4 +29,17 @@ pub trait IdentityUserUseCaseTrait {
         &'a self,
         access_token: &'a str,
     ) -> Pin<Box<dyn Future<Output = Result<LoggedInUserDto, ApplicationError>> + Send + 'a>>;
+
+    /// Same as `get_logged_in_user`, for a user known by id, e.g. the owner of an API key
+    fn get_logged_in_user_by_id(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<LoggedInUserDto, ApplicationError>>;
+
+    /// Whether the user has the permission, and through which assigned and inherited roles
+    fn explain_permission(
+        &self,
+        user_id: i32,
+        permission_code: &str,
+    ) -> impl Future<Output = Result<PermissionExplanationDto, ApplicationError>>;
 }
@@ -0,0 +1,7 @@
+use chrono::{DateTime, Utc};
+
+pub struct MagicLinkDto {
+    /// Goes into the emailed link; it only works together with the device's nonce
+    pub token: String,
+    pub expiration: DateTime<Utc>,
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct MfaEnrolmentDto {
+    /// Base32 secret for authenticator apps that cannot scan the QR code
+    pub secret: String,
+    pub otpauth_uri: String,
+}
@@ -0,0 +1,7 @@
+use super::login_claims::LoginClaims;
+
+pub struct MfaLoginResult {
+    pub claims: LoginClaims,
+    /// Issued when the login also completed a mandatory enrolment
+    pub recovery_codes: Option<Vec<String>>,
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct MfaStatusDto {
+    pub enabled: bool,
+    /// Set when one of the user's roles makes two-factor authentication mandatory
+    pub required: bool,
+    pub recovery_codes_remaining: u64,
+}
@@ -1,23 +1,66 @@
+pub mod api_key_creation_dto;
+pub mod api_key_dto;
+pub mod api_key_principal_dto;
+pub mod created_api_key_dto;
+pub mod external_authorization_dto;
+pub mod external_login_dto;
+pub mod external_login_provider_dto;
+pub mod identity_api_key_usecase;
+pub mod identity_api_key_usecase_trait;
 pub mod identity_authenticate_usecase;
 pub mod identity_authenticate_usecase_trait;
 pub mod identity_authorize_usecase;
 pub mod identity_authorize_usecase_trait;
+pub mod identity_external_login_usecase;
+pub mod identity_external_login_usecase_trait;
+pub mod identity_login_throttle_usecase;
+pub mod identity_login_throttle_usecase_trait;
+pub mod identity_mfa_usecase;
+pub mod identity_mfa_usecase_trait;
+pub mod identity_signing_key_usecase;
+pub mod identity_signing_key_usecase_trait;
+pub mod identity_user_status_usecase;
+pub mod identity_user_status_usecase_trait;
 pub mod identity_user_token_usecase;
 pub mod identity_user_token_usecase_trait;
 pub mod identity_user_trait;
 pub mod identity_user_usecase;
 pub mod identity_user_usecase_trait;
 pub mod login_claims;
+pub mod magic_link_dto;
+pub mod mfa_enrolment_dto;
+pub mod mfa_login_result;
+pub mod mfa_status_dto;
+pub mod password_login_result;
+pub mod permission_explanation_dto;
+pub mod service_account_creation_dto;
 pub mod user_creation_dto;
+pub mod user_session_dto;
+pub mod user_status_dto;
+pub mod user_suspension_dto;
 pub mod user_token_creation_dto;
 pub mod user_token_dto;
 pub mod user_token_updation_dto;
 
 // Re-exports
-pub use identity_authenticate_usecase::IdentityAuthenticateUseCase;
+pub use identity_api_key_usecase::IdentityApiKeyUseCase;
+pub use identity_api_key_usecase_trait::IdentityApiKeyUseCaseTrait;
+pub use identity_authenticate_usecase::{
+    IdentityAuthenticateDependencies, IdentityAuthenticateUseCase,
+};
 pub use identity_authenticate_usecase_trait::IdentityAuthenticateUseCaseTrait;
 pub use identity_authorize_usecase::IdentityAuthorizeUseCase;
 pub use identity_authorize_usecase_trait::IdentityAuthorizeUseCaseTrait;
+pub use identity_external_login_usecase::IdentityExternalLoginUseCase;
+pub use identity_external_login_usecase_trait::IdentityExternalLoginUseCaseTrait;
+pub use identity_login_throttle_usecase::IdentityLoginThrottleUseCase;
+pub use identity_login_throttle_usecase_trait::IdentityLoginThrottleUseCaseTrait;
+pub use identity_mfa_usecase::IdentityMfaUseCase;
+pub use identity_mfa_usecase_trait::IdentityMfaUseCaseTrait;
+pub use identity_signing_key_usecase::IdentitySigningKeyUseCase;
+pub use identity_signing_key_usecase_trait::IdentitySigningKeyUseCaseTrait;
+pub use identity_user_status_usecase::IdentityUserStatusUseCase;
+pub use identity_user_status_usecase_trait::IdentityUserStatusUseCaseTrait;
 pub use identity_user_token_usecase::IdentityUserTokenUseCase;
 pub use identity_user_token_usecase_trait::IdentityUserTokenUseCaseTrait;
 pub use identity_user_usecase::IdentityUserUseCase;
@@ -0,0 +1,26 @@
+use super::login_claims::LoginClaims;
+use chrono::{DateTime, Utc};
+
+pub enum PasswordLoginResult {
+    Authenticated(LoginClaims),
+    /// The password was right but a second factor is still needed
+    MfaRequired(MfaChallenge),
+    /// The password was wrong and this failure locked the account, which the caller
+    /// should tell its owner about
+    Locked(AccountLockout),
+}
+
+pub struct MfaChallenge {
+    /// Short-lived token that only the two-factor endpoints accept
+    pub mfa_token: String,
+    pub expiration: u64,
+    /// The user's role requires two-factor authentication but they have not enrolled yet
+    pub enrolment_required: bool,
+}
+
+pub struct AccountLockout {
+    pub user_id: i32,
+    pub email: String,
+    pub name: String,
+    pub locked_until: DateTime<Utc>,
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// Why a user has, or does not have, a permission
+#[derive(Serialize, Deserialize, Default)]
+pub struct PermissionExplanationDto {
+    pub user_id: i32,
+    pub permission_code: String,
+    pub granted: bool,
+    /// ROOT_ADMIN passes every permission check, whether or not it holds the permission
+    pub is_root_admin: bool,
+    pub sources: Vec<PermissionSourceDto>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct PermissionSourceDto {
+    /// Empty when the permission is assigned to the user. Otherwise the assigned role,
+    /// followed by the roles it inherits from up to the one holding the permission.
+    pub role_path: Vec<String>,
+}
@@ -0,0 +1,5 @@
+pub struct ServiceAccountCreationDto {
+    /// Also makes up the account's email, which can never receive mail
+    pub name: String,
+    pub display_name: Option<String>,
+}
@@ -0,0 +1,9 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct UserSessionDto {
+    pub id: String,
+    pub last_refreshed_on: DateTime<Utc>,
+    pub expiration: DateTime<Utc>,
+}
@@ -0,0 +1,12 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct UserStatusDto {
+    pub user_id: i32,
+    pub status_id: i32,
+    pub status_reason: Option<String>,
+    pub status_expires_on: Option<DateTime<Utc>>,
+    pub status_changed_by_id: Option<i32>,
+    pub status_changed_on: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,10 @@
+use chrono::{DateTime, Utc};
+
+pub struct UserSuspensionDto {
+    pub reason: String,
+    /// When the suspension lifts by itself; `None` keeps it until reinstated
+    pub expires_on: Option<DateTime<Utc>>,
+    /// Bans the user for good instead of suspending them
+    pub banned: bool,
+    pub changed_by_id: i32,
+}
@@ -30,6 +30,7 @@ pub mod user_permission_creation_dto;
 pub mod user_permission_dto;
 pub mod user_role_creation_dto;
 pub mod user_role_dto;
+pub mod user_stamps_cache;
 pub mod user_updation_dto;
 pub mod user_usecase;
 pub mod user_usecase_trait;
@@ -40,5 +41,6 @@ pub use permission_usecase::PermissionUseCase;
 pub use permission_usecase_trait::PermissionUseCaseTrait;
 pub use role_usecase::RoleUseCase;
 pub use role_usecase_trait::RoleUseCaseTrait;
+pub use user_stamps_cache::UserStampsCache;
 pub use user_usecase::UserUseCase;
 pub use user_usecase_trait::UserUseCaseTrait;
@@ -2,6 +2,7 @@
 pub struct RoleCreationDto {
     pub name: String,
     pub description: Option<String>,
+    pub parent_id: Option<i32>,
     pub created_by_id: Option<i32>,
     pub updated_by_id: Option<i32>,
 }
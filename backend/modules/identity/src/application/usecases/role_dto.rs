@@ -10,4 +10,8 @@ pub struct RoleDto {
     pub created_on: DateTime<Utc>,
     pub updated_on: DateTime<Utc>,
     pub updated_by_id: Option<i32>,
+    /// Members must use two-factor authentication to sign in
+    pub requires_mfa: bool,
+    /// The role this one inherits permissions from
+    pub parent_id: Option<i32>,
 }
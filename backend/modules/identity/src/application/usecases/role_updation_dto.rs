@@ -3,5 +3,6 @@ pub struct RoleUpdationDto {
     pub name: Option<String>,
     pub description: Option<String>,
     pub is_actived: Option<bool>,
+    pub requires_mfa: Option<bool>,
     pub updated_by_id: i32,
 }
@@ -2,7 +2,7 @@ use super::{
     role_creation_dto::RoleCreationDto, role_deletion_dto::RoleDeletionDto, role_dto::RoleDto,
     role_permission_creation_dto::RolePermissionCreationDto,
     role_permission_dto::RolePermissionDto, role_updation_dto::RoleUpdationDto,
-    role_usecase_trait::RoleUseCaseTrait, user_role_dto::UserRoleDto,
+    role_usecase_trait::RoleUseCaseTrait, roles::ROLE_ROOT_ADMIN, user_role_dto::UserRoleDto,
 };
 use crate::domain::{
     models::{role_model::RoleModel, role_permission_model::RolePermissionModel},
@@ -11,8 +11,13 @@ use crate::domain::{
         role_repository_trait::RoleRepositoryTrait,
         user_role_repository_trait::UserRoleRepositoryTrait,
     },
+    rules::role_hierarchy_rule::{RoleHierarchyRule, RoleParents},
 };
 use chrono::Utc;
+use rex_game_shared::domain::transaction_manager_trait::{
+    TransactionManagerTrait, TransactionWrapperTrait,
+};
+use rex_game_shared::infrastructure::database::TransactionManager;
 use rex_game_shared::{domain::models::page_list_model::PageListModel, ApplicationError};
 use std::{future::Future, pin::Pin};
 
@@ -26,6 +31,7 @@ where
     _role_repository: R,
     _role_permission_repository: RP,
     _user_role_repository: UR,
+    _transaction_manager: TransactionManager,
 }
 
 impl<R, RP, UR> RoleUseCase<R, RP, UR>
@@ -38,19 +44,64 @@ where
         role_repository: R,
         role_permission_repository: RP,
         user_role_repository: UR,
+        transaction_manager: TransactionManager,
     ) -> Self {
         Self {
             _role_repository: role_repository,
             _role_permission_repository: role_permission_repository,
             _user_role_repository: user_role_repository,
+            _transaction_manager: transaction_manager,
+        }
+    }
+
+    /// A role may inherit from any active role except ROOT_ADMIN, which is only ever
+    /// assigned directly
+    async fn ensure_can_inherit_from(&self, parent_id: i32) -> Result<(), ApplicationError> {
+        let parent = self
+            ._role_repository
+            .get_by_id(parent_id)
+            .await
+            .map_err(|_| ApplicationError::invalid_input("The parent role does not exist"))?;
+        if parent.name == ROLE_ROOT_ADMIN {
+            return Err(ApplicationError::invalid_input(
+                "No role can inherit from the ROOT_ADMIN role",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks for a cycle against the locked hierarchy, so two changes made at once cannot
+    /// each pass the check and close a loop together
+    async fn set_role_parent_with_transaction(
+        &self,
+        id: i32,
+        parent_id: Option<i32>,
+        updated_by_id: i32,
+        transaction: &dyn TransactionWrapperTrait,
+    ) -> Result<(), ApplicationError> {
+        if let Some(parent_id) = parent_id {
+            let parents = self
+                ._role_repository
+                .get_parents_with_transaction(Box::new(transaction))
+                .await?;
+            if RoleHierarchyRule::creates_cycle(id, parent_id, &parents) {
+                return Err(ApplicationError::conflict(
+                    "A role cannot inherit from itself or from a role that inherits from it",
+                ));
+            }
         }
+
+        self._role_repository
+            .update_parent_without_commit(id, parent_id, updated_by_id, Box::new(transaction))
+            .await?;
+        Ok(())
     }
 }
 
 impl<R, RP, UR> RoleUseCaseTrait for RoleUseCase<R, RP, UR>
 where
     RP: RolePermissionRepositoryTrait + Send + Sync + Clone + 'static,
-    R: RoleRepositoryTrait,
+    R: RoleRepositoryTrait + Send + Sync + Clone + 'static,
     RP: RolePermissionRepositoryTrait,
     UR: UserRoleRepositoryTrait + Send + Sync + Clone + 'static,
 {
@@ -78,6 +129,8 @@ where
                         updated_on: f.updated_on.with_timezone(&Utc),
                         created_by_id: f.created_by_id,
                         updated_by_id: f.updated_by_id,
+                        requires_mfa: f.requires_mfa,
+                        parent_id: f.parent_id,
                     })
                     .collect();
                 Ok(PageListModel {
@@ -103,6 +156,8 @@ where
                         updated_on: f.updated_on.with_timezone(&Utc),
                         created_by_id: f.created_by_id,
                         updated_by_id: f.updated_by_id,
+                        requires_mfa: f.requires_mfa,
+                        parent_id: f.parent_id,
                     })
                     .collect();
                 Ok(items)
@@ -122,6 +177,8 @@ where
                 updated_on: f.updated_on.with_timezone(&Utc),
                 created_by_id: f.created_by_id,
                 updated_by_id: f.updated_by_id,
+                requires_mfa: f.requires_mfa,
+                parent_id: f.parent_id,
             }),
             None => None,
         }
@@ -138,15 +195,22 @@ where
                 created_on: f.created_on.with_timezone(&Utc),
                 updated_on: f.updated_on.with_timezone(&Utc),
                 updated_by_id: f.updated_by_id,
+                requires_mfa: f.requires_mfa,
+                parent_id: f.parent_id,
             }),
             Err(err) => Err(ApplicationError::Infrastructure(err)),
         }
     }
 
     async fn create_role(&self, role_req: RoleCreationDto) -> Result<i32, ApplicationError> {
+        if let Some(parent_id) = role_req.parent_id {
+            self.ensure_can_inherit_from(parent_id).await?;
+        }
+
         let active_role = RoleModel {
             name: role_req.name,
             description: role_req.description,
+            parent_id: role_req.parent_id,
             created_by_id: role_req.created_by_id,
             updated_by_id: role_req.updated_by_id,
             ..Default::default()
@@ -176,6 +240,9 @@ where
                     Some(is_actived) => exist.is_actived = is_actived,
                     None => {}
                 };
+                if let Some(requires_mfa) = role_req.requires_mfa {
+                    exist.requires_mfa = requires_mfa;
+                }
                 let updated = self._role_repository.update(exist).await;
                 match updated {
                     Ok(i) => Some(i),
@@ -186,6 +253,88 @@ where
         }
     }
 
+    async fn set_role_parent(
+        &self,
+        id: i32,
+        parent_id: Option<i32>,
+        updated_by_id: i32,
+    ) -> Result<(), ApplicationError> {
+        self._role_repository
+            .get_by_id(id)
+            .await
+            .map_err(|_| ApplicationError::not_found("Role", id.to_string()))?;
+        if let Some(parent_id) = parent_id {
+            self.ensure_can_inherit_from(parent_id).await?;
+        }
+
+        let transaction = self._transaction_manager.begin().await?;
+        let updated = self
+            .set_role_parent_with_transaction(id, parent_id, updated_by_id, &transaction)
+            .await;
+        match updated {
+            Ok(()) => {
+                self._transaction_manager.commit(transaction).await?;
+                Ok(())
+            }
+            Err(err) => {
+                self._transaction_manager.rollback(transaction).await?;
+                Err(err)
+            }
+        }
+    }
+
+    fn get_role_parents(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<RoleParents, ApplicationError>> + Send>> {
+        let role_repository = self._role_repository.clone();
+        Box::pin(async move { Ok(role_repository.get_parents().await?) })
+    }
+
+    fn get_inherited_roles(
+        &self,
+        role_ids: Vec<i32>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<RoleDto>, ApplicationError>> + Send>> {
+        let role_repository = self._role_repository.clone();
+        Box::pin(async move {
+            let parents = role_repository.get_parents().await?;
+            let inherited_ids = RoleHierarchyRule::inherited_role_ids(&role_ids, &parents);
+            let roles = role_repository.get_by_ids(inherited_ids).await?;
+            Ok(roles
+                .into_iter()
+                .map(|f| RoleDto {
+                    id: f.id,
+                    name: f.name,
+                    description: f.description,
+                    created_on: f.created_on.with_timezone(&Utc),
+                    updated_on: f.updated_on.with_timezone(&Utc),
+                    created_by_id: f.created_by_id,
+                    updated_by_id: f.updated_by_id,
+                    requires_mfa: f.requires_mfa,
+                    parent_id: f.parent_id,
+                })
+                .collect())
+        })
+    }
+
+    async fn get_inheriting_role_ids(
+        &self,
+        role_ids: Vec<i32>,
+    ) -> Result<Vec<i32>, ApplicationError> {
+        let parents = self._role_repository.get_parents().await?;
+        Ok(RoleHierarchyRule::inheriting_role_ids(&role_ids, &parents))
+    }
+
+    async fn get_inheriting_role_ids_by_permission(
+        &self,
+        permission_id: i32,
+    ) -> Result<Vec<i32>, ApplicationError> {
+        let role_ids = self
+            ._role_permission_repository
+            .get_role_ids_by_permission_id(permission_id)
+            .await?;
+        self.get_inheriting_role_ids(role_ids).await
+    }
+
     async fn delete_role_by_id(&self, id: i32, delete_req: RoleDeletionDto) -> Option<bool> {
         let updation = RoleUpdationDto {
             updated_by_id: delete_req.updated_by_id,
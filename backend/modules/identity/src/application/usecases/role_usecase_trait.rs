@@ -4,6 +4,7 @@ use super::{
     role_permission_dto::RolePermissionDto, role_updation_dto::RoleUpdationDto,
     user_role_dto::UserRoleDto,
 };
+use crate::domain::rules::role_hierarchy_rule::RoleParents;
 use rex_game_shared::{domain::models::page_list_model::PageListModel, ApplicationError};
 use std::{future::Future, pin::Pin};
 
@@ -30,6 +31,33 @@ pub trait RoleUseCaseTrait {
         &self,
         role_req: RoleCreationDto,
     ) -> impl Future<Output = Result<i32, ApplicationError>>;
+    /// Makes the role inherit from `parent_id`, or from nothing. Refused when the role
+    /// would end up inheriting from itself.
+    fn set_role_parent(
+        &self,
+        id: i32,
+        parent_id: Option<i32>,
+        updated_by_id: i32,
+    ) -> impl Future<Output = Result<(), ApplicationError>>;
+    /// Every active role, with the role it inherits from
+    fn get_role_parents(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<RoleParents, ApplicationError>> + Send>>;
+    /// The roles and every role they inherit from
+    fn get_inherited_roles(
+        &self,
+        role_ids: Vec<i32>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<RoleDto>, ApplicationError>> + Send>>;
+    /// The roles and every role inheriting from them
+    fn get_inheriting_role_ids(
+        &self,
+        role_ids: Vec<i32>,
+    ) -> impl Future<Output = Result<Vec<i32>, ApplicationError>>;
+    /// The roles granting the permission, including the ones inheriting it
+    fn get_inheriting_role_ids_by_permission(
+        &self,
+        permission_id: i32,
+    ) -> impl Future<Output = Result<Vec<i32>, ApplicationError>>;
     fn delete_role_by_id(
         &self,
         id: i32,
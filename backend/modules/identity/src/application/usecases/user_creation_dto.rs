@@ -5,4 +5,5 @@ pub struct UserCreationDto {
     pub password: String,
     pub security_stamp: String,
     pub status_id: i32,
+    pub kind_id: i32,
 }
@@ -12,4 +12,5 @@ pub struct UserDetailsDto {
     pub updated_on: DateTime<Utc>,
     pub updated_by_id: Option<i32>,
     pub status_id: i32,
+    pub kind_id: i32,
 }
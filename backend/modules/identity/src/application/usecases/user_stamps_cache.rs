@@ -0,0 +1,164 @@
+use crate::domain::models::{
+    user_stamps_model::UserStampsModel, user_status_model::UserStatusModel,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+/// How long looked-up stamps and statuses are trusted before the database is asked again.
+/// Changes on this instance evict immediately; this only bounds staleness across instances.
+const USER_STAMPS_TTL: Duration = Duration::from_secs(30);
+
+type Entries<T> = Arc<RwLock<HashMap<i32, (T, Instant)>>>;
+
+/// Short-lived per-user cache of what every request is checked against: the token stamps
+/// and the account status. Shared by every clone of the use cases holding it.
+#[derive(Clone, Default)]
+pub struct UserStampsCache {
+    _entries: Entries<UserStampsModel>,
+    _statuses: Entries<UserStatusModel>,
+}
+
+impl UserStampsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn get_fresh<T: Clone>(entries: &Entries<T>, user_id: i32) -> Option<T> {
+        let entries = entries.read().ok()?;
+        match entries.get(&user_id) {
+            Some((value, cached_at)) if cached_at.elapsed() < USER_STAMPS_TTL => {
+                Some(value.to_owned())
+            }
+            _ => None,
+        }
+    }
+
+    fn insert<T: Clone>(entries: &Entries<T>, user_id: i32, value: &T) {
+        if let Ok(mut entries) = entries.write() {
+            entries.retain(|_, (_, cached_at)| cached_at.elapsed() < USER_STAMPS_TTL);
+            entries.insert(user_id, (value.to_owned(), Instant::now()));
+        }
+    }
+
+    pub fn get(&self, user_id: i32) -> Option<UserStampsModel> {
+        Self::get_fresh(&self._entries, user_id)
+    }
+
+    pub fn set(&self, user_id: i32, stamps: &UserStampsModel) {
+        Self::insert(&self._entries, user_id, stamps);
+    }
+
+    pub fn get_status(&self, user_id: i32) -> Option<UserStatusModel> {
+        Self::get_fresh(&self._statuses, user_id)
+    }
+
+    pub fn set_status(&self, user_id: i32, status: &UserStatusModel) {
+        Self::insert(&self._statuses, user_id, status);
+    }
+
+    /// Evicts the user's stamps and status
+    pub fn remove(&self, user_id: i32) {
+        if let Ok(mut entries) = self._entries.write() {
+            entries.remove(&user_id);
+        }
+        if let Ok(mut statuses) = self._statuses.write() {
+            statuses.remove(&user_id);
+        }
+    }
+
+    /// For changes that reach more users than are worth looking up
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self._entries.write() {
+            entries.clear();
+        }
+        if let Ok(mut statuses) = self._statuses.write() {
+            statuses.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::rules::{StaleToken, TokenStampRule};
+
+    fn stamps(security_stamp: &str) -> UserStampsModel {
+        UserStampsModel {
+            security_stamp: security_stamp.to_string(),
+            authorization_version: 1,
+        }
+    }
+
+    #[test]
+    fn test_cached_stamps_are_returned() {
+        let cache = UserStampsCache::new();
+        cache.set(1, &stamps("stamp"));
+
+        assert_eq!(cache.get(1).unwrap().security_stamp, "stamp");
+        assert!(cache.get(2).is_none());
+    }
+
+    #[test]
+    fn test_removed_user_is_looked_up_again() {
+        let cache = UserStampsCache::new();
+        cache.set(1, &stamps("before-reset"));
+        let cached = cache.get(1).unwrap();
+        assert_eq!(
+            TokenStampRule::check_security_stamp(Some("before-reset"), &cached.security_stamp),
+            Ok(())
+        );
+
+        // The next lookup misses and caches whatever stamp the database has now
+        cache.remove(1);
+        assert!(cache.get(1).is_none());
+
+        cache.set(1, &stamps("after-reset"));
+        let cached = cache.get(1).unwrap();
+        assert_eq!(
+            TokenStampRule::check_security_stamp(Some("before-reset"), &cached.security_stamp),
+            Err(StaleToken::SecurityStamp)
+        );
+    }
+
+    #[test]
+    fn test_remove_evicts_the_status_too() {
+        let cache = UserStampsCache::new();
+        cache.set(1, &stamps("stamp"));
+        cache.set_status(
+            1,
+            &UserStatusModel {
+                user_id: 1,
+                ..Default::default()
+            },
+        );
+        assert_eq!(cache.get_status(1).unwrap().user_id, 1);
+
+        cache.remove(1);
+        assert!(cache.get(1).is_none());
+        assert!(cache.get_status(1).is_none());
+    }
+
+    #[test]
+    fn test_clear_evicts_every_user() {
+        let cache = UserStampsCache::new();
+        cache.set(1, &stamps("a"));
+        cache.set(2, &stamps("b"));
+
+        cache.clear();
+        assert!(cache.get(1).is_none());
+        assert!(cache.get(2).is_none());
+    }
+
+    #[test]
+    fn test_clones_share_the_cache() {
+        let cache = UserStampsCache::new();
+        let clone = cache.clone();
+        cache.set(1, &stamps("stamp"));
+
+        clone.remove(1);
+        assert!(cache.get(1).is_none());
+    }
+}
@@ -3,7 +3,8 @@ use std::{future::Future, pin::Pin};
 use crate::domain::{
     models::{
         user_model::UserModel, user_permission_model::UserPermissionModel,
-        user_role_model::UserRoleModel, user_statuses::UserStatuses,
+        user_role_model::UserRoleModel, user_stamps_model::UserStampsModel,
+        user_statuses::UserStatuses,
     },
     repositories::{
         role_repository_trait::RoleRepositoryTrait,
@@ -22,8 +23,8 @@ use super::{
     user_details_dto::UserDetailsDto, user_dto::UserDto,
     user_permission_creation_dto::UserPermissionCreationDto,
     user_permission_dto::UserPermissionDto, user_role_creation_dto::UserRoleCreationDto,
-    user_role_dto::UserRoleDto, user_updation_dto::UserUpdationDto,
-    user_usecase_trait::UserUseCaseTrait,
+    user_role_dto::UserRoleDto, user_stamps_cache::UserStampsCache,
+    user_updation_dto::UserUpdationDto, user_usecase_trait::UserUseCaseTrait,
 };
 
 #[derive(Clone)]
@@ -41,6 +42,7 @@ where
     _user_role_repository: URT,
     _user_permission_repository: UP,
     _password_hasher: PH,
+    _user_stamps_cache: UserStampsCache,
 }
 
 impl<UT, RT, URT, UP, PH> UserUseCase<UT, RT, URT, UP, PH>
@@ -57,6 +59,7 @@ where
         user_role_repository: URT,
         user_permission_repository: UP,
         password_hasher: PH,
+        user_stamps_cache: UserStampsCache,
     ) -> Self {
         Self {
             _user_repository: user_repository,
@@ -64,8 +67,27 @@ where
             _user_role_repository: user_role_repository,
             _user_permission_repository: user_permission_repository,
             _password_hasher: password_hasher,
+            _user_stamps_cache: user_stamps_cache,
         }
     }
+
+    /// Stamps of the user, from the cache while they are fresh
+    async fn get_stamps(
+        user_repository: UT,
+        user_stamps_cache: UserStampsCache,
+        user_id: i32,
+    ) -> Result<UserStampsModel, ApplicationError> {
+        if let Some(stamps) = user_stamps_cache.get(user_id) {
+            return Ok(stamps);
+        }
+
+        let stamps = user_repository
+            .get_stamps(user_id)
+            .await
+            .map_err(ApplicationError::Infrastructure)?;
+        user_stamps_cache.set(user_id, &stamps);
+        Ok(stamps)
+    }
 }
 
 impl<UT, RT, URT, UP, PH> UserUseCaseTrait for UserUseCase<UT, RT, URT, UP, PH>
@@ -97,12 +119,87 @@ where
                     updated_on: f.updated_on.with_timezone(&Utc),
                     updated_by_id: f.updated_by_id,
                     status_id: f.status_id,
+                    kind_id: f.kind_id,
                 }),
                 Err(err) => Err(ApplicationError::Infrastructure(err)),
             }
         })
     }
 
+    fn get_security_stamp(
+        &self,
+        user_id: i32,
+    ) -> Pin<Box<dyn Future<Output = Result<String, ApplicationError>> + Send>> {
+        let user_repository = self._user_repository.clone();
+        let user_stamps_cache = self._user_stamps_cache.clone();
+        Box::pin(async move {
+            let stamps = Self::get_stamps(user_repository, user_stamps_cache, user_id).await?;
+            Ok(stamps.security_stamp)
+        })
+    }
+
+    fn get_authorization_version(
+        &self,
+        user_id: i32,
+    ) -> Pin<Box<dyn Future<Output = Result<i32, ApplicationError>> + Send>> {
+        let user_repository = self._user_repository.clone();
+        let user_stamps_cache = self._user_stamps_cache.clone();
+        Box::pin(async move {
+            let stamps = Self::get_stamps(user_repository, user_stamps_cache, user_id).await?;
+            Ok(stamps.authorization_version)
+        })
+    }
+
+    async fn rotate_security_stamp(&self, user_id: i32) -> Result<(), ApplicationError> {
+        let mut existing = self
+            ._user_repository
+            .get_by_id(user_id)
+            .await
+            .map_err(ApplicationError::Infrastructure)?;
+
+        existing.security_stamp = self._password_hasher.generate_salt();
+        self._user_repository
+            .update(existing)
+            .await
+            .map_err(ApplicationError::Infrastructure)?;
+        self._user_stamps_cache.remove(user_id);
+        Ok(())
+    }
+
+    async fn increment_authorization_version(&self, user_id: i32) -> Result<(), ApplicationError> {
+        self._user_repository
+            .increment_authorization_version(user_id)
+            .await
+            .map_err(ApplicationError::Infrastructure)?;
+        self._user_stamps_cache.remove(user_id);
+        Ok(())
+    }
+
+    async fn increment_authorization_version_by_roles(
+        &self,
+        role_ids: Vec<i32>,
+    ) -> Result<(), ApplicationError> {
+        self._user_repository
+            .increment_authorization_version_by_roles(role_ids)
+            .await
+            .map_err(ApplicationError::Infrastructure)?;
+        self._user_stamps_cache.clear();
+        Ok(())
+    }
+
+    async fn increment_authorization_version_by_permission(
+        &self,
+        permission_id: i32,
+        role_ids: Vec<i32>,
+    ) -> Result<(), ApplicationError> {
+        self._user_repository
+            .increment_authorization_version_by_permission(permission_id, role_ids)
+            .await
+            .map_err(ApplicationError::Infrastructure)?;
+        self._user_stamps_cache.clear();
+        Ok(())
+    }
+
     async fn get_user_by_name(&self, name: &String) -> Result<UserDto, ApplicationError> {
         let existing = self._user_repository.get_by_name(&name).await;
         match existing {
@@ -116,6 +213,7 @@ where
                 updated_on: f.updated_on.with_timezone(&Utc),
                 updated_by_id: f.updated_by_id,
                 status_id: f.status_id,
+                kind_id: f.kind_id,
             }),
             Err(err) => Err(ApplicationError::Infrastructure(err)),
         }
@@ -134,6 +232,7 @@ where
                 updated_on: f.updated_on.with_timezone(&Utc),
                 updated_by_id: f.updated_by_id,
                 status_id: f.status_id,
+                kind_id: f.kind_id,
             }),
             Err(err) => Err(ApplicationError::Infrastructure(err)),
         }
@@ -149,6 +248,7 @@ where
             display_name: user_req.display_name,
             email: user_req.email,
             status_id: user_req.status_id,
+            kind_id: user_req.kind_id,
             password_hash: user_req.password,
             security_stamp: user_req.security_stamp,
             ..Default::default()
@@ -190,6 +290,7 @@ where
                         display_name: f.display_name,
                         email: f.email,
                         status_id: f.status_id,
+                        kind_id: f.kind_id,
                         updated_by_id: f.updated_by_id,
                         created_on: f.created_on.with_timezone(&Utc),
                         updated_on: f.updated_on.with_timezone(&Utc),
@@ -210,6 +311,7 @@ where
             display_name: user_req.display_name,
             email: user_req.email,
             status_id: user_req.status_id,
+            kind_id: user_req.kind_id,
             password_hash: user_req.password,
             security_stamp: user_req.security_stamp,
             ..Default::default()
@@ -250,12 +352,17 @@ where
                     None => {}
                 };
                 match user_req.status_id {
-                    Some(status_id) => exist.status_id = status_id,
+                    Some(status_id) => {
+                        // Status changes (e.g. deletion) must also end existing sessions
+                        exist.security_stamp = self._password_hasher.generate_salt();
+                        exist.status_id = status_id
+                    }
                     None => {}
                 };
 
                 exist.updated_by_id = user_req.updated_by_id;
                 let updated = self._user_repository.update(exist).await;
+                self._user_stamps_cache.remove(id);
                 match updated {
                     Ok(i) => Some(i),
                     Err(_) => None,
@@ -316,10 +423,15 @@ where
                 ..Default::default()
             })
             .collect::<Vec<UserRoleModel>>();
-        match self._user_role_repository.create_many(user_roles).await {
-            Ok(inserted) => Ok(inserted),
-            Err(err) => Err(ApplicationError::Infrastructure(err)),
+        let inserted = match self._user_role_repository.create_many(user_roles).await {
+            Ok(inserted) => inserted,
+            Err(err) => return Err(ApplicationError::Infrastructure(err)),
+        };
+
+        if inserted > 0 {
+            self.increment_authorization_version(user_id).await?;
         }
+        Ok(inserted)
     }
 
     async fn unassign_roles(
@@ -334,16 +446,21 @@ where
                 ..Default::default()
             })
             .collect::<Vec<UserRoleModel>>();
-        match self
+        let deleted = match self
             ._user_role_repository
             .delete_many(user_id, deleted_roles)
             .await
         {
-            Ok(deleted) => Ok(deleted),
+            Ok(deleted) => deleted,
             Err(err) => {
                 return Err(ApplicationError::Infrastructure(err));
             }
+        };
+
+        if deleted > 0 {
+            self.increment_authorization_version(user_id).await?;
         }
+        Ok(deleted)
     }
 
     fn get_user_roles_by_user_id(
@@ -439,14 +556,19 @@ where
                 ..Default::default()
             })
             .collect::<Vec<UserPermissionModel>>();
-        match self
+        let inserted = match self
             ._user_permission_repository
             .create_many(user_permissions)
             .await
         {
-            Ok(inserted) => Ok(inserted),
-            Err(err) => Err(ApplicationError::Infrastructure(err)),
+            Ok(inserted) => inserted,
+            Err(err) => return Err(ApplicationError::Infrastructure(err)),
+        };
+
+        if inserted > 0 {
+            self.increment_authorization_version(user_id).await?;
         }
+        Ok(inserted)
     }
 
     async fn unassign_permissions(
@@ -461,13 +583,18 @@ where
                 ..Default::default()
             })
             .collect::<Vec<UserPermissionModel>>();
-        match self
+        let deleted = match self
             ._user_permission_repository
             .delete_many(user_id, deleted_permissions)
             .await
         {
-            Ok(deleted) => Ok(deleted),
-            Err(err) => Err(ApplicationError::Infrastructure(err)),
+            Ok(deleted) => deleted,
+            Err(err) => return Err(ApplicationError::Infrastructure(err)),
+        };
+
+        if deleted > 0 {
+            self.increment_authorization_version(user_id).await?;
         }
+        Ok(deleted)
     }
 }
@@ -16,6 +16,37 @@ pub trait UserUseCaseTrait {
         &self,
         email: &str,
     ) -> Pin<Box<dyn Future<Output = Result<UserDetailsDto, ApplicationError>> + Send>>;
+    fn get_security_stamp(
+        &self,
+        user_id: i32,
+    ) -> Pin<Box<dyn Future<Output = Result<String, ApplicationError>> + Send>>;
+    /// Invalidates every access and refresh token issued to the user so far
+    fn rotate_security_stamp(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<(), ApplicationError>>;
+    fn get_authorization_version(
+        &self,
+        user_id: i32,
+    ) -> Pin<Box<dyn Future<Output = Result<i32, ApplicationError>> + Send>>;
+    /// Makes access tokens carrying the user's old roles and permissions be refreshed;
+    /// refresh tokens keep working
+    fn increment_authorization_version(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<(), ApplicationError>>;
+    /// Same as `increment_authorization_version`, for every user holding any of the roles
+    fn increment_authorization_version_by_roles(
+        &self,
+        role_ids: Vec<i32>,
+    ) -> impl Future<Output = Result<(), ApplicationError>>;
+    /// Same as `increment_authorization_version`, for every user holding the permission
+    /// directly, or holding any of the roles granting it
+    fn increment_authorization_version_by_permission(
+        &self,
+        permission_id: i32,
+        role_ids: Vec<i32>,
+    ) -> impl Future<Output = Result<(), ApplicationError>>;
     fn get_user_by_name(
         &self,
         name: &String,
@@ -1,7 +1,9 @@
 pub mod models;
 pub mod repositories;
+pub mod rules;
 pub mod services;
 
 pub use models::*;
 pub use repositories::*;
+pub use rules::*;
 pub use services::*;
@@ -1,17 +1,31 @@
 pub mod permission_model;
 pub mod role_model;
 pub mod role_permission_model;
+pub mod signing_key_model;
+pub mod user_api_key_model;
+pub mod user_external_login_model;
+pub mod user_login_throttle_model;
+pub mod user_mfa_model;
 pub mod user_model;
 pub mod user_permission_model;
 pub mod user_role_model;
+pub mod user_stamps_model;
+pub mod user_status_model;
 pub mod user_statuses;
 pub mod user_token_model;
 
 pub use permission_model::PermissionModel;
 pub use role_model::RoleModel;
 pub use role_permission_model::RolePermissionModel;
+pub use signing_key_model::SigningKeyModel;
+pub use user_api_key_model::UserApiKeyModel;
+pub use user_external_login_model::UserExternalLoginModel;
+pub use user_login_throttle_model::UserLoginThrottleModel;
+pub use user_mfa_model::UserMfaModel;
 pub use user_model::UserModel;
 pub use user_permission_model::UserPermissionModel;
 pub use user_role_model::UserRoleModel;
+pub use user_stamps_model::UserStampsModel;
+pub use user_status_model::UserStatusModel;
 pub use user_statuses::UserStatuses;
 pub use user_token_model::UserTokenModel;
@@ -10,4 +10,7 @@ pub struct RoleModel {
     pub updated_on: DateTime<Utc>,
     pub updated_by_id: Option<i32>,
     pub is_actived: bool,
+    pub requires_mfa: bool,
+    /// The role this one inherits permissions from
+    pub parent_id: Option<i32>,
 }
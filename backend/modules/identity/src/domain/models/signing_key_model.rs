@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Default, Clone)]
+pub struct SigningKeyModel {
+    pub id: i32,
+    /// Key id, sent in the `kid` header of every token the key signs
+    pub kid: String,
+    /// JWT algorithm name, e.g. `EdDSA` or `RS256`
+    pub algorithm: String,
+    /// Base64 of the DER encoded private key
+    pub private_key: String,
+    /// Public key as a JSON Web Key
+    pub public_key: String,
+    pub created_on: DateTime<Utc>,
+    /// The key verifies tokens right away but signs only from then on
+    pub activates_on: DateTime<Utc>,
+    /// Set once the key is replaced; it stops verifying then
+    pub expires_on: Option<DateTime<Utc>>,
+}
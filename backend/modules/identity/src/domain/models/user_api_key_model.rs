@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Default, Clone)]
+pub struct UserApiKeyModel {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    /// Start of the key, kept in clear so the owner can tell keys apart
+    pub key_prefix: String,
+    /// SHA-256 of the whole key
+    pub key_hash: String,
+    /// Permission codes the key may use, a subset of the owner's
+    pub scopes: Vec<String>,
+    pub created_by_id: Option<i32>,
+    pub created_on: DateTime<Utc>,
+    pub expires_on: Option<DateTime<Utc>>,
+    pub last_used_on: Option<DateTime<Utc>>,
+    pub revoked_on: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Default, Clone)]
+pub struct UserExternalLoginModel {
+    pub id: i32,
+    pub user_id: i32,
+    /// Name of the provider in the configuration, e.g. `google`
+    pub provider: String,
+    /// The provider's stable id for the account, the `sub` claim
+    pub subject: String,
+    pub email: Option<String>,
+    pub created_on: DateTime<Utc>,
+    pub last_used_on: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,9 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Default, Clone)]
+pub struct UserLoginThrottleModel {
+    pub user_id: i32,
+    pub failed_login_count: i32,
+    pub last_failed_login_on: Option<DateTime<Utc>>,
+    pub locked_until: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,9 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Default, Clone)]
+pub struct UserMfaModel {
+    pub user_id: i32,
+    pub secret: Option<String>,
+    pub enabled_on: Option<DateTime<Utc>>,
+    pub last_used_step: Option<i64>,
+}
@@ -13,4 +13,6 @@ pub struct UserModel {
     pub updated_on: DateTime<Utc>,
     pub updated_by_id: Option<i32>,
     pub status_id: i32,
+    /// See `UserKinds`
+    pub kind_id: i32,
 }
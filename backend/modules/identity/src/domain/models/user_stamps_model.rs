@@ -0,0 +1,9 @@
+/// Values every access token is checked against; a token carrying older ones is refused
+#[derive(Default, Clone)]
+pub struct UserStampsModel {
+    /// Rotated to end every session of the user
+    pub security_stamp: String,
+    /// Raised when the user's roles or permissions change, so tokens carrying the old ones
+    /// have to be refreshed
+    pub authorization_version: i32,
+}
@@ -0,0 +1,11 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Default, Clone)]
+pub struct UserStatusModel {
+    pub user_id: i32,
+    pub status_id: i32,
+    pub status_reason: Option<String>,
+    pub status_expires_on: Option<DateTime<Utc>>,
+    pub status_changed_by_id: Option<i32>,
+    pub status_changed_on: Option<DateTime<Utc>>,
+}
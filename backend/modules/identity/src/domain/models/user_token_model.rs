@@ -12,4 +12,5 @@ pub struct UserTokenModel {
     pub updated_by_id: i32,
     pub is_actived: bool,
     pub purpose: i32,
+    pub family_id: Option<String>,
 }
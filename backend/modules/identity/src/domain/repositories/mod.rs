@@ -1,15 +1,27 @@
 pub mod permission_repository_trait;
 pub mod role_permission_repository_trait;
 pub mod role_repository_trait;
+pub mod signing_key_repository_trait;
+pub mod user_api_key_repository_trait;
+pub mod user_external_login_repository_trait;
+pub mod user_login_throttle_repository_trait;
+pub mod user_mfa_repository_trait;
 pub mod user_permission_repository_trait;
 pub mod user_repository_trait;
 pub mod user_role_repository_trait;
+pub mod user_status_repository_trait;
 pub mod user_token_repository_trait;
 
 pub use permission_repository_trait::PermissionRepositoryTrait;
 pub use role_permission_repository_trait::RolePermissionRepositoryTrait;
 pub use role_repository_trait::RoleRepositoryTrait;
+pub use signing_key_repository_trait::SigningKeyRepositoryTrait;
+pub use user_api_key_repository_trait::UserApiKeyRepositoryTrait;
+pub use user_external_login_repository_trait::UserExternalLoginRepositoryTrait;
+pub use user_login_throttle_repository_trait::UserLoginThrottleRepositoryTrait;
+pub use user_mfa_repository_trait::UserMfaRepositoryTrait;
 pub use user_permission_repository_trait::UserPermissionRepositoryTrait;
 pub use user_repository_trait::UserRepositoryTrait;
 pub use user_role_repository_trait::UserRoleRepositoryTrait;
+pub use user_status_repository_trait::UserStatusRepositoryTrait;
 pub use user_token_repository_trait::UserTokenRepositoryTrait;
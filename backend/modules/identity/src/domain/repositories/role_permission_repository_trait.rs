@@ -26,6 +26,10 @@ pub trait RolePermissionRepositoryTrait {
     fn get_list(
         &self,
     ) -> Pin<Box<dyn Future<Output = Result<Vec<RolePermissionModel>, InfraError>> + Send>>;
+    fn get_role_ids_by_permission_id(
+        &self,
+        permission_id: i32,
+    ) -> impl Future<Output = Result<Vec<i32>, InfraError>>;
     fn create_many(
         &self,
         role_permission_req: Vec<RolePermissionModel>,
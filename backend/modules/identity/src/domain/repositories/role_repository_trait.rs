@@ -1,5 +1,6 @@
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
 use rex_game_shared::InfraError;
-use std::future::Future;
+use std::{collections::HashMap, future::Future};
 
 use crate::domain::models::role_model::RoleModel;
 use rex_game_shared::domain::models::page_list_model::PageListModel;
@@ -9,8 +10,10 @@ pub trait RoleRepositoryTrait {
 
     fn get_by_name(&self, name: &str) -> impl Future<Output = Option<RoleModel>>;
     fn get_by_id(&self, id: i32) -> impl Future<Output = Result<RoleModel, InfraError>>;
-    fn get_by_ids(&self, ids: Vec<i32>)
-        -> impl Future<Output = Result<Vec<RoleModel>, InfraError>>;
+    fn get_by_ids(
+        &self,
+        ids: Vec<i32>,
+    ) -> impl Future<Output = Result<Vec<RoleModel>, InfraError>> + Send;
     fn get_paged_list(
         &self,
         name: Option<String>,
@@ -18,5 +21,22 @@ pub trait RoleRepositoryTrait {
         page_option: u64,
         page_size: Option<u64>,
     ) -> impl Future<Output = Result<PageListModel<RoleModel>, InfraError>>;
+    /// Every active role, with the role it inherits from
+    fn get_parents(
+        &self,
+    ) -> impl Future<Output = Result<HashMap<i32, Option<i32>>, InfraError>> + Send;
+    /// Like `get_parents`, with the role rows locked until the transaction ends so hierarchy
+    /// changes are checked one at a time
+    fn get_parents_with_transaction(
+        &self,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> impl Future<Output = Result<HashMap<i32, Option<i32>>, InfraError>> + Send;
     fn update(&self, role_req: RoleModel) -> impl Future<Output = Result<bool, InfraError>>;
+    fn update_parent_without_commit(
+        &self,
+        id: i32,
+        parent_id: Option<i32>,
+        updated_by_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> impl Future<Output = Result<bool, InfraError>>;
 }
@@ -0,0 +1,20 @@
+use crate::domain::models::signing_key_model::SigningKeyModel;
+use chrono::{DateTime, Utc};
+use rex_game_shared::InfraError;
+use std::future::Future;
+
+pub trait SigningKeyRepositoryTrait {
+    /// Keys that have not expired by `now`, including ones not active yet
+    fn get_valid(
+        &self,
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Vec<SigningKeyModel>, InfraError>>;
+    fn create(&self, signing_key: SigningKeyModel)
+        -> impl Future<Output = Result<i32, InfraError>>;
+    /// Schedules the expiry of every key that activates before the new key and has no expiry yet
+    fn expire_replaced(
+        &self,
+        activates_before: DateTime<Utc>,
+        expires_on: DateTime<Utc>,
+    ) -> impl Future<Output = Result<u64, InfraError>>;
+}
@@ -0,0 +1,32 @@
+use crate::domain::models::user_api_key_model::UserApiKeyModel;
+use chrono::{DateTime, Utc};
+use rex_game_shared::InfraError;
+use std::future::Future;
+
+pub trait UserApiKeyRepositoryTrait {
+    fn get_by_hash(
+        &self,
+        key_hash: &str,
+    ) -> impl Future<Output = Result<Option<UserApiKeyModel>, InfraError>>;
+    /// Keys of the user that have not been revoked, newest first
+    fn get_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<Vec<UserApiKeyModel>, InfraError>>;
+    fn create(&self, api_key: UserApiKeyModel) -> impl Future<Output = Result<i32, InfraError>>;
+    /// Only writes when the key was last used before `stale_before`, so a busy key does not
+    /// update its row on every request
+    fn mark_used(
+        &self,
+        id: i32,
+        used_on: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+    ) -> impl Future<Output = Result<(), InfraError>>;
+    /// Returns false when the user has no such key, or it was already revoked
+    fn revoke(
+        &self,
+        user_id: i32,
+        id: i32,
+        revoked_on: DateTime<Utc>,
+    ) -> impl Future<Output = Result<bool, InfraError>>;
+}
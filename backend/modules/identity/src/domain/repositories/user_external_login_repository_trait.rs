@@ -0,0 +1,41 @@
+use crate::domain::models::user_external_login_model::UserExternalLoginModel;
+use chrono::{DateTime, Utc};
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
+use rex_game_shared::InfraError;
+use std::future::Future;
+
+pub trait UserExternalLoginRepositoryTrait {
+    fn get_by_subject(
+        &self,
+        provider: &str,
+        subject: &str,
+    ) -> impl Future<Output = Result<Option<UserExternalLoginModel>, InfraError>>;
+    fn get_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<Vec<UserExternalLoginModel>, InfraError>>;
+    /// Links the provider account. Returns false when it is already linked to a user, or
+    /// the user already has an account at that provider.
+    fn create(
+        &self,
+        external_login: UserExternalLoginModel,
+    ) -> impl Future<Output = Result<bool, InfraError>>;
+    fn create_without_commit(
+        &self,
+        external_login: UserExternalLoginModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> impl Future<Output = Result<bool, InfraError>>;
+    /// Records a sign-in, along with the email the provider gave this time
+    fn mark_used(
+        &self,
+        id: i32,
+        email: Option<String>,
+        used_on: DateTime<Utc>,
+    ) -> impl Future<Output = Result<(), InfraError>>;
+    /// Returns false when the user had no link to the provider
+    fn delete(
+        &self,
+        user_id: i32,
+        provider: &str,
+    ) -> impl Future<Output = Result<bool, InfraError>>;
+}
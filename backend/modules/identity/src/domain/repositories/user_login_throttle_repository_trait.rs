@@ -0,0 +1,33 @@
+use crate::domain::models::user_login_throttle_model::UserLoginThrottleModel;
+use chrono::{DateTime, Utc};
+use rex_game_shared::InfraError;
+use std::future::Future;
+
+pub trait UserLoginThrottleRepositoryTrait {
+    fn get_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<UserLoginThrottleModel, InfraError>>;
+    /// Counts one more failed login. Returns the failures in a row so far.
+    fn record_failure(
+        &self,
+        user_id: i32,
+        failed_on: DateTime<Utc>,
+    ) -> impl Future<Output = Result<i32, InfraError>>;
+    /// Locks the account and starts counting failures again. Returns false when it was
+    /// already locked, so only one caller reports the lock.
+    fn lock(
+        &self,
+        user_id: i32,
+        locked_until: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = Result<bool, InfraError>>;
+    /// Forgets earlier failures after a successful login
+    fn reset(&self, user_id: i32) -> impl Future<Output = Result<(), InfraError>>;
+    /// Lifts a lock early. Returns false when the account was not locked.
+    fn unlock(
+        &self,
+        user_id: i32,
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = Result<bool, InfraError>>;
+}
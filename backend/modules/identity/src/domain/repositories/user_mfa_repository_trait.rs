@@ -0,0 +1,48 @@
+use crate::domain::models::user_mfa_model::UserMfaModel;
+use rex_game_shared::InfraError;
+use std::future::Future;
+
+pub trait UserMfaRepositoryTrait {
+    fn get_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<UserMfaModel, InfraError>>;
+    /// Whether any active role of the user makes two-factor authentication mandatory
+    fn is_required(&self, user_id: i32) -> impl Future<Output = Result<bool, InfraError>>;
+    /// Stores a secret that is not confirmed yet, replacing any earlier one
+    fn set_pending_secret(
+        &self,
+        user_id: i32,
+        secret: &str,
+    ) -> impl Future<Output = Result<(), InfraError>>;
+    /// Turns two-factor authentication on and replaces the recovery codes
+    fn enable(
+        &self,
+        user_id: i32,
+        used_step: i64,
+        recovery_code_hashes: Vec<String>,
+    ) -> impl Future<Output = Result<(), InfraError>>;
+    /// Clears the secret and deletes the recovery codes
+    fn disable(&self, user_id: i32) -> impl Future<Output = Result<(), InfraError>>;
+    /// Records a TOTP time step as used. Returns false when it, or a later one, already was.
+    fn use_time_step(
+        &self,
+        user_id: i32,
+        step: i64,
+    ) -> impl Future<Output = Result<bool, InfraError>>;
+    /// Marks an unused recovery code as used. Returns false when there is no such code.
+    fn use_recovery_code(
+        &self,
+        user_id: i32,
+        code_hash: &str,
+    ) -> impl Future<Output = Result<bool, InfraError>>;
+    fn replace_recovery_codes(
+        &self,
+        user_id: i32,
+        recovery_code_hashes: Vec<String>,
+    ) -> impl Future<Output = Result<(), InfraError>>;
+    fn count_unused_recovery_codes(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<u64, InfraError>>;
+}
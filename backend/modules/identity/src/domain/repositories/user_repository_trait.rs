@@ -1,7 +1,7 @@
 use rex_game_shared::InfraError;
 use std::{future::Future, pin::Pin};
 
-use crate::domain::models::user_model::UserModel;
+use crate::domain::models::{user_model::UserModel, user_stamps_model::UserStampsModel};
 use rex_game_shared::domain::{
     models::page_list_model::PageListModel, transaction_manager_trait::TransactionWrapperTrait,
 };
@@ -31,4 +31,25 @@ pub trait UserRepositoryTrait {
     fn get_by_id(&self, id: i32) -> impl Future<Output = Result<UserModel, InfraError>>;
     fn get_by_name(&self, name: &String) -> impl Future<Output = Result<UserModel, InfraError>>;
     fn update(&self, user_req: UserModel) -> impl Future<Output = Result<bool, InfraError>>;
+    fn get_stamps(
+        &self,
+        id: i32,
+    ) -> Pin<Box<dyn Future<Output = Result<UserStampsModel, InfraError>> + Send>>;
+    /// Returns the number of users whose authorization version was raised
+    fn increment_authorization_version(
+        &self,
+        id: i32,
+    ) -> impl Future<Output = Result<u64, InfraError>>;
+    /// Raises the authorization version of every user holding any of the roles
+    fn increment_authorization_version_by_roles(
+        &self,
+        role_ids: Vec<i32>,
+    ) -> impl Future<Output = Result<u64, InfraError>>;
+    /// Raises the authorization version of every user holding the permission directly, or
+    /// holding any of the roles granting it
+    fn increment_authorization_version_by_permission(
+        &self,
+        permission_id: i32,
+        role_ids: Vec<i32>,
+    ) -> impl Future<Output = Result<u64, InfraError>>;
 }
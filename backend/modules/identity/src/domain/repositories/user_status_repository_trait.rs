@@ -0,0 +1,24 @@
+use crate::domain::models::user_status_model::UserStatusModel;
+use chrono::{DateTime, Utc};
+use rex_game_shared::InfraError;
+use std::future::Future;
+
+pub trait UserStatusRepositoryTrait {
+    fn get_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> impl Future<Output = Result<UserStatusModel, InfraError>>;
+    /// Moves the user to `status`, but only from one of `from_status_ids`. Returns false when
+    /// the user was in another status.
+    fn change_status(
+        &self,
+        status: UserStatusModel,
+        from_status_ids: Vec<i32>,
+    ) -> impl Future<Output = Result<bool, InfraError>>;
+    /// Reactivates the user when their suspension has run out. Returns false when it has not.
+    fn lift_expired_suspension(
+        &self,
+        user_id: i32,
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = Result<bool, InfraError>>;
+}
@@ -1,9 +1,15 @@
 use crate::domain::models::user_token_model::UserTokenModel;
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
 use rex_game_shared::InfraError;
 use std::future::Future;
 
 pub trait UserTokenRepositoryTrait {
     fn create(&self, user_token: UserTokenModel) -> impl Future<Output = Result<i32, InfraError>>;
+    fn create_without_commit(
+        &self,
+        user_token: UserTokenModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> impl Future<Output = Result<i32, InfraError>>;
     fn get_by_id(
         &self,
         id: i32,
@@ -16,4 +22,26 @@ pub trait UserTokenRepositoryTrait {
         &self,
         user_req: UserTokenModel,
     ) -> impl Future<Output = Result<bool, InfraError>> + Send;
+    /// Returns false when the token was already deactivated, e.g. by a concurrent refresh
+    fn deactivate_if_active(
+        &self,
+        id: i32,
+        updated_by_id: i32,
+    ) -> impl Future<Output = Result<bool, InfraError>> + Send;
+    /// Deactivates every active token in the family and returns how many were revoked
+    fn deactivate_family(
+        &self,
+        user_id: i32,
+        family_id: &str,
+    ) -> impl Future<Output = Result<u64, InfraError>> + Send;
+    fn deactivate_by_user(
+        &self,
+        user_id: i32,
+        purpose: i32,
+    ) -> impl Future<Output = Result<u64, InfraError>> + Send;
+    fn get_active_by_user(
+        &self,
+        user_id: i32,
+        purpose: i32,
+    ) -> impl Future<Output = Result<Vec<UserTokenModel>, InfraError>> + Send;
 }
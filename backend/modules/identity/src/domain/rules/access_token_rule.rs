@@ -0,0 +1,28 @@
+use rex_game_shared::domain::enums::user_token_porposes::UserTokenPurposes;
+
+/// Why a valid token cannot be used to access the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefusedAccessToken {
+    /// Only good for finishing a two-factor login
+    MfaPending,
+    /// Issued for another purpose, e.g. a refresh or password reset token
+    WrongPurpose,
+}
+
+pub struct AccessTokenRule;
+
+impl AccessTokenRule {
+    /// Only tokens issued by a completed login are access tokens.
+    pub fn check_purpose(token_type: &str) -> Result<(), RefusedAccessToken> {
+        if token_type == UserTokenPurposes::Login.as_str() {
+            return Ok(());
+        }
+
+        if token_type == UserTokenPurposes::MfaPending.as_str() {
+            return Err(RefusedAccessToken::MfaPending);
+        }
+
+        Err(RefusedAccessToken::WrongPurpose)
+    }
+}
+
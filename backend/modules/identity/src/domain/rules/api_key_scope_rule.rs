@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+
+use crate::domain::models::user_api_key_model::UserApiKeyModel;
+
+/// What an API key may do. A key never does more than its scopes, nor more than its owner
+/// is allowed right now.
+pub struct ApiKeyScopeRule;
+
+impl ApiKeyScopeRule {
+    /// Requested scopes that are not in `granted`, in the order they were asked for
+    pub fn missing_scopes(requested: &[String], granted: &HashSet<String>) -> Vec<String> {
+        requested
+            .iter()
+            .filter(|scope| !granted.contains(*scope))
+            .cloned()
+            .collect()
+    }
+
+    /// Scopes the owner still holds. Root admins are not checked against permissions
+    /// anywhere else, so their keys keep every scope.
+    pub fn effective_permissions(
+        scopes: &[String],
+        granted: &HashSet<String>,
+        is_root_admin: bool,
+    ) -> Vec<String> {
+        if is_root_admin {
+            return scopes.to_vec();
+        }
+        scopes
+            .iter()
+            .filter(|scope| granted.contains(*scope))
+            .cloned()
+            .collect()
+    }
+
+    pub fn is_usable(api_key: &UserApiKeyModel, now: DateTime<Utc>) -> bool {
+        api_key.revoked_on.is_none() && api_key.expires_on.is_none_or(|expires_on| expires_on > now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use rex_game_shared::test_helpers::now;
+
+    fn codes(values: &[&str]) -> Vec<String> {
+        values.iter().map(|f| f.to_string()).collect()
+    }
+
+    fn granted(values: &[&str]) -> HashSet<String> {
+        codes(values).into_iter().collect()
+    }
+
+    #[test]
+    fn test_scopes_outside_the_grant_are_missing() {
+        let missing = ApiKeyScopeRule::missing_scopes(
+            &codes(&["flashcard:read", "user:delete", "flashcard:create"]),
+            &granted(&["flashcard:read", "flashcard:create"]),
+        );
+        assert_eq!(missing, codes(&["user:delete"]));
+    }
+
+    #[test]
+    fn test_key_loses_permissions_its_owner_lost() {
+        let permissions = ApiKeyScopeRule::effective_permissions(
+            &codes(&["flashcard:read", "flashcard:create"]),
+            &granted(&["flashcard:read", "user:read"]),
+            false,
+        );
+        assert_eq!(permissions, codes(&["flashcard:read"]));
+
+        let root_admin = ApiKeyScopeRule::effective_permissions(
+            &codes(&["flashcard:read", "user:update"]),
+            &HashSet::new(),
+            true,
+        );
+        assert_eq!(root_admin, codes(&["flashcard:read", "user:update"]));
+    }
+
+    #[test]
+    fn test_key_is_usable_until_it_expires_or_is_revoked() {
+        let mut api_key = UserApiKeyModel {
+            expires_on: Some(now() + Duration::seconds(1)),
+            ..Default::default()
+        };
+        assert!(ApiKeyScopeRule::is_usable(&api_key, now()));
+
+        api_key.expires_on = Some(now());
+        assert!(!ApiKeyScopeRule::is_usable(&api_key, now()));
+
+        api_key.expires_on = None;
+        api_key.revoked_on = Some(now() - Duration::days(1));
+        assert!(!ApiKeyScopeRule::is_usable(&api_key, now()));
+    }
+}
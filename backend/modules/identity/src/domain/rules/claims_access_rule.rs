@@ -0,0 +1,23 @@
+/// What the roles and permissions carried by a token or an API key allow.
+pub struct ClaimsAccessRule;
+
+impl ClaimsAccessRule {
+    /// Whether any of `roles` is one of the `required` roles.
+    pub fn has_required_role(roles: &[String], required: &[String]) -> bool {
+        roles.iter().any(|role| required.contains(role))
+    }
+
+    /// Whether any of `permissions` is one of the `required` permissions. Root admins pass
+    /// without them.
+    pub fn has_required_permission(
+        permissions: &[String],
+        required: &[String],
+        is_root_admin: bool,
+    ) -> bool {
+        is_root_admin
+            || permissions
+                .iter()
+                .any(|permission| required.contains(permission))
+    }
+}
+
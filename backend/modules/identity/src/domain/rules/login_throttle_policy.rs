@@ -0,0 +1,121 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::models::user_login_throttle_model::UserLoginThrottleModel;
+
+/// How failed password logins of one account slow down and lock it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoginThrottlePolicy {
+    /// Failed attempts in a row that lock the account
+    pub max_failed_attempts: i32,
+    /// Wait after the first failure; it doubles with every further one
+    pub base_delay_secs: i64,
+    /// How long a lock lasts, which also caps the wait between attempts
+    pub lockout_secs: i64,
+}
+
+impl Default for LoginThrottlePolicy {
+    fn default() -> Self {
+        Self {
+            max_failed_attempts: 5,
+            base_delay_secs: 1,
+            lockout_secs: 15 * 60,
+        }
+    }
+}
+
+impl LoginThrottlePolicy {
+    /// Wait required after `failed_login_count` failures in a row.
+    pub fn delay_after(&self, failed_login_count: i32) -> Duration {
+        if failed_login_count <= 0 {
+            return Duration::zero();
+        }
+
+        let doublings = (failed_login_count - 1).min(30) as u32;
+        let delay_secs = self
+            .base_delay_secs
+            .saturating_mul(1_i64 << doublings)
+            .min(self.lockout_secs);
+        Duration::seconds(delay_secs)
+    }
+
+    /// Whole seconds until the account accepts another password, or `None` when it does now.
+    pub fn retry_after(&self, state: &UserLoginThrottleModel, now: DateTime<Utc>) -> Option<i64> {
+        let ready_on = match state.locked_until {
+            Some(locked_until) if locked_until > now => locked_until,
+            _ => state.last_failed_login_on? + self.delay_after(state.failed_login_count),
+        };
+        if ready_on <= now {
+            return None;
+        }
+
+        // Rounded up, so a client that waits exactly this long is let in
+        let wait_ms = (ready_on - now).num_milliseconds();
+        Some((wait_ms + 999) / 1000)
+    }
+
+    pub fn should_lock(&self, failed_login_count: i32) -> bool {
+        failed_login_count >= self.max_failed_attempts
+    }
+
+    pub fn locked_until(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now + Duration::seconds(self.lockout_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rex_game_shared::test_helpers::now;
+
+    fn state(
+        failed_login_count: i32,
+        last_failed_secs_ago: Option<i64>,
+        locked_for_secs: Option<i64>,
+    ) -> UserLoginThrottleModel {
+        UserLoginThrottleModel {
+            user_id: 1,
+            failed_login_count,
+            last_failed_login_on: last_failed_secs_ago.map(|secs| now() - Duration::seconds(secs)),
+            locked_until: locked_for_secs.map(|secs| now() + Duration::seconds(secs)),
+        }
+    }
+
+    #[test]
+    fn test_delay_doubles_with_each_failure_up_to_the_lockout() {
+        let policy = LoginThrottlePolicy::default();
+        assert_eq!(policy.delay_after(0), Duration::zero());
+        assert_eq!(policy.delay_after(1), Duration::seconds(1));
+        assert_eq!(policy.delay_after(4), Duration::seconds(8));
+        assert_eq!(policy.delay_after(i32::MAX), Duration::seconds(15 * 60));
+    }
+
+    #[test]
+    fn test_waits_out_delay_after_failure() {
+        let policy = LoginThrottlePolicy::default();
+        assert_eq!(policy.retry_after(&state(0, None, None), now()), None);
+        // Three failures: the next attempt is allowed 4 seconds after the last one
+        assert_eq!(policy.retry_after(&state(3, Some(1), None), now()), Some(3));
+        assert_eq!(policy.retry_after(&state(3, Some(4), None), now()), None);
+    }
+
+    #[test]
+    fn test_locked_account_waits_for_lock() {
+        let policy = LoginThrottlePolicy::default();
+        assert_eq!(
+            policy.retry_after(&state(0, Some(1), Some(600)), now()),
+            Some(600)
+        );
+        assert_eq!(
+            policy.retry_after(&state(0, Some(900), Some(-1)), now()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_locks_at_max_failed_attempts() {
+        let policy = LoginThrottlePolicy::default();
+        assert!(!policy.should_lock(4));
+        assert!(policy.should_lock(5));
+        assert_eq!(policy.locked_until(now()), now() + Duration::seconds(900));
+    }
+}
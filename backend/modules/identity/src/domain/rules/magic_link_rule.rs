@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use rex_game_shared::domain::enums::user_token_porposes::UserTokenPurposes;
+
+use crate::domain::models::user_token_model::UserTokenModel;
+
+/// Why a stored sign-in link cannot be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidMagicLink {
+    /// The link or the nonce of the device is missing
+    Missing,
+    /// The stored token was issued for another purpose
+    WrongPurpose,
+    Expired,
+    /// The link was already used to sign in
+    AlreadyUsed,
+}
+
+pub struct MagicLinkRule;
+
+impl MagicLinkRule {
+    /// The value hashed in place of the link, which binds it to the nonce of the device
+    /// that asked for it.
+    pub fn bound_value(token: &str, nonce: &str) -> Result<String, InvalidMagicLink> {
+        if token.is_empty() || nonce.is_empty() {
+            return Err(InvalidMagicLink::Missing);
+        }
+
+        Ok(format!("{}.{}", token, nonce))
+    }
+
+    /// Checks that a stored sign-in link can still be used at `now`.
+    pub fn check(user_token: &UserTokenModel, now: DateTime<Utc>) -> Result<(), InvalidMagicLink> {
+        if user_token.purpose != UserTokenPurposes::MagicLink as i32 {
+            return Err(InvalidMagicLink::WrongPurpose);
+        }
+
+        if (user_token.expiration as i64) <= now.timestamp() {
+            return Err(InvalidMagicLink::Expired);
+        }
+
+        if !user_token.is_actived {
+            return Err(InvalidMagicLink::AlreadyUsed);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rex_game_shared::test_helpers::now;
+
+    fn magic_link(expires_in_secs: i64) -> UserTokenModel {
+        UserTokenModel {
+            id: 1,
+            user_id: 1,
+            purpose: UserTokenPurposes::MagicLink as i32,
+            expiration: (now().timestamp() + expires_in_secs) as i32,
+            is_actived: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_only_an_unused_live_link_can_sign_in() {
+        assert_eq!(MagicLinkRule::check(&magic_link(60), now()), Ok(()));
+
+        let mut used = magic_link(60);
+        used.is_actived = false;
+        assert_eq!(
+            MagicLinkRule::check(&used, now()),
+            Err(InvalidMagicLink::AlreadyUsed)
+        );
+        assert_eq!(
+            MagicLinkRule::check(&magic_link(0), now()),
+            Err(InvalidMagicLink::Expired)
+        );
+    }
+
+    #[test]
+    fn test_link_is_bound_to_the_requesting_device() {
+        let requested = MagicLinkRule::bound_value("token", "device-a").unwrap();
+        assert_eq!(
+            MagicLinkRule::bound_value("token", "device-a"),
+            Ok(requested.clone())
+        );
+        assert_ne!(
+            MagicLinkRule::bound_value("token", "device-b").unwrap(),
+            requested
+        );
+        assert_eq!(
+            MagicLinkRule::bound_value("token", ""),
+            Err(InvalidMagicLink::Missing)
+        );
+    }
+}
@@ -0,0 +1,23 @@
+pub mod access_token_rule;
+pub mod api_key_scope_rule;
+pub mod claims_access_rule;
+pub mod login_throttle_policy;
+pub mod magic_link_rule;
+pub mod ownership_policy;
+pub mod refresh_token_rule;
+pub mod role_hierarchy_rule;
+pub mod signing_key_rotation_policy;
+pub mod token_stamp_rule;
+pub mod user_access_rule;
+
+pub use access_token_rule::*;
+pub use api_key_scope_rule::*;
+pub use claims_access_rule::*;
+pub use login_throttle_policy::*;
+pub use magic_link_rule::*;
+pub use ownership_policy::*;
+pub use refresh_token_rule::*;
+pub use role_hierarchy_rule::*;
+pub use signing_key_rotation_policy::*;
+pub use token_stamp_rule::*;
+pub use user_access_rule::*;
@@ -0,0 +1,79 @@
+use rex_game_shared::domain::enums::permission_codes::PermissionCodes;
+
+/// Who may act on a resource that records its creator: holders of `any` on every one of
+/// them, holders of `own` only on those they created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnershipPolicy {
+    pub any: PermissionCodes,
+    pub own: PermissionCodes,
+}
+
+impl OwnershipPolicy {
+    pub const FLASHCARD_UPDATE: OwnershipPolicy = OwnershipPolicy {
+        any: PermissionCodes::FlashcardUpdate,
+        own: PermissionCodes::FlashcardUpdateOwn,
+    };
+    pub const FLASHCARD_DELETE: OwnershipPolicy = OwnershipPolicy {
+        any: PermissionCodes::FlashcardDelete,
+        own: PermissionCodes::FlashcardDeleteOwn,
+    };
+    pub const FLASHCARD_TYPE_UPDATE: OwnershipPolicy = OwnershipPolicy {
+        any: PermissionCodes::FlashcardTypeUpdate,
+        own: PermissionCodes::FlashcardTypeUpdateOwn,
+    };
+    pub const FLASHCARD_TYPE_DELETE: OwnershipPolicy = OwnershipPolicy {
+        any: PermissionCodes::FlashcardTypeDelete,
+        own: PermissionCodes::FlashcardTypeDeleteOwn,
+    };
+
+    /// The permission codes either of which lets a request reach the resource; ownership
+    /// is only known once it is loaded
+    pub fn codes(&self) -> Vec<String> {
+        vec![self.any.as_str().to_string(), self.own.as_str().to_string()]
+    }
+
+    /// Whether someone holding `permissions` may act on a resource created by `owner_id`.
+    /// Resources without a recorded creator belong to nobody, so only `any` reaches them.
+    pub fn allows(&self, permissions: &[String], user_id: i32, owner_id: Option<i32>) -> bool {
+        let holds = |code: PermissionCodes| permissions.iter().any(|p| p == code.as_str());
+        holds(self.any) || (holds(self.own) && owner_id == Some(user_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(values: &[PermissionCodes]) -> Vec<String> {
+        values.iter().map(|f| f.as_str().to_string()).collect()
+    }
+
+    #[test]
+    fn test_any_permission_reaches_every_resource() {
+        let permissions = codes(&[PermissionCodes::FlashcardUpdate]);
+        let policy = OwnershipPolicy::FLASHCARD_UPDATE;
+        assert!(policy.allows(&permissions, 1, Some(1)));
+        assert!(policy.allows(&permissions, 1, Some(2)));
+        assert!(policy.allows(&permissions, 1, None));
+    }
+
+    #[test]
+    fn test_own_permission_reaches_only_created_resources() {
+        let permissions = codes(&[PermissionCodes::FlashcardUpdateOwn]);
+        let policy = OwnershipPolicy::FLASHCARD_UPDATE;
+        assert!(policy.allows(&permissions, 1, Some(1)));
+        assert!(!policy.allows(&permissions, 1, Some(2)));
+        assert!(!policy.allows(&permissions, 1, None));
+    }
+
+    #[test]
+    fn test_permissions_do_not_carry_over_between_actions() {
+        let permissions = codes(&[
+            PermissionCodes::FlashcardUpdate,
+            PermissionCodes::FlashcardTypeDeleteOwn,
+        ]);
+        assert!(!OwnershipPolicy::FLASHCARD_DELETE.allows(&permissions, 1, Some(1)));
+        assert!(!OwnershipPolicy::FLASHCARD_TYPE_UPDATE.allows(&permissions, 1, Some(1)));
+        assert!(OwnershipPolicy::FLASHCARD_TYPE_DELETE.allows(&permissions, 1, Some(1)));
+    }
+}
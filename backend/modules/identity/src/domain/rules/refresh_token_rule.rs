@@ -0,0 +1,161 @@
+use rex_game_shared::domain::enums::user_token_porposes::UserTokenPurposes;
+
+use crate::domain::models::user_token_model::UserTokenModel;
+
+/// What a refresh token presented for rotation allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefreshTokenCheck {
+    /// The token is the live member of its family and can be rotated
+    Rotate { family_id: String },
+    /// The token was issued outside of a family and cannot be rotated
+    Unrecognized,
+    /// The whole family has to be revoked
+    RevokeFamily {
+        family_id: String,
+        reason: RefreshTokenRevocation,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshTokenRevocation {
+    /// The token was minted before the user's security stamp rotated
+    StampRotated,
+    /// The token was already rotated, so this is a copy coming back
+    Replayed,
+}
+
+pub struct RefreshTokenRule;
+
+impl RefreshTokenRule {
+    /// Whether a stored token is a refresh token issued to `user_id`.
+    pub fn belongs_to(user_token: &UserTokenModel, user_id: i32) -> bool {
+        user_token.user_id == user_id
+            && user_token.purpose == UserTokenPurposes::RefreshToken as i32
+    }
+
+    /// Checks a stored refresh token against the stamp it was minted with
+    /// (`token_stamp`) and the user's current `security_stamp`.
+    pub fn check(
+        user_token: &UserTokenModel,
+        token_stamp: Option<&str>,
+        security_stamp: &str,
+    ) -> RefreshTokenCheck {
+        let family_id = match &user_token.family_id {
+            Some(family_id) => family_id.clone(),
+            None => return RefreshTokenCheck::Unrecognized,
+        };
+
+        if token_stamp != Some(security_stamp) {
+            return RefreshTokenCheck::RevokeFamily {
+                family_id,
+                reason: RefreshTokenRevocation::StampRotated,
+            };
+        }
+
+        if !user_token.is_actived {
+            return RefreshTokenCheck::RevokeFamily {
+                family_id,
+                reason: RefreshTokenRevocation::Replayed,
+            };
+        }
+
+        RefreshTokenCheck::Rotate { family_id }
+    }
+
+    /// Finds session `session_id` among the active refresh tokens of `user_id`.
+    /// A session of another user is never found, so it cannot be revoked.
+    pub fn find_session<'a>(
+        active_tokens: &'a [UserTokenModel],
+        user_id: i32,
+        session_id: &str,
+    ) -> Option<&'a UserTokenModel> {
+        active_tokens.iter().find(|user_token| {
+            user_token.is_actived
+                && Self::belongs_to(user_token, user_id)
+                && user_token.family_id.as_deref() == Some(session_id)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refresh_token(user_id: i32, family_id: &str, is_actived: bool) -> UserTokenModel {
+        UserTokenModel {
+            id: 1,
+            user_id,
+            purpose: UserTokenPurposes::RefreshToken as i32,
+            family_id: Some(family_id.to_string()),
+            is_actived,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_live_token_is_rotated_within_its_family() {
+        let user_token = refresh_token(1, "family-a", true);
+        assert_eq!(
+            RefreshTokenRule::check(&user_token, Some("stamp"), "stamp"),
+            RefreshTokenCheck::Rotate {
+                family_id: "family-a".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_replayed_token_revokes_the_family() {
+        let rotated = refresh_token(1, "family-a", false);
+        assert_eq!(
+            RefreshTokenRule::check(&rotated, Some("stamp"), "stamp"),
+            RefreshTokenCheck::RevokeFamily {
+                family_id: "family-a".to_string(),
+                reason: RefreshTokenRevocation::Replayed,
+            }
+        );
+    }
+
+    #[test]
+    fn test_token_minted_before_stamp_rotated_revokes_the_family() {
+        let user_token = refresh_token(1, "family-a", true);
+        assert_eq!(
+            RefreshTokenRule::check(&user_token, Some("old-stamp"), "stamp"),
+            RefreshTokenCheck::RevokeFamily {
+                family_id: "family-a".to_string(),
+                reason: RefreshTokenRevocation::StampRotated,
+            }
+        );
+        assert_eq!(
+            RefreshTokenRule::check(&user_token, None, "stamp"),
+            RefreshTokenCheck::RevokeFamily {
+                family_id: "family-a".to_string(),
+                reason: RefreshTokenRevocation::StampRotated,
+            }
+        );
+    }
+
+    #[test]
+    fn test_token_belongs_only_to_its_user_and_purpose() {
+        let user_token = refresh_token(1, "family-a", true);
+        assert!(RefreshTokenRule::belongs_to(&user_token, 1));
+        assert!(!RefreshTokenRule::belongs_to(&user_token, 2));
+
+        let confirmation = UserTokenModel {
+            purpose: UserTokenPurposes::SignupConfirmation as i32,
+            ..refresh_token(1, "family-a", true)
+        };
+        assert!(!RefreshTokenRule::belongs_to(&confirmation, 1));
+    }
+
+    #[test]
+    fn test_only_live_sessions_of_the_user_are_found() {
+        let tokens = vec![
+            refresh_token(1, "family-a", true),
+            refresh_token(2, "family-b", true),
+            refresh_token(1, "family-c", false),
+        ];
+        assert!(RefreshTokenRule::find_session(&tokens, 1, "family-a").is_some());
+        assert!(RefreshTokenRule::find_session(&tokens, 1, "family-b").is_none());
+        assert!(RefreshTokenRule::find_session(&tokens, 1, "family-c").is_none());
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::{HashMap, HashSet};
+
+/// The role each active role inherits from, by role id.
+pub type RoleParents = HashMap<i32, Option<i32>>;
+
+/// How roles inherit from each other. `parents` maps each active role to the role it
+/// inherits from; a role missing from it inherits nothing.
+pub struct RoleHierarchyRule;
+
+impl RoleHierarchyRule {
+    /// The role followed by its parent, the parent's parent and so on. Stops at a role
+    /// that is not active, or where the chain loops back on itself.
+    pub fn lineage(role_id: i32, parents: &HashMap<i32, Option<i32>>) -> Vec<i32> {
+        let mut lineage = vec![role_id];
+        let mut current = role_id;
+        while let Some(Some(parent_id)) = parents.get(&current) {
+            if lineage.contains(parent_id) || !parents.contains_key(parent_id) {
+                break;
+            }
+            lineage.push(*parent_id);
+            current = *parent_id;
+        }
+        lineage
+    }
+
+    /// The roles and every role they inherit from
+    pub fn inherited_role_ids(role_ids: &[i32], parents: &HashMap<i32, Option<i32>>) -> Vec<i32> {
+        let mut inherited: Vec<i32> = role_ids
+            .iter()
+            .flat_map(|role_id| Self::lineage(*role_id, parents))
+            .collect();
+        inherited.sort();
+        inherited.dedup();
+        inherited
+    }
+
+    /// The roles and every role inheriting from them, i.e. the roles whose permissions
+    /// change when theirs do
+    pub fn inheriting_role_ids(role_ids: &[i32], parents: &HashMap<i32, Option<i32>>) -> Vec<i32> {
+        let targets: HashSet<i32> = role_ids.iter().copied().collect();
+        let mut inheriting: Vec<i32> = parents
+            .keys()
+            .copied()
+            .filter(|role_id| {
+                Self::lineage(*role_id, parents)
+                    .iter()
+                    .any(|ancestor_id| targets.contains(ancestor_id))
+            })
+            .chain(role_ids.iter().copied())
+            .collect();
+        inheriting.sort();
+        inheriting.dedup();
+        inheriting
+    }
+
+    /// For each role of the lineage in `targets`, the path from `role_id` up to it. Tells
+    /// through which inherited roles the role gets, say, a permission.
+    pub fn paths_to(
+        role_id: i32,
+        targets: &HashSet<i32>,
+        parents: &HashMap<i32, Option<i32>>,
+    ) -> Vec<Vec<i32>> {
+        let lineage = Self::lineage(role_id, parents);
+        (0..lineage.len())
+            .filter(|index| targets.contains(&lineage[*index]))
+            .map(|index| lineage[..=index].to_vec())
+            .collect()
+    }
+
+    /// Whether making `parent_id` the parent of `role_id` would have the role inherit
+    /// from itself
+    pub fn creates_cycle(
+        role_id: i32,
+        parent_id: i32,
+        parents: &HashMap<i32, Option<i32>>,
+    ) -> bool {
+        role_id == parent_id || Self::lineage(parent_id, parents).contains(&role_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1 Student <- 2 Assistant <- 3 Teacher, and 4 Guest on its own
+    fn parents() -> HashMap<i32, Option<i32>> {
+        HashMap::from([(1, None), (2, Some(1)), (3, Some(2)), (4, None)])
+    }
+
+    #[test]
+    fn test_lineage_walks_up_to_the_root_or_a_break() {
+        assert_eq!(RoleHierarchyRule::lineage(3, &parents()), vec![3, 2, 1]);
+        assert_eq!(RoleHierarchyRule::lineage(4, &parents()), vec![4]);
+
+        let mut inactive_parent = parents();
+        inactive_parent.remove(&2);
+        assert_eq!(RoleHierarchyRule::lineage(3, &inactive_parent), vec![3]);
+
+        let looped = HashMap::from([(1, Some(2)), (2, Some(1))]);
+        assert_eq!(RoleHierarchyRule::lineage(1, &looped), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_inherited_roles_include_every_ancestor_once() {
+        let inherited = RoleHierarchyRule::inherited_role_ids(&[2, 3, 4], &parents());
+        assert_eq!(inherited, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_inheriting_roles_include_every_descendant() {
+        let inheriting = RoleHierarchyRule::inheriting_role_ids(&[1], &parents());
+        assert_eq!(inheriting, vec![1, 2, 3]);
+
+        let inheriting = RoleHierarchyRule::inheriting_role_ids(&[3], &parents());
+        assert_eq!(inheriting, vec![3]);
+    }
+
+    #[test]
+    fn test_paths_lead_to_each_target_in_the_lineage() {
+        let targets = HashSet::from([1, 3, 4]);
+        let paths = RoleHierarchyRule::paths_to(3, &targets, &parents());
+        assert_eq!(paths, vec![vec![3], vec![3, 2, 1]]);
+
+        let paths = RoleHierarchyRule::paths_to(2, &HashSet::from([3]), &parents());
+        assert!(paths.is_empty());
+    }
+
+    #[test]
+    fn test_role_cannot_inherit_from_itself_or_a_descendant() {
+        assert!(RoleHierarchyRule::creates_cycle(1, 1, &parents()));
+        assert!(RoleHierarchyRule::creates_cycle(1, 3, &parents()));
+        assert!(!RoleHierarchyRule::creates_cycle(3, 4, &parents()));
+        assert!(!RoleHierarchyRule::creates_cycle(4, 3, &parents()));
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::models::signing_key_model::SigningKeyModel;
+
+/// When token signing keys are replaced, and how long the old ones keep verifying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SigningKeyRotationPolicy {
+    /// How long a key signs before a new one replaces it
+    pub rotation_secs: i64,
+    /// How long a new key is published before it signs, so every server knows it by then
+    pub propagation_secs: i64,
+    /// Longest lifetime of a token, which a replaced key keeps verifying for
+    pub token_lifetime_secs: i64,
+}
+
+impl Default for SigningKeyRotationPolicy {
+    fn default() -> Self {
+        Self {
+            rotation_secs: 30 * 24 * 60 * 60,
+            propagation_secs: 15 * 60,
+            token_lifetime_secs: 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+impl SigningKeyRotationPolicy {
+    /// Whether a new key is due, judged by the newest key that has not been replaced yet.
+    pub fn is_rotation_due(&self, signing_keys: &[SigningKeyModel], now: DateTime<Utc>) -> bool {
+        let newest_activates_on = signing_keys
+            .iter()
+            .filter(|f| f.expires_on.is_none())
+            .map(|f| f.activates_on)
+            .max();
+        match newest_activates_on {
+            Some(activates_on) => activates_on + Duration::seconds(self.rotation_secs) <= now,
+            None => true,
+        }
+    }
+
+    /// A new key waits until it has been published, unless nothing could sign meanwhile.
+    pub fn activates_on(
+        &self,
+        signing_keys: &[SigningKeyModel],
+        now: DateTime<Utc>,
+    ) -> DateTime<Utc> {
+        let can_sign = signing_keys.iter().any(|f| {
+            f.activates_on <= now && f.expires_on.is_none_or(|expires_on| expires_on > now)
+        });
+        if can_sign {
+            now + Duration::seconds(self.propagation_secs)
+        } else {
+            now
+        }
+    }
+
+    /// Keys replaced by a key activating then verify until the last token they signed expires.
+    pub fn replaced_key_expires_on(&self, new_key_activates_on: DateTime<Utc>) -> DateTime<Utc> {
+        new_key_activates_on + Duration::seconds(self.token_lifetime_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rex_game_shared::test_helpers::now;
+
+    fn key(activated_days_ago: i64, expires_in_days: Option<i64>) -> SigningKeyModel {
+        SigningKeyModel {
+            activates_on: now() - Duration::days(activated_days_ago),
+            expires_on: expires_in_days.map(|days| now() + Duration::days(days)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_rotation_is_due_when_no_current_key_is_recent() {
+        let policy = SigningKeyRotationPolicy::default();
+        assert!(policy.is_rotation_due(&[], now()));
+        assert!(!policy.is_rotation_due(&[key(29, None)], now()));
+        assert!(policy.is_rotation_due(&[key(30, None)], now()));
+        // Replaced keys do not count
+        assert!(policy.is_rotation_due(&[key(1, Some(7))], now()));
+    }
+
+    #[test]
+    fn test_pending_key_postpones_rotation() {
+        let policy = SigningKeyRotationPolicy::default();
+        assert!(!policy.is_rotation_due(&[key(40, Some(7)), key(-1, None)], now()));
+    }
+
+    #[test]
+    fn test_new_key_waits_for_propagation_unless_none_can_sign() {
+        let policy = SigningKeyRotationPolicy::default();
+        assert_eq!(
+            policy.activates_on(&[key(30, None)], now()),
+            now() + Duration::minutes(15)
+        );
+        assert_eq!(policy.activates_on(&[], now()), now());
+        // A key that has not activated yet cannot sign either
+        assert_eq!(policy.activates_on(&[key(-1, None)], now()), now());
+    }
+}
@@ -0,0 +1,75 @@
+/// Why an access token no longer matches its user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaleToken {
+    /// Issued before the security stamp rotated
+    SecurityStamp,
+    /// Carries roles or permissions that have since changed
+    AuthorizationVersion,
+}
+
+pub struct TokenStampRule;
+
+impl TokenStampRule {
+    /// Checks the security stamp a token was issued with against the user's current one.
+    /// Tokens without a stamp predate stamps and are refused as well.
+    pub fn check_security_stamp(
+        token_stamp: Option<&str>,
+        current_stamp: &str,
+    ) -> Result<(), StaleToken> {
+        if token_stamp != Some(current_stamp) {
+            return Err(StaleToken::SecurityStamp);
+        }
+
+        Ok(())
+    }
+
+    /// Checks the authorization version a token was issued with against the user's
+    /// current one. Tokens without a version are refused as well.
+    pub fn check_authorization_version(
+        token_version: Option<i32>,
+        current_version: i32,
+    ) -> Result<(), StaleToken> {
+        if token_version != Some(current_version) {
+            return Err(StaleToken::AuthorizationVersion);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_must_carry_the_current_stamp() {
+        assert_eq!(
+            TokenStampRule::check_security_stamp(Some("stamp"), "stamp"),
+            Ok(())
+        );
+        assert_eq!(
+            TokenStampRule::check_security_stamp(Some("old-stamp"), "stamp"),
+            Err(StaleToken::SecurityStamp)
+        );
+        assert_eq!(
+            TokenStampRule::check_security_stamp(None, "stamp"),
+            Err(StaleToken::SecurityStamp)
+        );
+    }
+
+    #[test]
+    fn test_token_must_carry_the_current_authorization_version() {
+        assert_eq!(
+            TokenStampRule::check_authorization_version(Some(3), 3),
+            Ok(())
+        );
+        assert_eq!(
+            TokenStampRule::check_authorization_version(Some(2), 3),
+            Err(StaleToken::AuthorizationVersion)
+        );
+        assert_eq!(
+            TokenStampRule::check_authorization_version(None, 3),
+            Err(StaleToken::AuthorizationVersion)
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use rex_game_shared::domain::enums::user_statuses::UserStatuses;
+
+use crate::domain::models::user_status_model::UserStatusModel;
+
+/// Whether a user may sign in and use the API, given their status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserAccess {
+    Allowed,
+    /// The suspension has run out; the user is let in once it is lifted
+    SuspensionExpired,
+    /// The sign-up has not been confirmed yet
+    NotConfirmed,
+    /// Deleted, or in a status this version does not know
+    Removed,
+    /// Suspended until `until`, or until reinstated when there is none
+    Suspended {
+        reason: Option<String>,
+        until: Option<DateTime<Utc>>,
+    },
+    Banned {
+        reason: Option<String>,
+    },
+}
+
+pub struct UserAccessRule;
+
+impl UserAccessRule {
+    pub fn check(status: &UserStatusModel, now: DateTime<Utc>) -> UserAccess {
+        match UserStatuses::try_from(status.status_id) {
+            Ok(UserStatuses::Actived) => UserAccess::Allowed,
+            Ok(UserStatuses::Pending) => UserAccess::NotConfirmed,
+            Ok(UserStatuses::Suspended) => match status.status_expires_on {
+                Some(expires_on) if expires_on <= now => UserAccess::SuspensionExpired,
+                until => UserAccess::Suspended {
+                    reason: status.status_reason.clone(),
+                    until,
+                },
+            },
+            Ok(UserStatuses::Banned) => UserAccess::Banned {
+                reason: status.status_reason.clone(),
+            },
+            Ok(UserStatuses::Deleted) | Err(_) => UserAccess::Removed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use rex_game_shared::test_helpers::now;
+
+    fn status(status: i32, expires_in_secs: Option<i64>) -> UserStatusModel {
+        UserStatusModel {
+            user_id: 1,
+            status_id: status,
+            status_reason: Some("Cheating".to_string()),
+            status_expires_on: expires_in_secs.map(|secs| now() + Duration::seconds(secs)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_access_follows_the_account_status() {
+        let check = |status_id: i32| UserAccessRule::check(&status(status_id, None), now());
+        assert_eq!(check(UserStatuses::Actived as i32), UserAccess::Allowed);
+        assert_eq!(
+            check(UserStatuses::Pending as i32),
+            UserAccess::NotConfirmed
+        );
+        assert_eq!(check(UserStatuses::Deleted as i32), UserAccess::Removed);
+        assert_eq!(check(99), UserAccess::Removed);
+    }
+
+    #[test]
+    fn test_suspended_user_is_denied_until_expiry() {
+        let access =
+            UserAccessRule::check(&status(UserStatuses::Suspended as i32, Some(60)), now());
+        assert_eq!(
+            access,
+            UserAccess::Suspended {
+                reason: Some("Cheating".to_string()),
+                until: Some(now() + Duration::seconds(60)),
+            }
+        );
+
+        let access = UserAccessRule::check(&status(UserStatuses::Suspended as i32, Some(0)), now());
+        assert_eq!(access, UserAccess::SuspensionExpired);
+    }
+
+    #[test]
+    fn test_banned_user_is_denied_even_with_expiry() {
+        let access = UserAccessRule::check(&status(UserStatuses::Banned as i32, Some(-60)), now());
+        assert_eq!(
+            access,
+            UserAccess::Banned {
+                reason: Some("Cheating".to_string())
+            }
+        );
+    }
+}
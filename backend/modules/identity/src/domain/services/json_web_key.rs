@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Public key in the JSON Web Key format of RFC 7517, as published in a JWK set
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct JsonWebKey {
+    pub kty: String,
+    pub kid: String,
+    pub alg: String,
+    #[serde(rename = "use")]
+    pub key_use: String,
+    /// Curve of an `OKP` key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    /// Public key of an `OKP` key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    /// Modulus of an `RSA` key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    /// Exponent of an `RSA` key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+}
@@ -1,9 +1,19 @@
+pub mod json_web_key;
+pub mod oidc_client_trait;
+pub mod oidc_types;
 pub mod password_hasher_trait;
+pub mod signing_keyring_trait;
 pub mod token_helper_trait;
 pub mod token_types;
+pub mod totp_helper_trait;
 
+pub use json_web_key::JsonWebKey;
+pub use oidc_client_trait::OidcClientTrait;
+pub use oidc_types::{OidcAuthorization, OidcIdentity, OidcProviderInfo};
 pub use password_hasher_trait::PasswordHasherTrait;
+pub use signing_keyring_trait::SigningKeyringTrait;
 pub use token_helper_trait::TokenHelperTrait;
+pub use totp_helper_trait::TotpHelperTrait;
 pub use token_types::{
     AccessTokenResult, TokenGenerationOptions, TokenGenerationResult, TokenValidationResult,
 };
@@ -0,0 +1,24 @@
+use rex_game_shared::ApplicationError;
+use std::future::Future;
+
+use super::oidc_types::{OidcAuthorization, OidcIdentity, OidcProviderInfo};
+
+/// OpenID Connect relying party, using the authorization code flow with PKCE
+pub trait OidcClientTrait {
+    fn get_providers(&self) -> Vec<OidcProviderInfo>;
+    /// Starts a sign-in at the provider. `link_user_id` marks a flow that links the provider
+    /// account to a signed-in user instead.
+    fn begin_authorization(
+        &self,
+        provider: &str,
+        link_user_id: Option<i32>,
+    ) -> impl Future<Output = Result<OidcAuthorization, ApplicationError>>;
+    /// Checks the state against the flow token, redeems the code and validates the ID token
+    fn complete_authorization(
+        &self,
+        provider: &str,
+        code: &str,
+        state: &str,
+        flow_token: &str,
+    ) -> impl Future<Output = Result<OidcIdentity, ApplicationError>>;
+}
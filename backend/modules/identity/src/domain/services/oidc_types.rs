@@ -0,0 +1,25 @@
+pub struct OidcProviderInfo {
+    /// Name used in URLs, e.g. `google`
+    pub name: String,
+    /// Name shown on the sign-in button
+    pub display_name: String,
+}
+
+pub struct OidcAuthorization {
+    /// Where to send the browser to sign in at the provider
+    pub authorization_url: String,
+    /// Signed state, nonce and PKCE verifier of this flow. It has to come back with the
+    /// callback and must not end up in a URL.
+    pub flow_token: String,
+}
+
+/// The account at the provider, taken from a validated ID token
+pub struct OidcIdentity {
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub name: Option<String>,
+    /// Set when the flow was started to link the account to this user
+    pub link_user_id: Option<i32>,
+}
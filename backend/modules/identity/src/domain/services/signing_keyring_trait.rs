@@ -0,0 +1,13 @@
+use crate::domain::models::signing_key_model::SigningKeyModel;
+use rex_game_shared::ApplicationError;
+
+use super::json_web_key::JsonWebKey;
+
+/// Keys in memory that sign and verify tokens
+pub trait SigningKeyringTrait {
+    /// Creates a key pair. The caller decides when it activates.
+    fn generate_key(&self, algorithm: &str) -> Result<SigningKeyModel, ApplicationError>;
+    /// Replaces the keys in use with the given ones
+    fn load(&self, signing_keys: Vec<SigningKeyModel>) -> Result<(), ApplicationError>;
+    fn public_keys(&self) -> Vec<JsonWebKey>;
+}
@@ -4,12 +4,15 @@ use super::token_types::{TokenGenerationOptions, TokenGenerationResult, TokenVal
 
 pub trait TokenHelperTrait {
     fn generate_token(&self, options: TokenGenerationOptions) -> Option<TokenGenerationResult>;
-    fn refresh_access_token(
+    /// Checks that the refresh token was issued with the access token, which may have expired,
+    /// and returns the access token's claims
+    fn validate_refresh_token(
         &self,
-        token: &str,
+        access_token: &str,
         refresh_token: &str,
-        refresh_expiration: i64,
-    ) -> Option<TokenGenerationResult>;
+    ) -> Option<TokenValidationResult>;
     fn validate_token(&self, access_token: &str)
         -> Result<TokenValidationResult, ApplicationError>;
+    /// One-way digest used to store issued tokens without keeping them usable
+    fn hash_token(&self, token: &str) -> String;
 }
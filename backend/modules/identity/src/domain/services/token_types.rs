@@ -6,6 +6,10 @@ pub struct TokenValidationResult {
     pub token_type: String,
     pub iat: Option<i64>,
     pub jti: String,
+    pub security_stamp: Option<String>,
+    pub permissions: Vec<String>,
+    pub roles: Vec<String>,
+    pub authorization_version: Option<i32>,
 }
 
 pub struct TokenGenerationResult {
@@ -34,4 +38,8 @@ pub struct TokenGenerationOptions {
     // Include permissions and roles to avoid database queries on every request
     pub permissions: Vec<String>,
     pub roles: Vec<String>,
+    // Tokens stop being accepted once the user's stamp rotates
+    pub security_stamp: Option<String>,
+    // Access tokens stop being accepted once the user's roles or permissions change
+    pub authorization_version: Option<i32>,
 }
@@ -0,0 +1,11 @@
+pub trait TotpHelperTrait {
+    /// New random shared secret, base32 encoded
+    fn generate_secret(&self) -> String;
+    /// `otpauth://` URI authenticator apps read from a QR code
+    fn provisioning_uri(&self, secret: &str, account_name: &str) -> String;
+    /// Time step the code belongs to, when it is valid at `timestamp` give or take one step
+    fn verify_code(&self, secret: &str, code: &str, timestamp: i64) -> Option<i64>;
+    fn generate_recovery_codes(&self) -> Vec<String>;
+    /// Canonical form of a recovery code, so dashes, spaces and case do not matter
+    fn normalize_recovery_code(&self, code: &str) -> String;
+}
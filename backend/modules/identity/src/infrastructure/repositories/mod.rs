@@ -1,15 +1,27 @@
 pub mod permission_repository;
 pub mod role_permission_repository;
 pub mod role_repository;
+pub mod signing_key_repository;
+pub mod user_api_key_repository;
+pub mod user_external_login_repository;
+pub mod user_login_throttle_repository;
+pub mod user_mfa_repository;
 pub mod user_permission_repository;
 pub mod user_repository;
 pub mod user_role_repository;
+pub mod user_status_repository;
 pub mod user_token_repository;
 
 pub use permission_repository::PermissionRepository;
 pub use role_permission_repository::RolePermissionRepository;
 pub use role_repository::RoleRepository;
+pub use signing_key_repository::SigningKeyRepository;
+pub use user_api_key_repository::UserApiKeyRepository;
+pub use user_external_login_repository::UserExternalLoginRepository;
+pub use user_login_throttle_repository::UserLoginThrottleRepository;
+pub use user_mfa_repository::UserMfaRepository;
 pub use user_permission_repository::UserPermissionRepository;
 pub use user_repository::UserRepository;
 pub use user_role_repository::UserRoleRepository;
+pub use user_status_repository::UserStatusRepository;
 pub use user_token_repository::UserTokenRepository;
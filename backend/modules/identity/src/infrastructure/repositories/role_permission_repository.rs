@@ -218,6 +218,21 @@ impl RolePermissionRepositoryTrait for RolePermissionRepository {
         })
     }
 
+    async fn get_role_ids_by_permission_id(
+        &self,
+        permission_id: i32,
+    ) -> Result<Vec<i32>, InfraError> {
+        let db = self._db_connection.as_ref();
+        RolePermission::find()
+            .filter(role_permission::Column::PermissionId.eq(permission_id))
+            .select_only()
+            .column(role_permission::Column::RoleId)
+            .into_tuple::<i32>()
+            .all(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))
+    }
+
     async fn create_many(
         &self,
         role_permission_req: Vec<RolePermissionModel>,
@@ -4,12 +4,14 @@ use crate::domain::{
 };
 use chrono::Utc;
 use rex_game_shared::domain::models::page_list_model::PageListModel;
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
+use rex_game_shared::infrastructure::database::SeaOrmTransactionWrapper;
 use rex_game_shared::InfraError;
 use sea_orm::{
-    ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
-    QueryOrder, Set,
+    sea_query::Expr, ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set,
 };
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 #[derive(Clone)]
 pub struct RoleRepository {
@@ -31,6 +33,7 @@ impl RoleRepositoryTrait for RoleRepository {
         let role = role::ActiveModel {
             name: Set(role_req.name),
             description: Set(role_req.description),
+            parent_id: Set(role_req.parent_id),
             created_by_id: Set(role_req.created_by_id),
             updated_by_id: Set(role_req.updated_by_id),
             created_on: Set(Utc::now().fixed_offset()),
@@ -170,6 +173,42 @@ impl RoleRepositoryTrait for RoleRepository {
         }
     }
 
+    async fn get_parents(&self) -> Result<HashMap<i32, Option<i32>>, InfraError> {
+        let db = self._db_connection.as_ref();
+        let roles = Role::find()
+            .filter(role::Column::IsActived.eq(true))
+            .select_only()
+            .columns([role::Column::Id, role::Column::ParentId])
+            .into_tuple::<(i32, Option<i32>)>()
+            .all(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(roles.into_iter().collect())
+    }
+
+    async fn get_parents_with_transaction(
+        &self,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<HashMap<i32, Option<i32>>, InfraError> {
+        let it = transaction.as_ref().as_any();
+        let transact = match it.downcast_ref::<SeaOrmTransactionWrapper>() {
+            Some(i) => i,
+            None => return Err(InfraError::database("Unable to cast the transaction")),
+        };
+        let roles = Role::find()
+            .filter(role::Column::IsActived.eq(true))
+            .select_only()
+            .columns([role::Column::Id, role::Column::ParentId])
+            .lock_exclusive()
+            .into_tuple::<(i32, Option<i32>)>()
+            .all(transact.txn.as_ref().unwrap())
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(roles.into_iter().collect())
+    }
+
     async fn update(&self, role_req: RoleModel) -> Result<bool, InfraError> {
         let db = self._db_connection.as_ref();
         let existing = Role::find_by_id(role_req.id).one(db).await;
@@ -191,7 +230,9 @@ impl RoleRepositoryTrait for RoleRepository {
         role.updated_by_id = Set(role_req.updated_by_id);
         role.description = Set(role_req.description);
         role.is_actived = Set(role_req.is_actived);
+        role.requires_mfa = Set(role_req.requires_mfa);
         role.name = Set(role_req.name);
+        role.parent_id = Set(role_req.parent_id);
         role.updated_on = Set(Utc::now().fixed_offset());
 
         match Role::update(role).exec(db).await {
@@ -199,6 +240,33 @@ impl RoleRepositoryTrait for RoleRepository {
             Err(err) => Err(InfraError::database(err.to_string().as_str())),
         }
     }
+
+    async fn update_parent_without_commit(
+        &self,
+        id: i32,
+        parent_id: Option<i32>,
+        updated_by_id: i32,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<bool, InfraError> {
+        let it = transaction.as_ref().as_any();
+        let transact = match it.downcast_ref::<SeaOrmTransactionWrapper>() {
+            Some(i) => i,
+            None => return Err(InfraError::database("Unable to cast the transaction")),
+        };
+        let updated = Role::update_many()
+            .col_expr(role::Column::ParentId, Expr::value(parent_id))
+            .col_expr(role::Column::UpdatedById, Expr::value(updated_by_id))
+            .col_expr(
+                role::Column::UpdatedOn,
+                Expr::value(Utc::now().fixed_offset()),
+            )
+            .filter(role::Column::Id.eq(id))
+            .exec(transact.txn.as_ref().unwrap())
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(updated.rows_affected > 0)
+    }
 }
 
 fn map_entity_to_model(role: role::Model) -> RoleModel {
@@ -211,5 +279,7 @@ fn map_entity_to_model(role: role::Model) -> RoleModel {
         created_by_id: role.created_by_id,
         updated_by_id: role.updated_by_id,
         is_actived: role.is_actived,
+        requires_mfa: role.requires_mfa,
+        parent_id: role.parent_id,
     }
 }
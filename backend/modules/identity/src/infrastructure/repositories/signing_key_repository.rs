@@ -0,0 +1,97 @@
+use crate::domain::{
+    models::signing_key_model::SigningKeyModel,
+    repositories::signing_key_repository_trait::SigningKeyRepositoryTrait,
+};
+use chrono::{DateTime, Utc};
+use rex_game_entities::entities::signing_key::{self, Entity as SigningKey};
+use rex_game_shared::InfraError;
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct SigningKeyRepository {
+    _db_connection: Arc<DatabaseConnection>,
+}
+
+impl SigningKeyRepository {
+    pub fn new(db_connection: Arc<DatabaseConnection>) -> Self {
+        Self {
+            _db_connection: db_connection,
+        }
+    }
+
+    fn to_model(f: signing_key::Model) -> SigningKeyModel {
+        SigningKeyModel {
+            id: f.id,
+            kid: f.kid,
+            algorithm: f.algorithm,
+            private_key: f.private_key,
+            public_key: f.public_key,
+            created_on: f.created_on.with_timezone(&Utc),
+            activates_on: f.activates_on.with_timezone(&Utc),
+            expires_on: f
+                .expires_on
+                .map(|expires_on| expires_on.with_timezone(&Utc)),
+        }
+    }
+}
+
+impl SigningKeyRepositoryTrait for SigningKeyRepository {
+    async fn get_valid(&self, now: DateTime<Utc>) -> Result<Vec<SigningKeyModel>, InfraError> {
+        let db = self._db_connection.as_ref();
+        let existing = SigningKey::find()
+            .filter(
+                Condition::any()
+                    .add(signing_key::Column::ExpiresOn.is_null())
+                    .add(signing_key::Column::ExpiresOn.gt(now.fixed_offset())),
+            )
+            .order_by_desc(signing_key::Column::ActivatesOn)
+            .all(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(existing.into_iter().map(Self::to_model).collect())
+    }
+
+    async fn create(&self, signing_key: SigningKeyModel) -> Result<i32, InfraError> {
+        let db = self._db_connection.as_ref();
+        let new_signing_key = signing_key::ActiveModel {
+            kid: Set(signing_key.kid),
+            algorithm: Set(signing_key.algorithm),
+            private_key: Set(signing_key.private_key),
+            public_key: Set(signing_key.public_key),
+            created_on: Set(Utc::now().fixed_offset()),
+            activates_on: Set(signing_key.activates_on.fixed_offset()),
+            expires_on: Set(signing_key.expires_on.map(|f| f.fixed_offset())),
+            ..Default::default()
+        };
+
+        let inserted = SigningKey::insert(new_signing_key)
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+        Ok(inserted.last_insert_id)
+    }
+
+    async fn expire_replaced(
+        &self,
+        activates_before: DateTime<Utc>,
+        expires_on: DateTime<Utc>,
+    ) -> Result<u64, InfraError> {
+        let db = self._db_connection.as_ref();
+        let result = SigningKey::update_many()
+            .col_expr(
+                signing_key::Column::ExpiresOn,
+                Expr::value(expires_on.fixed_offset()),
+            )
+            .filter(signing_key::Column::ExpiresOn.is_null())
+            .filter(signing_key::Column::ActivatesOn.lt(activates_before.fixed_offset()))
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,139 @@
+use crate::domain::{
+    models::user_api_key_model::UserApiKeyModel,
+    repositories::user_api_key_repository_trait::UserApiKeyRepositoryTrait,
+};
+use chrono::{DateTime, Utc};
+use rex_game_entities::entities::user_api_key::{self, Entity as UserApiKey};
+use rex_game_shared::InfraError;
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct UserApiKeyRepository {
+    _db_connection: Arc<DatabaseConnection>,
+}
+
+impl UserApiKeyRepository {
+    pub fn new(db_connection: Arc<DatabaseConnection>) -> Self {
+        Self {
+            _db_connection: db_connection,
+        }
+    }
+
+    fn to_model(f: user_api_key::Model) -> UserApiKeyModel {
+        UserApiKeyModel {
+            id: f.id,
+            user_id: f.user_id,
+            name: f.name,
+            key_prefix: f.key_prefix,
+            key_hash: f.key_hash,
+            // Stored space separated, like OAuth scopes
+            scopes: f.scopes.split_whitespace().map(String::from).collect(),
+            created_by_id: f.created_by_id,
+            created_on: f.created_on.with_timezone(&Utc),
+            expires_on: f
+                .expires_on
+                .map(|expires_on| expires_on.with_timezone(&Utc)),
+            last_used_on: f.last_used_on.map(|used_on| used_on.with_timezone(&Utc)),
+            revoked_on: f
+                .revoked_on
+                .map(|revoked_on| revoked_on.with_timezone(&Utc)),
+        }
+    }
+}
+
+impl UserApiKeyRepositoryTrait for UserApiKeyRepository {
+    async fn get_by_hash(&self, key_hash: &str) -> Result<Option<UserApiKeyModel>, InfraError> {
+        let db = self._db_connection.as_ref();
+        let existing = UserApiKey::find()
+            .filter(user_api_key::Column::KeyHash.eq(key_hash))
+            .one(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(existing.map(Self::to_model))
+    }
+
+    async fn get_by_user_id(&self, user_id: i32) -> Result<Vec<UserApiKeyModel>, InfraError> {
+        let db = self._db_connection.as_ref();
+        let existing = UserApiKey::find()
+            .filter(user_api_key::Column::UserId.eq(user_id))
+            .filter(user_api_key::Column::RevokedOn.is_null())
+            .order_by_desc(user_api_key::Column::CreatedOn)
+            .all(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(existing.into_iter().map(Self::to_model).collect())
+    }
+
+    async fn create(&self, api_key: UserApiKeyModel) -> Result<i32, InfraError> {
+        let db = self._db_connection.as_ref();
+        let new_api_key = user_api_key::ActiveModel {
+            user_id: Set(api_key.user_id),
+            name: Set(api_key.name),
+            key_prefix: Set(api_key.key_prefix),
+            key_hash: Set(api_key.key_hash),
+            scopes: Set(api_key.scopes.join(" ")),
+            created_by_id: Set(api_key.created_by_id),
+            created_on: Set(Utc::now().fixed_offset()),
+            expires_on: Set(api_key.expires_on.map(|f| f.fixed_offset())),
+            ..Default::default()
+        };
+
+        let inserted = UserApiKey::insert(new_api_key)
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+        Ok(inserted.last_insert_id)
+    }
+
+    async fn mark_used(
+        &self,
+        id: i32,
+        used_on: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+    ) -> Result<(), InfraError> {
+        let db = self._db_connection.as_ref();
+        UserApiKey::update_many()
+            .col_expr(
+                user_api_key::Column::LastUsedOn,
+                Expr::value(used_on.fixed_offset()),
+            )
+            .filter(user_api_key::Column::Id.eq(id))
+            .filter(
+                Condition::any()
+                    .add(user_api_key::Column::LastUsedOn.is_null())
+                    .add(user_api_key::Column::LastUsedOn.lt(stale_before.fixed_offset())),
+            )
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+        Ok(())
+    }
+
+    async fn revoke(
+        &self,
+        user_id: i32,
+        id: i32,
+        revoked_on: DateTime<Utc>,
+    ) -> Result<bool, InfraError> {
+        let db = self._db_connection.as_ref();
+        let result = UserApiKey::update_many()
+            .col_expr(
+                user_api_key::Column::RevokedOn,
+                Expr::value(revoked_on.fixed_offset()),
+            )
+            .filter(user_api_key::Column::Id.eq(id))
+            .filter(user_api_key::Column::UserId.eq(user_id))
+            .filter(user_api_key::Column::RevokedOn.is_null())
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -0,0 +1,149 @@
+use crate::domain::{
+    models::user_external_login_model::UserExternalLoginModel,
+    repositories::user_external_login_repository_trait::UserExternalLoginRepositoryTrait,
+};
+use chrono::{DateTime, Utc};
+use rex_game_entities::entities::user_external_login::{self, Entity as UserExternalLogin};
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
+use rex_game_shared::infrastructure::database::SeaOrmTransactionWrapper;
+use rex_game_shared::InfraError;
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    Set,
+};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct UserExternalLoginRepository {
+    _db_connection: Arc<DatabaseConnection>,
+}
+
+impl UserExternalLoginRepository {
+    pub fn new(db_connection: Arc<DatabaseConnection>) -> Self {
+        Self {
+            _db_connection: db_connection,
+        }
+    }
+
+    fn to_model(f: user_external_login::Model) -> UserExternalLoginModel {
+        UserExternalLoginModel {
+            id: f.id,
+            user_id: f.user_id,
+            provider: f.provider,
+            subject: f.subject,
+            email: f.email,
+            created_on: f.created_on.with_timezone(&Utc),
+            last_used_on: f.last_used_on.map(|used_on| used_on.with_timezone(&Utc)),
+        }
+    }
+
+    async fn insert<C: ConnectionTrait>(
+        db: &C,
+        external_login: UserExternalLoginModel,
+    ) -> Result<bool, InfraError> {
+        let new_external_login = user_external_login::ActiveModel {
+            user_id: Set(external_login.user_id),
+            provider: Set(external_login.provider),
+            subject: Set(external_login.subject),
+            email: Set(external_login.email),
+            created_on: Set(Utc::now().fixed_offset()),
+            last_used_on: Set(external_login.last_used_on.map(|f| f.fixed_offset())),
+            ..Default::default()
+        };
+
+        // Either unique index may be hit, so the conflict has no target
+        let inserted = UserExternalLogin::insert(new_external_login)
+            .on_conflict(OnConflict::new().do_nothing().to_owned())
+            .exec(db)
+            .await;
+        match inserted {
+            Ok(_) => Ok(true),
+            Err(DbErr::RecordNotInserted) => Ok(false),
+            Err(err) => Err(InfraError::database(err.to_string())),
+        }
+    }
+}
+
+impl UserExternalLoginRepositoryTrait for UserExternalLoginRepository {
+    async fn get_by_subject(
+        &self,
+        provider: &str,
+        subject: &str,
+    ) -> Result<Option<UserExternalLoginModel>, InfraError> {
+        let db = self._db_connection.as_ref();
+        let existing = UserExternalLogin::find()
+            .filter(user_external_login::Column::Provider.eq(provider))
+            .filter(user_external_login::Column::Subject.eq(subject))
+            .one(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(existing.map(Self::to_model))
+    }
+
+    async fn get_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Result<Vec<UserExternalLoginModel>, InfraError> {
+        let db = self._db_connection.as_ref();
+        let existing = UserExternalLogin::find()
+            .filter(user_external_login::Column::UserId.eq(user_id))
+            .order_by_asc(user_external_login::Column::Provider)
+            .all(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(existing.into_iter().map(Self::to_model).collect())
+    }
+
+    async fn create(&self, external_login: UserExternalLoginModel) -> Result<bool, InfraError> {
+        let db = self._db_connection.as_ref();
+        Self::insert(db, external_login).await
+    }
+
+    async fn create_without_commit(
+        &self,
+        external_login: UserExternalLoginModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<bool, InfraError> {
+        let it = transaction.as_ref().as_any();
+        let transact = match it.downcast_ref::<SeaOrmTransactionWrapper>() {
+            Some(i) => i,
+            None => return Err(InfraError::database("Unable to cast the transaction")),
+        };
+        Self::insert(transact.txn.as_ref().unwrap(), external_login).await
+    }
+
+    async fn mark_used(
+        &self,
+        id: i32,
+        email: Option<String>,
+        used_on: DateTime<Utc>,
+    ) -> Result<(), InfraError> {
+        let db = self._db_connection.as_ref();
+        UserExternalLogin::update_many()
+            .col_expr(user_external_login::Column::Email, Expr::value(email))
+            .col_expr(
+                user_external_login::Column::LastUsedOn,
+                Expr::value(used_on.fixed_offset()),
+            )
+            .filter(user_external_login::Column::Id.eq(id))
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: i32, provider: &str) -> Result<bool, InfraError> {
+        let db = self._db_connection.as_ref();
+        let result = UserExternalLogin::delete_many()
+            .filter(user_external_login::Column::UserId.eq(user_id))
+            .filter(user_external_login::Column::Provider.eq(provider))
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -0,0 +1,147 @@
+use crate::domain::{
+    models::user_login_throttle_model::UserLoginThrottleModel,
+    repositories::user_login_throttle_repository_trait::UserLoginThrottleRepositoryTrait,
+};
+use chrono::{DateTime, FixedOffset, Utc};
+use rex_game_entities::entities::{user, user::Entity as User};
+use rex_game_shared::InfraError;
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, Condition, DatabaseConnection, EntityTrait, ExprTrait,
+    QueryFilter,
+};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct UserLoginThrottleRepository {
+    _db_connection: Arc<DatabaseConnection>,
+}
+
+impl UserLoginThrottleRepository {
+    pub fn new(db_connection: Arc<DatabaseConnection>) -> Self {
+        Self {
+            _db_connection: db_connection,
+        }
+    }
+}
+
+impl UserLoginThrottleRepositoryTrait for UserLoginThrottleRepository {
+    async fn get_by_user_id(&self, user_id: i32) -> Result<UserLoginThrottleModel, InfraError> {
+        let db = self._db_connection.as_ref();
+        let existing = User::find_by_id(user_id)
+            .one(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        match existing {
+            Some(f) => Ok(UserLoginThrottleModel {
+                user_id: f.id,
+                failed_login_count: f.failed_login_count,
+                last_failed_login_on: f
+                    .last_failed_login_on
+                    .map(|failed_on| failed_on.with_timezone(&Utc)),
+                locked_until: f
+                    .locked_until
+                    .map(|locked_until| locked_until.with_timezone(&Utc)),
+            }),
+            None => Err(InfraError::not_found("User", user_id.to_string())),
+        }
+    }
+
+    async fn record_failure(
+        &self,
+        user_id: i32,
+        failed_on: DateTime<Utc>,
+    ) -> Result<i32, InfraError> {
+        let db = self._db_connection.as_ref();
+        // Incremented in the database, so concurrent failures are all counted
+        let updated = User::update_many()
+            .col_expr(
+                user::Column::FailedLoginCount,
+                Expr::col(user::Column::FailedLoginCount).add(1),
+            )
+            .col_expr(
+                user::Column::LastFailedLoginOn,
+                Expr::value(failed_on.fixed_offset()),
+            )
+            .filter(user::Column::Id.eq(user_id))
+            .exec_with_returning(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        match updated.first() {
+            Some(f) => Ok(f.failed_login_count),
+            None => Err(InfraError::not_found("User", user_id.to_string())),
+        }
+    }
+
+    async fn lock(
+        &self,
+        user_id: i32,
+        locked_until: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, InfraError> {
+        let db = self._db_connection.as_ref();
+        let result = User::update_many()
+            .col_expr(
+                user::Column::LockedUntil,
+                Expr::value(locked_until.fixed_offset()),
+            )
+            .col_expr(user::Column::FailedLoginCount, Expr::value(0))
+            .filter(user::Column::Id.eq(user_id))
+            .filter(
+                Condition::any()
+                    .add(user::Column::LockedUntil.is_null())
+                    .add(user::Column::LockedUntil.lte(now.fixed_offset())),
+            )
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn reset(&self, user_id: i32) -> Result<(), InfraError> {
+        let db = self._db_connection.as_ref();
+        User::update_many()
+            .col_expr(user::Column::FailedLoginCount, Expr::value(0))
+            .col_expr(
+                user::Column::LastFailedLoginOn,
+                Expr::value(Option::<DateTime<FixedOffset>>::None),
+            )
+            .col_expr(
+                user::Column::LockedUntil,
+                Expr::value(Option::<DateTime<FixedOffset>>::None),
+            )
+            .filter(user::Column::Id.eq(user_id))
+            .filter(
+                Condition::any()
+                    .add(user::Column::FailedLoginCount.gt(0))
+                    .add(user::Column::LockedUntil.is_not_null()),
+            )
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+        Ok(())
+    }
+
+    async fn unlock(&self, user_id: i32, now: DateTime<Utc>) -> Result<bool, InfraError> {
+        let db = self._db_connection.as_ref();
+        let result = User::update_many()
+            .col_expr(user::Column::FailedLoginCount, Expr::value(0))
+            .col_expr(
+                user::Column::LastFailedLoginOn,
+                Expr::value(Option::<DateTime<FixedOffset>>::None),
+            )
+            .col_expr(
+                user::Column::LockedUntil,
+                Expr::value(Option::<DateTime<FixedOffset>>::None),
+            )
+            .filter(user::Column::Id.eq(user_id))
+            .filter(user::Column::LockedUntil.gt(now.fixed_offset()))
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
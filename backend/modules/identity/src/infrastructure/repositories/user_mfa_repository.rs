@@ -0,0 +1,269 @@
+use crate::domain::{
+    models::user_mfa_model::UserMfaModel,
+    repositories::user_mfa_repository_trait::UserMfaRepositoryTrait,
+};
+use chrono::{DateTime, FixedOffset, Utc};
+use rex_game_entities::entities::{
+    role, user,
+    user::Entity as User,
+    user_recovery_code::{self, Entity as UserRecoveryCode},
+    user_role,
+};
+use rex_game_shared::InfraError;
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    JoinType, PaginatorTrait, QueryFilter, QuerySelect, RelationTrait, Set, TransactionTrait,
+};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct UserMfaRepository {
+    _db_connection: Arc<DatabaseConnection>,
+}
+
+impl UserMfaRepository {
+    pub fn new(db_connection: Arc<DatabaseConnection>) -> Self {
+        Self {
+            _db_connection: db_connection,
+        }
+    }
+}
+
+impl UserMfaRepositoryTrait for UserMfaRepository {
+    async fn get_by_user_id(&self, user_id: i32) -> Result<UserMfaModel, InfraError> {
+        let db = self._db_connection.as_ref();
+        let existing = User::find_by_id(user_id)
+            .one(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        match existing {
+            Some(f) => Ok(UserMfaModel {
+                user_id: f.id,
+                secret: f.mfa_secret,
+                enabled_on: f
+                    .mfa_enabled_on
+                    .map(|enabled_on| enabled_on.with_timezone(&Utc)),
+                last_used_step: f.mfa_last_used_step,
+            }),
+            None => Err(InfraError::not_found("User", user_id.to_string())),
+        }
+    }
+
+    async fn is_required(&self, user_id: i32) -> Result<bool, InfraError> {
+        let db = self._db_connection.as_ref();
+        let required_roles = role::Entity::find()
+            .join(JoinType::InnerJoin, role::Relation::UserRole.def())
+            .filter(user_role::Column::UserId.eq(user_id))
+            .filter(role::Column::RequiresMfa.eq(true))
+            .filter(role::Column::IsActived.eq(true))
+            .count(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(required_roles > 0)
+    }
+
+    async fn set_pending_secret(&self, user_id: i32, secret: &str) -> Result<(), InfraError> {
+        let db = self._db_connection.as_ref();
+        User::update_many()
+            .col_expr(user::Column::MfaSecret, Expr::value(secret))
+            .col_expr(
+                user::Column::MfaEnabledOn,
+                Expr::value(Option::<DateTime<FixedOffset>>::None),
+            )
+            .col_expr(
+                user::Column::MfaLastUsedStep,
+                Expr::value(Option::<i64>::None),
+            )
+            .filter(user::Column::Id.eq(user_id))
+            .filter(user::Column::MfaEnabledOn.is_null())
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+        Ok(())
+    }
+
+    async fn enable(
+        &self,
+        user_id: i32,
+        used_step: i64,
+        recovery_code_hashes: Vec<String>,
+    ) -> Result<(), InfraError> {
+        let db_transaction = self
+            ._db_connection
+            .begin()
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        let result = async {
+            User::update_many()
+                .col_expr(
+                    user::Column::MfaEnabledOn,
+                    Expr::value(Utc::now().fixed_offset()),
+                )
+                .col_expr(user::Column::MfaLastUsedStep, Expr::value(used_step))
+                .filter(user::Column::Id.eq(user_id))
+                .filter(user::Column::MfaSecret.is_not_null())
+                .exec(&db_transaction)
+                .await?;
+            self::insert_recovery_codes(&db_transaction, user_id, recovery_code_hashes).await
+        }
+        .await;
+
+        match result {
+            Ok(_) => db_transaction
+                .commit()
+                .await
+                .map_err(|err| InfraError::database(err.to_string().as_str())),
+            Err(err) => {
+                db_transaction
+                    .rollback()
+                    .await
+                    .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+                Err(InfraError::database(err.to_string().as_str()))
+            }
+        }
+    }
+
+    async fn disable(&self, user_id: i32) -> Result<(), InfraError> {
+        let db_transaction = self
+            ._db_connection
+            .begin()
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        let result = async {
+            User::update_many()
+                .col_expr(user::Column::MfaSecret, Expr::value(Option::<String>::None))
+                .col_expr(
+                    user::Column::MfaEnabledOn,
+                    Expr::value(Option::<DateTime<FixedOffset>>::None),
+                )
+                .col_expr(
+                    user::Column::MfaLastUsedStep,
+                    Expr::value(Option::<i64>::None),
+                )
+                .filter(user::Column::Id.eq(user_id))
+                .exec(&db_transaction)
+                .await?;
+            self::insert_recovery_codes(&db_transaction, user_id, vec![]).await
+        }
+        .await;
+
+        match result {
+            Ok(_) => db_transaction
+                .commit()
+                .await
+                .map_err(|err| InfraError::database(err.to_string().as_str())),
+            Err(err) => {
+                db_transaction
+                    .rollback()
+                    .await
+                    .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+                Err(InfraError::database(err.to_string().as_str()))
+            }
+        }
+    }
+
+    async fn use_time_step(&self, user_id: i32, step: i64) -> Result<bool, InfraError> {
+        let db = self._db_connection.as_ref();
+        let result = User::update_many()
+            .col_expr(user::Column::MfaLastUsedStep, Expr::value(step))
+            .filter(user::Column::Id.eq(user_id))
+            .filter(
+                Condition::any()
+                    .add(user::Column::MfaLastUsedStep.is_null())
+                    .add(user::Column::MfaLastUsedStep.lt(step)),
+            )
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn use_recovery_code(&self, user_id: i32, code_hash: &str) -> Result<bool, InfraError> {
+        let db = self._db_connection.as_ref();
+        let result = UserRecoveryCode::update_many()
+            .col_expr(
+                user_recovery_code::Column::UsedOn,
+                Expr::value(Utc::now().fixed_offset()),
+            )
+            .filter(user_recovery_code::Column::UserId.eq(user_id))
+            .filter(user_recovery_code::Column::CodeHash.eq(code_hash))
+            .filter(user_recovery_code::Column::UsedOn.is_null())
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn replace_recovery_codes(
+        &self,
+        user_id: i32,
+        recovery_code_hashes: Vec<String>,
+    ) -> Result<(), InfraError> {
+        let db_transaction = self
+            ._db_connection
+            .begin()
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        match self::insert_recovery_codes(&db_transaction, user_id, recovery_code_hashes).await {
+            Ok(_) => db_transaction
+                .commit()
+                .await
+                .map_err(|err| InfraError::database(err.to_string().as_str())),
+            Err(err) => {
+                db_transaction
+                    .rollback()
+                    .await
+                    .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+                Err(InfraError::database(err.to_string().as_str()))
+            }
+        }
+    }
+
+    async fn count_unused_recovery_codes(&self, user_id: i32) -> Result<u64, InfraError> {
+        let db = self._db_connection.as_ref();
+        UserRecoveryCode::find()
+            .filter(user_recovery_code::Column::UserId.eq(user_id))
+            .filter(user_recovery_code::Column::UsedOn.is_null())
+            .count(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))
+    }
+}
+
+/// Deletes the user's recovery codes and stores the given ones in their place
+async fn insert_recovery_codes<C: ConnectionTrait>(
+    db: &C,
+    user_id: i32,
+    recovery_code_hashes: Vec<String>,
+) -> Result<(), sea_orm::DbErr> {
+    UserRecoveryCode::delete_many()
+        .filter(user_recovery_code::Column::UserId.eq(user_id))
+        .exec(db)
+        .await?;
+
+    if recovery_code_hashes.is_empty() {
+        return Ok(());
+    }
+
+    let now = Utc::now().fixed_offset();
+    let recovery_codes =
+        recovery_code_hashes
+            .into_iter()
+            .map(|code_hash| user_recovery_code::ActiveModel {
+                user_id: Set(user_id),
+                code_hash: Set(code_hash),
+                created_on: Set(now),
+                ..Default::default()
+            });
+    UserRecoveryCode::insert_many(recovery_codes)
+        .exec(db)
+        .await?;
+    Ok(())
+}
@@ -1,11 +1,13 @@
 use crate::domain::{
-    models::{user_model::UserModel, user_statuses::UserStatuses},
+    models::{
+        user_model::UserModel, user_stamps_model::UserStampsModel, user_statuses::UserStatuses,
+    },
     repositories::user_repository_trait::UserRepositoryTrait,
 };
 use rex_game_entities::entities::{
     role,
     user::{self, Entity as User},
-    user_role,
+    user_permission, user_role,
 };
 use chrono::Utc;
 use rex_game_shared::domain::models::page_list_model::PageListModel;
@@ -13,8 +15,9 @@ use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
 use rex_game_shared::infrastructure::database::SeaOrmTransactionWrapper;
 use rex_game_shared::InfraError;
 use sea_orm::{
-    ColumnTrait, Condition, DatabaseConnection, EntityTrait, JoinType, PaginatorTrait, QueryFilter,
-    QueryOrder, QuerySelect, RelationTrait, Set, TransactionTrait,
+    sea_query::{Expr, Query},
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, ExprTrait, JoinType, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set, TransactionTrait,
 };
 use std::{future::Future, pin::Pin, sync::Arc};
 
@@ -29,6 +32,25 @@ impl UserRepository {
             _db_connection: db_connection,
         }
     }
+
+    async fn increment_authorization_version_where(
+        &self,
+        condition: Condition,
+    ) -> Result<u64, InfraError> {
+        let db = self._db_connection.as_ref();
+        // Incremented in the database, so concurrent changes each raise it
+        let result = User::update_many()
+            .col_expr(
+                user::Column::AuthorizationVersion,
+                Expr::col(user::Column::AuthorizationVersion).add(1),
+            )
+            .filter(condition)
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(result.rows_affected)
+    }
 }
 
 impl UserRepositoryTrait for UserRepository {
@@ -85,6 +107,7 @@ impl UserRepositoryTrait for UserRepository {
                 display_name: i.display_name,
                 email: i.email,
                 status_id: i.status_id,
+                kind_id: i.kind_id,
                 created_on: i.created_on.with_timezone(&Utc),
                 updated_on: i.updated_on.with_timezone(&Utc),
                 created_by_id: i.created_by_id,
@@ -108,6 +131,7 @@ impl UserRepositoryTrait for UserRepository {
             password_hash: Set(user_req.password_hash),
             security_stamp: Set(user_req.security_stamp),
             status_id: Set(user_req.status_id),
+            kind_id: Set(user_req.kind_id),
             created_by_id: Set(user_req.created_by_id),
             updated_by_id: Set(user_req.updated_by_id),
             created_on: Set(Utc::now().fixed_offset()),
@@ -157,6 +181,7 @@ impl UserRepositoryTrait for UserRepository {
             password_hash: Set(user_req.password_hash),
             security_stamp: Set(user_req.security_stamp),
             status_id: Set(user_req.status_id),
+            kind_id: Set(user_req.kind_id),
             created_by_id: Set(user_req.created_by_id),
             updated_by_id: Set(user_req.updated_by_id),
             created_on: Set(Utc::now().fixed_offset()),
@@ -265,12 +290,83 @@ impl UserRepositoryTrait for UserRepository {
         existing_user.name = Set(user_req.name);
         existing_user.status_id = Set(user_req.status_id);
         existing_user.password_hash = Set(user_req.password_hash);
+        existing_user.security_stamp = Set(user_req.security_stamp);
 
         match User::update(existing_user).exec(db).await {
             Ok(_) => Ok(true),
             Err(err) => Err(InfraError::database(err.to_string().as_str())),
         }
     }
+
+    fn get_stamps(
+        &self,
+        id: i32,
+    ) -> Pin<Box<dyn Future<Output = Result<UserStampsModel, InfraError>> + Send>> {
+        let db = self._db_connection.clone();
+        Box::pin(async move {
+            let stamps = User::find_by_id(id)
+                .select_only()
+                .column(user::Column::SecurityStamp)
+                .column(user::Column::AuthorizationVersion)
+                .into_tuple::<(String, i32)>()
+                .one(db.as_ref())
+                .await
+                .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+            match stamps {
+                Some((security_stamp, authorization_version)) => Ok(UserStampsModel {
+                    security_stamp,
+                    authorization_version,
+                }),
+                None => Err(InfraError::not_found("User not found", id.to_string())),
+            }
+        })
+    }
+
+    async fn increment_authorization_version(&self, id: i32) -> Result<u64, InfraError> {
+        self.increment_authorization_version_where(Condition::all().add(user::Column::Id.eq(id)))
+            .await
+    }
+
+    async fn increment_authorization_version_by_roles(
+        &self,
+        role_ids: Vec<i32>,
+    ) -> Result<u64, InfraError> {
+        let role_users = Query::select()
+            .column(user_role::Column::UserId)
+            .from(user_role::Entity)
+            .and_where(user_role::Column::RoleId.is_in(role_ids))
+            .to_owned();
+
+        self.increment_authorization_version_where(
+            Condition::all().add(user::Column::Id.in_subquery(role_users)),
+        )
+        .await
+    }
+
+    async fn increment_authorization_version_by_permission(
+        &self,
+        permission_id: i32,
+        role_ids: Vec<i32>,
+    ) -> Result<u64, InfraError> {
+        let permission_users = Query::select()
+            .column(user_permission::Column::UserId)
+            .from(user_permission::Entity)
+            .and_where(user_permission::Column::PermissionId.eq(permission_id))
+            .to_owned();
+        let role_users = Query::select()
+            .column(user_role::Column::UserId)
+            .from(user_role::Entity)
+            .and_where(user_role::Column::RoleId.is_in(role_ids))
+            .to_owned();
+
+        self.increment_authorization_version_where(
+            Condition::any()
+                .add(user::Column::Id.in_subquery(permission_users))
+                .add(user::Column::Id.in_subquery(role_users)),
+        )
+        .await
+    }
 }
 
 fn map_entity_to_model(f: user::Model) -> UserModel {
@@ -282,6 +378,7 @@ fn map_entity_to_model(f: user::Model) -> UserModel {
         password_hash: f.password_hash,
         security_stamp: f.security_stamp,
         status_id: f.status_id,
+        kind_id: f.kind_id,
         created_on: f.created_on.with_timezone(&Utc),
         updated_on: f.updated_on.with_timezone(&Utc),
         created_by_id: f.created_by_id,
@@ -0,0 +1,123 @@
+use crate::domain::{
+    models::user_status_model::UserStatusModel,
+    repositories::user_status_repository_trait::UserStatusRepositoryTrait,
+};
+use chrono::{DateTime, FixedOffset, Utc};
+use rex_game_entities::entities::{user, user::Entity as User};
+use rex_game_shared::{domain::enums::user_statuses::UserStatuses, InfraError};
+use sea_orm::{sea_query::Expr, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct UserStatusRepository {
+    _db_connection: Arc<DatabaseConnection>,
+}
+
+impl UserStatusRepository {
+    pub fn new(db_connection: Arc<DatabaseConnection>) -> Self {
+        Self {
+            _db_connection: db_connection,
+        }
+    }
+}
+
+impl UserStatusRepositoryTrait for UserStatusRepository {
+    async fn get_by_user_id(&self, user_id: i32) -> Result<UserStatusModel, InfraError> {
+        let db = self._db_connection.as_ref();
+        let existing = User::find_by_id(user_id)
+            .one(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        match existing {
+            Some(f) => Ok(UserStatusModel {
+                user_id: f.id,
+                status_id: f.status_id,
+                status_reason: f.status_reason,
+                status_expires_on: f
+                    .status_expires_on
+                    .map(|expires_on| expires_on.with_timezone(&Utc)),
+                status_changed_by_id: f.status_changed_by_id,
+                status_changed_on: f
+                    .status_changed_on
+                    .map(|changed_on| changed_on.with_timezone(&Utc)),
+            }),
+            None => Err(InfraError::not_found("User", user_id.to_string())),
+        }
+    }
+
+    async fn change_status(
+        &self,
+        status: UserStatusModel,
+        from_status_ids: Vec<i32>,
+    ) -> Result<bool, InfraError> {
+        let db = self._db_connection.as_ref();
+        let changed_on = status.status_changed_on.unwrap_or_else(Utc::now);
+        let result = User::update_many()
+            .col_expr(user::Column::StatusId, Expr::value(status.status_id))
+            .col_expr(
+                user::Column::StatusReason,
+                Expr::value(status.status_reason),
+            )
+            .col_expr(
+                user::Column::StatusExpiresOn,
+                Expr::value(status.status_expires_on.map(|f| f.fixed_offset())),
+            )
+            .col_expr(
+                user::Column::StatusChangedById,
+                Expr::value(status.status_changed_by_id),
+            )
+            .col_expr(
+                user::Column::StatusChangedOn,
+                Expr::value(changed_on.fixed_offset()),
+            )
+            .col_expr(
+                user::Column::UpdatedOn,
+                Expr::value(changed_on.fixed_offset()),
+            )
+            .filter(user::Column::Id.eq(status.user_id))
+            .filter(user::Column::StatusId.is_in(from_status_ids))
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn lift_expired_suspension(
+        &self,
+        user_id: i32,
+        now: DateTime<Utc>,
+    ) -> Result<bool, InfraError> {
+        let db = self._db_connection.as_ref();
+        let result = User::update_many()
+            .col_expr(
+                user::Column::StatusId,
+                Expr::value(UserStatuses::Actived as i32),
+            )
+            .col_expr(
+                user::Column::StatusReason,
+                Expr::value(Option::<String>::None),
+            )
+            .col_expr(
+                user::Column::StatusExpiresOn,
+                Expr::value(Option::<DateTime<FixedOffset>>::None),
+            )
+            .col_expr(
+                user::Column::StatusChangedById,
+                Expr::value(Option::<i32>::None),
+            )
+            .col_expr(
+                user::Column::StatusChangedOn,
+                Expr::value(now.fixed_offset()),
+            )
+            .filter(user::Column::Id.eq(user_id))
+            .filter(user::Column::StatusId.eq(UserStatuses::Suspended as i32))
+            .filter(user::Column::StatusExpiresOn.lte(now.fixed_offset()))
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -1,11 +1,16 @@
-use rex_game_entities::entities::user_token::{self, Entity as UserToken};
 use crate::domain::{
     models::user_token_model::UserTokenModel,
     repositories::user_token_repository_trait::UserTokenRepositoryTrait,
 };
 use chrono::Utc;
+use rex_game_entities::entities::user_token::{self, Entity as UserToken};
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
+use rex_game_shared::infrastructure::database::SeaOrmTransactionWrapper;
 use rex_game_shared::InfraError;
-use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set,
+};
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -34,6 +39,7 @@ impl UserTokenRepositoryTrait for UserTokenRepository {
             expiration: Set(user_token_req.expiration),
             is_actived: Set(user_token_req.is_actived),
             purpose: Set(user_token_req.purpose),
+            family_id: Set(user_token_req.family_id),
             ..Default::default()
         };
 
@@ -52,6 +58,37 @@ impl UserTokenRepositoryTrait for UserTokenRepository {
         }
     }
 
+    async fn create_without_commit(
+        &self,
+        user_token_req: UserTokenModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<i32, InfraError> {
+        let user_token = user_token::ActiveModel {
+            user_id: Set(user_token_req.user_id),
+            created_by_id: Set(user_token_req.created_by_id),
+            updated_by_id: Set(user_token_req.updated_by_id),
+            created_on: Set(Utc::now().fixed_offset()),
+            updated_on: Set(Utc::now().fixed_offset()),
+            token: Set(user_token_req.token),
+            expiration: Set(user_token_req.expiration),
+            is_actived: Set(user_token_req.is_actived),
+            purpose: Set(user_token_req.purpose),
+            family_id: Set(user_token_req.family_id),
+            ..Default::default()
+        };
+
+        let it = transaction.as_ref().as_any();
+        let transact = match it.downcast_ref::<SeaOrmTransactionWrapper>() {
+            Some(i) => i,
+            None => return Err(InfraError::database("Unable to cast the transaction")),
+        };
+        UserToken::insert(user_token)
+            .exec(transact.txn.as_ref().unwrap())
+            .await
+            .map(|inserted| inserted.last_insert_id)
+            .map_err(|err| InfraError::database(err.to_string().as_str()))
+    }
+
     async fn get_by_id(&self, id: i32) -> Result<UserTokenModel, InfraError> {
         let db = self._db_connection.clone();
         let existing = UserToken::find_by_id(id)
@@ -113,6 +150,82 @@ impl UserTokenRepositoryTrait for UserTokenRepository {
             Err(err) => Err(InfraError::database(err.to_string().as_str())),
         }
     }
+
+    async fn deactivate_if_active(&self, id: i32, updated_by_id: i32) -> Result<bool, InfraError> {
+        let db = self._db_connection.as_ref();
+        let result = UserToken::update_many()
+            .col_expr(user_token::Column::IsActived, Expr::value(false))
+            .col_expr(user_token::Column::UpdatedById, Expr::value(updated_by_id))
+            .col_expr(
+                user_token::Column::UpdatedOn,
+                Expr::value(Utc::now().fixed_offset()),
+            )
+            .filter(user_token::Column::Id.eq(id))
+            .filter(user_token::Column::IsActived.eq(true))
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn deactivate_family(&self, user_id: i32, family_id: &str) -> Result<u64, InfraError> {
+        let db = self._db_connection.as_ref();
+        let result = UserToken::update_many()
+            .col_expr(user_token::Column::IsActived, Expr::value(false))
+            .col_expr(user_token::Column::UpdatedById, Expr::value(user_id))
+            .col_expr(
+                user_token::Column::UpdatedOn,
+                Expr::value(Utc::now().fixed_offset()),
+            )
+            .filter(user_token::Column::UserId.eq(user_id))
+            .filter(user_token::Column::FamilyId.eq(family_id))
+            .filter(user_token::Column::IsActived.eq(true))
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn deactivate_by_user(&self, user_id: i32, purpose: i32) -> Result<u64, InfraError> {
+        let db = self._db_connection.as_ref();
+        let result = UserToken::update_many()
+            .col_expr(user_token::Column::IsActived, Expr::value(false))
+            .col_expr(user_token::Column::UpdatedById, Expr::value(user_id))
+            .col_expr(
+                user_token::Column::UpdatedOn,
+                Expr::value(Utc::now().fixed_offset()),
+            )
+            .filter(user_token::Column::UserId.eq(user_id))
+            .filter(user_token::Column::Purpose.eq(purpose))
+            .filter(user_token::Column::IsActived.eq(true))
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn get_active_by_user(
+        &self,
+        user_id: i32,
+        purpose: i32,
+    ) -> Result<Vec<UserTokenModel>, InfraError> {
+        let db = self._db_connection.as_ref();
+        let now = Utc::now().timestamp() as i32;
+        let user_tokens = UserToken::find()
+            .filter(user_token::Column::UserId.eq(user_id))
+            .filter(user_token::Column::Purpose.eq(purpose))
+            .filter(user_token::Column::IsActived.eq(true))
+            .filter(user_token::Column::Expiration.gt(now))
+            .order_by_desc(user_token::Column::CreatedOn)
+            .all(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string().as_str()))?;
+
+        Ok(user_tokens.into_iter().map(map_entity_to_model).collect())
+    }
 }
 
 fn map_entity_to_model(f: user_token::Model) -> UserTokenModel {
@@ -127,6 +240,6 @@ fn map_entity_to_model(f: user_token::Model) -> UserTokenModel {
         expiration: f.expiration,
         is_actived: f.is_actived,
         purpose: f.purpose,
-        ..Default::default()
+        family_id: f.family_id,
     }
 }
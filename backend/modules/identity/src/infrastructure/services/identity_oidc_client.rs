@@ -0,0 +1,457 @@
+use crate::domain::services::{
+    oidc_client_trait::OidcClientTrait,
+    oidc_types::{OidcAuthorization, OidcIdentity, OidcProviderInfo},
+};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use chrono::Utc;
+use data_encoding::BASE64URL_NOPAD;
+use jsonwebtoken::{
+    decode, decode_header, encode, jwk::JwkSet, Algorithm, DecodingKey, EncodingKey, Header,
+    Validation,
+};
+use reqwest::Url;
+use rex_game_shared::{ApplicationError, InfraError};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+/// How long a sign-in at the provider may take
+const FLOW_TOKEN_EXPIRATION_SECS: i64 = 10 * 60;
+/// Audience of flow tokens, so they are never mistaken for access tokens
+const FLOW_TOKEN_AUDIENCE: &str = "oidc_flow";
+/// How long discovery documents and signing keys are trusted before they are fetched again
+const METADATA_TTL: Duration = Duration::from_secs(60 * 60);
+/// Random bytes behind the state, nonce and PKCE verifier
+const RANDOM_VALUE_BYTES: usize = 32;
+/// ID tokens must be signed with a provider key; shared secrets are refused
+const ID_TOKEN_ALGORITHMS: [Algorithm; 9] = [
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+/// One OpenID Connect provider users can sign in with
+#[derive(Clone)]
+pub struct OidcProviderOptions {
+    pub name: String,
+    pub display_name: String,
+    /// Issuer URL; the discovery document is read from `{issuer}/.well-known/openid-configuration`
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub scopes: String,
+    /// Treat the email as verified even without an `email_verified` claim, for providers
+    /// that only hand out addresses they own
+    pub trust_email: bool,
+}
+
+#[derive(Deserialize)]
+struct DiscoveryDocument {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+struct ProviderMetadata {
+    discovery: DiscoveryDocument,
+    jwks: JwkSet,
+    fetched_on: Instant,
+}
+
+#[derive(Serialize, Deserialize)]
+struct OidcFlowClaims {
+    aud: String,
+    exp: i64,
+    provider: String,
+    state: String,
+    nonce: String,
+    code_verifier: String,
+    link_user_id: Option<i32>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    nonce: Option<String>,
+    email: Option<String>,
+    /// A boolean, though some providers send the string `"true"`
+    email_verified: Option<serde_json::Value>,
+    name: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct IdentityOidcClient {
+    _http_client: reqwest::Client,
+    _providers: Arc<Vec<OidcProviderOptions>>,
+    /// Callback URL registered at every provider, with `[provider]` standing in for its name
+    _redirect_url: Arc<String>,
+    _flow_secret: Arc<String>,
+    _metadata: Arc<RwLock<HashMap<String, Arc<ProviderMetadata>>>>,
+}
+
+impl IdentityOidcClient {
+    pub fn new(
+        providers: Vec<OidcProviderOptions>,
+        redirect_url: String,
+        flow_secret: String,
+    ) -> Self {
+        Self {
+            _http_client: reqwest::Client::new(),
+            _providers: Arc::new(providers),
+            _redirect_url: Arc::new(redirect_url),
+            _flow_secret: Arc::new(flow_secret),
+            _metadata: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    fn get_provider(&self, provider: &str) -> Result<&OidcProviderOptions, ApplicationError> {
+        self._providers
+            .iter()
+            .find(|f| f.name == provider)
+            .ok_or_else(|| ApplicationError::not_found("Sign-in provider", provider))
+    }
+
+    fn redirect_url(&self, provider: &str) -> String {
+        self._redirect_url.replace("[provider]", provider)
+    }
+
+    fn random_value() -> String {
+        let mut bytes = [0_u8; RANDOM_VALUE_BYTES];
+        OsRng.fill_bytes(&mut bytes);
+        BASE64URL_NOPAD.encode(&bytes)
+    }
+
+    /// S256 code challenge of a PKCE verifier, RFC 7636 section 4.2
+    fn code_challenge(code_verifier: &str) -> String {
+        BASE64URL_NOPAD.encode(&Sha256::digest(code_verifier.as_bytes()))
+    }
+
+    fn encode_flow_token(&self, claims: &OidcFlowClaims) -> Result<String, ApplicationError> {
+        encode(
+            &Header::new(Algorithm::HS256),
+            claims,
+            &EncodingKey::from_secret(self._flow_secret.as_bytes()),
+        )
+        .map_err(|_| ApplicationError::invalid_input("Failed to generate sign-in flow token"))
+    }
+
+    fn decode_flow_token(&self, flow_token: &str) -> Result<OidcFlowClaims, ApplicationError> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.set_audience(&[FLOW_TOKEN_AUDIENCE]);
+        validation.set_required_spec_claims(&["exp", "aud"]);
+        decode::<OidcFlowClaims>(
+            flow_token,
+            &DecodingKey::from_secret(self._flow_secret.as_bytes()),
+            &validation,
+        )
+        .map(|token_data| token_data.claims)
+        .map_err(|_| ApplicationError::invalid_token("The sign-in has expired, please try again"))
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, ApplicationError> {
+        let response = self
+            ._http_client
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| InfraError::external_service(err.to_string()))?;
+        let value = response
+            .json::<T>()
+            .await
+            .map_err(|err| InfraError::external_service(err.to_string()))?;
+        Ok(value)
+    }
+
+    /// Discovery document and signing keys of the provider, fetched again once they are
+    /// stale or when `refresh` asks for it, e.g. for a key id that is not known yet
+    async fn get_metadata(
+        &self,
+        provider: &OidcProviderOptions,
+        refresh: bool,
+    ) -> Result<Arc<ProviderMetadata>, ApplicationError> {
+        if !refresh {
+            let cached = self
+                ._metadata
+                .read()
+                .ok()
+                .and_then(|metadata| metadata.get(&provider.name).cloned());
+            if let Some(cached) = cached.filter(|f| f.fetched_on.elapsed() < METADATA_TTL) {
+                return Ok(cached);
+            }
+        }
+
+        let issuer = provider.issuer.trim_end_matches('/');
+        let discovery: DiscoveryDocument = self
+            .get_json(&format!("{issuer}/.well-known/openid-configuration"))
+            .await?;
+        // A document claiming another issuer could hand out someone else's keys
+        if discovery.issuer.trim_end_matches('/') != issuer {
+            return Err(ApplicationError::Infrastructure(
+                InfraError::external_service(format!(
+                    "Discovery document of {} names another issuer",
+                    provider.name
+                )),
+            ));
+        }
+        let jwks: JwkSet = self.get_json(&discovery.jwks_uri).await?;
+
+        let metadata = Arc::new(ProviderMetadata {
+            discovery,
+            jwks,
+            fetched_on: Instant::now(),
+        });
+        if let Ok(mut cache) = self._metadata.write() {
+            cache.insert(provider.name.to_owned(), metadata.clone());
+        }
+        Ok(metadata)
+    }
+
+    async fn validate_id_token(
+        &self,
+        provider: &OidcProviderOptions,
+        mut metadata: Arc<ProviderMetadata>,
+        id_token: &str,
+    ) -> Result<IdTokenClaims, ApplicationError> {
+        let invalid =
+            || ApplicationError::invalid_token("The provider returned an invalid ID token");
+        let header = decode_header(id_token).map_err(|_| invalid())?;
+        if !ID_TOKEN_ALGORITHMS.contains(&header.alg) {
+            return Err(invalid());
+        }
+
+        // Providers rotate keys, so an unknown key id gets one fresh look at the key set
+        let find_key = |metadata: &ProviderMetadata| match &header.kid {
+            Some(kid) => metadata.jwks.find(kid).cloned(),
+            None if metadata.jwks.keys.len() == 1 => metadata.jwks.keys.first().cloned(),
+            None => None,
+        };
+        let jwk = match find_key(metadata.as_ref()) {
+            Some(jwk) => jwk,
+            None => {
+                metadata = self.get_metadata(provider, true).await?;
+                find_key(metadata.as_ref()).ok_or_else(invalid)?
+            }
+        };
+        let decoding_key = DecodingKey::from_jwk(&jwk).map_err(|_| invalid())?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_audience(&[provider.client_id.as_str()]);
+        validation.set_issuer(&[metadata.discovery.issuer.as_str()]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        decode::<IdTokenClaims>(id_token, &decoding_key, &validation)
+            .map(|token_data| token_data.claims)
+            .map_err(|_| invalid())
+    }
+}
+
+impl OidcClientTrait for IdentityOidcClient {
+    fn get_providers(&self) -> Vec<OidcProviderInfo> {
+        self._providers
+            .iter()
+            .map(|f| OidcProviderInfo {
+                name: f.name.to_owned(),
+                display_name: f.display_name.to_owned(),
+            })
+            .collect()
+    }
+
+    async fn begin_authorization(
+        &self,
+        provider: &str,
+        link_user_id: Option<i32>,
+    ) -> Result<OidcAuthorization, ApplicationError> {
+        let provider = self.get_provider(provider)?;
+        let metadata = self.get_metadata(provider, false).await?;
+
+        let claims = OidcFlowClaims {
+            aud: FLOW_TOKEN_AUDIENCE.to_string(),
+            exp: Utc::now().timestamp() + FLOW_TOKEN_EXPIRATION_SECS,
+            provider: provider.name.to_owned(),
+            state: Self::random_value(),
+            nonce: Self::random_value(),
+            code_verifier: Self::random_value(),
+            link_user_id,
+        };
+        let code_challenge = Self::code_challenge(&claims.code_verifier);
+        let redirect_url = self.redirect_url(&provider.name);
+        let authorization_url = Url::parse_with_params(
+            &metadata.discovery.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", provider.client_id.as_str()),
+                ("redirect_uri", redirect_url.as_str()),
+                ("scope", provider.scopes.as_str()),
+                ("state", claims.state.as_str()),
+                ("nonce", claims.nonce.as_str()),
+                ("code_challenge", code_challenge.as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(|err| InfraError::external_service(err.to_string()))?;
+
+        Ok(OidcAuthorization {
+            authorization_url: authorization_url.to_string(),
+            flow_token: self.encode_flow_token(&claims)?,
+        })
+    }
+
+    async fn complete_authorization(
+        &self,
+        provider: &str,
+        code: &str,
+        state: &str,
+        flow_token: &str,
+    ) -> Result<OidcIdentity, ApplicationError> {
+        let provider = self.get_provider(provider)?;
+        let flow = self.decode_flow_token(flow_token)?;
+        if flow.provider != provider.name || flow.state != state {
+            return Err(ApplicationError::invalid_token(
+                "The sign-in does not match the one that was started",
+            ));
+        }
+
+        let metadata = self.get_metadata(provider, false).await?;
+        let redirect_url = self.redirect_url(&provider.name);
+        let response = self
+            ._http_client
+            .post(&metadata.discovery.token_endpoint)
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", redirect_url.as_str()),
+                ("client_id", provider.client_id.as_str()),
+                ("client_secret", provider.client_secret.as_str()),
+                ("code_verifier", flow.code_verifier.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|err| InfraError::external_service(err.to_string()))?;
+        // A refused code is the user's problem (expired or reused), not an outage
+        if response.status().is_client_error() {
+            return Err(ApplicationError::invalid_token(
+                "The provider did not accept the sign-in, please try again",
+            ));
+        }
+        let token_response = response
+            .error_for_status()
+            .map_err(|err| InfraError::external_service(err.to_string()))?
+            .json::<TokenResponse>()
+            .await
+            .map_err(|err| InfraError::external_service(err.to_string()))?;
+        let id_token = token_response.id_token.ok_or_else(|| {
+            ApplicationError::invalid_token("The provider did not return an ID token")
+        })?;
+
+        let claims = self
+            .validate_id_token(provider, metadata, &id_token)
+            .await?;
+        // The nonce ties the ID token to this flow, so one cannot be replayed into another
+        if claims.nonce.as_deref() != Some(flow.nonce.as_str()) {
+            return Err(ApplicationError::invalid_token(
+                "The provider returned an invalid ID token",
+            ));
+        }
+
+        let email_verified = provider.trust_email
+            || match claims.email_verified {
+                Some(serde_json::Value::Bool(verified)) => verified,
+                Some(serde_json::Value::String(verified)) => verified == "true",
+                _ => false,
+            };
+        Ok(OidcIdentity {
+            provider: provider.name.to_owned(),
+            subject: claims.sub,
+            email: claims.email,
+            email_verified,
+            name: claims.name,
+            link_user_id: flow.link_user_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> IdentityOidcClient {
+        IdentityOidcClient::new(
+            vec![],
+            "http://localhost:5173/account/external/[provider]/callback".to_string(),
+            "test-secret-that-is-long-enough-for-hs256".to_string(),
+        )
+    }
+
+    fn flow_claims() -> OidcFlowClaims {
+        OidcFlowClaims {
+            aud: FLOW_TOKEN_AUDIENCE.to_string(),
+            exp: Utc::now().timestamp() + 60,
+            provider: "google".to_string(),
+            state: "state".to_string(),
+            nonce: "nonce".to_string(),
+            code_verifier: "verifier".to_string(),
+            link_user_id: Some(7),
+        }
+    }
+
+    #[test]
+    fn test_code_challenge_is_base64url_sha256() {
+        assert_eq!(
+            IdentityOidcClient::code_challenge("dBjftJeZ4CVP-mJ92TfoP3C3fwbHhIoFpsfNJ7QnG7U"),
+            "_xH_TF3EcgYO97WiVmwH6hQOY_UV9bQtFLpJn-_ib3E"
+        );
+    }
+
+    #[test]
+    fn test_random_value_is_a_valid_pkce_verifier() {
+        let verifier = IdentityOidcClient::random_value();
+        // RFC 7636 allows 43 to 128 unreserved characters
+        assert_eq!(verifier.len(), 43);
+        assert!(verifier
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_ne!(verifier, IdentityOidcClient::random_value());
+    }
+
+    #[test]
+    fn test_flow_token_round_trip() {
+        let client = client();
+        let flow_token = client.encode_flow_token(&flow_claims()).unwrap();
+        let claims = client.decode_flow_token(&flow_token).unwrap();
+        assert_eq!(claims.provider, "google");
+        assert_eq!(claims.code_verifier, "verifier");
+        assert_eq!(claims.link_user_id, Some(7));
+    }
+
+    #[test]
+    fn test_foreign_or_expired_flow_token_is_rejected() {
+        let other_client = IdentityOidcClient::new(
+            vec![],
+            String::new(),
+            "another-secret-that-is-long-enough-too".to_string(),
+        );
+        let flow_token = other_client.encode_flow_token(&flow_claims()).unwrap();
+        assert!(client().decode_flow_token(&flow_token).is_err());
+
+        let mut claims = flow_claims();
+        claims.exp = Utc::now().timestamp() - 3600;
+        let flow_token = client().encode_flow_token(&claims).unwrap();
+        assert!(client().decode_flow_token(&flow_token).is_err());
+    }
+}
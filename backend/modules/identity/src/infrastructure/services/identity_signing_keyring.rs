@@ -0,0 +1,322 @@
+use crate::domain::{
+    models::signing_key_model::SigningKeyModel,
+    services::{json_web_key::JsonWebKey, signing_keyring_trait::SigningKeyringTrait},
+};
+use argon2::password_hash::rand_core::OsRng;
+use chrono::{DateTime, Utc};
+use data_encoding::{BASE64, BASE64URL_NOPAD};
+use jsonwebtoken::{
+    encode,
+    errors::{Error, ErrorKind},
+    Algorithm, DecodingKey, EncodingKey, Header,
+};
+use rex_game_shared::ApplicationError;
+use ring::{
+    rand::SystemRandom,
+    signature::{Ed25519KeyPair, KeyPair},
+};
+use rsa::{pkcs1::EncodeRsaPrivateKey, traits::PublicKeyParts, RsaPrivateKey};
+use serde::Serialize;
+use std::{
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+use uuid::Uuid;
+
+/// Modulus size of generated RSA keys
+const RSA_KEY_BITS: usize = 2048;
+
+struct LoadedSigningKey {
+    kid: String,
+    algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    public_key: JsonWebKey,
+    activates_on: DateTime<Utc>,
+    expires_on: Option<DateTime<Utc>>,
+}
+
+impl LoadedSigningKey {
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_on.is_some_and(|expires_on| expires_on <= now)
+    }
+}
+
+/// Token signing keys shared by every clone, reloaded from the database as keys rotate
+#[derive(Clone, Default)]
+pub struct IdentitySigningKeyring {
+    _signing_keys: Arc<RwLock<Vec<Arc<LoadedSigningKey>>>>,
+}
+
+impl IdentitySigningKeyring {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Signs the claims with the newest active key and names it in the `kid` header.
+    pub fn encode<T: Serialize>(&self, claims: &T) -> Result<String, Error> {
+        let now = Utc::now();
+        let signing_key = self
+            .read_keys()
+            .into_iter()
+            .filter(|f| f.activates_on <= now && !f.is_expired(now))
+            .max_by_key(|f| f.activates_on)
+            .ok_or_else(|| Error::from(ErrorKind::InvalidKeyFormat))?;
+
+        let mut header = Header::new(signing_key.algorithm);
+        header.kid = Some(signing_key.kid.to_owned());
+        encode(&header, claims, &signing_key.encoding_key)
+    }
+
+    /// Key that verifies tokens carrying the given `kid`, while it has not expired
+    pub fn decoding_key(&self, kid: &str) -> Option<(Algorithm, DecodingKey)> {
+        let now = Utc::now();
+        self.read_keys()
+            .into_iter()
+            .find(|f| f.kid == kid && !f.is_expired(now))
+            .map(|f| (f.algorithm, f.decoding_key.clone()))
+    }
+
+    fn read_keys(&self) -> Vec<Arc<LoadedSigningKey>> {
+        match self._signing_keys.read() {
+            Ok(keys) => keys.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    fn load_key(signing_key: SigningKeyModel) -> Result<LoadedSigningKey, ApplicationError> {
+        let invalid_key = |reason: &str| {
+            ApplicationError::invalid_state(format!(
+                "Signing key {} is invalid: {}",
+                signing_key.kid, reason
+            ))
+        };
+        let algorithm =
+            Algorithm::from_str(&signing_key.algorithm).map_err(|_| invalid_key("algorithm"))?;
+        let public_key: JsonWebKey =
+            serde_json::from_str(&signing_key.public_key).map_err(|_| invalid_key("public key"))?;
+        let private_key = BASE64
+            .decode(signing_key.private_key.as_bytes())
+            .map_err(|_| invalid_key("private key"))?;
+
+        let (encoding_key, decoding_key) = match algorithm {
+            Algorithm::EdDSA => {
+                let x = public_key
+                    .x
+                    .as_deref()
+                    .ok_or_else(|| invalid_key("public key"))?;
+                (
+                    EncodingKey::from_ed_der(&private_key),
+                    DecodingKey::from_ed_components(x).map_err(|_| invalid_key("public key"))?,
+                )
+            }
+            Algorithm::RS256 => {
+                let n = public_key
+                    .n
+                    .as_deref()
+                    .ok_or_else(|| invalid_key("public key"))?;
+                let e = public_key
+                    .e
+                    .as_deref()
+                    .ok_or_else(|| invalid_key("public key"))?;
+                (
+                    EncodingKey::from_rsa_der(&private_key),
+                    DecodingKey::from_rsa_components(n, e)
+                        .map_err(|_| invalid_key("public key"))?,
+                )
+            }
+            _ => return Err(invalid_key("algorithm")),
+        };
+
+        Ok(LoadedSigningKey {
+            kid: signing_key.kid,
+            algorithm,
+            encoding_key,
+            decoding_key,
+            public_key,
+            activates_on: signing_key.activates_on,
+            expires_on: signing_key.expires_on,
+        })
+    }
+}
+
+impl SigningKeyringTrait for IdentitySigningKeyring {
+    fn generate_key(&self, algorithm: &str) -> Result<SigningKeyModel, ApplicationError> {
+        let kid = Uuid::new_v4().simple().to_string();
+        let (private_key, public_key) = match algorithm {
+            "EdDSA" => {
+                let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).map_err(|_| {
+                    ApplicationError::invalid_state("Unable to generate an Ed25519 key")
+                })?;
+                let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).map_err(|_| {
+                    ApplicationError::invalid_state("Unable to generate an Ed25519 key")
+                })?;
+                let public_key = JsonWebKey {
+                    kty: "OKP".to_string(),
+                    kid: kid.to_owned(),
+                    alg: algorithm.to_string(),
+                    key_use: "sig".to_string(),
+                    crv: Some("Ed25519".to_string()),
+                    x: Some(BASE64URL_NOPAD.encode(key_pair.public_key().as_ref())),
+                    ..Default::default()
+                };
+                (pkcs8.as_ref().to_vec(), public_key)
+            }
+            "RS256" => {
+                let private_key = RsaPrivateKey::new(&mut OsRng, RSA_KEY_BITS).map_err(|_| {
+                    ApplicationError::invalid_state("Unable to generate an RSA key")
+                })?;
+                let der = private_key.to_pkcs1_der().map_err(|_| {
+                    ApplicationError::invalid_state("Unable to generate an RSA key")
+                })?;
+                let public_key = JsonWebKey {
+                    kty: "RSA".to_string(),
+                    kid: kid.to_owned(),
+                    alg: algorithm.to_string(),
+                    key_use: "sig".to_string(),
+                    n: Some(BASE64URL_NOPAD.encode(&private_key.n().to_bytes_be())),
+                    e: Some(BASE64URL_NOPAD.encode(&private_key.e().to_bytes_be())),
+                    ..Default::default()
+                };
+                (der.as_bytes().to_vec(), public_key)
+            }
+            _ => {
+                return Err(ApplicationError::invalid_input(format!(
+                    "Unsupported signing algorithm {}",
+                    algorithm
+                )))
+            }
+        };
+
+        let public_key = serde_json::to_string(&public_key)
+            .map_err(|_| ApplicationError::invalid_state("Unable to encode the public key"))?;
+        let now = Utc::now();
+        Ok(SigningKeyModel {
+            kid,
+            algorithm: algorithm.to_string(),
+            private_key: BASE64.encode(&private_key),
+            public_key,
+            created_on: now,
+            activates_on: now,
+            ..Default::default()
+        })
+    }
+
+    fn load(&self, signing_keys: Vec<SigningKeyModel>) -> Result<(), ApplicationError> {
+        let loaded_keys = signing_keys
+            .into_iter()
+            .map(|f| Self::load_key(f).map(Arc::new))
+            .collect::<Result<Vec<_>, _>>()?;
+        match self._signing_keys.write() {
+            Ok(mut keys) => *keys = loaded_keys,
+            Err(poisoned) => *poisoned.into_inner() = loaded_keys,
+        }
+        Ok(())
+    }
+
+    fn public_keys(&self) -> Vec<JsonWebKey> {
+        let now = Utc::now();
+        self.read_keys()
+            .into_iter()
+            .filter(|f| !f.is_expired(now))
+            .map(|f| f.public_key.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use jsonwebtoken::{decode, decode_header, Validation};
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct TestClaims {
+        sub: i32,
+        exp: u64,
+    }
+
+    fn claims() -> TestClaims {
+        TestClaims {
+            sub: 7,
+            exp: (Utc::now() + Duration::minutes(5)).timestamp() as u64,
+        }
+    }
+
+    fn verify(keyring: &IdentitySigningKeyring, token: &str) -> Option<TestClaims> {
+        let kid = decode_header(token).ok()?.kid?;
+        let (algorithm, decoding_key) = keyring.decoding_key(&kid)?;
+        decode::<TestClaims>(token, &decoding_key, &Validation::new(algorithm))
+            .ok()
+            .map(|f| f.claims)
+    }
+
+    #[test]
+    fn test_eddsa_round_trip() {
+        let keyring = IdentitySigningKeyring::new();
+        let key = keyring.generate_key("EdDSA").unwrap();
+        let kid = key.kid.to_owned();
+        keyring.load(vec![key]).unwrap();
+
+        let token = keyring.encode(&claims()).unwrap();
+        assert_eq!(decode_header(&token).unwrap().kid, Some(kid));
+        assert_eq!(verify(&keyring, &token).unwrap().sub, 7);
+    }
+
+    #[test]
+    fn test_rsa_round_trip() {
+        let keyring = IdentitySigningKeyring::new();
+        let key = keyring.generate_key("RS256").unwrap();
+        keyring.load(vec![key]).unwrap();
+
+        let token = keyring.encode(&claims()).unwrap();
+        assert_eq!(verify(&keyring, &token).unwrap().sub, 7);
+        assert_eq!(keyring.public_keys()[0].kty, "RSA");
+    }
+
+    #[test]
+    fn test_unknown_kid_is_rejected() {
+        let keyring = IdentitySigningKeyring::new();
+        keyring
+            .load(vec![keyring.generate_key("EdDSA").unwrap()])
+            .unwrap();
+        let token = keyring.encode(&claims()).unwrap();
+
+        let other = IdentitySigningKeyring::new();
+        other
+            .load(vec![other.generate_key("EdDSA").unwrap()])
+            .unwrap();
+        assert!(verify(&other, &token).is_none());
+    }
+
+    #[test]
+    fn test_pending_key_verifies_but_does_not_sign() {
+        let keyring = IdentitySigningKeyring::new();
+        let current = keyring.generate_key("EdDSA").unwrap();
+        let mut pending = keyring.generate_key("EdDSA").unwrap();
+        pending.activates_on = Utc::now() + Duration::minutes(15);
+        let current_kid = current.kid.to_owned();
+        let pending_kid = pending.kid.to_owned();
+        keyring.load(vec![current, pending]).unwrap();
+
+        let token = keyring.encode(&claims()).unwrap();
+        assert_eq!(decode_header(&token).unwrap().kid, Some(current_kid));
+        assert!(keyring.decoding_key(&pending_kid).is_some());
+        assert_eq!(keyring.public_keys().len(), 2);
+    }
+
+    #[test]
+    fn test_expired_key_is_rejected() {
+        let keyring = IdentitySigningKeyring::new();
+        let mut key = keyring.generate_key("EdDSA").unwrap();
+        keyring.load(vec![key.clone()]).unwrap();
+        let token = keyring.encode(&claims()).unwrap();
+
+        key.expires_on = Some(Utc::now() - Duration::seconds(1));
+        keyring.load(vec![key]).unwrap();
+        assert!(verify(&keyring, &token).is_none());
+        assert!(keyring.encode(&claims()).is_err());
+        assert!(keyring.public_keys().is_empty());
+    }
+}
@@ -1,4 +1,7 @@
-use super::token_claims::{AccessTokenClaims, HasExpiryTokenClaimTrait, RefreshTokenClaims};
+use super::{
+    identity_signing_keyring::IdentitySigningKeyring,
+    token_claims::{AccessTokenClaims, HasExpiryTokenClaimTrait, RefreshTokenClaims},
+};
 use crate::domain::services::{
     token_helper_trait::TokenHelperTrait,
     token_types::{TokenGenerationOptions, TokenGenerationResult, TokenValidationResult},
@@ -6,20 +9,16 @@ use crate::domain::services::{
 use chrono::{Duration, Utc};
 use jsonwebtoken::Algorithm;
 use jsonwebtoken::DecodingKey;
-use jsonwebtoken::EncodingKey;
 use jsonwebtoken::{
-    decode, encode,
+    decode, decode_header,
     errors::{Error, ErrorKind},
-    Header, Validation,
+    Validation,
 };
 use rex_game_shared::{
-    domain::{
-        enums::user_token_porposes::UserTokenPurposes,
-        helpers::configuration_helper_trait::ConfigurationHelperTrait,
-    },
-    ApplicationError,
+    domain::helpers::configuration_helper_trait::ConfigurationHelperTrait, ApplicationError,
 };
 use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -27,79 +26,94 @@ use uuid::Uuid;
 pub struct IdentityTokenHelper<CF: ConfigurationHelperTrait> {
     _configuration_helper: Arc<CF>,
     _client_id: Arc<String>,
-    _client_secret: String,
+    _keyring: IdentitySigningKeyring,
+    /// Secret of the HS256 tokens issued before signing keys, while those are still accepted
+    _legacy_secret: Option<String>,
 }
 
 impl<CF: ConfigurationHelperTrait> IdentityTokenHelper<CF> {
-    pub fn new(configuration_helper: Arc<CF>) -> Self {
+    pub fn new(
+        configuration_helper: Arc<CF>,
+        keyring: IdentitySigningKeyring,
+        accept_legacy_tokens: bool,
+    ) -> Self {
         return Self {
             _client_id: Arc::new(configuration_helper.get_value("JWT_CLIENT_ID")),
-            _client_secret: configuration_helper.get_value("JWT_CLIENT_SECRET"),
+            _keyring: keyring,
+            _legacy_secret: accept_legacy_tokens
+                .then(|| configuration_helper.get_value("JWT_CLIENT_SECRET")),
             _configuration_helper: configuration_helper,
         };
     }
 
-    fn get_token_claims<T>(&self, access_token: &str) -> Result<T, Error>
-    where
-        T: DeserializeOwned + HasExpiryTokenClaimTrait,
-    {
-        if access_token.is_empty() {
-            return Err(Error::from(ErrorKind::InvalidToken));
+    /// Picks the key named by the `kid` header. Tokens without one are legacy HS256 tokens.
+    fn get_decoding_key(&self, access_token: &str) -> Result<(Algorithm, DecodingKey), Error> {
+        let header = decode_header(access_token)?;
+        match (header.kid, &self._legacy_secret) {
+            (Some(kid), _) => self
+                ._keyring
+                .decoding_key(&kid)
+                .ok_or_else(|| Error::from(ErrorKind::InvalidToken)),
+            (None, Some(secret)) if header.alg == Algorithm::HS256 => Ok((
+                Algorithm::HS256,
+                DecodingKey::from_secret(secret.as_bytes()),
+            )),
+            (None, _) => Err(Error::from(ErrorKind::InvalidToken)),
         }
-        let mut validation = Validation::new(Algorithm::HS256);
-        validation.set_audience(&[self._client_id.to_string()]);
-        validation.set_issuer(&[self._client_id.to_string()]);
-        let secret_decoding = DecodingKey::from_secret(self._client_secret.as_bytes());
-        let token_claims = match decode::<T>(access_token, &secret_decoding, &validation) {
-            Ok(token_data) => token_data.claims,
-            Err(err) => return Err(err),
-        };
-
-        Ok(token_claims)
     }
 
-    fn validate_token<T>(&self, access_token: &str) -> Result<T, Error>
+    fn get_token_claims<T>(&self, access_token: &str) -> Result<T, Error>
     where
         T: DeserializeOwned + HasExpiryTokenClaimTrait,
     {
-        if access_token.is_empty() {
-            return Err(Error::from(ErrorKind::InvalidToken));
-        }
-        let mut validation = Validation::new(Algorithm::HS256);
-        validation.set_audience(&[self._client_id.to_string()]);
-        validation.set_issuer(&[self._client_id.to_string()]);
-
-        let secret_decoding = DecodingKey::from_secret(self._client_secret.as_bytes());
-        let token_claims = match decode::<T>(access_token, &secret_decoding, &validation) {
-            Ok(token_data) => token_data.claims,
-            Err(err) => return Err(err),
-        };
-
-        Ok(token_claims)
+        self.decode_token_claims(access_token, true)
     }
 
     /// Decode JWT claims without validating the `exp` field.
     /// Used during token refresh where the access token is expected to be expired.
     /// Still validates: signature, audience, and issuer.
     fn get_token_claims_without_exp_validation<T>(&self, access_token: &str) -> Result<T, Error>
+    where
+        T: DeserializeOwned + HasExpiryTokenClaimTrait,
+    {
+        self.decode_token_claims(access_token, false)
+    }
+
+    fn decode_token_claims<T>(&self, access_token: &str, validate_exp: bool) -> Result<T, Error>
     where
         T: DeserializeOwned + HasExpiryTokenClaimTrait,
     {
         if access_token.is_empty() {
             return Err(Error::from(ErrorKind::InvalidToken));
         }
-        let mut validation = Validation::new(Algorithm::HS256);
-        validation.validate_exp = false;
+        let (algorithm, decoding_key) = self.get_decoding_key(access_token)?;
+        let mut validation = Validation::new(algorithm);
+        validation.validate_exp = validate_exp;
         validation.set_audience(&[self._client_id.to_string()]);
         validation.set_issuer(&[self._client_id.to_string()]);
-        let secret_decoding = DecodingKey::from_secret(self._client_secret.as_bytes());
-        let token_claims = match decode::<T>(access_token, &secret_decoding, &validation) {
+        let token_claims = match decode::<T>(access_token, &decoding_key, &validation) {
             Ok(token_data) => token_data.claims,
             Err(err) => return Err(err),
         };
 
         Ok(token_claims)
     }
+
+    fn to_validation_result(claims: AccessTokenClaims) -> TokenValidationResult {
+        TokenValidationResult {
+            exp: claims.exp,
+            iss: claims.iss,
+            sub: claims.sub,
+            email: claims.email,
+            token_type: claims.token_type,
+            iat: claims.iat,
+            jti: claims.jti,
+            security_stamp: claims.security_stamp,
+            permissions: claims.permissions,
+            roles: claims.roles,
+            authorization_version: claims.authorization_version,
+        }
+    }
 }
 
 impl<CF: ConfigurationHelperTrait> TokenHelperTrait for IdentityTokenHelper<CF> {
@@ -120,10 +134,11 @@ impl<CF: ConfigurationHelperTrait> TokenHelperTrait for IdentityTokenHelper<CF>
             jti: Uuid::new_v4().to_string(),
             permissions: options.permissions.to_owned(),
             roles: options.roles.to_owned(),
+            security_stamp: options.security_stamp.to_owned(),
+            authorization_version: options.authorization_version,
         };
 
-        let secret_encoding = EncodingKey::from_secret(self._client_secret.as_bytes());
-        let token_result = encode(&Header::default(), &claims, &secret_encoding);
+        let token_result = self._keyring.encode(&claims);
 
         match token_result {
             Ok(token) => Some(TokenGenerationResult {
@@ -145,7 +160,7 @@ impl<CF: ConfigurationHelperTrait> TokenHelperTrait for IdentityTokenHelper<CF>
             return Err(ApplicationError::invalid_input("No Token"));
         }
 
-        let token_data_claims = match self.validate_token::<AccessTokenClaims>(access_token) {
+        let token_data_claims = match self.get_token_claims::<AccessTokenClaims>(access_token) {
             Ok(claims) => claims,
             Err(_) => {
                 return Err(ApplicationError::invalid_token(
@@ -154,23 +169,14 @@ impl<CF: ConfigurationHelperTrait> TokenHelperTrait for IdentityTokenHelper<CF>
             }
         };
 
-        Ok(TokenValidationResult {
-            exp: token_data_claims.exp,
-            iss: token_data_claims.iss,
-            sub: token_data_claims.sub,
-            email: token_data_claims.email,
-            token_type: token_data_claims.token_type,
-            iat: token_data_claims.iat,
-            jti: token_data_claims.jti,
-        })
+        Ok(Self::to_validation_result(token_data_claims))
     }
 
-    fn refresh_access_token(
+    fn validate_refresh_token(
         &self,
         access_token: &str,
         refresh_token: &str,
-        refresh_expiration: i64,
-    ) -> Option<TokenGenerationResult> {
+    ) -> Option<TokenValidationResult> {
         if access_token.is_empty() || refresh_token.is_empty() {
             return None;
         }
@@ -186,24 +192,19 @@ impl<CF: ConfigurationHelperTrait> TokenHelperTrait for IdentityTokenHelper<CF>
                     Err(_) => return None,
                 };
 
-                if access_claims.sub != rf_token_claims.sub {
+                if access_claims.sub != rf_token_claims.sub
+                    || access_claims.security_stamp != rf_token_claims.security_stamp
+                {
                     return None;
                 }
 
-                let purpose = UserTokenPurposes::RefreshToken.to_string();
-                let generated_token_options = TokenGenerationOptions {
-                    email: access_claims.email,
-                    user_id: access_claims.sub,
-                    exp_secs: Duration::milliseconds(refresh_expiration).num_seconds(),
-                    purpose: purpose,
-                    iat: Some(Utc::now().timestamp()),
-                    // Preserve permissions and roles from original token
-                    permissions: access_claims.permissions,
-                    roles: access_claims.roles,
-                };
-                self.generate_token(generated_token_options)
+                Some(Self::to_validation_result(access_claims))
             }
             Err(_) => None,
         }
     }
+
+    fn hash_token(&self, token: &str) -> String {
+        format!("{:x}", Sha256::digest(token.as_bytes()))
+    }
 }
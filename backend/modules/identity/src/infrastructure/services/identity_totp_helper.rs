@@ -0,0 +1,167 @@
+use crate::domain::services::totp_helper_trait::TotpHelperTrait;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use sha1::Sha1;
+use std::sync::Arc;
+
+const SECRET_BYTES: usize = 20;
+const CODE_DIGITS: u32 = 6;
+const TIME_STEP_SECS: i64 = 30;
+/// Steps accepted either side of the current one, to absorb clock drift
+const ALLOWED_DRIFT_STEPS: i64 = 1;
+const RECOVERY_CODE_COUNT: usize = 10;
+const RECOVERY_CODE_LENGTH: usize = 10;
+/// Lowercase letters and digits without the look-alikes 0, 1, l and o
+const RECOVERY_CODE_ALPHABET: &[u8] = b"23456789abcdefghijkmnpqrstuvwxyz";
+
+/// RFC 6238 time-based one-time passwords (HMAC-SHA1, 6 digits, 30 second steps), the
+/// defaults every authenticator app supports.
+#[derive(Clone)]
+pub struct IdentityTotpHelper {
+    _issuer: Arc<String>,
+}
+
+impl IdentityTotpHelper {
+    pub fn new(issuer: String) -> Self {
+        Self {
+            _issuer: Arc::new(issuer),
+        }
+    }
+
+    fn hotp(key: &[u8], counter: u64) -> u32 {
+        let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC accepts keys of any size");
+        mac.update(&counter.to_be_bytes());
+        let digest = mac.finalize().into_bytes();
+
+        // Dynamic truncation, RFC 4226 section 5.3
+        let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([
+            digest[offset] & 0x7f,
+            digest[offset + 1],
+            digest[offset + 2],
+            digest[offset + 3],
+        ]);
+        binary % 10_u32.pow(CODE_DIGITS)
+    }
+}
+
+impl TotpHelperTrait for IdentityTotpHelper {
+    fn generate_secret(&self) -> String {
+        let mut secret = [0_u8; SECRET_BYTES];
+        OsRng.fill_bytes(&mut secret);
+        BASE32_NOPAD.encode(&secret)
+    }
+
+    fn provisioning_uri(&self, secret: &str, account_name: &str) -> String {
+        let issuer = utf8_percent_encode(&self._issuer, NON_ALPHANUMERIC).to_string();
+        let account_name = utf8_percent_encode(account_name, NON_ALPHANUMERIC).to_string();
+        format!(
+            "otpauth://totp/{issuer}:{account_name}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits={CODE_DIGITS}&period={TIME_STEP_SECS}"
+        )
+    }
+
+    fn verify_code(&self, secret: &str, code: &str, timestamp: i64) -> Option<i64> {
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        if code.len() != CODE_DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let code = code.parse::<u32>().ok()?;
+        let key = BASE32_NOPAD
+            .decode(secret.trim_end_matches('=').to_uppercase().as_bytes())
+            .ok()?;
+
+        let current_step = timestamp.div_euclid(TIME_STEP_SECS);
+        (current_step - ALLOWED_DRIFT_STEPS..=current_step + ALLOWED_DRIFT_STEPS)
+            .filter(|step| *step >= 0)
+            .find(|step| Self::hotp(&key, *step as u64) == code)
+    }
+
+    fn generate_recovery_codes(&self) -> Vec<String> {
+        (0..RECOVERY_CODE_COUNT)
+            .map(|_| {
+                let mut bytes = [0_u8; RECOVERY_CODE_LENGTH];
+                OsRng.fill_bytes(&mut bytes);
+                let code: String = bytes
+                    .iter()
+                    .map(|byte| {
+                        RECOVERY_CODE_ALPHABET[*byte as usize % RECOVERY_CODE_ALPHABET.len()]
+                            as char
+                    })
+                    .collect();
+                let (first, second) = code.split_at(RECOVERY_CODE_LENGTH / 2);
+                format!("{first}-{second}")
+            })
+            .collect()
+    }
+
+    fn normalize_recovery_code(&self, code: &str) -> String {
+        code.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "12345678901234567890", the shared secret of the RFC 6238 test vectors
+    const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn test_verify_code_matches_rfc_6238_vectors() {
+        let helper = IdentityTotpHelper::new("Rex Game".to_string());
+        assert_eq!(helper.verify_code(RFC_SECRET, "287082", 59), Some(1));
+        assert_eq!(
+            helper.verify_code(RFC_SECRET, "081804", 1111111109),
+            Some(1111111109 / 30)
+        );
+        assert_eq!(
+            helper.verify_code(RFC_SECRET, "005924", 1234567890),
+            Some(1234567890 / 30)
+        );
+    }
+
+    #[test]
+    fn test_verify_code_allows_one_step_of_drift() {
+        let helper = IdentityTotpHelper::new("Rex Game".to_string());
+        assert_eq!(
+            helper.verify_code(RFC_SECRET, "081 804", 1111111109 + 30),
+            Some(1111111109 / 30)
+        );
+        assert_eq!(
+            helper.verify_code(RFC_SECRET, "081804", 1111111109 + 90),
+            None
+        );
+        assert_eq!(helper.verify_code(RFC_SECRET, "81804", 1111111109), None);
+    }
+
+    #[test]
+    fn test_generated_secret_round_trips() {
+        let helper = IdentityTotpHelper::new("Rex Game".to_string());
+        let secret = helper.generate_secret();
+        let key = BASE32_NOPAD.decode(secret.as_bytes()).unwrap();
+        let code = format!("{:06}", IdentityTotpHelper::hotp(&key, 1000));
+        assert_eq!(helper.verify_code(&secret, &code, 1000 * 30), Some(1000));
+        assert!(helper
+            .provisioning_uri(&secret, "admin@example.com")
+            .starts_with("otpauth://totp/Rex%20Game:admin%40example%2Ecom?secret="));
+    }
+
+    #[test]
+    fn test_recovery_codes_are_unique_and_normalized() {
+        let helper = IdentityTotpHelper::new("Rex Game".to_string());
+        let codes = helper.generate_recovery_codes();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+        assert!(codes
+            .iter()
+            .all(|code| code.len() == RECOVERY_CODE_LENGTH + 1));
+        assert_eq!(
+            helper.normalize_recovery_code(" AB2C3-D4EF5 "),
+            "ab2c3d4ef5".to_string()
+        );
+    }
+}
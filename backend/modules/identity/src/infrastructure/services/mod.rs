@@ -1,9 +1,15 @@
 // Identity Service Implementations
 
+pub mod identity_oidc_client;
 pub mod identity_password_hasher;
+pub mod identity_signing_keyring;
 pub mod identity_token_helper;
+pub mod identity_totp_helper;
 pub mod token_claims;
 
+pub use identity_oidc_client::{IdentityOidcClient, OidcProviderOptions};
 pub use identity_password_hasher::IdentityPasswordHasher;
+pub use identity_signing_keyring::IdentitySigningKeyring;
 pub use identity_token_helper::IdentityTokenHelper;
+pub use identity_totp_helper::IdentityTotpHelper;
 pub use token_claims::{AccessTokenClaims, HasExpiryTokenClaimTrait, RefreshTokenClaims};
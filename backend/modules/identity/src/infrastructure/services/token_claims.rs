@@ -19,6 +19,10 @@ pub struct AccessTokenClaims {
     pub permissions: Vec<String>,
     #[serde(default)]
     pub roles: Vec<String>,
+    #[serde(default)]
+    pub security_stamp: Option<String>,
+    #[serde(default)]
+    pub authorization_version: Option<i32>,
 }
 
 impl HasExpiryTokenClaimTrait for AccessTokenClaims {
@@ -36,6 +40,8 @@ pub struct RefreshTokenClaims {
     pub token_type: String,
     pub iat: i64,
     pub jti: String,
+    #[serde(default)]
+    pub security_stamp: Option<String>,
 }
 
 impl HasExpiryTokenClaimTrait for RefreshTokenClaims {
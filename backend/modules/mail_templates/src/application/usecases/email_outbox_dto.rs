@@ -0,0 +1,35 @@
+use rex_game_shared::domain::helpers::email_helper_trait::EmailMessage;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailOutboxDto {
+    pub id: i32,
+    pub to_email: String,
+    pub to_name: Option<String>,
+    pub from_email: String,
+    pub from_name: Option<String>,
+    pub subject: String,
+    pub html_body: Option<String>,
+    pub text_body: Option<String>,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: String,
+    pub sent_on: Option<String>,
+    pub created_on: String,
+    pub updated_on: String,
+}
+
+impl EmailOutboxDto {
+    pub fn into_message(self) -> EmailMessage {
+        EmailMessage {
+            to_name: self.to_name,
+            to_email: self.to_email,
+            from_name: self.from_name,
+            from_email: self.from_email,
+            subject: self.subject,
+            text_body: self.text_body,
+            html_body: self.html_body,
+        }
+    }
+}
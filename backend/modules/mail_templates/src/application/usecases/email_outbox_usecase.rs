@@ -0,0 +1,183 @@
+use super::{EmailOutboxDto, EmailOutboxUseCaseTrait};
+use crate::{
+    domain::models::{EmailOutboxModel, EmailOutboxStatuses},
+    infrastructure::repositories::EmailOutboxRepository,
+};
+use chrono::{Duration, Utc};
+use rex_game_shared::{
+    domain::{
+        helpers::email_helper_trait::EmailMessage, models::page_list_model::PageListModel,
+        transaction_manager_trait::TransactionWrapperTrait,
+    },
+    InfraError,
+};
+
+/// Attempts before an email is moved to `dead`
+pub const MAX_EMAIL_ATTEMPTS: i32 = 8;
+const FIRST_RETRY_DELAY_SECS: i64 = 30;
+const MAX_RETRY_DELAY_SECS: i64 = 60 * 60;
+/// How long a claimed email stays hidden from other workers while it is sent
+const CLAIM_LEASE_SECS: i64 = 5 * 60;
+
+/// Delay before the next attempt after `attempts` failed ones: 30s, 1m, 2m, ... capped
+/// at an hour. `None` once the attempts are used up.
+pub fn retry_delay(attempts: i32) -> Option<Duration> {
+    if attempts >= MAX_EMAIL_ATTEMPTS {
+        return None;
+    }
+    let exponent = (attempts - 1).clamp(0, 16) as u32;
+    let delay = FIRST_RETRY_DELAY_SECS.saturating_mul(2_i64.pow(exponent));
+    Some(Duration::seconds(delay.min(MAX_RETRY_DELAY_SECS)))
+}
+
+#[derive(Clone)]
+pub struct EmailOutboxUseCase {
+    _email_outbox_repository: EmailOutboxRepository,
+}
+
+impl EmailOutboxUseCase {
+    pub fn new(email_outbox_repository: EmailOutboxRepository) -> Self {
+        Self {
+            _email_outbox_repository: email_outbox_repository,
+        }
+    }
+}
+
+impl EmailOutboxUseCaseTrait for EmailOutboxUseCase {
+    async fn enqueue(&self, message: EmailMessage) -> Result<i32, InfraError> {
+        self._email_outbox_repository
+            .create(map_message_to_model(message))
+            .await
+    }
+
+    async fn enqueue_with_transaction(
+        &self,
+        message: EmailMessage,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<i32, InfraError> {
+        self._email_outbox_repository
+            .create_without_commit(map_message_to_model(message), transaction)
+            .await
+    }
+
+    async fn claim_due(&self, limit: u64) -> Result<Vec<EmailOutboxDto>, InfraError> {
+        let now = Utc::now();
+        let lease_until = now + Duration::seconds(CLAIM_LEASE_SECS);
+        let due = self._email_outbox_repository.get_due(now, limit).await?;
+
+        let mut claimed = Vec::with_capacity(due.len());
+        for email in due {
+            if self
+                ._email_outbox_repository
+                .claim(email.id, email.next_attempt_at, lease_until)
+                .await?
+            {
+                claimed.push(map_model_to_dto(email));
+            }
+        }
+        Ok(claimed)
+    }
+
+    async fn record_sent(&self, id: i32, attempts: i32) -> Result<(), InfraError> {
+        self._email_outbox_repository.mark_sent(id, attempts).await
+    }
+
+    async fn record_failure(
+        &self,
+        id: i32,
+        attempts: i32,
+        error: String,
+    ) -> Result<(), InfraError> {
+        let next_attempt_at = retry_delay(attempts).map(|delay| Utc::now() + delay);
+        self._email_outbox_repository
+            .mark_failed(id, attempts, &error, next_attempt_at)
+            .await
+    }
+
+    async fn get_list(
+        &self,
+        status: Option<EmailOutboxStatuses>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<PageListModel<EmailOutboxDto>, InfraError> {
+        let result = self
+            ._email_outbox_repository
+            .get_paged_list(status, page, page_size)
+            .await?;
+
+        Ok(PageListModel {
+            items: result.items.into_iter().map(map_model_to_dto).collect(),
+            total_count: result.total_count,
+        })
+    }
+
+    async fn get_by_id(&self, id: i32) -> Result<EmailOutboxDto, InfraError> {
+        let model = self._email_outbox_repository.get_by_id(id).await?;
+        Ok(map_model_to_dto(model))
+    }
+
+    async fn retry(&self, id: i32) -> Result<bool, InfraError> {
+        self._email_outbox_repository.get_by_id(id).await?;
+        self._email_outbox_repository.requeue(id).await
+    }
+}
+
+fn map_message_to_model(message: EmailMessage) -> EmailOutboxModel {
+    let now = Utc::now();
+    EmailOutboxModel {
+        id: 0,
+        to_email: message.to_email,
+        to_name: message.to_name,
+        from_email: message.from_email,
+        from_name: message.from_name,
+        subject: message.subject,
+        html_body: message.html_body,
+        text_body: message.text_body,
+        status: EmailOutboxStatuses::Pending,
+        attempts: 0,
+        last_error: None,
+        next_attempt_at: now,
+        sent_on: None,
+        created_on: now,
+        updated_on: now,
+    }
+}
+
+fn map_model_to_dto(model: EmailOutboxModel) -> EmailOutboxDto {
+    EmailOutboxDto {
+        id: model.id,
+        to_email: model.to_email,
+        to_name: model.to_name,
+        from_email: model.from_email,
+        from_name: model.from_name,
+        subject: model.subject,
+        html_body: model.html_body,
+        text_body: model.text_body,
+        status: model.status.as_str().to_string(),
+        attempts: model.attempts,
+        last_error: model.last_error,
+        next_attempt_at: model.next_attempt_at.to_rfc3339(),
+        sent_on: model.sent_on.map(|sent_on| sent_on.to_rfc3339()),
+        created_on: model.created_on.to_rfc3339(),
+        updated_on: model.updated_on.to_rfc3339(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_an_hour() {
+        assert_eq!(retry_delay(1), Some(Duration::seconds(30)));
+        assert_eq!(retry_delay(2), Some(Duration::seconds(60)));
+        assert_eq!(retry_delay(4), Some(Duration::seconds(240)));
+        assert_eq!(retry_delay(7), Some(Duration::seconds(1920)));
+    }
+
+    #[test]
+    fn test_retry_delay_stops_after_the_last_attempt() {
+        assert_eq!(retry_delay(MAX_EMAIL_ATTEMPTS), None);
+        assert_eq!(retry_delay(MAX_EMAIL_ATTEMPTS + 1), None);
+    }
+}
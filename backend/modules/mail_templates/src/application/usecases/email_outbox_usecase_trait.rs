@@ -0,0 +1,57 @@
+use super::EmailOutboxDto;
+use crate::domain::models::EmailOutboxStatuses;
+use rex_game_shared::{
+    domain::{
+        helpers::email_helper_trait::EmailMessage, models::page_list_model::PageListModel,
+        transaction_manager_trait::TransactionWrapperTrait,
+    },
+    InfraError,
+};
+use std::future::Future;
+
+pub trait EmailOutboxUseCaseTrait {
+    /// Queues an email for the background worker.
+    fn enqueue(&self, message: EmailMessage) -> impl Future<Output = Result<i32, InfraError>>;
+
+    /// Queues an email as part of `transaction`, so it is only sent if the transaction
+    /// commits.
+    fn enqueue_with_transaction(
+        &self,
+        message: EmailMessage,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> impl Future<Output = Result<i32, InfraError>>;
+
+    /// Claims up to `limit` due emails for sending. Claimed emails are not handed out
+    /// again until their lease runs out.
+    fn claim_due(
+        &self,
+        limit: u64,
+    ) -> impl Future<Output = Result<Vec<EmailOutboxDto>, InfraError>> + Send;
+
+    fn record_sent(
+        &self,
+        id: i32,
+        attempts: i32,
+    ) -> impl Future<Output = Result<(), InfraError>> + Send;
+
+    /// Schedules the next attempt with exponential backoff, or moves the email to `dead`
+    /// once the attempts are used up.
+    fn record_failure(
+        &self,
+        id: i32,
+        attempts: i32,
+        error: String,
+    ) -> impl Future<Output = Result<(), InfraError>> + Send;
+
+    fn get_list(
+        &self,
+        status: Option<EmailOutboxStatuses>,
+        page: u64,
+        page_size: u64,
+    ) -> impl Future<Output = Result<PageListModel<EmailOutboxDto>, InfraError>>;
+
+    fn get_by_id(&self, id: i32) -> impl Future<Output = Result<EmailOutboxDto, InfraError>>;
+
+    /// Sends a dead email again. Returns false when the email is not dead.
+    fn retry(&self, id: i32) -> impl Future<Output = Result<bool, InfraError>>;
+}
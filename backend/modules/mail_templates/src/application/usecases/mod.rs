@@ -1,3 +1,6 @@
+pub mod email_outbox_dto;
+pub mod email_outbox_usecase;
+pub mod email_outbox_usecase_trait;
 pub mod mail_template_creation_dto;
 pub mod mail_template_deletion_dto;
 pub mod mail_template_dto;
@@ -5,6 +8,9 @@ pub mod mail_template_updation_dto;
 pub mod mail_template_usecase;
 pub mod mail_template_usecase_trait;
 
+pub use email_outbox_dto::EmailOutboxDto;
+pub use email_outbox_usecase::EmailOutboxUseCase;
+pub use email_outbox_usecase_trait::EmailOutboxUseCaseTrait;
 pub use mail_template_creation_dto::MailTemplateCreationDto;
 pub use mail_template_deletion_dto::MailTemplateDeletionDto;
 pub use mail_template_dto::MailTemplateDto;
@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailOutboxStatuses {
+    Pending,
+    Sent,
+    /// Gave up after the last attempt; only an admin retry sends it again
+    Dead,
+}
+
+impl EmailOutboxStatuses {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmailOutboxStatuses::Pending => "pending",
+            EmailOutboxStatuses::Sent => "sent",
+            EmailOutboxStatuses::Dead => "dead",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(EmailOutboxStatuses::Pending),
+            "sent" => Some(EmailOutboxStatuses::Sent),
+            "dead" => Some(EmailOutboxStatuses::Dead),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct EmailOutboxModel {
+    pub id: i32,
+    pub to_email: String,
+    pub to_name: Option<String>,
+    pub from_email: String,
+    pub from_name: Option<String>,
+    pub subject: String,
+    pub html_body: Option<String>,
+    pub text_body: Option<String>,
+    pub status: EmailOutboxStatuses,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub sent_on: Option<DateTime<Utc>>,
+    pub created_on: DateTime<Utc>,
+    pub updated_on: DateTime<Utc>,
+}
@@ -1,3 +1,5 @@
+pub mod email_outbox_model;
 pub mod mail_template_model;
 
+pub use email_outbox_model::*;
 pub use mail_template_model::*;
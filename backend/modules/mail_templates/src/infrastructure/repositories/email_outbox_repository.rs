@@ -0,0 +1,250 @@
+use crate::domain::models::{EmailOutboxModel, EmailOutboxStatuses};
+use chrono::{DateTime, Utc};
+use rex_game_entities::entities::email_outbox::{self, Entity as EmailOutbox};
+use rex_game_shared::domain::models::page_list_model::PageListModel;
+use rex_game_shared::domain::transaction_manager_trait::TransactionWrapperTrait;
+use rex_game_shared::infrastructure::database::SeaOrmTransactionWrapper;
+use rex_game_shared::InfraError;
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct EmailOutboxRepository {
+    _db_connection: Arc<DatabaseConnection>,
+}
+
+impl EmailOutboxRepository {
+    pub fn new(db_connection: Arc<DatabaseConnection>) -> Self {
+        Self {
+            _db_connection: db_connection,
+        }
+    }
+
+    pub async fn create(&self, email_req: EmailOutboxModel) -> Result<i32, InfraError> {
+        let db = self._db_connection.as_ref();
+        self::insert(db, email_req).await
+    }
+
+    pub async fn create_without_commit(
+        &self,
+        email_req: EmailOutboxModel,
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<i32, InfraError> {
+        let transact = match transaction
+            .as_ref()
+            .as_any()
+            .downcast_ref::<SeaOrmTransactionWrapper>()
+        {
+            Some(i) => i,
+            None => return Err(InfraError::database("Unable to cast the transaction")),
+        };
+        self::insert(transact.txn.as_ref().unwrap(), email_req).await
+    }
+
+    /// Pending emails whose next attempt is due, oldest first.
+    pub async fn get_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<EmailOutboxModel>, InfraError> {
+        let db = self._db_connection.as_ref();
+        let items = EmailOutbox::find()
+            .filter(email_outbox::Column::Status.eq(EmailOutboxStatuses::Pending.as_str()))
+            .filter(email_outbox::Column::NextAttemptAt.lte(now.fixed_offset()))
+            .order_by_asc(email_outbox::Column::NextAttemptAt)
+            .limit(limit)
+            .all(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string()))?;
+
+        Ok(items.into_iter().map(self::map_entity_to_model).collect())
+    }
+
+    /// Pushes `next_attempt_at` out to `lease_until` so no other worker picks the email
+    /// up while it is being sent. Returns false when another worker claimed it first.
+    pub async fn claim(
+        &self,
+        id: i32,
+        due_at: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> Result<bool, InfraError> {
+        let db = self._db_connection.as_ref();
+        let result = EmailOutbox::update_many()
+            .col_expr(
+                email_outbox::Column::NextAttemptAt,
+                Expr::value(lease_until.fixed_offset()),
+            )
+            .filter(email_outbox::Column::Id.eq(id))
+            .filter(email_outbox::Column::Status.eq(EmailOutboxStatuses::Pending.as_str()))
+            .filter(email_outbox::Column::NextAttemptAt.eq(due_at.fixed_offset()))
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    pub async fn mark_sent(&self, id: i32, attempts: i32) -> Result<(), InfraError> {
+        let db = self._db_connection.as_ref();
+        let now = Utc::now().fixed_offset();
+        EmailOutbox::update_many()
+            .col_expr(
+                email_outbox::Column::Status,
+                Expr::value(EmailOutboxStatuses::Sent.as_str()),
+            )
+            .col_expr(email_outbox::Column::Attempts, Expr::value(attempts))
+            .col_expr(email_outbox::Column::SentOn, Expr::value(now))
+            .col_expr(email_outbox::Column::UpdatedOn, Expr::value(now))
+            .filter(email_outbox::Column::Id.eq(id))
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string()))?;
+        Ok(())
+    }
+
+    /// Records a failed attempt. Without `next_attempt_at` the email is moved to `dead`.
+    pub async fn mark_failed(
+        &self,
+        id: i32,
+        attempts: i32,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<(), InfraError> {
+        let db = self._db_connection.as_ref();
+        let now = Utc::now();
+        let status = match next_attempt_at {
+            Some(_) => EmailOutboxStatuses::Pending,
+            None => EmailOutboxStatuses::Dead,
+        };
+        EmailOutbox::update_many()
+            .col_expr(email_outbox::Column::Status, Expr::value(status.as_str()))
+            .col_expr(email_outbox::Column::Attempts, Expr::value(attempts))
+            .col_expr(email_outbox::Column::LastError, Expr::value(error))
+            .col_expr(
+                email_outbox::Column::NextAttemptAt,
+                Expr::value(next_attempt_at.unwrap_or(now).fixed_offset()),
+            )
+            .col_expr(
+                email_outbox::Column::UpdatedOn,
+                Expr::value(now.fixed_offset()),
+            )
+            .filter(email_outbox::Column::Id.eq(id))
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string()))?;
+        Ok(())
+    }
+
+    /// Puts a dead email back in the queue with a fresh attempt budget. Returns false
+    /// when the email is not dead.
+    pub async fn requeue(&self, id: i32) -> Result<bool, InfraError> {
+        let db = self._db_connection.as_ref();
+        let now = Utc::now().fixed_offset();
+        let result = EmailOutbox::update_many()
+            .col_expr(
+                email_outbox::Column::Status,
+                Expr::value(EmailOutboxStatuses::Pending.as_str()),
+            )
+            .col_expr(email_outbox::Column::Attempts, Expr::value(0))
+            .col_expr(email_outbox::Column::NextAttemptAt, Expr::value(now))
+            .col_expr(email_outbox::Column::UpdatedOn, Expr::value(now))
+            .filter(email_outbox::Column::Id.eq(id))
+            .filter(email_outbox::Column::Status.eq(EmailOutboxStatuses::Dead.as_str()))
+            .exec(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    pub async fn get_by_id(&self, id: i32) -> Result<EmailOutboxModel, InfraError> {
+        let db = self._db_connection.as_ref();
+        EmailOutbox::find_by_id(id)
+            .one(db)
+            .await
+            .map_err(|err| InfraError::database(err.to_string()))?
+            .map(self::map_entity_to_model)
+            .ok_or(InfraError::not_found("EmailOutbox", id.to_string()))
+    }
+
+    pub async fn get_paged_list(
+        &self,
+        status: Option<EmailOutboxStatuses>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<PageListModel<EmailOutboxModel>, InfraError> {
+        let db = self._db_connection.as_ref();
+        let mut query = EmailOutbox::find();
+        if let Some(status) = status {
+            query = query.filter(email_outbox::Column::Status.eq(status.as_str()));
+        }
+
+        let paginator = query
+            .order_by_desc(email_outbox::Column::UpdatedOn)
+            .paginate(db, page_size);
+        let total_count = paginator
+            .num_items()
+            .await
+            .map_err(|err| InfraError::database(err.to_string()))?;
+        let items = paginator
+            .fetch_page(page.saturating_sub(1))
+            .await
+            .map_err(|err| InfraError::database(err.to_string()))?;
+
+        Ok(PageListModel {
+            items: items.into_iter().map(self::map_entity_to_model).collect(),
+            total_count,
+        })
+    }
+}
+
+async fn insert<C: ConnectionTrait>(
+    db: &C,
+    email_req: EmailOutboxModel,
+) -> Result<i32, InfraError> {
+    let now = Utc::now().fixed_offset();
+    let email = email_outbox::ActiveModel {
+        to_email: Set(email_req.to_email),
+        to_name: Set(email_req.to_name),
+        from_email: Set(email_req.from_email),
+        from_name: Set(email_req.from_name),
+        subject: Set(email_req.subject),
+        html_body: Set(email_req.html_body),
+        text_body: Set(email_req.text_body),
+        status: Set(EmailOutboxStatuses::Pending.as_str().to_string()),
+        attempts: Set(0),
+        next_attempt_at: Set(now),
+        created_on: Set(now),
+        updated_on: Set(now),
+        ..Default::default()
+    };
+
+    EmailOutbox::insert(email)
+        .exec(db)
+        .await
+        .map(|result| result.last_insert_id)
+        .map_err(|err| InfraError::database(err.to_string()))
+}
+
+fn map_entity_to_model(email: email_outbox::Model) -> EmailOutboxModel {
+    EmailOutboxModel {
+        id: email.id,
+        to_email: email.to_email,
+        to_name: email.to_name,
+        from_email: email.from_email,
+        from_name: email.from_name,
+        subject: email.subject,
+        html_body: email.html_body,
+        text_body: email.text_body,
+        status: EmailOutboxStatuses::parse(&email.status).unwrap_or(EmailOutboxStatuses::Pending),
+        attempts: email.attempts,
+        last_error: email.last_error,
+        next_attempt_at: email.next_attempt_at.with_timezone(&Utc),
+        sent_on: email.sent_on.map(|sent_on| sent_on.with_timezone(&Utc)),
+        created_on: email.created_on.with_timezone(&Utc),
+        updated_on: email.updated_on.with_timezone(&Utc),
+    }
+}
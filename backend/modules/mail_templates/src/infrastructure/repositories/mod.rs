@@ -1,3 +1,5 @@
+pub mod email_outbox_repository;
 pub mod mail_template_repository;
 
+pub use email_outbox_repository::*;
 pub use mail_template_repository::*;
@@ -43,6 +43,9 @@ pub enum ApplicationError {
     /// Token expired or invalid
     InvalidToken(String),
 
+    /// Too many failed attempts; the next one is accepted after this many seconds
+    TooManyAttempts { retry_after_secs: i64 },
+
     // ========================================================================
     // State Errors
     // ========================================================================
@@ -100,6 +103,10 @@ impl ApplicationError {
         Self::InvalidToken(message.into())
     }
 
+    pub fn too_many_attempts(retry_after_secs: i64) -> Self {
+        Self::TooManyAttempts { retry_after_secs }
+    }
+
     pub fn invalid_state(message: impl Into<String>) -> Self {
         Self::InvalidState(message.into())
     }
@@ -124,6 +131,11 @@ impl fmt::Display for ApplicationError {
             Self::Forbidden(msg) => write!(f, "Forbidden: {}", msg),
             Self::InvalidCredentials => write!(f, "Invalid credentials"),
             Self::InvalidToken(msg) => write!(f, "Invalid token: {}", msg),
+            Self::TooManyAttempts { retry_after_secs } => write!(
+                f,
+                "Too many failed attempts, try again in {} seconds",
+                retry_after_secs
+            ),
             Self::InvalidState(msg) => write!(f, "Invalid state: {}", msg),
             Self::Conflict(msg) => write!(f, "Conflict: {}", msg),
             Self::Infrastructure(err) => write!(f, "Infrastructure error: {}", err),
@@ -142,3 +154,9 @@ impl From<InfraError> for ApplicationError {
         }
     }
 }
+
+impl From<sea_orm::DbErr> for ApplicationError {
+    fn from(err: sea_orm::DbErr) -> Self {
+        Self::Infrastructure(InfraError::from(err))
+    }
+}
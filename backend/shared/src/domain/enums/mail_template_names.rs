@@ -5,4 +5,8 @@ impl MailTemplateNames {
     pub const USER_REGISTRATION_COMPLETION: &'static str = "user_registration_completion";
     pub const PASSWORD_RESET_REQUEST: &'static str = "password_reset_request";
     pub const PASSWORD_RESET_CONFIRMATION: &'static str = "password_reset_confirmation";
+    pub const ACCOUNT_LOCKED: &'static str = "account_locked";
+    pub const ACCOUNT_UNLOCKED: &'static str = "account_unlocked";
+    pub const MAGIC_LINK_LOGIN: &'static str = "magic_link_login";
+    pub const STREAK_REMINDER: &'static str = "streak_reminder";
 }
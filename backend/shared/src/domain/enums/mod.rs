@@ -1,3 +1,5 @@
 pub mod mail_template_names;
 pub mod permission_codes;
+pub mod user_kinds;
+pub mod user_statuses;
 pub mod user_token_porposes;
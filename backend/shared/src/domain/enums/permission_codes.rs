@@ -35,11 +35,15 @@ pub enum PermissionCodes {
     FlashcardTypeCreate,
     FlashcardTypeDelete,
     FlashcardTypeUpdate,
+    FlashcardTypeDeleteOwn,
+    FlashcardTypeUpdateOwn,
     // Flashcard
     FlashcardRead,
     FlashcardCreate,
     FlashcardDelete,
     FlashcardUpdate,
+    FlashcardDeleteOwn,
+    FlashcardUpdateOwn,
     // Flashcard File
     FlashcardFileRead,
     FlashcardFileCreate,
@@ -105,11 +109,15 @@ impl PermissionCodes {
             PermissionCodes::FlashcardTypeCreate => "flashcard_type:create",
             PermissionCodes::FlashcardTypeDelete => "flashcard_type:delete",
             PermissionCodes::FlashcardTypeUpdate => "flashcard_type:update",
+            PermissionCodes::FlashcardTypeDeleteOwn => "flashcard_type:delete_own",
+            PermissionCodes::FlashcardTypeUpdateOwn => "flashcard_type:update_own",
 
             PermissionCodes::FlashcardRead => "flashcard:read",
             PermissionCodes::FlashcardCreate => "flashcard:create",
             PermissionCodes::FlashcardDelete => "flashcard:delete",
             PermissionCodes::FlashcardUpdate => "flashcard:update",
+            PermissionCodes::FlashcardDeleteOwn => "flashcard:delete_own",
+            PermissionCodes::FlashcardUpdateOwn => "flashcard:update_own",
 
             PermissionCodes::FlashcardFileRead => "flashcard_file:read",
             PermissionCodes::FlashcardFileCreate => "flashcard_file:create",
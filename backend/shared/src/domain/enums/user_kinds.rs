@@ -0,0 +1,19 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserKinds {
+    /// A person who signs in
+    Person = 1,
+    /// Used by integrations and scripts through API keys; it cannot sign in
+    ServiceAccount = 2,
+}
+
+impl TryFrom<i32> for UserKinds {
+    type Error = ();
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(UserKinds::Person),
+            2 => Ok(UserKinds::ServiceAccount),
+            _ => Err(()),
+        }
+    }
+}
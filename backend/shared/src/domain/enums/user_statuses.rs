@@ -0,0 +1,25 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserStatuses {
+    Pending = 1,
+    Actived = 2,
+    Deleted = 3,
+    /// Locked out by an administrator, optionally until a given time
+    Suspended = 4,
+    /// Locked out by an administrator for good
+    Banned = 5,
+}
+
+impl TryFrom<i32> for UserStatuses {
+    type Error = ();
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(UserStatuses::Pending),
+            2 => Ok(UserStatuses::Actived),
+            3 => Ok(UserStatuses::Deleted),
+            4 => Ok(UserStatuses::Suspended),
+            5 => Ok(UserStatuses::Banned),
+            _ => Err(()),
+        }
+    }
+}
@@ -3,6 +3,8 @@ pub enum UserTokenPurposes {
     RefreshToken = 2,
     ForgotPassword = 3,
     SignupConfirmation = 4,
+    MfaPending = 5,
+    MagicLink = 6,
 }
 
 impl UserTokenPurposes {
@@ -12,6 +14,8 @@ impl UserTokenPurposes {
             UserTokenPurposes::RefreshToken => "refresh_token",
             UserTokenPurposes::ForgotPassword => "forgot_password",
             UserTokenPurposes::SignupConfirmation => "signup_confirmation",
+            UserTokenPurposes::MfaPending => "mfa_pending",
+            UserTokenPurposes::MagicLink => "magic_link",
         }
     }
 
@@ -0,0 +1,11 @@
+use crate::InfraError;
+use async_trait::async_trait;
+
+/// Binary object storage addressed by key, such as a local folder or an S3 bucket.
+#[async_trait]
+pub trait BlobStoreTrait: Send + Sync {
+    async fn put(&self, key: &str, data: &[u8], content_type: &str) -> Result<(), InfraError>;
+    async fn get(&self, key: &str) -> Result<Vec<u8>, InfraError>;
+    /// Deleting a missing key is not an error.
+    async fn delete(&self, key: &str) -> Result<(), InfraError>;
+}
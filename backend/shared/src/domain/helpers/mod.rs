@@ -1,3 +1,4 @@
+pub mod blob_store_trait;
 pub mod configuration_helper_trait;
 pub mod email_helper_trait;
 pub mod file_helper_trait;
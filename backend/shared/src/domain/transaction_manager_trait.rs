@@ -2,7 +2,7 @@ use std::any::Any;
 
 use crate::InfraError;
 
-pub trait TransactionWrapperTrait {
+pub trait TransactionWrapperTrait: Send + Sync {
     fn as_any(&self) -> &dyn Any;
 }
 
@@ -0,0 +1,76 @@
+use crate::{
+    domain::helpers::blob_store_trait::BlobStoreTrait,
+    infrastructure::helpers::{
+        configuration_helper::ConfigurationHelper, local_blob_store::LocalBlobStore,
+        s3_blob_store::S3BlobStore,
+    },
+};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+const DEFAULT_LOCAL_PATH: &str = "uploads/blobs";
+const DEFAULT_S3_REGION: &str = "us-east-1";
+
+/// Blob store backends supported by the application
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlobStoreType {
+    Local,
+    S3,
+}
+
+impl From<&str> for BlobStoreType {
+    fn from(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "s3" => BlobStoreType::S3,
+            _ => BlobStoreType::Local,
+        }
+    }
+}
+
+/// Factory for creating the blob store based on configuration
+pub struct BlobStoreFactory;
+
+impl BlobStoreFactory {
+    /// Get the configured blob store type from configuration
+    pub fn get_store_type(config: &ConfigurationHelper) -> BlobStoreType {
+        BlobStoreType::from(config.get_optional("BLOB_STORE_PROVIDER").as_str())
+    }
+
+    /// Create the configured blob store. S3 settings are required when the provider is `s3`.
+    pub fn create(config: &ConfigurationHelper) -> Arc<dyn BlobStoreTrait> {
+        match Self::get_store_type(config) {
+            BlobStoreType::S3 => {
+                let region = config.get_optional("BLOB_STORE_S3_REGION");
+                Arc::new(S3BlobStore::new(
+                    &config.get("BLOB_STORE_S3_ENDPOINT"),
+                    &config.get("BLOB_STORE_S3_BUCKET"),
+                    if region.is_empty() {
+                        DEFAULT_S3_REGION
+                    } else {
+                        &region
+                    },
+                    &config.get("BLOB_STORE_S3_ACCESS_KEY_ID"),
+                    &config.get("BLOB_STORE_S3_SECRET_ACCESS_KEY"),
+                ))
+            }
+            BlobStoreType::Local => {
+                let path = config.get_optional("BLOB_STORE_LOCAL_PATH");
+                Arc::new(LocalBlobStore::new(if path.is_empty() {
+                    DEFAULT_LOCAL_PATH.to_string()
+                } else {
+                    path
+                }))
+            }
+        }
+    }
+
+    /// New unique key for a blob under `prefix`, e.g. `flashcards/3f2c...`.
+    pub fn new_key(prefix: &str) -> String {
+        format!("{}/{}", prefix, uuid::Uuid::new_v4().simple())
+    }
+
+    /// Hex SHA-256 of the blob, stored next to its key to detect corruption.
+    pub fn checksum(data: &[u8]) -> String {
+        hex::encode(Sha256::digest(data))
+    }
+}
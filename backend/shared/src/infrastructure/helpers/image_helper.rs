@@ -0,0 +1,267 @@
+use crate::InfraError;
+use image::{
+    codecs::{jpeg::JpegEncoder, png::PngEncoder, webp::WebPEncoder},
+    DynamicImage, ImageDecoder, ImageFormat, ImageReader,
+};
+use std::io::Cursor;
+
+const JPEG_QUALITY: u8 = 85;
+
+/// The sizes images are served at, bounded by their longest side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageVariantSize {
+    Thumb,
+    Medium,
+    Original,
+}
+
+impl ImageVariantSize {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "thumb" => Some(Self::Thumb),
+            "medium" => Some(Self::Medium),
+            "original" => Some(Self::Original),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Thumb => "thumb",
+            Self::Medium => "medium",
+            Self::Original => "original",
+        }
+    }
+
+    pub fn max_dimension(&self) -> u32 {
+        match self {
+            Self::Thumb => 256,
+            Self::Medium => 768,
+            Self::Original => 2048,
+        }
+    }
+}
+
+/// The encodings a variant can be requested in. `Fallback` resolves to PNG for images
+/// with transparency and to JPEG otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageVariantFormat {
+    WebP,
+    Fallback,
+}
+
+impl ImageVariantFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::WebP => "webp",
+            Self::Fallback => "fallback",
+        }
+    }
+
+    /// Picks WebP when the `Accept` header lists it with a non-zero quality.
+    pub fn from_accept(accept: Option<&str>) -> Self {
+        let accepts_webp = accept
+            .map(|accept| {
+                accept.split(',').any(|range| {
+                    let mut parts = range.split(';');
+                    let media_type = parts.next().unwrap_or_default().trim();
+                    let rejected = parts.any(|param| {
+                        param
+                            .trim()
+                            .strip_prefix("q=")
+                            .and_then(|quality| quality.trim().parse::<f32>().ok())
+                            .map(|quality| quality <= 0.0)
+                            .unwrap_or(false)
+                    });
+                    media_type.eq_ignore_ascii_case("image/webp") && !rejected
+                })
+            })
+            .unwrap_or(false);
+        if accepts_webp {
+            Self::WebP
+        } else {
+            Self::Fallback
+        }
+    }
+}
+
+pub struct ImageVariant {
+    pub data: Vec<u8>,
+    pub content_type: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+pub struct ImageHelper {}
+
+impl ImageHelper {
+    /// Re-encodes an upload in its own format so EXIF and other metadata are dropped and
+    /// the pixels are rotated upright. GIFs are kept as they are so animations survive.
+    pub fn sanitize(data: &[u8], content_type: &str) -> Result<Vec<u8>, InfraError> {
+        let format = match content_type {
+            "image/jpeg" | "image/jpg" => ImageFormat::Jpeg,
+            "image/png" => ImageFormat::Png,
+            "image/webp" => ImageFormat::WebP,
+            _ => return Ok(data.to_vec()),
+        };
+        let image = Self::decode(data)?;
+        Self::encode(&image, format)
+    }
+
+    /// Same as `sanitize`, off the async runtime since decoding is CPU bound.
+    pub async fn sanitize_blocking(
+        data: Vec<u8>,
+        content_type: String,
+    ) -> Result<Vec<u8>, InfraError> {
+        tokio::task::spawn_blocking(move || Self::sanitize(&data, &content_type))
+            .await
+            .map_err(|err| InfraError::file_storage(err.to_string()))?
+    }
+
+    pub fn render_variant(
+        data: &[u8],
+        size: ImageVariantSize,
+        format: ImageVariantFormat,
+    ) -> Result<ImageVariant, InfraError> {
+        let mut image = Self::decode(data)?;
+        let max_dimension = size.max_dimension();
+        if image.width() > max_dimension || image.height() > max_dimension {
+            image = image.thumbnail(max_dimension, max_dimension);
+        }
+
+        let format = match format {
+            ImageVariantFormat::WebP => ImageFormat::WebP,
+            ImageVariantFormat::Fallback if image.color().has_alpha() => ImageFormat::Png,
+            ImageVariantFormat::Fallback => ImageFormat::Jpeg,
+        };
+        Ok(ImageVariant {
+            data: Self::encode(&image, format)?,
+            content_type: format.to_mime_type().to_string(),
+            width: image.width(),
+            height: image.height(),
+        })
+    }
+
+    /// Same as `render_variant`, off the async runtime.
+    pub async fn render_variant_blocking(
+        data: Vec<u8>,
+        size: ImageVariantSize,
+        format: ImageVariantFormat,
+    ) -> Result<ImageVariant, InfraError> {
+        tokio::task::spawn_blocking(move || Self::render_variant(&data, size, format))
+            .await
+            .map_err(|err| InfraError::file_storage(err.to_string()))?
+    }
+
+    fn decode(data: &[u8]) -> Result<DynamicImage, InfraError> {
+        let mut decoder = ImageReader::new(Cursor::new(data))
+            .with_guessed_format()
+            .map_err(invalid_image)?
+            .into_decoder()
+            .map_err(invalid_image)?;
+        let orientation = decoder.orientation().map_err(invalid_image)?;
+        let mut image = DynamicImage::from_decoder(decoder).map_err(invalid_image)?;
+        image.apply_orientation(orientation);
+        Ok(image)
+    }
+
+    fn encode(image: &DynamicImage, format: ImageFormat) -> Result<Vec<u8>, InfraError> {
+        let mut buffer = Vec::new();
+        let has_alpha = image.color().has_alpha();
+        let result = match format {
+            ImageFormat::Jpeg => DynamicImage::ImageRgb8(image.to_rgb8())
+                .write_with_encoder(JpegEncoder::new_with_quality(&mut buffer, JPEG_QUALITY)),
+            ImageFormat::WebP if has_alpha => DynamicImage::ImageRgba8(image.to_rgba8())
+                .write_with_encoder(WebPEncoder::new_lossless(&mut buffer)),
+            ImageFormat::WebP => DynamicImage::ImageRgb8(image.to_rgb8())
+                .write_with_encoder(WebPEncoder::new_lossless(&mut buffer)),
+            _ => image.write_with_encoder(PngEncoder::new(&mut buffer)),
+        };
+        result
+            .map_err(|err| InfraError::file_storage(format!("Failed to encode image: {}", err)))?;
+        Ok(buffer)
+    }
+}
+
+fn invalid_image(err: impl std::fmt::Display) -> InfraError {
+    InfraError::file_storage(format!("Invalid image: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage, Rgba, RgbaImage};
+
+    fn png_of(image: DynamicImage) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut buffer), ImageFormat::Png)
+            .unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_accept_header_negotiates_webp() {
+        assert_eq!(
+            ImageVariantFormat::from_accept(Some("image/avif,image/webp,*/*;q=0.8")),
+            ImageVariantFormat::WebP
+        );
+        assert_eq!(
+            ImageVariantFormat::from_accept(Some("image/webp;q=0, image/png")),
+            ImageVariantFormat::Fallback
+        );
+        assert_eq!(
+            ImageVariantFormat::from_accept(None),
+            ImageVariantFormat::Fallback
+        );
+    }
+
+    #[test]
+    fn test_variants_are_scaled_to_fit() {
+        let data = png_of(DynamicImage::ImageRgb8(RgbImage::from_pixel(
+            1024,
+            512,
+            Rgb([10, 20, 30]),
+        )));
+
+        let thumb =
+            ImageHelper::render_variant(&data, ImageVariantSize::Thumb, ImageVariantFormat::WebP)
+                .unwrap();
+        assert_eq!((thumb.width, thumb.height), (256, 128));
+        assert_eq!(thumb.content_type, "image/webp");
+
+        let original = ImageHelper::render_variant(
+            &data,
+            ImageVariantSize::Original,
+            ImageVariantFormat::Fallback,
+        )
+        .unwrap();
+        assert_eq!((original.width, original.height), (1024, 512));
+        assert_eq!(original.content_type, "image/jpeg");
+    }
+
+    #[test]
+    fn test_fallback_keeps_transparency() {
+        let data = png_of(DynamicImage::ImageRgba8(RgbaImage::from_pixel(
+            32,
+            32,
+            Rgba([0, 0, 0, 0]),
+        )));
+        let variant = ImageHelper::render_variant(
+            &data,
+            ImageVariantSize::Medium,
+            ImageVariantFormat::Fallback,
+        )
+        .unwrap();
+        assert_eq!(variant.content_type, "image/png");
+    }
+
+    #[test]
+    fn test_invalid_data_is_rejected() {
+        assert!(ImageHelper::sanitize(b"not an image", "image/png").is_err());
+        assert_eq!(
+            ImageHelper::sanitize(b"GIF89a", "image/gif").unwrap(),
+            b"GIF89a".to_vec()
+        );
+    }
+}
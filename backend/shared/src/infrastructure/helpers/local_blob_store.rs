@@ -0,0 +1,109 @@
+use crate::{domain::helpers::blob_store_trait::BlobStoreTrait, InfraError};
+use async_trait::async_trait;
+use std::{
+    io::ErrorKind,
+    path::{Component, Path, PathBuf},
+};
+
+/// Stores blobs as files under a root folder, one file per key.
+#[derive(Clone)]
+pub struct LocalBlobStore {
+    root: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path_of(&self, key: &str) -> Result<PathBuf, InfraError> {
+        let relative = Path::new(key);
+        let is_plain = !key.is_empty()
+            && relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+        if !is_plain {
+            return Err(InfraError::file_storage(format!(
+                "Invalid blob key: {}",
+                key
+            )));
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl BlobStoreTrait for LocalBlobStore {
+    async fn put(&self, key: &str, data: &[u8], _content_type: &str) -> Result<(), InfraError> {
+        let path = self.path_of(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|err| InfraError::file_storage(err.to_string()))?;
+        }
+
+        // Write aside and rename so readers never see a partial file
+        let temp_path = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4()));
+        tokio::fs::write(&temp_path, data)
+            .await
+            .map_err(|err| InfraError::file_storage(err.to_string()))?;
+        tokio::fs::rename(&temp_path, &path)
+            .await
+            .map_err(|err| InfraError::file_storage(err.to_string()))
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, InfraError> {
+        let path = self.path_of(key)?;
+        tokio::fs::read(&path)
+            .await
+            .map_err(|err| match err.kind() {
+                ErrorKind::NotFound => InfraError::not_found("Blob", key),
+                _ => InfraError::file_storage(err.to_string()),
+            })
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), InfraError> {
+        let path = self.path_of(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(InfraError::file_storage(err.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store() -> LocalBlobStore {
+        LocalBlobStore::new(
+            std::env::temp_dir().join(format!("rex-blobs-{}", uuid::Uuid::new_v4())),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_put_get_and_delete_round_trip() {
+        let store = temp_store();
+        store
+            .put("flashcards/a.png", &[1, 2, 3], "image/png")
+            .await
+            .unwrap();
+        assert_eq!(store.get("flashcards/a.png").await.unwrap(), vec![1, 2, 3]);
+
+        store.delete("flashcards/a.png").await.unwrap();
+        store.delete("flashcards/a.png").await.unwrap();
+        assert!(matches!(
+            store.get("flashcards/a.png").await,
+            Err(InfraError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_keys_cannot_leave_the_root() {
+        let store = temp_store();
+        assert!(store.get("../secret").await.is_err());
+        assert!(store.get("/etc/passwd").await.is_err());
+        assert!(store.put("", &[1], "image/png").await.is_err());
+    }
+}
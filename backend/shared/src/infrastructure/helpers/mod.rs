@@ -1,3 +1,4 @@
+pub mod blob_store_provider;
 pub mod configuration_helper;
 pub mod datetime_helper;
 pub mod datetime_helper_trait;
@@ -7,4 +8,7 @@ pub mod file_helper;
 pub mod file_helper_object_trait;
 pub mod html_helper;
 pub mod html_helper_trait;
+pub mod image_helper;
+pub mod local_blob_store;
 pub mod resend_email_helper;
+pub mod s3_blob_store;
@@ -1,6 +1,8 @@
 pub mod application;
 pub mod domain;
 pub mod infrastructure;
+#[cfg(feature = "test-helpers")]
+pub mod test_helpers;
 
 // Re-export specific items to avoid ambiguous glob re-exports
 pub use application::errors::ApplicationError;
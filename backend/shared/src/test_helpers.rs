@@ -0,0 +1,6 @@
+use chrono::{DateTime, Utc};
+
+/// A fixed instant for unit tests that compare times
+pub fn now() -> DateTime<Utc> {
+    DateTime::from_timestamp(1_760_000_000, 0).unwrap()
+}
@@ -4,15 +4,19 @@ use rex_game_games::{
     FlashcardTypeRepository, ScoringUseCase, {FlashcardTypeUseCase, FlashcardUseCase},
 };
 use rex_game_identity::{
-    IdentityPasswordHasher, IdentityTokenHelper, PermissionRepository, RolePermissionRepository,
-    RoleRepository, UserPermissionRepository, UserRepository, UserRoleRepository,
-    UserTokenRepository,
+    IdentityOidcClient, IdentityPasswordHasher, IdentitySigningKeyring, IdentityTokenHelper,
+    IdentityTotpHelper, PermissionRepository, RolePermissionRepository, RoleRepository,
+    SigningKeyRepository, UserApiKeyRepository, UserExternalLoginRepository,
+    UserLoginThrottleRepository, UserMfaRepository, UserPermissionRepository, UserRepository,
+    UserRoleRepository, UserStatusRepository, UserTokenRepository,
     {
-        IdentityAuthenticateUseCase, IdentityAuthorizeUseCase, IdentityUserTokenUseCase,
+        IdentityApiKeyUseCase, IdentityAuthenticateUseCase, IdentityAuthorizeUseCase,
+        IdentityExternalLoginUseCase, IdentityLoginThrottleUseCase, IdentityMfaUseCase,
+        IdentitySigningKeyUseCase, IdentityUserStatusUseCase, IdentityUserTokenUseCase,
         IdentityUserUseCase, PermissionUseCase, RoleUseCase, UserUseCase,
     },
 };
-use rex_game_mail_templates::application::MailTemplateUseCase;
+use rex_game_mail_templates::application::{EmailOutboxUseCase, MailTemplateUseCase};
 use rex_game_shared::infrastructure::{
     helpers::{
         configuration_helper::ConfigurationHelper, datetime_helper::DateTimeHelper,
@@ -24,6 +28,33 @@ use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 use crate::middlewares::rate_limit_middleware::RateLimiter;
 
+/// The API key use case as wired into the application
+type AppApiKeyUseCase = IdentityApiKeyUseCase<
+    UserApiKeyRepository,
+    IdentityUserUseCase<
+        IdentityPasswordHasher,
+        UserUseCase<
+            UserRepository,
+            RoleRepository,
+            UserRoleRepository,
+            UserPermissionRepository,
+            IdentityPasswordHasher,
+        >,
+        RoleUseCase<RoleRepository, RolePermissionRepository, UserRoleRepository>,
+        IdentityTokenHelper<ConfigurationHelper>,
+    >,
+    UserUseCase<
+        UserRepository,
+        RoleRepository,
+        UserRoleRepository,
+        UserPermissionRepository,
+        IdentityPasswordHasher,
+    >,
+    PermissionRepository,
+    IdentityPasswordHasher,
+    IdentityTokenHelper<ConfigurationHelper>,
+>;
+
 /// Group for all use cases
 #[derive(Clone)]
 pub struct UseCases {
@@ -63,7 +94,49 @@ pub struct UseCases {
             IdentityPasswordHasher,
         >,
         IdentityTokenHelper<ConfigurationHelper>,
+        UserTokenRepository,
+        IdentityMfaUseCase<
+            UserMfaRepository,
+            IdentityTotpHelper,
+            IdentityTokenHelper<ConfigurationHelper>,
+        >,
+        IdentityLoginThrottleUseCase<UserLoginThrottleRepository>,
+        IdentityUserStatusUseCase<UserStatusRepository>,
+        IdentityUserUseCase<
+            IdentityPasswordHasher,
+            UserUseCase<
+                UserRepository,
+                RoleRepository,
+                UserRoleRepository,
+                UserPermissionRepository,
+                IdentityPasswordHasher,
+            >,
+            RoleUseCase<RoleRepository, RolePermissionRepository, UserRoleRepository>,
+            IdentityTokenHelper<ConfigurationHelper>,
+        >,
+    >,
+    pub identity_login_throttle: IdentityLoginThrottleUseCase<UserLoginThrottleRepository>,
+    pub identity_user_status: IdentityUserStatusUseCase<UserStatusRepository>,
+    pub identity_external_login: IdentityExternalLoginUseCase<
+        IdentityOidcClient,
+        UserExternalLoginRepository,
+        UserUseCase<
+            UserRepository,
+            RoleRepository,
+            UserRoleRepository,
+            UserPermissionRepository,
+            IdentityPasswordHasher,
+        >,
+        IdentityPasswordHasher,
+    >,
+    pub identity_api_key: AppApiKeyUseCase,
+    pub identity_mfa: IdentityMfaUseCase<
+        UserMfaRepository,
+        IdentityTotpHelper,
+        IdentityTokenHelper<ConfigurationHelper>,
     >,
+    pub identity_signing_key:
+        IdentitySigningKeyUseCase<SigningKeyRepository, IdentitySigningKeyring>,
     pub role: RoleUseCase<RoleRepository, RolePermissionRepository, UserRoleRepository>,
     pub permission: PermissionUseCase<PermissionRepository>,
     pub identity_authorize: IdentityAuthorizeUseCase<
@@ -73,6 +146,7 @@ pub struct UseCases {
     >,
     pub identity_user_token: IdentityUserTokenUseCase<UserTokenRepository>,
     pub mail_template: MailTemplateUseCase,
+    pub email_outbox: EmailOutboxUseCase,
     pub scoring: ScoringUseCase,
 }
 
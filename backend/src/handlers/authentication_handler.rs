@@ -1,19 +1,56 @@
 use crate::{
     app_state::AppState,
+    helpers::account_email_helper::AccountEmailHelper,
     validators::validation_helper::ValidationHelper,
     view_models::{
-        authentications::user_login_request::UserLoginRequest, users::login_result::LoginResult,
+        authentications::{
+            api_key_creation_request::ApiKeyCreationRequest,
+            external_login_callback_request::ExternalLoginCallbackRequest,
+            magic_link_login_request::MagicLinkLoginRequest, magic_link_request::MagicLinkRequest,
+            mfa_challenge_result::MfaChallengeResult, mfa_code_request::MfaCodeRequest,
+            mfa_enrolment_request::MfaEnrolmentRequest, mfa_login_request::MfaLoginRequest,
+            user_login_request::UserLoginRequest,
+        },
+        users::{current_user::CurrentUser, login_result::LoginResult},
         HandlerError, HandlerResult,
     },
 };
-use axum::{extract::State, http::HeaderMap, Json};
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
 use axum_extra::extract::{
     cookie::{Cookie, Expiration, SameSite},
     CookieJar,
 };
+use chrono::Utc;
 use hyper::StatusCode;
-use rex_game_identity::application::usecases::auth::IdentityAuthenticateUseCaseTrait;
+use rex_game_identity::application::usecases::auth::{
+    api_key_creation_dto::ApiKeyCreationDto,
+    api_key_dto::ApiKeyDto,
+    created_api_key_dto::CreatedApiKeyDto,
+    external_authorization_dto::ExternalAuthorizationDto,
+    external_login_dto::ExternalLoginDto,
+    external_login_provider_dto::ExternalLoginProviderDto,
+    login_claims::LoginClaims,
+    mfa_enrolment_dto::MfaEnrolmentDto,
+    mfa_status_dto::MfaStatusDto,
+    password_login_result::{MfaChallenge, PasswordLoginResult},
+    user_session_dto::UserSessionDto,
+    IdentityApiKeyUseCaseTrait, IdentityAuthenticateUseCaseTrait,
+    IdentityExternalLoginUseCaseTrait, IdentityMfaUseCaseTrait,
+};
+use rex_game_identity::application::usecases::UserUseCaseTrait;
+use rex_game_identity::domain::models::user_statuses::UserStatuses;
+use rex_game_shared::domain::enums::mail_template_names::MailTemplateNames;
+use rex_game_shared::domain::transaction_manager_trait::{
+    TransactionManagerTrait, TransactionWrapperTrait,
+};
 use rex_game_shared::infrastructure::helpers::datetime_helper_trait::DateTimeHelperTrait;
+use rex_game_shared::ApplicationError;
+use std::sync::Arc;
 use validator::{Validate, ValidationErrors};
 
 impl AuthenticationHandler {
@@ -21,7 +58,7 @@ impl AuthenticationHandler {
         State(_state): State<AppState>,
         jar: CookieJar,
         Json(payload): Json<Option<UserLoginRequest>>,
-    ) -> HandlerResult<(CookieJar, Json<LoginResult>)> {
+    ) -> HandlerResult<Response> {
         let req: UserLoginRequest = match payload {
             Some(req) => req,
             None => {
@@ -42,13 +79,17 @@ impl AuthenticationHandler {
             };
         })?;
 
-        let token_claims = match _state
+        let login_result = match _state
             .usecases
             .identity_authenticate
             .password_login(&req.email, &req.password)
             .await
         {
             Ok(result) => result,
+            // Suspensions and unconfirmed accounts are only reported after a right password
+            Err(
+                err @ (ApplicationError::TooManyAttempts { .. } | ApplicationError::Forbidden(_)),
+            ) => return Err(HandlerError::from(err)),
             Err(_) => {
                 return Err(HandlerError {
                     status: StatusCode::UNAUTHORIZED,
@@ -58,6 +99,544 @@ impl AuthenticationHandler {
             }
         };
 
+        let token_claims = match login_result {
+            PasswordLoginResult::Authenticated(token_claims) => token_claims,
+            PasswordLoginResult::Locked(lockout) => {
+                let locked_until = lockout.locked_until.format("%d/%m/%Y %H:%M").to_string();
+                // The lock is already stored, so a failure to queue the email is only logged
+                if let Err(err) = AccountEmailHelper::queue(
+                    &_state,
+                    MailTemplateNames::ACCOUNT_LOCKED,
+                    &lockout.name,
+                    &lockout.email,
+                    &[("[locked_until]", locked_until)],
+                )
+                .await
+                {
+                    tracing::error!(
+                        user_id = lockout.user_id,
+                        "Failed to queue account locked email: {}",
+                        err
+                    );
+                }
+
+                let retry_after_secs = (lockout.locked_until - Utc::now()).num_seconds().max(1);
+                return Err(HandlerError::from(ApplicationError::too_many_attempts(
+                    retry_after_secs,
+                )));
+            }
+            PasswordLoginResult::MfaRequired(challenge) => {
+                return Self::mfa_challenge(&_state, challenge);
+            }
+        };
+
+        Ok(Self::sign_in(&_state, jar, token_claims, None)?.into_response())
+    }
+
+    /// POST /auth/magic-link - Email a single-use sign-in link for the device holding `nonce`.
+    /// The answer is the same whether or not the email belongs to a user.
+    pub async fn request_magic_link(
+        State(_state): State<AppState>,
+        Json(payload): Json<Option<MagicLinkRequest>>,
+    ) -> HandlerResult<Json<bool>> {
+        let req = match payload {
+            Some(req) => req,
+            None => {
+                return Err(HandlerError {
+                    status: StatusCode::BAD_REQUEST,
+                    message: "Invalid request payload".to_string(),
+                    ..Default::default()
+                })
+            }
+        };
+
+        req.validate().map_err(|e: ValidationErrors| {
+            let errors = ValidationHelper::new().flatten_errors(e);
+            HandlerError {
+                status: StatusCode::BAD_REQUEST,
+                message: "Validation error".to_string(),
+                field_errors: Some(errors),
+            }
+        })?;
+
+        let magic_link_url = _state.helpers.configuration.get_optional("MAGIC_LINK_URL");
+        if magic_link_url.is_empty() {
+            return Err(HandlerError {
+                status: StatusCode::NOT_FOUND,
+                message: "Sign-in links are not enabled".to_string(),
+                ..Default::default()
+            });
+        }
+
+        // Only active users get a link; everyone else gets the same answer and no email
+        let existing_user = match _state.usecases.user.get_user_by_email(&req.email).await {
+            Ok(user) if user.status_id == UserStatuses::Actived as i32 => user,
+            _ => return Ok(Json(true)),
+        };
+
+        // The link and its email are committed together; the outbox worker sends it
+        let transaction = _state
+            .transaction_manager
+            .begin()
+            .await
+            .map_err(|err| HandlerError {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                message: err.to_string(),
+                ..Default::default()
+            })?;
+        let queued = async {
+            let transaction_ref: &dyn TransactionWrapperTrait = &transaction;
+            let magic_link = _state
+                .usecases
+                .identity_authenticate
+                .create_magic_link(existing_user.id, &req.nonce, Box::new(transaction_ref))
+                .await
+                .map_err(HandlerError::from)?;
+
+            AccountEmailHelper::queue_with_transaction(
+                &_state,
+                MailTemplateNames::MAGIC_LINK_LOGIN,
+                &existing_user.name,
+                &existing_user.email,
+                &[
+                    (
+                        "[login_url]",
+                        magic_link_url.replace("[token]", &magic_link.token),
+                    ),
+                    (
+                        "[expiration_date]",
+                        magic_link.expiration.format("%d/%m/%Y %H:%M").to_string(),
+                    ),
+                ],
+                Box::new(transaction_ref),
+            )
+            .await
+            .map_err(|err| HandlerError {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                message: err.to_string(),
+                ..Default::default()
+            })?;
+
+            Ok::<(), HandlerError>(())
+        }
+        .await;
+
+        match queued {
+            Ok(_) => {
+                _state
+                    .transaction_manager
+                    .commit(transaction)
+                    .await
+                    .map_err(|err| HandlerError {
+                        status: StatusCode::INTERNAL_SERVER_ERROR,
+                        message: err.to_string(),
+                        ..Default::default()
+                    })?;
+                Ok(Json(true))
+            }
+            Err(err) => {
+                let _ = _state.transaction_manager.rollback(transaction).await;
+                Err(err)
+            }
+        }
+    }
+
+    /// POST /auth/magic-link/login - Sign in with an emailed link, on the device that asked
+    /// for it
+    pub async fn magic_link_login(
+        State(_state): State<AppState>,
+        jar: CookieJar,
+        Json(payload): Json<Option<MagicLinkLoginRequest>>,
+    ) -> HandlerResult<Response> {
+        let req = match payload {
+            Some(req) => req,
+            None => {
+                return Err(HandlerError {
+                    status: StatusCode::BAD_REQUEST,
+                    message: "Invalid request payload".to_string(),
+                    ..Default::default()
+                })
+            }
+        };
+
+        req.validate().map_err(|e: ValidationErrors| {
+            let errors = ValidationHelper::new().flatten_errors(e);
+            HandlerError {
+                status: StatusCode::BAD_REQUEST,
+                message: "Validation error".to_string(),
+                field_errors: Some(errors),
+            }
+        })?;
+
+        let login_result = _state
+            .usecases
+            .identity_authenticate
+            .magic_link_login(&req.token, &req.nonce)
+            .await
+            .map_err(HandlerError::from)?;
+        match login_result {
+            PasswordLoginResult::Authenticated(token_claims) => {
+                Ok(Self::sign_in(&_state, jar, token_claims, None)?.into_response())
+            }
+            PasswordLoginResult::MfaRequired(challenge) => Self::mfa_challenge(&_state, challenge),
+            // Sign-in links are not throttled, so they never lock the account
+            PasswordLoginResult::Locked(_) => Err(HandlerError {
+                status: StatusCode::UNAUTHORIZED,
+                message: "Authentication failed".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// GET /auth/external/providers - Providers users can sign in with
+    pub async fn get_external_login_providers(
+        State(_state): State<AppState>,
+    ) -> HandlerResult<Json<Vec<ExternalLoginProviderDto>>> {
+        Ok(Json(
+            _state.usecases.identity_external_login.get_providers(),
+        ))
+    }
+
+    /// POST /auth/external/{provider}/authorization - Start a sign-in at an external provider
+    pub async fn begin_external_login(
+        State(_state): State<AppState>,
+        Path(provider): Path<String>,
+    ) -> HandlerResult<Json<ExternalAuthorizationDto>> {
+        let authorization = _state
+            .usecases
+            .identity_external_login
+            .begin_sign_in(&provider)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(authorization))
+    }
+
+    /// POST /auth/external/{provider}/callback - Finish a sign-in at an external provider,
+    /// creating the user on their first sign-in
+    pub async fn complete_external_login(
+        State(_state): State<AppState>,
+        jar: CookieJar,
+        Path(provider): Path<String>,
+        Json(payload): Json<Option<ExternalLoginCallbackRequest>>,
+    ) -> HandlerResult<Response> {
+        let req = Self::get_external_login_callback_request(payload)?;
+        let user_id = _state
+            .usecases
+            .identity_external_login
+            .complete_sign_in(&provider, &req.code, &req.state, &req.flow_token)
+            .await
+            .map_err(HandlerError::from)?;
+
+        let login_result = _state
+            .usecases
+            .identity_authenticate
+            .external_login(user_id)
+            .await
+            .map_err(HandlerError::from)?;
+        match login_result {
+            PasswordLoginResult::Authenticated(token_claims) => {
+                Ok(Self::sign_in(&_state, jar, token_claims, None)?.into_response())
+            }
+            PasswordLoginResult::MfaRequired(challenge) => Self::mfa_challenge(&_state, challenge),
+            // External logins are not throttled, so they never lock the account
+            PasswordLoginResult::Locked(_) => Err(HandlerError {
+                status: StatusCode::UNAUTHORIZED,
+                message: "Authentication failed".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// GET /auth/external-logins - Providers linked to the current user
+    pub async fn get_external_logins(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+    ) -> HandlerResult<Json<Vec<ExternalLoginDto>>> {
+        let external_logins = _state
+            .usecases
+            .identity_external_login
+            .get_links(current_user.id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(external_logins))
+    }
+
+    /// POST /auth/external/{provider}/link - Start linking a provider account to the current user
+    pub async fn begin_external_login_link(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Path(provider): Path<String>,
+    ) -> HandlerResult<Json<ExternalAuthorizationDto>> {
+        let authorization = _state
+            .usecases
+            .identity_external_login
+            .begin_link(&provider, current_user.id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(authorization))
+    }
+
+    /// POST /auth/external/{provider}/link/callback - Link the provider account
+    pub async fn complete_external_login_link(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Path(provider): Path<String>,
+        Json(payload): Json<Option<ExternalLoginCallbackRequest>>,
+    ) -> HandlerResult<Json<ExternalLoginDto>> {
+        let req = Self::get_external_login_callback_request(payload)?;
+        let external_login = _state
+            .usecases
+            .identity_external_login
+            .complete_link(
+                &provider,
+                current_user.id,
+                &req.code,
+                &req.state,
+                &req.flow_token,
+            )
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(external_login))
+    }
+
+    /// DELETE /auth/external-logins/{provider} - Unlink a provider from the current user
+    pub async fn delete_external_login(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Path(provider): Path<String>,
+    ) -> HandlerResult<Json<bool>> {
+        let deleted = _state
+            .usecases
+            .identity_external_login
+            .unlink(current_user.id, &provider)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(deleted))
+    }
+
+    /// POST /auth/mfa/verify - Finish a login with a TOTP or recovery code
+    pub async fn verify_mfa(
+        State(_state): State<AppState>,
+        jar: CookieJar,
+        Json(payload): Json<Option<MfaLoginRequest>>,
+    ) -> HandlerResult<(CookieJar, Json<LoginResult>)> {
+        let req = match payload {
+            Some(req) => req,
+            None => {
+                return Err(HandlerError {
+                    status: StatusCode::BAD_REQUEST,
+                    message: "Invalid request payload".to_string(),
+                    ..Default::default()
+                })
+            }
+        };
+
+        req.validate().map_err(|e: ValidationErrors| {
+            let errors = ValidationHelper::new().flatten_errors(e);
+            HandlerError {
+                status: StatusCode::BAD_REQUEST,
+                message: "Validation error".to_string(),
+                field_errors: Some(errors),
+            }
+        })?;
+
+        let mfa_login = _state
+            .usecases
+            .identity_authenticate
+            .complete_mfa_login(&req.mfa_token, &req.code)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Self::sign_in(&_state, jar, mfa_login.claims, mfa_login.recovery_codes)
+    }
+
+    /// POST /auth/mfa/enrolment - Start the enrolment a user's role requires, mid-login
+    pub async fn begin_mfa_enrolment_for_login(
+        State(_state): State<AppState>,
+        Json(payload): Json<Option<MfaEnrolmentRequest>>,
+    ) -> HandlerResult<Json<MfaEnrolmentDto>> {
+        let req = match payload {
+            Some(req) => req,
+            None => {
+                return Err(HandlerError {
+                    status: StatusCode::BAD_REQUEST,
+                    message: "Invalid request payload".to_string(),
+                    ..Default::default()
+                })
+            }
+        };
+
+        let enrolment = _state
+            .usecases
+            .identity_authenticate
+            .begin_mfa_enrolment(&req.mfa_token)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(enrolment))
+    }
+
+    /// GET /auth/mfa - Two-factor status of the current user
+    pub async fn get_mfa_status(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+    ) -> HandlerResult<Json<MfaStatusDto>> {
+        let status = _state
+            .usecases
+            .identity_mfa
+            .get_status(current_user.id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(status))
+    }
+
+    /// POST /auth/mfa - Generate a new secret for the current user
+    pub async fn begin_mfa_enrolment(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+    ) -> HandlerResult<Json<MfaEnrolmentDto>> {
+        let enrolment = _state
+            .usecases
+            .identity_mfa
+            .begin_enrolment(current_user.id, &current_user.email)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(enrolment))
+    }
+
+    /// POST /auth/mfa/confirmation - Turn two-factor authentication on with a first code
+    pub async fn confirm_mfa_enrolment(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Json(payload): Json<Option<MfaCodeRequest>>,
+    ) -> HandlerResult<Json<Vec<String>>> {
+        let req = Self::get_mfa_code_request(payload)?;
+        let recovery_codes = _state
+            .usecases
+            .identity_mfa
+            .confirm_enrolment(current_user.id, &req.code)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(recovery_codes))
+    }
+
+    /// POST /auth/mfa/recovery-codes - Replace the current user's recovery codes
+    pub async fn regenerate_recovery_codes(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Json(payload): Json<Option<MfaCodeRequest>>,
+    ) -> HandlerResult<Json<Vec<String>>> {
+        let req = Self::get_mfa_code_request(payload)?;
+        let recovery_codes = _state
+            .usecases
+            .identity_mfa
+            .regenerate_recovery_codes(current_user.id, &req.code)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(recovery_codes))
+    }
+
+    /// DELETE /auth/mfa - Turn two-factor authentication off, unless a role requires it
+    pub async fn disable_mfa(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Json(payload): Json<Option<MfaCodeRequest>>,
+    ) -> HandlerResult<Json<bool>> {
+        let req = Self::get_mfa_code_request(payload)?;
+        let disabled = _state
+            .usecases
+            .identity_mfa
+            .disable(current_user.id, &req.code)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(disabled))
+    }
+
+    fn get_mfa_code_request(payload: Option<MfaCodeRequest>) -> HandlerResult<MfaCodeRequest> {
+        let req = match payload {
+            Some(req) => req,
+            None => {
+                return Err(HandlerError {
+                    status: StatusCode::BAD_REQUEST,
+                    message: "Invalid request payload".to_string(),
+                    ..Default::default()
+                })
+            }
+        };
+
+        req.validate().map_err(|e: ValidationErrors| {
+            let errors = ValidationHelper::new().flatten_errors(e);
+            HandlerError {
+                status: StatusCode::BAD_REQUEST,
+                message: "Validation error".to_string(),
+                field_errors: Some(errors),
+            }
+        })?;
+        Ok(req)
+    }
+
+    fn get_external_login_callback_request(
+        payload: Option<ExternalLoginCallbackRequest>,
+    ) -> HandlerResult<ExternalLoginCallbackRequest> {
+        let req = match payload {
+            Some(req) => req,
+            None => {
+                return Err(HandlerError {
+                    status: StatusCode::BAD_REQUEST,
+                    message: "Invalid request payload".to_string(),
+                    ..Default::default()
+                })
+            }
+        };
+
+        req.validate().map_err(|e: ValidationErrors| {
+            let errors = ValidationHelper::new().flatten_errors(e);
+            HandlerError {
+                status: StatusCode::BAD_REQUEST,
+                message: "Validation error".to_string(),
+                field_errors: Some(errors),
+            }
+        })?;
+        Ok(req)
+    }
+
+    /// Answers a login that still needs the second factor
+    fn mfa_challenge(_state: &AppState, challenge: MfaChallenge) -> HandlerResult<Response> {
+        let expiration = _state
+            .helpers
+            .date_time
+            .timestamp_to_utc_date_time(challenge.expiration)
+            .map_err(|_| HandlerError {
+                status: StatusCode::BAD_REQUEST,
+                message: "Invalid two-factor token expiration".to_string(),
+                ..Default::default()
+            })?;
+        Ok(Json(MfaChallengeResult {
+            mfa_required: true,
+            mfa_token: challenge.mfa_token,
+            expiration,
+            enrolment_required: challenge.enrolment_required,
+        })
+        .into_response())
+    }
+
+    /// Sets the refresh token cookie and returns the access token
+    fn sign_in(
+        _state: &AppState,
+        jar: CookieJar,
+        token_claims: LoginClaims,
+        recovery_codes: Option<Vec<String>>,
+    ) -> HandlerResult<(CookieJar, Json<LoginResult>)> {
         let mut cookie = Cookie::new("refresh_token", token_claims.refresh_token);
         cookie.set_http_only(true);
         cookie.set_same_site(SameSite::Lax);
@@ -97,14 +676,25 @@ impl AuthenticationHandler {
             Json(LoginResult {
                 access_token: token_claims.access_token,
                 expiration: access_token_expires,
+                recovery_codes,
             }),
         ))
     }
 
     pub async fn logout(
         State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
         jar: CookieJar,
     ) -> HandlerResult<(CookieJar, Json<bool>)> {
+        if let Some(refresh_token) = jar.get("refresh_token") {
+            _state
+                .usecases
+                .identity_authenticate
+                .logout(current_user.id, refresh_token.value())
+                .await
+                .map_err(HandlerError::from)?;
+        }
+
         let mut cookie = Cookie::new("refresh_token", "");
         cookie.set_http_only(true);
         cookie.set_same_site(SameSite::Lax);
@@ -167,6 +757,7 @@ impl AuthenticationHandler {
             .await
         {
             Ok(result) => result,
+            Err(err @ ApplicationError::Forbidden(_)) => return Err(HandlerError::from(err)),
             Err(_) => {
                 return Err(HandlerError {
                     status: StatusCode::UNAUTHORIZED,
@@ -214,9 +805,137 @@ impl AuthenticationHandler {
             Json(LoginResult {
                 access_token: token_claims.access_token,
                 expiration: access_token_expires,
+                recovery_codes: None,
             }),
         ))
     }
+
+    /// GET /auth/sessions - List the current user's active sessions
+    pub async fn get_sessions(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+    ) -> HandlerResult<Json<Vec<UserSessionDto>>> {
+        let sessions = _state
+            .usecases
+            .identity_authenticate
+            .get_sessions(current_user.id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(sessions))
+    }
+
+    /// DELETE /auth/sessions/{session_id} - Revoke one of the current user's sessions
+    pub async fn revoke_session(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Path(session_id): Path<String>,
+    ) -> HandlerResult<Json<bool>> {
+        let revoked = _state
+            .usecases
+            .identity_authenticate
+            .revoke_session(current_user.id, &session_id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(revoked))
+    }
+
+    /// GET /auth/api-keys - List the current user's API keys
+    pub async fn get_api_keys(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+    ) -> HandlerResult<Json<Vec<ApiKeyDto>>> {
+        Self::ensure_not_api_key(&current_user)?;
+        let api_keys = _state
+            .usecases
+            .identity_api_key
+            .get_api_keys(current_user.id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(api_keys))
+    }
+
+    /// POST /auth/api-keys - Create an API key for the current user; the key is only returned once
+    pub async fn create_api_key(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Json(payload): Json<Option<ApiKeyCreationRequest>>,
+    ) -> HandlerResult<(StatusCode, Json<CreatedApiKeyDto>)> {
+        Self::ensure_not_api_key(&current_user)?;
+        let req = Self::get_api_key_creation_request(payload)?;
+        let created = _state
+            .usecases
+            .identity_api_key
+            .create_api_key(
+                current_user.id,
+                ApiKeyCreationDto {
+                    name: req.name,
+                    scopes: req.scopes,
+                    expires_on: req.expires_on,
+                    created_by_id: current_user.id,
+                },
+            )
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok((StatusCode::CREATED, Json(created)))
+    }
+
+    /// DELETE /auth/api-keys/{id} - Revoke one of the current user's API keys
+    pub async fn revoke_api_key(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Path(id): Path<i32>,
+    ) -> HandlerResult<Json<bool>> {
+        Self::ensure_not_api_key(&current_user)?;
+        let revoked = _state
+            .usecases
+            .identity_api_key
+            .revoke_api_key(current_user.id, id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(revoked))
+    }
+
+    /// A key must not be able to mint or revoke keys, or a leaked one could outlive its revocation
+    pub(crate) fn ensure_not_api_key(current_user: &CurrentUser) -> HandlerResult<()> {
+        if current_user.api_key_id.is_some() {
+            return Err(HandlerError {
+                status: StatusCode::FORBIDDEN,
+                message: "API keys cannot be managed with an API key".to_string(),
+                ..Default::default()
+            });
+        }
+        Ok(())
+    }
+
+    pub(crate) fn get_api_key_creation_request(
+        payload: Option<ApiKeyCreationRequest>,
+    ) -> HandlerResult<ApiKeyCreationRequest> {
+        let req = match payload {
+            Some(req) => req,
+            None => {
+                return Err(HandlerError {
+                    status: StatusCode::BAD_REQUEST,
+                    message: "Invalid request payload".to_string(),
+                    ..Default::default()
+                })
+            }
+        };
+
+        req.validate().map_err(|e: ValidationErrors| {
+            let errors = ValidationHelper::new().flatten_errors(e);
+            HandlerError {
+                status: StatusCode::BAD_REQUEST,
+                message: "Validation error".to_string(),
+                field_errors: Some(errors),
+            }
+        })?;
+        Ok(req)
+    }
 }
 
 pub struct AuthenticationHandler {}
@@ -0,0 +1,128 @@
+use crate::{
+    app_state::AppState,
+    view_models::{users::current_user::CurrentUser, HandlerError, HandlerResult},
+};
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use hyper::StatusCode;
+use rex_game_identity::application::usecases::roles::*;
+use rex_game_mail_templates::{
+    application::{EmailOutboxDto, EmailOutboxUseCaseTrait},
+    domain::models::EmailOutboxStatuses,
+};
+use rex_game_shared::{domain::models::PageListModel, InfraError};
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+pub struct EmailOutboxQuery {
+    page: Option<u64>,
+    page_size: Option<u64>,
+    status: Option<String>,
+}
+
+impl EmailOutboxHandler {
+    pub async fn get_emails(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Query(params): Query<EmailOutboxQuery>,
+    ) -> HandlerResult<Json<PageListModel<EmailOutboxDto>>> {
+        Self::ensure_root_admin(&current_user)?;
+
+        let status = match params.status.as_deref().filter(|status| !status.is_empty()) {
+            Some(status) => Some(EmailOutboxStatuses::parse(status).ok_or(HandlerError {
+                status: StatusCode::BAD_REQUEST,
+                message: "Status must be one of 'pending', 'sent' or 'dead'".to_string(),
+                ..Default::default()
+            })?),
+            None => None,
+        };
+        let page = params.page.unwrap_or(1);
+        let page_size = params.page_size.unwrap_or(10);
+
+        let emails = _state
+            .usecases
+            .email_outbox
+            .get_list(status, page, page_size)
+            .await
+            .map_err(|_| HandlerError {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                message: "Failed to get queued emails".to_string(),
+                ..Default::default()
+            })?;
+        Ok(Json(emails))
+    }
+
+    pub async fn get_email_by_id(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Path(id): Path<i32>,
+    ) -> HandlerResult<Json<EmailOutboxDto>> {
+        Self::ensure_root_admin(&current_user)?;
+
+        let email = _state
+            .usecases
+            .email_outbox
+            .get_by_id(id)
+            .await
+            .map_err(Self::map_lookup_error)?;
+        Ok(Json(email))
+    }
+
+    pub async fn retry_email(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Path(id): Path<i32>,
+    ) -> HandlerResult<Json<bool>> {
+        Self::ensure_root_admin(&current_user)?;
+
+        let requeued = _state
+            .usecases
+            .email_outbox
+            .retry(id)
+            .await
+            .map_err(Self::map_lookup_error)?;
+        if !requeued {
+            return Err(HandlerError {
+                status: StatusCode::CONFLICT,
+                message: "Only dead emails can be retried".to_string(),
+                ..Default::default()
+            });
+        }
+        Ok(Json(true))
+    }
+
+    fn ensure_root_admin(current_user: &CurrentUser) -> Result<(), HandlerError> {
+        if !current_user
+            .roles
+            .iter()
+            .any(|role| role == ROLE_ROOT_ADMIN)
+        {
+            return Err(HandlerError {
+                status: StatusCode::FORBIDDEN,
+                message: "You do not have permission to manage queued emails".to_string(),
+                ..Default::default()
+            });
+        }
+        Ok(())
+    }
+
+    fn map_lookup_error(err: InfraError) -> HandlerError {
+        match err {
+            InfraError::NotFound { .. } => HandlerError {
+                status: StatusCode::NOT_FOUND,
+                message: "Email not found".to_string(),
+                ..Default::default()
+            },
+            _ => HandlerError {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                message: "Failed to load the queued email".to_string(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+pub struct EmailOutboxHandler {}
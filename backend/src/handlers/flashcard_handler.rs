@@ -1,29 +1,49 @@
 use crate::{
     app_state::AppState,
-    helpers::http_helper::HttpHelper,
-    validators::{validate_content_type, validate_file_size, validation_helper::ValidationHelper},
+    handlers::flashcard_type_handler::FlashcardTypeHandler,
+    helpers::{
+        flashcard_package_helper::{
+            FlashcardPackageFormat, FlashcardPackageHelper, FlashcardPackageItem,
+        },
+        http_cache_helper::{
+            ByteRange, HttpCacheHelper, IMMUTABLE_CACHE_CONTROL, REVALIDATE_CACHE_CONTROL,
+        },
+        http_helper::HttpHelper,
+    },
+    validators::{
+        detect_content_type, validate_content_type, validate_file_size,
+        validation_helper::ValidationHelper,
+    },
     view_models::{
-        flashcards::flashcard_request::FlashcardRequest, users::current_user::CurrentUser,
+        flashcards::{
+            flashcard_import_report::{FlashcardImportReport, FlashcardImportRowResult},
+            flashcard_request::FlashcardRequest,
+        },
+        users::current_user::CurrentUser,
         HandlerError, HandlerResult,
     },
 };
 use axum::{
     body::Body,
     extract::{Multipart, Path, Query, State},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
     response::Response,
     Extension, Json,
 };
-use rex_game_entities::entities::{game_type, game_type_flashcard};
+use rex_game_entities::entities::{flashcard_type_relation, game_type, game_type_flashcard};
 use rex_game_games::{
     FlashcardCreationDto, FlashcardDetailDto, FlashcardDto, FlashcardGameTypeInfo,
     FlashcardTypeUseCaseTrait, FlashcardUpdationDto, FlashcardUseCaseTrait,
 };
-use rex_game_identity::application::usecases::roles::*;
-use rex_game_shared::domain::models::PageListModel;
+use rex_game_identity::domain::rules::OwnershipPolicy;
+use rex_game_shared::{
+    domain::models::PageListModel,
+    infrastructure::helpers::image_helper::{ImageVariantFormat, ImageVariantSize},
+    ApplicationError,
+};
 use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
 use serde::Deserialize;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use validator::{Validate, ValidationErrors};
 
 #[derive(Deserialize)]
@@ -33,7 +53,66 @@ pub struct FlashcardQuery {
     game_type_code: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct FlashcardImageQuery {
+    v: Option<String>,
+    size: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct FlashcardExportQuery {
+    format: Option<String>,
+}
+
 impl FlashcardHandler {
+    /// Loads the flashcard so the policy can tell whether the user created it
+    async fn authorize_flashcard(
+        state: &AppState,
+        current_user: &CurrentUser,
+        id: i32,
+        policy: &OwnershipPolicy,
+    ) -> HandlerResult<()> {
+        let flashcard = match state.usecases.flashcard.get_flashcard_by_id(id).await {
+            Some(flashcard) => flashcard,
+            None => {
+                return Err(HandlerError {
+                    status: StatusCode::NOT_FOUND,
+                    message: "Flashcard not found".to_string(),
+                    ..Default::default()
+                })
+            }
+        };
+
+        if !current_user.can(policy, Some(flashcard.created_by_id)) {
+            return Err(HandlerError {
+                status: StatusCode::FORBIDDEN,
+                message: "You can only change the flashcards you created".to_string(),
+                ..Default::default()
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Putting a card in a type or taking it out changes that type as well
+    async fn authorize_flashcard_types(
+        state: &AppState,
+        current_user: &CurrentUser,
+        type_ids: &[i32],
+    ) -> HandlerResult<()> {
+        for type_id in type_ids {
+            FlashcardTypeHandler::authorize_flashcard_type(
+                state,
+                current_user,
+                *type_id,
+                &OwnershipPolicy::FLASHCARD_TYPE_UPDATE,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
     pub async fn get_flashcards(
         State(_state): State<AppState>,
         Query(params): Query<FlashcardQuery>,
@@ -112,36 +191,141 @@ impl FlashcardHandler {
             sub_description: flashcard.sub_description,
             created_on: flashcard.created_on,
             updated_on: flashcard.updated_on,
+            created_by_id: flashcard.created_by_id,
             image_id: flashcard.image_id,
+            image_version: flashcard.image_version,
             flashcard_types: flashcard_types.into_iter().map(|f| f.into()).collect(),
             game_types: game_type_infos,
         }))
     }
 
+    /// Serves an image with validators for conditional requests and `Range` support.
+    /// URLs carrying the current `?v=` version are cacheable forever.
     pub async fn get_flashcard_image(
         Path(file_id): Path<i32>,
+        Query(params): Query<FlashcardImageQuery>,
         State(_state): State<AppState>,
+        headers: HeaderMap,
     ) -> HandlerResult<Response<Body>> {
-        let flashcard_file = _state
+        let image_info = _state
             .usecases
             .flashcard
-            .get_image_by_file_id(file_id)
+            .get_image_info_by_file_id(file_id)
             .await
-            .map_err(|err| HandlerError {
-                status: StatusCode::INTERNAL_SERVER_ERROR,
-                message: format!("Failed to fetch flashcard image: {}", err),
-                ..Default::default()
-            })?;
+            .map_err(HandlerError::from)?;
+
+        // Without `size` the stored image is sent as is; with it, a resized copy in WebP
+        // when the client accepts it, or in PNG/JPEG otherwise.
+        let variant = match params.size.as_deref() {
+            Some(size) => match ImageVariantSize::parse(size) {
+                Some(size) => Some((
+                    size,
+                    ImageVariantFormat::from_accept(
+                        headers
+                            .get(header::ACCEPT)
+                            .and_then(|value| value.to_str().ok()),
+                    ),
+                )),
+                None => {
+                    return Err(HandlerError {
+                        status: StatusCode::BAD_REQUEST,
+                        message: "Image size must be one of: thumb, medium, original".to_string(),
+                        ..Default::default()
+                    })
+                }
+            },
+            None => None,
+        };
 
-        let response =
-            HttpHelper::build_file_respone(flashcard_file.data, &flashcard_file.content_type)
-                .map_err(|err| HandlerError {
-                    status: StatusCode::INTERNAL_SERVER_ERROR,
-                    message: format!("Failed to build response: {}", err),
-                    ..Default::default()
-                })?;
+        let entity_tag = image_info.checksum.as_deref().map(|checksum| match variant {
+            Some((size, format)) => HttpCacheHelper::entity_tag(&format!(
+                "{}-{}-{}",
+                checksum,
+                size.as_str(),
+                format.as_str()
+            )),
+            None => HttpCacheHelper::entity_tag(checksum),
+        });
+        let last_modified = image_info.updated_on;
+        let is_current_version =
+            params.v.is_some() && params.v.as_deref() == image_info.version().as_deref();
+        let cache_control = if is_current_version {
+            IMMUTABLE_CACHE_CONTROL
+        } else {
+            REVALIDATE_CACHE_CONTROL
+        };
+
+        let mut builder = Response::builder()
+            .header(header::CACHE_CONTROL, cache_control)
+            .header(
+                header::LAST_MODIFIED,
+                HttpCacheHelper::format_http_date(last_modified),
+            )
+            .header(header::ACCEPT_RANGES, "bytes");
+        if let Some(entity_tag) = entity_tag.as_deref() {
+            builder = builder.header(header::ETAG, entity_tag);
+        }
+        if variant.is_some() {
+            builder = builder.header(header::VARY, "Accept");
+        }
 
-        Ok(response)
+        let build_error = |err: axum::http::Error| HandlerError {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: format!("Failed to build response: {}", err),
+            ..Default::default()
+        };
+        if HttpCacheHelper::is_not_modified(&headers, entity_tag.as_deref(), last_modified) {
+            return builder
+                .status(StatusCode::NOT_MODIFIED)
+                .body(Body::empty())
+                .map_err(build_error);
+        }
+
+        let flashcard_file = match variant {
+            Some((size, format)) => {
+                _state
+                    .usecases
+                    .flashcard
+                    .get_image_variant(file_id, size, format)
+                    .await
+            }
+            None => {
+                _state
+                    .usecases
+                    .flashcard
+                    .get_image_by_file_id(file_id)
+                    .await
+            }
+        }
+        .map_err(|err| HandlerError {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: format!("Failed to fetch flashcard image: {}", err),
+            ..Default::default()
+        })?;
+        let length = flashcard_file.data.len() as u64;
+        builder = builder.header(header::CONTENT_TYPE, flashcard_file.content_type);
+
+        match HttpCacheHelper::byte_range(&headers, entity_tag.as_deref(), last_modified, length) {
+            ByteRange::Full => builder
+                .status(StatusCode::OK)
+                .body(Body::from(flashcard_file.data))
+                .map_err(build_error),
+            ByteRange::Partial(start, end) => builder
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header(
+                    header::CONTENT_RANGE,
+                    format!("bytes {}-{}/{}", start, end, length),
+                )
+                .body(Body::from(
+                    flashcard_file.data[start as usize..=end as usize].to_vec(),
+                ))
+                .map_err(build_error),
+            ByteRange::Unsatisfiable => builder
+                .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                .header(header::CONTENT_RANGE, format!("bytes */{}", length))
+                .body(Body::empty())
+                .map_err(build_error),
+        }
     }
 
     pub async fn create_flashcard(
@@ -149,21 +333,6 @@ impl FlashcardHandler {
         Extension(current_user): Extension<Arc<CurrentUser>>,
         mut multipart: Multipart,
     ) -> HandlerResult<Json<i32>> {
-        // Debug: Check current user ID
-        eprintln!("DEBUG: Current user ID = {}", current_user.id);
-        eprintln!("DEBUG: Current user roles = {:?}", current_user.roles);
-
-        if !current_user
-            .roles
-            .iter()
-            .any(|role| role == ROLE_ROOT_ADMIN)
-        {
-            return Err(HandlerError {
-                status: StatusCode::FORBIDDEN,
-                message: "You do not have permission to create flashcards".to_string(),
-                ..Default::default()
-            });
-        }
         let mut flashcard_req = FlashcardRequest {
             ..Default::default()
         };
@@ -246,6 +415,8 @@ impl FlashcardHandler {
             };
         })?;
 
+        Self::authorize_flashcard_types(&_state, &current_user, &flashcard_req.type_ids).await?;
+
         let new_flashcard = FlashcardCreationDto {
             name: flashcard_req.name,
             description: flashcard_req.description,
@@ -298,17 +469,13 @@ impl FlashcardHandler {
         Path(id): Path<i32>,
         mut multipart: Multipart,
     ) -> HandlerResult<Json<bool>> {
-        if !current_user
-            .roles
-            .iter()
-            .any(|role| role == ROLE_ROOT_ADMIN)
-        {
-            return Err(HandlerError {
-                status: StatusCode::FORBIDDEN,
-                message: "You do not have permission to update flashcards".to_string(),
-                ..Default::default()
-            });
-        }
+        Self::authorize_flashcard(
+            &_state,
+            &current_user,
+            id,
+            &OwnershipPolicy::FLASHCARD_UPDATE,
+        )
+        .await?;
 
         let mut flashcard = FlashcardUpdationDto {
             updated_by_id: current_user.id,
@@ -406,6 +573,28 @@ impl FlashcardHandler {
             }
         }
 
+        if let Some(type_ids) = &flashcard.type_ids {
+            let current_type_ids: Vec<i32> = flashcard_type_relation::Entity::find()
+                .filter(flashcard_type_relation::Column::FlashcardId.eq(id))
+                .all(_state.db_connection.as_ref())
+                .await
+                .map_err(|e| HandlerError {
+                    status: StatusCode::INTERNAL_SERVER_ERROR,
+                    message: format!("Failed to load flashcard types: {}", e),
+                    ..Default::default()
+                })?
+                .into_iter()
+                .map(|f| f.flashcard_type_id)
+                .collect();
+            let changed_type_ids: Vec<i32> = type_ids
+                .iter()
+                .filter(|f| !current_type_ids.contains(f))
+                .chain(current_type_ids.iter().filter(|f| !type_ids.contains(f)))
+                .copied()
+                .collect();
+            Self::authorize_flashcard_types(&_state, &current_user, &changed_type_ids).await?;
+        }
+
         _state
             .usecases
             .flashcard
@@ -458,17 +647,13 @@ impl FlashcardHandler {
         Extension(current_user): Extension<Arc<CurrentUser>>,
         Path(id): Path<i32>,
     ) -> HandlerResult<Json<u64>> {
-        if !current_user
-            .roles
-            .iter()
-            .any(|role| role == ROLE_ROOT_ADMIN)
-        {
-            return Err(HandlerError {
-                status: StatusCode::FORBIDDEN,
-                message: "You do not have permission to delete flashcards".to_string(),
-                ..Default::default()
-            });
-        }
+        Self::authorize_flashcard(
+            &_state,
+            &current_user,
+            id,
+            &OwnershipPolicy::FLASHCARD_DELETE,
+        )
+        .await?;
 
         let deleted_numbers = _state
             .usecases
@@ -489,17 +674,13 @@ impl FlashcardHandler {
         Extension(current_user): Extension<Arc<CurrentUser>>,
         Path(id): Path<i32>,
     ) -> HandlerResult<Json<bool>> {
-        if !current_user
-            .roles
-            .iter()
-            .any(|role| role == ROLE_ROOT_ADMIN)
-        {
-            return Err(HandlerError {
-                status: StatusCode::FORBIDDEN,
-                message: "You do not have permission to toggle flashcard status".to_string(),
-                ..Default::default()
-            });
-        }
+        Self::authorize_flashcard(
+            &_state,
+            &current_user,
+            id,
+            &OwnershipPolicy::FLASHCARD_UPDATE,
+        )
+        .await?;
 
         let new_status = _state
             .usecases
@@ -514,6 +695,256 @@ impl FlashcardHandler {
 
         Ok(Json(new_status))
     }
+
+    /// Imports a CSV/JSON manifest with a zip of images, or an Anki package. Nothing is
+    /// written unless every row is valid; the report lists the outcome of each row.
+    pub async fn import_flashcards(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        mut multipart: Multipart,
+    ) -> HandlerResult<(StatusCode, Json<FlashcardImportReport>)> {
+        let mut format: Option<FlashcardPackageFormat> = None;
+        let mut type_ids: Vec<i32> = Vec::new();
+        let mut manifest: Option<(String, Vec<u8>)> = None;
+        let mut images: Option<Vec<u8>> = None;
+        let mut package: Option<(String, Vec<u8>)> = None;
+
+        while let Some(field) = multipart.next_field().await.map_err(|err| HandlerError {
+            status: StatusCode::BAD_REQUEST,
+            message: format!("Invalid upload: {}", err),
+            ..Default::default()
+        })? {
+            let field_name = field.name().unwrap_or("").to_string();
+            let file_name = field.file_name().unwrap_or_default().to_string();
+            let data = field.bytes().await.map_err(|err| HandlerError {
+                status: StatusCode::BAD_REQUEST,
+                message: format!("Invalid upload: {}", err),
+                ..Default::default()
+            })?;
+            match field_name.as_str() {
+                "format" => {
+                    let value = String::from_utf8_lossy(&data);
+                    format = Some(FlashcardPackageFormat::parse(&value).ok_or(HandlerError {
+                        status: StatusCode::BAD_REQUEST,
+                        message: format!("Unsupported format: {}", value),
+                        ..Default::default()
+                    })?);
+                }
+                name if name.contains("type_ids") => {
+                    let type_id = String::from_utf8_lossy(&data)
+                        .trim()
+                        .parse::<i32>()
+                        .map_err(|err| HandlerError {
+                            status: StatusCode::BAD_REQUEST,
+                            message: format!("Invalid type_id: {}", err),
+                            ..Default::default()
+                        })?;
+                    type_ids.push(type_id);
+                }
+                "manifest" => manifest = Some((file_name, data.to_vec())),
+                "images" => images = Some(data.to_vec()),
+                "package" => package = Some((file_name, data.to_vec())),
+                _ => {}
+            }
+        }
+
+        let items = tokio::task::spawn_blocking(
+            move || -> Result<Vec<FlashcardPackageItem>, ApplicationError> {
+                match (package, manifest) {
+                    (Some((file_name, package)), _) => {
+                        match format.or(FlashcardPackageFormat::from_file_name(&file_name)) {
+                            Some(FlashcardPackageFormat::Apkg) | None => {
+                                FlashcardPackageHelper::read_apkg(&package)
+                            }
+                            Some(_) => Err(ApplicationError::invalid_input(
+                                "CSV and JSON imports are uploaded as a manifest",
+                            )),
+                        }
+                    }
+                    (None, Some((file_name, manifest))) => {
+                        let format = format
+                            .or(FlashcardPackageFormat::from_file_name(&file_name))
+                            .ok_or(ApplicationError::invalid_input(
+                                "Unable to tell the manifest format, set format to csv or json",
+                            ))?;
+                        FlashcardPackageHelper::read_manifest(format, &manifest, images.as_deref())
+                    }
+                    (None, None) => Err(ApplicationError::invalid_input(
+                        "A manifest or an Anki package is required",
+                    )),
+                }
+            },
+        )
+        .await
+        .map_err(|err| HandlerError {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: format!("Failed to read the package: {}", err),
+            ..Default::default()
+        })?
+        .map_err(HandlerError::from)?;
+
+        if items.is_empty() {
+            return Err(HandlerError {
+                status: StatusCode::BAD_REQUEST,
+                message: "The package contains no flashcards".to_string(),
+                ..Default::default()
+            });
+        }
+
+        let mut known_types: HashMap<i32, bool> = HashMap::new();
+        let mut rows: Vec<FlashcardImportRowResult> = Vec::with_capacity(items.len());
+        let mut new_flashcards: Vec<FlashcardCreationDto> = Vec::with_capacity(items.len());
+        for (index, item) in items.into_iter().enumerate() {
+            let mut row_type_ids = item.row.type_ids.clone();
+            row_type_ids.extend(type_ids.iter());
+            row_type_ids.sort_unstable();
+            row_type_ids.dedup();
+
+            let mut flashcard_req = FlashcardRequest {
+                name: item.row.name.clone(),
+                description: item.row.description.clone(),
+                sub_description: item.row.sub_description.clone(),
+                type_ids: row_type_ids,
+                ..Default::default()
+            };
+            if let Some(image) = item.image {
+                flashcard_req.content_type = detect_content_type(&image.data).unwrap_or_default();
+                flashcard_req.file_name = image.file_name;
+                flashcard_req.image_data = Some(image.data);
+            }
+
+            let mut errors = match flashcard_req.validate() {
+                Ok(_) => HashMap::new(),
+                Err(e) => ValidationHelper::new().flatten_errors(e),
+            };
+            if flashcard_req.image_data.is_none() {
+                let message = match item.row.image.as_deref() {
+                    Some(path) => format!("Image {} was not found in the package", path),
+                    None => "An image is required".to_string(),
+                };
+                errors.insert("image".to_string(), message);
+            }
+            if flashcard_req.type_ids.is_empty() {
+                errors.insert(
+                    "type_ids".to_string(),
+                    "At least one flashcard type is required".to_string(),
+                );
+            }
+            for type_id in flashcard_req.type_ids.iter() {
+                if !known_types.contains_key(type_id) {
+                    let exists = _state
+                        .usecases
+                        .flashcard_type
+                        .get_flashcard_type_by_id(*type_id)
+                        .await
+                        .is_some();
+                    known_types.insert(*type_id, exists);
+                }
+                if known_types.get(type_id) == Some(&false) {
+                    errors.insert(
+                        "type_ids".to_string(),
+                        format!("Flashcard type {} does not exist", type_id),
+                    );
+                }
+            }
+
+            rows.push(FlashcardImportRowResult {
+                row: index + 1,
+                name: flashcard_req.name.clone(),
+                flashcard_id: None,
+                errors: if errors.is_empty() { None } else { Some(errors) },
+            });
+            new_flashcards.push(FlashcardCreationDto {
+                name: flashcard_req.name,
+                description: flashcard_req.description,
+                sub_description: flashcard_req.sub_description,
+                content_type: flashcard_req.content_type,
+                file_name: flashcard_req.file_name,
+                image_data: flashcard_req.image_data,
+                type_ids: flashcard_req.type_ids,
+                created_by_id: current_user.id,
+                updated_by_id: current_user.id,
+            });
+        }
+
+        if rows.iter().any(|r| r.errors.is_some()) {
+            return Ok((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(FlashcardImportReport {
+                    imported_count: 0,
+                    rows,
+                }),
+            ));
+        }
+
+        let ids = _state
+            .usecases
+            .flashcard
+            .import_flashcards(new_flashcards)
+            .await
+            .map_err(HandlerError::from)?;
+        for (row, id) in rows.iter_mut().zip(ids.iter()) {
+            row.flashcard_id = Some(*id);
+        }
+
+        Ok((
+            StatusCode::OK,
+            Json(FlashcardImportReport {
+                imported_count: ids.len(),
+                rows,
+            }),
+        ))
+    }
+
+    pub async fn export_flashcards(
+        State(_state): State<AppState>,
+        Path(flashcard_type_id): Path<i32>,
+        Query(params): Query<FlashcardExportQuery>,
+    ) -> HandlerResult<Response<Body>> {
+        let format = match params.format.as_deref() {
+            Some(value) => FlashcardPackageFormat::parse(value).ok_or(HandlerError {
+                status: StatusCode::BAD_REQUEST,
+                message: format!("Unsupported format: {}", value),
+                ..Default::default()
+            })?,
+            None => FlashcardPackageFormat::Csv,
+        };
+        let flashcard_type = _state
+            .usecases
+            .flashcard_type
+            .get_flashcard_type_by_id(flashcard_type_id)
+            .await
+            .ok_or(HandlerError {
+                status: StatusCode::NOT_FOUND,
+                message: "Flashcard type not found".to_string(),
+                ..Default::default()
+            })?;
+
+        let flashcards = _state
+            .usecases
+            .flashcard
+            .export_flashcards(flashcard_type_id)
+            .await
+            .map_err(HandlerError::from)?;
+        let deck_name = flashcard_type.name.clone();
+        let package = tokio::task::spawn_blocking(move || {
+            FlashcardPackageHelper::write_package(format, &deck_name, &flashcards)
+        })
+        .await
+        .map_err(|err| HandlerError {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: format!("Failed to build the export: {}", err),
+            ..Default::default()
+        })?
+        .map_err(HandlerError::from)?;
+
+        HttpHelper::build_attachment_response(
+            package,
+            "application/zip",
+            &format!("{}.{}", flashcard_type.name, format.file_extension()),
+        )
+        .map_err(HandlerError::from)
+    }
 }
 
 pub struct FlashcardHandler {}
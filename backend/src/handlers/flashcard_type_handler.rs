@@ -13,7 +13,7 @@ use rex_game_games::{
     },
     FlashcardTypeUseCaseTrait,
 };
-use rex_game_identity::application::usecases::roles::*;
+use rex_game_identity::domain::rules::OwnershipPolicy;
 use rex_game_shared::domain::models::PageListModel;
 use serde::Deserialize;
 use validator::{Validate, ValidationErrors};
@@ -35,6 +35,40 @@ pub struct FlashcardQuery {
 }
 
 impl FlashcardTypeHandler {
+    /// Loads the flashcard type so the policy can tell whether the user created it
+    pub(crate) async fn authorize_flashcard_type(
+        state: &AppState,
+        current_user: &CurrentUser,
+        id: i32,
+        policy: &OwnershipPolicy,
+    ) -> HandlerResult<()> {
+        let flashcard_type = match state
+            .usecases
+            .flashcard_type
+            .get_flashcard_type_by_id(id)
+            .await
+        {
+            Some(flashcard_type) => flashcard_type,
+            None => {
+                return Err(HandlerError {
+                    status: StatusCode::NOT_FOUND,
+                    message: "Flashcard type not found".to_string(),
+                    ..Default::default()
+                })
+            }
+        };
+
+        if !current_user.can(policy, flashcard_type.created_by_id) {
+            return Err(HandlerError {
+                status: StatusCode::FORBIDDEN,
+                message: "You can only change the flashcard types you created".to_string(),
+                ..Default::default()
+            });
+        }
+
+        Ok(())
+    }
+
     pub async fn get_flashcard_types(
         State(_state): State<AppState>,
         Query(params): Query<FlashcardQuery>,
@@ -104,18 +138,6 @@ impl FlashcardTypeHandler {
             });
         }
 
-        if !current_user
-            .roles
-            .iter()
-            .any(|role| role == ROLE_ROOT_ADMIN)
-        {
-            return Err(HandlerError {
-                status: StatusCode::FORBIDDEN,
-                message: "You do not have permission to create flashcard types".to_string(),
-                ..Default::default()
-            });
-        }
-
         match req.description {
             Some(description) if description.is_empty() => {
                 return Err(HandlerError {
@@ -189,17 +211,13 @@ impl FlashcardTypeHandler {
             ..Default::default()
         };
 
-        if !current_user
-            .roles
-            .iter()
-            .any(|role| role == ROLE_ROOT_ADMIN)
-        {
-            return Err(HandlerError {
-                status: StatusCode::FORBIDDEN,
-                message: "You do not have permission to update flashcard types".to_string(),
-                ..Default::default()
-            });
-        }
+        Self::authorize_flashcard_type(
+            &_state,
+            &current_user,
+            id,
+            &OwnershipPolicy::FLASHCARD_TYPE_UPDATE,
+        )
+        .await?;
 
         for (key, value) in &requests {
             if key.to_lowercase() == "name" {
@@ -239,17 +257,14 @@ impl FlashcardTypeHandler {
         Extension(current_user): Extension<Arc<CurrentUser>>,
         Path(id): Path<i32>,
     ) -> HandlerResult<Json<u64>> {
-        if !current_user
-            .roles
-            .iter()
-            .any(|role| role == ROLE_ROOT_ADMIN)
-        {
-            return Err(HandlerError {
-                status: StatusCode::FORBIDDEN,
-                message: "You do not have permission to delete flashcard types".to_string(),
-                ..Default::default()
-            });
-        }
+        Self::authorize_flashcard_type(
+            &_state,
+            &current_user,
+            id,
+            &OwnershipPolicy::FLASHCARD_TYPE_DELETE,
+        )
+        .await?;
+
         let deleted_numbers = _state
             .usecases
             .flashcard_type
@@ -273,17 +288,13 @@ impl FlashcardTypeHandler {
         Extension(current_user): Extension<Arc<CurrentUser>>,
         Path(id): Path<i32>,
     ) -> HandlerResult<Json<bool>> {
-        if !current_user
-            .roles
-            .iter()
-            .any(|role| role == ROLE_ROOT_ADMIN)
-        {
-            return Err(HandlerError {
-                status: StatusCode::FORBIDDEN,
-                message: "You do not have permission to toggle flashcard type status".to_string(),
-                ..Default::default()
-            });
-        }
+        Self::authorize_flashcard_type(
+            &_state,
+            &current_user,
+            id,
+            &OwnershipPolicy::FLASHCARD_TYPE_UPDATE,
+        )
+        .await?;
 
         let new_status = _state
             .usecases
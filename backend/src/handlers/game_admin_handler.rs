@@ -1,21 +1,23 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
+use rex_game_games::scoring::domain::rules::{AchievementCriteria, ProgressionSettings};
 use rex_game_games::{
-    AchievementCreationDto, AchievementUpdationDto, AdminAchievementDto, AdminGameSessionDto,
-    AdminUserStatsDto, FlashcardDto, FlashcardTypeUseCaseTrait,
-    GameTypeCreationDto, GameTypeDto, GameTypeUpdationDto, ScoringUseCaseTrait,
+    AchievementBackfillCreationDto, AchievementBackfillDto, AchievementCreationDto,
+    AchievementUpdationDto, AdminAchievementDto, AdminGameSessionDto, AdminUserStatsDto,
+    FlashcardDto, FlashcardTypeUseCaseTrait, GameTypeCreationDto, GameTypeDto, GameTypeUpdationDto,
+    ScoringUseCaseTrait,
 };
 use rex_game_shared::domain::models::PageListModel;
 use serde::Deserialize;
 
 use crate::{
     app_state::AppState,
-    view_models::{HandlerError, HandlerResult},
+    view_models::{users::current_user::CurrentUser, HandlerError, HandlerResult},
 };
 
 #[derive(Deserialize)]
@@ -179,6 +181,14 @@ impl GameAdminHandler {
 
     // ---- Achievements ----
 
+    fn validate_achievement_criteria(criteria: &AchievementCriteria) -> HandlerResult<()> {
+        criteria.validate().map_err(|message| HandlerError {
+            status: StatusCode::BAD_REQUEST,
+            message: format!("Invalid criteria: {}", message),
+            ..Default::default()
+        })
+    }
+
     pub async fn get_achievements(
         State(state): State<AppState>,
         Query(params): Query<AdminListQuery>,
@@ -238,6 +248,10 @@ impl GameAdminHandler {
             });
         }
 
+        if let Some(criteria) = &dto.criteria {
+            Self::validate_achievement_criteria(criteria)?;
+        }
+
         match state.usecases.scoring.admin_create_achievement(dto).await {
             Ok(id) => Ok(Json(id)),
             Err(e) => Err(HandlerError {
@@ -281,6 +295,16 @@ impl GameAdminHandler {
                 "icon" => dto.icon = value.as_str().map(|s| s.to_string()),
                 "points" => dto.points = value.as_i64().map(|v| v as i32),
                 "category" => dto.category = value.as_str().map(|s| s.to_string()),
+                "criteria" => {
+                    let criteria: AchievementCriteria = serde_json::from_value(value.clone())
+                        .map_err(|err| HandlerError {
+                            status: StatusCode::BAD_REQUEST,
+                            message: format!("Invalid criteria: {}", err),
+                            ..Default::default()
+                        })?;
+                    Self::validate_achievement_criteria(&criteria)?;
+                    dto.criteria = Some(criteria);
+                }
                 _ => {}
             }
         }
@@ -333,6 +357,65 @@ impl GameAdminHandler {
         }
     }
 
+    // ---- Achievement Backfills ----
+
+    pub async fn get_achievement_backfills(
+        State(state): State<AppState>,
+        Query(params): Query<AdminListQuery>,
+    ) -> Result<Json<PageListModel<AchievementBackfillDto>>, StatusCode> {
+        let page = params.page.unwrap_or(1);
+        let page_size = params.page_size.unwrap_or(10);
+        match state
+            .usecases
+            .scoring
+            .admin_get_achievement_backfills(page, page_size)
+            .await
+        {
+            Ok(data) => Ok(Json(data)),
+            Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+        }
+    }
+
+    pub async fn get_achievement_backfill_by_id(
+        Path(id): Path<i32>,
+        State(state): State<AppState>,
+    ) -> HandlerResult<Json<AchievementBackfillDto>> {
+        match state
+            .usecases
+            .scoring
+            .admin_get_achievement_backfill_by_id(id)
+            .await
+        {
+            Ok(Some(data)) => Ok(Json(data)),
+            Ok(None) => Err(HandlerError {
+                status: StatusCode::NOT_FOUND,
+                message: "Achievement backfill not found".to_string(),
+                ..Default::default()
+            }),
+            Err(_) => Err(HandlerError {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                message: "Failed to fetch achievement backfill".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Queues a backfill; progress is read back from the backfill while the worker runs it
+    pub async fn create_achievement_backfill(
+        State(state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Json(payload): Json<Option<AchievementBackfillCreationDto>>,
+    ) -> HandlerResult<Json<i32>> {
+        let dto = payload.unwrap_or_default();
+        state
+            .usecases
+            .scoring
+            .admin_create_achievement_backfill(current_user.id, dto)
+            .await
+            .map(Json)
+            .map_err(HandlerError::from)
+    }
+
     // ---- Game Sessions ----
 
     pub async fn get_game_sessions(
@@ -399,6 +482,40 @@ impl GameAdminHandler {
         }
     }
 
+    // ---- Progression ----
+
+    pub async fn get_progression_settings(
+        State(state): State<AppState>,
+    ) -> HandlerResult<Json<ProgressionSettings>> {
+        match state
+            .usecases
+            .scoring
+            .admin_get_progression_settings()
+            .await
+        {
+            Ok(settings) => Ok(Json(settings)),
+            Err(_) => Err(HandlerError {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                message: "Failed to fetch progression settings".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    pub async fn update_progression_settings(
+        State(state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Json(payload): Json<ProgressionSettings>,
+    ) -> HandlerResult<Json<ProgressionSettings>> {
+        state
+            .usecases
+            .scoring
+            .admin_update_progression_settings(current_user.id, payload)
+            .await
+            .map(Json)
+            .map_err(HandlerError::from)
+    }
+
     // ---- Game Type Flashcards ----
 
     pub async fn get_game_type_flashcards(
@@ -433,7 +550,9 @@ impl GameAdminHandler {
                 sub_description: f.sub_description,
                 created_on: f.created_on.with_timezone(&chrono::Utc),
                 updated_on: f.updated_on.with_timezone(&chrono::Utc),
+                created_by_id: f.created_by_id,
                 image_id: f.file_id,
+                image_version: None,
                 is_actived: f.is_actived,
                 flashcard_type_names: vec![],
             };
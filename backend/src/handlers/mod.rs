@@ -1,4 +1,5 @@
 pub mod authentication_handler;
+pub mod email_outbox_handler;
 pub mod health_handler;
 pub mod flashcard_handler;
 pub mod flashcard_type_handler;
@@ -9,3 +10,4 @@ pub mod role_handler;
 pub mod scoring_handler;
 pub mod setup_handler;
 pub mod user_handler;
+pub mod well_known_handler;
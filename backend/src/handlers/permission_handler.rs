@@ -286,14 +286,17 @@ impl PermissionHandler {
             .permission
             .update_permission(id, updating)
             .await;
-        return match result {
-            None => Err(HandlerError {
+        if result.is_none() {
+            return Err(HandlerError {
                 status: StatusCode::INTERNAL_SERVER_ERROR,
                 message: "Failed to update permission".to_string(),
                 ..Default::default()
-            }),
-            Some(_) => Ok(Json(true)),
-        };
+            });
+        }
+
+        // Access tokens carry permission codes, so the permission's holders refresh theirs
+        Self::refresh_permission_holders(&_state, id).await?;
+        Ok(Json(true))
     }
 
     pub async fn delete_permission(
@@ -343,7 +346,10 @@ impl PermissionHandler {
             .await;
 
         match is_succeed {
-            Some(u) => Ok(Json(u)),
+            Some(u) => {
+                Self::refresh_permission_holders(&_state, id).await?;
+                Ok(Json(u))
+            }
             None => {
                 return Err(HandlerError {
                     status: StatusCode::INTERNAL_SERVER_ERROR,
@@ -413,6 +419,26 @@ impl PermissionHandler {
             }
         }
     }
+
+    /// Makes the holders of the permission, directly or through any role granting or
+    /// inheriting it, refresh their access tokens
+    async fn refresh_permission_holders(
+        _state: &AppState,
+        permission_id: i32,
+    ) -> HandlerResult<()> {
+        let role_ids = _state
+            .usecases
+            .role
+            .get_inheriting_role_ids_by_permission(permission_id)
+            .await
+            .map_err(HandlerError::from)?;
+        _state
+            .usecases
+            .user
+            .increment_authorization_version_by_permission(permission_id, role_ids)
+            .await
+            .map_err(HandlerError::from)
+    }
 }
 
 pub struct PermissionHandler {}
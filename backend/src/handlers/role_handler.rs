@@ -17,8 +17,9 @@ use rex_game_identity::application::usecases::{
     role_permission_creation_dto::RolePermissionCreationDto,
     role_permission_dto::RolePermissionDto, role_updation_dto::RoleUpdationDto,
     roles::ROLE_ROOT_ADMIN, user_role_dto::UserRoleDto, PermissionUseCaseTrait, RoleUseCaseTrait,
+    UserUseCaseTrait,
 };
-use rex_game_shared::domain::models::PageListModel;
+use rex_game_shared::{domain::models::PageListModel, ApplicationError};
 use serde::Deserialize;
 use std::{collections::HashMap, sync::Arc};
 use validator::{Validate, ValidationErrors};
@@ -127,13 +128,14 @@ impl RoleHandler {
         let new_role = RoleCreationDto {
             name: req.name,
             description: req.description,
+            parent_id: req.parent_id,
             created_by_id: Some(current_user.id),
             updated_by_id: Some(current_user.id),
-            ..Default::default()
         };
         let created_result = _state.usecases.role.create_role(new_role).await;
         match created_result {
             Ok(created_id) => Ok(Json(created_id)),
+            Err(err @ ApplicationError::InvalidInput(_)) => Err(HandlerError::from(err)),
             Err(_) => Err(HandlerError {
                 status: StatusCode::INTERNAL_SERVER_ERROR,
                 message: "Failed to create role".to_string(),
@@ -166,10 +168,15 @@ impl RoleHandler {
             });
         }
 
-        if requests.get("name").is_none() && requests.get("description").is_none() {
+        if !requests.contains_key("name")
+            && !requests.contains_key("description")
+            && !requests.contains_key("requires_mfa")
+            && !requests.contains_key("parent_id")
+        {
             return Err(HandlerError {
                 status: StatusCode::BAD_REQUEST,
-                message: "At least one of 'name' or 'description' must be provided".to_string(),
+                message: "At least one of 'name', 'description', 'requires_mfa' or 'parent_id' must be provided"
+                    .to_string(),
                 ..Default::default()
             });
         }
@@ -209,6 +216,7 @@ impl RoleHandler {
             updated_by_id: current_user.id,
             ..Default::default()
         };
+        let mut parent_id = None;
 
         for (key, value) in &requests {
             if key.to_lowercase() == "name" {
@@ -233,18 +241,49 @@ impl RoleHandler {
                 updating.name = Some(value.to_string());
             } else if key.to_lowercase() == "description" {
                 updating.description = Some(value.to_string())
+            } else if key.to_lowercase() == "requires_mfa" {
+                let requires_mfa = value.parse::<bool>().map_err(|_| HandlerError {
+                    status: StatusCode::BAD_REQUEST,
+                    message: "requires_mfa must be 'true' or 'false'".to_string(),
+                    ..Default::default()
+                })?;
+                updating.requires_mfa = Some(requires_mfa);
+            } else if key.to_lowercase() == "parent_id" {
+                // An empty value stops the role inheriting from anything
+                let parent = match value.trim() {
+                    "" => None,
+                    value => Some(value.parse::<i32>().map_err(|_| HandlerError {
+                        status: StatusCode::BAD_REQUEST,
+                        message: "parent_id must be a role id or empty".to_string(),
+                        ..Default::default()
+                    })?),
+                };
+                parent_id = Some(parent);
             }
         }
 
+        if let Some(parent_id) = parent_id {
+            _state
+                .usecases
+                .role
+                .set_role_parent(id, parent_id, current_user.id)
+                .await
+                .map_err(HandlerError::from)?;
+        }
+
         let result = _state.usecases.role.update_role(id, updating).await;
-        return match result {
-            None => Err(HandlerError {
+        if result.is_none() {
+            return Err(HandlerError {
                 status: StatusCode::INTERNAL_SERVER_ERROR,
                 message: "Failed to update role".to_string(),
                 ..Default::default()
-            }),
-            Some(_) => Ok(Json(true)),
-        };
+            });
+        }
+
+        // Access tokens carry role names and inherited permissions, so the holders of the
+        // role and of roles inheriting from it refresh theirs
+        Self::refresh_role_holders(&_state, vec![id]).await?;
+        Ok(Json(true))
     }
 
     pub async fn delete_role(
@@ -287,6 +326,14 @@ impl RoleHandler {
             });
         }
 
+        // Roles stop inheriting from a deleted role, so find them first
+        let role_ids = _state
+            .usecases
+            .role
+            .get_inheriting_role_ids(vec![id])
+            .await
+            .map_err(HandlerError::from)?;
+
         let is_succeed = _state
             .usecases
             .role
@@ -294,7 +341,15 @@ impl RoleHandler {
             .await;
 
         match is_succeed {
-            Some(u) => Ok(Json(u)),
+            Some(u) => {
+                _state
+                    .usecases
+                    .user
+                    .increment_authorization_version_by_roles(role_ids)
+                    .await
+                    .map_err(HandlerError::from)?;
+                Ok(Json(u))
+            }
             None => {
                 return Err(HandlerError {
                     status: StatusCode::INTERNAL_SERVER_ERROR,
@@ -426,6 +481,8 @@ impl RoleHandler {
             .await
             .ok();
 
+        Self::refresh_role_holders(&_state, vec![role_id]).await?;
+
         Ok(Json(to_be_assigned_permissons.len() as i32))
     }
 
@@ -503,6 +560,23 @@ impl RoleHandler {
             }
         }
     }
+
+    /// Makes the holders of the roles, and of roles inheriting from them, refresh their
+    /// access tokens
+    async fn refresh_role_holders(_state: &AppState, role_ids: Vec<i32>) -> HandlerResult<()> {
+        let role_ids = _state
+            .usecases
+            .role
+            .get_inheriting_role_ids(role_ids)
+            .await
+            .map_err(HandlerError::from)?;
+        _state
+            .usecases
+            .user
+            .increment_authorization_version_by_roles(role_ids)
+            .await
+            .map_err(HandlerError::from)
+    }
 }
 
 pub struct RoleHandler {}
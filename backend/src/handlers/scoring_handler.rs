@@ -1,5 +1,6 @@
 use crate::{
     app_state::AppState,
+    validators::validation_helper::ValidationHelper,
     view_models::{users::current_user::CurrentUser, HandlerError, HandlerResult},
 };
 use axum::{
@@ -8,12 +9,15 @@ use axum::{
     Extension, Json,
 };
 use rex_game_games::{
-    AchievementDto, CompleteGameSessionDto, GameCompleteResponseDto, GameProgressDto,
-    GameSessionDto, GameTypeDto, LeaderboardEntryDto, SaveGameProgressDto, ScoringUseCaseTrait,
-    StartGameSessionDto, UserStatsDto,
+    AchievementDto, CompleteGameSessionDto, DueCardDto, GameAnswerResultDto,
+    GameCompleteResponseDto, GameProgressDto, GameSessionDto, GameSessionStartedDto, GameTypeDto,
+    LeaderboardEntryDto, LeaderboardFilterDto, LeaderboardPositionDto, SaveGameProgressDto,
+    ScoringUseCaseTrait, StartGameSessionDto, StreakDto, StreakSettingsDto, SubmitGameAnswerDto,
+    UserStatsDto,
 };
 use serde::Deserialize;
 use std::sync::Arc;
+use validator::{Validate, ValidationErrors};
 
 #[derive(Deserialize)]
 pub struct PaginationQuery {
@@ -27,11 +31,34 @@ pub struct BestGamesQuery {
     limit: Option<u64>,
 }
 
+#[derive(Deserialize)]
+pub struct LeaderboardQuery {
+    page: Option<u64>,
+    page_size: Option<u64>,
+    period: Option<String>,
+    game_type: Option<String>,
+    flashcard_type_id: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct LeaderboardPositionQuery {
+    period: Option<String>,
+    game_type: Option<String>,
+    flashcard_type_id: Option<i32>,
+    radius: Option<u64>,
+}
+
 #[derive(Deserialize)]
 pub struct GameProgressQuery {
     game_type: String,
 }
 
+#[derive(Deserialize)]
+pub struct DueCardsQuery {
+    flashcard_type_id: i32,
+    limit: Option<u64>,
+}
+
 pub struct ScoringHandler {}
 
 impl ScoringHandler {
@@ -58,8 +85,8 @@ impl ScoringHandler {
         State(state): State<AppState>,
         Extension(current_user): Extension<Arc<CurrentUser>>,
         Json(dto): Json<StartGameSessionDto>,
-    ) -> HandlerResult<Json<i32>> {
-        let session_id = state
+    ) -> HandlerResult<Json<GameSessionStartedDto>> {
+        let started = state
             .usecases
             .scoring
             .start_game_session(current_user.id, dto)
@@ -70,7 +97,30 @@ impl ScoringHandler {
                 ..Default::default()
             })?;
 
-        Ok(Json(session_id))
+        Ok(Json(started))
+    }
+
+    /// POST /games/sessions/{session_id}/answers - Submit an answer for a dealt card
+    pub async fn submit_game_answer(
+        State(state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Path(session_id): Path<i32>,
+        Json(dto): Json<SubmitGameAnswerDto>,
+    ) -> HandlerResult<Json<GameAnswerResultDto>> {
+        dto.validate().map_err(|e: ValidationErrors| HandlerError {
+            status: StatusCode::BAD_REQUEST,
+            message: "Validation error".to_string(),
+            field_errors: Some(ValidationHelper::new().flatten_errors(e)),
+        })?;
+
+        let result = state
+            .usecases
+            .scoring
+            .submit_game_answer(current_user.id, session_id, dto)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(result))
     }
 
     /// POST /games/sessions/complete - Complete a game session
@@ -84,15 +134,27 @@ impl ScoringHandler {
             .scoring
             .complete_game_session(current_user.id, dto)
             .await
-            .map_err(|err| HandlerError {
-                status: StatusCode::BAD_REQUEST,
-                message: format!("Failed to complete game session: {}", err),
-                ..Default::default()
-            })?;
+            .map_err(HandlerError::from)?;
 
         Ok(Json(response))
     }
 
+    /// POST /games/sessions/{session_id}/abandon - Abandon an in-progress game session
+    pub async fn abandon_game_session(
+        State(state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Path(session_id): Path<i32>,
+    ) -> HandlerResult<Json<bool>> {
+        let abandoned = state
+            .usecases
+            .scoring
+            .abandon_game_session(current_user.id, session_id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(abandoned))
+    }
+
     /// GET /games/history - Get current user's game history
     pub async fn get_game_history(
         State(state): State<AppState>,
@@ -176,28 +238,98 @@ impl ScoringHandler {
         Ok(Json(stats))
     }
 
-    /// GET /leaderboard - Get global leaderboard
+    /// GET /users/me/streak - Get current user's daily streak and recent streak days
+    pub async fn get_my_streak(
+        State(state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+    ) -> HandlerResult<Json<StreakDto>> {
+        let streak = state
+            .usecases
+            .scoring
+            .get_user_streak(current_user.id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(streak))
+    }
+
+    /// PUT /users/me/streak/settings - Set current user's timezone offset and reminders
+    pub async fn update_my_streak_settings(
+        State(state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Json(dto): Json<StreakSettingsDto>,
+    ) -> HandlerResult<Json<StreakDto>> {
+        let streak = state
+            .usecases
+            .scoring
+            .update_streak_settings(current_user.id, dto)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(streak))
+    }
+
+    /// POST /users/me/streak/freezes - Spend score on a streak freeze
+    pub async fn purchase_streak_freeze(
+        State(state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+    ) -> HandlerResult<Json<StreakDto>> {
+        let streak = state
+            .usecases
+            .scoring
+            .purchase_streak_freeze(current_user.id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(streak))
+    }
+
+    /// GET /leaderboard - Get a leaderboard, all-time over all games unless filtered
     pub async fn get_leaderboard(
         State(state): State<AppState>,
-        Query(params): Query<PaginationQuery>,
+        Query(params): Query<LeaderboardQuery>,
     ) -> HandlerResult<Json<Vec<LeaderboardEntryDto>>> {
         let page = params.page.unwrap_or(1);
         let page_size = params.page_size.unwrap_or(10);
+        let filter = LeaderboardFilterDto {
+            period: params.period,
+            game_type_code: params.game_type,
+            flashcard_type_id: params.flashcard_type_id,
+        };
 
         let entries = state
             .usecases
             .scoring
-            .get_leaderboard(page, page_size)
+            .get_leaderboard(filter, page, page_size)
             .await
-            .map_err(|err| HandlerError {
-                status: StatusCode::INTERNAL_SERVER_ERROR,
-                message: format!("Failed to fetch leaderboard: {}", err),
-                ..Default::default()
-            })?;
+            .map_err(HandlerError::from)?;
 
         Ok(Json(entries))
     }
 
+    /// GET /leaderboard/me - Get current user's rank and the players around them
+    pub async fn get_my_leaderboard_position(
+        State(state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Query(params): Query<LeaderboardPositionQuery>,
+    ) -> HandlerResult<Json<LeaderboardPositionDto>> {
+        let radius = params.radius.unwrap_or(5);
+        let filter = LeaderboardFilterDto {
+            period: params.period,
+            game_type_code: params.game_type,
+            flashcard_type_id: params.flashcard_type_id,
+        };
+
+        let position = state
+            .usecases
+            .scoring
+            .get_leaderboard_position(current_user.id, filter, radius)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(position))
+    }
+
     /// GET /achievements - Get all achievements (with user progress if authenticated)
     pub async fn get_achievements(
         State(state): State<AppState>,
@@ -294,4 +426,21 @@ impl ScoringHandler {
 
         Ok(StatusCode::NO_CONTENT)
     }
+    /// GET /reviews/due - Get the current user's review deck for a flashcard type
+    pub async fn get_due_cards(
+        State(state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Query(params): Query<DueCardsQuery>,
+    ) -> HandlerResult<Json<Vec<DueCardDto>>> {
+        let limit = params.limit.unwrap_or(20);
+
+        let cards = state
+            .usecases
+            .scoring
+            .get_due_cards(current_user.id, params.flashcard_type_id, limit)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(cards))
+    }
 }
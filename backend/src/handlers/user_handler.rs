@@ -1,10 +1,15 @@
+use crate::handlers::authentication_handler::AuthenticationHandler;
+use crate::helpers::account_email_helper::AccountEmailHelper;
 use crate::validators::validation_helper::ValidationHelper;
 use crate::view_models::users::assign_permission_request::AssignPermissionRequest;
 use crate::view_models::users::assign_role_request::AssignRoleRequest;
 use crate::view_models::users::confirm_user_request::ConfirmUserRequest;
 use crate::view_models::users::current_user::CurrentUser;
 use crate::view_models::users::forgot_password_request::ForgotPasswordRequest;
+use crate::view_models::authentications::api_key_creation_request::ApiKeyCreationRequest;
 use crate::view_models::users::reset_password_request::ResetPasswordRequest;
+use crate::view_models::users::service_account_creation_request::ServiceAccountCreationRequest;
+use crate::view_models::users::suspend_user_request::SuspendUserRequest;
 use crate::view_models::{HandlerError, HandlerResult};
 use crate::{app_state::AppState, view_models::users::signup_request::SignupRequest};
 use axum::extract::{Path, Query};
@@ -14,9 +19,16 @@ use chrono::{DateTime, Duration, Utc};
 use hyper::StatusCode;
 use rex_game_identity::application::usecases::{
     auth::{
+        api_key_creation_dto::ApiKeyCreationDto, api_key_dto::ApiKeyDto,
+        created_api_key_dto::CreatedApiKeyDto,
+        permission_explanation_dto::PermissionExplanationDto,
+        service_account_creation_dto::ServiceAccountCreationDto,
         user_creation_dto::UserCreationDto, user_token_creation_dto::UserTokenCreationDto,
-        user_token_updation_dto::UserTokenUpdationDto, IdentityUserTokenUseCaseTrait,
-        IdentityUserUseCaseTrait,
+        user_status_dto::UserStatusDto, user_suspension_dto::UserSuspensionDto,
+        user_token_updation_dto::UserTokenUpdationDto, IdentityApiKeyUseCaseTrait,
+        IdentityAuthenticateUseCaseTrait,
+        IdentityLoginThrottleUseCaseTrait, IdentityUserStatusUseCaseTrait,
+        IdentityUserTokenUseCaseTrait, IdentityUserUseCaseTrait,
     },
     roles::ROLE_ROOT_ADMIN,
     user_deletion_dto::UserDeletionDto,
@@ -34,7 +46,11 @@ use rex_game_identity::domain::services::TokenGenerationOptions;
 use rex_game_mail_templates::application::MailTemplateUseCaseTrait;
 use rex_game_shared::domain::enums::mail_template_names::MailTemplateNames;
 use rex_game_shared::domain::enums::user_token_porposes::UserTokenPurposes;
-use rex_game_shared::domain::helpers::email_helper_trait::{EmailHelperTrait, EmailMessage};
+use rex_game_mail_templates::application::EmailOutboxUseCaseTrait;
+use rex_game_shared::domain::helpers::email_helper_trait::EmailMessage;
+use rex_game_shared::domain::transaction_manager_trait::{
+    TransactionManagerTrait, TransactionWrapperTrait,
+};
 use rex_game_shared::domain::models::PageListModel;
 use rex_game_shared::infrastructure::helpers::configuration_helper::ConfigurationHelper;
 use serde::Deserialize;
@@ -147,121 +163,162 @@ impl UserHandler {
             ..Default::default()
         };
 
-        let signup_result = _state
+        let register_mail_template = match _state
             .usecases
-            .identity_user
-            .create_user(new_user, &req.password)
+            .mail_template
+            .get_by_name(MailTemplateNames::USER_REGISTRATION_CONFIRMATION.to_string())
             .await
-            .map_err(|err| HandlerError {
-                status: StatusCode::INTERNAL_SERVER_ERROR,
-                message: err.to_string(),
-                ..Default::default()
-            })?;
-
-        let generated_token_options = TokenGenerationOptions {
-            email: Some(signup_result.email),
-            user_id: signup_result.id,
-            exp_secs: Duration::days(1).num_seconds(),
-            purpose: UserTokenPurposes::SignupConfirmation.to_string(),
-            iat: Some(Utc::now().timestamp()),
-            permissions: vec![],
-            roles: vec![],
-        };
-        let generated_token_option = _state.helpers.token.generate_token(generated_token_options);
-
-        let generated_token = match generated_token_option {
-            Some(token) => token,
-            None => {
+        {
+            Ok(template) => template,
+            Err(_) => {
                 return Err(HandlerError {
                     status: StatusCode::INTERNAL_SERVER_ERROR,
-                    message: "Failed to generate signup token".to_string(),
+                    message: "Failed to get mail template".to_string(),
                     ..Default::default()
-                })
+                });
             }
         };
 
-        let token_creation = UserTokenCreationDto {
-            created_by_id: signup_result.id,
-            expiration: generated_token.exp as i32,
-            purpose: UserTokenPurposes::SignupConfirmation as i32,
-            token: generated_token.token.clone(),
-            user_id: signup_result.id,
-            updated_by_id: signup_result.id,
-        };
-
-        _state
-            .usecases
-            .identity_user_token
-            .create_user_token(token_creation)
+        // The user, its confirmation token and the email are committed together. The email
+        // is sent later by the outbox worker, so a slow mail server cannot fail the signup.
+        let transaction = _state
+            .transaction_manager
+            .begin()
             .await
             .map_err(|err| HandlerError {
                 status: StatusCode::INTERNAL_SERVER_ERROR,
                 message: err.to_string(),
                 ..Default::default()
             })?;
-
-        let register_mail_template = match _state
-            .usecases
-            .mail_template
-            .get_by_name(MailTemplateNames::USER_REGISTRATION_CONFIRMATION.to_string())
-            .await
-        {
-            Ok(template) => template,
-            Err(_) => {
-                return Err(HandlerError {
+        let signup_result = async {
+            let transaction_ref: &dyn TransactionWrapperTrait = &transaction;
+            let signup_result = _state
+                .usecases
+                .identity_user
+                .create_user_with_transaction(new_user, &req.password, Box::new(transaction_ref))
+                .await
+                .map_err(|err| HandlerError {
                     status: StatusCode::INTERNAL_SERVER_ERROR,
-                    message: "Failed to get mail template".to_string(),
+                    message: err.to_string(),
                     ..Default::default()
-                });
-            }
-        };
+                })?;
 
-        if register_mail_template.is_enabled {
-            let configuration_helper = Arc::new(ConfigurationHelper::new());
-            let verification_url = configuration_helper.get("SIGNUP_VERIFICATION_URL");
-            let from_name = configuration_helper.get("EMAIL_FROM_NAME");
-            let username = configuration_helper.get("SMTP_USERNAME");
-            let platform_name = configuration_helper.get("PLATFORM_NAME");
-            let platform_url = configuration_helper.get("PLATFORM_URL");
-            let expiration_date = DateTime::from_timestamp(generated_token.exp as i64, 0)
-                .unwrap()
-                .format("%d/%m/%Y %H:%M")
-                .to_string();
-
-            let verification_url = verification_url.replace("[token]", &generated_token.token);
-            let email_body = register_mail_template
-                .body
-                .replace("[user_name]", &req.name)
-                .replace("[confirmation_url]", &verification_url)
-                .replace("[expiration_date]", &expiration_date)
-                .replace("[platform_name]", &platform_name)
-                .replace("[platform_url]", &platform_url);
-
-            let subject = register_mail_template
-                .subject
-                .replace("[platform_name]", &platform_name);
+            let generated_token_options = TokenGenerationOptions {
+                email: Some(signup_result.email),
+                user_id: signup_result.id,
+                exp_secs: Duration::days(1).num_seconds(),
+                purpose: UserTokenPurposes::SignupConfirmation.to_string(),
+                iat: Some(Utc::now().timestamp()),
+                permissions: vec![],
+                roles: vec![],
+                security_stamp: None,
+                authorization_version: None,
+            };
+            let generated_token_option =
+                _state.helpers.token.generate_token(generated_token_options);
+
+            let generated_token = match generated_token_option {
+                Some(token) => token,
+                None => {
+                    return Err(HandlerError {
+                        status: StatusCode::INTERNAL_SERVER_ERROR,
+                        message: "Failed to generate signup token".to_string(),
+                        ..Default::default()
+                    })
+                }
+            };
+
+            let token_creation = UserTokenCreationDto {
+                created_by_id: signup_result.id,
+                expiration: generated_token.exp as i32,
+                purpose: UserTokenPurposes::SignupConfirmation as i32,
+                token: generated_token.token.clone(),
+                user_id: signup_result.id,
+                updated_by_id: signup_result.id,
+            };
 
             _state
-                .helpers
-                .email
-                .send_email(EmailMessage {
-                    to_name: Some(req.name.to_owned()),
-                    to_email: req.email,
-                    from_name: Some(from_name.to_owned()),
-                    from_email: username.to_owned(),
-                    subject: subject,
-                    text_body: None,
-                    html_body: Some(email_body),
-                })
+                .usecases
+                .identity_user_token
+                .create_user_token_with_transaction(token_creation, Box::new(transaction_ref))
                 .await
                 .map_err(|err| HandlerError {
                     status: StatusCode::INTERNAL_SERVER_ERROR,
                     message: err.to_string(),
                     ..Default::default()
                 })?;
-        }
 
-        Ok(Json(signup_result.id))
+            if register_mail_template.is_enabled {
+                let configuration_helper = Arc::new(ConfigurationHelper::new());
+                let verification_url = configuration_helper.get("SIGNUP_VERIFICATION_URL");
+                let from_name = configuration_helper.get("EMAIL_FROM_NAME");
+                let username = configuration_helper.get("SMTP_USERNAME");
+                let platform_name = configuration_helper.get("PLATFORM_NAME");
+                let platform_url = configuration_helper.get("PLATFORM_URL");
+                let expiration_date = DateTime::from_timestamp(generated_token.exp as i64, 0)
+                    .unwrap()
+                    .format("%d/%m/%Y %H:%M")
+                    .to_string();
+
+                let verification_url =
+                    verification_url.replace("[token]", &generated_token.token);
+                let email_body = register_mail_template
+                    .body
+                    .replace("[user_name]", &req.name)
+                    .replace("[confirmation_url]", &verification_url)
+                    .replace("[expiration_date]", &expiration_date)
+                    .replace("[platform_name]", &platform_name)
+                    .replace("[platform_url]", &platform_url);
+
+                let subject = register_mail_template
+                    .subject
+                    .replace("[platform_name]", &platform_name);
+
+                _state
+                    .usecases
+                    .email_outbox
+                    .enqueue_with_transaction(
+                        EmailMessage {
+                            to_name: Some(req.name.to_owned()),
+                            to_email: req.email.to_owned(),
+                            from_name: Some(from_name.to_owned()),
+                            from_email: username.to_owned(),
+                            subject,
+                            text_body: None,
+                            html_body: Some(email_body),
+                        },
+                        Box::new(transaction_ref),
+                    )
+                    .await
+                    .map_err(|err| HandlerError {
+                        status: StatusCode::INTERNAL_SERVER_ERROR,
+                        message: err.to_string(),
+                        ..Default::default()
+                    })?;
+            }
+
+            Ok::<i32, HandlerError>(signup_result.id)
+        }
+        .await;
+
+        match signup_result {
+            Ok(user_id) => {
+                _state
+                    .transaction_manager
+                    .commit(transaction)
+                    .await
+                    .map_err(|err| HandlerError {
+                        status: StatusCode::INTERNAL_SERVER_ERROR,
+                        message: err.to_string(),
+                        ..Default::default()
+                    })?;
+                Ok(Json(user_id))
+            }
+            Err(err) => {
+                let _ = _state.transaction_manager.rollback(transaction).await;
+                Err(err)
+            }
+        }
     }
 
     pub async fn confirm_user(
@@ -376,6 +433,15 @@ impl UserHandler {
             });
         }
 
+        // Confirming must not lift a suspension or ban
+        if existing_user.status_id != (UserStatuses::Pending as i32) {
+            return Err(HandlerError {
+                status: StatusCode::CONFLICT,
+                message: "The user is not awaiting confirmation".to_string(),
+                ..Default::default()
+            });
+        }
+
         let updating = UserUpdationDto {
             status_id: Some(UserStatuses::Actived as i32),
             ..Default::default()
@@ -432,6 +498,22 @@ impl UserHandler {
                 ..Default::default()
             })?;
 
+        let forgot_password_mail_template = match _state
+            .usecases
+            .mail_template
+            .get_by_name(MailTemplateNames::PASSWORD_RESET_REQUEST.to_string())
+            .await
+        {
+            Ok(template) => template,
+            Err(_) => {
+                return Err(HandlerError {
+                    status: StatusCode::INTERNAL_SERVER_ERROR,
+                    message: "Failed to get mail template".to_string(),
+                    ..Default::default()
+                });
+            }
+        };
+
         let generated_token_options = TokenGenerationOptions {
             email: None,
             user_id: existing_user.id,
@@ -440,6 +522,8 @@ impl UserHandler {
             iat: None,
             permissions: vec![],
             roles: vec![],
+            security_stamp: None,
+            authorization_version: None,
         };
         let generated_token_option = _state.helpers.token.generate_token(generated_token_options);
 
@@ -463,79 +547,101 @@ impl UserHandler {
             updated_by_id: existing_user.id,
         };
 
-        _state
-            .usecases
-            .identity_user_token
-            .create_user_token(token_creation)
+        // The token and its email are committed together; the outbox worker sends it
+        let transaction = _state
+            .transaction_manager
+            .begin()
             .await
             .map_err(|err| HandlerError {
                 status: StatusCode::INTERNAL_SERVER_ERROR,
                 message: err.to_string(),
                 ..Default::default()
             })?;
-
-        let forgot_password_mail_template = match _state
-            .usecases
-            .mail_template
-            .get_by_name(MailTemplateNames::PASSWORD_RESET_REQUEST.to_string())
-            .await
-        {
-            Ok(template) => template,
-            Err(_) => {
-                return Err(HandlerError {
-                    status: StatusCode::INTERNAL_SERVER_ERROR,
-                    message: "Failed to get mail template".to_string(),
-                    ..Default::default()
-                });
-            }
-        };
-
-        if forgot_password_mail_template.is_enabled {
-            let configuration_helper = _state.helpers.configuration;
-            let reset_password_url: String = configuration_helper.get("RESET_PASSWORD_URL");
-            let from_name: String = configuration_helper.get("EMAIL_FROM_NAME");
-            let username: String = configuration_helper.get("SMTP_USERNAME");
-            let platform_name: String = configuration_helper.get("PLATFORM_NAME");
-            let platform_url: String = configuration_helper.get("PLATFORM_URL");
-            let expiration_date: String = DateTime::from_timestamp(generated_token.exp as i64, 0)
-                .unwrap()
-                .format("%d/%m/%Y %H:%M")
-                .to_string();
-
-            let reset_password_url = reset_password_url.replace("[token]", &generated_token.token);
-            let email_body = forgot_password_mail_template
-                .body
-                .replace("[user_name]", &existing_user.name)
-                .replace("[reset_url]", &reset_password_url)
-                .replace("[expiration_date]", &expiration_date)
-                .replace("[platform_name]", &platform_name)
-                .replace("[platform_url]", &platform_url);
-
-            let subject = forgot_password_mail_template
-                .subject
-                .replace("[platform_name]", &platform_name);
-
+        let queued = async {
+            let transaction_ref: &dyn TransactionWrapperTrait = &transaction;
             _state
-                .helpers
-                .email
-                .send_email(EmailMessage {
-                    to_name: Some(existing_user.name.to_owned()),
-                    to_email: req.email,
-                    from_name: Some(from_name.to_owned()),
-                    from_email: username.to_owned(),
-                    subject: subject,
-                    text_body: None,
-                    html_body: Some(email_body),
-                })
+                .usecases
+                .identity_user_token
+                .create_user_token_with_transaction(token_creation, Box::new(transaction_ref))
                 .await
                 .map_err(|err| HandlerError {
                     status: StatusCode::INTERNAL_SERVER_ERROR,
                     message: err.to_string(),
                     ..Default::default()
                 })?;
-        }
 
-        Ok(Json(true))
+            if forgot_password_mail_template.is_enabled {
+                let configuration_helper = _state.helpers.configuration.clone();
+                let reset_password_url: String = configuration_helper.get("RESET_PASSWORD_URL");
+                let from_name: String = configuration_helper.get("EMAIL_FROM_NAME");
+                let username: String = configuration_helper.get("SMTP_USERNAME");
+                let platform_name: String = configuration_helper.get("PLATFORM_NAME");
+                let platform_url: String = configuration_helper.get("PLATFORM_URL");
+                let expiration_date: String =
+                    DateTime::from_timestamp(generated_token.exp as i64, 0)
+                        .unwrap()
+                        .format("%d/%m/%Y %H:%M")
+                        .to_string();
+
+                let reset_password_url =
+                    reset_password_url.replace("[token]", &generated_token.token);
+                let email_body = forgot_password_mail_template
+                    .body
+                    .replace("[user_name]", &existing_user.name)
+                    .replace("[reset_url]", &reset_password_url)
+                    .replace("[expiration_date]", &expiration_date)
+                    .replace("[platform_name]", &platform_name)
+                    .replace("[platform_url]", &platform_url);
+
+                let subject = forgot_password_mail_template
+                    .subject
+                    .replace("[platform_name]", &platform_name);
+
+                _state
+                    .usecases
+                    .email_outbox
+                    .enqueue_with_transaction(
+                        EmailMessage {
+                            to_name: Some(existing_user.name.to_owned()),
+                            to_email: req.email.to_owned(),
+                            from_name: Some(from_name.to_owned()),
+                            from_email: username.to_owned(),
+                            subject,
+                            text_body: None,
+                            html_body: Some(email_body),
+                        },
+                        Box::new(transaction_ref),
+                    )
+                    .await
+                    .map_err(|err| HandlerError {
+                        status: StatusCode::INTERNAL_SERVER_ERROR,
+                        message: err.to_string(),
+                        ..Default::default()
+                    })?;
+            }
+
+            Ok::<(), HandlerError>(())
+        }
+        .await;
+
+        match queued {
+            Ok(_) => {
+                _state
+                    .transaction_manager
+                    .commit(transaction)
+                    .await
+                    .map_err(|err| HandlerError {
+                        status: StatusCode::INTERNAL_SERVER_ERROR,
+                        message: err.to_string(),
+                        ..Default::default()
+                    })?;
+                Ok(Json(true))
+            }
+            Err(err) => {
+                let _ = _state.transaction_manager.rollback(transaction).await;
+                Err(err)
+            }
+        }
     }
 
     pub async fn reset_password(
@@ -665,7 +771,19 @@ impl UserHandler {
         State(_state): State<AppState>,
         Extension(current_user): Extension<Arc<CurrentUser>>,
     ) -> Result<Json<CurrentUser>, StatusCode> {
-        Ok(Json((*current_user).clone()))
+        let mut current_user = (*current_user).clone();
+
+        // Access tokens carry the roles and permissions but not the name
+        let user = _state
+            .usecases
+            .user
+            .get_user_by_id(current_user.id)
+            .await
+            .map_err(|_| StatusCode::NOT_FOUND)?;
+        current_user.name = user.name;
+        current_user.display_name = user.display_name;
+
+        Ok(Json(current_user))
     }
 
     pub async fn update_user(
@@ -812,6 +930,175 @@ impl UserHandler {
         }
     }
 
+    pub async fn sign_out_user(
+        State(_state): State<AppState>,
+        Path(user_id): Path<i32>,
+    ) -> HandlerResult<Json<bool>> {
+        let signed_out = _state
+            .usecases
+            .identity_authenticate
+            .sign_out_everywhere(user_id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(signed_out))
+    }
+
+    /// POST /users/{user_id}/unlock - Lift a lock left by too many failed logins
+    pub async fn unlock_user(
+        State(_state): State<AppState>,
+        Path(user_id): Path<i32>,
+    ) -> HandlerResult<Json<bool>> {
+        let user = _state
+            .usecases
+            .user
+            .get_user_by_id(user_id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        let unlocked = _state
+            .usecases
+            .identity_login_throttle
+            .unlock(user_id)
+            .await
+            .map_err(HandlerError::from)?;
+        if !unlocked {
+            return Err(HandlerError {
+                status: StatusCode::CONFLICT,
+                message: "User is not locked".to_string(),
+                ..Default::default()
+            });
+        }
+
+        // The unlock is already stored, so a failure to queue the email is only logged
+        if let Err(err) = AccountEmailHelper::queue(
+            &_state,
+            MailTemplateNames::ACCOUNT_UNLOCKED,
+            &user.name,
+            &user.email,
+            &[],
+        )
+        .await
+        {
+            tracing::error!(user_id, "Failed to queue account unlocked email: {}", err);
+        }
+
+        Ok(Json(true))
+    }
+
+    /// GET /users/{user_id}/status - Status of the user, with the reason and expiry of a
+    /// suspension or ban
+    pub async fn get_user_status(
+        State(_state): State<AppState>,
+        Path(user_id): Path<i32>,
+    ) -> HandlerResult<Json<UserStatusDto>> {
+        let status = _state
+            .usecases
+            .identity_user_status
+            .get_status(user_id)
+            .await
+            .map_err(HandlerError::from)?;
+        Ok(Json(status))
+    }
+
+    /// POST /users/{user_id}/suspension - Suspend or ban a user and sign them out everywhere
+    pub async fn suspend_user(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Path(user_id): Path<i32>,
+        Json(payload): Json<Option<SuspendUserRequest>>,
+    ) -> HandlerResult<Json<UserStatusDto>> {
+        let req = match payload {
+            Some(req) => req,
+            None => {
+                return Err(HandlerError {
+                    status: StatusCode::BAD_REQUEST,
+                    message: "Invalid request payload".to_string(),
+                    ..Default::default()
+                })
+            }
+        };
+
+        req.validate().map_err(|e: ValidationErrors| {
+            let errors = ValidationHelper::new().flatten_errors(e);
+            HandlerError {
+                status: StatusCode::BAD_REQUEST,
+                message: "Validation error".to_string(),
+                field_errors: Some(errors),
+            }
+        })?;
+
+        // Only a root admin may lock out another root admin
+        let target_roles = _state
+            .usecases
+            .user
+            .get_user_roles_by_user_id(user_id)
+            .await
+            .map_err(HandlerError::from)?;
+        if target_roles
+            .iter()
+            .any(|role| role.role_name == ROLE_ROOT_ADMIN)
+            && !current_user
+                .roles
+                .iter()
+                .any(|role| role == ROLE_ROOT_ADMIN)
+        {
+            return Err(HandlerError {
+                status: StatusCode::FORBIDDEN,
+                message: "Only a root admin can suspend a root admin".to_string(),
+                ..Default::default()
+            });
+        }
+
+        let status = _state
+            .usecases
+            .identity_user_status
+            .suspend(
+                user_id,
+                UserSuspensionDto {
+                    reason: req.reason,
+                    expires_on: req.expires_on,
+                    banned: req.banned,
+                    changed_by_id: current_user.id,
+                },
+            )
+            .await
+            .map_err(HandlerError::from)?;
+
+        // Existing access and refresh tokens stop working right away
+        _state
+            .usecases
+            .identity_authenticate
+            .sign_out_everywhere(user_id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok(Json(status))
+    }
+
+    /// DELETE /users/{user_id}/suspension - Lift a suspension or ban
+    pub async fn reinstate_user(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Path(user_id): Path<i32>,
+    ) -> HandlerResult<Json<bool>> {
+        let reinstated = _state
+            .usecases
+            .identity_user_status
+            .reinstate(user_id, current_user.id)
+            .await
+            .map_err(HandlerError::from)?;
+        if !reinstated {
+            return Err(HandlerError {
+                status: StatusCode::CONFLICT,
+                message: "User is not suspended".to_string(),
+                ..Default::default()
+            });
+        }
+
+        Ok(Json(true))
+    }
+
     pub async fn assign_roles(
         State(_state): State<AppState>,
         Extension(current_user): Extension<Arc<CurrentUser>>,
@@ -1149,6 +1436,139 @@ impl UserHandler {
             }
         }
     }
+
+    /// GET /users/{user_id}/permissions/{code} - Whether the user has the permission, and
+    /// through which roles
+    pub async fn explain_permission(
+        State(_state): State<AppState>,
+        Path((user_id, code)): Path<(i32, String)>,
+    ) -> HandlerResult<Json<PermissionExplanationDto>> {
+        let explanation = _state
+            .usecases
+            .identity_user
+            .explain_permission(user_id, &code)
+            .await
+            .map_err(HandlerError::from)?;
+        Ok(Json(explanation))
+    }
+
+    /// POST /users/service-accounts - Create a service account, which cannot sign in and only
+    /// acts through API keys
+    pub async fn create_service_account(
+        State(_state): State<AppState>,
+        Json(payload): Json<Option<ServiceAccountCreationRequest>>,
+    ) -> HandlerResult<(StatusCode, Json<i32>)> {
+        let req = match payload {
+            Some(req) => req,
+            None => {
+                return Err(HandlerError {
+                    status: StatusCode::BAD_REQUEST,
+                    message: "Invalid request payload".to_string(),
+                    ..Default::default()
+                })
+            }
+        };
+
+        req.validate().map_err(|e: ValidationErrors| {
+            let errors = ValidationHelper::new().flatten_errors(e);
+            HandlerError {
+                status: StatusCode::BAD_REQUEST,
+                message: "Validation error".to_string(),
+                field_errors: Some(errors),
+            }
+        })?;
+
+        let user_id = _state
+            .usecases
+            .identity_api_key
+            .create_service_account(ServiceAccountCreationDto {
+                name: req.name,
+                display_name: req.display_name,
+            })
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok((StatusCode::CREATED, Json(user_id)))
+    }
+
+    /// GET /users/{user_id}/api-keys - List a service account's API keys
+    pub async fn get_api_keys(
+        State(_state): State<AppState>,
+        Path(user_id): Path<i32>,
+    ) -> HandlerResult<Json<Vec<ApiKeyDto>>> {
+        _state
+            .usecases
+            .identity_api_key
+            .ensure_service_account(user_id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        let api_keys = _state
+            .usecases
+            .identity_api_key
+            .get_api_keys(user_id)
+            .await
+            .map_err(HandlerError::from)?;
+        Ok(Json(api_keys))
+    }
+
+    /// POST /users/{user_id}/api-keys - Create an API key for a service account, limited to the
+    /// permissions assigned to it; the key is only returned once
+    pub async fn create_api_key(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Path(user_id): Path<i32>,
+        Json(payload): Json<Option<ApiKeyCreationRequest>>,
+    ) -> HandlerResult<(StatusCode, Json<CreatedApiKeyDto>)> {
+        AuthenticationHandler::ensure_not_api_key(&current_user)?;
+        let req = AuthenticationHandler::get_api_key_creation_request(payload)?;
+        _state
+            .usecases
+            .identity_api_key
+            .ensure_service_account(user_id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        let created = _state
+            .usecases
+            .identity_api_key
+            .create_api_key(
+                user_id,
+                ApiKeyCreationDto {
+                    name: req.name,
+                    scopes: req.scopes,
+                    expires_on: req.expires_on,
+                    created_by_id: current_user.id,
+                },
+            )
+            .await
+            .map_err(HandlerError::from)?;
+
+        Ok((StatusCode::CREATED, Json(created)))
+    }
+
+    /// DELETE /users/{user_id}/api-keys/{id} - Revoke one of a service account's API keys
+    pub async fn revoke_api_key(
+        State(_state): State<AppState>,
+        Extension(current_user): Extension<Arc<CurrentUser>>,
+        Path((user_id, id)): Path<(i32, i32)>,
+    ) -> HandlerResult<Json<bool>> {
+        AuthenticationHandler::ensure_not_api_key(&current_user)?;
+        _state
+            .usecases
+            .identity_api_key
+            .ensure_service_account(user_id)
+            .await
+            .map_err(HandlerError::from)?;
+
+        let revoked = _state
+            .usecases
+            .identity_api_key
+            .revoke_api_key(user_id, id)
+            .await
+            .map_err(HandlerError::from)?;
+        Ok(Json(revoked))
+    }
 }
 
 pub struct UserHandler {}
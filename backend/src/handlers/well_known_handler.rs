@@ -0,0 +1,31 @@
+use crate::app_state::AppState;
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use rex_game_identity::{domain::services::JsonWebKey, IdentitySigningKeyUseCaseTrait};
+use serde::Serialize;
+
+/// Short enough that verifiers see a new key before it starts signing
+const JWKS_CACHE_CONTROL: &str = "public, max-age=300";
+
+#[derive(Serialize)]
+pub struct JsonWebKeySet {
+    pub keys: Vec<JsonWebKey>,
+}
+
+pub struct WellKnownHandler;
+
+impl WellKnownHandler {
+    /// Public keys that verify the tokens this server issues
+    pub async fn get_jwks(State(state): State<AppState>) -> Response {
+        let keys = state.usecases.identity_signing_key.get_public_keys();
+        (
+            [(header::CACHE_CONTROL, JWKS_CACHE_CONTROL)],
+            Json(JsonWebKeySet { keys }),
+        )
+            .into_response()
+    }
+}
@@ -0,0 +1,97 @@
+use crate::app_state::AppState;
+use rex_game_mail_templates::application::{EmailOutboxUseCaseTrait, MailTemplateUseCaseTrait};
+use rex_game_shared::{
+    domain::{
+        helpers::email_helper_trait::EmailMessage,
+        transaction_manager_trait::TransactionWrapperTrait,
+    },
+    InfraError,
+};
+
+pub struct AccountEmailHelper {}
+
+impl AccountEmailHelper {
+    /// Fills in a mail template and queues it for the outbox worker. Nothing is queued when
+    /// the template is disabled. `[user_name]`, `[platform_name]` and `[platform_url]` are
+    /// always replaced; `placeholders` adds the rest.
+    pub async fn queue(
+        state: &AppState,
+        template_name: &str,
+        to_name: &str,
+        to_email: &str,
+        placeholders: &[(&str, String)],
+    ) -> Result<(), InfraError> {
+        let message =
+            Self::build_message(state, template_name, to_name, to_email, placeholders).await?;
+        if let Some(message) = message {
+            state.usecases.email_outbox.enqueue(message).await?;
+        }
+        Ok(())
+    }
+
+    /// Same as `queue`, but the email is only sent if `transaction` commits.
+    pub async fn queue_with_transaction(
+        state: &AppState,
+        template_name: &str,
+        to_name: &str,
+        to_email: &str,
+        placeholders: &[(&str, String)],
+        transaction: Box<&dyn TransactionWrapperTrait>,
+    ) -> Result<(), InfraError> {
+        let message =
+            Self::build_message(state, template_name, to_name, to_email, placeholders).await?;
+        if let Some(message) = message {
+            state
+                .usecases
+                .email_outbox
+                .enqueue_with_transaction(message, transaction)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn build_message(
+        state: &AppState,
+        template_name: &str,
+        to_name: &str,
+        to_email: &str,
+        placeholders: &[(&str, String)],
+    ) -> Result<Option<EmailMessage>, InfraError> {
+        let mail_template = state
+            .usecases
+            .mail_template
+            .get_by_name(template_name.to_string())
+            .await?;
+        if !mail_template.is_enabled {
+            return Ok(None);
+        }
+
+        let configuration_helper = state.helpers.configuration.clone();
+        let from_name: String = configuration_helper.get("EMAIL_FROM_NAME");
+        let username: String = configuration_helper.get("SMTP_USERNAME");
+        let platform_name: String = configuration_helper.get("PLATFORM_NAME");
+        let platform_url: String = configuration_helper.get("PLATFORM_URL");
+
+        let mut email_body = mail_template
+            .body
+            .replace("[user_name]", to_name)
+            .replace("[platform_name]", &platform_name)
+            .replace("[platform_url]", &platform_url);
+        for (placeholder, value) in placeholders {
+            email_body = email_body.replace(placeholder, value);
+        }
+        let subject = mail_template
+            .subject
+            .replace("[platform_name]", &platform_name);
+
+        Ok(Some(EmailMessage {
+            to_name: Some(to_name.to_owned()),
+            to_email: to_email.to_owned(),
+            from_name: Some(from_name),
+            from_email: username,
+            subject,
+            text_body: None,
+            html_body: Some(email_body),
+        }))
+    }
+}
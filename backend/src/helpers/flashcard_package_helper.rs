@@ -0,0 +1,734 @@
+use rex_game_games::FlashcardExportDto;
+use rex_game_shared::ApplicationError;
+use rusqlite::{params, Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Write};
+use zip::{write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+/// Largest number of cards accepted in one import.
+pub const MAX_PACKAGE_ROWS: usize = 1000;
+/// Images are read one byte past the 2MB flashcard limit so validation can reject them.
+const MAX_IMAGE_READ_BYTES: u64 = 2 * 1024 * 1024 + 1;
+const ANKI_FIELD_SEPARATOR: char = '\u{1f}';
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlashcardPackageFormat {
+    Csv,
+    Json,
+    Apkg,
+}
+
+impl FlashcardPackageFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "csv" => Some(FlashcardPackageFormat::Csv),
+            "json" => Some(FlashcardPackageFormat::Json),
+            "apkg" | "anki" => Some(FlashcardPackageFormat::Apkg),
+            _ => None,
+        }
+    }
+
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        file_name
+            .rsplit_once('.')
+            .and_then(|(_, extension)| Self::parse(extension))
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            FlashcardPackageFormat::Csv | FlashcardPackageFormat::Json => "zip",
+            FlashcardPackageFormat::Apkg => "apkg",
+        }
+    }
+}
+
+/// One card of a CSV or JSON manifest. `image` is a path inside the images zip.
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+pub struct FlashcardPackageRow {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub sub_description: Option<String>,
+    #[serde(default)]
+    pub image: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub type_ids: Vec<i32>,
+}
+
+pub struct FlashcardPackageImage {
+    pub file_name: String,
+    pub data: Vec<u8>,
+}
+
+/// A manifest row with its image resolved from the package.
+pub struct FlashcardPackageItem {
+    pub row: FlashcardPackageRow,
+    pub image: Option<FlashcardPackageImage>,
+}
+
+pub struct FlashcardPackageHelper {}
+
+impl FlashcardPackageHelper {
+    /// Reads a CSV or JSON manifest and looks its images up in the optional zip.
+    pub fn read_manifest(
+        format: FlashcardPackageFormat,
+        manifest: &[u8],
+        images: Option<&[u8]>,
+    ) -> Result<Vec<FlashcardPackageItem>, ApplicationError> {
+        let rows = match format {
+            FlashcardPackageFormat::Csv => Self::read_csv_rows(manifest)?,
+            FlashcardPackageFormat::Json => {
+                serde_json::from_slice::<Vec<FlashcardPackageRow>>(manifest).map_err(|err| {
+                    ApplicationError::invalid_input(format!("Invalid JSON manifest: {}", err))
+                })?
+            }
+            FlashcardPackageFormat::Apkg => {
+                return Err(ApplicationError::invalid_input(
+                    "Anki packages are uploaded as a package, not a manifest",
+                ))
+            }
+        };
+        Self::ensure_row_limit(rows.len())?;
+
+        let mut archive = match images {
+            Some(images) => Some(ZipArchive::new(Cursor::new(images)).map_err(|err| {
+                ApplicationError::invalid_input(format!("Invalid images zip: {}", err))
+            })?),
+            None => None,
+        };
+        let entry_names = archive
+            .as_ref()
+            .map(|a| a.file_names().map(|n| n.to_string()).collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        let mut items = Vec::with_capacity(rows.len());
+        for row in rows {
+            let image = match (row.image.as_deref(), archive.as_mut()) {
+                (Some(path), Some(archive)) => {
+                    // Exact path first, then the bare file name anywhere in the zip
+                    let entry_name =
+                        entry_names.iter().find(|n| n.as_str() == path).or_else(|| {
+                            entry_names
+                                .iter()
+                                .find(|n| base_name(n) == base_name(path) && !n.ends_with('/'))
+                        });
+                    match entry_name {
+                        Some(entry_name) => Some(FlashcardPackageImage {
+                            file_name: base_name(entry_name).to_string(),
+                            data: read_zip_entry(archive, entry_name, MAX_IMAGE_READ_BYTES)?,
+                        }),
+                        None => None,
+                    }
+                }
+                _ => None,
+            };
+            items.push(FlashcardPackageItem { row, image });
+        }
+
+        Ok(items)
+    }
+
+    /// Reads the notes of an Anki package. The first text field becomes the name, the
+    /// next ones the descriptions, and the first `<img>` of the note its image.
+    pub fn read_apkg(package: &[u8]) -> Result<Vec<FlashcardPackageItem>, ApplicationError> {
+        let mut archive = ZipArchive::new(Cursor::new(package)).map_err(|err| {
+            ApplicationError::invalid_input(format!("Invalid Anki package: {}", err))
+        })?;
+        let entry_names: Vec<String> = archive.file_names().map(|n| n.to_string()).collect();
+        let has_entry = |name: &str| entry_names.iter().any(|n| n == name);
+
+        // Packages made for current Anki only keep a placeholder deck in collection.anki2
+        let collection_name = if has_entry("collection.anki21") {
+            "collection.anki21"
+        } else if has_entry("collection.anki21b") {
+            return Err(ApplicationError::invalid_input(
+                "This Anki package format is not supported, export it with \"Support older Anki versions\" enabled",
+            ));
+        } else if has_entry("collection.anki2") {
+            "collection.anki2"
+        } else {
+            return Err(ApplicationError::invalid_input(
+                "The Anki package has no collection",
+            ));
+        };
+
+        let collection = read_zip_entry(&mut archive, collection_name, u64::MAX)?;
+        let media: HashMap<String, String> = if has_entry("media") {
+            let media = read_zip_entry(&mut archive, "media", u64::MAX)?;
+            serde_json::from_slice(&media).map_err(|_| {
+                ApplicationError::invalid_input("The Anki package media list is not supported")
+            })?
+        } else {
+            HashMap::new()
+        };
+        let media_entries: HashMap<String, String> = media
+            .into_iter()
+            .map(|(entry, name)| (name, entry))
+            .collect();
+
+        let collection_file = tempfile::NamedTempFile::new()
+            .map_err(|err| ApplicationError::invalid_input(err.to_string()))?;
+        std::fs::write(collection_file.path(), &collection)
+            .map_err(|err| ApplicationError::invalid_input(err.to_string()))?;
+        let connection =
+            Connection::open_with_flags(collection_file.path(), OpenFlags::SQLITE_OPEN_READ_ONLY)
+                .map_err(anki_error)?;
+        let notes = {
+            let mut statement = connection
+                .prepare("SELECT flds FROM notes ORDER BY id")
+                .map_err(anki_error)?;
+            let rows = statement
+                .query_map([], |row| row.get::<_, String>(0))
+                .map_err(anki_error)?;
+            rows.collect::<Result<Vec<String>, _>>()
+                .map_err(anki_error)?
+        };
+        Self::ensure_row_limit(notes.len())?;
+
+        let mut items = Vec::with_capacity(notes.len());
+        for note in notes {
+            let fields: Vec<&str> = note.split(ANKI_FIELD_SEPARATOR).collect();
+            let mut texts = fields
+                .iter()
+                .map(|f| strip_html(f))
+                .filter(|t| !t.is_empty());
+            let row = FlashcardPackageRow {
+                name: texts.next().unwrap_or_default(),
+                description: texts.next(),
+                sub_description: texts.next(),
+                image: fields.iter().find_map(|f| first_image_source(f)),
+                type_ids: vec![],
+            };
+
+            let image = match row.image.as_deref().and_then(|i| media_entries.get(i)) {
+                Some(entry) if has_entry(entry) => Some(FlashcardPackageImage {
+                    file_name: row.image.clone().unwrap_or_default(),
+                    data: read_zip_entry(&mut archive, entry, MAX_IMAGE_READ_BYTES)?,
+                }),
+                _ => None,
+            };
+            items.push(FlashcardPackageItem { row, image });
+        }
+
+        Ok(items)
+    }
+
+    /// Packs flashcards as a zip of manifest plus images, or as an Anki package.
+    pub fn write_package(
+        format: FlashcardPackageFormat,
+        deck_name: &str,
+        flashcards: &[FlashcardExportDto],
+    ) -> Result<Vec<u8>, ApplicationError> {
+        match format {
+            FlashcardPackageFormat::Csv | FlashcardPackageFormat::Json => {
+                Self::write_manifest_package(format, flashcards)
+            }
+            FlashcardPackageFormat::Apkg => Self::write_apkg(deck_name, flashcards),
+        }
+    }
+
+    fn ensure_row_limit(count: usize) -> Result<(), ApplicationError> {
+        if count > MAX_PACKAGE_ROWS {
+            return Err(ApplicationError::invalid_input(format!(
+                "A package can contain at most {} flashcards",
+                MAX_PACKAGE_ROWS
+            )));
+        }
+        Ok(())
+    }
+
+    fn read_csv_rows(manifest: &[u8]) -> Result<Vec<FlashcardPackageRow>, ApplicationError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(manifest);
+        let headers = reader
+            .headers()
+            .map_err(|err| {
+                ApplicationError::invalid_input(format!("Invalid CSV manifest: {}", err))
+            })?
+            .clone();
+        let column = |name: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(name));
+        let name_column = column("name").ok_or(ApplicationError::invalid_input(
+            "The CSV manifest needs a name column",
+        ))?;
+        let description_column = column("description");
+        let sub_description_column = column("sub_description");
+        let image_column = column("image");
+        let type_ids_column = column("type_ids");
+
+        let mut rows = Vec::new();
+        for (index, record) in reader.records().enumerate() {
+            let record = record.map_err(|err| {
+                ApplicationError::invalid_input(format!("Invalid CSV row {}: {}", index + 1, err))
+            })?;
+            let value = |column: Option<usize>| {
+                column
+                    .and_then(|c| record.get(c))
+                    .filter(|v| !v.is_empty())
+                    .map(|v| v.to_string())
+            };
+            let type_ids = match value(type_ids_column) {
+                Some(type_ids) => type_ids
+                    .split(';')
+                    .map(|id| id.trim())
+                    .filter(|id| !id.is_empty())
+                    .map(|id| id.parse::<i32>())
+                    .collect::<Result<Vec<i32>, _>>()
+                    .map_err(|err| {
+                        ApplicationError::invalid_input(format!(
+                            "Invalid type_ids in CSV row {}: {}",
+                            index + 1,
+                            err
+                        ))
+                    })?,
+                None => vec![],
+            };
+            rows.push(FlashcardPackageRow {
+                name: value(Some(name_column)).unwrap_or_default(),
+                description: value(description_column),
+                sub_description: value(sub_description_column),
+                image: value(image_column),
+                type_ids,
+            });
+        }
+
+        Ok(rows)
+    }
+
+    fn write_manifest_package(
+        format: FlashcardPackageFormat,
+        flashcards: &[FlashcardExportDto],
+    ) -> Result<Vec<u8>, ApplicationError> {
+        let rows: Vec<FlashcardPackageRow> = flashcards
+            .iter()
+            .map(|f| FlashcardPackageRow {
+                name: f.name.clone(),
+                description: f.description.clone(),
+                sub_description: f.sub_description.clone(),
+                image: Some(format!("images/{}", export_file_name(f))),
+                type_ids: vec![],
+            })
+            .collect();
+
+        let (manifest_name, manifest) = match format {
+            FlashcardPackageFormat::Json => (
+                "flashcards.json",
+                serde_json::to_vec_pretty(&rows).map_err(export_error)?,
+            ),
+            _ => {
+                let mut writer = csv::Writer::from_writer(Vec::new());
+                writer
+                    .write_record([
+                        "name",
+                        "description",
+                        "sub_description",
+                        "image",
+                        "type_ids",
+                    ])
+                    .map_err(export_error)?;
+                for row in rows.iter() {
+                    writer
+                        .write_record([
+                            row.name.as_str(),
+                            row.description.as_deref().unwrap_or_default(),
+                            row.sub_description.as_deref().unwrap_or_default(),
+                            row.image.as_deref().unwrap_or_default(),
+                            "",
+                        ])
+                        .map_err(export_error)?;
+                }
+                ("flashcards.csv", writer.into_inner().map_err(export_error)?)
+            }
+        };
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        zip.start_file(manifest_name, options)
+            .map_err(export_error)?;
+        zip.write_all(&manifest).map_err(export_error)?;
+        for flashcard in flashcards {
+            zip.start_file(format!("images/{}", export_file_name(flashcard)), options)
+                .map_err(export_error)?;
+            zip.write_all(&flashcard.image_data).map_err(export_error)?;
+        }
+
+        Ok(zip.finish().map_err(export_error)?.into_inner())
+    }
+
+    fn write_apkg(
+        deck_name: &str,
+        flashcards: &[FlashcardExportDto],
+    ) -> Result<Vec<u8>, ApplicationError> {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let now_secs = now_ms / 1000;
+        let model_id = now_ms;
+        let deck_id = now_ms + 1;
+
+        let collection_file = tempfile::NamedTempFile::new().map_err(export_error)?;
+        {
+            let connection = Connection::open(collection_file.path()).map_err(export_error)?;
+            connection
+                .execute_batch(ANKI_SCHEMA)
+                .map_err(export_error)?;
+
+            let fields: Vec<Value> = ["Front", "Back", "Extra"]
+                .iter()
+                .enumerate()
+                .map(|(ord, name)| {
+                    json!({
+                        "name": name,
+                        "ord": ord,
+                        "sticky": false,
+                        "rtl": false,
+                        "font": "Arial",
+                        "size": 20,
+                        "media": []
+                    })
+                })
+                .collect();
+            let model = json!({
+                model_id.to_string(): {
+                    "id": model_id,
+                    "name": "Rex Game Flashcard",
+                    "type": 0,
+                    "mod": now_secs,
+                    "usn": -1,
+                    "sortf": 0,
+                    "did": deck_id,
+                    "tmpls": [{
+                        "name": "Card 1",
+                        "ord": 0,
+                        "qfmt": "{{Front}}",
+                        "afmt": "{{FrontSide}}<hr id=answer>{{Back}}<br>{{Extra}}",
+                        "did": null,
+                        "bqfmt": "",
+                        "bafmt": ""
+                    }],
+                    "flds": fields,
+                    "css": ".card { font-family: arial; font-size: 20px; text-align: center; }",
+                    "latexPre": "\\documentclass[12pt]{article}\n\\special{papersize=3in,5in}\n\\usepackage{amssymb,amsmath}\n\\pagestyle{empty}\n\\begin{document}\n",
+                    "latexPost": "\\end{document}",
+                    "tags": [],
+                    "vers": [],
+                    "req": [[0, "any", [0]]]
+                }
+            });
+            let deck = |id: i64, name: &str| {
+                json!({
+                    "id": id,
+                    "name": name,
+                    "mod": now_secs,
+                    "usn": -1,
+                    "desc": "",
+                    "dyn": 0,
+                    "conf": 1,
+                    "collapsed": false,
+                    "browserCollapsed": false,
+                    "newToday": [0, 0],
+                    "revToday": [0, 0],
+                    "lrnToday": [0, 0],
+                    "timeToday": [0, 0],
+                    "extendNew": 0,
+                    "extendRev": 0
+                })
+            };
+            let decks = json!({
+                "1": deck(1, "Default"),
+                deck_id.to_string(): deck(deck_id, deck_name),
+            });
+            let deck_config = json!({
+                "1": {
+                    "id": 1,
+                    "name": "Default",
+                    "mod": 0,
+                    "usn": 0,
+                    "maxTaken": 60,
+                    "autoplay": true,
+                    "timer": 0,
+                    "replayq": true,
+                    "dyn": false,
+                    "new": { "delays": [1, 10], "ints": [1, 4, 7], "initialFactor": 2500, "order": 1, "perDay": 20, "bury": true },
+                    "lapse": { "delays": [10], "mult": 0, "minInt": 1, "leechFails": 8, "leechAction": 0 },
+                    "rev": { "perDay": 200, "ease4": 1.3, "ivlFct": 1, "maxIvl": 36500, "bury": true, "hardFactor": 1.2 }
+                }
+            });
+            let config = json!({
+                "nextPos": flashcards.len() + 1,
+                "estTimes": true,
+                "activeDecks": [deck_id],
+                "sortType": "noteFld",
+                "timeLim": 0,
+                "sortBackwards": false,
+                "addToCur": true,
+                "curDeck": deck_id,
+                "newSpread": 0,
+                "dueCounts": true,
+                "curModel": model_id.to_string(),
+                "collapseTime": 1200
+            });
+            connection
+                .execute(
+                    "INSERT INTO col VALUES (1, ?1, ?2, ?2, 11, 0, 0, 0, ?3, ?4, ?5, ?6, '{}')",
+                    params![
+                        now_secs,
+                        now_ms,
+                        config.to_string(),
+                        model.to_string(),
+                        decks.to_string(),
+                        deck_config.to_string()
+                    ],
+                )
+                .map_err(export_error)?;
+
+            for (position, flashcard) in flashcards.iter().enumerate() {
+                let note_id = now_ms + position as i64;
+                let front = escape_html(&flashcard.name);
+                let back = format!(
+                    "<img src=\"{}\"><br>{}",
+                    escape_html(&export_file_name(flashcard)),
+                    escape_html(flashcard.description.as_deref().unwrap_or_default())
+                );
+                let extra = escape_html(flashcard.sub_description.as_deref().unwrap_or_default());
+                let fields = [front.as_str(), back.as_str(), extra.as_str()]
+                    .join(&ANKI_FIELD_SEPARATOR.to_string());
+                connection
+                    .execute(
+                        "INSERT INTO notes VALUES (?1, ?2, ?3, ?4, -1, '', ?5, ?6, ?7, 0, '')",
+                        params![
+                            note_id,
+                            format!("rex-game-{}", flashcard.id),
+                            model_id,
+                            now_secs,
+                            fields,
+                            flashcard.name,
+                            field_checksum(&flashcard.name)
+                        ],
+                    )
+                    .map_err(export_error)?;
+                connection
+                    .execute(
+                        "INSERT INTO cards VALUES (?1, ?1, ?2, 0, ?3, -1, 0, 0, ?4, 0, 0, 0, 0, 0, 0, 0, 0, '')",
+                        params![note_id, deck_id, now_secs, position as i64 + 1],
+                    )
+                    .map_err(export_error)?;
+            }
+        }
+        let collection = std::fs::read(collection_file.path()).map_err(export_error)?;
+
+        let media: HashMap<String, String> = flashcards
+            .iter()
+            .enumerate()
+            .map(|(index, f)| (index.to_string(), export_file_name(f)))
+            .collect();
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        zip.start_file("collection.anki2", options)
+            .map_err(export_error)?;
+        zip.write_all(&collection).map_err(export_error)?;
+        zip.start_file("media", options).map_err(export_error)?;
+        zip.write_all(
+            serde_json::to_string(&media)
+                .map_err(export_error)?
+                .as_bytes(),
+        )
+        .map_err(export_error)?;
+        for (index, flashcard) in flashcards.iter().enumerate() {
+            zip.start_file(index.to_string(), options)
+                .map_err(export_error)?;
+            zip.write_all(&flashcard.image_data).map_err(export_error)?;
+        }
+
+        Ok(zip.finish().map_err(export_error)?.into_inner())
+    }
+}
+
+const ANKI_SCHEMA: &str = "
+CREATE TABLE col (id integer primary key, crt integer not null, mod integer not null, scm integer not null, ver integer not null, dty integer not null, usn integer not null, ls integer not null, conf text not null, models text not null, decks text not null, dconf text not null, tags text not null);
+CREATE TABLE notes (id integer primary key, guid text not null, mid integer not null, mod integer not null, usn integer not null, tags text not null, flds text not null, sfld integer not null, csum integer not null, flags integer not null, data text not null);
+CREATE TABLE cards (id integer primary key, nid integer not null, did integer not null, ord integer not null, mod integer not null, usn integer not null, type integer not null, queue integer not null, due integer not null, ivl integer not null, factor integer not null, reps integer not null, lapses integer not null, left integer not null, odue integer not null, odid integer not null, flags integer not null, data text not null);
+CREATE TABLE revlog (id integer primary key, cid integer not null, usn integer not null, ease integer not null, ivl integer not null, lastIvl integer not null, factor integer not null, time integer not null, type integer not null);
+CREATE TABLE graves (usn integer not null, oid integer not null, type integer not null);
+CREATE INDEX ix_notes_usn on notes (usn);
+CREATE INDEX ix_cards_usn on cards (usn);
+CREATE INDEX ix_revlog_usn on revlog (usn);
+CREATE INDEX ix_cards_nid on cards (nid);
+CREATE INDEX ix_cards_sched on cards (did, queue, due);
+CREATE INDEX ix_revlog_cid on revlog (cid);
+CREATE INDEX ix_notes_csum on notes (csum);
+";
+
+fn read_zip_entry(
+    archive: &mut ZipArchive<Cursor<&[u8]>>,
+    entry_name: &str,
+    max_bytes: u64,
+) -> Result<Vec<u8>, ApplicationError> {
+    let entry = archive.by_name(entry_name).map_err(|err| {
+        ApplicationError::invalid_input(format!("Unable to read {}: {}", entry_name, err))
+    })?;
+    let mut data = Vec::new();
+    entry
+        .take(max_bytes)
+        .read_to_end(&mut data)
+        .map_err(|err| {
+            ApplicationError::invalid_input(format!("Unable to read {}: {}", entry_name, err))
+        })?;
+    Ok(data)
+}
+
+fn base_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/// Image names in exports are prefixed with the flashcard id, as names repeat across cards.
+fn export_file_name(flashcard: &FlashcardExportDto) -> String {
+    format!("{}_{}", flashcard.id, base_name(&flashcard.file_name))
+}
+
+fn first_image_source(field: &str) -> Option<String> {
+    let lower = field.to_lowercase();
+    let tag_start = lower.find("<img")?;
+    let src_start = lower[tag_start..].find("src=")? + tag_start + 4;
+    let rest = &field[src_start..];
+    let source = match rest.chars().next()? {
+        quote @ ('"' | '\'') => rest[1..].split(quote).next()?,
+        _ => rest.split([' ', '>']).next()?,
+    };
+    Some(decode_html_entities(source)).filter(|s| !s.is_empty())
+}
+
+/// Drops markup from an Anki field and decodes the common entities.
+pub fn strip_html(field: &str) -> String {
+    let mut text = String::with_capacity(field.len());
+    let mut in_tag = false;
+    for c in field.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode_html_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn decode_html_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Anki's duplicate check: the first 8 hex digits of the SHA-1 of the sort field.
+fn field_checksum(field: &str) -> i64 {
+    let digest = Sha1::digest(strip_html(field).as_bytes());
+    u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) as i64
+}
+
+fn anki_error(err: rusqlite::Error) -> ApplicationError {
+    ApplicationError::invalid_input(format!("Unable to read the Anki collection: {}", err))
+}
+
+fn export_error<E: std::fmt::Display>(err: E) -> ApplicationError {
+    ApplicationError::business_rule(format!("Failed to build the export: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exported(id: i32, name: &str) -> FlashcardExportDto {
+        FlashcardExportDto {
+            id,
+            name: name.to_string(),
+            description: Some("A fruit".to_string()),
+            file_name: "apple.png".to_string(),
+            content_type: "image/png".to_string(),
+            image_data: vec![1, 2, 3],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_csv_manifest_reads_optional_columns() {
+        let manifest = b"name,image,type_ids\nApple,images/apple.png,1;2\nPear,,\n";
+        let items =
+            FlashcardPackageHelper::read_manifest(FlashcardPackageFormat::Csv, manifest, None)
+                .unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].row.name, "Apple");
+        assert_eq!(items[0].row.type_ids, vec![1, 2]);
+        assert_eq!(items[1].row.image, None);
+        assert_eq!(items[1].row.description, None);
+    }
+
+    #[test]
+    fn test_exported_package_reimports_with_images() {
+        let package = FlashcardPackageHelper::write_package(
+            FlashcardPackageFormat::Json,
+            "Fruits",
+            &[exported(7, "Apple")],
+        )
+        .unwrap();
+        let mut archive = ZipArchive::new(Cursor::new(package.as_slice())).unwrap();
+        let manifest = read_zip_entry(&mut archive, "flashcards.json", u64::MAX).unwrap();
+
+        let items = FlashcardPackageHelper::read_manifest(
+            FlashcardPackageFormat::Json,
+            &manifest,
+            Some(package.as_slice()),
+        )
+        .unwrap();
+        assert_eq!(items[0].row.name, "Apple");
+        let image = items[0].image.as_ref().unwrap();
+        assert_eq!(image.file_name, "7_apple.png");
+        assert_eq!(image.data, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_anki_package_round_trips() {
+        let package = FlashcardPackageHelper::write_package(
+            FlashcardPackageFormat::Apkg,
+            "Fruits",
+            &[exported(7, "Apple & Co")],
+        )
+        .unwrap();
+
+        let items = FlashcardPackageHelper::read_apkg(&package).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].row.name, "Apple & Co");
+        assert_eq!(items[0].row.description.as_deref(), Some("A fruit"));
+        assert_eq!(items[0].image.as_ref().unwrap().data, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_html_is_stripped_from_fields() {
+        assert_eq!(
+            strip_html("<b>Apple</b>&nbsp;&amp; pear<br>"),
+            "Apple & pear"
+        );
+        assert_eq!(
+            first_image_source("Front <img src=\"apple.png\">"),
+            Some("apple.png".to_string())
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use axum::http::{header, HeaderMap};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+
+/// For URLs carrying the content version: the bytes behind them never change.
+pub const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+/// For unversioned URLs: cache, but revalidate with the ETag before each use.
+pub const REVALIDATE_CACHE_CONTROL: &str = "public, no-cache";
+
+const HTTP_DATE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ByteRange {
+    /// No usable `Range`, send the whole body
+    Full,
+    /// Inclusive byte offsets
+    Partial(u64, u64),
+    Unsatisfiable,
+}
+
+pub struct HttpCacheHelper {}
+
+impl HttpCacheHelper {
+    pub fn entity_tag(checksum: &str) -> String {
+        format!("\"{}\"", checksum)
+    }
+
+    pub fn format_http_date(value: DateTime<Utc>) -> String {
+        value.format(HTTP_DATE_FORMAT).to_string()
+    }
+
+    pub fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+        NaiveDateTime::parse_from_str(value.trim(), HTTP_DATE_FORMAT)
+            .ok()
+            .map(|date| Utc.from_utc_datetime(&date))
+    }
+
+    /// Whether a conditional GET can be answered with 304. `If-None-Match` takes precedence
+    /// over `If-Modified-Since` when both are sent.
+    pub fn is_not_modified(
+        headers: &HeaderMap,
+        entity_tag: Option<&str>,
+        last_modified: DateTime<Utc>,
+    ) -> bool {
+        if let Some(if_none_match) = header_str(headers, header::IF_NONE_MATCH) {
+            return match entity_tag {
+                Some(entity_tag) => if_none_match.split(',').any(|candidate| {
+                    let candidate = candidate.trim();
+                    candidate == "*" || candidate.trim_start_matches("W/") == entity_tag
+                }),
+                None => false,
+            };
+        }
+
+        match header_str(headers, header::IF_MODIFIED_SINCE).and_then(Self::parse_http_date) {
+            Some(since) => last_modified.timestamp() <= since.timestamp(),
+            None => false,
+        }
+    }
+
+    /// Resolves `Range` against a body of `length` bytes. Only single ranges are served;
+    /// anything else, or an `If-Range` that no longer matches, gets the full body.
+    pub fn byte_range(
+        headers: &HeaderMap,
+        entity_tag: Option<&str>,
+        last_modified: DateTime<Utc>,
+        length: u64,
+    ) -> ByteRange {
+        let range = match header_str(headers, header::RANGE) {
+            Some(range) => range,
+            None => return ByteRange::Full,
+        };
+
+        if let Some(if_range) = header_str(headers, header::IF_RANGE) {
+            let if_range = if_range.trim();
+            let matches = if if_range.starts_with('"') {
+                Some(if_range) == entity_tag
+            } else {
+                Self::parse_http_date(if_range)
+                    .map(|date| date.timestamp() == last_modified.timestamp())
+                    .unwrap_or(false)
+            };
+            if !matches {
+                return ByteRange::Full;
+            }
+        }
+
+        Self::parse_range(range, length)
+    }
+
+    pub fn parse_range(range: &str, length: u64) -> ByteRange {
+        let spec = match range.trim().strip_prefix("bytes=") {
+            Some(spec) if !spec.contains(',') => spec.trim(),
+            _ => return ByteRange::Full,
+        };
+        let (start, end) = match spec.split_once('-') {
+            Some(bounds) => bounds,
+            None => return ByteRange::Full,
+        };
+
+        match (start.parse::<u64>(), end.parse::<u64>()) {
+            // bytes=-N, the last N bytes
+            (Err(_), Ok(suffix)) if start.is_empty() => {
+                if suffix == 0 || length == 0 {
+                    ByteRange::Unsatisfiable
+                } else {
+                    ByteRange::Partial(length.saturating_sub(suffix), length - 1)
+                }
+            }
+            (Ok(start), _) if start >= length => ByteRange::Unsatisfiable,
+            (Ok(start), Err(_)) if end.is_empty() => ByteRange::Partial(start, length - 1),
+            (Ok(start), Ok(end)) if start <= end => ByteRange::Partial(start, end.min(length - 1)),
+            _ => ByteRange::Full,
+        }
+    }
+}
+
+fn header_str(headers: &HeaderMap, name: header::HeaderName) -> Option<&str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn modified_on() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 17, 8, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn test_http_dates_round_trip() {
+        let formatted = HttpCacheHelper::format_http_date(modified_on());
+        assert_eq!(formatted, "Sat, 17 Oct 2026 08:30:00 GMT");
+        assert_eq!(
+            HttpCacheHelper::parse_http_date(&formatted),
+            Some(modified_on())
+        );
+    }
+
+    #[test]
+    fn test_if_none_match_wins_over_if_modified_since() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_static("\"old\", W/\"abc\""),
+        );
+        assert!(HttpCacheHelper::is_not_modified(
+            &headers,
+            Some("\"abc\""),
+            modified_on()
+        ));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"old\""));
+        headers.insert(
+            header::IF_MODIFIED_SINCE,
+            HeaderValue::from_static("Sat, 17 Oct 2026 08:30:00 GMT"),
+        );
+        assert!(!HttpCacheHelper::is_not_modified(
+            &headers,
+            Some("\"abc\""),
+            modified_on()
+        ));
+
+        headers.remove(header::IF_NONE_MATCH);
+        assert!(HttpCacheHelper::is_not_modified(
+            &headers,
+            Some("\"abc\""),
+            modified_on()
+        ));
+    }
+
+    #[test]
+    fn test_ranges_are_resolved_against_the_length() {
+        assert_eq!(
+            HttpCacheHelper::parse_range("bytes=0-9", 100),
+            ByteRange::Partial(0, 9)
+        );
+        assert_eq!(
+            HttpCacheHelper::parse_range("bytes=90-", 100),
+            ByteRange::Partial(90, 99)
+        );
+        assert_eq!(
+            HttpCacheHelper::parse_range("bytes=-10", 100),
+            ByteRange::Partial(90, 99)
+        );
+        assert_eq!(
+            HttpCacheHelper::parse_range("bytes=50-500", 100),
+            ByteRange::Partial(50, 99)
+        );
+        assert_eq!(
+            HttpCacheHelper::parse_range("bytes=100-", 100),
+            ByteRange::Unsatisfiable
+        );
+        assert_eq!(
+            HttpCacheHelper::parse_range("bytes=0-1,5-6", 100),
+            ByteRange::Full
+        );
+        assert_eq!(
+            HttpCacheHelper::parse_range("items=0-1", 100),
+            ByteRange::Full
+        );
+    }
+
+    #[test]
+    fn test_stale_if_range_sends_the_full_body() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::RANGE, HeaderValue::from_static("bytes=0-9"));
+        headers.insert(header::IF_RANGE, HeaderValue::from_static("\"old\""));
+        assert_eq!(
+            HttpCacheHelper::byte_range(&headers, Some("\"abc\""), modified_on(), 100),
+            ByteRange::Full
+        );
+
+        headers.insert(header::IF_RANGE, HeaderValue::from_static("\"abc\""));
+        assert_eq!(
+            HttpCacheHelper::byte_range(&headers, Some("\"abc\""), modified_on(), 100),
+            ByteRange::Partial(0, 9)
+        );
+    }
+}
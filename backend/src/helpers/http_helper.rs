@@ -26,4 +26,28 @@ impl HttpHelper {
             Err(_) => Err(ApplicationError::invalid_input("Invalid data")),
         }
     }
+
+    pub fn build_attachment_response(
+        file_data: Vec<u8>,
+        content_type: &str,
+        file_name: &str,
+    ) -> Result<Response<Body>, ApplicationError> {
+        let mut response = Self::build_file_respone(file_data, content_type)?;
+        let safe_name: String = file_name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let disposition = HeaderValue::from_str(&format!("attachment; filename=\"{}\"", safe_name))
+            .map_err(|_| ApplicationError::invalid_input("Invalid file name"))?;
+        response
+            .headers_mut()
+            .insert(header::CONTENT_DISPOSITION, disposition);
+        Ok(response)
+    }
 }
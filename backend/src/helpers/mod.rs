@@ -1 +1,4 @@
+pub mod account_email_helper;
+pub mod flashcard_package_helper;
+pub mod http_cache_helper;
 pub mod http_helper;
@@ -6,6 +6,7 @@ pub mod routings;
 pub mod startup;
 pub mod validators;
 pub mod view_models;
+pub mod workers;
 
 fn main() {
     startup::start()
@@ -1,12 +1,19 @@
 use crate::{
-    app_state::AppState, middlewares::AuthorizedState,
-    view_models::users::current_user::CurrentUser,
+    app_state::AppState,
+    middlewares::AuthorizedState,
+    view_models::{users::current_user::CurrentUser, HandlerError},
+};
+use axum::{
+    body::Body,
+    extract::Request,
+    response::{IntoResponse, Response},
 };
-use axum::{body::Body, extract::Request, response::Response};
 use hyper::StatusCode;
 use rex_game_identity::application::usecases::auth::{
-    IdentityAuthenticateUseCaseTrait, IdentityUserUseCaseTrait,
+    api_key_principal_dto::ApiKeyPrincipalDto, IdentityApiKeyUseCaseTrait,
+    IdentityAuthenticateUseCaseTrait, IdentityUserStatusUseCaseTrait,
 };
+use rex_game_shared::ApplicationError;
 use std::{
     future::Future,
     pin::Pin,
@@ -53,78 +60,163 @@ where
     }
 
     fn call(&mut self, mut req: Request) -> Self::Future {
-        let auth_token = match get_auth_token(&req) {
-            Some(token) => token.to_string(),
+        let credentials = match get_credentials(&req) {
+            Some(credentials) => credentials,
             None => {
                 return Box::pin(async { Ok(unauthorized_response()) });
             }
         };
 
-        // Verify the access token and extract user claims
-        let user_claims = match self
-            .app_state
-            .usecases
-            .identity_authenticate
-            .validate_token(&auth_token)
-        {
-            Ok(claims) => claims,
-            Err(_) => {
-                return Box::pin(async { Ok(unauthorized_response()) });
-            }
-        };
-
-        let user_id = user_claims.sub;
         let app_state = self.app_state.clone();
         let mut inner = self.inner.clone();
-        let email = user_claims.email;
 
         Box::pin(async move {
-            // Fetch current user with roles and permissions
-            let current_user = match app_state
-                .usecases
-                .identity_user
-                .get_logged_in_user(auth_token.as_str())
-                .await
-            {
-                Ok(user) => user,
-                Err(_) => return Ok(unauthorized_response()),
+            let current_user = match authenticate(&app_state, credentials).await {
+                Ok(current_user) => current_user,
+                Err(response) => return Ok(response),
             };
 
-            let email = match email {
-                Some(e) => e,
-                None => return Ok(unauthorized_response()),
-            };
-
-            let role_names: Vec<String> = current_user
-                .roles
-                .into_iter()
-                .map(|role| role.role_name)
-                .collect();
-            let user_permission_codes: Vec<String> = current_user
-                .permissions
-                .into_iter()
-                .map(|perm| perm.permisson_code)
-                .collect();
-
-            let user = Arc::new(CurrentUser {
-                id: user_id,
-                email,
-                name: current_user.name,
-                display_name: current_user.display_name,
-                roles: role_names,
-                permissions: user_permission_codes,
-            });
-
             req.extensions_mut().insert(AuthorizedState::IsInRole);
-            req.extensions_mut().insert(user);
+            req.extensions_mut().insert(current_user);
 
             inner.call(req).await
         })
     }
 }
 
+/// What a request authenticates with, taken out of it before anything is awaited
+pub enum Credentials {
+    /// Already checked by an outer layer
+    Authenticated(Arc<CurrentUser>),
+    ApiKey(String),
+    AccessToken(String),
+}
+
+/// Finds the request's credentials, preferring the user an outer layer already authenticated
+pub fn get_credentials(req: &Request) -> Option<Credentials> {
+    if let Some(current_user) = req.extensions().get::<Arc<CurrentUser>>() {
+        return Some(Credentials::Authenticated(current_user.clone()));
+    }
+
+    if let Some(api_key) = get_api_key(req) {
+        return Some(Credentials::ApiKey(api_key.to_string()));
+    }
+
+    get_auth_token(req).map(|token| Credentials::AccessToken(token.to_string()))
+}
+
+/// Checks the credentials and finds who the request acts for. On failure, returns the
+/// response to send back.
+pub async fn authenticate(
+    app_state: &AppState,
+    credentials: Credentials,
+) -> Result<Arc<CurrentUser>, Response> {
+    match credentials {
+        Credentials::Authenticated(current_user) => Ok(current_user),
+        Credentials::ApiKey(api_key) => {
+            let principal = authenticate_api_key(app_state, &api_key).await?;
+            Ok(Arc::new(CurrentUser::from(principal)))
+        }
+        Credentials::AccessToken(access_token) => {
+            let current_user = authenticate_access_token(app_state, &access_token).await?;
+            Ok(Arc::new(current_user))
+        }
+    }
+}
+
+/// Checks an access token and the status of its user. The roles and permissions are taken
+/// from the token, which is refused once they have changed.
+async fn authenticate_access_token(
+    app_state: &AppState,
+    access_token: &str,
+) -> Result<CurrentUser, Response> {
+    let user_claims = app_state
+        .usecases
+        .identity_authenticate
+        .validate_token(access_token)
+        .map_err(|_| unauthorized_response())?;
+    let email = match user_claims.email {
+        Some(email) => email,
+        None => return Err(unauthorized_response()),
+    };
+
+    // Reject tokens issued before a password reset, status change or forced logout
+    app_state
+        .usecases
+        .identity_authenticate
+        .validate_security_stamp(user_claims.sub, user_claims.security_stamp.as_deref())
+        .await
+        .map_err(|_| unauthorized_response())?;
+
+    // Reject tokens carrying roles or permissions that have since changed; the client
+    // refreshes the token to get the current ones
+    app_state
+        .usecases
+        .identity_authenticate
+        .validate_authorization_version(user_claims.sub, user_claims.authorization_version)
+        .await
+        .map_err(|_| unauthorized_response())?;
+
+    ensure_can_access(app_state, user_claims.sub).await?;
+
+    // Access tokens do not carry the name; handlers that show it load the user
+    Ok(CurrentUser {
+        id: user_claims.sub,
+        email,
+        name: String::new(),
+        display_name: None,
+        roles: user_claims.roles,
+        permissions: user_claims.permissions,
+        api_key_id: None,
+    })
+}
+
+/// Suspended and banned users are told why; anyone else is just turned away
+async fn ensure_can_access(app_state: &AppState, user_id: i32) -> Result<(), Response> {
+    match app_state
+        .usecases
+        .identity_user_status
+        .ensure_can_access(user_id)
+        .await
+    {
+        Ok(()) => Ok(()),
+        Err(err @ ApplicationError::Forbidden(_)) => Err(HandlerError::from(err).into_response()),
+        Err(_) => Err(unauthorized_response()),
+    }
+}
+
+/// Checks an API key and the status of the user it acts for. On failure, returns the
+/// response to send back.
+pub async fn authenticate_api_key(
+    app_state: &AppState,
+    api_key: &str,
+) -> Result<ApiKeyPrincipalDto, Response> {
+    let principal = app_state
+        .usecases
+        .identity_api_key
+        .authenticate(api_key)
+        .await
+        .map_err(|_| unauthorized_response())?;
+    ensure_can_access(app_state, principal.user_id).await?;
+    Ok(principal)
+}
+
+/// Extract an API key from the `X-Api-Key` header, or from the Authorization header
+/// (ApiKey scheme)
+pub fn get_api_key(req: &Request) -> Option<&str> {
+    req.headers()
+        .get("X-Api-Key")
+        .and_then(|header| header.to_str().ok())
+        .or_else(|| {
+            req.headers()
+                .get("Authorization")
+                .and_then(|header| header.to_str().ok())
+                .and_then(|header| header.strip_prefix("ApiKey "))
+        })
+}
+
 /// Extract token from Authorization header (Bearer scheme)
-fn get_auth_token(req: &Request) -> Option<&str> {
+pub fn get_auth_token(req: &Request) -> Option<&str> {
     req.headers()
         .get("Authorization")
         .and_then(|header| header.to_str().ok())
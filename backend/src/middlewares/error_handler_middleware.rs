@@ -3,6 +3,7 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use rex_game_shared::ApplicationError;
 use serde::Serialize;
 use std::collections::HashMap;
 
@@ -158,7 +159,9 @@ pub fn transform_db_error(error: &str) -> ErrorResponse {
 pub fn transform_app_error(error_type: &str, message: &str) -> ErrorResponse {
     match error_type {
         "NotFound" => ErrorResponse::not_found(),
-        "InvalidInput" => ErrorResponse::bad_request(message.to_string()),
+        "InvalidInput" | "ValidationFailed" | "BusinessRuleViolated" => {
+            ErrorResponse::bad_request(message.to_string())
+        }
         "InvalidCredentials" => ErrorResponse::new(
             StatusCode::UNAUTHORIZED,
             "Invalid Credentials".to_string(),
@@ -166,8 +169,29 @@ pub fn transform_app_error(error_type: &str, message: &str) -> ErrorResponse {
         ),
         "InvalidToken" => ErrorResponse::unauthorized(),
         "Unauthorized" => ErrorResponse::unauthorized(),
-        "Forbidden" => ErrorResponse::forbidden(),
-        "Conflict" => ErrorResponse::conflict(message.to_string()),
+        // The reason is kept, e.g. why an account cannot sign in
+        "Forbidden" => ErrorResponse::new(
+            StatusCode::FORBIDDEN,
+            "Forbidden".to_string(),
+            message.to_string(),
+        ),
+        "Conflict" | "DuplicateEntry" | "AlreadyExists" => {
+            ErrorResponse::conflict(message.to_string())
+        }
+        "InvalidState" => ErrorResponse::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Unprocessable Entity".to_string(),
+            message.to_string(),
+        ),
+        "TooManyAttempts" => ErrorResponse::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too Many Requests".to_string(),
+            message.to_string(),
+        ),
+        "Infrastructure" => {
+            eprintln!("[Infrastructure Error] {}", message);
+            ErrorResponse::internal_server_error()
+        }
         _ => {
             // Log unknown error types
             eprintln!("[Unknown App Error] type={}, message={}", error_type, message);
@@ -176,6 +200,27 @@ pub fn transform_app_error(error_type: &str, message: &str) -> ErrorResponse {
     }
 }
 
+/// Transform an application error, see `transform_app_error`
+pub fn transform_application_error(error: &ApplicationError) -> ErrorResponse {
+    let error_type = match error {
+        ApplicationError::ValidationFailed(_) => "ValidationFailed",
+        ApplicationError::BusinessRuleViolated(_) => "BusinessRuleViolated",
+        ApplicationError::InvalidInput(_) => "InvalidInput",
+        ApplicationError::EntityNotFound { .. } => "NotFound",
+        ApplicationError::DuplicateEntry(_) => "DuplicateEntry",
+        ApplicationError::AlreadyExists(_) => "AlreadyExists",
+        ApplicationError::Unauthorized(_) => "Unauthorized",
+        ApplicationError::Forbidden(_) => "Forbidden",
+        ApplicationError::InvalidCredentials => "InvalidCredentials",
+        ApplicationError::InvalidToken(_) => "InvalidToken",
+        ApplicationError::TooManyAttempts { .. } => "TooManyAttempts",
+        ApplicationError::InvalidState(_) => "InvalidState",
+        ApplicationError::Conflict(_) => "Conflict",
+        ApplicationError::Infrastructure(_) => "Infrastructure",
+    };
+    transform_app_error(error_type, &error.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +243,20 @@ mod tests {
         let error = transform_db_error("no rows returned by SELECT");
         assert_eq!(error.status, 404);
     }
+
+    #[test]
+    fn test_transform_application_error_conflict() {
+        let error = transform_application_error(&ApplicationError::conflict("Already completed"));
+        assert_eq!(error.status, 409);
+        assert_eq!(error.message, "Conflict: Already completed");
+    }
+
+    #[test]
+    fn test_transform_application_error_hides_infrastructure_details() {
+        let error = transform_application_error(&ApplicationError::Infrastructure(
+            rex_game_shared::InfraError::database("relation does not exist"),
+        ));
+        assert_eq!(error.status, 500);
+        assert!(!error.message.contains("relation"));
+    }
 }
@@ -1,24 +1,27 @@
-use std::sync::Arc;
+use std::{convert::Infallible, sync::Arc};
 
 use axum::{
+    extract::DefaultBodyLimit,
     middleware,
     routing::{delete, get, patch, post, put},
     Router,
 };
 
-use rex_game_identity::roles::ROLE_ROOT_ADMIN;
+use rex_game_identity::{domain::rules::OwnershipPolicy, roles::ROLE_ROOT_ADMIN};
 use rex_game_shared::domain::enums::permission_codes::PermissionCodes;
 use tower::ServiceBuilder;
 
 use crate::{
     app_state::AppState,
     handlers::{
-        authentication_handler::AuthenticationHandler, flashcard_handler::FlashcardHandler,
+        authentication_handler::AuthenticationHandler,
+        email_outbox_handler::EmailOutboxHandler, flashcard_handler::FlashcardHandler,
         flashcard_type_handler::FlashcardTypeHandler, game_admin_handler::GameAdminHandler,
         health_handler::HealthHandler,
         mail_template_handler::MailTemplateHandler, permission_handler::PermissionHandler,
         role_handler::RoleHandler, scoring_handler::ScoringHandler,
         setup_handler::SetupHandler, user_handler::UserHandler,
+        well_known_handler::WellKnownHandler,
     },
     middlewares::{
         authenticate_middleware::AuthenticateLayer,
@@ -26,6 +29,9 @@ use crate::{
     },
 };
 
+/// Upload limit for flashcard packages, which carry every card's image.
+const MAX_IMPORT_BODY_BYTES: usize = 200 * 1024 * 1024;
+
 pub struct AppRouting {
     pub app_state: Arc<AppState>,
 }
@@ -34,11 +40,63 @@ impl AppRouting {
     pub fn build_authenticated_routes(&self, router: Router<AppState>) -> Router<AppState> {
         router
             .route("/auth/logout", delete(AuthenticationHandler::logout))
+            .route("/auth/sessions", get(AuthenticationHandler::get_sessions))
+            .route(
+                "/auth/sessions/{session_id}",
+                delete(AuthenticationHandler::revoke_session),
+            )
+            .route(
+                "/auth/mfa",
+                get(AuthenticationHandler::get_mfa_status)
+                    .post(AuthenticationHandler::begin_mfa_enrolment)
+                    .delete(AuthenticationHandler::disable_mfa),
+            )
+            .route(
+                "/auth/mfa/confirmation",
+                post(AuthenticationHandler::confirm_mfa_enrolment),
+            )
+            .route(
+                "/auth/mfa/recovery-codes",
+                post(AuthenticationHandler::regenerate_recovery_codes),
+            )
+            .route(
+                "/auth/external-logins",
+                get(AuthenticationHandler::get_external_logins),
+            )
+            .route(
+                "/auth/external-logins/{provider}",
+                delete(AuthenticationHandler::delete_external_login),
+            )
+            .route(
+                "/auth/external/{provider}/link",
+                post(AuthenticationHandler::begin_external_login_link),
+            )
+            .route(
+                "/auth/external/{provider}/link/callback",
+                post(AuthenticationHandler::complete_external_login_link),
+            )
+            .route(
+                "/auth/api-keys",
+                get(AuthenticationHandler::get_api_keys)
+                    .post(AuthenticationHandler::create_api_key),
+            )
+            .route(
+                "/auth/api-keys/{id}",
+                delete(AuthenticationHandler::revoke_api_key),
+            )
             .route("/users/me", get(UserHandler::get_current_user))
             .route("/users", get(UserHandler::get_users))
             .route("/users/{id}", patch(UserHandler::update_user))
             // Scoring routes (authenticated)
             .route("/games/sessions", post(ScoringHandler::start_game_session))
+            .route(
+                "/games/sessions/{session_id}/answers",
+                post(ScoringHandler::submit_game_answer),
+            )
+            .route(
+                "/games/sessions/{session_id}/abandon",
+                post(ScoringHandler::abandon_game_session),
+            )
             .route(
                 "/games/sessions/complete",
                 post(ScoringHandler::complete_game_session),
@@ -51,11 +109,25 @@ impl AppRouting {
                 "/games/progress",
                 delete(ScoringHandler::reset_game_progress),
             )
+            .route("/reviews/due", get(ScoringHandler::get_due_cards))
             .route("/users/me/stats", get(ScoringHandler::get_my_stats))
             .route(
                 "/users/me/achievements",
                 get(ScoringHandler::get_my_achievements),
             )
+            .route("/users/me/streak", get(ScoringHandler::get_my_streak))
+            .route(
+                "/users/me/streak/settings",
+                put(ScoringHandler::update_my_streak_settings),
+            )
+            .route(
+                "/users/me/streak/freezes",
+                post(ScoringHandler::purchase_streak_freeze),
+            )
+            .route(
+                "/leaderboard/me",
+                get(ScoringHandler::get_my_leaderboard_position),
+            )
             .layer(ServiceBuilder::new().layer(AuthenticateLayer {
                 app_state: self.app_state.clone(),
             }))
@@ -73,6 +145,18 @@ impl AppRouting {
                 "/auth/refresh",
                 post(AuthenticationHandler::refresh_access_token),
             )
+            .route(
+                "/auth/mfa/enrolment",
+                post(AuthenticationHandler::begin_mfa_enrolment_for_login),
+            )
+            .route(
+                "/auth/external/{provider}/authorization",
+                post(AuthenticationHandler::begin_external_login),
+            )
+            .route(
+                "/auth/external/{provider}/callback",
+                post(AuthenticationHandler::complete_external_login),
+            )
             .route("/users", post(UserHandler::create_user))
             .route("/users/confirmations", post(UserHandler::confirm_user))
             .route("/setup", post(SetupHandler::setup))
@@ -81,8 +165,18 @@ impl AppRouting {
                 async move { limiter.middleware(req, next).await }
             }));
 
-        // Password recovery with very strict rate limiting (3 req/min)
+        // Password recovery, sign-in links and second-factor codes with very strict rate
+        // limiting (3 req/min)
         let password_routes = Router::new()
+            .route("/auth/mfa/verify", post(AuthenticationHandler::verify_mfa))
+            .route(
+                "/auth/magic-link",
+                post(AuthenticationHandler::request_magic_link),
+            )
+            .route(
+                "/auth/magic-link/login",
+                post(AuthenticationHandler::magic_link_login),
+            )
             .route("/users/password", post(UserHandler::forgot_password))
             .route("/users/password", patch(UserHandler::reset_password))
             .route_layer(middleware::from_fn(move |req, next| {
@@ -111,6 +205,10 @@ impl AppRouting {
             )
             .route("/users/{id}", get(UserHandler::get_user_by_id))
             .route("/setup/status", get(SetupHandler::get_status))
+            .route(
+                "/auth/external/providers",
+                get(AuthenticationHandler::get_external_login_providers),
+            )
             // Public scoring routes
             .route("/game-types", get(ScoringHandler::get_game_types))
             .route("/leaderboard", get(ScoringHandler::get_leaderboard))
@@ -133,6 +231,18 @@ impl AppRouting {
             .merge(general_routes)
     }
 
+    /// Routes served from the site root rather than under `/api`
+    pub fn build_well_known_routes(&self, router: Router<AppState>) -> Router<AppState> {
+        let api_limiter = self.app_state.rate_limiters.api.clone();
+
+        router
+            .route("/.well-known/jwks.json", get(WellKnownHandler::get_jwks))
+            .route_layer(middleware::from_fn(move |req, next| {
+                let limiter = api_limiter.clone();
+                async move { limiter.middleware(req, next).await }
+            }))
+    }
+
     pub fn build_admin_routes(&self, router: Router<AppState>) -> Router<AppState> {
         router
             .route(
@@ -191,6 +301,64 @@ impl AppRouting {
                     permissions: vec![PermissionCodes::UserDelete.as_str().to_string()],
                 }),
             )
+            .route(
+                "/users/{user_id}/sessions",
+                delete(UserHandler::sign_out_user).layer(AuthorizeByPermissionLayer {
+                    app_state: self.app_state.clone(),
+                    permissions: vec![PermissionCodes::UserUpdate.as_str().to_string()],
+                }),
+            )
+            .route(
+                "/users/{user_id}/unlock",
+                post(UserHandler::unlock_user).layer(AuthorizeByPermissionLayer {
+                    app_state: self.app_state.clone(),
+                    permissions: vec![PermissionCodes::UserUpdate.as_str().to_string()],
+                }),
+            )
+            .route(
+                "/users/{user_id}/status",
+                get(UserHandler::get_user_status).layer(AuthorizeByPermissionLayer {
+                    app_state: self.app_state.clone(),
+                    permissions: vec![PermissionCodes::UserRead.as_str().to_string()],
+                }),
+            )
+            .route(
+                "/users/{user_id}/suspension",
+                post(UserHandler::suspend_user)
+                    .delete(UserHandler::reinstate_user)
+                    .layer(AuthorizeByPermissionLayer {
+                        app_state: self.app_state.clone(),
+                        permissions: vec![PermissionCodes::UserUpdate.as_str().to_string()],
+                    }),
+            )
+            .route(
+                "/users/service-accounts",
+                post(UserHandler::create_service_account).layer(AuthorizeByPermissionLayer {
+                    app_state: self.app_state.clone(),
+                    permissions: vec![PermissionCodes::UserCreate.as_str().to_string()],
+                }),
+            )
+            .route(
+                "/users/{user_id}/api-keys",
+                get(UserHandler::get_api_keys).layer(AuthorizeByPermissionLayer {
+                    app_state: self.app_state.clone(),
+                    permissions: vec![PermissionCodes::UserRead.as_str().to_string()],
+                }),
+            )
+            .route(
+                "/users/{user_id}/api-keys",
+                post(UserHandler::create_api_key).layer(AuthorizeByPermissionLayer {
+                    app_state: self.app_state.clone(),
+                    permissions: vec![PermissionCodes::UserUpdate.as_str().to_string()],
+                }),
+            )
+            .route(
+                "/users/{user_id}/api-keys/{id}",
+                delete(UserHandler::revoke_api_key).layer(AuthorizeByPermissionLayer {
+                    app_state: self.app_state.clone(),
+                    permissions: vec![PermissionCodes::UserUpdate.as_str().to_string()],
+                }),
+            )
             .route(
                 "/users/{user_id}/roles",
                 get(UserHandler::get_roles).layer(AuthorizeByPermissionLayer {
@@ -254,6 +422,13 @@ impl AppRouting {
                     permissions: vec![PermissionCodes::UserPermissionRead.as_str().to_string()],
                 }),
             )
+            .route(
+                "/users/{user_id}/permissions/{code}",
+                get(UserHandler::explain_permission).layer(AuthorizeByPermissionLayer {
+                    app_state: self.app_state.clone(),
+                    permissions: vec![PermissionCodes::UserPermissionRead.as_str().to_string()],
+                }),
+            )
             .route(
                 "/flashcards",
                 post(FlashcardHandler::create_flashcard).layer(AuthorizeByPermissionLayer {
@@ -261,25 +436,41 @@ impl AppRouting {
                     permissions: vec![PermissionCodes::FlashcardCreate.as_str().to_string()],
                 }),
             )
+            .route(
+                "/flashcards/import",
+                post(FlashcardHandler::import_flashcards)
+                    .layer::<_, Infallible>(DefaultBodyLimit::max(MAX_IMPORT_BODY_BYTES))
+                    .layer(AuthorizeByPermissionLayer {
+                        app_state: self.app_state.clone(),
+                        permissions: vec![PermissionCodes::FlashcardCreate.as_str().to_string()],
+                    }),
+            )
+            .route(
+                "/flashcard-types/{id}/export",
+                get(FlashcardHandler::export_flashcards).layer(AuthorizeByPermissionLayer {
+                    app_state: self.app_state.clone(),
+                    permissions: vec![PermissionCodes::FlashcardRead.as_str().to_string()],
+                }),
+            )
             .route(
                 "/flashcards/{id}",
                 patch(FlashcardHandler::update_flashcard).layer(AuthorizeByPermissionLayer {
                     app_state: self.app_state.clone(),
-                    permissions: vec![PermissionCodes::FlashcardUpdate.as_str().to_string()],
+                    permissions: OwnershipPolicy::FLASHCARD_UPDATE.codes(),
                 }),
             )
             .route(
                 "/flashcards/{id}",
                 delete(FlashcardHandler::delete_flashcard).layer(AuthorizeByPermissionLayer {
                     app_state: self.app_state.clone(),
-                    permissions: vec![PermissionCodes::FlashcardDelete.as_str().to_string()],
+                    permissions: OwnershipPolicy::FLASHCARD_DELETE.codes(),
                 }),
             )
             .route(
                 "/flashcards/{id}/toggle-active",
                 put(FlashcardHandler::toggle_flashcard_active).layer(AuthorizeByPermissionLayer {
                     app_state: self.app_state.clone(),
-                    permissions: vec![PermissionCodes::FlashcardUpdate.as_str().to_string()],
+                    permissions: OwnershipPolicy::FLASHCARD_UPDATE.codes(),
                 }),
             )
             .route(
@@ -298,9 +489,7 @@ impl AppRouting {
                 patch(FlashcardTypeHandler::update_flashcard_type).layer(
                     AuthorizeByPermissionLayer {
                         app_state: self.app_state.clone(),
-                        permissions: vec![PermissionCodes::FlashcardTypeUpdate
-                            .as_str()
-                            .to_string()],
+                        permissions: OwnershipPolicy::FLASHCARD_TYPE_UPDATE.codes(),
                     },
                 ),
             )
@@ -309,9 +498,7 @@ impl AppRouting {
                 delete(FlashcardTypeHandler::delete_flashcard_type).layer(
                     AuthorizeByPermissionLayer {
                         app_state: self.app_state.clone(),
-                        permissions: vec![PermissionCodes::FlashcardTypeDelete
-                            .as_str()
-                            .to_string()],
+                        permissions: OwnershipPolicy::FLASHCARD_TYPE_DELETE.codes(),
                     },
                 ),
             )
@@ -320,9 +507,7 @@ impl AppRouting {
                 put(FlashcardTypeHandler::toggle_flashcard_type_active).layer(
                     AuthorizeByPermissionLayer {
                         app_state: self.app_state.clone(),
-                        permissions: vec![PermissionCodes::FlashcardTypeUpdate
-                            .as_str()
-                            .to_string()],
+                        permissions: OwnershipPolicy::FLASHCARD_TYPE_UPDATE.codes(),
                     },
                 ),
             )
@@ -388,6 +573,28 @@ impl AppRouting {
                     },
                 ),
             )
+            // Email outbox
+            .route(
+                "/email-outbox",
+                get(EmailOutboxHandler::get_emails).layer(AuthorizeByPermissionLayer {
+                    app_state: self.app_state.clone(),
+                    permissions: vec![PermissionCodes::MailTemplateRead.as_str().to_string()],
+                }),
+            )
+            .route(
+                "/email-outbox/{id}",
+                get(EmailOutboxHandler::get_email_by_id).layer(AuthorizeByPermissionLayer {
+                    app_state: self.app_state.clone(),
+                    permissions: vec![PermissionCodes::MailTemplateRead.as_str().to_string()],
+                }),
+            )
+            .route(
+                "/email-outbox/{id}/retry",
+                post(EmailOutboxHandler::retry_email).layer(AuthorizeByPermissionLayer {
+                    app_state: self.app_state.clone(),
+                    permissions: vec![PermissionCodes::MailTemplateUpdate.as_str().to_string()],
+                }),
+            )
             // Game Type Admin
             .route(
                 "/admin/game-types",
@@ -498,6 +705,33 @@ impl AppRouting {
                     },
                 ),
             )
+            .route(
+                "/admin/achievement-backfills",
+                get(GameAdminHandler::get_achievement_backfills).layer(
+                    AuthorizeByPermissionLayer {
+                        app_state: self.app_state.clone(),
+                        permissions: vec![PermissionCodes::AchievementRead.as_str().to_string()],
+                    },
+                ),
+            )
+            .route(
+                "/admin/achievement-backfills/{id}",
+                get(GameAdminHandler::get_achievement_backfill_by_id).layer(
+                    AuthorizeByPermissionLayer {
+                        app_state: self.app_state.clone(),
+                        permissions: vec![PermissionCodes::AchievementRead.as_str().to_string()],
+                    },
+                ),
+            )
+            .route(
+                "/admin/achievement-backfills",
+                post(GameAdminHandler::create_achievement_backfill).layer(
+                    AuthorizeByPermissionLayer {
+                        app_state: self.app_state.clone(),
+                        permissions: vec![PermissionCodes::AchievementUpdate.as_str().to_string()],
+                    },
+                ),
+            )
             // Game Session Admin
             .route(
                 "/admin/game-sessions",
@@ -528,6 +762,23 @@ impl AppRouting {
                     permissions: vec![PermissionCodes::UserStatsUpdate.as_str().to_string()],
                 }),
             )
+            // Progression Admin
+            .route(
+                "/admin/progression",
+                get(GameAdminHandler::get_progression_settings).layer(AuthorizeByPermissionLayer {
+                    app_state: self.app_state.clone(),
+                    permissions: vec![PermissionCodes::UserStatsRead.as_str().to_string()],
+                }),
+            )
+            .route(
+                "/admin/progression",
+                put(GameAdminHandler::update_progression_settings).layer(
+                    AuthorizeByPermissionLayer {
+                        app_state: self.app_state.clone(),
+                        permissions: vec![PermissionCodes::UserStatsUpdate.as_str().to_string()],
+                    },
+                ),
+            )
             .layer(ServiceBuilder::new().layer(AuthorizeByRoleLayer {
                 app_state: self.app_state.clone(),
                 roles: vec![ROLE_ROOT_ADMIN.to_string()],
@@ -3,34 +3,46 @@ use crate::middlewares::rate_limit_middleware::{
     api_rate_limiter, auth_rate_limiter, strict_rate_limiter,
 };
 use crate::routings::app_routing::AppRouting;
+use crate::workers::achievement_backfill_worker::AchievementBackfillWorker;
+use crate::workers::email_outbox_worker::EmailOutboxWorker;
+use crate::workers::signing_key_worker::SigningKeyWorker;
+use crate::workers::streak_reminder_worker::StreakReminderWorker;
 use app_state::{AppState, Helpers, RateLimiters, UseCases};
 use axum::http::request::Parts;
 use axum::http::HeaderValue;
 use axum::Router;
+use chrono::FixedOffset;
 use hyper::{header, Method};
 // New modular imports
 use rex_game_games::{
     FlashcardFileRepository, FlashcardRepository, FlashcardTypeRelationRepository,
     FlashcardTypeRepository, ScoringRepository, ScoringRepositoryTrait, ScoringUseCase,
 };
+use rex_game_games::scoring::domain::rules::StreakPolicy;
 use rex_game_games::{FlashcardTypeUseCase, FlashcardUseCase};
+use rex_game_identity::domain::rules::{LoginThrottlePolicy, SigningKeyRotationPolicy};
 use rex_game_identity::{
-    IdentityAuthenticateUseCase, IdentityAuthorizeUseCase, IdentityUserTokenUseCase,
-    IdentityUserUseCase, PermissionUseCase, RoleUseCase, UserUseCase,
+    IdentityApiKeyUseCase, IdentityAuthenticateDependencies, IdentityAuthenticateUseCase,
+    IdentityAuthorizeUseCase, IdentityExternalLoginUseCase, IdentityLoginThrottleUseCase,
+    IdentityMfaUseCase, IdentitySigningKeyUseCase, IdentitySigningKeyUseCaseTrait,
+    IdentityUserStatusUseCase, IdentityUserTokenUseCase, IdentityUserUseCase, PermissionUseCase,
+    RoleUseCase, UserStampsCache, UserUseCase,
 };
 use rex_game_identity::{
-    IdentityPasswordHasher, IdentityTokenHelper, PermissionRepository, RolePermissionRepository,
-    RoleRepository, UserPermissionRepository, UserRepository, UserRoleRepository,
-    UserTokenRepository,
+    IdentityOidcClient, IdentityPasswordHasher, IdentitySigningKeyring, IdentityTokenHelper,
+    IdentityTotpHelper, OidcProviderOptions, PermissionRepository, RolePermissionRepository,
+    RoleRepository, SigningKeyRepository, UserApiKeyRepository, UserExternalLoginRepository,
+    UserLoginThrottleRepository, UserMfaRepository, UserPermissionRepository, UserRepository,
+    UserRoleRepository, UserStatusRepository, UserTokenRepository,
 };
-use rex_game_mail_templates::application::MailTemplateUseCase;
-use rex_game_mail_templates::MailTemplateRepository;
+use rex_game_mail_templates::application::{EmailOutboxUseCase, MailTemplateUseCase};
+use rex_game_mail_templates::{EmailOutboxRepository, MailTemplateRepository};
 use rex_game_shared::infrastructure::database::SeaOrmConnection;
 use rex_game_shared::infrastructure::{
     database::transaction_manager::TransactionManager,
     helpers::{
-        configuration_helper::ConfigurationHelper, datetime_helper::DateTimeHelper,
-        email_helper::EmailHelper, html_helper,
+        blob_store_provider::BlobStoreFactory, configuration_helper::ConfigurationHelper,
+        datetime_helper::DateTimeHelper, email_helper::EmailHelper, html_helper,
     },
 };
 use std::sync::Arc;
@@ -73,7 +85,9 @@ pub async fn start() {
     };
 
     let flashcard_repository = FlashcardRepository::new(Arc::clone(&db_connection.pool));
-    let flashcard_file_repository = FlashcardFileRepository::new(Arc::clone(&db_connection.pool));
+    let blob_store = BlobStoreFactory::create(&configuration_helper);
+    let flashcard_file_repository =
+        FlashcardFileRepository::new(Arc::clone(&db_connection.pool), blob_store);
     let flashcard_type_relation_repository =
         FlashcardTypeRelationRepository::new(Arc::clone(&db_connection.pool));
     let user_repository = UserRepository::new(Arc::clone(&db_connection.pool));
@@ -83,59 +97,198 @@ pub async fn start() {
     let user_permission_repository = UserPermissionRepository::new(Arc::clone(&db_connection.pool));
     let role_permission_repository = RolePermissionRepository::new(Arc::clone(&db_connection.pool));
     let identity_password_hasher = IdentityPasswordHasher::new();
-    let identity_token_helper = IdentityTokenHelper::new(configuration_helper.clone());
+    // Tokens are signed with rotating keys kept in the database and published as a JWK set
+    let identity_signing_keyring = IdentitySigningKeyring::new();
+    let signing_key_rotation_defaults = SigningKeyRotationPolicy::default();
+    let signing_key_rotation_policy = SigningKeyRotationPolicy {
+        rotation_secs: configuration_helper
+            .get_optional("JWT_KEY_ROTATION_DAYS")
+            .parse::<i64>()
+            .map(|days| days * 24 * 60 * 60)
+            .unwrap_or(signing_key_rotation_defaults.rotation_secs),
+        propagation_secs: signing_key_rotation_defaults.propagation_secs,
+        token_lifetime_secs: ["JWT_EXPIRATION", "JWT_REFRESH_EXPIRATION"]
+            .iter()
+            .filter_map(|key| configuration_helper.get_optional(key).parse::<i64>().ok())
+            .max()
+            .map(|milliseconds| milliseconds / 1000)
+            .unwrap_or(signing_key_rotation_defaults.token_lifetime_secs),
+    };
+    let signing_algorithm = configuration_helper.get_optional("JWT_SIGNING_ALGORITHM");
+    let signing_algorithm = if signing_algorithm.is_empty() {
+        "EdDSA".to_string()
+    } else {
+        signing_algorithm
+    };
+    let identity_signing_key_usecase = IdentitySigningKeyUseCase::new(
+        SigningKeyRepository::new(Arc::clone(&db_connection.pool)),
+        identity_signing_keyring.clone(),
+        signing_key_rotation_policy,
+        &signing_algorithm,
+    );
+    if let Err(err) = identity_signing_key_usecase.refresh_keys().await {
+        tracing::error!(error = %err, "Failed to load the token signing keys");
+        return;
+    }
+    let identity_token_helper = IdentityTokenHelper::new(
+        configuration_helper.clone(),
+        identity_signing_keyring,
+        configuration_helper
+            .get_optional("JWT_ACCEPT_LEGACY_TOKENS")
+            .parse()
+            .unwrap_or(true),
+    );
+    let transaction_manager = TransactionManager::new(Arc::clone(&db_connection.pool));
 
     let flashcard_usecase = FlashcardUseCase::new(
         flashcard_repository,
         flashcard_file_repository,
         flashcard_type_relation_repository,
+        transaction_manager.clone(),
     );
 
     let flashcard_type_repository = FlashcardTypeRepository::new(Arc::clone(&db_connection.pool));
     let flashcard_type_usecase = FlashcardTypeUseCase::new(flashcard_type_repository);
+    // Stamps and statuses changed through one use case are evicted for the other
+    let user_stamps_cache = UserStampsCache::new();
     let user_usecase = UserUseCase::new(
         user_repository,
         role_repository.clone(),
         user_role_repository.clone(),
         user_permission_repository.clone(),
         identity_password_hasher.clone(),
+        user_stamps_cache.clone(),
     );
     let role_usecase = RoleUseCase::new(
         role_repository,
         role_permission_repository.clone(),
         user_role_repository.clone(),
+        transaction_manager.clone(),
     );
-    let permission_usecase = PermissionUseCase::new(permission_repository);
+    let permission_usecase = PermissionUseCase::new(permission_repository.clone());
     let user_token_repository = UserTokenRepository::new(Arc::clone(&db_connection.pool));
+    let user_mfa_repository = UserMfaRepository::new(Arc::clone(&db_connection.pool));
+    let mfa_issuer = configuration_helper.get_optional("MFA_ISSUER");
+    let mfa_issuer = if mfa_issuer.is_empty() {
+        "Rex Game".to_string()
+    } else {
+        mfa_issuer
+    };
+    let identity_mfa_usecase = IdentityMfaUseCase::new(
+        user_mfa_repository,
+        IdentityTotpHelper::new(mfa_issuer),
+        identity_token_helper.clone(),
+    );
+    // Failed logins are tracked per account, on top of the per-IP rate limits
+    let login_throttle_defaults = LoginThrottlePolicy::default();
+    let login_throttle_policy = LoginThrottlePolicy {
+        max_failed_attempts: configuration_helper
+            .get_optional("LOGIN_MAX_FAILED_ATTEMPTS")
+            .parse()
+            .unwrap_or(login_throttle_defaults.max_failed_attempts),
+        base_delay_secs: configuration_helper
+            .get_optional("LOGIN_BASE_DELAY_SECONDS")
+            .parse()
+            .unwrap_or(login_throttle_defaults.base_delay_secs),
+        lockout_secs: configuration_helper
+            .get_optional("LOGIN_LOCKOUT_SECONDS")
+            .parse()
+            .unwrap_or(login_throttle_defaults.lockout_secs),
+    };
+    let identity_login_throttle_usecase = IdentityLoginThrottleUseCase::new(
+        UserLoginThrottleRepository::new(Arc::clone(&db_connection.pool)),
+        login_throttle_policy,
+    );
+    let identity_user_status_usecase = IdentityUserStatusUseCase::new(
+        UserStatusRepository::new(Arc::clone(&db_connection.pool)),
+        user_stamps_cache,
+    );
     let identity_user_usecase = IdentityUserUseCase::new(
         identity_password_hasher.clone(),
         user_usecase.clone(),
         role_usecase.clone(),
         identity_token_helper.clone(),
     );
-    let identity_authenticate_usecase = IdentityAuthenticateUseCase::new(
-        configuration_helper.clone(),
-        identity_password_hasher,
+    let identity_external_login_usecase = IdentityExternalLoginUseCase::new(
+        IdentityOidcClient::new(
+            load_oidc_providers(&configuration_helper),
+            configuration_helper.get_optional("OIDC_REDIRECT_URL"),
+            configuration_helper.get("JWT_CLIENT_SECRET"),
+        ),
+        UserExternalLoginRepository::new(Arc::clone(&db_connection.pool)),
         user_usecase.clone(),
+        identity_password_hasher.clone(),
+        transaction_manager.clone(),
+    );
+    let identity_api_key_usecase = IdentityApiKeyUseCase::new(
+        UserApiKeyRepository::new(Arc::clone(&db_connection.pool)),
+        identity_user_usecase.clone(),
+        user_usecase.clone(),
+        permission_repository,
+        identity_password_hasher.clone(),
         identity_token_helper.clone(),
     );
+    let identity_authenticate_usecase = IdentityAuthenticateUseCase::new(
+        configuration_helper.clone(),
+        IdentityAuthenticateDependencies {
+            password_hasher: identity_password_hasher,
+            user_usecase: user_usecase.clone(),
+            token_helper: identity_token_helper.clone(),
+            user_token_repository: user_token_repository.clone(),
+            mfa_usecase: identity_mfa_usecase.clone(),
+            login_throttle_usecase: identity_login_throttle_usecase.clone(),
+            user_status_usecase: identity_user_status_usecase.clone(),
+            identity_user_usecase: identity_user_usecase.clone(),
+        },
+    );
     let identity_authorize_usecase = IdentityAuthorizeUseCase::new(
         user_role_repository,
         user_permission_repository,
         role_permission_repository,
     );
     let date_time_helper = DateTimeHelper::new();
-    let transaction_manager = TransactionManager::new(Arc::clone(&db_connection.pool));
     let email_helper = EmailHelper::new();
     let identity_user_token_usecase = IdentityUserTokenUseCase::new(user_token_repository);
     let mail_template_repository = MailTemplateRepository::new(Arc::clone(&db_connection.pool));
     let mail_template_usecase = MailTemplateUseCase::new(mail_template_repository);
+    let email_outbox_repository = EmailOutboxRepository::new(Arc::clone(&db_connection.pool));
+    let email_outbox_usecase = EmailOutboxUseCase::new(email_outbox_repository);
     let html_helper = html_helper::HtmlHelper::new();
 
     // Scoring module
     let scoring_repository: Arc<dyn ScoringRepositoryTrait> =
         Arc::new(ScoringRepository::new(Arc::clone(&db_connection.pool)));
-    let scoring_usecase = ScoringUseCase::new(scoring_repository);
+    // Leaderboard days, weeks and months start at midnight in this fixed offset, e.g. "+07:00"
+    let leaderboard_offset = configuration_helper
+        .get_optional("LEADERBOARD_UTC_OFFSET")
+        .parse::<FixedOffset>()
+        .unwrap_or(FixedOffset::east_opt(0).unwrap());
+    // Freezes and reminders of the daily streaks
+    let streak_defaults = StreakPolicy::default();
+    let streak_policy = StreakPolicy {
+        max_freezes: configuration_helper
+            .get_optional("STREAK_MAX_FREEZES")
+            .parse()
+            .unwrap_or(streak_defaults.max_freezes),
+        freeze_earned_every_days: configuration_helper
+            .get_optional("STREAK_FREEZE_EARNED_EVERY_DAYS")
+            .parse()
+            .unwrap_or(streak_defaults.freeze_earned_every_days),
+        freeze_price: configuration_helper
+            .get_optional("STREAK_FREEZE_PRICE")
+            .parse()
+            .unwrap_or(streak_defaults.freeze_price),
+        reminder_hour: configuration_helper
+            .get_optional("STREAK_REMINDER_HOUR")
+            .parse()
+            .unwrap_or(streak_defaults.reminder_hour),
+    };
+    let scoring_usecase = ScoringUseCase::new(
+        scoring_repository,
+        transaction_manager.clone(),
+        leaderboard_offset,
+        streak_policy,
+    );
 
     // Create use cases group
     let usecases = UseCases {
@@ -144,14 +297,28 @@ pub async fn start() {
         user: user_usecase,
         identity_user: identity_user_usecase,
         identity_authenticate: identity_authenticate_usecase,
+        identity_login_throttle: identity_login_throttle_usecase,
+        identity_user_status: identity_user_status_usecase,
+        identity_external_login: identity_external_login_usecase,
+        identity_api_key: identity_api_key_usecase,
+        identity_mfa: identity_mfa_usecase,
+        identity_signing_key: identity_signing_key_usecase.clone(),
         role: role_usecase,
         permission: permission_usecase,
         identity_authorize: identity_authorize_usecase,
         identity_user_token: identity_user_token_usecase,
         mail_template: mail_template_usecase,
-        scoring: scoring_usecase,
+        email_outbox: email_outbox_usecase.clone(),
+        scoring: scoring_usecase.clone(),
     };
 
+    // Emails queued in the outbox are sent in the background of this process
+    EmailOutboxWorker::new(email_outbox_usecase, email_helper.clone()).spawn();
+    // Signing keys are rotated and reloaded from the database in the background
+    SigningKeyWorker::new(identity_signing_key_usecase).spawn();
+    // Achievement backfills requested by admins are replayed in the background
+    AchievementBackfillWorker::new(scoring_usecase).spawn();
+
     // Create helpers group
     let helpers = Helpers {
         email: email_helper,
@@ -177,6 +344,9 @@ pub async fn start() {
         rate_limiters,
     };
 
+    // Players whose streak is at risk are reminded by email in the background
+    StreakReminderWorker::new(app_state.clone()).spawn();
+
     let authenticated_routes = AppRouting {
         app_state: Arc::new(app_state.clone()),
     }
@@ -192,6 +362,11 @@ pub async fn start() {
     }
     .build_public_routes(admin_authenticated_routes);
 
+    let well_known_routes = AppRouting {
+        app_state: Arc::new(app_state.clone()),
+    }
+    .build_well_known_routes(Router::new());
+
     let cors = CorsLayer::new()
         // allow `GET` and `POST` when accessing the resource
         .allow_methods([
@@ -216,7 +391,10 @@ pub async fn start() {
             },
         ))
         .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE]);
-    let app_routes = Router::new().nest("/api", public_routes).layer(cors);
+    let app_routes = Router::new()
+        .nest("/api", public_routes)
+        .merge(well_known_routes)
+        .layer(cors);
     let stated_routes = app_routes.with_state(app_state);
 
     // Get server configuration from environment
@@ -248,3 +426,49 @@ pub async fn start() {
         tracing::error!(error = ?err, "Server error");
     }
 }
+
+/// Reads the OpenID Connect providers listed in `OIDC_PROVIDERS`, each configured by
+/// `OIDC_<NAME>_*` settings. Providers with missing settings are skipped with a warning.
+fn load_oidc_providers(configuration_helper: &ConfigurationHelper) -> Vec<OidcProviderOptions> {
+    let provider_names = configuration_helper.get_optional("OIDC_PROVIDERS");
+    provider_names
+        .split(',')
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !name.is_empty())
+        .filter_map(|name| {
+            let prefix = format!("OIDC_{}", name.to_uppercase());
+            let issuer = configuration_helper.get_optional(&format!("{}_ISSUER", prefix));
+            let client_id = configuration_helper.get_optional(&format!("{}_CLIENT_ID", prefix));
+            let client_secret =
+                configuration_helper.get_optional(&format!("{}_CLIENT_SECRET", prefix));
+            if issuer.is_empty() || client_id.is_empty() {
+                tracing::warn!(provider = %name, "OpenID Connect provider is not configured");
+                return None;
+            }
+
+            let display_name =
+                configuration_helper.get_optional(&format!("{}_DISPLAY_NAME", prefix));
+            let scopes = configuration_helper.get_optional(&format!("{}_SCOPES", prefix));
+            Some(OidcProviderOptions {
+                display_name: if display_name.is_empty() {
+                    name.to_owned()
+                } else {
+                    display_name
+                },
+                issuer,
+                client_id,
+                client_secret,
+                scopes: if scopes.is_empty() {
+                    "openid email profile".to_string()
+                } else {
+                    scopes
+                },
+                trust_email: configuration_helper
+                    .get_optional(&format!("{}_TRUST_EMAIL", prefix))
+                    .parse()
+                    .unwrap_or(false),
+                name,
+            })
+        })
+        .collect()
+}
@@ -39,6 +39,18 @@ pub fn validate_file_content(data: &Vec<u8>) -> Result<(), ValidationError> {
     validate_content_type(mime)
 }
 
+/// Accepts names made of letters, digits, `-`, `_` and `.`, so they can be used in an email address
+pub fn validate_account_name(name: &str) -> Result<(), ValidationError> {
+    if name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        Ok(())
+    } else {
+        Err(ValidationError::new("invalid_account_name"))
+    }
+}
+
 /// Detects the actual content type from file data and returns it
 /// Returns the detected MIME type if valid, otherwise returns an error
 pub fn detect_content_type(data: &Vec<u8>) -> Result<String, ValidationError> {
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct ExternalLoginCallbackRequest {
+    #[validate(length(min = 1, message = "Authorization code is required"))]
+    pub code: String,
+    #[validate(length(min = 1, message = "State is required"))]
+    pub state: String,
+    #[validate(length(min = 1, message = "Flow token is required"))]
+    pub flow_token: String,
+}
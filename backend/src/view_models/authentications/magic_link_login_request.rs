@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct MagicLinkLoginRequest {
+    #[validate(length(min = 1, message = "Token is required"))]
+    pub token: String,
+    #[validate(length(min = 1, message = "Nonce is required"))]
+    pub nonce: String,
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct MagicLinkRequest {
+    #[validate(email)]
+    #[validate(length(
+        min = 5,
+        max = 200,
+        message = "Email must be between 5 and 200 characters"
+    ))]
+    pub email: String,
+    /// Random value the requesting device keeps in a cookie; the link only works with it
+    #[validate(length(
+        min = 32,
+        max = 128,
+        message = "Nonce must be between 32 and 128 characters"
+    ))]
+    pub nonce: String,
+}
@@ -0,0 +1,11 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct MfaChallengeResult {
+    pub mfa_required: bool,
+    pub mfa_token: String,
+    pub expiration: DateTime<Utc>,
+    /// The user has to set up an authenticator before the login can finish
+    pub enrolment_required: bool,
+}
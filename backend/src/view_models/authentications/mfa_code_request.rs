@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct MfaCodeRequest {
+    #[validate(length(
+        min = 6,
+        max = 20,
+        message = "Code must be between 6 and 20 characters"
+    ))]
+    pub code: String,
+}
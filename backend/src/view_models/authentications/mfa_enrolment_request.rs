@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct MfaEnrolmentRequest {
+    #[validate(length(min = 1, message = "Two-factor token is required"))]
+    pub mfa_token: String,
+}
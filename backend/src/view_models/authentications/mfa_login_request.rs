@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct MfaLoginRequest {
+    #[validate(length(min = 1, message = "Two-factor token is required"))]
+    pub mfa_token: String,
+    #[validate(length(
+        min = 6,
+        max = 20,
+        message = "Code must be between 6 and 20 characters"
+    ))]
+    pub code: String,
+}
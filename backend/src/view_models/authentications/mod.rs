@@ -1 +1,9 @@
+pub mod api_key_creation_request;
+pub mod external_login_callback_request;
+pub mod magic_link_login_request;
+pub mod magic_link_request;
+pub mod mfa_challenge_result;
+pub mod mfa_code_request;
+pub mod mfa_enrolment_request;
+pub mod mfa_login_request;
 pub mod user_login_request;
@@ -0,0 +1,17 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Serialize)]
+pub struct FlashcardImportReport {
+    pub imported_count: usize,
+    pub rows: Vec<FlashcardImportRowResult>,
+}
+
+/// Outcome of one package row. `row` is 1-based, `errors` is set when the row failed validation.
+#[derive(Serialize)]
+pub struct FlashcardImportRowResult {
+    pub row: usize,
+    pub name: String,
+    pub flashcard_id: Option<i32>,
+    pub errors: Option<HashMap<String, String>>,
+}
@@ -1 +1,2 @@
+pub mod flashcard_import_report;
 pub mod flashcard_request;
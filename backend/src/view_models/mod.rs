@@ -5,6 +5,9 @@ use axum::{
     Json,
 };
 use hyper::StatusCode;
+use rex_game_shared::ApplicationError;
+
+use crate::middlewares::error_handler_middleware::transform_application_error;
 use serde::Serialize;
 
 pub mod authentications;
@@ -38,4 +41,17 @@ impl IntoResponse for HandlerError {
     }
 }
 
+/// Same status and message as the error handler middleware gives
+impl From<ApplicationError> for HandlerError {
+    fn from(err: ApplicationError) -> Self {
+        let response = transform_application_error(&err);
+        HandlerError {
+            status: StatusCode::from_u16(response.status)
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            message: response.message,
+            ..Default::default()
+        }
+    }
+}
+
 pub type HandlerResult<T> = Result<T, HandlerError>;
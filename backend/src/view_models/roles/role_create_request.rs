@@ -10,4 +10,6 @@ pub struct RoleCreateRequest {
     ))]
     pub name: String,
     pub description: Option<String>,
+    /// Role to inherit permissions from
+    pub parent_id: Option<i32>,
 }
@@ -1,11 +1,48 @@
+use rex_game_identity::{
+    application::usecases::{
+        auth::api_key_principal_dto::ApiKeyPrincipalDto, roles::ROLE_ROOT_ADMIN,
+    },
+    domain::rules::OwnershipPolicy,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct CurrentUser {
     pub id: i32,
     pub email: String,
+    /// Not carried by access tokens; empty unless the request was made with an API key
     pub name: String,
     pub display_name: Option<String>,
     pub roles: Vec<String>,
     pub permissions: Vec<String>,
+    /// Set when the request was made with an API key rather than a signed-in session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<i32>,
+}
+
+impl CurrentUser {
+    /// Root admins pass every permission check, unless acting through an API key which is
+    /// held to its scopes
+    pub fn is_root_admin(&self) -> bool {
+        self.api_key_id.is_none() && self.roles.iter().any(|role| role == ROLE_ROOT_ADMIN)
+    }
+
+    /// Whether the user may act on a resource created by `owner_id`
+    pub fn can(&self, policy: &OwnershipPolicy, owner_id: Option<i32>) -> bool {
+        self.is_root_admin() || policy.allows(&self.permissions, self.id, owner_id)
+    }
+}
+
+impl From<ApiKeyPrincipalDto> for CurrentUser {
+    fn from(principal: ApiKeyPrincipalDto) -> Self {
+        Self {
+            id: principal.user_id,
+            email: principal.email,
+            name: principal.name,
+            display_name: principal.display_name,
+            roles: principal.roles,
+            permissions: principal.permissions,
+            api_key_id: Some(principal.api_key_id),
+        }
+    }
 }
@@ -6,4 +6,7 @@ pub struct LoginResult {
     pub access_token: String,
     pub expiration: DateTime<Utc>,
     // pub refresh_token: String,
+    /// Only set when the login also finished a two-factor enrolment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_codes: Option<Vec<String>>,
 }
@@ -5,4 +5,6 @@ pub mod current_user;
 pub mod forgot_password_request;
 pub mod login_result;
 pub mod reset_password_request;
+pub mod service_account_creation_request;
 pub mod signup_request;
+pub mod suspend_user_request;
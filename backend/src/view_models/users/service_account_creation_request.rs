@@ -0,0 +1,23 @@
+use crate::validators::validate_account_name;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct ServiceAccountCreationRequest {
+    #[validate(length(
+        min = 3,
+        max = 100,
+        message = "Name must be between 3 and 100 characters"
+    ))]
+    #[validate(custom(
+        function = "validate_account_name",
+        message = "Name may only contain letters, digits, '-', '_' and '.'"
+    ))]
+    pub name: String,
+    #[validate(length(
+        min = 3,
+        max = 100,
+        message = "Display name must be between 3 and 100 characters"
+    ))]
+    pub display_name: Option<String>,
+}
@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Validate)]
+pub struct SuspendUserRequest {
+    #[validate(length(
+        min = 1,
+        max = 500,
+        message = "Reason must be between 1 and 500 characters"
+    ))]
+    pub reason: String,
+    /// Leave empty to suspend until reinstated
+    pub expires_on: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub banned: bool,
+}
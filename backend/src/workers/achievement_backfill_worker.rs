@@ -0,0 +1,109 @@
+use rex_game_games::{AchievementBackfillDto, ScoringUseCase, ScoringUseCaseTrait};
+use rex_game_shared::ApplicationError;
+use std::time::Duration;
+
+/// How long the worker sleeps when no backfill is waiting
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// Users replayed per transaction
+const BATCH_SIZE: u64 = 100;
+
+/// Runs admin-requested achievement backfills in the background of the server process.
+pub struct AchievementBackfillWorker {
+    scoring_usecase: ScoringUseCase,
+}
+
+impl AchievementBackfillWorker {
+    pub fn new(scoring_usecase: ScoringUseCase) -> Self {
+        Self { scoring_usecase }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move { self.run().await })
+    }
+
+    async fn run(self) {
+        tracing::info!("Achievement backfill worker started");
+        loop {
+            if !self.process_batch().await {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+
+    /// Runs one batch and returns whether there may be more work right away.
+    async fn process_batch(&self) -> bool {
+        let result = self
+            .scoring_usecase
+            .process_achievement_backfill_batch(BATCH_SIZE)
+            .await;
+        Self::report_batch(result)
+    }
+
+    fn report_batch(result: Result<Option<AchievementBackfillDto>, ApplicationError>) -> bool {
+        match result {
+            Ok(Some(backfill)) => {
+                tracing::info!(
+                    backfill_id = backfill.id,
+                    status = %backfill.status,
+                    processed_users = backfill.processed_users,
+                    total_users = backfill.total_users,
+                    affected_users = backfill.affected_users,
+                    unlocked_count = backfill.unlocked_count,
+                    is_dry_run = backfill.is_dry_run,
+                    "Achievement backfill batch processed"
+                );
+                true
+            }
+            Ok(None) => false,
+            Err(err) => {
+                tracing::error!(error = %err, "Achievement backfill batch failed");
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn backfill(status: &str, processed_users: i32) -> AchievementBackfillDto {
+        AchievementBackfillDto {
+            id: 1,
+            achievement_id: None,
+            is_dry_run: false,
+            status: status.to_string(),
+            total_users: 250,
+            processed_users,
+            affected_users: 0,
+            unlocked_count: 0,
+            last_error: None,
+            created_by_id: None,
+            started_on: Some(Utc::now()),
+            completed_on: None,
+            created_on: Utc::now(),
+            updated_on: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_processed_batch_asks_for_the_next_one_right_away() {
+        assert!(AchievementBackfillWorker::report_batch(Ok(Some(backfill(
+            "running", 100
+        )))));
+        // The next poll finds nothing left and sleeps
+        assert!(AchievementBackfillWorker::report_batch(Ok(Some(backfill(
+            "completed",
+            250
+        )))));
+    }
+
+    #[test]
+    fn test_idle_or_failed_batch_waits_for_the_poll_interval() {
+        assert!(!AchievementBackfillWorker::report_batch(Ok(None)));
+        assert!(!AchievementBackfillWorker::report_batch(Err(
+            ApplicationError::conflict("backfill is locked")
+        )));
+    }
+}
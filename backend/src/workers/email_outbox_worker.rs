@@ -0,0 +1,73 @@
+use rex_game_mail_templates::application::{EmailOutboxUseCase, EmailOutboxUseCaseTrait};
+use rex_game_shared::{
+    domain::helpers::email_helper_trait::EmailHelperTrait,
+    infrastructure::helpers::email_helper::EmailHelper,
+};
+use std::time::Duration;
+
+/// How long the worker sleeps when the outbox has nothing due
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const BATCH_SIZE: u64 = 20;
+
+/// Sends queued emails from the outbox in the background of the server process.
+pub struct EmailOutboxWorker {
+    email_outbox_usecase: EmailOutboxUseCase,
+    email_helper: EmailHelper,
+}
+
+impl EmailOutboxWorker {
+    pub fn new(email_outbox_usecase: EmailOutboxUseCase, email_helper: EmailHelper) -> Self {
+        Self {
+            email_outbox_usecase,
+            email_helper,
+        }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move { self.run().await })
+    }
+
+    async fn run(self) {
+        tracing::info!("Email outbox worker started");
+        loop {
+            let processed = self.process_batch().await;
+            if processed < BATCH_SIZE as usize {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+
+    /// Sends one batch of due emails and returns how many were claimed.
+    async fn process_batch(&self) -> usize {
+        let emails = match self.email_outbox_usecase.claim_due(BATCH_SIZE).await {
+            Ok(emails) => emails,
+            Err(err) => {
+                tracing::error!(error = %err, "Failed to claim queued emails");
+                return 0;
+            }
+        };
+
+        let claimed = emails.len();
+        for email in emails {
+            let id = email.id;
+            let attempts = email.attempts + 1;
+            let recorded = match self.email_helper.send_email(email.into_message()).await {
+                Ok(_) => {
+                    tracing::info!(email_id = id, attempts, "Queued email sent");
+                    self.email_outbox_usecase.record_sent(id, attempts).await
+                }
+                Err(err) => {
+                    tracing::warn!(email_id = id, attempts, error = %err, "Queued email failed");
+                    self.email_outbox_usecase
+                        .record_failure(id, attempts, err.to_string())
+                        .await
+                }
+            };
+
+            if let Err(err) = recorded {
+                tracing::error!(email_id = id, error = %err, "Failed to record email attempt");
+            }
+        }
+        claimed
+    }
+}
@@ -0,0 +1,4 @@
+pub mod achievement_backfill_worker;
+pub mod email_outbox_worker;
+pub mod signing_key_worker;
+pub mod streak_reminder_worker;
@@ -0,0 +1,43 @@
+use rex_game_identity::{
+    IdentitySigningKeyUseCase, IdentitySigningKeyUseCaseTrait, IdentitySigningKeyring,
+    SigningKeyRepository,
+};
+use std::time::Duration;
+
+/// How often keys are reloaded. Must stay well below the time a new key is published
+/// before it signs, so every server knows the key by then.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Rotates the token signing key when it is due and picks up keys rotated by other servers.
+pub struct SigningKeyWorker {
+    signing_key_usecase: IdentitySigningKeyUseCase<SigningKeyRepository, IdentitySigningKeyring>,
+}
+
+impl SigningKeyWorker {
+    pub fn new(
+        signing_key_usecase: IdentitySigningKeyUseCase<
+            SigningKeyRepository,
+            IdentitySigningKeyring,
+        >,
+    ) -> Self {
+        Self {
+            signing_key_usecase,
+        }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move { self.run().await })
+    }
+
+    async fn run(self) {
+        tracing::info!("Signing key worker started");
+        loop {
+            tokio::time::sleep(REFRESH_INTERVAL).await;
+            match self.signing_key_usecase.refresh_keys().await {
+                Ok(true) => tracing::info!("Token signing key rotated"),
+                Ok(false) => {}
+                Err(err) => tracing::error!(error = %err, "Failed to refresh the signing keys"),
+            }
+        }
+    }
+}
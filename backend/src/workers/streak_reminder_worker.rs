@@ -0,0 +1,78 @@
+use crate::{app_state::AppState, helpers::account_email_helper::AccountEmailHelper};
+use rex_game_games::ScoringUseCaseTrait;
+use rex_game_shared::domain::enums::mail_template_names::MailTemplateNames;
+use std::time::Duration;
+
+/// How long the worker sleeps after every player was checked
+const POLL_INTERVAL: Duration = Duration::from_secs(15 * 60);
+/// Players checked per batch
+const BATCH_SIZE: u64 = 200;
+
+/// Emails players whose daily streak is at risk, once per local day, in the background of
+/// the server process.
+pub struct StreakReminderWorker {
+    app_state: AppState,
+}
+
+impl StreakReminderWorker {
+    pub fn new(app_state: AppState) -> Self {
+        Self { app_state }
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move { self.run().await })
+    }
+
+    async fn run(self) {
+        tracing::info!("Streak reminder worker started");
+        let mut after_user_id = 0;
+        loop {
+            match self.process_batch(after_user_id).await {
+                Some(next_user_id) => after_user_id = next_user_id,
+                None => {
+                    after_user_id = 0;
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                }
+            }
+        }
+    }
+
+    /// Queues the reminders due in one batch of players and returns where the next batch
+    /// starts, `None` once the pass is over.
+    async fn process_batch(&self, after_user_id: i32) -> Option<i32> {
+        let batch = match self
+            .app_state
+            .usecases
+            .scoring
+            .claim_streak_reminders(after_user_id, BATCH_SIZE)
+            .await
+        {
+            Ok(batch) => batch,
+            Err(err) => {
+                tracing::error!(error = %err, "Failed to claim streak reminders");
+                return None;
+            }
+        };
+
+        for reminder in batch.reminders {
+            // The reminder is already claimed for today, so a failure is only logged
+            if let Err(err) = AccountEmailHelper::queue(
+                &self.app_state,
+                MailTemplateNames::STREAK_REMINDER,
+                &reminder.user_name,
+                &reminder.email,
+                &[("[streak_days]", reminder.current_streak.to_string())],
+            )
+            .await
+            {
+                tracing::error!(
+                    user_id = reminder.user_id,
+                    "Failed to queue streak reminder email: {}",
+                    err
+                );
+            }
+        }
+
+        batch.next_user_id
+    }
+}
@@ -1,8 +1,13 @@
-import type { Achievement } from "$lib/models/achievement";
+import type {
+  Achievement,
+  AchievementBackfill,
+  AchievementCriteria,
+} from "$lib/models/achievement";
 import { BaseApi } from "./baseApi";
 
 export class AchievementAdminApi extends BaseApi {
   private baseUrl = "/admin/achievements";
+  private backfillUrl = "/admin/achievement-backfills";
 
   async getList(
     fetch: Function,
@@ -44,7 +49,15 @@ export class AchievementAdminApi extends BaseApi {
 
   async create(
     fetch: Function,
-    data: { code: string; name: string; description?: string; icon?: string; points: number; category?: string }
+    data: {
+      code: string;
+      name: string;
+      description?: string;
+      icon?: string;
+      points: number;
+      category?: string;
+      criteria?: AchievementCriteria;
+    }
   ) {
     const response: Response = await this.post(fetch, this.baseUrl, data, {
       observe: true,
@@ -59,7 +72,15 @@ export class AchievementAdminApi extends BaseApi {
   async update(
     fetch: Function,
     id: number,
-    data: { code?: string; name?: string; description?: string; icon?: string; points?: number; category?: string }
+    data: {
+      code?: string;
+      name?: string;
+      description?: string;
+      icon?: string;
+      points?: number;
+      category?: string;
+      criteria?: AchievementCriteria;
+    }
   ) {
     const response: Response = await this.patch(fetch, `${this.baseUrl}/${id}`, data, {
       observe: true,
@@ -93,4 +114,31 @@ export class AchievementAdminApi extends BaseApi {
     }
     return (await response.json()) as boolean;
   }
+
+  async queueBackfill(
+    fetch: Function,
+    data: { achievement_id?: number; dry_run: boolean }
+  ) {
+    const response: Response = await this.post(fetch, this.backfillUrl, data, {
+      observe: true,
+    });
+    if (response.status !== 200) {
+      const errorText = await response.text();
+      throw new Error(errorText || "Failed to queue achievement backfill");
+    }
+    return (await response.json()) as number;
+  }
+
+  async getBackfill(fetch: Function, id: number) {
+    const response: Response = await this.get(
+      fetch,
+      `${this.backfillUrl}/${id}`,
+      new URLSearchParams(),
+      { observe: true }
+    );
+    if (response.status !== 200) {
+      throw new Error("Failed to fetch achievement backfill");
+    }
+    return (await response.json()) as AchievementBackfill;
+  }
 }
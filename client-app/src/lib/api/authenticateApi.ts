@@ -3,6 +3,47 @@ import { BaseApi } from "./baseApi";
 import type { BaseApiOptions } from "./apiOptions";
 import { ACCESS_TOKEN } from "$lib/common/contants";
 
+export interface UserSession {
+  id: string;
+  last_refreshed_on: string;
+  expiration: string;
+}
+
+export interface MfaChallenge {
+  mfa_required: true;
+  mfa_token: string;
+  expiration: string;
+  enrolment_required: boolean;
+}
+
+export interface MfaEnrolment {
+  secret: string;
+  otpauth_uri: string;
+}
+
+export interface ExternalLoginProvider {
+  name: string;
+  display_name: string;
+}
+
+export interface ExternalAuthorization {
+  authorization_url: string;
+  flow_token: string;
+}
+
+export interface ExternalLogin {
+  provider: string;
+  email: string | null;
+  created_on: string;
+  last_used_on: string | null;
+}
+
+export interface ExternalLoginCallback {
+  code: string;
+  state: string;
+  flow_token: string;
+}
+
 class AuthenticateApi extends BaseApi {
   constructor(options: BaseApiOptions) {
     super(options);
@@ -17,6 +58,51 @@ class AuthenticateApi extends BaseApi {
     return await response;
   }
 
+  async requestMagicLink(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    email: string,
+    nonce: string
+  ) {
+    return await this.post(fetch, "/auth/magic-link", { email, nonce }, { observe: true });
+  }
+
+  async magicLinkLogin(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    token: string,
+    nonce: string
+  ) {
+    return await this.post(fetch, "/auth/magic-link/login", { token, nonce }, { observe: true });
+  }
+
+  async verifyMfa(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    mfaToken: string,
+    code: string
+  ) {
+    return await this.post(
+      fetch,
+      "/auth/mfa/verify",
+      { mfa_token: mfaToken, code },
+      { observe: true }
+    );
+  }
+
+  async beginMfaEnrolment(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    mfaToken: string
+  ): Promise<MfaEnrolment | null> {
+    const response: Response = await this.post(
+      fetch,
+      "/auth/mfa/enrolment",
+      { mfa_token: mfaToken },
+      { observe: true }
+    );
+    if (!response.ok) {
+      return null;
+    }
+    return await response.json();
+  }
+
   async refreshToken(fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>) {
     const response = await this.post(fetch, "/auth/refresh", {}, { observe: true });
     return await response;
@@ -27,6 +113,107 @@ class AuthenticateApi extends BaseApi {
     return response;
   }
 
+  async getSessions(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>
+  ): Promise<UserSession[]> {
+    return await this.get(fetch, "/auth/sessions", new URLSearchParams());
+  }
+
+  async revokeSession(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    sessionId: string
+  ) {
+    return await this.delete(fetch, `/auth/sessions/${sessionId}`);
+  }
+
+  async getExternalLoginProviders(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>
+  ): Promise<ExternalLoginProvider[]> {
+    const response: Response = await this.get(
+      fetch,
+      "/auth/external/providers",
+      new URLSearchParams(),
+      { observe: true }
+    );
+    if (!response.ok) {
+      return [];
+    }
+    return await response.json();
+  }
+
+  async beginExternalLogin(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    provider: string
+  ): Promise<ExternalAuthorization | null> {
+    const response: Response = await this.post(
+      fetch,
+      `/auth/external/${encodeURIComponent(provider)}/authorization`,
+      {},
+      { observe: true }
+    );
+    if (!response.ok) {
+      return null;
+    }
+    return await response.json();
+  }
+
+  async completeExternalLogin(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    provider: string,
+    callback: ExternalLoginCallback
+  ) {
+    return await this.post(
+      fetch,
+      `/auth/external/${encodeURIComponent(provider)}/callback`,
+      callback,
+      { observe: true }
+    );
+  }
+
+  async getExternalLogins(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>
+  ): Promise<ExternalLogin[]> {
+    return await this.get(fetch, "/auth/external-logins", new URLSearchParams());
+  }
+
+  async beginExternalLoginLink(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    provider: string
+  ): Promise<ExternalAuthorization | null> {
+    const response: Response = await this.post(
+      fetch,
+      `/auth/external/${encodeURIComponent(provider)}/link`,
+      {},
+      { observe: true }
+    );
+    if (!response.ok) {
+      return null;
+    }
+    return await response.json();
+  }
+
+  async completeExternalLoginLink(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    provider: string,
+    callback: ExternalLoginCallback
+  ) {
+    return await this.post(
+      fetch,
+      `/auth/external/${encodeURIComponent(provider)}/link/callback`,
+      callback,
+      { observe: true }
+    );
+  }
+
+  async deleteExternalLogin(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    provider: string
+  ) {
+    return await this.delete(fetch, `/auth/external-logins/${encodeURIComponent(provider)}`, {
+      observe: true,
+    });
+  }
+
   removeRefreshToken(cookies: Cookies) {
     const cookie_key = "refresh_token";
     cookies.delete(cookie_key, { path: "/" });
@@ -100,4 +100,49 @@ export class FlashcardApi extends BaseApi {
     }
     return await response.json();
   }
+
+  async importPackage(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    data: FormData
+  ): Promise<FlashcardImportReport> {
+    const response = await this.post(fetch, `${this.baseUrl}/import`, data, { observe: true });
+    // 422 still carries the per-row report
+    if (response.status !== 200 && response.status !== 422) {
+      const error = await response.json();
+      if (error && error.error) {
+        throw error;
+      }
+      throw new Error("Failed to import flashcards");
+    }
+    return await response.json();
+  }
+
+  async exportPackage(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    flashcardTypeId: number,
+    format: "csv" | "json" | "apkg" = "csv"
+  ): Promise<Blob> {
+    const response = await this.get(
+      fetch,
+      `/flashcard-types/${flashcardTypeId}/export`,
+      new URLSearchParams({ format }),
+      { observe: true }
+    );
+    if (response.status !== 200) {
+      throw new Error("Failed to export flashcards");
+    }
+    return await response.blob();
+  }
+}
+
+export interface FlashcardImportRowResult {
+  row: number;
+  name: string;
+  flashcard_id: number | null;
+  errors: Record<string, string> | null;
+}
+
+export interface FlashcardImportReport {
+  imported_count: number;
+  rows: FlashcardImportRowResult[];
 }
@@ -46,7 +46,7 @@ export class RoleApi extends BaseApi {
 
   async create(
     fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
-    data: { name: string; description: string }
+    data: { name: string; description: string; parent_id?: number }
   ) {
     const response = await this.post(fetch, this.baseUrl, data, { observe: true });
     if (response.status !== 200) {
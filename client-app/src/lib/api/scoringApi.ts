@@ -14,14 +14,34 @@ export interface StartGameSessionRequest {
   flashcard_type_id?: number;
 }
 
-export interface CompleteGameSessionRequest {
+export interface DealtCard {
+  position: number;
+  flashcard_id: number;
+}
+
+export interface GameSessionStarted {
   session_id: number;
+  started_at: string;
+  cards: DealtCard[];
+}
+
+export interface SubmitGameAnswerRequest {
+  flashcard_id: number;
+  answer: string;
+  is_match?: boolean;
+}
+
+export interface GameAnswerResult {
+  flashcard_id: number;
+  is_correct: boolean;
+  points: number;
+  combo: number;
   score: number;
-  max_score?: number;
-  correct_answers: number;
-  wrong_answers: number;
-  combo_max: number;
-  time_spent_seconds: number;
+  remaining_cards: number;
+}
+
+export interface CompleteGameSessionRequest {
+  session_id: number;
 }
 
 export interface GameSession {
@@ -51,6 +71,12 @@ export interface UserStats {
   average_accuracy: number;
   current_streak: number;
   best_streak: number;
+  total_xp: number;
+  level: number;
+  /** Total XP at which the current level was reached */
+  level_xp: number;
+  /** Total XP needed for the next level, null at the top level */
+  next_level_xp: number | null;
   rank?: number;
 }
 
@@ -65,6 +91,46 @@ export interface LeaderboardEntry {
   average_accuracy: number;
 }
 
+export type LeaderboardPeriod = "daily" | "weekly" | "monthly" | "all_time";
+
+export interface LeaderboardFilter {
+  period?: LeaderboardPeriod;
+  gameType?: string;
+  flashcardTypeId?: number;
+}
+
+export interface LeaderboardPosition {
+  period: LeaderboardPeriod;
+  period_start: string;
+  rank: number | null;
+  entries: LeaderboardEntry[];
+}
+
+export interface StreakDay {
+  day: string;
+  kind: "played" | "frozen";
+}
+
+export interface Streak {
+  current_streak: number;
+  best_streak: number;
+  freezes: number;
+  max_freezes: number;
+  freeze_price: number;
+  available_points: number;
+  today: string;
+  played_today: boolean;
+  at_risk: boolean;
+  utc_offset_minutes: number;
+  reminders_enabled: boolean;
+  recent_days: StreakDay[];
+}
+
+export interface StreakSettingsRequest {
+  utc_offset_minutes: number;
+  reminders_enabled: boolean;
+}
+
 export interface Achievement {
   id: number;
   code: string;
@@ -77,9 +143,23 @@ export interface Achievement {
   unlocked_at?: string;
 }
 
+export interface XpAward {
+  session_xp: number;
+  accuracy_xp: number;
+  achievement_xp: number;
+  total: number;
+}
+
+export interface LevelUp {
+  level: number;
+  level_xp: number;
+}
+
 export interface GameCompleteResponse {
   session: GameSession;
   new_achievements: Achievement[];
+  xp_earned: XpAward;
+  level_ups: LevelUp[];
   updated_stats: UserStats;
 }
 
@@ -97,10 +177,19 @@ export interface GameProgress {
 
 export interface SaveGameProgressRequest {
   game_type_code: string;
-  current_level: number;
   total_score: number;
 }
 
+export interface DueCard {
+  flashcard_id: number;
+  flashcard_name?: string;
+  is_new: boolean;
+  ease_factor: number;
+  interval_days: number;
+  lapses: number;
+  due_at: string;
+}
+
 export class ScoringApi extends BaseApi {
   constructor(options: BaseApiOptions) {
     super(options);
@@ -116,14 +205,23 @@ export class ScoringApi extends BaseApi {
   async getLeaderboard(
     fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
     page: number = 1,
-    pageSize: number = 10
+    pageSize: number = 10,
+    filter: LeaderboardFilter = {}
   ): Promise<LeaderboardEntry[]> {
-    const params = new URLSearchParams();
+    const params = this.leaderboardParams(filter);
     params.set("page", page.toString());
     params.set("page_size", pageSize.toString());
     return await this.get(fetch, "/leaderboard", params);
   }
 
+  private leaderboardParams(filter: LeaderboardFilter): URLSearchParams {
+    const params = new URLSearchParams();
+    if (filter.period) params.set("period", filter.period);
+    if (filter.gameType) params.set("game_type", filter.gameType);
+    if (filter.flashcardTypeId) params.set("flashcard_type_id", filter.flashcardTypeId.toString());
+    return params;
+  }
+
   async getAchievements(
     fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>
   ): Promise<Achievement[]> {
@@ -141,10 +239,18 @@ export class ScoringApi extends BaseApi {
   async startGameSession(
     fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
     request: StartGameSessionRequest
-  ): Promise<number> {
+  ): Promise<GameSessionStarted> {
     return await this.post(fetch, "/games/sessions", request);
   }
 
+  async submitGameAnswer(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    sessionId: number,
+    request: SubmitGameAnswerRequest
+  ): Promise<GameAnswerResult> {
+    return await this.post(fetch, `/games/sessions/${sessionId}/answers`, request);
+  }
+
   async completeGameSession(
     fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
     request: CompleteGameSessionRequest
@@ -152,6 +258,13 @@ export class ScoringApi extends BaseApi {
     return await this.post(fetch, "/games/sessions/complete", request);
   }
 
+  async abandonGameSession(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    sessionId: number
+  ): Promise<boolean> {
+    return await this.post(fetch, `/games/sessions/${sessionId}/abandon`, {});
+  }
+
   async getGameHistory(
     fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
     page: number = 1,
@@ -180,6 +293,35 @@ export class ScoringApi extends BaseApi {
     return await this.get(fetch, "/users/me/stats", new URLSearchParams());
   }
 
+  async getMyLeaderboardPosition(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    filter: LeaderboardFilter = {},
+    radius: number = 5
+  ): Promise<LeaderboardPosition> {
+    const params = this.leaderboardParams(filter);
+    params.set("radius", radius.toString());
+    return await this.get(fetch, "/leaderboard/me", params);
+  }
+
+  async getMyStreak(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>
+  ): Promise<Streak> {
+    return await this.get(fetch, "/users/me/streak", new URLSearchParams());
+  }
+
+  async updateMyStreakSettings(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    request: StreakSettingsRequest
+  ): Promise<Streak> {
+    return await this.put(fetch, "/users/me/streak/settings", request);
+  }
+
+  async purchaseStreakFreeze(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>
+  ): Promise<Streak> {
+    return await this.post(fetch, "/users/me/streak/freezes", {});
+  }
+
   async getMyAchievements(
     fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>
   ): Promise<Achievement[]> {
@@ -209,4 +351,16 @@ export class ScoringApi extends BaseApi {
   ): Promise<void> {
     await this.delete(fetch, `/games/progress?game_type=${encodeURIComponent(gameTypeCode)}`, { observe: true });
   }
+
+  // Review endpoints
+  async getDueCards(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    flashcardTypeId: number,
+    limit: number = 20
+  ): Promise<DueCard[]> {
+    const params = new URLSearchParams();
+    params.set("flashcard_type_id", flashcardTypeId.toString());
+    params.set("limit", limit.toString());
+    return await this.get(fetch, "/reviews/due", params);
+  }
 }
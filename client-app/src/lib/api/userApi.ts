@@ -3,9 +3,11 @@ import type {
   ConfirmUserRequest,
   ForgotPasswordRequest,
   ResetPasswordRequest,
+  SuspendUserRequest,
   UserRequest,
+  UserStatus,
 } from "$lib/models/user";
-import type { UserPermission } from "$lib/models/user-permission";
+import type { PermissionExplanation, UserPermission } from "$lib/models/user-permission";
 import type { UserRole } from "$lib/models/user-role";
 import type { BaseApiOptions } from "./apiOptions";
 
@@ -127,6 +129,72 @@ class UserApi extends BaseApi {
     return await response.json();
   }
 
+  async signOutEverywhere(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    id: number
+  ) {
+    const response = await this.delete(fetch, `${this.baseUrl}/${id}/sessions`, {
+      observe: true,
+    });
+    if (response.status !== 200) {
+      throw new Error("Failed to sign out user");
+    }
+    return await response.json();
+  }
+
+  async unlock(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    id: number
+  ) {
+    const response = await this.post(fetch, `${this.baseUrl}/${id}/unlock`, {}, {
+      observe: true,
+    });
+    if (response.status !== 200) {
+      throw new Error("Failed to unlock user");
+    }
+    return await response.json();
+  }
+
+  async getStatus(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    id: number
+  ): Promise<UserStatus> {
+    const response = await this.get(fetch, `${this.baseUrl}/${id}/status`, new URLSearchParams(), {
+      observe: true,
+    });
+    if (response.status !== 200) {
+      throw new Error("Failed to fetch user status");
+    }
+    return await response.json();
+  }
+
+  async suspend(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    id: number,
+    request: SuspendUserRequest
+  ): Promise<UserStatus> {
+    const response = await this.post(fetch, `${this.baseUrl}/${id}/suspension`, request, {
+      observe: true,
+    });
+    if (response.status !== 200) {
+      throw new Error("Failed to suspend user");
+    }
+    return await response.json();
+  }
+
+  async reinstate(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    id: number
+  ) {
+    const response = await this.delete(fetch, `${this.baseUrl}/${id}/suspension`, {
+      observe: true,
+    });
+    if (response.status !== 200) {
+      throw new Error("Failed to reinstate user");
+    }
+    return await response.json();
+  }
+
   async getRoleList(
     fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
     id: number,
@@ -203,6 +271,23 @@ class UserApi extends BaseApi {
     return await response.json();
   }
 
+  async explainPermission(
+    fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
+    id: number,
+    code: string
+  ): Promise<PermissionExplanation> {
+    const response = await this.get(
+      fetch,
+      `${this.baseUrl}/${id}/permissions/${encodeURIComponent(code)}`,
+      new URLSearchParams(),
+      { observe: true }
+    );
+    if (response.status !== 200) {
+      throw new Error("Failed to explain user permission");
+    }
+    return await response.json();
+  }
+
   async forgotPassword(
     fetch: (input: RequestInfo | URL, init?: RequestInit) => Promise<Response>,
     data: ForgotPasswordRequest
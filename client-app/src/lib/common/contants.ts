@@ -22,6 +22,13 @@ export const APP_URLS = {
   SIGNUP_URL: "/account/signup",
 };
 
+// Keeps the flow token of a sign-in at an external provider until it redirects back
+export const EXTERNAL_LOGIN_FLOW_COOKIE = "external_login_flow";
+
+// Binds an emailed sign-in link to the browser that asked for it
+export const MAGIC_LINK_NONCE_COOKIE = "magic_link_nonce";
+export const MAGIC_LINK_URL = "/account/magic-link";
+
 export enum ACCESS_TOKEN {
   ADMIN_ACCESS_TOKEN = "s_access_token",
   ADMIN_ACCESS_TOKEN_EXP = "s_access_token_exp",
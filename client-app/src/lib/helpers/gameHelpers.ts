@@ -48,7 +48,7 @@ export async function generateGameCards(flashcards: Flashcard[], pairsNeeded: nu
   const flashcardsWithImages = await Promise.all(
     selectedFlashcards.map(async (flashcard) => {
       try {
-        const imageUrl = await getImageBase64Url(flashcard.image_id);
+        const imageUrl = await getImageBase64Url(flashcard.image_id, flashcard.image_version, "thumb");
         return { ...flashcard, imageUrl };
       } catch (error) {
         console.error(`Failed to load image for flashcard ${flashcard.id}:`, error);
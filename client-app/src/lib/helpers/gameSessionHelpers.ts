@@ -0,0 +1,94 @@
+import type { Flashcard, FlashcardDetail } from "$lib/models/flashcard";
+import type { DealtCard, ScoringApi, SubmitGameAnswerRequest } from "$lib/api/scoringApi";
+import { FlashcardApi } from "$lib/api/flashcardApi";
+import { ACCESS_TOKEN } from "$lib/common/contants";
+import Cookies from "js-cookie";
+
+/**
+ * Resolve the flashcards the server dealt for a session, in dealt order.
+ * Dealt cards that are not in the loaded list are fetched by id.
+ *
+ * @param flashcards - Flashcards already loaded by the game
+ * @param dealtCards - Cards returned when the session was started
+ * @returns Promise<Array of dealt flashcards>
+ */
+export async function resolveDealtFlashcards(
+  flashcards: Flashcard[],
+  dealtCards: DealtCard[]
+): Promise<Flashcard[]> {
+  const loaded = new Map(flashcards.map((f) => [f.id, f]));
+  const ordered = [...dealtCards].sort((a, b) => a.position - b.position);
+
+  const resolved = await Promise.all(
+    ordered.map(async (card) => {
+      const flashcard = loaded.get(card.flashcard_id);
+      if (flashcard) return flashcard;
+
+      try {
+        const api = new FlashcardApi({
+          cookies: Cookies,
+          tokenKey: ACCESS_TOKEN.USER_ACCESS_TOKEN,
+        });
+        const detail: FlashcardDetail = await api.getById(fetch, card.flashcard_id);
+        return {
+          ...detail,
+          is_actived: true,
+          flashcard_type_names: detail.flashcard_types.map((t) => t.name),
+        } as Flashcard;
+      } catch (error) {
+        console.error(`Failed to load dealt flashcard ${card.flashcard_id}:`, error);
+        return null;
+      }
+    })
+  );
+
+  return resolved.filter((f): f is Flashcard => f !== null);
+}
+
+/**
+ * Answer log for one game session
+ * Submits answers one at a time, in the order they were given, so the server
+ * times and scores each of them. Only dealt cards that are still open are sent.
+ */
+export class GameAnswerLog {
+  readonly dealtCards: DealtCard[];
+  private readonly openCardIds: Set<number>;
+  private queue: Promise<void> = Promise.resolve();
+
+  constructor(
+    private readonly api: ScoringApi,
+    private readonly sessionId: number,
+    dealtCards: DealtCard[]
+  ) {
+    this.dealtCards = dealtCards;
+    this.openCardIds = new Set(dealtCards.map((c) => c.flashcard_id));
+  }
+
+  /**
+   * Queue an answer for a dealt card
+   *
+   * @param request - Answer payload
+   * @param closesCard - Whether this answer closes the card (false keeps it open for a retry)
+   */
+  submit(request: SubmitGameAnswerRequest, closesCard: boolean = true): void {
+    if (!this.openCardIds.has(request.flashcard_id)) return;
+    if (closesCard) {
+      this.openCardIds.delete(request.flashcard_id);
+    }
+
+    this.queue = this.queue
+      .then(async () => {
+        await this.api.submitGameAnswer(fetch, this.sessionId, request);
+      })
+      .catch((error) => {
+        console.error(`Failed to submit answer for flashcard ${request.flashcard_id}:`, error);
+      });
+  }
+
+  /**
+   * Wait until every queued answer has been submitted
+   */
+  async flush(): Promise<void> {
+    await this.queue;
+  }
+}
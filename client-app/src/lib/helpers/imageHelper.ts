@@ -15,8 +15,34 @@ export const setImageBase64Url = (image: File | null) => {
   });
 };
 
-export const getImageBase64Url = (image_id: number) => {
-  return fetch(`${PUBLIC_API_URL}/flashcards/images/${image_id}`).then(async (response) => {
+/**
+ * Resized variants served by the API: thumb fits 256px, medium 768px, original 2048px.
+ * Leaving the size out returns the uploaded image.
+ */
+export type ImageSize = "thumb" | "medium" | "original";
+
+/**
+ * Image URL for a flashcard file. With the version the response is cached as immutable;
+ * without it the browser revalidates with the ETag.
+ */
+export const getImageUrl = (image_id: number, image_version?: string | null, size?: ImageSize) => {
+  const params = new URLSearchParams();
+  if (image_version) {
+    params.set("v", image_version);
+  }
+  if (size) {
+    params.set("size", size);
+  }
+  const query = params.toString();
+  const url = `${PUBLIC_API_URL}/flashcards/images/${image_id}`;
+  return query ? `${url}?${query}` : url;
+};
+
+export const getImageBase64Url = (image_id: number, image_version?: string | null, size?: ImageSize) => {
+  // fetch() sends `Accept: */*`, so ask for WebP explicitly like an <img> would
+  return fetch(getImageUrl(image_id, image_version, size), {
+    headers: { Accept: "image/webp,image/*;q=0.8" },
+  }).then(async (response) => {
     const blob = await response.blob();
     return new Promise<string>((resolve, reject) => {
       const reader = new FileReader();
@@ -61,7 +61,7 @@ export async function generateQuizQuestions(
       // Load image
       let imageUrl = "";
       try {
-        imageUrl = await getImageBase64Url(flashcard.image_id);
+        imageUrl = await getImageBase64Url(flashcard.image_id, flashcard.image_version, "medium");
       } catch (error) {
         console.error(
           `Failed to load image for flashcard ${flashcard.id}:`,
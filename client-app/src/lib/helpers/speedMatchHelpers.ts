@@ -37,7 +37,7 @@ export async function generateSpeedMatchPairs(
   await Promise.all(
     toLoad.map(async (fc) => {
       try {
-        const url = await getImageBase64Url(fc.image_id);
+        const url = await getImageBase64Url(fc.image_id, fc.image_version, "thumb");
         imageCache.set(fc.id, url);
       } catch {
         imageCache.set(fc.id, "");
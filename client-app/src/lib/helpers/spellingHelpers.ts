@@ -36,7 +36,7 @@ export async function generateSpellingQuestions(
   await Promise.all(
     selected.map(async (fc) => {
       try {
-        const url = await getImageBase64Url(fc.image_id);
+        const url = await getImageBase64Url(fc.image_id, fc.image_version, "medium");
         imageCache.set(fc.id, url);
       } catch {
         imageCache.set(fc.id, "");
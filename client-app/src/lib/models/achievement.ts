@@ -6,6 +6,7 @@ export interface Achievement {
   icon: string;
   points: number;
   category: string;
+  criteria?: AchievementCriteria | null;
   is_actived: boolean;
   created_on: string;
   updated_on: string;
@@ -19,4 +20,55 @@ export interface AchievementRequest {
   icon: string;
   points: number;
   category: string;
+  /** Criteria as JSON text, edited as is */
+  criteria: string;
+}
+
+export type AchievementMetric =
+  | "games_played"
+  | "score"
+  | "best_score"
+  | "accuracy"
+  | "time_spent_seconds"
+  | "combo_max"
+  | "correct_answers"
+  | "wrong_answers"
+  | "streak_days";
+
+export type AchievementComparator = "gte" | "gt" | "lte" | "lt" | "eq";
+
+export type AchievementScope = "session" | "lifetime" | "game_type" | "flashcard_type";
+
+export interface AchievementCondition {
+  metric: AchievementMetric;
+  comparator: AchievementComparator;
+  threshold: number;
+  scope: AchievementScope;
+  game_type_code?: string;
+  flashcard_type_id?: number;
+  window_days?: number;
+}
+
+export interface AchievementCriteria {
+  all: AchievementCondition[];
+}
+
+export type AchievementBackfillStatus = "queued" | "running" | "completed" | "failed";
+
+export interface AchievementBackfill {
+  id: number;
+  /** Null when every active achievement is replayed */
+  achievement_id: number | null;
+  is_dry_run: boolean;
+  status: AchievementBackfillStatus;
+  total_users: number;
+  processed_users: number;
+  affected_users: number;
+  unlocked_count: number;
+  last_error: string | null;
+  created_by_id: number | null;
+  started_on: string | null;
+  completed_on: string | null;
+  created_on: string;
+  updated_on: string;
 }
@@ -11,5 +11,7 @@ export interface AdminUserStats {
   average_accuracy: number;
   current_streak: number;
   best_streak: number;
+  total_xp: number;
+  level: number;
   last_played_at: string;
 }
@@ -5,6 +5,7 @@ export interface FlashcardType {
   is_actived: boolean;
   created_on: string;
   updated_on: string;
+  created_by_id?: number | null;
 }
 
 export interface FlashcardTypeRequest {
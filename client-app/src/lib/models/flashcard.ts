@@ -7,11 +7,13 @@ export interface Flashcard {
   description: string;
   sub_description?: string;
   image_id: number;
+  image_version?: string | null;
   flashcard_type_id?: number;
   is_actived: boolean;
   flashcard_type_names: string[];
   created_on: string;
   updated_on: string;
+  created_by_id: number;
   image_url?: string;
 }
 
@@ -27,8 +29,10 @@ export interface FlashcardDetail {
   description: string;
   sub_description: string;
   image_id: number;
+  image_version?: string | null;
   created_on: string;
   updated_on: string;
+  created_by_id: number;
   flashcard_types: FlashcardType[];
   game_types: FlashcardGameTypeInfo[];
 }
@@ -2,6 +2,8 @@ export interface Role {
   id: number;
   name: string;
   description: string;
+  requires_mfa: boolean;
+  parent_id?: number; // Role this one inherits permissions from
   created_on: string;
   updated_on: string;
   assigned?: boolean; // Optional field to indicate if the role is assigned to the user
@@ -9,3 +9,15 @@ export interface UserPermission {
   created_on: string;
   updated_on: string;
 }
+
+export interface PermissionExplanation {
+  user_id: number;
+  permission_code: string;
+  granted: boolean;
+  is_root_admin: boolean;
+  sources: PermissionSource[];
+}
+
+export interface PermissionSource {
+  role_path: string[]; // Empty when the permission is assigned to the user directly
+}
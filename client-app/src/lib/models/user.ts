@@ -33,6 +33,21 @@ export interface UserDto {
   status_id: number;
 }
 
+export interface UserStatus {
+  user_id: number;
+  status_id: number;
+  status_reason?: string;
+  status_expires_on?: string;
+  status_changed_by_id?: number;
+  status_changed_on?: string;
+}
+
+export interface SuspendUserRequest {
+  reason: string;
+  expires_on?: string;
+  banned?: boolean;
+}
+
 export interface UserItem {
   id: number;
   name: string;
@@ -0,0 +1,45 @@
+import type { Cookies } from "@sveltejs/kit";
+import { ACCESS_TOKEN } from "$lib/common/contants";
+import { container } from "$lib/di";
+
+/**
+ * Keep the tokens of a successful login in the user's cookies.
+ * The API sets the refresh token as a cookie on its own response, which is copied here.
+ */
+export function signIn(
+  cookies: Cookies,
+  response: Response,
+  loginResponse: { access_token: string; expiration: string }
+) {
+  const cookieHeaders = response.headers.getSetCookie();
+  const refresh_token_data = container.cookieHelper.parseSetCookie(
+    cookieHeaders,
+    "refresh_token"
+  );
+  if (!refresh_token_data) {
+    console.error("No Set-Cookie header found in the response.");
+    return false;
+  }
+
+  cookies.set(
+    ACCESS_TOKEN.USER_REFRESH_TOKEN,
+    refresh_token_data.value,
+    refresh_token_data.options
+  );
+  const { access_token, expiration } = loginResponse;
+  cookies.set(ACCESS_TOKEN.USER_ACCESS_TOKEN, access_token, {
+    path: "/",
+    httpOnly: false,
+    sameSite: "strict",
+    secure: true,
+    expires: new Date(expiration),
+  });
+  cookies.set(ACCESS_TOKEN.USER_ACCESS_TOKEN_EXP, expiration, {
+    path: "/",
+    httpOnly: false,
+    sameSite: "strict",
+    secure: true,
+    expires: new Date(expiration),
+  });
+  return true;
+}
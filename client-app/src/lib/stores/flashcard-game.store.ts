@@ -15,6 +15,7 @@ import {
   calculateLevelScore,
 } from "$lib/helpers/gameHelpers";
 import { ScoringApi, type GameCompleteResponse, type Achievement, type GameProgress } from "$lib/api/scoringApi";
+import { GameAnswerLog, resolveDealtFlashcards } from "$lib/helpers/gameSessionHelpers";
 import { ACCESS_TOKEN } from "$lib/common/contants";
 import Cookies from "js-cookie";
 
@@ -71,6 +72,9 @@ let timerInterval: number | null = null;
 // Scoring API instance
 let scoringApi: ScoringApi | null = null;
 
+// Answers for the current session's dealt cards
+let answerLog: GameAnswerLog | null = null;
+
 /**
  * Check if user is logged in
  */
@@ -146,20 +150,23 @@ export async function initializeGame(
     try {
       console.log("[Scoring] Starting game session...");
       const api = getScoringApi();
-      const sessionId = await api.startGameSession(fetch, {
+      const { session_id: sessionId, cards } = await api.startGameSession(fetch, {
         game_type_code: "memory_match",
         flashcard_type_id: config?.flashcardTypeId,
       });
       currentSessionId.set(sessionId);
+      answerLog = new GameAnswerLog(api, sessionId, cards);
       console.log("[Scoring] Game session started:", sessionId);
     } catch (error) {
       console.error("[Scoring] Failed to start game session on backend:", error);
       // Continue playing even if backend fails
       currentSessionId.set(null);
+      answerLog = null;
     }
   } else {
     console.log("[Scoring] User not logged in, skipping session creation");
     currentSessionId.set(null);
+    answerLog = null;
   }
 
   startNewLevel();
@@ -175,7 +182,17 @@ export async function startNewLevel() {
   const pairsForLevel = getPairsForLevel(currentStats.level);
 
   try {
-    const cards = await generateGameCards(currentFlashcards, pairsForLevel);
+    // Deal the server's cards first and fill the rest of the grid from the loaded deck
+    const dealtFlashcards = answerLog
+      ? await resolveDealtFlashcards(currentFlashcards, answerLog.dealtCards)
+      : [];
+    const dealtIds = new Set(dealtFlashcards.map((f) => f.id));
+    const levelFlashcards = [
+      ...dealtFlashcards,
+      ...currentFlashcards.filter((f) => !dealtIds.has(f.id)),
+    ];
+
+    const cards = await generateGameCards(levelFlashcards, pairsForLevel);
 
     gameCards.set(cards);
     selectedCards.set([]);
@@ -239,6 +256,15 @@ function checkMatch(cards: GameCard[]) {
   const config = get(gameConfig);
   const stats = get(gameStats);
 
+  // A miss keeps the card open, so the player can still match it
+  answerLog?.submit(
+    {
+      flashcard_id: card1.flashcardId,
+      answer: card2.name,
+    },
+    isMatch
+  );
+
   setTimeout(() => {
     gameCards.update((allCards) =>
       allCards.map((c) => {
@@ -319,7 +345,7 @@ async function handleLevelComplete() {
 
     // Save game progress to database
     if (isUserLoggedIn()) {
-      await saveGameProgress(newLevel, newScore);
+      await saveGameProgress(newScore);
       // Start new session for next level
       await startNewSession();
     }
@@ -357,14 +383,8 @@ async function saveLevelSession(): Promise<GameCompleteResponse | null> {
 
   try {
     const api = getScoringApi();
-    const result = await api.completeGameSession(fetch, {
-      session_id: sessionId,
-      score: stats.score,
-      correct_answers: currentTotalCorrectAnswers,
-      wrong_answers: currentWrongAnswers,
-      combo_max: currentMaxCombo,
-      time_spent_seconds: timeSpentSeconds,
-    });
+    await answerLog?.flush();
+    const result = await api.completeGameSession(fetch, { session_id: sessionId });
 
     // Store the result
     lastGameResult.set(result);
@@ -396,15 +416,17 @@ async function startNewSession(): Promise<void> {
   try {
     const api = getScoringApi();
     const flashcardTypeId = get(currentFlashcardTypeId);
-    const sessionId = await api.startGameSession(fetch, {
+    const { session_id: sessionId, cards } = await api.startGameSession(fetch, {
       game_type_code: "memory_match",
       flashcard_type_id: flashcardTypeId,
     });
     currentSessionId.set(sessionId);
+    answerLog = new GameAnswerLog(api, sessionId, cards);
     console.log("[Scoring] New level session started:", sessionId);
   } catch (error) {
     console.warn("[Scoring] Failed to start new level session:", error);
     currentSessionId.set(null);
+    answerLog = null;
   }
 }
 
@@ -442,14 +464,8 @@ export async function endGameSession(): Promise<GameCompleteResponse | null> {
 
   try {
     const api = getScoringApi();
-    const result = await api.completeGameSession(fetch, {
-      session_id: sessionId,
-      score: stats.score,
-      correct_answers: currentTotalCorrectAnswers,
-      wrong_answers: currentWrongAnswers,
-      combo_max: currentMaxCombo,
-      time_spent_seconds: timeSpentSeconds,
-    });
+    await answerLog?.flush();
+    const result = await api.completeGameSession(fetch, { session_id: sessionId });
 
     // Store the result
     lastGameResult.set(result);
@@ -461,12 +477,14 @@ export async function endGameSession(): Promise<GameCompleteResponse | null> {
 
     // Clear session ID
     currentSessionId.set(null);
+    answerLog = null;
 
     console.log("Game session completed:", result);
     return result;
   } catch (error) {
     console.error("Failed to save game session:", error);
     currentSessionId.set(null);
+    answerLog = null;
     return null;
   }
 }
@@ -542,16 +560,19 @@ export async function resetGame() {
   if (isUserLoggedIn()) {
     try {
       const api = getScoringApi();
-      const sessionId = await api.startGameSession(fetch, {
+      const { session_id: sessionId, cards } = await api.startGameSession(fetch, {
         game_type_code: "memory_match",
       });
       currentSessionId.set(sessionId);
+      answerLog = new GameAnswerLog(api, sessionId, cards);
     } catch (error) {
       console.warn("Failed to start new game session:", error);
       currentSessionId.set(null);
+      answerLog = null;
     }
   } else {
     currentSessionId.set(null);
+    answerLog = null;
   }
 
   startNewLevel();
@@ -598,7 +619,7 @@ export async function getGameProgress(): Promise<GameProgress | null> {
 /**
  * Save game progress to database
  */
-async function saveGameProgress(level: number, score: number): Promise<void> {
+async function saveGameProgress(score: number): Promise<void> {
   if (!isUserLoggedIn()) {
     return;
   }
@@ -607,7 +628,6 @@ async function saveGameProgress(level: number, score: number): Promise<void> {
     const api = getScoringApi();
     const progress = await api.saveGameProgress(fetch, {
       game_type_code: "memory_match",
-      current_level: level,
       total_score: score,
     });
     savedProgress.set(progress);
@@ -20,6 +20,10 @@ import {
   type Achievement,
   type GameProgress,
 } from "$lib/api/scoringApi";
+import {
+  GameAnswerLog,
+  resolveDealtFlashcards,
+} from "$lib/helpers/gameSessionHelpers";
 import { ACCESS_TOKEN } from "$lib/common/contants";
 import Cookies from "js-cookie";
 
@@ -81,6 +85,9 @@ let timerInterval: number | null = null;
 // Scoring API instance
 let scoringApi: ScoringApi | null = null;
 
+// Answers for the current session's dealt cards
+let answerLog: GameAnswerLog | null = null;
+
 /**
  * Check if user is logged in
  */
@@ -175,18 +182,21 @@ export async function initializeQuiz(
   if (isUserLoggedIn()) {
     try {
       const api = getScoringApi();
-      const sessionId = await api.startGameSession(fetch, {
+      const { session_id: sessionId, cards } = await api.startGameSession(fetch, {
         game_type_code: "quiz",
         flashcard_type_id: config?.flashcardTypeId,
       });
       quizSessionId.set(sessionId);
+      answerLog = new GameAnswerLog(api, sessionId, cards);
       console.log("[Quiz] Game session started:", sessionId);
     } catch (error) {
       console.error("[Quiz] Failed to start game session:", error);
       quizSessionId.set(null);
+      answerLog = null;
     }
   } else {
     quizSessionId.set(null);
+    answerLog = null;
   }
 
   await startNewRound();
@@ -203,8 +213,17 @@ export async function startNewRound() {
   const numQuestions = getQuestionsForLevel(stats.level);
 
   try {
+    // Ask about the cards the server dealt, so every answer can be scored
+    const dealtFlashcards = answerLog
+      ? await resolveDealtFlashcards(currentFlashcards, answerLog.dealtCards)
+      : [];
+    const roundFlashcards =
+      dealtFlashcards.length >= config.optionsPerQuestion
+        ? dealtFlashcards
+        : currentFlashcards;
+
     const questions = await generateQuizQuestions(
-      currentFlashcards,
+      roundFlashcards,
       numQuestions,
       config.optionsPerQuestion
     );
@@ -253,6 +272,11 @@ export async function answerQuestion(selectedAnswer: string) {
   // Check if answer is correct
   const isCorrect = selectedAnswer === question.correctAnswer;
 
+  answerLog?.submit({
+    flashcard_id: question.flashcardId,
+    answer: selectedAnswer,
+  });
+
   // Update question state
   quizQuestions.update((qs) =>
     qs.map((q, i) =>
@@ -366,7 +390,7 @@ export async function nextLevel() {
 
   // Save progress and start new session
   if (isUserLoggedIn()) {
-    await saveQuizProgress(newLevel, newScore);
+    await saveQuizProgress(newScore);
     await startNewQuizSession();
   }
 
@@ -380,24 +404,10 @@ async function saveQuizSession(): Promise<GameCompleteResponse | null> {
   const sessionId = get(quizSessionId);
   if (!sessionId) return null;
 
-  const stats = get(quizStats);
-  const startTime = get(quizSessionStartTime);
-  const currentMaxCombo = get(quizMaxCombo);
-
-  const timeSpentSeconds = startTime
-    ? Math.floor((Date.now() - startTime) / 1000)
-    : stats.timeElapsed;
-
   try {
     const api = getScoringApi();
-    const result = await api.completeGameSession(fetch, {
-      session_id: sessionId,
-      score: stats.score,
-      correct_answers: stats.correctAnswers,
-      wrong_answers: stats.wrongAnswers,
-      combo_max: currentMaxCombo,
-      time_spent_seconds: timeSpentSeconds,
-    });
+    await answerLog?.flush();
+    const result = await api.completeGameSession(fetch, { session_id: sessionId });
 
     quizLastGameResult.set(result);
 
@@ -422,15 +432,17 @@ async function startNewQuizSession(): Promise<void> {
   try {
     const api = getScoringApi();
     const flashcardTypeId = get(quizCurrentFlashcardTypeId);
-    const sessionId = await api.startGameSession(fetch, {
+    const { session_id: sessionId, cards } = await api.startGameSession(fetch, {
       game_type_code: "quiz",
       flashcard_type_id: flashcardTypeId,
     });
     quizSessionId.set(sessionId);
+    answerLog = new GameAnswerLog(api, sessionId, cards);
     console.log("[Quiz] New session started:", sessionId);
   } catch (error) {
     console.warn("[Quiz] Failed to start new session:", error);
     quizSessionId.set(null);
+    answerLog = null;
   }
 }
 
@@ -448,6 +460,7 @@ export async function endQuizSession(): Promise<GameCompleteResponse | null> {
 
   const result = await saveQuizSession();
   quizSessionId.set(null);
+  answerLog = null;
 
   return result;
 }
@@ -507,13 +520,15 @@ export async function resetQuiz() {
   if (isUserLoggedIn()) {
     try {
       const api = getScoringApi();
-      const sessionId = await api.startGameSession(fetch, {
+      const { session_id: sessionId, cards } = await api.startGameSession(fetch, {
         game_type_code: "quiz",
       });
       quizSessionId.set(sessionId);
+      answerLog = new GameAnswerLog(api, sessionId, cards);
     } catch (error) {
       console.warn("[Quiz] Failed to start new session:", error);
       quizSessionId.set(null);
+      answerLog = null;
     }
   }
 
@@ -565,14 +580,13 @@ export async function getQuizProgress(): Promise<GameProgress | null> {
 /**
  * Save game progress
  */
-async function saveQuizProgress(level: number, score: number): Promise<void> {
+async function saveQuizProgress(score: number): Promise<void> {
   if (!isUserLoggedIn()) return;
 
   try {
     const api = getScoringApi();
     const progress = await api.saveGameProgress(fetch, {
       game_type_code: "quiz",
-      current_level: level,
       total_score: score,
     });
     quizSavedProgress.set(progress);
@@ -19,6 +19,7 @@ import {
   type Achievement,
   type GameProgress,
 } from "$lib/api/scoringApi";
+import { GameAnswerLog, resolveDealtFlashcards } from "$lib/helpers/gameSessionHelpers";
 import { ACCESS_TOKEN } from "$lib/common/contants";
 import Cookies from "js-cookie";
 
@@ -80,6 +81,9 @@ let timerInterval: number | null = null;
 // Scoring API instance
 let scoringApi: ScoringApi | null = null;
 
+// Answers for the current session's dealt cards
+let answerLog: GameAnswerLog | null = null;
+
 /**
  * Check if user is logged in
  */
@@ -178,18 +182,21 @@ export async function initializeSpeedMatch(
   if (isUserLoggedIn()) {
     try {
       const api = getScoringApi();
-      const sessionId = await api.startGameSession(fetch, {
+      const { session_id: sessionId, cards } = await api.startGameSession(fetch, {
         game_type_code: "speed_match",
         flashcard_type_id: config?.flashcardTypeId,
       });
       speedMatchSessionId.set(sessionId);
+      answerLog = new GameAnswerLog(api, sessionId, cards);
       console.log("[SpeedMatch] Game session started:", sessionId);
     } catch (error) {
       console.error("[SpeedMatch] Failed to start game session:", error);
       speedMatchSessionId.set(null);
+      answerLog = null;
     }
   } else {
     speedMatchSessionId.set(null);
+    answerLog = null;
   }
 
   await startNewRound();
@@ -208,7 +215,13 @@ export async function startNewRound() {
   try {
     speedMatchState.set("loading");
 
-    const pairs = await generateSpeedMatchPairs(currentFlashcards, numPairs);
+    // Pair up the cards the server dealt, so every answer can be scored
+    const dealtFlashcards = answerLog
+      ? await resolveDealtFlashcards(currentFlashcards, answerLog.dealtCards)
+      : [];
+    const roundFlashcards = dealtFlashcards.length >= 2 ? dealtFlashcards : currentFlashcards;
+
+    const pairs = await generateSpeedMatchPairs(roundFlashcards, numPairs);
 
     speedMatchPairs.set(pairs);
     currentPairIndex.set(0);
@@ -247,6 +260,12 @@ export async function answerSpeedMatch(userSaysMatch: boolean) {
   // Check if answer is correct
   const isCorrect = userSaysMatch === pair.isMatch;
 
+  answerLog?.submit({
+    flashcard_id: pair.leftFlashcardId,
+    answer: pair.rightName,
+    is_match: userSaysMatch,
+  });
+
   // Calculate score
   const points = calculateSpeedMatchScore(
     isCorrect,
@@ -335,7 +354,7 @@ export async function nextSpeedMatchLevel() {
 
   // Save progress and start new session
   if (isUserLoggedIn()) {
-    await saveSpeedMatchProgress(newLevel, newScore);
+    await saveSpeedMatchProgress(newScore);
     await startNewSpeedMatchSession();
   }
 
@@ -349,25 +368,10 @@ async function saveSpeedMatchSession(): Promise<GameCompleteResponse | null> {
   const sessionId = get(speedMatchSessionId);
   if (!sessionId) return null;
 
-  const stats = get(speedMatchStats);
-  const startTime = get(speedMatchSessionStartTime);
-  const currentMaxCombo = get(speedMatchMaxCombo);
-  const config = get(speedMatchConfig);
-
-  const timeSpentSeconds = startTime
-    ? Math.floor((Date.now() - startTime) / 1000)
-    : config.timePerRound - stats.timeRemaining;
-
   try {
     const api = getScoringApi();
-    const result = await api.completeGameSession(fetch, {
-      session_id: sessionId,
-      score: stats.score,
-      correct_answers: stats.correct,
-      wrong_answers: stats.wrong,
-      combo_max: currentMaxCombo,
-      time_spent_seconds: timeSpentSeconds,
-    });
+    await answerLog?.flush();
+    const result = await api.completeGameSession(fetch, { session_id: sessionId });
 
     speedMatchLastGameResult.set(result);
 
@@ -392,15 +396,17 @@ async function startNewSpeedMatchSession(): Promise<void> {
   try {
     const api = getScoringApi();
     const flashcardTypeId = get(speedMatchCurrentFlashcardTypeId);
-    const sessionId = await api.startGameSession(fetch, {
+    const { session_id: sessionId, cards } = await api.startGameSession(fetch, {
       game_type_code: "speed_match",
       flashcard_type_id: flashcardTypeId,
     });
     speedMatchSessionId.set(sessionId);
+    answerLog = new GameAnswerLog(api, sessionId, cards);
     console.log("[SpeedMatch] New session started:", sessionId);
   } catch (error) {
     console.warn("[SpeedMatch] Failed to start new session:", error);
     speedMatchSessionId.set(null);
+    answerLog = null;
   }
 }
 
@@ -418,6 +424,7 @@ export async function endSpeedMatchSession(): Promise<GameCompleteResponse | nul
 
   const result = await saveSpeedMatchSession();
   speedMatchSessionId.set(null);
+  answerLog = null;
 
   return result;
 }
@@ -486,13 +493,15 @@ export async function resetSpeedMatch() {
   if (isUserLoggedIn()) {
     try {
       const api = getScoringApi();
-      const sessionId = await api.startGameSession(fetch, {
+      const { session_id: sessionId, cards } = await api.startGameSession(fetch, {
         game_type_code: "speed_match",
       });
       speedMatchSessionId.set(sessionId);
+      answerLog = new GameAnswerLog(api, sessionId, cards);
     } catch (error) {
       console.warn("[SpeedMatch] Failed to start new session:", error);
       speedMatchSessionId.set(null);
+      answerLog = null;
     }
   }
 
@@ -544,17 +553,13 @@ export async function getSpeedMatchProgress(): Promise<GameProgress | null> {
 /**
  * Save game progress
  */
-async function saveSpeedMatchProgress(
-  level: number,
-  score: number
-): Promise<void> {
+async function saveSpeedMatchProgress(score: number): Promise<void> {
   if (!isUserLoggedIn()) return;
 
   try {
     const api = getScoringApi();
     const progress = await api.saveGameProgress(fetch, {
       game_type_code: "speed_match",
-      current_level: level,
       total_score: score,
     });
     speedMatchSavedProgress.set(progress);
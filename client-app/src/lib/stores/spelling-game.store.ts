@@ -20,6 +20,7 @@ import {
   type Achievement,
   type GameProgress,
 } from "$lib/api/scoringApi";
+import { GameAnswerLog, resolveDealtFlashcards } from "$lib/helpers/gameSessionHelpers";
 import { ACCESS_TOKEN } from "$lib/common/contants";
 import Cookies from "js-cookie";
 
@@ -88,6 +89,9 @@ let timerInterval: number | null = null;
 // Scoring API instance
 let scoringApi: ScoringApi | null = null;
 
+// Answers for the current session's dealt cards
+let answerLog: GameAnswerLog | null = null;
+
 /**
  * Check if user is logged in
  */
@@ -185,18 +189,21 @@ export async function initializeSpelling(
   if (isUserLoggedIn()) {
     try {
       const api = getScoringApi();
-      const sessionId = await api.startGameSession(fetch, {
+      const { session_id: sessionId, cards } = await api.startGameSession(fetch, {
         game_type_code: "spelling",
         flashcard_type_id: config?.flashcardTypeId,
       });
       spellingSessionId.set(sessionId);
+      answerLog = new GameAnswerLog(api, sessionId, cards);
       console.log("[Spelling] Game session started:", sessionId);
     } catch (error) {
       console.error("[Spelling] Failed to start game session:", error);
       spellingSessionId.set(null);
+      answerLog = null;
     }
   } else {
     spellingSessionId.set(null);
+    answerLog = null;
   }
 
   await startNewRound();
@@ -214,8 +221,14 @@ export async function startNewRound() {
   try {
     spellingState.set("loading");
 
+    // Ask about the cards the server dealt, so every answer can be scored
+    const dealtFlashcards = answerLog
+      ? await resolveDealtFlashcards(currentFlashcards, answerLog.dealtCards)
+      : [];
+    const roundFlashcards = dealtFlashcards.length > 0 ? dealtFlashcards : currentFlashcards;
+
     const questions = await generateSpellingQuestions(
-      currentFlashcards,
+      roundFlashcards,
       numQuestions
     );
 
@@ -265,6 +278,11 @@ export async function submitSpellingAnswer(input: string) {
   // Check if answer is correct
   const isCorrect = normalizeAnswer(input, question.correctAnswer);
 
+  answerLog?.submit({
+    flashcard_id: question.flashcardId,
+    answer: input,
+  });
+
   // Calculate score
   const currentHints = get(hintsRevealed);
   const points = calculateSpellingScore(
@@ -374,7 +392,7 @@ export async function nextSpellingLevel() {
 
   // Save progress and start new session
   if (isUserLoggedIn()) {
-    await saveSpellingProgress(newLevel, newScore);
+    await saveSpellingProgress(newScore);
     await startNewSpellingSession();
   }
 
@@ -388,24 +406,10 @@ async function saveSpellingSession(): Promise<GameCompleteResponse | null> {
   const sessionId = get(spellingSessionId);
   if (!sessionId) return null;
 
-  const stats = get(spellingStats);
-  const startTime = get(spellingSessionStartTime);
-  const currentMaxCombo = get(spellingMaxCombo);
-
-  const timeSpentSeconds = startTime
-    ? Math.floor((Date.now() - startTime) / 1000)
-    : stats.timeElapsed;
-
   try {
     const api = getScoringApi();
-    const result = await api.completeGameSession(fetch, {
-      session_id: sessionId,
-      score: stats.score,
-      correct_answers: stats.correct,
-      wrong_answers: stats.wrong,
-      combo_max: currentMaxCombo,
-      time_spent_seconds: timeSpentSeconds,
-    });
+    await answerLog?.flush();
+    const result = await api.completeGameSession(fetch, { session_id: sessionId });
 
     spellingLastGameResult.set(result);
 
@@ -430,15 +434,17 @@ async function startNewSpellingSession(): Promise<void> {
   try {
     const api = getScoringApi();
     const flashcardTypeId = get(spellingCurrentFlashcardTypeId);
-    const sessionId = await api.startGameSession(fetch, {
+    const { session_id: sessionId, cards } = await api.startGameSession(fetch, {
       game_type_code: "spelling",
       flashcard_type_id: flashcardTypeId,
     });
     spellingSessionId.set(sessionId);
+    answerLog = new GameAnswerLog(api, sessionId, cards);
     console.log("[Spelling] New session started:", sessionId);
   } catch (error) {
     console.warn("[Spelling] Failed to start new session:", error);
     spellingSessionId.set(null);
+    answerLog = null;
   }
 }
 
@@ -456,6 +462,7 @@ export async function endSpellingSession(): Promise<GameCompleteResponse | null>
 
   const result = await saveSpellingSession();
   spellingSessionId.set(null);
+  answerLog = null;
 
   return result;
 }
@@ -517,13 +524,15 @@ export async function resetSpelling() {
   if (isUserLoggedIn()) {
     try {
       const api = getScoringApi();
-      const sessionId = await api.startGameSession(fetch, {
+      const { session_id: sessionId, cards } = await api.startGameSession(fetch, {
         game_type_code: "spelling",
       });
       spellingSessionId.set(sessionId);
+      answerLog = new GameAnswerLog(api, sessionId, cards);
     } catch (error) {
       console.warn("[Spelling] Failed to start new session:", error);
       spellingSessionId.set(null);
+      answerLog = null;
     }
   }
 
@@ -575,17 +584,13 @@ export async function getSpellingProgress(): Promise<GameProgress | null> {
 /**
  * Save game progress
  */
-async function saveSpellingProgress(
-  level: number,
-  score: number
-): Promise<void> {
+async function saveSpellingProgress(score: number): Promise<void> {
   if (!isUserLoggedIn()) return;
 
   try {
     const api = getScoringApi();
     const progress = await api.saveGameProgress(fetch, {
       game_type_code: "spelling",
-      current_level: level,
       total_score: score,
     });
     spellingSavedProgress.set(progress);
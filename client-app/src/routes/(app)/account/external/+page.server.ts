@@ -0,0 +1,56 @@
+import { fail, redirect, type Actions } from "@sveltejs/kit";
+import { APP_URLS, EXTERNAL_LOGIN_FLOW_COOKIE } from "$lib/common/contants";
+import { AuthenticateApi } from "$lib/api/authenticateApi";
+import { UserServerApiOptions } from "$lib/api/apiOptions";
+
+export const load = async ({ parent, cookies, fetch }) => {
+  const parentData = await parent();
+  if (!parentData?.currentUser) {
+    throw redirect(302, APP_URLS.LOGIN_URL);
+  }
+
+  const authenticateApi = new AuthenticateApi(new UserServerApiOptions(cookies));
+  const [providers, externalLogins] = await Promise.all([
+    authenticateApi.getExternalLoginProviders(fetch).catch(() => []),
+    authenticateApi.getExternalLogins(fetch).catch(() => []),
+  ]);
+  return { providers, externalLogins };
+};
+
+export const actions: Actions = {
+  link: async ({ request, cookies, fetch }) => {
+    const authenticateApi = new AuthenticateApi(new UserServerApiOptions(cookies));
+    const data = await request.formData();
+    const provider = data.get("provider") as string;
+
+    const authorization = await authenticateApi
+      .beginExternalLoginLink(fetch, provider)
+      .catch(() => null);
+    if (!authorization) {
+      return fail(400, { message: "Không thể liên kết tài khoản lúc này" });
+    }
+
+    cookies.set(EXTERNAL_LOGIN_FLOW_COOKIE, authorization.flow_token, {
+      path: "/account/external",
+      httpOnly: true,
+      sameSite: "lax",
+      secure: true,
+      maxAge: 10 * 60,
+    });
+    throw redirect(302, authorization.authorization_url);
+  },
+  unlink: async ({ request, cookies, fetch }) => {
+    const authenticateApi = new AuthenticateApi(new UserServerApiOptions(cookies));
+    const data = await request.formData();
+    const provider = data.get("provider") as string;
+
+    const response: Response = await authenticateApi.deleteExternalLogin(fetch, provider);
+    if (!response.ok) {
+      const body = await response.json().catch(() => null);
+      return fail(response.status, {
+        message: (body?.message as string | undefined) ?? "Không thể hủy liên kết",
+      });
+    }
+    return { success: true };
+  },
+};
@@ -0,0 +1,64 @@
+import { redirect } from "@sveltejs/kit";
+import { APP_URLS, EXTERNAL_LOGIN_FLOW_COOKIE } from "$lib/common/contants";
+import { AuthenticateApi, type MfaChallenge } from "$lib/api/authenticateApi";
+import { UserServerApiOptions } from "$lib/api/apiOptions";
+import { signIn } from "$lib/services/signInService";
+
+const EXTERNAL_LOGINS_URL = "/account/external";
+
+async function getErrorMessage(response: Response | undefined) {
+  const body = await response?.json().catch(() => null);
+  return (body?.message as string | undefined) ?? "Đăng nhập thất bại, vui lòng thử lại";
+}
+
+export const load = async ({ parent, params, url, cookies, fetch }) => {
+  const parentData = await parent();
+  const currentUser = parentData?.currentUser;
+
+  // The flow token is good for one callback only
+  const flowToken = cookies.get(EXTERNAL_LOGIN_FLOW_COOKIE);
+  cookies.delete(EXTERNAL_LOGIN_FLOW_COOKIE, { path: "/account/external" });
+
+  const code = url.searchParams.get("code");
+  const state = url.searchParams.get("state");
+  if (!flowToken || !code || !state) {
+    // Also reached when the user cancels at the provider, which sends back an error instead
+    return { message: "Đăng nhập đã bị hủy hoặc đã hết hạn, vui lòng thử lại" };
+  }
+
+  const authenticateApi = new AuthenticateApi(new UserServerApiOptions(cookies));
+  const callback = { code, state, flow_token: flowToken };
+
+  // A signed-in user came back from linking the provider to their account
+  if (currentUser) {
+    const response: Response | undefined = await authenticateApi
+      .completeExternalLoginLink(fetch, params.provider, callback)
+      .catch(() => undefined);
+    if (!response || !response.ok) {
+      return { message: await getErrorMessage(response) };
+    }
+    throw redirect(302, EXTERNAL_LOGINS_URL);
+  }
+
+  const response: Response | undefined = await authenticateApi
+    .completeExternalLogin(fetch, params.provider, callback)
+    .catch(() => undefined);
+  if (!response || response.status !== 200) {
+    return { message: await getErrorMessage(response) };
+  }
+
+  const loginResponse = await response.json();
+  if (loginResponse.mfa_required) {
+    const challenge = loginResponse as MfaChallenge;
+    const enrolment = challenge.enrolment_required
+      ? await authenticateApi.beginMfaEnrolment(fetch, challenge.mfa_token)
+      : null;
+    return { mfa: { mfa_token: challenge.mfa_token, enrolment } };
+  }
+
+  if (!signIn(cookies, response, loginResponse)) {
+    return { message: "Đăng nhập thất bại, vui lòng thử lại" };
+  }
+
+  throw redirect(302, APP_URLS.HOME);
+};
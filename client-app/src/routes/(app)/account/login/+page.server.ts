@@ -1,19 +1,25 @@
-import { redirect, type Actions } from "@sveltejs/kit";
-import { ACCESS_TOKEN, APP_URLS } from "$lib/common/contants";
-import { AuthenticateApi } from "$lib/api/authenticateApi";
+import { randomBytes } from "node:crypto";
+import { fail, redirect, type Actions } from "@sveltejs/kit";
+import {
+  APP_URLS,
+  EXTERNAL_LOGIN_FLOW_COOKIE,
+  MAGIC_LINK_NONCE_COOKIE,
+  MAGIC_LINK_URL,
+} from "$lib/common/contants";
+import { AuthenticateApi, type MfaChallenge } from "$lib/api/authenticateApi";
 import { UserServerApiOptions } from "$lib/api/apiOptions";
-import { container } from "$lib/di";
+import { signIn } from "$lib/services/signInService";
 
-export const load = async ({ parent }) => {
+export const load = async ({ parent, cookies, fetch }) => {
   const parentData = await parent();
   const currentUser = parentData?.currentUser;
 
   if (!currentUser) {
-    return {};
-  }
-
-  if (!currentUser) {
-    return {};
+    const authenticateApi = new AuthenticateApi(new UserServerApiOptions(cookies));
+    const externalLoginProviders = await authenticateApi
+      .getExternalLoginProviders(fetch)
+      .catch(() => []);
+    return { externalLoginProviders };
   }
 
   throw redirect(302, APP_URLS.HOME);
@@ -43,37 +49,101 @@ export const actions: Actions = {
       throw redirect(302, APP_URLS.LOGIN_URL);
     }
 
-    const cookieHeaders = response.headers.getSetCookie();
-    const refresh_token_data = container.cookieHelper.parseSetCookie(
-      cookieHeaders,
-      "refresh_token"
-    );
-    if (!refresh_token_data) {
-      console.error("No Set-Cookie header found in the response.");
+    const loginResponse = await response.json();
+    if (loginResponse.mfa_required) {
+      // Password was right; the second factor is asked on the same page
+      const challenge = loginResponse as MfaChallenge;
+      const enrolment = challenge.enrolment_required
+        ? await authenticateApi.beginMfaEnrolment(fetch, challenge.mfa_token)
+        : null;
+      return { mfa: { mfa_token: challenge.mfa_token, enrolment } };
+    }
+
+    if (!signIn(cookies, response, loginResponse)) {
       return;
     }
 
-    cookies.set(
-      ACCESS_TOKEN.USER_REFRESH_TOKEN,
-      refresh_token_data.value,
-      refresh_token_data.options
-    );
-    const loginResponse = await response.json();
-    const { access_token, expiration } = loginResponse;
-    cookies.set(ACCESS_TOKEN.USER_ACCESS_TOKEN, access_token, {
-      path: "/",
-      httpOnly: false,
-      sameSite: "strict",
+    throw redirect(302, APP_URLS.HOME);
+  },
+  magicLink: async ({ request, cookies, fetch }) => {
+    const authenticateApi = new AuthenticateApi(new UserServerApiOptions(cookies));
+    const data = await request.formData();
+    const email = data.get("email") as string;
+
+    // The link only signs in the browser holding this nonce
+    const nonce = randomBytes(32).toString("base64url");
+    const response: Response | undefined = await authenticateApi
+      .requestMagicLink(fetch, email, nonce)
+      .catch(() => undefined);
+    if (!response || !response.ok) {
+      return fail(response?.status ?? 400, {
+        magicLinkMessage: "Không thể gửi liên kết đăng nhập, vui lòng thử lại sau",
+      });
+    }
+
+    cookies.set(MAGIC_LINK_NONCE_COOKIE, nonce, {
+      path: MAGIC_LINK_URL,
+      httpOnly: true,
+      sameSite: "lax",
       secure: true,
-      expires: new Date(expiration),
+      maxAge: 15 * 60,
     });
-    cookies.set(ACCESS_TOKEN.USER_ACCESS_TOKEN_EXP, expiration, {
-      path: "/",
-      httpOnly: false,
-      sameSite: "strict",
+    return { magicLinkSent: true };
+  },
+  external: async ({ request, cookies, fetch }) => {
+    const authenticateApi = new AuthenticateApi(new UserServerApiOptions(cookies));
+    const data = await request.formData();
+    const provider = data.get("provider") as string;
+
+    const authorization = await authenticateApi
+      .beginExternalLogin(fetch, provider)
+      .catch(() => null);
+    if (!authorization) {
+      return fail(400, { message: "External sign-in is not available" });
+    }
+
+    // The flow token must come back with the callback, but never through a URL
+    cookies.set(EXTERNAL_LOGIN_FLOW_COOKIE, authorization.flow_token, {
+      path: "/account/external",
+      httpOnly: true,
+      sameSite: "lax",
       secure: true,
-      expires: new Date(expiration),
+      maxAge: 10 * 60,
     });
+    throw redirect(302, authorization.authorization_url);
+  },
+  verify: async ({ request, cookies, fetch }) => {
+    const authenticateApi = new AuthenticateApi(new UserServerApiOptions(cookies));
+    const data = await request.formData();
+    const mfa_token = data.get("mfa_token") as string;
+    const code = data.get("code") as string;
+    const secret = data.get("secret") as string | null;
+    const otpauth_uri = data.get("otpauth_uri") as string | null;
+    const enrolment = secret && otpauth_uri ? { secret, otpauth_uri } : null;
+
+    let response: Response | undefined;
+    try {
+      response = await authenticateApi.verifyMfa(fetch, mfa_token, code);
+    } catch (error) {
+      console.error("Error during two-factor verification:", error);
+    }
+
+    if (!response || response.status !== 200) {
+      return fail(400, {
+        message: "Invalid verification code",
+        mfa: { mfa_token, enrolment },
+      });
+    }
+
+    const loginResponse = await response.json();
+    if (!signIn(cookies, response, loginResponse)) {
+      return;
+    }
+
+    if (loginResponse.recovery_codes) {
+      // Shown once, so the page keeps them on screen until the user moves on
+      return { recovery_codes: loginResponse.recovery_codes as string[] };
+    }
 
     throw redirect(302, APP_URLS.HOME);
   },
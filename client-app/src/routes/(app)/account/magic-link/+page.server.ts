@@ -0,0 +1,43 @@
+import { redirect } from "@sveltejs/kit";
+import { APP_URLS, MAGIC_LINK_NONCE_COOKIE, MAGIC_LINK_URL } from "$lib/common/contants";
+import { AuthenticateApi, type MfaChallenge } from "$lib/api/authenticateApi";
+import { UserServerApiOptions } from "$lib/api/apiOptions";
+import { signIn } from "$lib/services/signInService";
+
+const EXPIRED_MESSAGE =
+  "Liên kết đăng nhập không hợp lệ, đã hết hạn hoặc được mở trên một trình duyệt khác";
+
+export const load = async ({ url, cookies, fetch }) => {
+  // The nonce is good for one sign-in only
+  const nonce = cookies.get(MAGIC_LINK_NONCE_COOKIE);
+  cookies.delete(MAGIC_LINK_NONCE_COOKIE, { path: MAGIC_LINK_URL });
+
+  const token = url.searchParams.get("token");
+  if (!nonce || !token) {
+    return { message: EXPIRED_MESSAGE };
+  }
+
+  const authenticateApi = new AuthenticateApi(new UserServerApiOptions(cookies));
+  const response: Response | undefined = await authenticateApi
+    .magicLinkLogin(fetch, token, nonce)
+    .catch(() => undefined);
+  if (!response || response.status !== 200) {
+    const body = await response?.json().catch(() => null);
+    return { message: (body?.message as string | undefined) ?? EXPIRED_MESSAGE };
+  }
+
+  const loginResponse = await response.json();
+  if (loginResponse.mfa_required) {
+    const challenge = loginResponse as MfaChallenge;
+    const enrolment = challenge.enrolment_required
+      ? await authenticateApi.beginMfaEnrolment(fetch, challenge.mfa_token)
+      : null;
+    return { mfa: { mfa_token: challenge.mfa_token, enrolment } };
+  }
+
+  if (!signIn(cookies, response, loginResponse)) {
+    return { message: "Đăng nhập thất bại, vui lòng thử lại" };
+  }
+
+  throw redirect(302, APP_URLS.HOME);
+};
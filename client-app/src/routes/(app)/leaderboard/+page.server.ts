@@ -1,20 +1,34 @@
-import { ScoringApi } from "$lib/api/scoringApi";
+import { ScoringApi, type LeaderboardFilter, type LeaderboardPeriod } from "$lib/api/scoringApi";
 import { UserServerApiOptions } from "$lib/api/apiOptions";
 import type { PageServerLoad } from "./$types";
 
-export const load: PageServerLoad = async ({ fetch, cookies }) => {
+const PERIODS: LeaderboardPeriod[] = ["daily", "weekly", "monthly", "all_time"];
+
+export const load: PageServerLoad = async ({ fetch, cookies, url }) => {
   const scoringApi = new ScoringApi(new UserServerApiOptions(cookies));
+  const requestedPeriod = url.searchParams.get("period") as LeaderboardPeriod | null;
+  const filter: LeaderboardFilter = {
+    period: requestedPeriod && PERIODS.includes(requestedPeriod) ? requestedPeriod : "all_time",
+    gameType: url.searchParams.get("game_type") || undefined,
+  };
 
-  const [leaderboard, myStats] = await Promise.all([
-    scoringApi.getLeaderboard(fetch, 1, 20).catch((error) => {
+  const [leaderboard, myStats, myPosition, myStreak, gameTypes] = await Promise.all([
+    scoringApi.getLeaderboard(fetch, 1, 20, filter).catch((error) => {
       console.error("Failed to fetch leaderboard:", error);
       return [];
     }),
     scoringApi.getMyStats(fetch).catch(() => null),
+    scoringApi.getMyLeaderboardPosition(fetch, filter).catch(() => null),
+    scoringApi.getMyStreak(fetch).catch(() => null),
+    scoringApi.getGameTypes(fetch).catch(() => []),
   ]);
 
   return {
     leaderboard: leaderboard || [],
     myStats,
+    myPosition,
+    myStreak,
+    gameTypes,
+    filter,
   };
 };
@@ -2,7 +2,12 @@ import { get, writable, type Writable } from "svelte/store";
 import { AchievementAdminApi } from "$lib/api/achievementAdminApi";
 import Cookies from "js-cookie";
 import type { Pager } from "../../../../components/molecules/pagination/pager";
-import type { Achievement, AchievementRequest } from "$lib/models/achievement";
+import type {
+  Achievement,
+  AchievementBackfill,
+  AchievementCriteria,
+  AchievementRequest,
+} from "$lib/models/achievement";
 import * as accessService from "$lib/services/accessService";
 import type { CurrentUser } from "$lib/models/current-user";
 import { PermissionCodes } from "$lib/common/permissions";
@@ -25,6 +30,7 @@ export const edittingData: Writable<AchievementRequest> = writable({
   icon: "",
   points: 0,
   category: "",
+  criteria: "",
 });
 
 export const showDeletionModal = writable(false);
@@ -32,6 +38,10 @@ export const deletionError = writable("");
 export const isDeletionSubmitting = writable(false);
 export const deletingData = writable({ id: 0, name: "" });
 
+export const backfill: Writable<AchievementBackfill | null> = writable(null);
+export const backfillError = writable("");
+const backfillPollInterval = 2000;
+
 export const fetchItems = async (page: number) => {
   const response = await achievementService.getList(fetch, page, itemsPerPage);
   items.set(response.items);
@@ -49,7 +59,22 @@ export const submit = async (data: AchievementRequest) => {
   return await create(data);
 };
 
+// Achievements without criteria never unlock, so an empty field is sent as no criteria
+const parseCriteria = (text: string): AchievementCriteria | undefined => {
+  if (!text || text.trim() === "") {
+    return undefined;
+  }
+  return JSON.parse(text) as AchievementCriteria;
+};
+
 export const create = async (data: AchievementRequest) => {
+  let criteria: AchievementCriteria | undefined;
+  try {
+    criteria = parseCriteria(data.criteria);
+  } catch {
+    creationError.set("Criteria must be valid JSON");
+    return;
+  }
   isSubmitting.set(true);
   await achievementService
     .create(fetch, {
@@ -59,6 +84,7 @@ export const create = async (data: AchievementRequest) => {
       icon: data.icon,
       points: data.points,
       category: data.category,
+      criteria,
     })
     .then(async () => {
       await fetchItems(1);
@@ -73,6 +99,13 @@ export const create = async (data: AchievementRequest) => {
 };
 
 export const update = async (id: number, data: AchievementRequest) => {
+  let criteria: AchievementCriteria | undefined;
+  try {
+    criteria = parseCriteria(data.criteria);
+  } catch {
+    creationError.set("Criteria must be valid JSON");
+    return;
+  }
   isSubmitting.set(true);
   await achievementService
     .update(fetch, id, {
@@ -82,6 +115,7 @@ export const update = async (id: number, data: AchievementRequest) => {
       icon: data.icon,
       points: data.points,
       category: data.category,
+      criteria,
     })
     .then(async () => {
       await fetchItems(1);
@@ -123,7 +157,16 @@ export const changePage = (page: number) => {
 
 export const toggleCreationModal = (isShown: boolean = false) => {
   showCreationModal.set(isShown);
-  edittingData.set({ id: 0, code: "", name: "", description: "", icon: "", points: 0, category: "" });
+  edittingData.set({
+    id: 0,
+    code: "",
+    name: "",
+    description: "",
+    icon: "",
+    points: 0,
+    category: "",
+    criteria: "",
+  });
 };
 
 export const openEditingModal = (id: number) => {
@@ -137,6 +180,7 @@ export const openEditingModal = (id: number) => {
         icon: response.icon,
         points: response.points,
         category: response.category,
+        criteria: response.criteria ? JSON.stringify(response.criteria, null, 2) : "",
       });
       showCreationModal.set(true);
     }
@@ -186,6 +230,30 @@ export const toggleActive = async (id: number) => {
   }
 };
 
+// The worker runs the backfill in batches; its progress is polled until it finishes
+export const startBackfill = async (achievementId: number, dryRun: boolean) => {
+  backfillError.set("");
+  try {
+    const id = await achievementService.queueBackfill(fetch, {
+      achievement_id: achievementId,
+      dry_run: dryRun,
+    });
+    await pollBackfill(id);
+  } catch (error) {
+    backfillError.set((error as Error).message);
+  }
+};
+
+const pollBackfill = async (id: number) => {
+  const current = await achievementService.getBackfill(fetch, id);
+  backfill.set(current);
+  if (current.status === "queued" || current.status === "running") {
+    setTimeout(() => {
+      pollBackfill(id).catch((error) => backfillError.set(error.message));
+    }, backfillPollInterval);
+  }
+};
+
 export const canUpdate = (currentUser: CurrentUser | undefined) => {
   return (
     (currentUser && accessService.isRootAdmin(currentUser)) ||
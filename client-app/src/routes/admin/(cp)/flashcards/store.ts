@@ -48,7 +48,7 @@ export const fetchItems = async (page: number) => {
 
   for (const item of response.items) {
     if (item.image_id) {
-      item.image_url = await getImageBase64Url(item.image_id);
+      item.image_url = await getImageBase64Url(item.image_id, item.image_version, "thumb");
     }
   }
   items.set(response.items);
@@ -205,7 +205,7 @@ export const openEditingModal = async (id: number) => {
 
   getById(id).then(async (response: FlashcardDetail) => {
     if (response) {
-      const imageBase64Url = await getImageBase64Url(response.image_id);
+      const imageBase64Url = await getImageBase64Url(response.image_id, response.image_version);
       const data: FlashcardRequest = {
         id: response.id,
         name: response.name,
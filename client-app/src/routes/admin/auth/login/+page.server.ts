@@ -1,5 +1,5 @@
-import { redirect, type Actions } from "@sveltejs/kit";
-import { AuthenticateApi } from "../../../../lib/api/authenticateApi";
+import { fail, redirect, type Actions, type Cookies } from "@sveltejs/kit";
+import { AuthenticateApi, type MfaChallenge } from "../../../../lib/api/authenticateApi";
 import { ACCESS_TOKEN, ADMIN_URLS, ROLE_NAMES } from "$lib/common/contants";
 import { AdminServerApiOptions } from "$lib/api/apiOptions";
 import { container } from "$lib/di";
@@ -19,6 +19,44 @@ export const load = async ({ parent }) => {
   throw redirect(302, ADMIN_URLS.DASHBOARD_URL);
 };
 
+function signIn(
+  cookies: Cookies,
+  response: Response,
+  loginResponse: { access_token: string; expiration: string }
+) {
+  const cookieHeaders = response.headers.getSetCookie();
+  const refresh_token_data = container.cookieHelper.parseSetCookie(
+    cookieHeaders,
+    "refresh_token"
+  );
+  if (!refresh_token_data) {
+    console.error("No Set-Cookie header found in the response.");
+    return false;
+  }
+
+  cookies.set(
+    ACCESS_TOKEN.ADMIN_REFRESH_TOKEN,
+    refresh_token_data.value,
+    refresh_token_data.options
+  );
+  const { access_token, expiration } = loginResponse;
+  cookies.set(ACCESS_TOKEN.ADMIN_ACCESS_TOKEN, access_token, {
+    path: "/",
+    httpOnly: false,
+    sameSite: "strict",
+    secure: true,
+    expires: new Date(expiration),
+  });
+  cookies.set(ACCESS_TOKEN.ADMIN_ACCESS_TOKEN_EXP, expiration, {
+    path: "/",
+    httpOnly: false,
+    sameSite: "strict",
+    secure: true,
+    expires: new Date(expiration),
+  });
+  return true;
+}
+
 export const actions: Actions = {
   login: async ({ request, cookies, fetch }) => {
     const authenticateApi = new AuthenticateApi(new AdminServerApiOptions(cookies));
@@ -43,37 +81,54 @@ export const actions: Actions = {
       throw redirect(302, ADMIN_URLS.LOGIN_URL);
     }
 
-    const cookieHeaders = response.headers.getSetCookie();
-    const refresh_token_data = container.cookieHelper.parseSetCookie(
-      cookieHeaders,
-      "refresh_token"
-    );
-    if (!refresh_token_data) {
-      console.error("No Set-Cookie header found in the response.");
+    const loginResponse = await response.json();
+    if (loginResponse.mfa_required) {
+      // Password was right; the second factor is asked on the same page
+      const challenge = loginResponse as MfaChallenge;
+      const enrolment = challenge.enrolment_required
+        ? await authenticateApi.beginMfaEnrolment(fetch, challenge.mfa_token)
+        : null;
+      return { mfa: { mfa_token: challenge.mfa_token, enrolment } };
+    }
+
+    if (!signIn(cookies, response, loginResponse)) {
       return;
     }
 
-    cookies.set(
-      ACCESS_TOKEN.ADMIN_REFRESH_TOKEN,
-      refresh_token_data.value,
-      refresh_token_data.options
-    );
+    throw redirect(302, ADMIN_URLS.DASHBOARD_URL);
+  },
+  verify: async ({ request, cookies, fetch }) => {
+    const authenticateApi = new AuthenticateApi(new AdminServerApiOptions(cookies));
+    const data = await request.formData();
+    const mfa_token = data.get("mfa_token") as string;
+    const code = data.get("code") as string;
+    const secret = data.get("secret") as string | null;
+    const otpauth_uri = data.get("otpauth_uri") as string | null;
+    const enrolment = secret && otpauth_uri ? { secret, otpauth_uri } : null;
+
+    let response: Response | undefined;
+    try {
+      response = await authenticateApi.verifyMfa(fetch, mfa_token, code);
+    } catch (error) {
+      console.error("Error during two-factor verification:", error);
+    }
+
+    if (!response || response.status !== 200) {
+      return fail(400, {
+        message: "Invalid verification code",
+        mfa: { mfa_token, enrolment },
+      });
+    }
+
     const loginResponse = await response.json();
-    const { access_token, expiration } = loginResponse;
-    cookies.set(ACCESS_TOKEN.ADMIN_ACCESS_TOKEN, access_token, {
-      path: "/",
-      httpOnly: false,
-      sameSite: "strict",
-      secure: true,
-      expires: new Date(expiration),
-    });
-    cookies.set(ACCESS_TOKEN.ADMIN_ACCESS_TOKEN_EXP, expiration, {
-      path: "/",
-      httpOnly: false,
-      sameSite: "strict",
-      secure: true,
-      expires: new Date(expiration),
-    });
+    if (!signIn(cookies, response, loginResponse)) {
+      return;
+    }
+
+    if (loginResponse.recovery_codes) {
+      // Shown once, so the page keeps them on screen until the admin moves on
+      return { recovery_codes: loginResponse.recovery_codes as string[] };
+    }
 
     throw redirect(302, ADMIN_URLS.DASHBOARD_URL);
   },